@@ -0,0 +1,128 @@
+use {
+    anchor_lang::{AccountDeserialize, Result},
+    collectible_vault::state::{
+        AssetAppraisal, AssetImpairment, AssetRedemptionInfo, BorrowerProfile, CollectionCounter,
+        FractionVault, InsuranceReserve, Listing, LoanCounter, LoanInfo, LoanRecord, Offer,
+        PrimaryListing, Rental, VaultConfig, VersionedAccount,
+    },
+};
+
+/**
+ * Deserializes any Collectible Vault account from raw account data, checking its discriminator.
+ */
+pub fn deserialize<T: AccountDeserialize>(data: &[u8]) -> Result<T> {
+    T::try_deserialize(&mut &data[..])
+}
+
+/**
+ * Returns true if raw account data of type T is stored in an older layout and must be upgraded
+ * with `migrate_account` before the program can use it.
+ */
+pub fn needs_migration<T: VersionedAccount>(data: &[u8]) -> Result<bool> {
+    Ok(T::stored_version(data)? != T::VERSION)
+}
+
+/**
+ * Deserializes a LoanInfo account.
+ */
+pub fn loan_info(data: &[u8]) -> Result<LoanInfo> {
+    deserialize(data)
+}
+
+/**
+ * Deserializes an AssetRedemptionInfo account.
+ */
+pub fn asset_redemption_info(data: &[u8]) -> Result<AssetRedemptionInfo> {
+    deserialize(data)
+}
+
+/**
+ * Deserializes a CollectionCounter account.
+ */
+pub fn collection_counter(data: &[u8]) -> Result<CollectionCounter> {
+    deserialize(data)
+}
+
+/**
+ * Deserializes a LoanCounter account.
+ */
+pub fn loan_counter(data: &[u8]) -> Result<LoanCounter> {
+    deserialize(data)
+}
+
+/**
+ * Deserializes a LoanRecord account.
+ */
+pub fn loan_record(data: &[u8]) -> Result<LoanRecord> {
+    deserialize(data)
+}
+
+/**
+ * Deserializes a BorrowerProfile account.
+ */
+pub fn borrower_profile(data: &[u8]) -> Result<BorrowerProfile> {
+    deserialize(data)
+}
+
+/**
+ * Deserializes a VaultConfig account.
+ */
+pub fn vault_config(data: &[u8]) -> Result<VaultConfig> {
+    deserialize(data)
+}
+
+/**
+ * Deserializes a PrimaryListing account.
+ */
+pub fn primary_listing(data: &[u8]) -> Result<PrimaryListing> {
+    deserialize(data)
+}
+
+/**
+ * Deserializes a marketplace Listing account.
+ */
+pub fn listing(data: &[u8]) -> Result<Listing> {
+    deserialize(data)
+}
+
+/**
+ * Deserializes a marketplace Offer account.
+ */
+pub fn offer(data: &[u8]) -> Result<Offer> {
+    deserialize(data)
+}
+
+/**
+ * Deserializes a FractionVault account.
+ */
+pub fn fraction_vault(data: &[u8]) -> Result<FractionVault> {
+    deserialize(data)
+}
+
+/**
+ * Deserializes a Rental account.
+ */
+pub fn rental(data: &[u8]) -> Result<Rental> {
+    deserialize(data)
+}
+
+/**
+ * Deserializes an InsuranceReserve account.
+ */
+pub fn insurance_reserve(data: &[u8]) -> Result<InsuranceReserve> {
+    deserialize(data)
+}
+
+/**
+ * Deserializes an AssetAppraisal account.
+ */
+pub fn asset_appraisal(data: &[u8]) -> Result<AssetAppraisal> {
+    deserialize(data)
+}
+
+/**
+ * Deserializes an AssetImpairment account.
+ */
+pub fn asset_impairment(data: &[u8]) -> Result<AssetImpairment> {
+    deserialize(data)
+}
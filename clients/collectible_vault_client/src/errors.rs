@@ -0,0 +1,103 @@
+use {
+    anchor_lang::{error::ERROR_CODE_OFFSET, solana_program::instruction::InstructionError},
+    collectible_vault::errors::ErrorCode,
+};
+
+/**
+ * Every Collectible Vault error code, in declaration order.
+ * Anchor assigns codes sequentially from ERROR_CODE_OFFSET, so the index in this list is the code offset.
+ */
+pub const ERROR_CODES: &[ErrorCode] = &[
+    ErrorCode::CollectionMintDoesNotMatch,
+    ErrorCode::UnauthorizedTransactionSigner,
+    ErrorCode::LoanAlreadyActive,
+    ErrorCode::InvalidLoanDuration,
+    ErrorCode::InvalidInterestRate,
+    ErrorCode::InvalidNFTAccount,
+    ErrorCode::InsufficientFunds,
+    ErrorCode::InvalidBorrower,
+    ErrorCode::LoanNotActive,
+    ErrorCode::LoanExpired,
+    ErrorCode::InvalidLender,
+    ErrorCode::LoanNotExpired,
+    ErrorCode::CalculationError,
+    ErrorCode::LoanAlreadyFunded,
+    ErrorCode::UnauthorizedLoanCancellation,
+    ErrorCode::RedemptionRequestAlreadyFulfilled,
+    ErrorCode::RedemptionRequestNotActive,
+    ErrorCode::UnauthorizedRedemptionRequest,
+    ErrorCode::AssociatedTokenAccountHasNoTokenBalance,
+    ErrorCode::InvalidLoanRequestExpiry,
+    ErrorCode::LoanRequestExpired,
+    ErrorCode::LoanRequestNotExpired,
+    ErrorCode::BorrowerReputationTooLow,
+    ErrorCode::InvalidLoanNoteAccount,
+    ErrorCode::AccountAlreadyMigrated,
+    ErrorCode::UnsupportedAccountVersion,
+    ErrorCode::UnsupportedAccountType,
+    ErrorCode::MissingProgrammableAccounts,
+    ErrorCode::NonTransferableNft,
+    ErrorCode::UnsupportedCoreAsset,
+    ErrorCode::VaultAccountNotSweepable,
+    ErrorCode::InvalidVaultAccountPayer,
+    ErrorCode::InvalidRoyaltyCreator,
+    ErrorCode::InvalidSalePrice,
+    ErrorCode::TreasuryNotConfigured,
+    ErrorCode::InvalidTreasury,
+    ErrorCode::MissingUsdcPaymentAccounts,
+    ErrorCode::InvalidPaymentAccount,
+    ErrorCode::NftHasOpenLoan,
+    ErrorCode::NftHasRedemptionRequest,
+    ErrorCode::InvalidSeller,
+    ErrorCode::InvalidOfferExpiry,
+    ErrorCode::OfferExpired,
+    ErrorCode::InvalidDownPayment,
+    ErrorCode::InvalidFractionSupply,
+    ErrorCode::InvalidReservePrice,
+    ErrorCode::FractionVaultNotActive,
+    ErrorCode::BuyoutAuctionEnded,
+    ErrorCode::BidTooLow,
+    ErrorCode::InvalidPreviousBidder,
+    ErrorCode::BuyoutNotComplete,
+    ErrorCode::InvalidBuyoutWinner,
+    ErrorCode::InvalidFractionAmount,
+    ErrorCode::InvalidRedemptionThreshold,
+    ErrorCode::RedemptionThresholdNotMet,
+    ErrorCode::InvalidFractionHolder,
+    ErrorCode::InvalidRentalFee,
+    ErrorCode::InvalidRentalDuration,
+    ErrorCode::NftAlreadyRented,
+    ErrorCode::NftNotRented,
+    ErrorCode::RentalNotEnded,
+    ErrorCode::InvalidRentalOwner,
+    ErrorCode::InvalidInsuranceFee,
+    ErrorCode::InvalidAppraisal,
+    ErrorCode::InvalidInsuranceDeposit,
+    ErrorCode::NftImpaired,
+    ErrorCode::InsuranceAlreadyClaimed,
+    ErrorCode::InsufficientInsuranceReserve,
+    ErrorCode::InvalidCoreCollection,
+    ErrorCode::InvalidRenter,
+    ErrorCode::TransferableNft,
+    ErrorCode::RedemptionApprovalRevoked,
+];
+
+/**
+ * Decodes a custom program error code into the program's ErrorCode.
+ * Returns None if the code does not belong to the Collectible Vault program.
+ */
+pub fn decode_error_code(code: u32) -> Option<ErrorCode> {
+    let index = code.checked_sub(ERROR_CODE_OFFSET)?;
+    ERROR_CODES.get(index as usize).copied()
+}
+
+/**
+ * Decodes an instruction error returned by the runtime into the program's ErrorCode.
+ * Returns None for non-custom errors and for custom errors raised by other programs or by Anchor itself.
+ */
+pub fn decode_instruction_error(error: &InstructionError) -> Option<ErrorCode> {
+    match error {
+        InstructionError::Custom(code) => decode_error_code(*code),
+        _ => None,
+    }
+}
@@ -0,0 +1,2203 @@
+use {
+    crate::pda,
+    anchor_lang::{
+        prelude::Pubkey,
+        pubkey,
+        solana_program::{
+            instruction::{AccountMeta, Instruction},
+            system_program, sysvar,
+        },
+        InstructionData, ToAccountMetas,
+    },
+    anchor_spl::{
+        associated_token::{
+            self, get_associated_token_address, get_associated_token_address_with_program_id,
+        },
+        token_2022,
+    },
+    collectible_vault::{
+        accounts,
+        constants::{
+            admin_constants::get_admin_account_pubkey, payment_constants::get_usdc_mint_pubkey,
+        },
+        instruction,
+        instructions::{
+            compressed_nft::{compressed_asset_id, CompressedLeaf},
+            core_asset::MPL_CORE_PROGRAM_ID,
+            nft_transfer::NftStandard,
+        },
+        state::{ReputationGate, SaleCurrency},
+        ID as PROGRAM_ID,
+    },
+    mpl_bubblegum::programs::{SPL_ACCOUNT_COMPRESSION_ID, SPL_NOOP_ID},
+};
+
+/// The Metaplex Token Auth Rules program that evaluates programmable NFT rule sets
+pub const TOKEN_AUTH_RULES_PROGRAM_ID: Pubkey =
+    pubkey!("auth9SigNpDKz4sJJ1DfCTuZrZNSAgh9sFD3rboVmgg");
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+/**
+ * Token Metadata accounts for moving an NFT of the given standard between two token accounts.
+ * Classic NFTs need none of them.
+ */
+pub fn programmable_transfer(
+    standard: NftStandard,
+    nft_mint: &Pubkey,
+    source: &Pubkey,
+    destination: &Pubkey,
+) -> accounts::ProgrammableTransfer {
+    match standard {
+        NftStandard::NonFungible => accounts::ProgrammableTransfer {
+            metadata: None,
+            master_edition: None,
+            source_token_record: None,
+            destination_token_record: None,
+            authorization_rules: None,
+            authorization_rules_program: None,
+            token_metadata_program: None,
+            sysvar_instructions: None,
+        },
+        NftStandard::ProgrammableNonFungible { rule_set } => accounts::ProgrammableTransfer {
+            metadata: Some(pda::metadata(nft_mint).0),
+            master_edition: Some(pda::master_edition(nft_mint).0),
+            source_token_record: Some(pda::token_record(nft_mint, source).0),
+            destination_token_record: Some(pda::token_record(nft_mint, destination).0),
+            authorization_rules: rule_set,
+            authorization_rules_program: rule_set.map(|_| TOKEN_AUTH_RULES_PROGRAM_ID),
+            token_metadata_program: Some(mpl_token_metadata::ID),
+            sysvar_instructions: Some(sysvar::instructions::ID),
+        },
+    }
+}
+
+/**
+ * Token Metadata accounts for burning an NFT of the given standard held in `token_account`.
+ * `collection_mint` is the NFT's verified collection, if it has one.
+ */
+pub fn metadata_burn(
+    standard: NftStandard,
+    nft_mint: &Pubkey,
+    token_account: &Pubkey,
+    collection_mint: Option<&Pubkey>,
+) -> accounts::MetadataBurn {
+    accounts::MetadataBurn {
+        metadata: pda::metadata(nft_mint).0,
+        master_edition: pda::master_edition(nft_mint).0,
+        token_record: matches!(standard, NftStandard::ProgrammableNonFungible { .. })
+            .then(|| pda::token_record(nft_mint, token_account).0),
+        collection_metadata: collection_mint.map(|mint| pda::metadata(mint).0),
+        token_metadata_program: mpl_token_metadata::ID,
+        sysvar_instructions: sysvar::instructions::ID,
+    }
+}
+
+/**
+ * USDC accounts for paying a price of the given currency from `payer` to `recipient`.
+ * SOL prices need none of them.
+ */
+pub fn usdc_payment(
+    currency: SaleCurrency,
+    payer: &Pubkey,
+    recipient: &Pubkey,
+) -> accounts::UsdcPayment {
+    match currency {
+        SaleCurrency::Sol => accounts::UsdcPayment {
+            usdc_mint: None,
+            payer_usdc_account: None,
+            recipient_usdc_account: None,
+            usdc_token_program: None,
+        },
+        SaleCurrency::Usdc => {
+            let usdc_mint = get_usdc_mint_pubkey();
+            accounts::UsdcPayment {
+                usdc_mint: Some(usdc_mint),
+                payer_usdc_account: Some(get_associated_token_address(payer, &usdc_mint)),
+                recipient_usdc_account: Some(get_associated_token_address(recipient, &usdc_mint)),
+                usdc_token_program: Some(anchor_spl::token::ID),
+            }
+        }
+    }
+}
+
+/**
+ * Builds a `mint_nft` instruction.
+ * The admin `payer` and the new `mint` keypair must both sign.
+ * Programmable NFTs also pass the owner's token record and the instructions sysvar.
+ */
+pub fn mint_nft(
+    payer: &Pubkey,
+    mint: &Pubkey,
+    owner: &Pubkey,
+    collection_mint: &Pubkey,
+    product_detail_uri: String,
+    standard: NftStandard,
+) -> Instruction {
+    let collection_metadata = pda::metadata(collection_mint).0;
+    let owner_token_account = get_associated_token_address(owner, mint);
+    let programmable = matches!(standard, NftStandard::ProgrammableNonFungible { .. });
+    build(
+        accounts::MintNFT {
+            mint: *mint,
+            metadata: pda::metadata(mint).0,
+            master_edition: pda::master_edition(mint).0,
+            payer: *payer,
+            system_program: system_program::ID,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: associated_token::ID,
+            rent: sysvar::rent::ID,
+            token_metadata_program: mpl_token_metadata::ID,
+            collection: collection_metadata,
+            collection_mint: *collection_mint,
+            collection_metadata,
+            collection_master_edition: pda::master_edition(collection_mint).0,
+            collection_counter: pda::collection_counter().0,
+            owner: *owner,
+            owner_token_account,
+            token_record: programmable.then(|| pda::token_record(mint, &owner_token_account).0),
+            sysvar_instructions: programmable.then_some(sysvar::instructions::ID),
+        },
+        instruction::MintNft {
+            product_detail_uri,
+            standard,
+        },
+    )
+}
+
+/**
+ * Builds a `create_collection` instruction.
+ * The admin `payer` and the new collection `mint` keypair must both sign.
+ */
+pub fn create_collection(payer: &Pubkey, mint: &Pubkey) -> Instruction {
+    build(
+        accounts::CreateCollection {
+            mint: *mint,
+            metadata: pda::metadata(mint).0,
+            token_account: get_associated_token_address(payer, mint),
+            payer: *payer,
+            system_program: system_program::ID,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: associated_token::ID,
+            rent: sysvar::rent::ID,
+            token_metadata_program: mpl_token_metadata::ID,
+            master_edition: pda::master_edition(mint).0,
+            collection_counter: pda::collection_counter().0,
+        },
+        instruction::CreateCollection {},
+    )
+}
+
+/**
+ * Builds a `migrate_account` instruction that upgrades any versioned state account.
+ * The admin `payer` must sign and covers the additional rent.
+ */
+pub fn migrate_account(payer: &Pubkey, account: &Pubkey) -> Instruction {
+    build(
+        accounts::MigrateAccount {
+            account: *account,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        instruction::MigrateAccount {},
+    )
+}
+
+/**
+ * Builds a `set_default_royalties` instruction signed by the admin `payer`.
+ */
+pub fn set_default_royalties(payer: &Pubkey, enabled: bool) -> Instruction {
+    build(
+        accounts::SetDefaultRoyalties {
+            vault_config: pda::vault_config().0,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        instruction::SetDefaultRoyalties { enabled },
+    )
+}
+
+/**
+ * Builds a `set_treasury` instruction signed by the admin `payer`.
+ */
+pub fn set_treasury(payer: &Pubkey, treasury: &Pubkey) -> Instruction {
+    build(
+        accounts::SetTreasury {
+            vault_config: pda::vault_config().0,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        instruction::SetTreasury {
+            treasury: *treasury,
+        },
+    )
+}
+
+/**
+ * Builds a `set_insurance_fee` instruction that the admin signs as `payer`.
+ */
+pub fn set_insurance_fee(payer: &Pubkey, insurance_fee_bps: u16) -> Instruction {
+    build(
+        accounts::SetInsuranceFee {
+            vault_config: pda::vault_config().0,
+            insurance_reserve: pda::insurance_reserve().0,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        instruction::SetInsuranceFee { insurance_fee_bps },
+    )
+}
+
+/**
+ * Appends the NFT's metadata creators, in metadata order, to an instruction that pays its royalty:
+ * default claims when default royalties are enabled, and marketplace sales. Append them before any
+ * transfer hook accounts.
+ */
+pub fn with_royalty_creators(mut instruction: Instruction, creators: &[Pubkey]) -> Instruction {
+    instruction.accounts.extend(
+        creators
+            .iter()
+            .map(|creator| AccountMeta::new(*creator, false)),
+    );
+    instruction
+}
+
+/**
+ * Builds a `create_asset_redemption_request` instruction signed by the NFT owner.
+ * `token_program` is the SPL Token or Token-2022 program that owns the NFT mint.
+ */
+pub fn create_asset_redemption_request(
+    owner: &Pubkey,
+    nft_mint: &Pubkey,
+    standard: NftStandard,
+    token_program: &Pubkey,
+) -> Instruction {
+    let asset_redemption_vault = pda::asset_redemption_vault().0;
+    build(
+        accounts::CreateAssetRedemptionRequest {
+            asset_redemption_info: pda::asset_redemption_info(nft_mint).0,
+            nft_mint: *nft_mint,
+            owner_nft_account: get_associated_token_address_with_program_id(
+                owner,
+                nft_mint,
+                token_program,
+            ),
+            asset_redemption_nft_account: get_associated_token_address_with_program_id(
+                &asset_redemption_vault,
+                nft_mint,
+                token_program,
+            ),
+            asset_redemption_vault,
+            owner: *owner,
+            asset_impairment: pda::asset_impairment(nft_mint).0,
+            system_program: system_program::ID,
+            token_program: *token_program,
+            associated_token_program: associated_token::ID,
+            rent: sysvar::rent::ID,
+            programmable: programmable_transfer(
+                standard,
+                nft_mint,
+                &get_associated_token_address_with_program_id(owner, nft_mint, token_program),
+                &get_associated_token_address_with_program_id(
+                    &asset_redemption_vault,
+                    nft_mint,
+                    token_program,
+                ),
+            ),
+        },
+        instruction::CreateAssetRedemptionRequest {},
+    )
+}
+
+/**
+ * Builds a `cancel_asset_redemption_request` instruction signed by the NFT owner.
+ * `token_program` is the SPL Token or Token-2022 program that owns the NFT mint.
+ */
+pub fn cancel_asset_redemption_request(
+    owner: &Pubkey,
+    nft_mint: &Pubkey,
+    standard: NftStandard,
+    token_program: &Pubkey,
+) -> Instruction {
+    let asset_redemption_vault = pda::asset_redemption_vault().0;
+    build(
+        accounts::CancelAssetRedemptionRequest {
+            asset_redemption_info: pda::asset_redemption_info(nft_mint).0,
+            nft_mint: *nft_mint,
+            owner_nft_account: get_associated_token_address_with_program_id(
+                owner,
+                nft_mint,
+                token_program,
+            ),
+            asset_redemption_nft_account: get_associated_token_address_with_program_id(
+                &asset_redemption_vault,
+                nft_mint,
+                token_program,
+            ),
+            asset_redemption_vault,
+            owner: *owner,
+            system_program: system_program::ID,
+            token_program: *token_program,
+            associated_token_program: associated_token::ID,
+            programmable: programmable_transfer(
+                standard,
+                nft_mint,
+                &get_associated_token_address_with_program_id(
+                    &asset_redemption_vault,
+                    nft_mint,
+                    token_program,
+                ),
+                &get_associated_token_address_with_program_id(owner, nft_mint, token_program),
+            ),
+        },
+        instruction::CancelAssetRedemptionRequest {},
+    )
+}
+
+/**
+ * Builds a `fulfill_asset_redemption_request` instruction signed by the admin.
+ */
+pub fn fulfill_asset_redemption_request(admin: &Pubkey, nft_mint: &Pubkey) -> Instruction {
+    build(
+        accounts::FulfillAssetRedemptionRequest {
+            asset_redemption_info: pda::asset_redemption_info(nft_mint).0,
+            nft_mint: *nft_mint,
+            admin: *admin,
+            system_program: system_program::ID,
+        },
+        instruction::FulfillAssetRedemptionRequest {},
+    )
+}
+
+/**
+ * Builds a `stake_nft_for_loan` instruction signed by the NFT owner.
+ * `token_program` is the SPL Token or Token-2022 program that owns the NFT mint.
+ */
+#[allow(clippy::too_many_arguments)]
+pub fn stake_nft_for_loan(
+    owner: &Pubkey,
+    nft_mint: &Pubkey,
+    loan_amount: u64,
+    interest_rate: u64,
+    duration: i64,
+    request_expiry: Option<i64>,
+    standard: NftStandard,
+    token_program: &Pubkey,
+) -> Instruction {
+    let vault_authority = pda::vault_authority().0;
+    build(
+        accounts::StakeNftForLoan {
+            loan_info: pda::loan_info(nft_mint).0,
+            loan_counter: pda::loan_counter(nft_mint).0,
+            nft_mint: *nft_mint,
+            owner_nft_account: get_associated_token_address_with_program_id(
+                owner,
+                nft_mint,
+                token_program,
+            ),
+            vault_nft_account: get_associated_token_address_with_program_id(
+                &vault_authority,
+                nft_mint,
+                token_program,
+            ),
+            vault_authority,
+            owner: *owner,
+            asset_impairment: pda::asset_impairment(nft_mint).0,
+            system_program: system_program::ID,
+            token_program: *token_program,
+            associated_token_program: associated_token::ID,
+            rent: sysvar::rent::ID,
+            programmable: programmable_transfer(
+                standard,
+                nft_mint,
+                &get_associated_token_address_with_program_id(owner, nft_mint, token_program),
+                &get_associated_token_address_with_program_id(
+                    &vault_authority,
+                    nft_mint,
+                    token_program,
+                ),
+            ),
+        },
+        instruction::StakeNftForLoan {
+            loan_amount,
+            interest_rate,
+            duration,
+            request_expiry,
+        },
+    )
+}
+
+/**
+ * Builds a `provide_loan_liquidity` instruction signed by the lender.
+ * `loan_id` is the sequence number stored in the loan's LoanInfo account.
+ * The borrower's profile is passed only when a reputation gate is set.
+ */
+pub fn provide_loan_liquidity(
+    lender: &Pubkey,
+    borrower: &Pubkey,
+    nft_mint: &Pubkey,
+    loan_id: u64,
+    reputation_gate: Option<ReputationGate>,
+) -> Instruction {
+    let loan_note_mint = pda::loan_note_mint(nft_mint, loan_id).0;
+    build(
+        accounts::ProvideLoanLiquidity {
+            loan_info: pda::loan_info(nft_mint).0,
+            lender: *lender,
+            borrower: *borrower,
+            borrower_profile: reputation_gate.map(|_| pda::borrower_profile(borrower).0),
+            loan_note_mint,
+            lender_note_account: get_associated_token_address(lender, &loan_note_mint),
+            vault_authority: pda::vault_authority().0,
+            asset_impairment: pda::asset_impairment(nft_mint).0,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        instruction::ProvideLoanLiquidity { reputation_gate },
+    )
+}
+
+/**
+ * Builds a `repay_loan` instruction signed by `payer`, who may be the borrower or a third party.
+ * `note_holder` is the current owner of the loan note, who receives the repayment.
+ * `token_program` is the SPL Token or Token-2022 program that owns the NFT mint.
+ */
+pub fn repay_loan(
+    payer: &Pubkey,
+    borrower: &Pubkey,
+    note_holder: &Pubkey,
+    nft_mint: &Pubkey,
+    loan_id: u64,
+    standard: NftStandard,
+    token_program: &Pubkey,
+) -> Instruction {
+    let vault_authority = pda::vault_authority().0;
+    let loan_note_mint = pda::loan_note_mint(nft_mint, loan_id).0;
+    build(
+        accounts::RepayLoan {
+            loan_info: pda::loan_info(nft_mint).0,
+            nft_mint: *nft_mint,
+            vault_nft_account: get_associated_token_address_with_program_id(
+                &vault_authority,
+                nft_mint,
+                token_program,
+            ),
+            borrower_nft_account: get_associated_token_address_with_program_id(
+                borrower,
+                nft_mint,
+                token_program,
+            ),
+            vault_authority,
+            borrower: *borrower,
+            payer: *payer,
+            loan_note_mint,
+            lender_note_account: get_associated_token_address(note_holder, &loan_note_mint),
+            lender: *note_holder,
+            loan_record: pda::loan_record(nft_mint, loan_id).0,
+            borrower_profile: pda::borrower_profile(borrower).0,
+            token_program: *token_program,
+            associated_token_program: associated_token::ID,
+            note_token_program: anchor_spl::token::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+            programmable: programmable_transfer(
+                standard,
+                nft_mint,
+                &get_associated_token_address_with_program_id(
+                    &vault_authority,
+                    nft_mint,
+                    token_program,
+                ),
+                &get_associated_token_address_with_program_id(borrower, nft_mint, token_program),
+            ),
+        },
+        instruction::RepayLoan {},
+    )
+}
+
+/**
+ * Builds a `claim_delinquent_nft` instruction signed by the current loan note holder.
+ * `token_program` is the SPL Token or Token-2022 program that owns the NFT mint.
+ */
+pub fn claim_delinquent_nft(
+    note_holder: &Pubkey,
+    borrower: &Pubkey,
+    nft_mint: &Pubkey,
+    loan_id: u64,
+    standard: NftStandard,
+    token_program: &Pubkey,
+) -> Instruction {
+    let vault_authority = pda::vault_authority().0;
+    let loan_note_mint = pda::loan_note_mint(nft_mint, loan_id).0;
+    build(
+        accounts::ClaimDelinquentNft {
+            loan_info: pda::loan_info(nft_mint).0,
+            nft_mint: *nft_mint,
+            borrower: *borrower,
+            vault_nft_account: get_associated_token_address_with_program_id(
+                &vault_authority,
+                nft_mint,
+                token_program,
+            ),
+            lender_nft_account: get_associated_token_address_with_program_id(
+                note_holder,
+                nft_mint,
+                token_program,
+            ),
+            vault_authority,
+            loan_note_mint,
+            lender_note_account: get_associated_token_address(note_holder, &loan_note_mint),
+            lender: *note_holder,
+            loan_record: pda::loan_record(nft_mint, loan_id).0,
+            borrower_profile: pda::borrower_profile(borrower).0,
+            vault_config: pda::vault_config().0,
+            nft_metadata: pda::metadata(nft_mint).0,
+            token_program: *token_program,
+            associated_token_program: associated_token::ID,
+            note_token_program: anchor_spl::token::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+            programmable: programmable_transfer(
+                standard,
+                nft_mint,
+                &get_associated_token_address_with_program_id(
+                    &vault_authority,
+                    nft_mint,
+                    token_program,
+                ),
+                &get_associated_token_address_with_program_id(note_holder, nft_mint, token_program),
+            ),
+        },
+        instruction::ClaimDelinquentNft {},
+    )
+}
+
+/**
+ * Builds a `cancel_loan_request` instruction signed by the NFT owner.
+ * `token_program` is the SPL Token or Token-2022 program that owns the NFT mint.
+ */
+pub fn cancel_loan_request(
+    owner: &Pubkey,
+    nft_mint: &Pubkey,
+    loan_id: u64,
+    standard: NftStandard,
+    token_program: &Pubkey,
+) -> Instruction {
+    let vault_authority = pda::vault_authority().0;
+    build(
+        accounts::CancelLoanRequest {
+            owner: *owner,
+            loan_info: pda::loan_info(nft_mint).0,
+            nft_mint: *nft_mint,
+            owner_nft_account: get_associated_token_address_with_program_id(
+                owner,
+                nft_mint,
+                token_program,
+            ),
+            vault_nft_account: get_associated_token_address_with_program_id(
+                &vault_authority,
+                nft_mint,
+                token_program,
+            ),
+            vault_authority,
+            loan_record: pda::loan_record(nft_mint, loan_id).0,
+            borrower_profile: pda::borrower_profile(owner).0,
+            token_program: *token_program,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            programmable: programmable_transfer(
+                standard,
+                nft_mint,
+                &get_associated_token_address_with_program_id(
+                    &vault_authority,
+                    nft_mint,
+                    token_program,
+                ),
+                &get_associated_token_address_with_program_id(owner, nft_mint, token_program),
+            ),
+        },
+        instruction::CancelLoanRequest {},
+    )
+}
+
+/**
+ * Builds a `close_expired_loan_request` instruction signed by any cranker.
+ * `token_program` is the SPL Token or Token-2022 program that owns the NFT mint.
+ */
+pub fn close_expired_loan_request(
+    cranker: &Pubkey,
+    nft_owner: &Pubkey,
+    nft_mint: &Pubkey,
+    loan_id: u64,
+    standard: NftStandard,
+    token_program: &Pubkey,
+) -> Instruction {
+    let vault_authority = pda::vault_authority().0;
+    build(
+        accounts::CloseExpiredLoanRequest {
+            cranker: *cranker,
+            loan_info: pda::loan_info(nft_mint).0,
+            nft_owner: *nft_owner,
+            nft_mint: *nft_mint,
+            owner_nft_account: get_associated_token_address_with_program_id(
+                nft_owner,
+                nft_mint,
+                token_program,
+            ),
+            vault_nft_account: get_associated_token_address_with_program_id(
+                &vault_authority,
+                nft_mint,
+                token_program,
+            ),
+            vault_authority,
+            loan_record: pda::loan_record(nft_mint, loan_id).0,
+            borrower_profile: pda::borrower_profile(nft_owner).0,
+            token_program: *token_program,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            programmable: programmable_transfer(
+                standard,
+                nft_mint,
+                &get_associated_token_address_with_program_id(
+                    &vault_authority,
+                    nft_mint,
+                    token_program,
+                ),
+                &get_associated_token_address_with_program_id(nft_owner, nft_mint, token_program),
+            ),
+        },
+        instruction::CloseExpiredLoanRequest {},
+    )
+}
+
+/**
+ * Builds a `lock_nft_for_loan` instruction signed by the NFT owner.
+ * The NFT must be a classic SPL Token NFT with a master edition.
+ */
+pub fn lock_nft_for_loan(
+    owner: &Pubkey,
+    nft_mint: &Pubkey,
+    loan_amount: u64,
+    interest_rate: u64,
+    duration: i64,
+    request_expiry: Option<i64>,
+) -> Instruction {
+    build(
+        accounts::LockNftForLoan {
+            loan_info: pda::loan_info(nft_mint).0,
+            loan_counter: pda::loan_counter(nft_mint).0,
+            nft_mint: *nft_mint,
+            owner_nft_account: get_associated_token_address(owner, nft_mint),
+            nft_edition: pda::master_edition(nft_mint).0,
+            vault_authority: pda::vault_authority().0,
+            owner: *owner,
+            asset_impairment: pda::asset_impairment(nft_mint).0,
+            system_program: system_program::ID,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: associated_token::ID,
+            token_metadata_program: mpl_token_metadata::ID,
+        },
+        instruction::LockNftForLoan {
+            loan_amount,
+            interest_rate,
+            duration,
+            request_expiry,
+        },
+    )
+}
+
+/**
+ * Builds a `repay_locked_loan` instruction signed by `payer`, who may be the borrower or a third party.
+ * `note_holder` is the current owner of the loan note, who receives the repayment.
+ */
+pub fn repay_locked_loan(
+    payer: &Pubkey,
+    borrower: &Pubkey,
+    note_holder: &Pubkey,
+    nft_mint: &Pubkey,
+    loan_id: u64,
+) -> Instruction {
+    let loan_note_mint = pda::loan_note_mint(nft_mint, loan_id).0;
+    build(
+        accounts::RepayLockedLoan {
+            loan_info: pda::loan_info(nft_mint).0,
+            nft_mint: *nft_mint,
+            borrower_nft_account: get_associated_token_address(borrower, nft_mint),
+            nft_edition: pda::master_edition(nft_mint).0,
+            vault_authority: pda::vault_authority().0,
+            borrower: *borrower,
+            payer: *payer,
+            loan_note_mint,
+            lender_note_account: get_associated_token_address(note_holder, &loan_note_mint),
+            lender: *note_holder,
+            loan_record: pda::loan_record(nft_mint, loan_id).0,
+            borrower_profile: pda::borrower_profile(borrower).0,
+            token_program: anchor_spl::token::ID,
+            system_program: system_program::ID,
+            token_metadata_program: mpl_token_metadata::ID,
+        },
+        instruction::RepayLockedLoan {},
+    )
+}
+
+/**
+ * Builds a `claim_delinquent_locked_nft` instruction signed by the current loan note holder.
+ */
+pub fn claim_delinquent_locked_nft(
+    note_holder: &Pubkey,
+    borrower: &Pubkey,
+    nft_mint: &Pubkey,
+    loan_id: u64,
+) -> Instruction {
+    let loan_note_mint = pda::loan_note_mint(nft_mint, loan_id).0;
+    build(
+        accounts::ClaimDelinquentLockedNft {
+            vault_config: pda::vault_config().0,
+            nft_metadata: pda::metadata(nft_mint).0,
+            loan_info: pda::loan_info(nft_mint).0,
+            nft_mint: *nft_mint,
+            borrower: *borrower,
+            borrower_nft_account: get_associated_token_address(borrower, nft_mint),
+            lender_nft_account: get_associated_token_address(note_holder, nft_mint),
+            nft_edition: pda::master_edition(nft_mint).0,
+            vault_authority: pda::vault_authority().0,
+            loan_note_mint,
+            lender_note_account: get_associated_token_address(note_holder, &loan_note_mint),
+            lender: *note_holder,
+            loan_record: pda::loan_record(nft_mint, loan_id).0,
+            borrower_profile: pda::borrower_profile(borrower).0,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            token_metadata_program: mpl_token_metadata::ID,
+        },
+        instruction::ClaimDelinquentLockedNft {},
+    )
+}
+
+/**
+ * Builds a `cancel_locked_loan_request` instruction signed by the NFT owner.
+ */
+pub fn cancel_locked_loan_request(owner: &Pubkey, nft_mint: &Pubkey, loan_id: u64) -> Instruction {
+    build(
+        accounts::CancelLockedLoanRequest {
+            owner: *owner,
+            loan_info: pda::loan_info(nft_mint).0,
+            nft_mint: *nft_mint,
+            owner_nft_account: get_associated_token_address(owner, nft_mint),
+            nft_edition: pda::master_edition(nft_mint).0,
+            vault_authority: pda::vault_authority().0,
+            loan_record: pda::loan_record(nft_mint, loan_id).0,
+            borrower_profile: pda::borrower_profile(owner).0,
+            token_program: anchor_spl::token::ID,
+            system_program: system_program::ID,
+            token_metadata_program: mpl_token_metadata::ID,
+        },
+        instruction::CancelLockedLoanRequest {},
+    )
+}
+
+/**
+ * Builds a `close_expired_locked_loan_request` crank instruction signed by any `cranker`.
+ */
+pub fn close_expired_locked_loan_request(
+    cranker: &Pubkey,
+    nft_owner: &Pubkey,
+    nft_mint: &Pubkey,
+    loan_id: u64,
+) -> Instruction {
+    build(
+        accounts::CloseExpiredLockedLoanRequest {
+            cranker: *cranker,
+            loan_info: pda::loan_info(nft_mint).0,
+            nft_owner: *nft_owner,
+            nft_mint: *nft_mint,
+            owner_nft_account: get_associated_token_address(nft_owner, nft_mint),
+            nft_edition: pda::master_edition(nft_mint).0,
+            vault_authority: pda::vault_authority().0,
+            loan_record: pda::loan_record(nft_mint, loan_id).0,
+            borrower_profile: pda::borrower_profile(nft_owner).0,
+            token_program: anchor_spl::token::ID,
+            system_program: system_program::ID,
+            token_metadata_program: mpl_token_metadata::ID,
+        },
+        instruction::CloseExpiredLockedLoanRequest {},
+    )
+}
+
+/**
+ * Builds a `sweep_baseline_vault_account` crank instruction signed by any `cranker`, for an NFT with
+ * no LoanCounter. The rent is refunded to the admin.
+ * `token_program` is the SPL Token or Token-2022 program that owns the NFT mint.
+ */
+pub fn sweep_baseline_vault_account(
+    cranker: &Pubkey,
+    nft_mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    let vault_authority = pda::vault_authority().0;
+    build(
+        accounts::SweepBaselineVaultAccount {
+            cranker: *cranker,
+            nft_mint: *nft_mint,
+            vault_nft_account: get_associated_token_address_with_program_id(
+                &vault_authority,
+                nft_mint,
+                token_program,
+            ),
+            vault_authority,
+            loan_counter: pda::loan_counter(nft_mint).0,
+            admin: get_admin_account_pubkey(),
+            token_program: *token_program,
+        },
+        instruction::SweepBaselineVaultAccount {},
+    )
+}
+
+/**
+ * Builds a `mint_core_asset` instruction.
+ * The admin `payer` and the new `asset` keypair must both sign.
+ */
+pub fn mint_core_asset(
+    payer: &Pubkey,
+    asset: &Pubkey,
+    owner: &Pubkey,
+    product_detail_uri: String,
+) -> Instruction {
+    build(
+        accounts::MintCoreAsset {
+            asset: *asset,
+            payer: *payer,
+            collection_counter: pda::collection_counter().0,
+            owner: *owner,
+            core_program: MPL_CORE_PROGRAM_ID,
+            system_program: system_program::ID,
+        },
+        instruction::MintCoreAsset { product_detail_uri },
+    )
+}
+
+/**
+ * Builds a `stake_core_asset_for_loan` instruction signed by the asset owner.
+ * `collection` is the collection the asset belongs to, if it has one.
+ */
+pub fn stake_core_asset_for_loan(
+    owner: &Pubkey,
+    asset: &Pubkey,
+    collection: Option<&Pubkey>,
+    loan_amount: u64,
+    interest_rate: u64,
+    duration: i64,
+    request_expiry: Option<i64>,
+) -> Instruction {
+    build(
+        accounts::StakeCoreAssetForLoan {
+            loan_info: pda::loan_info(asset).0,
+            loan_counter: pda::loan_counter(asset).0,
+            asset: *asset,
+            collection: collection.copied(),
+            vault_authority: pda::vault_authority().0,
+            asset_impairment: pda::asset_impairment(asset).0,
+            owner: *owner,
+            core_program: MPL_CORE_PROGRAM_ID,
+            system_program: system_program::ID,
+        },
+        instruction::StakeCoreAssetForLoan {
+            loan_amount,
+            interest_rate,
+            duration,
+            request_expiry,
+        },
+    )
+}
+
+/**
+ * Builds a `repay_core_loan` instruction signed by `payer`, who may be the borrower or a third party.
+ * `note_holder` is the current owner of the loan note, who receives the repayment.
+ * `collection` is the collection the asset belongs to, if it has one.
+ */
+pub fn repay_core_loan(
+    payer: &Pubkey,
+    borrower: &Pubkey,
+    note_holder: &Pubkey,
+    asset: &Pubkey,
+    collection: Option<&Pubkey>,
+    loan_id: u64,
+) -> Instruction {
+    let loan_note_mint = pda::loan_note_mint(asset, loan_id).0;
+    build(
+        accounts::RepayCoreLoan {
+            loan_info: pda::loan_info(asset).0,
+            asset: *asset,
+            collection: collection.copied(),
+            vault_authority: pda::vault_authority().0,
+            borrower: *borrower,
+            payer: *payer,
+            loan_note_mint,
+            lender_note_account: get_associated_token_address(note_holder, &loan_note_mint),
+            lender: *note_holder,
+            loan_record: pda::loan_record(asset, loan_id).0,
+            borrower_profile: pda::borrower_profile(borrower).0,
+            token_program: anchor_spl::token::ID,
+            core_program: MPL_CORE_PROGRAM_ID,
+            system_program: system_program::ID,
+        },
+        instruction::RepayCoreLoan {},
+    )
+}
+
+/**
+ * Builds a `claim_delinquent_core_asset` instruction signed by the current loan note holder.
+ * `collection` is the collection the asset belongs to, if it has one.
+ */
+pub fn claim_delinquent_core_asset(
+    lender: &Pubkey,
+    borrower: &Pubkey,
+    asset: &Pubkey,
+    collection: Option<&Pubkey>,
+    loan_id: u64,
+) -> Instruction {
+    let loan_note_mint = pda::loan_note_mint(asset, loan_id).0;
+    build(
+        accounts::ClaimDelinquentCoreAsset {
+            loan_info: pda::loan_info(asset).0,
+            asset: *asset,
+            collection: collection.copied(),
+            vault_authority: pda::vault_authority().0,
+            loan_note_mint,
+            lender_note_account: get_associated_token_address(lender, &loan_note_mint),
+            lender: *lender,
+            loan_record: pda::loan_record(asset, loan_id).0,
+            borrower_profile: pda::borrower_profile(borrower).0,
+            token_program: anchor_spl::token::ID,
+            core_program: MPL_CORE_PROGRAM_ID,
+            system_program: system_program::ID,
+        },
+        instruction::ClaimDelinquentCoreAsset {},
+    )
+}
+
+/**
+ * Builds a `cancel_core_loan_request` instruction signed by the asset owner.
+ * `collection` is the collection the asset belongs to, if it has one.
+ */
+pub fn cancel_core_loan_request(
+    owner: &Pubkey,
+    asset: &Pubkey,
+    collection: Option<&Pubkey>,
+    loan_id: u64,
+) -> Instruction {
+    build(
+        accounts::CancelCoreLoanRequest {
+            owner: *owner,
+            loan_info: pda::loan_info(asset).0,
+            asset: *asset,
+            collection: collection.copied(),
+            vault_authority: pda::vault_authority().0,
+            loan_record: pda::loan_record(asset, loan_id).0,
+            borrower_profile: pda::borrower_profile(owner).0,
+            core_program: MPL_CORE_PROGRAM_ID,
+            system_program: system_program::ID,
+        },
+        instruction::CancelCoreLoanRequest {},
+    )
+}
+
+/**
+ * Builds a `close_expired_core_loan_request` crank instruction signed by any `cranker`.
+ * `collection` is the collection the asset belongs to, if it has one.
+ */
+pub fn close_expired_core_loan_request(
+    cranker: &Pubkey,
+    nft_owner: &Pubkey,
+    asset: &Pubkey,
+    collection: Option<&Pubkey>,
+    loan_id: u64,
+) -> Instruction {
+    build(
+        accounts::CloseExpiredCoreLoanRequest {
+            cranker: *cranker,
+            loan_info: pda::loan_info(asset).0,
+            nft_owner: *nft_owner,
+            asset: *asset,
+            collection: collection.copied(),
+            vault_authority: pda::vault_authority().0,
+            loan_record: pda::loan_record(asset, loan_id).0,
+            borrower_profile: pda::borrower_profile(nft_owner).0,
+            core_program: MPL_CORE_PROGRAM_ID,
+            system_program: system_program::ID,
+        },
+        instruction::CloseExpiredCoreLoanRequest {},
+    )
+}
+
+/**
+ * Builds a `create_core_asset_redemption_request` instruction signed by the asset owner.
+ * `collection` is the collection the asset belongs to, if it has one.
+ */
+pub fn create_core_asset_redemption_request(
+    owner: &Pubkey,
+    asset: &Pubkey,
+    collection: Option<&Pubkey>,
+) -> Instruction {
+    build(
+        accounts::CreateCoreAssetRedemptionRequest {
+            asset_redemption_info: pda::asset_redemption_info(asset).0,
+            asset: *asset,
+            collection: collection.copied(),
+            asset_redemption_vault: pda::asset_redemption_vault().0,
+            owner: *owner,
+            core_program: MPL_CORE_PROGRAM_ID,
+            system_program: system_program::ID,
+        },
+        instruction::CreateCoreAssetRedemptionRequest {},
+    )
+}
+
+/**
+ * Builds a `cancel_core_asset_redemption_request` instruction signed by the asset owner.
+ * `collection` is the collection the asset belongs to, if it has one.
+ */
+pub fn cancel_core_asset_redemption_request(
+    owner: &Pubkey,
+    asset: &Pubkey,
+    collection: Option<&Pubkey>,
+) -> Instruction {
+    build(
+        accounts::CancelCoreAssetRedemptionRequest {
+            asset_redemption_info: pda::asset_redemption_info(asset).0,
+            asset: *asset,
+            collection: collection.copied(),
+            asset_redemption_vault: pda::asset_redemption_vault().0,
+            owner: *owner,
+            core_program: MPL_CORE_PROGRAM_ID,
+            system_program: system_program::ID,
+        },
+        instruction::CancelCoreAssetRedemptionRequest {},
+    )
+}
+
+/**
+ * Builds a `fulfill_core_asset_redemption_request` instruction signed by the admin.
+ * `collection` is the collection the asset belongs to, if it has one.
+ */
+pub fn fulfill_core_asset_redemption_request(
+    admin: &Pubkey,
+    asset: &Pubkey,
+    collection: Option<&Pubkey>,
+) -> Instruction {
+    build(
+        accounts::FulfillCoreAssetRedemptionRequest {
+            asset_redemption_info: pda::asset_redemption_info(asset).0,
+            asset: *asset,
+            collection: collection.copied(),
+            asset_redemption_vault: pda::asset_redemption_vault().0,
+            admin: *admin,
+            core_program: MPL_CORE_PROGRAM_ID,
+            system_program: system_program::ID,
+        },
+        instruction::FulfillCoreAssetRedemptionRequest {},
+    )
+}
+
+/**
+ * Builds a `mint_compressed_nft` instruction signed by the admin `payer`.
+ * The admin must be the creator or delegate of `merkle_tree`.
+ */
+pub fn mint_compressed_nft(
+    payer: &Pubkey,
+    merkle_tree: &Pubkey,
+    owner: &Pubkey,
+    collection_mint: &Pubkey,
+    product_detail_uri: String,
+) -> Instruction {
+    build(
+        accounts::MintCompressedNft {
+            payer: *payer,
+            collection_counter: pda::collection_counter().0,
+            tree_config: pda::tree_config(merkle_tree).0,
+            merkle_tree: *merkle_tree,
+            owner: *owner,
+            collection_mint: *collection_mint,
+            collection_metadata: pda::metadata(collection_mint).0,
+            collection_master_edition: pda::master_edition(collection_mint).0,
+            bubblegum_signer: pda::bubblegum_signer().0,
+            log_wrapper: SPL_NOOP_ID,
+            compression_program: SPL_ACCOUNT_COMPRESSION_ID,
+            token_metadata_program: mpl_token_metadata::ID,
+            bubblegum_program: mpl_bubblegum::ID,
+            system_program: system_program::ID,
+        },
+        instruction::MintCompressedNft { product_detail_uri },
+    )
+}
+
+/// Appends the proof nodes of a compressed NFT leaf as remaining accounts.
+fn with_proof(mut instruction: Instruction, proof: &[Pubkey]) -> Instruction {
+    instruction.accounts.extend(
+        proof
+            .iter()
+            .map(|node| AccountMeta::new_readonly(*node, false)),
+    );
+    instruction
+}
+
+/**
+ * Builds a `create_compressed_asset_redemption_request` instruction signed by the leaf owner.
+ * `leaf_delegate` is the leaf's current delegate, which is the owner unless one was set.
+ * `proof` is the leaf's proof from an indexer, without the nodes stored in the tree's canopy.
+ */
+pub fn create_compressed_asset_redemption_request(
+    owner: &Pubkey,
+    leaf_delegate: &Pubkey,
+    merkle_tree: &Pubkey,
+    leaf: CompressedLeaf,
+    proof: &[Pubkey],
+) -> Instruction {
+    with_proof(
+        build(
+            accounts::CreateCompressedAssetRedemptionRequest {
+                asset_redemption_info: pda::asset_redemption_info(&compressed_asset_id(
+                    merkle_tree,
+                    leaf.nonce,
+                ))
+                .0,
+                tree_config: pda::tree_config(merkle_tree).0,
+                merkle_tree: *merkle_tree,
+                leaf_delegate: *leaf_delegate,
+                asset_redemption_vault: pda::asset_redemption_vault().0,
+                owner: *owner,
+                log_wrapper: SPL_NOOP_ID,
+                compression_program: SPL_ACCOUNT_COMPRESSION_ID,
+                bubblegum_program: mpl_bubblegum::ID,
+                system_program: system_program::ID,
+            },
+            instruction::CreateCompressedAssetRedemptionRequest { leaf },
+        ),
+        proof,
+    )
+}
+
+/**
+ * Builds a `cancel_compressed_asset_redemption_request` instruction signed by the original owner.
+ * `proof` is the leaf's proof from an indexer, without the nodes stored in the tree's canopy.
+ */
+pub fn cancel_compressed_asset_redemption_request(
+    owner: &Pubkey,
+    merkle_tree: &Pubkey,
+    leaf: CompressedLeaf,
+    proof: &[Pubkey],
+) -> Instruction {
+    with_proof(
+        build(
+            accounts::CancelCompressedAssetRedemptionRequest {
+                asset_redemption_info: pda::asset_redemption_info(&compressed_asset_id(
+                    merkle_tree,
+                    leaf.nonce,
+                ))
+                .0,
+                tree_config: pda::tree_config(merkle_tree).0,
+                merkle_tree: *merkle_tree,
+                asset_redemption_vault: pda::asset_redemption_vault().0,
+                owner: *owner,
+                log_wrapper: SPL_NOOP_ID,
+                compression_program: SPL_ACCOUNT_COMPRESSION_ID,
+                bubblegum_program: mpl_bubblegum::ID,
+                system_program: system_program::ID,
+            },
+            instruction::CancelCompressedAssetRedemptionRequest { leaf },
+        ),
+        proof,
+    )
+}
+
+/**
+ * Builds a `fulfill_compressed_asset_redemption_request` instruction signed by the admin.
+ */
+pub fn fulfill_compressed_asset_redemption_request(
+    admin: &Pubkey,
+    merkle_tree: &Pubkey,
+    nonce: u64,
+) -> Instruction {
+    build(
+        accounts::FulfillCompressedAssetRedemptionRequest {
+            asset_redemption_info: pda::asset_redemption_info(&compressed_asset_id(
+                merkle_tree,
+                nonce,
+            ))
+            .0,
+            merkle_tree: *merkle_tree,
+            admin: *admin,
+            system_program: system_program::ID,
+        },
+        instruction::FulfillCompressedAssetRedemptionRequest { nonce },
+    )
+}
+
+/**
+ * Builds a `create_non_transferable_asset_redemption_request` instruction signed by the NFT owner.
+ * The NFT must be a Token-2022 mint with the non-transferable extension.
+ */
+pub fn create_non_transferable_asset_redemption_request(
+    owner: &Pubkey,
+    nft_mint: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::CreateNonTransferableAssetRedemptionRequest {
+            asset_redemption_info: pda::asset_redemption_info(nft_mint).0,
+            nft_mint: *nft_mint,
+            owner_nft_account: get_associated_token_address_with_program_id(
+                owner,
+                nft_mint,
+                &token_2022::ID,
+            ),
+            asset_redemption_vault: pda::asset_redemption_vault().0,
+            owner: *owner,
+            asset_impairment: pda::asset_impairment(nft_mint).0,
+            system_program: system_program::ID,
+            token_program: token_2022::ID,
+        },
+        instruction::CreateNonTransferableAssetRedemptionRequest {},
+    )
+}
+
+/**
+ * Builds a `cancel_non_transferable_asset_redemption_request` instruction signed by the NFT owner.
+ */
+pub fn cancel_non_transferable_asset_redemption_request(
+    owner: &Pubkey,
+    nft_mint: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::CancelNonTransferableAssetRedemptionRequest {
+            asset_redemption_info: pda::asset_redemption_info(nft_mint).0,
+            nft_mint: *nft_mint,
+            owner_nft_account: get_associated_token_address_with_program_id(
+                owner,
+                nft_mint,
+                &token_2022::ID,
+            ),
+            owner: *owner,
+            token_program: token_2022::ID,
+        },
+        instruction::CancelNonTransferableAssetRedemptionRequest {},
+    )
+}
+
+/**
+ * Builds a `fulfill_non_transferable_asset_redemption_request` instruction signed by the admin.
+ * `owner` is the wallet that requested the redemption and still holds the NFT.
+ */
+pub fn fulfill_non_transferable_asset_redemption_request(
+    admin: &Pubkey,
+    owner: &Pubkey,
+    nft_mint: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::FulfillNonTransferableAssetRedemptionRequest {
+            asset_redemption_info: pda::asset_redemption_info(nft_mint).0,
+            nft_mint: *nft_mint,
+            owner_nft_account: get_associated_token_address_with_program_id(
+                owner,
+                nft_mint,
+                &token_2022::ID,
+            ),
+            asset_redemption_vault: pda::asset_redemption_vault().0,
+            admin: *admin,
+            token_program: token_2022::ID,
+        },
+        instruction::FulfillNonTransferableAssetRedemptionRequest {},
+    )
+}
+
+/**
+ * Builds a `create_primary_listing` instruction signed by the admin `payer`.
+ * Mint the NFT to `pda::sale_escrow()` with `mint_nft` first, usually in the same transaction.
+ */
+pub fn create_primary_listing(
+    payer: &Pubkey,
+    nft_mint: &Pubkey,
+    price: u64,
+    currency: SaleCurrency,
+    token_program: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::CreatePrimaryListing {
+            primary_listing: pda::primary_listing(nft_mint).0,
+            nft_mint: *nft_mint,
+            escrow_nft_account: get_associated_token_address_with_program_id(
+                &pda::sale_escrow().0,
+                nft_mint,
+                token_program,
+            ),
+            sale_escrow: pda::sale_escrow().0,
+            payer: *payer,
+            token_program: *token_program,
+            system_program: system_program::ID,
+        },
+        instruction::CreatePrimaryListing { price, currency },
+    )
+}
+
+/**
+ * Builds a `cancel_primary_listing` instruction signed by the admin `payer`, who receives the NFT.
+ */
+pub fn cancel_primary_listing(
+    payer: &Pubkey,
+    nft_mint: &Pubkey,
+    standard: NftStandard,
+    token_program: &Pubkey,
+) -> Instruction {
+    let escrow_nft_account = get_associated_token_address_with_program_id(
+        &pda::sale_escrow().0,
+        nft_mint,
+        token_program,
+    );
+    let admin_nft_account =
+        get_associated_token_address_with_program_id(payer, nft_mint, token_program);
+    build(
+        accounts::CancelPrimaryListing {
+            primary_listing: pda::primary_listing(nft_mint).0,
+            nft_mint: *nft_mint,
+            escrow_nft_account,
+            admin_nft_account,
+            sale_escrow: pda::sale_escrow().0,
+            payer: *payer,
+            token_program: *token_program,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            programmable: programmable_transfer(
+                standard,
+                nft_mint,
+                &escrow_nft_account,
+                &admin_nft_account,
+            ),
+        },
+        instruction::CancelPrimaryListing {},
+    )
+}
+
+/**
+ * Builds a `purchase_listed_item` instruction signed by the buyer.
+ * `currency` must match the listing; USDC purchases pay from the buyer's USDC associated token
+ * account to the treasury's.
+ */
+pub fn purchase_listed_item(
+    buyer: &Pubkey,
+    treasury: &Pubkey,
+    nft_mint: &Pubkey,
+    currency: SaleCurrency,
+    standard: NftStandard,
+    token_program: &Pubkey,
+) -> Instruction {
+    let escrow_nft_account = get_associated_token_address_with_program_id(
+        &pda::sale_escrow().0,
+        nft_mint,
+        token_program,
+    );
+    let buyer_nft_account =
+        get_associated_token_address_with_program_id(buyer, nft_mint, token_program);
+    build(
+        accounts::PurchaseListedItem {
+            buyer: *buyer,
+            primary_listing: pda::primary_listing(nft_mint).0,
+            nft_mint: *nft_mint,
+            escrow_nft_account,
+            buyer_nft_account,
+            sale_escrow: pda::sale_escrow().0,
+            vault_config: pda::vault_config().0,
+            treasury: *treasury,
+            admin: get_admin_account_pubkey(),
+            insurance_reserve: pda::insurance_reserve().0,
+            asset_impairment: pda::asset_impairment(nft_mint).0,
+            token_program: *token_program,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            programmable: programmable_transfer(
+                standard,
+                nft_mint,
+                &escrow_nft_account,
+                &buyer_nft_account,
+            ),
+            usdc: usdc_payment(currency, buyer, treasury),
+        },
+        instruction::PurchaseListedItem {},
+    )
+}
+
+/**
+ * Builds a `list_nft` instruction signed by the seller, moving the NFT into the marketplace escrow.
+ */
+pub fn list_nft(
+    seller: &Pubkey,
+    nft_mint: &Pubkey,
+    price: u64,
+    standard: NftStandard,
+    token_program: &Pubkey,
+) -> Instruction {
+    let seller_nft_account =
+        get_associated_token_address_with_program_id(seller, nft_mint, token_program);
+    let escrow_nft_account = get_associated_token_address_with_program_id(
+        &pda::marketplace_escrow().0,
+        nft_mint,
+        token_program,
+    );
+    build(
+        accounts::ListNft {
+            seller: *seller,
+            listing: pda::listing(nft_mint).0,
+            nft_mint: *nft_mint,
+            seller_nft_account,
+            escrow_nft_account,
+            marketplace_escrow: pda::marketplace_escrow().0,
+            loan_info: pda::loan_info(nft_mint).0,
+            asset_redemption_info: pda::asset_redemption_info(nft_mint).0,
+            asset_impairment: pda::asset_impairment(nft_mint).0,
+            token_program: *token_program,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            programmable: programmable_transfer(
+                standard,
+                nft_mint,
+                &seller_nft_account,
+                &escrow_nft_account,
+            ),
+        },
+        instruction::ListNft { price },
+    )
+}
+
+/**
+ * Builds a `cancel_listing` instruction signed by the seller.
+ */
+pub fn cancel_listing(
+    seller: &Pubkey,
+    nft_mint: &Pubkey,
+    standard: NftStandard,
+    token_program: &Pubkey,
+) -> Instruction {
+    let seller_nft_account =
+        get_associated_token_address_with_program_id(seller, nft_mint, token_program);
+    let escrow_nft_account = get_associated_token_address_with_program_id(
+        &pda::marketplace_escrow().0,
+        nft_mint,
+        token_program,
+    );
+    build(
+        accounts::CancelListing {
+            seller: *seller,
+            listing: pda::listing(nft_mint).0,
+            nft_mint: *nft_mint,
+            seller_nft_account,
+            escrow_nft_account,
+            marketplace_escrow: pda::marketplace_escrow().0,
+            token_program: *token_program,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            programmable: programmable_transfer(
+                standard,
+                nft_mint,
+                &escrow_nft_account,
+                &seller_nft_account,
+            ),
+        },
+        instruction::CancelListing {},
+    )
+}
+
+/**
+ * Builds a `buy_listing` instruction signed by the buyer.
+ * NFTs with Token Metadata creators also need `with_royalty_creators`.
+ */
+pub fn buy_listing(
+    buyer: &Pubkey,
+    seller: &Pubkey,
+    nft_mint: &Pubkey,
+    standard: NftStandard,
+    token_program: &Pubkey,
+) -> Instruction {
+    let escrow_nft_account = get_associated_token_address_with_program_id(
+        &pda::marketplace_escrow().0,
+        nft_mint,
+        token_program,
+    );
+    let buyer_nft_account =
+        get_associated_token_address_with_program_id(buyer, nft_mint, token_program);
+    build(
+        accounts::BuyListing {
+            buyer: *buyer,
+            listing: pda::listing(nft_mint).0,
+            nft_mint: *nft_mint,
+            escrow_nft_account,
+            buyer_nft_account,
+            marketplace_escrow: pda::marketplace_escrow().0,
+            seller: *seller,
+            loan_info: pda::loan_info(nft_mint).0,
+            asset_redemption_info: pda::asset_redemption_info(nft_mint).0,
+            nft_metadata: pda::metadata(nft_mint).0,
+            asset_impairment: pda::asset_impairment(nft_mint).0,
+            token_program: *token_program,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            programmable: programmable_transfer(
+                standard,
+                nft_mint,
+                &escrow_nft_account,
+                &buyer_nft_account,
+            ),
+        },
+        instruction::BuyListing {},
+    )
+}
+
+/**
+ * Builds a `buy_listing_with_loan` instruction signed by the buyer and the lender.
+ * `loan_id` is the sequence number the new loan will take: one more than the NFT's `LoanCounter`
+ * count, or 1 if the NFT has never backed a loan.
+ */
+#[allow(clippy::too_many_arguments)]
+pub fn buy_listing_with_loan(
+    buyer: &Pubkey,
+    lender: &Pubkey,
+    seller: &Pubkey,
+    nft_mint: &Pubkey,
+    loan_id: u64,
+    down_payment: u64,
+    interest_amount: u64,
+    duration: i64,
+    standard: NftStandard,
+    token_program: &Pubkey,
+) -> Instruction {
+    let escrow_nft_account = get_associated_token_address_with_program_id(
+        &pda::marketplace_escrow().0,
+        nft_mint,
+        token_program,
+    );
+    let vault_nft_account = get_associated_token_address_with_program_id(
+        &pda::vault_authority().0,
+        nft_mint,
+        token_program,
+    );
+    let loan_note_mint = pda::loan_note_mint(nft_mint, loan_id).0;
+    build(
+        accounts::BuyListingWithLoan {
+            buyer: *buyer,
+            lender: *lender,
+            listing: pda::listing(nft_mint).0,
+            loan_info: pda::loan_info(nft_mint).0,
+            loan_counter: pda::loan_counter(nft_mint).0,
+            nft_mint: *nft_mint,
+            escrow_nft_account,
+            vault_nft_account,
+            marketplace_escrow: pda::marketplace_escrow().0,
+            vault_authority: pda::vault_authority().0,
+            seller: *seller,
+            asset_redemption_info: pda::asset_redemption_info(nft_mint).0,
+            nft_metadata: pda::metadata(nft_mint).0,
+            loan_note_mint,
+            lender_note_account: get_associated_token_address(lender, &loan_note_mint),
+            asset_impairment: pda::asset_impairment(nft_mint).0,
+            token_program: *token_program,
+            note_token_program: anchor_spl::token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+            programmable: programmable_transfer(
+                standard,
+                nft_mint,
+                &escrow_nft_account,
+                &vault_nft_account,
+            ),
+        },
+        instruction::BuyListingWithLoan {
+            down_payment,
+            interest_amount,
+            duration,
+        },
+    )
+}
+
+/**
+ * Builds a `make_offer` instruction signed by the buyer.
+ */
+pub fn make_offer(
+    buyer: &Pubkey,
+    nft_mint: &Pubkey,
+    price: u64,
+    expiry: Option<i64>,
+) -> Instruction {
+    build(
+        accounts::MakeOffer {
+            buyer: *buyer,
+            offer: pda::offer(nft_mint, buyer).0,
+            nft_mint: *nft_mint,
+            system_program: system_program::ID,
+        },
+        instruction::MakeOffer { price, expiry },
+    )
+}
+
+/**
+ * Builds a `cancel_offer` instruction signed by the buyer.
+ */
+pub fn cancel_offer(buyer: &Pubkey, nft_mint: &Pubkey) -> Instruction {
+    build(
+        accounts::CancelOffer {
+            buyer: *buyer,
+            offer: pda::offer(nft_mint, buyer).0,
+        },
+        instruction::CancelOffer {},
+    )
+}
+
+/**
+ * Builds an `accept_offer` instruction signed by the NFT holder.
+ * NFTs with Token Metadata creators also need `with_royalty_creators`.
+ */
+pub fn accept_offer(
+    seller: &Pubkey,
+    buyer: &Pubkey,
+    nft_mint: &Pubkey,
+    standard: NftStandard,
+    token_program: &Pubkey,
+) -> Instruction {
+    let seller_nft_account =
+        get_associated_token_address_with_program_id(seller, nft_mint, token_program);
+    let buyer_nft_account =
+        get_associated_token_address_with_program_id(buyer, nft_mint, token_program);
+    build(
+        accounts::AcceptOffer {
+            seller: *seller,
+            offer: pda::offer(nft_mint, buyer).0,
+            nft_mint: *nft_mint,
+            seller_nft_account,
+            buyer_nft_account,
+            buyer: *buyer,
+            loan_info: pda::loan_info(nft_mint).0,
+            asset_redemption_info: pda::asset_redemption_info(nft_mint).0,
+            nft_metadata: pda::metadata(nft_mint).0,
+            asset_impairment: pda::asset_impairment(nft_mint).0,
+            token_program: *token_program,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            programmable: programmable_transfer(
+                standard,
+                nft_mint,
+                &seller_nft_account,
+                &buyer_nft_account,
+            ),
+        },
+        instruction::AcceptOffer {},
+    )
+}
+
+/**
+ * Builds a `fractionalize_nft` instruction signed by the NFT owner.
+ * `token_program` is the SPL Token or Token-2022 program that owns the NFT mint; the fractions always use SPL Token.
+ */
+pub fn fractionalize_nft(
+    owner: &Pubkey,
+    nft_mint: &Pubkey,
+    fraction_supply: u64,
+    reserve_price: u64,
+    redemption_threshold_bps: u16,
+    standard: NftStandard,
+    token_program: &Pubkey,
+) -> Instruction {
+    let fraction_vault = pda::fraction_vault(nft_mint).0;
+    let fraction_mint = pda::fraction_mint(nft_mint).0;
+    let owner_nft_account =
+        get_associated_token_address_with_program_id(owner, nft_mint, token_program);
+    let vault_nft_account =
+        get_associated_token_address_with_program_id(&fraction_vault, nft_mint, token_program);
+    build(
+        accounts::FractionalizeNft {
+            owner: *owner,
+            fraction_vault,
+            nft_mint: *nft_mint,
+            owner_nft_account,
+            vault_nft_account,
+            fraction_mint,
+            owner_fraction_account: get_associated_token_address(owner, &fraction_mint),
+            loan_info: pda::loan_info(nft_mint).0,
+            asset_redemption_info: pda::asset_redemption_info(nft_mint).0,
+            asset_impairment: pda::asset_impairment(nft_mint).0,
+            token_program: *token_program,
+            fraction_token_program: anchor_spl::token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+            programmable: programmable_transfer(
+                standard,
+                nft_mint,
+                &owner_nft_account,
+                &vault_nft_account,
+            ),
+        },
+        instruction::FractionalizeNft {
+            fraction_supply,
+            reserve_price,
+            redemption_threshold_bps,
+        },
+    )
+}
+
+/**
+ * Builds a `place_buyout_bid` instruction signed by the bidder.
+ * `previous_bidder` is the current top bidder, who is refunded, or None for the opening bid.
+ */
+pub fn place_buyout_bid(
+    bidder: &Pubkey,
+    previous_bidder: Option<&Pubkey>,
+    nft_mint: &Pubkey,
+    bid: u64,
+) -> Instruction {
+    build(
+        accounts::PlaceBuyoutBid {
+            bidder: *bidder,
+            fraction_vault: pda::fraction_vault(nft_mint).0,
+            previous_bidder: previous_bidder.copied(),
+            asset_impairment: pda::asset_impairment(nft_mint).0,
+            system_program: system_program::ID,
+        },
+        instruction::PlaceBuyoutBid { bid },
+    )
+}
+
+/**
+ * Builds a `claim_buyout_proceeds` instruction signed by a fraction holder, burning `amount`
+ * fractions from the holder's associated token account.
+ */
+pub fn claim_buyout_proceeds(holder: &Pubkey, nft_mint: &Pubkey, amount: u64) -> Instruction {
+    let fraction_mint = pda::fraction_mint(nft_mint).0;
+    build(
+        accounts::ClaimBuyoutProceeds {
+            holder: *holder,
+            fraction_vault: pda::fraction_vault(nft_mint).0,
+            fraction_mint,
+            holder_fraction_account: get_associated_token_address(holder, &fraction_mint),
+            token_program: anchor_spl::token::ID,
+        },
+        instruction::ClaimBuyoutProceeds { amount },
+    )
+}
+
+/**
+ * Builds a `claim_buyout_nft` instruction signed by the buyout winner.
+ * `curator` is the owner who fractionalized the NFT.
+ */
+pub fn claim_buyout_nft(
+    winner: &Pubkey,
+    curator: &Pubkey,
+    nft_mint: &Pubkey,
+    standard: NftStandard,
+    token_program: &Pubkey,
+) -> Instruction {
+    let fraction_vault = pda::fraction_vault(nft_mint).0;
+    let vault_nft_account =
+        get_associated_token_address_with_program_id(&fraction_vault, nft_mint, token_program);
+    let winner_nft_account =
+        get_associated_token_address_with_program_id(winner, nft_mint, token_program);
+    build(
+        accounts::ClaimBuyoutNft {
+            winner: *winner,
+            fraction_vault,
+            nft_mint: *nft_mint,
+            vault_nft_account,
+            winner_nft_account,
+            curator: *curator,
+            asset_impairment: pda::asset_impairment(nft_mint).0,
+            token_program: *token_program,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            programmable: programmable_transfer(
+                standard,
+                nft_mint,
+                &vault_nft_account,
+                &winner_nft_account,
+            ),
+        },
+        instruction::ClaimBuyoutNft {},
+    )
+}
+
+/**
+ * Builds a `redeem_buyout_nft` instruction signed by the buyout winner.
+ * `curator` is the owner who fractionalized the NFT.
+ */
+pub fn redeem_buyout_nft(
+    winner: &Pubkey,
+    curator: &Pubkey,
+    nft_mint: &Pubkey,
+    standard: NftStandard,
+    token_program: &Pubkey,
+) -> Instruction {
+    let fraction_vault = pda::fraction_vault(nft_mint).0;
+    let vault_nft_account =
+        get_associated_token_address_with_program_id(&fraction_vault, nft_mint, token_program);
+    let asset_redemption_nft_account = get_associated_token_address_with_program_id(
+        &pda::asset_redemption_vault().0,
+        nft_mint,
+        token_program,
+    );
+    build(
+        accounts::RedeemBuyoutNft {
+            winner: *winner,
+            fraction_vault,
+            asset_redemption_info: pda::asset_redemption_info(nft_mint).0,
+            nft_mint: *nft_mint,
+            vault_nft_account,
+            asset_redemption_nft_account,
+            asset_redemption_vault: pda::asset_redemption_vault().0,
+            curator: *curator,
+            asset_impairment: pda::asset_impairment(nft_mint).0,
+            token_program: *token_program,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+            programmable: programmable_transfer(
+                standard,
+                nft_mint,
+                &vault_nft_account,
+                &asset_redemption_nft_account,
+            ),
+        },
+        instruction::RedeemBuyoutNft {},
+    )
+}
+
+/**
+ * Builds a `redeem_fractionalized_nft` instruction signed by the payer and every holder in `holders`,
+ * each burning all the fractions in their associated token account.
+ * `recipient` becomes the owner of the redemption request and `curator` is the owner who fractionalized the NFT.
+ */
+pub fn redeem_fractionalized_nft(
+    payer: &Pubkey,
+    recipient: &Pubkey,
+    curator: &Pubkey,
+    nft_mint: &Pubkey,
+    holders: &[Pubkey],
+    standard: NftStandard,
+    token_program: &Pubkey,
+) -> Instruction {
+    let fraction_vault = pda::fraction_vault(nft_mint).0;
+    let fraction_mint = pda::fraction_mint(nft_mint).0;
+    let vault_nft_account =
+        get_associated_token_address_with_program_id(&fraction_vault, nft_mint, token_program);
+    let asset_redemption_nft_account = get_associated_token_address_with_program_id(
+        &pda::asset_redemption_vault().0,
+        nft_mint,
+        token_program,
+    );
+    let mut instruction = build(
+        accounts::RedeemFractionalizedNft {
+            payer: *payer,
+            fraction_vault,
+            fraction_mint,
+            asset_redemption_info: pda::asset_redemption_info(nft_mint).0,
+            nft_mint: *nft_mint,
+            vault_nft_account,
+            asset_redemption_nft_account,
+            asset_redemption_vault: pda::asset_redemption_vault().0,
+            recipient: *recipient,
+            curator: *curator,
+            asset_impairment: pda::asset_impairment(nft_mint).0,
+            token_program: *token_program,
+            fraction_token_program: anchor_spl::token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+            programmable: programmable_transfer(
+                standard,
+                nft_mint,
+                &vault_nft_account,
+                &asset_redemption_nft_account,
+            ),
+        },
+        instruction::RedeemFractionalizedNft {
+            holder_count: holders.len() as u8,
+        },
+    );
+    instruction
+        .accounts
+        .extend(holders.iter().flat_map(|holder| {
+            [
+                AccountMeta::new_readonly(*holder, true),
+                AccountMeta::new(get_associated_token_address(holder, &fraction_mint), false),
+            ]
+        }));
+    instruction
+}
+
+/**
+ * Builds a `list_nft_for_rent` instruction signed by the NFT owner.
+ * `token_program` is the SPL Token or Token-2022 program that owns the NFT mint.
+ */
+pub fn list_nft_for_rent(
+    owner: &Pubkey,
+    nft_mint: &Pubkey,
+    daily_fee: u64,
+    max_days: u16,
+    standard: NftStandard,
+    token_program: &Pubkey,
+) -> Instruction {
+    let rental = pda::rental(nft_mint).0;
+    let owner_nft_account =
+        get_associated_token_address_with_program_id(owner, nft_mint, token_program);
+    let rental_nft_account =
+        get_associated_token_address_with_program_id(&rental, nft_mint, token_program);
+    build(
+        accounts::ListNftForRent {
+            owner: *owner,
+            rental,
+            nft_mint: *nft_mint,
+            owner_nft_account,
+            rental_nft_account,
+            loan_info: pda::loan_info(nft_mint).0,
+            asset_redemption_info: pda::asset_redemption_info(nft_mint).0,
+            asset_impairment: pda::asset_impairment(nft_mint).0,
+            token_program: *token_program,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            programmable: programmable_transfer(
+                standard,
+                nft_mint,
+                &owner_nft_account,
+                &rental_nft_account,
+            ),
+        },
+        instruction::ListNftForRent {
+            daily_fee,
+            max_days,
+        },
+    )
+}
+
+/**
+ * Builds a `rent_nft` instruction signed by the renter.
+ * `owner` is the owner who listed the NFT for rent.
+ */
+pub fn rent_nft(renter: &Pubkey, owner: &Pubkey, nft_mint: &Pubkey, days: u16) -> Instruction {
+    let usage_mint = pda::rental_usage_mint(nft_mint).0;
+    build(
+        accounts::RentNft {
+            renter: *renter,
+            rental: pda::rental(nft_mint).0,
+            owner: *owner,
+            vault_config: pda::vault_config().0,
+            insurance_reserve: pda::insurance_reserve().0,
+            asset_impairment: pda::asset_impairment(nft_mint).0,
+            usage_mint,
+            renter_usage_account: rental_usage_account(renter, nft_mint),
+            usage_token_program: token_2022::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::RentNft { days },
+    )
+}
+
+/**
+ * Builds a `cancel_rental_listing` instruction signed by the NFT owner.
+ */
+pub fn cancel_rental_listing(
+    owner: &Pubkey,
+    nft_mint: &Pubkey,
+    standard: NftStandard,
+    token_program: &Pubkey,
+) -> Instruction {
+    let rental = pda::rental(nft_mint).0;
+    let owner_nft_account =
+        get_associated_token_address_with_program_id(owner, nft_mint, token_program);
+    let rental_nft_account =
+        get_associated_token_address_with_program_id(&rental, nft_mint, token_program);
+    build(
+        accounts::CancelRentalListing {
+            owner: *owner,
+            rental,
+            nft_mint: *nft_mint,
+            owner_nft_account,
+            rental_nft_account,
+            token_program: *token_program,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            programmable: programmable_transfer(
+                standard,
+                nft_mint,
+                &rental_nft_account,
+                &owner_nft_account,
+            ),
+        },
+        instruction::CancelRentalListing {},
+    )
+}
+
+/**
+ * Builds an `end_rental` instruction that anyone may sign as `payer` once the rental term has ended.
+ * `owner` is the owner who listed the NFT for rent and `renter` the renter whose term ended.
+ */
+pub fn end_rental(
+    payer: &Pubkey,
+    owner: &Pubkey,
+    renter: &Pubkey,
+    nft_mint: &Pubkey,
+    standard: NftStandard,
+    token_program: &Pubkey,
+) -> Instruction {
+    let rental = pda::rental(nft_mint).0;
+    let owner_nft_account =
+        get_associated_token_address_with_program_id(owner, nft_mint, token_program);
+    let rental_nft_account =
+        get_associated_token_address_with_program_id(&rental, nft_mint, token_program);
+    build(
+        accounts::EndRental {
+            payer: *payer,
+            rental,
+            nft_mint: *nft_mint,
+            owner: *owner,
+            owner_nft_account,
+            rental_nft_account,
+            renter: *renter,
+            usage_mint: pda::rental_usage_mint(nft_mint).0,
+            renter_usage_account: rental_usage_account(renter, nft_mint),
+            token_program: *token_program,
+            usage_token_program: token_2022::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            programmable: programmable_transfer(
+                standard,
+                nft_mint,
+                &rental_nft_account,
+                &owner_nft_account,
+            ),
+        },
+        instruction::EndRental {},
+    )
+}
+
+/**
+ * Returns the renter's Token-2022 associated token account holding the usage token of a rented NFT.
+ */
+pub fn rental_usage_account(renter: &Pubkey, nft_mint: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program_id(
+        renter,
+        &pda::rental_usage_mint(nft_mint).0,
+        &token_2022::ID,
+    )
+}
+
+/**
+ * Builds a `fund_insurance_reserve` instruction depositing `amount` lamports from `funder`.
+ */
+pub fn fund_insurance_reserve(funder: &Pubkey, amount: u64) -> Instruction {
+    build(
+        accounts::FundInsuranceReserve {
+            funder: *funder,
+            insurance_reserve: pda::insurance_reserve().0,
+            system_program: system_program::ID,
+        },
+        instruction::FundInsuranceReserve { amount },
+    )
+}
+
+/**
+ * Builds a `record_appraisal` instruction that the admin signs as `payer`.
+ */
+pub fn record_appraisal(payer: &Pubkey, nft_mint: &Pubkey, appraised_value: u64) -> Instruction {
+    build(
+        accounts::RecordAppraisal {
+            payer: *payer,
+            nft_mint: *nft_mint,
+            asset_appraisal: pda::asset_appraisal(nft_mint).0,
+            system_program: system_program::ID,
+        },
+        instruction::RecordAppraisal { appraised_value },
+    )
+}
+
+/**
+ * Builds a `declare_asset_impaired` instruction that the admin signs as `payer`.
+ */
+pub fn declare_asset_impaired(payer: &Pubkey, nft_mint: &Pubkey) -> Instruction {
+    build(
+        accounts::DeclareAssetImpaired {
+            payer: *payer,
+            nft_mint: *nft_mint,
+            asset_appraisal: pda::asset_appraisal(nft_mint).0,
+            asset_impairment: pda::asset_impairment(nft_mint).0,
+            system_program: system_program::ID,
+        },
+        instruction::DeclareAssetImpaired {},
+    )
+}
+
+/**
+ * Builds a `claim_insurance_payout` instruction that burns `holder`'s impaired NFT for its payout.
+ * `collection_mint` is the NFT's verified collection, if it has one.
+ */
+pub fn claim_insurance_payout(
+    holder: &Pubkey,
+    nft_mint: &Pubkey,
+    standard: NftStandard,
+    collection_mint: Option<&Pubkey>,
+    token_program: &Pubkey,
+) -> Instruction {
+    let holder_nft_account =
+        get_associated_token_address_with_program_id(holder, nft_mint, token_program);
+    build(
+        accounts::ClaimInsurancePayout {
+            holder: *holder,
+            asset_impairment: pda::asset_impairment(nft_mint).0,
+            nft_mint: *nft_mint,
+            holder_nft_account,
+            insurance_reserve: pda::insurance_reserve().0,
+            token_program: *token_program,
+            system_program: system_program::ID,
+            metadata_burn: metadata_burn(standard, nft_mint, &holder_nft_account, collection_mint),
+        },
+        instruction::ClaimInsurancePayout {},
+    )
+}
+
+/**
+ * Builds a `claim_fractionalized_insurance_payout` crank instruction signed by any `cranker`.
+ * `curator` is the owner who fractionalized the NFT and `top_bidder` the buyout auction's top
+ * bidder, if it has a bid. `collection_mint` is the NFT's verified collection, if it has one.
+ */
+pub fn claim_fractionalized_insurance_payout(
+    cranker: &Pubkey,
+    curator: &Pubkey,
+    top_bidder: Option<&Pubkey>,
+    nft_mint: &Pubkey,
+    standard: NftStandard,
+    collection_mint: Option<&Pubkey>,
+    token_program: &Pubkey,
+) -> Instruction {
+    let fraction_vault = pda::fraction_vault(nft_mint).0;
+    let vault_nft_account =
+        get_associated_token_address_with_program_id(&fraction_vault, nft_mint, token_program);
+    build(
+        accounts::ClaimFractionalizedInsurancePayout {
+            cranker: *cranker,
+            asset_impairment: pda::asset_impairment(nft_mint).0,
+            fraction_vault,
+            nft_mint: *nft_mint,
+            vault_nft_account,
+            top_bidder: top_bidder.copied(),
+            curator: *curator,
+            insurance_reserve: pda::insurance_reserve().0,
+            token_program: *token_program,
+            system_program: system_program::ID,
+            metadata_burn: metadata_burn(standard, nft_mint, &vault_nft_account, collection_mint),
+        },
+        instruction::ClaimFractionalizedInsurancePayout {},
+    )
+}
@@ -0,0 +1,11 @@
+/**
+ * Rust client SDK for the Collectible Vault program.
+ * Provides PDA derivation, instruction builders, account deserializers and error decoding,
+ * so Rust services do not need to hand-build instructions against the program.
+ */
+pub mod accounts;
+pub mod errors;
+pub mod instructions;
+pub mod pda;
+
+pub use collectible_vault::{state, ID as PROGRAM_ID};
@@ -0,0 +1,217 @@
+use {
+    anchor_lang::prelude::Pubkey,
+    collectible_vault::{
+        constants::pda_constants::{
+            ASSET_APPRAISAL_SEED, ASSET_IMPAIRMENT_SEED, ASSET_REDEMPTION_INFO_SEED,
+            ASSET_REDEMPTION_VAULT_SEED, BORROWER_PROFILE_SEED, FRACTION_MINT_SEED,
+            FRACTION_VAULT_SEED, INSURANCE_RESERVE_SEED, LISTING_SEED, LOAN_COUNTER_SEED,
+            LOAN_INFO_SEED, LOAN_NOTE_SEED, LOAN_RECORD_SEED, MARKETPLACE_ESCROW_SEED, OFFER_SEED,
+            PRIMARY_LISTING_SEED, RENTAL_SEED, RENTAL_USAGE_MINT_SEED, SALE_ESCROW_SEED,
+            VAULT_COLLECTION_COUNTER_SEED, VAULT_CONFIG_SEED, VAULT_SEED,
+        },
+        ID as PROGRAM_ID,
+    },
+    mpl_bubblegum::accounts::TreeConfig,
+    mpl_token_metadata::accounts::{MasterEdition, Metadata, TokenRecord},
+};
+
+/**
+ * Derives the vault authority PDA that holds staked loan collateral.
+ */
+pub fn vault_authority() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VAULT_SEED], &PROGRAM_ID)
+}
+
+/**
+ * Derives the asset redemption vault PDA that holds NFTs pending redemption.
+ */
+pub fn asset_redemption_vault() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ASSET_REDEMPTION_VAULT_SEED], &PROGRAM_ID)
+}
+
+/**
+ * Derives the asset redemption info PDA for an NFT.
+ */
+pub fn asset_redemption_info(nft_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[ASSET_REDEMPTION_INFO_SEED, nft_mint.as_ref()],
+        &PROGRAM_ID,
+    )
+}
+
+/**
+ * Derives the vault collection counter PDA.
+ */
+pub fn collection_counter() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VAULT_COLLECTION_COUNTER_SEED], &PROGRAM_ID)
+}
+
+/**
+ * Derives the vault config PDA holding the admin-managed program settings.
+ */
+pub fn vault_config() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VAULT_CONFIG_SEED], &PROGRAM_ID)
+}
+
+/**
+ * Derives the sale escrow PDA that holds NFTs listed for their primary sale.
+ */
+pub fn sale_escrow() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SALE_ESCROW_SEED], &PROGRAM_ID)
+}
+
+/**
+ * Derives the primary listing PDA of an NFT listed for its primary sale.
+ */
+pub fn primary_listing(nft_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PRIMARY_LISTING_SEED, nft_mint.as_ref()], &PROGRAM_ID)
+}
+
+/**
+ * Derives the marketplace escrow PDA that holds NFTs listed for resale.
+ */
+pub fn marketplace_escrow() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[MARKETPLACE_ESCROW_SEED], &PROGRAM_ID)
+}
+
+/**
+ * Derives the marketplace listing PDA of an NFT listed for resale.
+ */
+pub fn listing(nft_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[LISTING_SEED, nft_mint.as_ref()], &PROGRAM_ID)
+}
+
+/**
+ * Derives the PDA of a buyer's marketplace offer on an NFT.
+ */
+pub fn offer(nft_mint: &Pubkey, buyer: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[OFFER_SEED, nft_mint.as_ref(), buyer.as_ref()],
+        &PROGRAM_ID,
+    )
+}
+
+/**
+ * Derives the fraction vault PDA holding a fractionalized NFT.
+ */
+pub fn fraction_vault(nft_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[FRACTION_VAULT_SEED, nft_mint.as_ref()], &PROGRAM_ID)
+}
+
+/**
+ * Derives the mint PDA of a fractionalized NFT's fraction tokens.
+ */
+pub fn fraction_mint(nft_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[FRACTION_MINT_SEED, nft_mint.as_ref()], &PROGRAM_ID)
+}
+
+/**
+ * Derives the rental PDA holding an NFT listed for rent.
+ */
+pub fn rental(nft_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[RENTAL_SEED, nft_mint.as_ref()], &PROGRAM_ID)
+}
+
+/**
+ * Derives the Token-2022 mint of the usage token a renter holds while renting an NFT.
+ */
+pub fn rental_usage_mint(nft_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[RENTAL_USAGE_MINT_SEED, nft_mint.as_ref()], &PROGRAM_ID)
+}
+
+/**
+ * Derives the insurance reserve PDA paying out claims on lost or damaged physical items.
+ */
+pub fn insurance_reserve() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[INSURANCE_RESERVE_SEED], &PROGRAM_ID)
+}
+
+/**
+ * Derives the asset appraisal PDA storing the last appraisal of an NFT's physical item.
+ */
+pub fn asset_appraisal(nft_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ASSET_APPRAISAL_SEED, nft_mint.as_ref()], &PROGRAM_ID)
+}
+
+/**
+ * Derives the asset impairment PDA marking an NFT whose physical item was lost or damaged.
+ */
+pub fn asset_impairment(nft_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ASSET_IMPAIRMENT_SEED, nft_mint.as_ref()], &PROGRAM_ID)
+}
+
+/**
+ * Derives the loan info PDA for the loan currently open against an NFT.
+ */
+pub fn loan_info(nft_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[LOAN_INFO_SEED, nft_mint.as_ref()], &PROGRAM_ID)
+}
+
+/**
+ * Derives the per-mint loan counter PDA for an NFT.
+ */
+pub fn loan_counter(nft_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[LOAN_COUNTER_SEED, nft_mint.as_ref()], &PROGRAM_ID)
+}
+
+/**
+ * Derives the loan record PDA archiving loan `loan_id` against an NFT.
+ */
+pub fn loan_record(nft_mint: &Pubkey, loan_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[LOAN_RECORD_SEED, nft_mint.as_ref(), &loan_id.to_le_bytes()],
+        &PROGRAM_ID,
+    )
+}
+
+/**
+ * Derives the loan note mint PDA for loan `loan_id` against an NFT.
+ */
+pub fn loan_note_mint(nft_mint: &Pubkey, loan_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[LOAN_NOTE_SEED, nft_mint.as_ref(), &loan_id.to_le_bytes()],
+        &PROGRAM_ID,
+    )
+}
+
+/**
+ * Derives the reputation profile PDA of a borrower wallet.
+ */
+pub fn borrower_profile(borrower: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[BORROWER_PROFILE_SEED, borrower.as_ref()], &PROGRAM_ID)
+}
+
+/**
+ * Derives the Metaplex metadata PDA of a mint.
+ */
+pub fn metadata(mint: &Pubkey) -> (Pubkey, u8) {
+    Metadata::find_pda(mint)
+}
+
+/**
+ * Derives the Metaplex master edition PDA of a mint.
+ */
+pub fn master_edition(mint: &Pubkey) -> (Pubkey, u8) {
+    MasterEdition::find_pda(mint)
+}
+
+/**
+ * Derives the Metaplex token record PDA of a programmable NFT's token account.
+ */
+pub fn token_record(mint: &Pubkey, token_account: &Pubkey) -> (Pubkey, u8) {
+    TokenRecord::find_pda(mint, token_account)
+}
+
+/**
+ * Derives the Bubblegum tree config PDA of a Merkle tree.
+ */
+pub fn tree_config(merkle_tree: &Pubkey) -> (Pubkey, u8) {
+    TreeConfig::find_pda(merkle_tree)
+}
+
+/**
+ * Derives the Bubblegum signer PDA that verifies collections of compressed NFTs.
+ */
+pub fn bubblegum_signer() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"collection_cpi"], &mpl_bubblegum::ID)
+}
@@ -0,0 +1,57 @@
+/**
+ * Error decoding tests: every variant declared in the program's ErrorCode, in declaration order,
+ * must decode from its on-chain code, so an error added to the program without mirroring it in
+ * ERROR_CODES fails here.
+ */
+use {
+    anchor_lang::{error::ERROR_CODE_OFFSET, solana_program::instruction::InstructionError},
+    collectible_vault::errors::ErrorCode,
+    collectible_vault_client::errors::{decode_error_code, decode_instruction_error, ERROR_CODES},
+};
+
+const PROGRAM_ERRORS_SOURCE: &str =
+    include_str!("../../../programs/collectible_vault/src/errors.rs");
+
+/// Returns the variant names of the program's ErrorCode enum, in declaration order
+fn declared_error_names() -> Vec<&'static str> {
+    let body = PROGRAM_ERRORS_SOURCE
+        .split_once("pub enum ErrorCode {")
+        .expect("ErrorCode enum")
+        .1;
+    body.lines()
+        .map(str::trim)
+        .take_while(|line| *line != "}")
+        .filter(|line| !line.starts_with("///") && !line.starts_with("#[") && !line.is_empty())
+        .map(|line| line.trim_end_matches(','))
+        .collect()
+}
+
+#[test]
+fn every_program_error_code_decodes() {
+    let names = declared_error_names();
+    assert!(!names.is_empty());
+    assert_eq!(ERROR_CODES.len(), names.len());
+
+    for (index, name) in names.iter().enumerate() {
+        let code = ERROR_CODE_OFFSET + index as u32;
+        let decoded = decode_error_code(code).unwrap_or_else(|| panic!("{name} does not decode"));
+        assert_eq!(decoded.name(), *name);
+        assert_eq!(u32::from(decoded), code);
+        assert_eq!(
+            decode_instruction_error(&InstructionError::Custom(code)).map(|error| error.name()),
+            Some(decoded.name())
+        );
+    }
+}
+
+#[test]
+fn foreign_error_codes_do_not_decode() {
+    let past_last = ERROR_CODE_OFFSET + ERROR_CODES.len() as u32;
+    assert!(decode_error_code(past_last).is_none());
+    assert!(decode_error_code(ERROR_CODE_OFFSET - 1).is_none());
+    assert!(decode_instruction_error(&InstructionError::InvalidAccountData).is_none());
+    assert_eq!(
+        u32::from(ErrorCode::CollectionMintDoesNotMatch),
+        ERROR_CODE_OFFSET
+    );
+}
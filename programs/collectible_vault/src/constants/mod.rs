@@ -1,2 +1,3 @@
 pub mod admin_constants;
+pub mod payment_constants;
 pub mod pda_constants;
@@ -0,0 +1,18 @@
+use anchor_lang::prelude::Pubkey;
+use std::str::FromStr;
+
+/**
+ * The public key string of the USDC mint
+ * Listings priced in USDC are paid with tokens of this mint.
+ */
+pub const USDC_MINT_PUBLIC_KEY_STR: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+
+/**
+ * Returns the Pubkey of the USDC mint
+ * This function converts the string representation of the public key into a Pubkey type
+ * # Panics
+ * Panics if the public key string is invalid
+ */
+pub fn get_usdc_mint_pubkey() -> Pubkey {
+    Pubkey::from_str(USDC_MINT_PUBLIC_KEY_STR).expect("Invalid public key")
+}
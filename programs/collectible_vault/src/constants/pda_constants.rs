@@ -27,3 +27,105 @@ pub const LOAN_INFO_SEED: &[u8] = b"loan";
  * This is used to track the vault information
  */
 pub const VAULT_SEED: &[u8] = b"vault";
+
+/**
+ * Seed for the per-mint loan counter PDA
+ * This is used to assign sequence numbers to loans against the same NFT
+ */
+pub const LOAN_COUNTER_SEED: &[u8] = b"loan_counter";
+
+/**
+ * Seed for the loan record PDA
+ * This is used to archive the outcome of each loan after it closes
+ */
+pub const LOAN_RECORD_SEED: &[u8] = b"loan_record";
+
+/**
+ * Seed for the borrower profile PDA
+ * This is used to track the loan history of each borrower wallet
+ */
+pub const BORROWER_PROFILE_SEED: &[u8] = b"borrower_profile";
+
+/**
+ * Seed for the loan note mint PDA
+ * This is used to mint the transferable NFT representing a lender's position in a loan
+ */
+pub const LOAN_NOTE_SEED: &[u8] = b"loan_note";
+
+/**
+ * Seed for the vault config PDA
+ * This is used to store the program-wide settings managed by the admin
+ */
+pub const VAULT_CONFIG_SEED: &[u8] = b"vault_config";
+
+/**
+ * Seed for the sale escrow PDA
+ * This is used to hold freshly minted NFTs listed for their primary sale
+ */
+pub const SALE_ESCROW_SEED: &[u8] = b"sale_escrow";
+
+/**
+ * Seed for the primary listing PDA
+ * This is used to track the price of an NFT listed for its primary sale
+ */
+pub const PRIMARY_LISTING_SEED: &[u8] = b"primary_listing";
+
+/**
+ * Seed for the marketplace escrow PDA
+ * This is used to hold NFTs listed for resale on the marketplace
+ */
+pub const MARKETPLACE_ESCROW_SEED: &[u8] = b"marketplace_escrow";
+
+/**
+ * Seed for the marketplace listing PDA
+ * This is used to track the seller and price of an NFT listed for resale
+ */
+pub const LISTING_SEED: &[u8] = b"listing";
+
+/**
+ * Seed for the marketplace offer PDA
+ * This is used to hold a buyer's offer on an NFT along with the offered lamports
+ */
+pub const OFFER_SEED: &[u8] = b"offer";
+
+/**
+ * Seed for the fraction vault PDA
+ * This is used to hold a fractionalized NFT and the bids of its buyout auction
+ */
+pub const FRACTION_VAULT_SEED: &[u8] = b"fraction_vault";
+
+/**
+ * Seed for the fraction mint PDA
+ * This is used to mint the fraction tokens of a fractionalized NFT
+ */
+pub const FRACTION_MINT_SEED: &[u8] = b"fraction_mint";
+
+/**
+ * Seed for the rental PDA
+ * This is used to hold an NFT listed for rent and record who holds its usage rights
+ */
+pub const RENTAL_SEED: &[u8] = b"rental";
+
+/**
+ * Seed for the rental usage mint PDA
+ * This is used to mint the non-transferable token a renter holds for the length of a rental term
+ */
+pub const RENTAL_USAGE_MINT_SEED: &[u8] = b"rental_usage_mint";
+
+/**
+ * Seed for the insurance reserve PDA
+ * This is used to hold the lamports paying out claims on lost or damaged physical items
+ */
+pub const INSURANCE_RESERVE_SEED: &[u8] = b"insurance_reserve";
+
+/**
+ * Seed for the asset appraisal PDA
+ * This is used to store the last appraised value of an NFT's physical item
+ */
+pub const ASSET_APPRAISAL_SEED: &[u8] = b"asset_appraisal";
+
+/**
+ * Seed for the asset impairment PDA
+ * This is used to mark an NFT whose physical item was lost or damaged, and track its insurance claim
+ */
+pub const ASSET_IMPAIRMENT_SEED: &[u8] = b"asset_impairment";
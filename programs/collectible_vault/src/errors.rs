@@ -79,4 +79,216 @@ pub enum ErrorCode {
     /// Thrown when attempting to create a redemption request for an associated token account that has no token balance
     #[msg("Associated token account has no token balance")]
     AssociatedTokenAccountHasNoTokenBalance,
+
+    /// Thrown when the provided loan request expiry is not in the future
+    #[msg("Invalid loan request expiry")]
+    InvalidLoanRequestExpiry,
+
+    /// Thrown when attempting to fund a loan request whose expiry has passed
+    #[msg("Loan request has expired")]
+    LoanRequestExpired,
+
+    /// Thrown when attempting to close a loan request that has no expiry or has not expired yet
+    #[msg("Loan request has not expired yet")]
+    LoanRequestNotExpired,
+
+    /// Thrown when a borrower's history does not satisfy the lender's reputation gate
+    #[msg("Borrower reputation does not meet the lender's requirements")]
+    BorrowerReputationTooLow,
+
+    /// Thrown when the provided loan note token account does not hold the loan note
+    #[msg("Loan note account must hold the loan note")]
+    InvalidLoanNoteAccount,
+
+    /// Thrown when migrating an account that already uses the current layout version
+    #[msg("Account is already at the current version")]
+    AccountAlreadyMigrated,
+
+    /// Thrown when an account's stored version has no upgrade path to the current layout
+    #[msg("Unsupported account version")]
+    UnsupportedAccountVersion,
+
+    /// Thrown when migrating an account whose type is not a versioned state account
+    #[msg("Account type cannot be migrated")]
+    UnsupportedAccountType,
+
+    /// Thrown when moving a programmable NFT without the Token Metadata accounts it requires
+    #[msg("Programmable NFT transfers require the metadata, edition, token record and sysvar accounts")]
+    MissingProgrammableAccounts,
+
+    /// Thrown when the NFT's Token-2022 mint is non-transferable and cannot be moved into or out of a vault
+    #[msg("Non-transferable NFTs cannot be deposited in the vault")]
+    NonTransferableNft,
+
+    /// Thrown when a Metaplex Core asset cannot be decoded or carries plugins that would let another authority move or burn it
+    #[msg("Core asset is not supported as vault collateral")]
+    UnsupportedCoreAsset,
+
+    /// Thrown when sweeping a vault token account that still holds a token or is frozen
+    #[msg("Vault token account must be empty and not frozen to be swept")]
+    VaultAccountNotSweepable,
+
+    /// Thrown when the loan records passed to a sweep do not show who paid for the vault token account
+    #[msg("Loan records do not identify the payer of the vault token account")]
+    InvalidVaultAccountPayer,
+
+    /// Thrown when the creator accounts passed for a royalty payment do not match the NFT's metadata creators
+    #[msg("Royalty creator accounts do not match the NFT metadata creators")]
+    InvalidRoyaltyCreator,
+
+    /// Thrown when listing an NFT for sale at a price of zero
+    #[msg("Sale price must be greater than zero")]
+    InvalidSalePrice,
+
+    /// Thrown when a sale is attempted before the admin has set the treasury
+    #[msg("Treasury has not been configured")]
+    TreasuryNotConfigured,
+
+    /// Thrown when the treasury account does not match the treasury in the vault config
+    #[msg("Treasury account does not match the configured treasury")]
+    InvalidTreasury,
+
+    /// Thrown when paying a USDC price without the USDC mint, token accounts and token program
+    #[msg("USDC payments require the USDC mint, both token accounts and the token program")]
+    MissingUsdcPaymentAccounts,
+
+    /// Thrown when a USDC token account is not held by the expected wallet or is not a USDC account
+    #[msg("Payment token account does not belong to the expected wallet or mint")]
+    InvalidPaymentAccount,
+
+    /// Thrown when trading an NFT that has an open loan against it
+    #[msg("NFT has an open loan and cannot be traded")]
+    NftHasOpenLoan,
+
+    /// Thrown when trading an NFT that has a pending or fulfilled redemption request
+    #[msg("NFT has a redemption request and cannot be traded")]
+    NftHasRedemptionRequest,
+
+    /// Thrown when the seller account does not match the listing's seller
+    #[msg("Seller does not match the listing")]
+    InvalidSeller,
+
+    /// Thrown when making an offer whose expiry is not in the future
+    #[msg("Offer expiry must be in the future")]
+    InvalidOfferExpiry,
+
+    /// Thrown when accepting an offer after its expiry
+    #[msg("Offer has expired")]
+    OfferExpired,
+
+    /// Thrown when a financed purchase's down payment is zero, covers the whole price or does not cover the creator royalty
+    #[msg("Down payment must cover the creator royalty and be less than the price")]
+    InvalidDownPayment,
+
+    /// Thrown when fractionalizing an NFT into zero fractions
+    #[msg("Fraction supply must be greater than zero")]
+    InvalidFractionSupply,
+
+    /// Thrown when fractionalizing an NFT with a zero buyout reserve price
+    #[msg("Reserve price must be greater than zero")]
+    InvalidReservePrice,
+
+    /// Thrown when bidding on a fractionalized NFT that has already left the fraction vault
+    #[msg("Fraction vault no longer holds the NFT")]
+    FractionVaultNotActive,
+
+    /// Thrown when bidding after the buyout auction has ended
+    #[msg("Buyout auction has ended")]
+    BuyoutAuctionEnded,
+
+    /// Thrown when a buyout bid is below the reserve price or the minimum raise over the top bid
+    #[msg("Bid is too low")]
+    BidTooLow,
+
+    /// Thrown when the previous top bidder account is missing or does not match the fraction vault
+    #[msg("Previous bidder does not match the top bidder")]
+    InvalidPreviousBidder,
+
+    /// Thrown when claiming buyout proceeds or the NFT before a buyout auction has ended
+    #[msg("Buyout is not complete")]
+    BuyoutNotComplete,
+
+    /// Thrown when someone other than the buyout winner claims the NFT
+    #[msg("Signer is not the buyout winner")]
+    InvalidBuyoutWinner,
+
+    /// Thrown when burning zero fractions
+    #[msg("Fraction amount must be greater than zero")]
+    InvalidFractionAmount,
+
+    /// Thrown when fractionalizing an NFT with a redemption threshold outside a strict majority up to the full supply
+    #[msg("Redemption threshold must be more than half and at most all of the fraction supply")]
+    InvalidRedemptionThreshold,
+
+    /// Thrown when the fractions burned to redeem a fractionalized NFT fall short of its redemption threshold
+    #[msg("Fractions burned do not meet the redemption threshold")]
+    RedemptionThresholdNotMet,
+
+    /// Thrown when a fraction holder did not sign or their fraction account does not hold the vault's fractions
+    #[msg("Invalid fraction holder account")]
+    InvalidFractionHolder,
+
+    /// Thrown when listing an NFT for rent with a zero daily fee
+    #[msg("Daily rental fee must be greater than zero")]
+    InvalidRentalFee,
+
+    /// Thrown when a rental term is zero days or longer than the owner allows
+    #[msg("Invalid rental duration")]
+    InvalidRentalDuration,
+
+    /// Thrown when renting, or withdrawing from rent, an NFT that is already rented
+    #[msg("NFT is already rented")]
+    NftAlreadyRented,
+
+    /// Thrown when ending a rental for an NFT that was never rented
+    #[msg("NFT is not rented")]
+    NftNotRented,
+
+    /// Thrown when ending a rental before its term is over
+    #[msg("Rental term has not ended")]
+    RentalNotEnded,
+
+    /// Thrown when someone other than the owner withdraws an NFT from rent
+    #[msg("Signer is not the rental owner")]
+    InvalidRentalOwner,
+
+    /// Thrown when the admin sets an insurance fee above VaultConfig::MAX_INSURANCE_FEE_BPS
+    #[msg("Insurance fee exceeds the maximum")]
+    InvalidInsuranceFee,
+
+    /// Thrown when recording a zero appraisal
+    #[msg("Appraised value must be greater than zero")]
+    InvalidAppraisal,
+
+    /// Thrown when depositing zero lamports into the insurance reserve
+    #[msg("Deposit must be greater than zero")]
+    InvalidInsuranceDeposit,
+
+    /// Thrown when an NFT declared impaired is listed, sold, rented, staked, fractionalized or sent for redemption
+    #[msg("NFT is frozen after its physical item was declared impaired")]
+    NftImpaired,
+
+    /// Thrown when claiming the insurance payout of an impaired NFT a second time
+    #[msg("Insurance payout has already been claimed")]
+    InsuranceAlreadyClaimed,
+
+    /// Thrown when the insurance reserve cannot cover a claim's payout
+    #[msg("Insurance reserve cannot cover the payout")]
+    InsufficientInsuranceReserve,
+
+    /// Thrown when the collection passed for a Metaplex Core asset is not the collection the asset belongs to
+    #[msg("Collection does not match the Core asset's collection")]
+    InvalidCoreCollection,
+
+    /// Thrown when the account passed as a rental's renter is not the renter recorded in it
+    #[msg("Account is not the rental's renter")]
+    InvalidRenter,
+
+    /// Thrown when a transferable NFT is sent for redemption through the non-transferable redemption flow
+    #[msg("Transferable NFTs are redeemed by depositing them in the vault")]
+    TransferableNft,
+
+    /// Thrown when fulfilling a non-transferable NFT's redemption after its owner revoked the vault's approval
+    #[msg("NFT owner revoked the redemption vault's approval to burn the NFT")]
+    RedemptionApprovalRevoked,
 }
@@ -0,0 +1,444 @@
+use {
+    crate::{instructions::nft_transfer::NftStandard, state::SaleCurrency},
+    anchor_lang::prelude::*,
+};
+
+/// Emitted when the admin creates a new collection
+#[event]
+pub struct CollectionCreated {
+    pub collection_mint: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin mints a new NFT into the vault collection
+#[event]
+pub struct NftMinted {
+    pub nft_mint: Pubkey,
+    pub collection_mint: Pubkey,
+    pub owner: Pubkey,
+    /// The item number of the NFT within its collection
+    pub item_number: u64,
+    pub product_detail_uri: String,
+    pub standard: NftStandard,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin mints a new compressed NFT into a Bubblegum Merkle tree
+#[event]
+pub struct CompressedNftMinted {
+    /// The asset id derived from the tree and nonce, used in place of a mint by the vault
+    pub asset_id: Pubkey,
+    pub merkle_tree: Pubkey,
+    pub nonce: u64,
+    pub collection_mint: Pubkey,
+    pub owner: Pubkey,
+    /// The item number of the NFT within its collection
+    pub item_number: u64,
+    pub product_detail_uri: String,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin mints a new Metaplex Core asset
+#[event]
+pub struct CoreAssetMinted {
+    pub asset: Pubkey,
+    pub owner: Pubkey,
+    /// The item number of the asset, shared with NFTs minted into the vault collection
+    pub item_number: u64,
+    pub product_detail_uri: String,
+    pub timestamp: i64,
+}
+
+/// Emitted when an NFT owner stakes their NFT and requests a loan
+#[event]
+pub struct LoanRequested {
+    pub loan_info: Pubkey,
+    pub nft_mint: Pubkey,
+    pub loan_id: u64,
+    pub borrower: Pubkey,
+    pub loan_amount: u64,
+    pub interest_amount: u64,
+    pub duration: i64,
+    pub request_expiry: Option<i64>,
+    pub timestamp: i64,
+}
+
+/// Emitted when a lender funds a loan request
+#[event]
+pub struct LoanFunded {
+    pub loan_info: Pubkey,
+    pub nft_mint: Pubkey,
+    pub loan_id: u64,
+    pub borrower: Pubkey,
+    pub lender: Pubkey,
+    pub loan_note_mint: Pubkey,
+    pub loan_amount: u64,
+    pub start_time: i64,
+}
+
+/// Emitted when a loan is repaid and the NFT is returned to the borrower
+#[event]
+pub struct LoanRepaid {
+    pub loan_info: Pubkey,
+    pub nft_mint: Pubkey,
+    pub loan_id: u64,
+    pub borrower: Pubkey,
+    /// The wallet that paid the repayment, which may differ from the borrower
+    pub payer: Pubkey,
+    /// The loan note holder who received the repayment
+    pub lender: Pubkey,
+    pub repayment_amount: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the loan note holder claims the NFT of a delinquent loan
+#[event]
+pub struct LoanDefaulted {
+    pub loan_info: Pubkey,
+    pub nft_mint: Pubkey,
+    pub loan_id: u64,
+    pub borrower: Pubkey,
+    /// The loan note holder who received the NFT
+    pub lender: Pubkey,
+    pub loan_amount: u64,
+    pub interest_amount: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when an unfunded loan request is closed and the NFT is returned to the borrower
+#[event]
+pub struct LoanCancelled {
+    pub loan_info: Pubkey,
+    pub nft_mint: Pubkey,
+    pub loan_id: u64,
+    pub borrower: Pubkey,
+    /// The wallet that closed the request; the borrower, or any cranker once the request expired
+    pub closed_by: Pubkey,
+    /// Indicates whether the request was closed because its request expiry passed
+    pub expired: bool,
+    pub timestamp: i64,
+}
+
+/// Emitted when an empty vault token account left behind by an earlier loan is closed
+#[event]
+pub struct VaultAccountSwept {
+    pub vault_nft_account: Pubkey,
+    pub nft_mint: Pubkey,
+    /// The borrower whose stake created the account, refunded its rent, or the admin for an account
+    /// left by a loan that recorded no payer
+    pub rent_recipient: Pubkey,
+    pub lamports: u64,
+    pub swept_by: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when an NFT owner requests redemption of the physical asset
+#[event]
+pub struct RedemptionRequested {
+    pub asset_redemption_info: Pubkey,
+    pub nft_mint: Pubkey,
+    pub owner: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when an NFT owner cancels their redemption request
+#[event]
+pub struct RedemptionCancelled {
+    pub asset_redemption_info: Pubkey,
+    pub nft_mint: Pubkey,
+    pub owner: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin fulfills a redemption request
+#[event]
+pub struct RedemptionFulfilled {
+    pub asset_redemption_info: Pubkey,
+    pub nft_mint: Pubkey,
+    pub owner: Pubkey,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a state account is upgraded to the current layout version
+#[event]
+pub struct AccountMigrated {
+    pub account: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+    pub payer: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin changes whether defaulted collateral claims pay royalties
+#[event]
+pub struct DefaultRoyaltiesConfigured {
+    pub enabled: bool,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a lender pays the NFT's royalty to its creators on claiming defaulted collateral
+#[event]
+pub struct DefaultRoyaltyPaid {
+    pub loan_info: Pubkey,
+    pub nft_mint: Pubkey,
+    pub loan_id: u64,
+    pub lender: Pubkey,
+    /// The loan principal the royalty was charged on
+    pub basis_amount: u64,
+    pub seller_fee_basis_points: u16,
+    pub royalty_amount: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin sets the wallet receiving primary sale proceeds
+#[event]
+pub struct TreasuryConfigured {
+    pub treasury: Pubkey,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin lists an NFT held by the sale escrow for its primary sale
+#[event]
+pub struct PrimaryListingCreated {
+    pub primary_listing: Pubkey,
+    pub nft_mint: Pubkey,
+    pub currency: SaleCurrency,
+    pub price: u64,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin withdraws an NFT from its primary sale
+#[event]
+pub struct PrimaryListingCancelled {
+    pub primary_listing: Pubkey,
+    pub nft_mint: Pubkey,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a buyer purchases an NFT listed for its primary sale
+#[event]
+pub struct ListedItemPurchased {
+    pub primary_listing: Pubkey,
+    pub nft_mint: Pubkey,
+    pub buyer: Pubkey,
+    pub treasury: Pubkey,
+    pub currency: SaleCurrency,
+    pub price: u64,
+    /// The share of the payment paid into the insurance reserve
+    pub insurance_fee: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a holder lists an NFT for resale on the marketplace
+#[event]
+pub struct NftListed {
+    pub listing: Pubkey,
+    pub nft_mint: Pubkey,
+    pub seller: Pubkey,
+    pub price: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a seller withdraws a marketplace listing
+#[event]
+pub struct ListingCancelled {
+    pub listing: Pubkey,
+    pub nft_mint: Pubkey,
+    pub seller: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a buyer purchases a listed NFT on the marketplace
+#[event]
+pub struct ListingSold {
+    pub listing: Pubkey,
+    pub nft_mint: Pubkey,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    pub price: u64,
+    /// The part of the price paid to the NFT's creators
+    pub royalty_amount: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a buyer makes an offer on an NFT
+#[event]
+pub struct OfferMade {
+    pub offer: Pubkey,
+    pub nft_mint: Pubkey,
+    pub buyer: Pubkey,
+    pub price: u64,
+    pub expiry: Option<i64>,
+    pub timestamp: i64,
+}
+
+/// Emitted when a buyer withdraws their offer
+#[event]
+pub struct OfferCancelled {
+    pub offer: Pubkey,
+    pub nft_mint: Pubkey,
+    pub buyer: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when an NFT holder accepts an offer
+#[event]
+pub struct OfferAccepted {
+    pub offer: Pubkey,
+    pub nft_mint: Pubkey,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    pub price: u64,
+    /// The part of the price paid to the NFT's creators
+    pub royalty_amount: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when an NFT is locked in a fraction vault and its fractions are minted
+#[event]
+pub struct NftFractionalized {
+    pub fraction_vault: Pubkey,
+    pub nft_mint: Pubkey,
+    pub fraction_mint: Pubkey,
+    pub curator: Pubkey,
+    pub fraction_supply: u64,
+    pub reserve_price: u64,
+    pub redemption_threshold_bps: u16,
+    pub timestamp: i64,
+}
+
+/// Emitted when a bid is placed in a buyout auction
+#[event]
+pub struct BuyoutBidPlaced {
+    pub fraction_vault: Pubkey,
+    pub nft_mint: Pubkey,
+    pub bidder: Pubkey,
+    pub bid: u64,
+    pub auction_end: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a fraction holder burns fractions for a share of the winning bid
+#[event]
+pub struct BuyoutProceedsClaimed {
+    pub fraction_vault: Pubkey,
+    pub nft_mint: Pubkey,
+    pub holder: Pubkey,
+    pub fractions_burned: u64,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the buyout winner takes the NFT out of the fraction vault
+#[event]
+pub struct BuyoutNftClaimed {
+    pub fraction_vault: Pubkey,
+    pub nft_mint: Pubkey,
+    pub winner: Pubkey,
+    pub price: u64,
+    /// True if the NFT was sent straight into an asset redemption request
+    pub redemption_requested: bool,
+    pub timestamp: i64,
+}
+
+/// Emitted when fraction holders controlling the redemption threshold redeem a fractionalized NFT
+#[event]
+pub struct FractionalizedNftRedeemed {
+    pub fraction_vault: Pubkey,
+    pub nft_mint: Pubkey,
+    pub recipient: Pubkey,
+    pub fractions_burned: u64,
+    /// The price paid for the fractions left outstanding, claimable by their holders
+    pub buyout_amount: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when an owner moves an NFT into rental escrow and lists its rental terms
+#[event]
+pub struct NftListedForRent {
+    pub rental: Pubkey,
+    pub nft_mint: Pubkey,
+    pub owner: Pubkey,
+    pub daily_fee: u64,
+    pub max_days: u16,
+    pub timestamp: i64,
+}
+
+/// Emitted when a renter pays for the usage rights of an NFT
+#[event]
+pub struct NftRented {
+    pub rental: Pubkey,
+    pub nft_mint: Pubkey,
+    pub owner: Pubkey,
+    pub renter: Pubkey,
+    pub days: u16,
+    pub fee: u64,
+    pub rental_end: i64,
+    /// The share of the payment paid into the insurance reserve
+    pub insurance_fee: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when an NFT leaves rental escrow and returns to its owner
+#[event]
+pub struct RentalEnded {
+    pub rental: Pubkey,
+    pub nft_mint: Pubkey,
+    pub owner: Pubkey,
+    /// The renter whose term ended, or None if the owner withdrew the NFT before it was rented
+    pub renter: Option<Pubkey>,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin sets the insurance reserve's share of fees
+#[event]
+pub struct InsuranceFeeConfigured {
+    pub insurance_fee_bps: u16,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when lamports are deposited into the insurance reserve
+#[event]
+pub struct InsuranceReserveFunded {
+    pub funder: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin records the appraised value of an NFT's physical item
+#[event]
+pub struct AssetAppraised {
+    pub asset_appraisal: Pubkey,
+    pub nft_mint: Pubkey,
+    pub appraised_value: u64,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin declares an NFT's physical item lost or damaged, freezing the NFT
+#[event]
+pub struct AssetImpaired {
+    pub asset_impairment: Pubkey,
+    pub nft_mint: Pubkey,
+    pub payout: u64,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the holder of an impaired NFT burns it for the insurance payout
+#[event]
+pub struct InsurancePayoutClaimed {
+    pub asset_impairment: Pubkey,
+    pub nft_mint: Pubkey,
+    /// The wallet paid, or the fraction vault holding the payout for its fraction holders
+    pub holder: Pubkey,
+    pub payout: u64,
+    pub timestamp: i64,
+}
@@ -1,6 +1,9 @@
 use {
     crate::{
-        constants::admin_constants::get_admin_account_pubkey, errors, state::CollectionCounter,
+        constants::admin_constants::get_admin_account_pubkey,
+        errors,
+        events::CollectionCreated,
+        state::{AccountHeader, CollectionCounter},
     },
     anchor_lang::prelude::*,
     anchor_spl::{
@@ -93,6 +96,7 @@ pub struct CreateCollection<'info> {
 
 pub fn handle(ctx: Context<CreateCollection>) -> Result<()> {
     // Initialize collection counter
+    ctx.accounts.collection_counter.header = AccountHeader::new(CollectionCounter::VERSION);
     ctx.accounts.collection_counter.count = 0;
     ctx.accounts.collection_counter.collection_mint = ctx.accounts.mint.key();
 
@@ -184,5 +188,11 @@ pub fn handle(ctx: Context<CreateCollection>) -> Result<()> {
         ],
     )?;
 
+    emit!(CollectionCreated {
+        collection_mint: ctx.accounts.mint.key(),
+        authority: ctx.accounts.payer.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
     Ok(())
 }
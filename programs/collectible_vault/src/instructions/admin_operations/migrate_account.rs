@@ -0,0 +1,107 @@
+use {
+    crate::{
+        constants::admin_constants::get_admin_account_pubkey,
+        errors,
+        events::AccountMigrated,
+        state::{AssetRedemptionInfo, CollectionCounter, LoanInfo, VersionedAccount},
+    },
+    anchor_lang::{prelude::*, Discriminator},
+};
+
+/**
+ * Allows the admin to upgrade any versioned state account to its current layout.
+ * The account type is identified by its discriminator, the account is reallocated in place,
+ * and the admin covers any additional rent.
+ */
+#[derive(Accounts)]
+pub struct MigrateAccount<'info> {
+    /// CHECK: Any program-owned state account; its type and stored version are validated before it is rewritten.
+    #[account(mut, owner = crate::ID)]
+    pub account: UncheckedAccount<'info>,
+
+    #[account(mut, constraint = payer.key() == get_admin_account_pubkey() @ errors::ErrorCode::UnauthorizedTransactionSigner)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle(ctx: Context<MigrateAccount>) -> Result<()> {
+    let discriminator: [u8; 8] = {
+        let data = ctx.accounts.account.try_borrow_data()?;
+        data.get(..8)
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or(ErrorCode::AccountDiscriminatorNotFound)?
+    };
+
+    let account = ctx.accounts.account.to_account_info();
+    let payer = ctx.accounts.payer.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+    match discriminator {
+        LoanInfo::DISCRIMINATOR => migrate_in_place::<LoanInfo>(&account, &payer, &system_program),
+        CollectionCounter::DISCRIMINATOR => {
+            migrate_in_place::<CollectionCounter>(&account, &payer, &system_program)
+        }
+        AssetRedemptionInfo::DISCRIMINATOR => {
+            migrate_in_place::<AssetRedemptionInfo>(&account, &payer, &system_program)
+        }
+        _ => err!(errors::ErrorCode::UnsupportedAccountType),
+    }
+}
+
+/**
+ * Upgrades a state account from its stored version to the current layout: decodes it, tops up
+ * rent from the payer, reallocates it to the current size and rewrites it.
+ */
+pub fn migrate_in_place<'info, T: VersionedAccount>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    // Decode the stored layout, rejecting accounts that are already current
+    let (from_version, upgraded) = {
+        let data = account.try_borrow_data()?;
+        let version = T::stored_version(&data)?;
+        require!(
+            version != T::VERSION,
+            errors::ErrorCode::AccountAlreadyMigrated
+        );
+        (version, T::upgrade_from(version, &data[8..])?)
+    };
+
+    // Top up rent for the current layout
+    let required_lamports = Rent::get()?.minimum_balance(T::SPACE);
+    let shortfall = required_lamports.saturating_sub(account.lamports());
+    if shortfall > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                anchor_lang::system_program::Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+
+    // Grow the account and rewrite it in the current layout
+    account.realloc(T::SPACE, false)?;
+    upgraded.try_serialize(&mut &mut account.try_borrow_mut_data()?[..])?;
+
+    msg!(
+        "Migrated account {} from version {} to version {}",
+        account.key(),
+        from_version,
+        T::VERSION
+    );
+
+    emit!(AccountMigrated {
+        account: account.key(),
+        from_version,
+        to_version: T::VERSION,
+        payer: payer.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
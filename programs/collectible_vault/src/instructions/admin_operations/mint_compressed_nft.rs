@@ -0,0 +1,159 @@
+use {
+    crate::{
+        constants::{
+            admin_constants::get_admin_account_pubkey, pda_constants::VAULT_COLLECTION_COUNTER_SEED,
+        },
+        errors,
+        events::CompressedNftMinted,
+        instructions::compressed_nft::compressed_asset_id,
+        state::CollectionCounter,
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token::Mint,
+    mpl_bubblegum::{
+        accounts::TreeConfig,
+        instructions::{
+            MintToCollectionV1Cpi, MintToCollectionV1CpiAccounts, MintToCollectionV1InstructionArgs,
+        },
+        types::{Collection, Creator, MetadataArgs, TokenProgramVersion, TokenStandard},
+    },
+};
+
+/**
+ * Allows the admin to mint a new compressed NFT into a Bubblegum Merkle tree.
+ * A compressed NFT is a leaf in the tree rather than a mint with its own accounts, so minting
+ * costs no rent. It is verified as a member of the vault collection like NFTs minted by `mint_nft`.
+ * The admin must be the tree's creator or delegate; the tree is created with Bubblegum directly.
+ */
+#[derive(Accounts)]
+pub struct MintCompressedNft<'info> {
+    #[account(mut, constraint = payer.key() == get_admin_account_pubkey() @ errors::ErrorCode::UnauthorizedTransactionSigner)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_COLLECTION_COUNTER_SEED],
+        bump,
+        constraint = collection_counter.collection_mint == collection_mint.key() @ errors::ErrorCode::CollectionMintDoesNotMatch
+    )]
+    pub collection_counter: Account<'info, CollectionCounter>,
+
+    /// CHECK: Bubblegum tree config of the Merkle tree, read for the new leaf's nonce
+    #[account(
+        mut,
+        seeds = [merkle_tree.key().as_ref()],
+        bump,
+        seeds::program = mpl_bubblegum::ID,
+        owner = mpl_bubblegum::ID,
+    )]
+    pub tree_config: UncheckedAccount<'info>,
+
+    /// CHECK: Account compression validates the Merkle tree
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// CHECK: Just used as the owner of the new leaf
+    pub owner: UncheckedAccount<'info>,
+
+    /// CHECK: Collection mint
+    pub collection_mint: Account<'info, Mint>,
+
+    /// CHECK: Collection metadata account
+    #[account(mut)]
+    pub collection_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Collection master edition
+    pub collection_master_edition: UncheckedAccount<'info>,
+
+    /// CHECK: Bubblegum validates its collection CPI signer
+    pub bubblegum_signer: UncheckedAccount<'info>,
+
+    /// CHECK: SPL Noop program ID check
+    #[account(address = mpl_bubblegum::programs::SPL_NOOP_ID)]
+    pub log_wrapper: UncheckedAccount<'info>,
+
+    /// CHECK: SPL Account Compression program ID check
+    #[account(address = mpl_bubblegum::programs::SPL_ACCOUNT_COMPRESSION_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+
+    /// CHECK: Metaplex program ID check
+    #[account(address = mpl_token_metadata::ID)]
+    pub token_metadata_program: UncheckedAccount<'info>,
+
+    /// CHECK: Bubblegum program ID check
+    #[account(address = mpl_bubblegum::ID)]
+    pub bubblegum_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle(ctx: Context<MintCompressedNft>, product_detail_uri: String) -> Result<()> {
+    let collection_counter = &mut ctx.accounts.collection_counter;
+    collection_counter.count += 1;
+    let item_number = collection_counter.count;
+
+    // The new leaf takes the tree's next nonce, which determines its asset id
+    let nonce = TreeConfig::from_bytes(&ctx.accounts.tree_config.try_borrow_data()?)
+        .map_err(|_| error!(ErrorCode::AccountDidNotDeserialize))?
+        .num_minted;
+
+    let metadata = MetadataArgs {
+        name: format!("Ascendry Item #{}", item_number),
+        symbol: "ASC".to_string(),
+        uri: product_detail_uri.clone(),
+        seller_fee_basis_points: 500,
+        primary_sale_happened: false,
+        is_mutable: true,
+        edition_nonce: None,
+        token_standard: Some(TokenStandard::NonFungible),
+        collection: Some(Collection {
+            verified: false,
+            key: ctx.accounts.collection_mint.key(),
+        }),
+        uses: None,
+        token_program_version: TokenProgramVersion::Original,
+        creators: vec![Creator {
+            address: ctx.accounts.payer.key(),
+            verified: false,
+            share: 100,
+        }],
+    };
+
+    // Bubblegum verifies the collection with the admin as collection authority
+    MintToCollectionV1Cpi::new(
+        &ctx.accounts.bubblegum_program.to_account_info(),
+        MintToCollectionV1CpiAccounts {
+            tree_config: &ctx.accounts.tree_config.to_account_info(),
+            leaf_owner: &ctx.accounts.owner.to_account_info(),
+            leaf_delegate: &ctx.accounts.owner.to_account_info(),
+            merkle_tree: &ctx.accounts.merkle_tree.to_account_info(),
+            payer: &ctx.accounts.payer.to_account_info(),
+            tree_creator_or_delegate: &ctx.accounts.payer.to_account_info(),
+            collection_authority: &ctx.accounts.payer.to_account_info(),
+            collection_authority_record_pda: None,
+            collection_mint: &ctx.accounts.collection_mint.to_account_info(),
+            collection_metadata: &ctx.accounts.collection_metadata.to_account_info(),
+            collection_edition: &ctx.accounts.collection_master_edition.to_account_info(),
+            bubblegum_signer: &ctx.accounts.bubblegum_signer.to_account_info(),
+            log_wrapper: &ctx.accounts.log_wrapper.to_account_info(),
+            compression_program: &ctx.accounts.compression_program.to_account_info(),
+            token_metadata_program: &ctx.accounts.token_metadata_program.to_account_info(),
+            system_program: &ctx.accounts.system_program.to_account_info(),
+        },
+        MintToCollectionV1InstructionArgs { metadata },
+    )
+    .invoke()?;
+
+    emit!(CompressedNftMinted {
+        asset_id: compressed_asset_id(&ctx.accounts.merkle_tree.key(), nonce),
+        merkle_tree: ctx.accounts.merkle_tree.key(),
+        nonce,
+        collection_mint: ctx.accounts.collection_mint.key(),
+        owner: ctx.accounts.owner.key(),
+        item_number,
+        product_detail_uri,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use {
+    crate::{
+        constants::{
+            admin_constants::get_admin_account_pubkey, pda_constants::VAULT_COLLECTION_COUNTER_SEED,
+        },
+        errors,
+        events::CoreAssetMinted,
+        instructions::core_asset::{create_core_asset, MplCore},
+        state::CollectionCounter,
+    },
+    anchor_lang::prelude::*,
+};
+
+/**
+ * Allows the admin to mint a new Metaplex Core asset.
+ * Core assets live in a single account, so they cost less to mint than a Token Metadata NFT with
+ * its mint, metadata and edition. Assets share item numbers with the vault collection.
+ */
+#[derive(Accounts)]
+pub struct MintCoreAsset<'info> {
+    // The new asset account, created by Metaplex Core
+    #[account(mut)]
+    pub asset: Signer<'info>,
+
+    #[account(mut, constraint = payer.key() == get_admin_account_pubkey() @ errors::ErrorCode::UnauthorizedTransactionSigner)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_COLLECTION_COUNTER_SEED],
+        bump,
+    )]
+    pub collection_counter: Account<'info, CollectionCounter>,
+
+    /// CHECK: Just used as the owner of the new asset
+    pub owner: UncheckedAccount<'info>,
+
+    pub core_program: Program<'info, MplCore>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle(ctx: Context<MintCoreAsset>, product_detail_uri: String) -> Result<()> {
+    let collection_counter = &mut ctx.accounts.collection_counter;
+    collection_counter.count += 1;
+    let item_number = collection_counter.count;
+
+    create_core_asset(
+        &ctx.accounts.asset.to_account_info(),
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.owner.to_account_info(),
+        &ctx.accounts.core_program.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        format!("Ascendry Item #{}", item_number),
+        product_detail_uri.clone(),
+    )?;
+
+    emit!(CoreAssetMinted {
+        asset: ctx.accounts.asset.key(),
+        owner: ctx.accounts.owner.key(),
+        item_number,
+        product_detail_uri,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -4,9 +4,11 @@ use {
             admin_constants::get_admin_account_pubkey, pda_constants::VAULT_COLLECTION_COUNTER_SEED,
         },
         errors,
+        events::NftMinted,
+        instructions::nft_transfer::NftStandard,
         state::CollectionCounter,
     },
-    anchor_lang::prelude::*,
+    anchor_lang::{prelude::*, solana_program::sysvar},
     anchor_spl::{
         associated_token::AssociatedToken,
         token::{Mint, Token, TokenAccount},
@@ -14,15 +16,17 @@ use {
     mpl_token_metadata::{
         instructions::{
             CreateMasterEditionV3, CreateMasterEditionV3InstructionArgs, CreateMetadataAccountV3,
-            CreateMetadataAccountV3InstructionArgs, VerifySizedCollectionItem,
+            CreateMetadataAccountV3InstructionArgs, CreateV1, CreateV1InstructionArgs, MintV1,
+            MintV1InstructionArgs, VerifyCollectionV1, VerifySizedCollectionItem,
         },
-        types::{Collection, Creator, DataV2},
+        types::{Collection, Creator, DataV2, PrintSupply, TokenStandard},
     },
 };
 
 /**
  * Allows the admin to mint a new NFT.
- * This instruction creates a new NFT with the provided product detail URI.
+ * This instruction creates a new NFT with the provided product detail URI, either as a classic NFT
+ * or as a programmable NFT whose transfers are enforced by an optional rule set.
  */
 #[derive(Accounts)]
 pub struct MintNFT<'info> {
@@ -88,18 +92,31 @@ pub struct MintNFT<'info> {
         associated_token::authority = owner,
     )]
     pub owner_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Token record of the owner's token account, created by Metaplex for programmable NFTs
+    #[account(mut)]
+    pub token_record: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Instructions sysvar required by Metaplex for programmable NFTs
+    #[account(address = sysvar::instructions::ID)]
+    pub sysvar_instructions: Option<UncheckedAccount<'info>>,
 }
 
-pub fn handle(ctx: Context<MintNFT>, product_detail_uri: String) -> Result<()> {
+pub fn handle(
+    ctx: Context<MintNFT>,
+    product_detail_uri: String,
+    standard: NftStandard,
+) -> Result<()> {
     let collection_counter = &mut ctx.accounts.collection_counter;
     collection_counter.count += 1;
+    let item_number = collection_counter.count;
 
-    let name: String = format!("Ascendry Item #{}", collection_counter.count);
+    let name: String = format!("Ascendry Item #{}", item_number);
 
     let data = DataV2 {
-        name: name,
+        name,
         symbol: "ASC".to_string(),
-        uri: product_detail_uri,
+        uri: product_detail_uri.clone(),
         seller_fee_basis_points: 500,
         creators: Some(vec![Creator {
             address: ctx.accounts.payer.key(),
@@ -113,22 +130,47 @@ pub fn handle(ctx: Context<MintNFT>, product_detail_uri: String) -> Result<()> {
         uses: None,
     };
 
+    match standard {
+        NftStandard::NonFungible => mint_non_fungible(ctx.accounts, data)?,
+        NftStandard::ProgrammableNonFungible { rule_set } => {
+            mint_programmable(ctx.accounts, data, rule_set)?
+        }
+    }
+
+    emit!(NftMinted {
+        nft_mint: ctx.accounts.mint.key(),
+        collection_mint: ctx.accounts.collection_mint.key(),
+        owner: ctx.accounts.owner.key(),
+        item_number,
+        product_detail_uri,
+        standard,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/**
+ * Creates the metadata and master edition of a classic NFT, mints it to the owner and verifies
+ * it as a member of the collection.
+ */
+fn mint_non_fungible(accounts: &MintNFT, data: DataV2) -> Result<()> {
     // Create Instruction Arguments
     let args = CreateMetadataAccountV3InstructionArgs {
-        data: data.clone(),
+        data,
         is_mutable: false,
         collection_details: None,
     };
 
     // Create metadata
     let metadata_ix = CreateMetadataAccountV3 {
-        metadata: ctx.accounts.metadata.key(),
-        mint: ctx.accounts.mint.key(),
-        mint_authority: ctx.accounts.payer.key(),
-        update_authority: (ctx.accounts.payer.key(), true),
-        payer: ctx.accounts.payer.key(),
-        system_program: ctx.accounts.system_program.key(),
-        rent: Some(ctx.accounts.rent.key()),
+        metadata: accounts.metadata.key(),
+        mint: accounts.mint.key(),
+        mint_authority: accounts.payer.key(),
+        update_authority: (accounts.payer.key(), true),
+        payer: accounts.payer.key(),
+        system_program: accounts.system_program.key(),
+        rent: Some(accounts.rent.key()),
     }
     .instruction(args);
 
@@ -136,23 +178,23 @@ pub fn handle(ctx: Context<MintNFT>, product_detail_uri: String) -> Result<()> {
     anchor_lang::solana_program::program::invoke(
         &metadata_ix,
         &[
-            ctx.accounts.metadata.to_account_info(),
-            ctx.accounts.mint.to_account_info(),
-            ctx.accounts.payer.to_account_info(),
-            ctx.accounts.system_program.to_account_info(),
-            ctx.accounts.rent.to_account_info(),
-            ctx.accounts.token_metadata_program.to_account_info(),
+            accounts.metadata.to_account_info(),
+            accounts.mint.to_account_info(),
+            accounts.payer.to_account_info(),
+            accounts.system_program.to_account_info(),
+            accounts.rent.to_account_info(),
+            accounts.token_metadata_program.to_account_info(),
         ],
     )?;
 
     // Mint token to the owner's token account instead of payer's
     anchor_spl::token::mint_to(
         CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
+            accounts.token_program.to_account_info(),
             anchor_spl::token::MintTo {
-                mint: ctx.accounts.mint.to_account_info(),
-                to: ctx.accounts.owner_token_account.to_account_info(), // Changed to owner's token account
-                authority: ctx.accounts.payer.to_account_info(),
+                mint: accounts.mint.to_account_info(),
+                to: accounts.owner_token_account.to_account_info(), // Changed to owner's token account
+                authority: accounts.payer.to_account_info(),
             },
         ),
         1,
@@ -160,15 +202,15 @@ pub fn handle(ctx: Context<MintNFT>, product_detail_uri: String) -> Result<()> {
 
     // Create master edition
     let master_edition_ix = CreateMasterEditionV3 {
-        edition: ctx.accounts.master_edition.key(),
-        mint: ctx.accounts.mint.key(),
-        update_authority: ctx.accounts.payer.key(),
-        mint_authority: ctx.accounts.payer.key(),
-        payer: ctx.accounts.payer.key(),
-        metadata: ctx.accounts.metadata.key(),
-        token_program: ctx.accounts.token_program.key(),
-        system_program: ctx.accounts.system_program.key(),
-        rent: Some(ctx.accounts.rent.key()),
+        edition: accounts.master_edition.key(),
+        mint: accounts.mint.key(),
+        update_authority: accounts.payer.key(),
+        mint_authority: accounts.payer.key(),
+        payer: accounts.payer.key(),
+        metadata: accounts.metadata.key(),
+        token_program: accounts.token_program.key(),
+        system_program: accounts.system_program.key(),
+        rent: Some(accounts.rent.key()),
     }
     .instruction(CreateMasterEditionV3InstructionArgs {
         max_supply: Some(0), // 0 means unique (non-fungible)
@@ -178,25 +220,25 @@ pub fn handle(ctx: Context<MintNFT>, product_detail_uri: String) -> Result<()> {
     anchor_lang::solana_program::program::invoke(
         &master_edition_ix,
         &[
-            ctx.accounts.master_edition.to_account_info(),
-            ctx.accounts.mint.to_account_info(),
-            ctx.accounts.payer.to_account_info(),
-            ctx.accounts.metadata.to_account_info(),
-            ctx.accounts.token_program.to_account_info(),
-            ctx.accounts.system_program.to_account_info(),
-            ctx.accounts.rent.to_account_info(),
-            ctx.accounts.token_metadata_program.to_account_info(),
+            accounts.master_edition.to_account_info(),
+            accounts.mint.to_account_info(),
+            accounts.payer.to_account_info(),
+            accounts.metadata.to_account_info(),
+            accounts.token_program.to_account_info(),
+            accounts.system_program.to_account_info(),
+            accounts.rent.to_account_info(),
+            accounts.token_metadata_program.to_account_info(),
         ],
     )?;
 
     // Now verify the collection membership
     let verify_ix = VerifySizedCollectionItem {
-        metadata: ctx.accounts.metadata.key(),
-        collection_authority: ctx.accounts.payer.key(),
-        payer: ctx.accounts.payer.key(),
-        collection_mint: ctx.accounts.collection_mint.key(),
-        collection: ctx.accounts.collection_metadata.key(),
-        collection_master_edition_account: ctx.accounts.collection_master_edition.key(),
+        metadata: accounts.metadata.key(),
+        collection_authority: accounts.payer.key(),
+        payer: accounts.payer.key(),
+        collection_mint: accounts.collection_mint.key(),
+        collection: accounts.collection_metadata.key(),
+        collection_master_edition_account: accounts.collection_master_edition.key(),
         collection_authority_record: None,
     }
     .instruction();
@@ -205,13 +247,139 @@ pub fn handle(ctx: Context<MintNFT>, product_detail_uri: String) -> Result<()> {
     anchor_lang::solana_program::program::invoke(
         &verify_ix,
         &[
-            ctx.accounts.metadata.to_account_info(),
-            ctx.accounts.payer.to_account_info(),
-            ctx.accounts.payer.to_account_info(), // Collection authority is the payer
-            ctx.accounts.collection_mint.to_account_info(),
-            ctx.accounts.collection_metadata.to_account_info(),
-            ctx.accounts.collection_master_edition.to_account_info(),
-            ctx.accounts.token_metadata_program.to_account_info(),
+            accounts.metadata.to_account_info(),
+            accounts.payer.to_account_info(),
+            accounts.payer.to_account_info(), // Collection authority is the payer
+            accounts.collection_mint.to_account_info(),
+            accounts.collection_metadata.to_account_info(),
+            accounts.collection_master_edition.to_account_info(),
+            accounts.token_metadata_program.to_account_info(),
+        ],
+    )?;
+
+    Ok(())
+}
+
+/**
+ * Creates a programmable NFT with Token Metadata `CreateV1`, mints it to the owner with `MintV1`,
+ * which freezes the owner's token account and creates its token record, and verifies it as a
+ * member of the collection.
+ */
+fn mint_programmable(accounts: &MintNFT, data: DataV2, rule_set: Option<Pubkey>) -> Result<()> {
+    let (Some(token_record), Some(sysvar_instructions)) =
+        (&accounts.token_record, &accounts.sysvar_instructions)
+    else {
+        return err!(errors::ErrorCode::MissingProgrammableAccounts);
+    };
+
+    // Create metadata and master edition
+    let create_ix = CreateV1 {
+        metadata: accounts.metadata.key(),
+        master_edition: Some(accounts.master_edition.key()),
+        mint: (accounts.mint.key(), false),
+        authority: accounts.payer.key(),
+        payer: accounts.payer.key(),
+        update_authority: (accounts.payer.key(), true),
+        system_program: accounts.system_program.key(),
+        sysvar_instructions: sysvar_instructions.key(),
+        spl_token_program: Some(accounts.token_program.key()),
+    }
+    .instruction(CreateV1InstructionArgs {
+        name: data.name,
+        symbol: data.symbol,
+        uri: data.uri,
+        seller_fee_basis_points: data.seller_fee_basis_points,
+        creators: data.creators,
+        primary_sale_happened: false,
+        is_mutable: false,
+        token_standard: TokenStandard::ProgrammableNonFungible,
+        collection: data.collection,
+        uses: None,
+        collection_details: None,
+        rule_set,
+        decimals: Some(0),
+        print_supply: Some(PrintSupply::Zero),
+    });
+
+    anchor_lang::solana_program::program::invoke(
+        &create_ix,
+        &[
+            accounts.metadata.to_account_info(),
+            accounts.master_edition.to_account_info(),
+            accounts.mint.to_account_info(),
+            accounts.payer.to_account_info(),
+            accounts.system_program.to_account_info(),
+            sysvar_instructions.to_account_info(),
+            accounts.token_program.to_account_info(),
+            accounts.token_metadata_program.to_account_info(),
+        ],
+    )?;
+
+    // Mint the token to the owner's token account, creating its token record
+    let mint_ix = MintV1 {
+        token: accounts.owner_token_account.key(),
+        token_owner: Some(accounts.owner.key()),
+        metadata: accounts.metadata.key(),
+        master_edition: Some(accounts.master_edition.key()),
+        token_record: Some(token_record.key()),
+        mint: accounts.mint.key(),
+        authority: accounts.payer.key(),
+        delegate_record: None,
+        payer: accounts.payer.key(),
+        system_program: accounts.system_program.key(),
+        sysvar_instructions: sysvar_instructions.key(),
+        spl_token_program: accounts.token_program.key(),
+        spl_ata_program: accounts.associated_token_program.key(),
+        authorization_rules_program: None,
+        authorization_rules: None,
+    }
+    .instruction(MintV1InstructionArgs {
+        amount: 1,
+        authorization_data: None,
+    });
+
+    anchor_lang::solana_program::program::invoke(
+        &mint_ix,
+        &[
+            accounts.owner_token_account.to_account_info(),
+            accounts.owner.to_account_info(),
+            accounts.metadata.to_account_info(),
+            accounts.master_edition.to_account_info(),
+            token_record.to_account_info(),
+            accounts.mint.to_account_info(),
+            accounts.payer.to_account_info(),
+            accounts.system_program.to_account_info(),
+            sysvar_instructions.to_account_info(),
+            accounts.token_program.to_account_info(),
+            accounts.associated_token_program.to_account_info(),
+            accounts.token_metadata_program.to_account_info(),
+        ],
+    )?;
+
+    // Verify the collection membership
+    let verify_ix = VerifyCollectionV1 {
+        authority: accounts.payer.key(),
+        delegate_record: None,
+        metadata: accounts.metadata.key(),
+        collection_mint: accounts.collection_mint.key(),
+        collection_metadata: Some(accounts.collection_metadata.key()),
+        collection_master_edition: Some(accounts.collection_master_edition.key()),
+        system_program: accounts.system_program.key(),
+        sysvar_instructions: sysvar_instructions.key(),
+    }
+    .instruction();
+
+    anchor_lang::solana_program::program::invoke(
+        &verify_ix,
+        &[
+            accounts.payer.to_account_info(),
+            accounts.metadata.to_account_info(),
+            accounts.collection_mint.to_account_info(),
+            accounts.collection_metadata.to_account_info(),
+            accounts.collection_master_edition.to_account_info(),
+            accounts.system_program.to_account_info(),
+            sysvar_instructions.to_account_info(),
+            accounts.token_metadata_program.to_account_info(),
         ],
     )?;
 
@@ -1,2 +1,8 @@
 pub mod create_collection;
+pub mod migrate_account;
+pub mod mint_compressed_nft;
+pub mod mint_core_asset;
 pub mod mint_nft;
+pub mod set_default_royalties;
+pub mod set_insurance_fee;
+pub mod set_treasury;
@@ -0,0 +1,44 @@
+use {
+    crate::{
+        constants::{admin_constants::get_admin_account_pubkey, pda_constants::VAULT_CONFIG_SEED},
+        errors,
+        events::DefaultRoyaltiesConfigured,
+        state::{AccountHeader, VaultConfig},
+    },
+    anchor_lang::prelude::*,
+};
+
+/**
+ * Allows the admin to turn royalty payments on defaulted collateral claims on or off.
+ * This instruction creates the vault config account the first time it is used.
+ */
+#[derive(Accounts)]
+pub struct SetDefaultRoyalties<'info> {
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = VaultConfig::INIT_SPACE,
+        seeds = [VAULT_CONFIG_SEED],
+        bump
+    )]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    #[account(mut, constraint = payer.key() == get_admin_account_pubkey() @ errors::ErrorCode::UnauthorizedTransactionSigner)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle(ctx: Context<SetDefaultRoyalties>, enabled: bool) -> Result<()> {
+    let vault_config = &mut ctx.accounts.vault_config;
+    vault_config.header = AccountHeader::new(VaultConfig::VERSION);
+    vault_config.default_royalties_enabled = enabled;
+
+    emit!(DefaultRoyaltiesConfigured {
+        enabled,
+        admin: ctx.accounts.payer.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use {
+    crate::{
+        constants::{
+            admin_constants::get_admin_account_pubkey,
+            pda_constants::{INSURANCE_RESERVE_SEED, VAULT_CONFIG_SEED},
+        },
+        errors,
+        events::InsuranceFeeConfigured,
+        state::{AccountHeader, InsuranceReserve, VaultConfig},
+    },
+    anchor_lang::prelude::*,
+};
+
+/**
+ * Allows the admin to set the share of SOL primary sale proceeds and rental fees paid into the
+ * insurance reserve.
+ * This instruction creates the vault config and insurance reserve accounts the first time it is used,
+ * so the reserve exists before any fee is paid into it.
+ */
+#[derive(Accounts)]
+pub struct SetInsuranceFee<'info> {
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = VaultConfig::INIT_SPACE,
+        seeds = [VAULT_CONFIG_SEED],
+        bump
+    )]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    // PDA holding the lamports that pay out insurance claims
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = InsuranceReserve::INIT_SPACE,
+        seeds = [INSURANCE_RESERVE_SEED],
+        bump
+    )]
+    pub insurance_reserve: Account<'info, InsuranceReserve>,
+
+    #[account(mut, constraint = payer.key() == get_admin_account_pubkey() @ errors::ErrorCode::UnauthorizedTransactionSigner)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle(ctx: Context<SetInsuranceFee>, insurance_fee_bps: u16) -> Result<()> {
+    require!(
+        insurance_fee_bps <= VaultConfig::MAX_INSURANCE_FEE_BPS,
+        errors::ErrorCode::InvalidInsuranceFee
+    );
+
+    let vault_config = &mut ctx.accounts.vault_config;
+    vault_config.header = AccountHeader::new(VaultConfig::VERSION);
+    vault_config.insurance_fee_bps = insurance_fee_bps;
+    ctx.accounts.insurance_reserve.header = AccountHeader::new(InsuranceReserve::VERSION);
+
+    emit!(InsuranceFeeConfigured {
+        insurance_fee_bps,
+        admin: ctx.accounts.payer.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
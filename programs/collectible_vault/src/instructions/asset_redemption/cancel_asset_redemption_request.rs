@@ -2,12 +2,14 @@ use {
     crate::{
         constants::pda_constants::{ASSET_REDEMPTION_INFO_SEED, ASSET_REDEMPTION_VAULT_SEED},
         errors,
+        events::RedemptionCancelled,
+        instructions::nft_transfer::*,
         state::AssetRedemptionInfo,
     },
     anchor_lang::prelude::*,
     anchor_spl::{
         associated_token::AssociatedToken,
-        token::{Mint, Token, TokenAccount},
+        token_interface::{Mint, TokenAccount, TokenInterface},
     },
 };
 
@@ -28,21 +30,23 @@ pub struct CancelAssetRedemptionRequest<'info> {
     )]
     pub asset_redemption_info: Account<'info, AssetRedemptionInfo>,
 
-    pub nft_mint: Account<'info, Mint>,
+    pub nft_mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
         associated_token::mint = nft_mint,
         associated_token::authority = owner,
+        associated_token::token_program = token_program,
     )]
-    pub owner_nft_account: Account<'info, TokenAccount>,
+    pub owner_nft_account: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         mut,
         associated_token::mint = nft_mint,
         associated_token::authority = asset_redemption_vault,
+        associated_token::token_program = token_program,
     )]
-    pub asset_redemption_nft_account: Account<'info, TokenAccount>,
+    pub asset_redemption_nft_account: InterfaceAccount<'info, TokenAccount>,
 
     /// CHECK: PDA for asset redemption authority
     #[account(
@@ -54,27 +58,35 @@ pub struct CancelAssetRedemptionRequest<'info> {
     #[account(mut)]
     pub owner: Signer<'info>,
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
+
+    // Token Metadata accounts, required only when the NFT is a programmable NFT
+    pub programmable: ProgrammableTransfer<'info>,
 }
 
-pub fn handle(ctx: Context<CancelAssetRedemptionRequest>) -> Result<()> {
+pub fn handle<'info>(
+    ctx: Context<'_, '_, '_, 'info, CancelAssetRedemptionRequest<'info>>,
+) -> Result<()> {
     let asset_redemption_bump = ctx.bumps.asset_redemption_vault;
     let seeds = &[ASSET_REDEMPTION_VAULT_SEED, &[asset_redemption_bump]];
     let signer = &[&seeds[..]];
 
     // Transfer NFT back to owner
-    anchor_spl::token::transfer(
-        CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            anchor_spl::token::Transfer {
-                from: ctx.accounts.asset_redemption_nft_account.to_account_info(),
-                to: ctx.accounts.owner_nft_account.to_account_info(),
-                authority: ctx.accounts.asset_redemption_vault.to_account_info(),
-            },
-            signer,
-        ),
-        1,
+    let programmable = ctx.accounts.programmable.transfer(
+        NftTransfer {
+            nft_mint: &ctx.accounts.nft_mint.to_account_info(),
+            source: &ctx.accounts.asset_redemption_nft_account.to_account_info(),
+            source_owner: &ctx.accounts.asset_redemption_vault.to_account_info(),
+            destination: &ctx.accounts.owner_nft_account.to_account_info(),
+            destination_owner: &ctx.accounts.owner.to_account_info(),
+            payer: &ctx.accounts.owner.to_account_info(),
+            token_program: &ctx.accounts.token_program.to_account_info(),
+            associated_token_program: &ctx.accounts.associated_token_program.to_account_info(),
+            system_program: &ctx.accounts.system_program.to_account_info(),
+            additional_accounts: ctx.remaining_accounts,
+        },
+        signer,
     )?;
 
     msg!(
@@ -83,17 +95,23 @@ pub fn handle(ctx: Context<CancelAssetRedemptionRequest>) -> Result<()> {
     );
 
     // Close the vault's token account
-    anchor_spl::token::close_account(CpiContext::new_with_signer(
-        ctx.accounts.token_program.to_account_info(),
-        anchor_spl::token::CloseAccount {
-            account: ctx.accounts.asset_redemption_nft_account.to_account_info(),
-            destination: ctx.accounts.owner.to_account_info(),
-            authority: ctx.accounts.asset_redemption_vault.to_account_info(),
-        },
+    close_emptied_nft_account(
+        &mut ctx.accounts.asset_redemption_nft_account,
+        programmable,
+        &ctx.accounts.owner.to_account_info(),
+        &ctx.accounts.asset_redemption_vault.to_account_info(),
+        &ctx.accounts.token_program.to_account_info(),
         signer,
-    ))?;
+    )?;
 
     msg!("Closed asset redemption account");
 
+    emit!(RedemptionCancelled {
+        asset_redemption_info: ctx.accounts.asset_redemption_info.key(),
+        nft_mint: ctx.accounts.nft_mint.key(),
+        owner: ctx.accounts.owner.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
     Ok(())
 }
@@ -0,0 +1,96 @@
+use {
+    crate::{
+        constants::pda_constants::{ASSET_REDEMPTION_INFO_SEED, ASSET_REDEMPTION_VAULT_SEED},
+        errors,
+        events::RedemptionCancelled,
+        instructions::compressed_nft::*,
+        state::AssetRedemptionInfo,
+    },
+    anchor_lang::prelude::*,
+};
+
+/**
+ * Allows a user to cancel a redemption request for a compressed NFT before it is fulfilled.
+ * The leaf is transferred back to the owner with a proof against the tree's current root.
+ */
+#[derive(Accounts)]
+#[instruction(leaf: CompressedLeaf)]
+pub struct CancelCompressedAssetRedemptionRequest<'info> {
+    #[account(
+        mut,
+        seeds = [ASSET_REDEMPTION_INFO_SEED, compressed_asset_id(&merkle_tree.key(), leaf.nonce).as_ref()],
+        bump,
+        constraint = asset_redemption_info.nft_owner == owner.key() @ errors::ErrorCode::UnauthorizedRedemptionRequest,
+        constraint = !asset_redemption_info.is_fulfilled @ errors::ErrorCode::RedemptionRequestAlreadyFulfilled,
+        close = owner
+    )]
+    pub asset_redemption_info: Account<'info, AssetRedemptionInfo>,
+
+    /// CHECK: Bubblegum validates the tree config of the Merkle tree
+    pub tree_config: UncheckedAccount<'info>,
+
+    /// CHECK: Account compression verifies the leaf against the Merkle tree
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// CHECK: PDA for asset redemption authority, which owns and delegates the leaf
+    #[account(
+        seeds = [ASSET_REDEMPTION_VAULT_SEED],
+        bump
+    )]
+    pub asset_redemption_vault: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: SPL Noop program ID check
+    #[account(address = mpl_bubblegum::programs::SPL_NOOP_ID)]
+    pub log_wrapper: UncheckedAccount<'info>,
+
+    /// CHECK: SPL Account Compression program ID check
+    #[account(address = mpl_bubblegum::programs::SPL_ACCOUNT_COMPRESSION_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+
+    /// CHECK: Bubblegum program ID check
+    #[account(address = mpl_bubblegum::ID)]
+    pub bubblegum_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle<'info>(
+    ctx: Context<'_, '_, '_, 'info, CancelCompressedAssetRedemptionRequest<'info>>,
+    leaf: CompressedLeaf,
+) -> Result<()> {
+    let asset_redemption_bump = ctx.bumps.asset_redemption_vault;
+    let seeds = &[ASSET_REDEMPTION_VAULT_SEED, &[asset_redemption_bump]];
+    let signer = &[&seeds[..]];
+
+    // Transfer the leaf back to the owner
+    let asset_redemption_vault = ctx.accounts.asset_redemption_vault.to_account_info();
+    CompressedTransfer {
+        tree_config: &ctx.accounts.tree_config.to_account_info(),
+        merkle_tree: &ctx.accounts.merkle_tree.to_account_info(),
+        log_wrapper: &ctx.accounts.log_wrapper.to_account_info(),
+        compression_program: &ctx.accounts.compression_program.to_account_info(),
+        bubblegum_program: &ctx.accounts.bubblegum_program.to_account_info(),
+        system_program: &ctx.accounts.system_program.to_account_info(),
+        proof: ctx.remaining_accounts,
+    }
+    .transfer(
+        leaf,
+        &asset_redemption_vault,
+        &asset_redemption_vault,
+        &ctx.accounts.owner.to_account_info(),
+        signer,
+    )?;
+
+    emit!(RedemptionCancelled {
+        asset_redemption_info: ctx.accounts.asset_redemption_info.key(),
+        nft_mint: ctx.accounts.asset_redemption_info.nft_mint,
+        owner: ctx.accounts.owner.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use {
+    crate::{
+        constants::pda_constants::{ASSET_REDEMPTION_INFO_SEED, ASSET_REDEMPTION_VAULT_SEED},
+        errors,
+        events::RedemptionCancelled,
+        instructions::core_asset::{CoreAsset, MplCore, MPL_CORE_PROGRAM_ID},
+        state::AssetRedemptionInfo,
+    },
+    anchor_lang::prelude::*,
+};
+
+/**
+ * Allows a user to cancel a redemption request for a Metaplex Core asset before it is fulfilled.
+ * This instruction thaws the asset and returns its delegate authorities to the owner.
+ */
+#[derive(Accounts)]
+pub struct CancelCoreAssetRedemptionRequest<'info> {
+    #[account(
+        mut,
+        seeds = [ASSET_REDEMPTION_INFO_SEED, asset.key().as_ref()],
+        bump,
+        constraint = asset_redemption_info.nft_owner == owner.key() @ errors::ErrorCode::UnauthorizedRedemptionRequest,
+        constraint = !asset_redemption_info.is_fulfilled @ errors::ErrorCode::RedemptionRequestAlreadyFulfilled,
+        close = owner
+    )]
+    pub asset_redemption_info: Account<'info, AssetRedemptionInfo>,
+
+    #[account(mut, owner = MPL_CORE_PROGRAM_ID)]
+    /// CHECK: Matched to the redemption account through its seeds
+    pub asset: UncheckedAccount<'info>,
+
+    // Collection of the asset, required when the asset belongs to one
+    #[account(mut)]
+    /// CHECK: Checked against the asset's update authority before it is passed to Core
+    pub collection: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: PDA for asset redemption authority
+    #[account(
+        seeds = [ASSET_REDEMPTION_VAULT_SEED],
+        bump
+    )]
+    pub asset_redemption_vault: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub core_program: Program<'info, MplCore>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle(ctx: Context<CancelCoreAssetRedemptionRequest>) -> Result<()> {
+    // Thaw the asset and hand its delegate authorities back to the owner
+    let asset_redemption_bump = ctx.bumps.asset_redemption_vault;
+    let seeds = &[ASSET_REDEMPTION_VAULT_SEED, &[asset_redemption_bump]];
+    CoreAsset {
+        asset: &ctx.accounts.asset.to_account_info(),
+        collection: ctx
+            .accounts
+            .collection
+            .as_ref()
+            .map(|account| account.as_ref()),
+        payer: &ctx.accounts.owner.to_account_info(),
+        core_program: &ctx.accounts.core_program.to_account_info(),
+        system_program: &ctx.accounts.system_program.to_account_info(),
+    }
+    .release(
+        &ctx.accounts.asset_redemption_vault.to_account_info(),
+        &[&seeds[..]],
+    )?;
+
+    emit!(RedemptionCancelled {
+        asset_redemption_info: ctx.accounts.asset_redemption_info.key(),
+        nft_mint: ctx.accounts.asset.key(),
+        owner: ctx.accounts.owner.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use {
+    crate::{
+        constants::pda_constants::ASSET_REDEMPTION_INFO_SEED, errors, events::RedemptionCancelled,
+        state::AssetRedemptionInfo,
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::{
+        token_2022::Token2022,
+        token_interface::{Mint, TokenAccount},
+    },
+};
+
+/**
+ * Allows a user to cancel a redemption request for a non-transferable Token-2022 NFT before it is fulfilled.
+ * This instruction revokes the asset redemption vault's approval over the NFT.
+ */
+#[derive(Accounts)]
+pub struct CancelNonTransferableAssetRedemptionRequest<'info> {
+    #[account(
+        mut,
+        seeds = [ASSET_REDEMPTION_INFO_SEED, nft_mint.key().as_ref()],
+        bump,
+        constraint = asset_redemption_info.nft_owner == owner.key() @ errors::ErrorCode::UnauthorizedRedemptionRequest,
+        constraint = !asset_redemption_info.is_fulfilled @ errors::ErrorCode::RedemptionRequestAlreadyFulfilled,
+        close = owner
+    )]
+    pub asset_redemption_info: Account<'info, AssetRedemptionInfo>,
+
+    #[account(mint::token_program = token_program)]
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = nft_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program,
+    )]
+    pub owner_nft_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token2022>,
+}
+
+pub fn handle(ctx: Context<CancelNonTransferableAssetRedemptionRequest>) -> Result<()> {
+    // Revoke the asset redemption vault's approval over the NFT
+    anchor_spl::token_interface::revoke(CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        anchor_spl::token_interface::Revoke {
+            source: ctx.accounts.owner_nft_account.to_account_info(),
+            authority: ctx.accounts.owner.to_account_info(),
+        },
+    ))?;
+
+    emit!(RedemptionCancelled {
+        asset_redemption_info: ctx.accounts.asset_redemption_info.key(),
+        nft_mint: ctx.accounts.nft_mint.key(),
+        owner: ctx.accounts.owner.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -1,13 +1,17 @@
 use {
     crate::{
-        constants::pda_constants::{ASSET_REDEMPTION_INFO_SEED, ASSET_REDEMPTION_VAULT_SEED},
+        constants::pda_constants::{
+            ASSET_IMPAIRMENT_SEED, ASSET_REDEMPTION_INFO_SEED, ASSET_REDEMPTION_VAULT_SEED,
+        },
         errors,
-        state::AssetRedemptionInfo,
+        events::RedemptionRequested,
+        instructions::nft_transfer::*,
+        state::{AccountHeader, AssetRedemptionInfo},
     },
     anchor_lang::prelude::*,
     anchor_spl::{
         associated_token::AssociatedToken,
-        token::{Mint, Token, TokenAccount},
+        token_interface::{Mint, TokenAccount, TokenInterface},
     },
 };
 
@@ -31,16 +35,17 @@ pub struct CreateAssetRedemptionRequest<'info> {
     pub asset_redemption_info: Account<'info, AssetRedemptionInfo>,
 
     // The NFT being used as collateral
-    pub nft_mint: Account<'info, Mint>,
+    pub nft_mint: InterfaceAccount<'info, Mint>,
 
     // Owner's token account containing the NFT to be staked
     #[account(
         mut,
         associated_token::mint = nft_mint,
         associated_token::authority = owner,
+        associated_token::token_program = token_program,
         constraint = owner_nft_account.amount == 1 @ errors::ErrorCode::AssociatedTokenAccountHasNoTokenBalance
     )]
-    pub owner_nft_account: Account<'info, TokenAccount>,
+    pub owner_nft_account: InterfaceAccount<'info, TokenAccount>,
 
     // Program's asset redemption token account where the NFT will be held during the redemption request
     #[account(
@@ -48,8 +53,9 @@ pub struct CreateAssetRedemptionRequest<'info> {
         payer = owner,
         associated_token::mint = nft_mint,
         associated_token::authority = asset_redemption_vault,
+        associated_token::token_program = token_program,
     )]
-    pub asset_redemption_nft_account: Account<'info, TokenAccount>,
+    pub asset_redemption_nft_account: InterfaceAccount<'info, TokenAccount>,
 
     /// CHECK: PDA for asset redemption authority
     #[account(
@@ -60,13 +66,27 @@ pub struct CreateAssetRedemptionRequest<'info> {
 
     #[account(mut)]
     pub owner: Signer<'info>,
+
+    /// CHECK: Impairment PDA of the NFT, which must not exist for the NFT to be redeemed
+    #[account(
+        seeds = [ASSET_IMPAIRMENT_SEED, nft_mint.key().as_ref()],
+        bump,
+        constraint = asset_impairment.data_is_empty() @ errors::ErrorCode::NftImpaired
+    )]
+    pub asset_impairment: UncheckedAccount<'info>,
+
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub rent: Sysvar<'info, Rent>,
+
+    // Token Metadata accounts, required only when the NFT is a programmable NFT
+    pub programmable: ProgrammableTransfer<'info>,
 }
 
-pub fn handle(ctx: Context<CreateAssetRedemptionRequest>) -> Result<()> {
+pub fn handle<'info>(
+    ctx: Context<'_, '_, '_, 'info, CreateAssetRedemptionRequest<'info>>,
+) -> Result<()> {
     msg!(
         "Creating asset redemption request for NFT: {}",
         ctx.accounts.nft_mint.key()
@@ -75,6 +95,7 @@ pub fn handle(ctx: Context<CreateAssetRedemptionRequest>) -> Result<()> {
 
     // Create the AssetRedemptionInfo account
     let asset_redemption_info = &mut ctx.accounts.asset_redemption_info;
+    asset_redemption_info.header = AccountHeader::new(AssetRedemptionInfo::VERSION);
     asset_redemption_info.nft_mint = ctx.accounts.nft_mint.key();
     asset_redemption_info.nft_owner = ctx.accounts.owner.key();
     asset_redemption_info.request_timestamp = Clock::get()?.unix_timestamp;
@@ -86,16 +107,20 @@ pub fn handle(ctx: Context<CreateAssetRedemptionRequest>) -> Result<()> {
     );
 
     // Transfer NFT from owner to program asset redemption account for escrow during fulfillment
-    anchor_spl::token::transfer(
-        CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            anchor_spl::token::Transfer {
-                from: ctx.accounts.owner_nft_account.to_account_info(),
-                to: ctx.accounts.asset_redemption_nft_account.to_account_info(),
-                authority: ctx.accounts.owner.to_account_info(),
-            },
-        ),
-        1,
+    ctx.accounts.programmable.transfer(
+        NftTransfer {
+            nft_mint: &ctx.accounts.nft_mint.to_account_info(),
+            source: &ctx.accounts.owner_nft_account.to_account_info(),
+            source_owner: &ctx.accounts.owner.to_account_info(),
+            destination: &ctx.accounts.asset_redemption_nft_account.to_account_info(),
+            destination_owner: &ctx.accounts.asset_redemption_vault.to_account_info(),
+            payer: &ctx.accounts.owner.to_account_info(),
+            token_program: &ctx.accounts.token_program.to_account_info(),
+            associated_token_program: &ctx.accounts.associated_token_program.to_account_info(),
+            system_program: &ctx.accounts.system_program.to_account_info(),
+            additional_accounts: ctx.remaining_accounts,
+        },
+        &[],
     )?;
 
     msg!(
@@ -103,5 +128,12 @@ pub fn handle(ctx: Context<CreateAssetRedemptionRequest>) -> Result<()> {
         ctx.accounts.nft_mint.key()
     );
 
+    emit!(RedemptionRequested {
+        asset_redemption_info: ctx.accounts.asset_redemption_info.key(),
+        nft_mint: ctx.accounts.nft_mint.key(),
+        owner: ctx.accounts.owner.key(),
+        timestamp: ctx.accounts.asset_redemption_info.request_timestamp,
+    });
+
     Ok(())
 }
@@ -0,0 +1,109 @@
+use {
+    crate::{
+        constants::pda_constants::{ASSET_REDEMPTION_INFO_SEED, ASSET_REDEMPTION_VAULT_SEED},
+        events::RedemptionRequested,
+        instructions::compressed_nft::*,
+        state::{AccountHeader, AssetRedemptionInfo},
+    },
+    anchor_lang::prelude::*,
+};
+
+/**
+ * Allows a user to create a redemption request for a compressed NFT.
+ * The leaf is transferred to the asset redemption vault with Bubblegum, using a proof of the leaf
+ * against the tree's current root. The proof nodes are passed as remaining accounts.
+ */
+#[derive(Accounts)]
+#[instruction(leaf: CompressedLeaf)]
+pub struct CreateCompressedAssetRedemptionRequest<'info> {
+    // PDA account to store redemption information, derived from the compressed NFT's asset id
+    #[account(
+        init,
+        payer = owner,
+        space = AssetRedemptionInfo::INIT_SPACE,
+        seeds = [ASSET_REDEMPTION_INFO_SEED, compressed_asset_id(&merkle_tree.key(), leaf.nonce).as_ref()],
+        bump
+    )]
+    pub asset_redemption_info: Account<'info, AssetRedemptionInfo>,
+
+    /// CHECK: Bubblegum validates the tree config of the Merkle tree
+    pub tree_config: UncheckedAccount<'info>,
+
+    /// CHECK: Account compression verifies the leaf against the Merkle tree
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// CHECK: Delegate of the leaf, part of the leaf's hash; the owner if none was set
+    pub leaf_delegate: UncheckedAccount<'info>,
+
+    /// CHECK: PDA for asset redemption authority, which receives the leaf
+    #[account(
+        seeds = [ASSET_REDEMPTION_VAULT_SEED],
+        bump
+    )]
+    pub asset_redemption_vault: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: SPL Noop program ID check
+    #[account(address = mpl_bubblegum::programs::SPL_NOOP_ID)]
+    pub log_wrapper: UncheckedAccount<'info>,
+
+    /// CHECK: SPL Account Compression program ID check
+    #[account(address = mpl_bubblegum::programs::SPL_ACCOUNT_COMPRESSION_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+
+    /// CHECK: Bubblegum program ID check
+    #[account(address = mpl_bubblegum::ID)]
+    pub bubblegum_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle<'info>(
+    ctx: Context<'_, '_, '_, 'info, CreateCompressedAssetRedemptionRequest<'info>>,
+    leaf: CompressedLeaf,
+) -> Result<()> {
+    let asset_id = compressed_asset_id(&ctx.accounts.merkle_tree.key(), leaf.nonce);
+    msg!(
+        "Creating asset redemption request for compressed NFT: {}",
+        asset_id
+    );
+    msg!("Owner: {}", ctx.accounts.owner.key());
+
+    // Create the AssetRedemptionInfo account; the asset id stands in for the NFT mint
+    let asset_redemption_info = &mut ctx.accounts.asset_redemption_info;
+    asset_redemption_info.header = AccountHeader::new(AssetRedemptionInfo::VERSION);
+    asset_redemption_info.nft_mint = asset_id;
+    asset_redemption_info.nft_owner = ctx.accounts.owner.key();
+    asset_redemption_info.request_timestamp = Clock::get()?.unix_timestamp;
+    asset_redemption_info.is_fulfilled = false;
+
+    // Transfer the leaf from the owner to the asset redemption vault for escrow during fulfillment
+    CompressedTransfer {
+        tree_config: &ctx.accounts.tree_config.to_account_info(),
+        merkle_tree: &ctx.accounts.merkle_tree.to_account_info(),
+        log_wrapper: &ctx.accounts.log_wrapper.to_account_info(),
+        compression_program: &ctx.accounts.compression_program.to_account_info(),
+        bubblegum_program: &ctx.accounts.bubblegum_program.to_account_info(),
+        system_program: &ctx.accounts.system_program.to_account_info(),
+        proof: ctx.remaining_accounts,
+    }
+    .transfer(
+        leaf,
+        &ctx.accounts.owner.to_account_info(),
+        &ctx.accounts.leaf_delegate.to_account_info(),
+        &ctx.accounts.asset_redemption_vault.to_account_info(),
+        &[],
+    )?;
+
+    emit!(RedemptionRequested {
+        asset_redemption_info: ctx.accounts.asset_redemption_info.key(),
+        nft_mint: asset_id,
+        owner: ctx.accounts.owner.key(),
+        timestamp: ctx.accounts.asset_redemption_info.request_timestamp,
+    });
+
+    Ok(())
+}
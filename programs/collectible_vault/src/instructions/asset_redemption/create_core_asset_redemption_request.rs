@@ -0,0 +1,94 @@
+use {
+    crate::{
+        constants::pda_constants::{ASSET_REDEMPTION_INFO_SEED, ASSET_REDEMPTION_VAULT_SEED},
+        events::RedemptionRequested,
+        instructions::core_asset::{CoreAsset, MplCore, MPL_CORE_PROGRAM_ID},
+        state::{AccountHeader, AssetRedemptionInfo},
+    },
+    anchor_lang::prelude::*,
+};
+
+/**
+ * Allows a user to create a redemption request for a Metaplex Core asset.
+ * The asset stays in the owner's wallet, frozen, with the asset redemption vault as its freeze and transfer delegate.
+ * It is moved to the asset redemption vault when the request is fulfilled.
+ */
+#[derive(Accounts)]
+pub struct CreateCoreAssetRedemptionRequest<'info> {
+    // PDA account to store redemption information, derived from the asset address
+    #[account(
+        init,
+        payer = owner,
+        space = AssetRedemptionInfo::INIT_SPACE,
+        seeds = [ASSET_REDEMPTION_INFO_SEED, asset.key().as_ref()],
+        bump
+    )]
+    pub asset_redemption_info: Account<'info, AssetRedemptionInfo>,
+
+    // The Core asset being redeemed
+    #[account(mut, owner = MPL_CORE_PROGRAM_ID)]
+    /// CHECK: Decoded and checked to be held by the owner before it is locked
+    pub asset: UncheckedAccount<'info>,
+
+    // Collection of the asset, required when the asset belongs to one
+    #[account(mut)]
+    /// CHECK: Checked against the asset's update authority before it is passed to Core
+    pub collection: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: PDA for asset redemption authority, which becomes the asset's freeze and transfer delegate
+    #[account(
+        seeds = [ASSET_REDEMPTION_VAULT_SEED],
+        bump
+    )]
+    pub asset_redemption_vault: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub core_program: Program<'info, MplCore>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle(ctx: Context<CreateCoreAssetRedemptionRequest>) -> Result<()> {
+    msg!(
+        "Creating asset redemption request for Core asset: {}",
+        ctx.accounts.asset.key()
+    );
+    msg!("Owner: {}", ctx.accounts.owner.key());
+
+    // Create the AssetRedemptionInfo account; the asset address stands in for the NFT mint
+    let asset_redemption_info = &mut ctx.accounts.asset_redemption_info;
+    asset_redemption_info.header = AccountHeader::new(AssetRedemptionInfo::VERSION);
+    asset_redemption_info.nft_mint = ctx.accounts.asset.key();
+    asset_redemption_info.nft_owner = ctx.accounts.owner.key();
+    asset_redemption_info.request_timestamp = Clock::get()?.unix_timestamp;
+    asset_redemption_info.is_fulfilled = false;
+
+    // Freeze the asset in the owner's wallet under the asset redemption vault
+    let asset_redemption_bump = ctx.bumps.asset_redemption_vault;
+    let seeds = &[ASSET_REDEMPTION_VAULT_SEED, &[asset_redemption_bump]];
+    CoreAsset {
+        asset: &ctx.accounts.asset.to_account_info(),
+        collection: ctx
+            .accounts
+            .collection
+            .as_ref()
+            .map(|account| account.as_ref()),
+        payer: &ctx.accounts.owner.to_account_info(),
+        core_program: &ctx.accounts.core_program.to_account_info(),
+        system_program: &ctx.accounts.system_program.to_account_info(),
+    }
+    .lock(
+        &ctx.accounts.owner.to_account_info(),
+        &ctx.accounts.asset_redemption_vault.to_account_info(),
+        &[&seeds[..]],
+    )?;
+
+    emit!(RedemptionRequested {
+        asset_redemption_info: ctx.accounts.asset_redemption_info.key(),
+        nft_mint: ctx.accounts.asset.key(),
+        owner: ctx.accounts.owner.key(),
+        timestamp: ctx.accounts.asset_redemption_info.request_timestamp,
+    });
+
+    Ok(())
+}
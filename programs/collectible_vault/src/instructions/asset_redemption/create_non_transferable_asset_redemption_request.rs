@@ -0,0 +1,110 @@
+use {
+    crate::{
+        constants::pda_constants::{
+            ASSET_IMPAIRMENT_SEED, ASSET_REDEMPTION_INFO_SEED, ASSET_REDEMPTION_VAULT_SEED,
+        },
+        errors,
+        events::RedemptionRequested,
+        instructions::nft_transfer::is_non_transferable,
+        state::{AccountHeader, AssetRedemptionInfo},
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::{
+        token_2022::Token2022,
+        token_interface::{Mint, TokenAccount},
+    },
+};
+
+/**
+ * Allows a user to create a redemption request for a non-transferable Token-2022 NFT.
+ * The NFT cannot be moved into the program vault, so it stays in the owner's wallet and the asset redemption
+ * vault is approved as its delegate. The NFT is burned through that approval when the request is fulfilled.
+ */
+#[derive(Accounts)]
+pub struct CreateNonTransferableAssetRedemptionRequest<'info> {
+    // PDA account to store redemption information, derived from the NFT mint address
+    #[account(
+        init,
+        payer = owner,
+        space = AssetRedemptionInfo::INIT_SPACE,
+        seeds = [ASSET_REDEMPTION_INFO_SEED, nft_mint.key().as_ref()],
+        bump
+    )]
+    pub asset_redemption_info: Account<'info, AssetRedemptionInfo>,
+
+    // The non-transferable NFT being redeemed
+    #[account(
+        mint::token_program = token_program,
+        constraint = is_non_transferable(&nft_mint.to_account_info())? @ errors::ErrorCode::TransferableNft
+    )]
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+
+    // Owner's token account containing the NFT, whose delegate becomes the asset redemption vault
+    #[account(
+        mut,
+        associated_token::mint = nft_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program,
+        constraint = owner_nft_account.amount == 1 @ errors::ErrorCode::AssociatedTokenAccountHasNoTokenBalance
+    )]
+    pub owner_nft_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: PDA for asset redemption authority, which becomes the delegate of the owner's token account
+    #[account(
+        seeds = [ASSET_REDEMPTION_VAULT_SEED],
+        bump
+    )]
+    pub asset_redemption_vault: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: Impairment PDA of the NFT, which must not exist for the NFT to be redeemed
+    #[account(
+        seeds = [ASSET_IMPAIRMENT_SEED, nft_mint.key().as_ref()],
+        bump,
+        constraint = asset_impairment.data_is_empty() @ errors::ErrorCode::NftImpaired
+    )]
+    pub asset_impairment: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+}
+
+pub fn handle(ctx: Context<CreateNonTransferableAssetRedemptionRequest>) -> Result<()> {
+    msg!(
+        "Creating asset redemption request for non-transferable NFT: {}",
+        ctx.accounts.nft_mint.key()
+    );
+    msg!("Owner: {}", ctx.accounts.owner.key());
+
+    // Create the AssetRedemptionInfo account
+    let asset_redemption_info = &mut ctx.accounts.asset_redemption_info;
+    asset_redemption_info.header = AccountHeader::new(AssetRedemptionInfo::VERSION);
+    asset_redemption_info.nft_mint = ctx.accounts.nft_mint.key();
+    asset_redemption_info.nft_owner = ctx.accounts.owner.key();
+    asset_redemption_info.request_timestamp = Clock::get()?.unix_timestamp;
+    asset_redemption_info.is_fulfilled = false;
+
+    // Approve the asset redemption vault to burn the NFT on fulfillment
+    anchor_spl::token_interface::approve(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            anchor_spl::token_interface::Approve {
+                to: ctx.accounts.owner_nft_account.to_account_info(),
+                delegate: ctx.accounts.asset_redemption_vault.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            },
+        ),
+        1,
+    )?;
+
+    emit!(RedemptionRequested {
+        asset_redemption_info: ctx.accounts.asset_redemption_info.key(),
+        nft_mint: ctx.accounts.nft_mint.key(),
+        owner: ctx.accounts.owner.key(),
+        timestamp: ctx.accounts.asset_redemption_info.request_timestamp,
+    });
+
+    Ok(())
+}
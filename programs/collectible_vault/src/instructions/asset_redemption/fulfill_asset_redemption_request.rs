@@ -4,10 +4,11 @@ use {
             admin_constants::get_admin_account_pubkey, pda_constants::ASSET_REDEMPTION_INFO_SEED,
         },
         errors,
+        events::RedemptionFulfilled,
         state::AssetRedemptionInfo,
     },
     anchor_lang::prelude::*,
-    anchor_spl::token::Mint,
+    anchor_spl::token_interface::Mint,
 };
 
 /**
@@ -28,7 +29,7 @@ pub struct FulfillAssetRedemptionRequest<'info> {
     pub asset_redemption_info: Account<'info, AssetRedemptionInfo>,
 
     // The NFT being used as collateral
-    pub nft_mint: Account<'info, Mint>,
+    pub nft_mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
@@ -52,5 +53,13 @@ pub fn handle(ctx: Context<FulfillAssetRedemptionRequest>) -> Result<()> {
         ctx.accounts.nft_mint.key()
     );
 
+    emit!(RedemptionFulfilled {
+        asset_redemption_info: ctx.accounts.asset_redemption_info.key(),
+        nft_mint: ctx.accounts.nft_mint.key(),
+        owner: ctx.accounts.asset_redemption_info.nft_owner,
+        admin: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
     Ok(())
 }
@@ -0,0 +1,57 @@
+use {
+    crate::{
+        constants::{
+            admin_constants::get_admin_account_pubkey, pda_constants::ASSET_REDEMPTION_INFO_SEED,
+        },
+        errors,
+        events::RedemptionFulfilled,
+        instructions::compressed_nft::compressed_asset_id,
+        state::AssetRedemptionInfo,
+    },
+    anchor_lang::prelude::*,
+};
+
+/**
+ * Allows the admin to fulfill a redemption request for a compressed NFT.
+ * The leaf is already held by the asset redemption vault, where it stays permanently.
+ */
+#[derive(Accounts)]
+#[instruction(nonce: u64)]
+pub struct FulfillCompressedAssetRedemptionRequest<'info> {
+    #[account(
+        mut,
+        seeds = [ASSET_REDEMPTION_INFO_SEED, compressed_asset_id(&merkle_tree.key(), nonce).as_ref()],
+        bump,
+        constraint = !asset_redemption_info.is_fulfilled @ errors::ErrorCode::RedemptionRequestAlreadyFulfilled,
+    )]
+    pub asset_redemption_info: Account<'info, AssetRedemptionInfo>,
+
+    /// CHECK: Only used to derive the compressed NFT's asset id
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = admin.key() == get_admin_account_pubkey() @ errors::ErrorCode::UnauthorizedRedemptionRequest,
+    )]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle(ctx: Context<FulfillCompressedAssetRedemptionRequest>, _nonce: u64) -> Result<()> {
+    ctx.accounts.asset_redemption_info.is_fulfilled = true;
+
+    msg!(
+        "Marked asset redemption request for compressed NFT {} as fulfilled",
+        ctx.accounts.asset_redemption_info.nft_mint
+    );
+
+    emit!(RedemptionFulfilled {
+        asset_redemption_info: ctx.accounts.asset_redemption_info.key(),
+        nft_mint: ctx.accounts.asset_redemption_info.nft_mint,
+        owner: ctx.accounts.asset_redemption_info.nft_owner,
+        admin: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,91 @@
+use {
+    crate::{
+        constants::{
+            admin_constants::get_admin_account_pubkey,
+            pda_constants::{ASSET_REDEMPTION_INFO_SEED, ASSET_REDEMPTION_VAULT_SEED},
+        },
+        errors,
+        events::RedemptionFulfilled,
+        instructions::core_asset::{CoreAsset, MplCore, MPL_CORE_PROGRAM_ID},
+        state::AssetRedemptionInfo,
+    },
+    anchor_lang::prelude::*,
+};
+
+/**
+ * Allows the admin to fulfill a redemption request for a Metaplex Core asset.
+ * The asset is moved from the owner's wallet to the asset redemption vault permanently; we do not burn it (for archival, sustainability).
+ */
+#[derive(Accounts)]
+pub struct FulfillCoreAssetRedemptionRequest<'info> {
+    #[account(
+        mut,
+        seeds = [ASSET_REDEMPTION_INFO_SEED, asset.key().as_ref()],
+        bump,
+        constraint = !asset_redemption_info.is_fulfilled @ errors::ErrorCode::RedemptionRequestAlreadyFulfilled,
+    )]
+    pub asset_redemption_info: Account<'info, AssetRedemptionInfo>,
+
+    #[account(mut, owner = MPL_CORE_PROGRAM_ID)]
+    /// CHECK: Matched to the redemption account through its seeds
+    pub asset: UncheckedAccount<'info>,
+
+    // Collection of the asset, required when the asset belongs to one
+    #[account(mut)]
+    /// CHECK: Checked against the asset's update authority before it is passed to Core
+    pub collection: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: PDA for asset redemption authority, which receives the asset
+    #[account(
+        seeds = [ASSET_REDEMPTION_VAULT_SEED],
+        bump
+    )]
+    pub asset_redemption_vault: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = admin.key() == get_admin_account_pubkey() @ errors::ErrorCode::UnauthorizedRedemptionRequest,
+    )]
+    pub admin: Signer<'info>,
+    pub core_program: Program<'info, MplCore>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle(ctx: Context<FulfillCoreAssetRedemptionRequest>) -> Result<()> {
+    ctx.accounts.asset_redemption_info.is_fulfilled = true;
+
+    // Thaw the asset and move it into the asset redemption vault
+    let asset_redemption_bump = ctx.bumps.asset_redemption_vault;
+    let seeds = &[ASSET_REDEMPTION_VAULT_SEED, &[asset_redemption_bump]];
+    CoreAsset {
+        asset: &ctx.accounts.asset.to_account_info(),
+        collection: ctx
+            .accounts
+            .collection
+            .as_ref()
+            .map(|account| account.as_ref()),
+        payer: &ctx.accounts.admin.to_account_info(),
+        core_program: &ctx.accounts.core_program.to_account_info(),
+        system_program: &ctx.accounts.system_program.to_account_info(),
+    }
+    .seize(
+        &ctx.accounts.asset_redemption_vault.to_account_info(),
+        &ctx.accounts.asset_redemption_vault.to_account_info(),
+        &[&seeds[..]],
+    )?;
+
+    msg!(
+        "Marked asset redemption request for Core asset {} as fulfilled",
+        ctx.accounts.asset.key()
+    );
+
+    emit!(RedemptionFulfilled {
+        asset_redemption_info: ctx.accounts.asset_redemption_info.key(),
+        nft_mint: ctx.accounts.asset.key(),
+        owner: ctx.accounts.asset_redemption_info.nft_owner,
+        admin: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,99 @@
+use {
+    crate::{
+        constants::{
+            admin_constants::get_admin_account_pubkey,
+            pda_constants::{ASSET_REDEMPTION_INFO_SEED, ASSET_REDEMPTION_VAULT_SEED},
+        },
+        errors,
+        events::RedemptionFulfilled,
+        state::AssetRedemptionInfo,
+    },
+    anchor_lang::{prelude::*, solana_program::program_option::COption},
+    anchor_spl::{
+        associated_token::get_associated_token_address_with_program_id,
+        token_2022::Token2022,
+        token_interface::{Mint, TokenAccount},
+    },
+};
+
+/**
+ * Allows the admin to fulfill a redemption request for a non-transferable Token-2022 NFT.
+ * The NFT cannot be archived in the program vault, so the asset redemption vault burns it in the owner's wallet
+ * through the approval given with the request. The request is only fulfilled if the NFT is burned.
+ */
+#[derive(Accounts)]
+pub struct FulfillNonTransferableAssetRedemptionRequest<'info> {
+    #[account(
+        mut,
+        seeds = [ASSET_REDEMPTION_INFO_SEED, nft_mint.key().as_ref()],
+        bump,
+        constraint = !asset_redemption_info.is_fulfilled @ errors::ErrorCode::RedemptionRequestAlreadyFulfilled,
+    )]
+    pub asset_redemption_info: Account<'info, AssetRedemptionInfo>,
+
+    // The non-transferable NFT being redeemed, whose supply drops when it is burned
+    #[account(
+        mut,
+        mint::token_program = token_program,
+    )]
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+
+    // Owner's token account containing the NFT, still delegated to the asset redemption vault
+    #[account(
+        mut,
+        address = get_associated_token_address_with_program_id(&asset_redemption_info.nft_owner, &nft_mint.key(), &token_program.key()),
+        constraint = owner_nft_account.amount == 1 @ errors::ErrorCode::AssociatedTokenAccountHasNoTokenBalance,
+        constraint = owner_nft_account.delegate == COption::Some(asset_redemption_vault.key())
+            && owner_nft_account.delegated_amount == 1 @ errors::ErrorCode::RedemptionApprovalRevoked
+    )]
+    pub owner_nft_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: PDA for asset redemption authority, which burns the NFT as delegate
+    #[account(
+        seeds = [ASSET_REDEMPTION_VAULT_SEED],
+        bump
+    )]
+    pub asset_redemption_vault: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = admin.key() == get_admin_account_pubkey() @ errors::ErrorCode::UnauthorizedRedemptionRequest,
+    )]
+    pub admin: Signer<'info>,
+    pub token_program: Program<'info, Token2022>,
+}
+
+pub fn handle(ctx: Context<FulfillNonTransferableAssetRedemptionRequest>) -> Result<()> {
+    ctx.accounts.asset_redemption_info.is_fulfilled = true;
+
+    // Burn the NFT in the owner's wallet as its delegate
+    let asset_redemption_bump = ctx.bumps.asset_redemption_vault;
+    let seeds = &[ASSET_REDEMPTION_VAULT_SEED, &[asset_redemption_bump]];
+    anchor_spl::token_interface::burn(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            anchor_spl::token_interface::Burn {
+                mint: ctx.accounts.nft_mint.to_account_info(),
+                from: ctx.accounts.owner_nft_account.to_account_info(),
+                authority: ctx.accounts.asset_redemption_vault.to_account_info(),
+            },
+            &[&seeds[..]],
+        ),
+        1,
+    )?;
+
+    msg!(
+        "Burned non-transferable NFT {} and marked its asset redemption request as fulfilled",
+        ctx.accounts.nft_mint.key()
+    );
+
+    emit!(RedemptionFulfilled {
+        asset_redemption_info: ctx.accounts.asset_redemption_info.key(),
+        nft_mint: ctx.accounts.nft_mint.key(),
+        owner: ctx.accounts.asset_redemption_info.nft_owner,
+        admin: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -1,3 +1,12 @@
 pub mod cancel_asset_redemption_request;
+pub mod cancel_compressed_asset_redemption_request;
+pub mod cancel_core_asset_redemption_request;
+pub mod cancel_non_transferable_asset_redemption_request;
 pub mod create_asset_redemption_request;
+pub mod create_compressed_asset_redemption_request;
+pub mod create_core_asset_redemption_request;
+pub mod create_non_transferable_asset_redemption_request;
 pub mod fulfill_asset_redemption_request;
+pub mod fulfill_compressed_asset_redemption_request;
+pub mod fulfill_core_asset_redemption_request;
+pub mod fulfill_non_transferable_asset_redemption_request;
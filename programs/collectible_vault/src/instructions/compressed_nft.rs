@@ -0,0 +1,80 @@
+use {
+    anchor_lang::prelude::*,
+    mpl_bubblegum::instructions::{TransferCpi, TransferCpiAccounts, TransferInstructionArgs},
+};
+
+/**
+ * Identifies a compressed NFT leaf in a Bubblegum Merkle tree at the tree's current root.
+ * Indexers return these values with the leaf's proof; the proof nodes are passed as remaining accounts.
+ */
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct CompressedLeaf {
+    pub root: [u8; 32],
+    pub data_hash: [u8; 32],
+    pub creator_hash: [u8; 32],
+    /// The leaf's nonce, from which its asset id is derived
+    pub nonce: u64,
+    /// The leaf's position in the tree
+    pub index: u32,
+}
+
+/**
+ * Returns the asset id of the compressed NFT minted into `merkle_tree` with `nonce`.
+ * The asset id stands in for the NFT mint in the vault's PDAs.
+ */
+pub fn compressed_asset_id(merkle_tree: &Pubkey, nonce: u64) -> Pubkey {
+    mpl_bubblegum::utils::get_asset_id(merkle_tree, nonce)
+}
+
+/**
+ * The Bubblegum accounts taking part in moving a compressed NFT.
+ */
+pub struct CompressedTransfer<'a, 'info> {
+    pub tree_config: &'a AccountInfo<'info>,
+    pub merkle_tree: &'a AccountInfo<'info>,
+    pub log_wrapper: &'a AccountInfo<'info>,
+    pub compression_program: &'a AccountInfo<'info>,
+    pub bubblegum_program: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+    /// Proof nodes of the leaf, from the leaf's sibling up to the tree's canopy
+    pub proof: &'a [AccountInfo<'info>],
+}
+
+impl<'info> CompressedTransfer<'_, 'info> {
+    /**
+     * Moves the leaf from `leaf_owner` to `new_leaf_owner`, which also becomes its delegate.
+     * `leaf_owner` must sign, directly or through `signer_seeds`.
+     */
+    pub fn transfer(
+        &self,
+        leaf: CompressedLeaf,
+        leaf_owner: &AccountInfo<'info>,
+        leaf_delegate: &AccountInfo<'info>,
+        new_leaf_owner: &AccountInfo<'info>,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        let proof: Vec<_> = self.proof.iter().map(|node| (node, false, false)).collect();
+        TransferCpi::new(
+            self.bubblegum_program,
+            TransferCpiAccounts {
+                tree_config: self.tree_config,
+                leaf_owner: (leaf_owner, true),
+                leaf_delegate: (leaf_delegate, false),
+                new_leaf_owner,
+                merkle_tree: self.merkle_tree,
+                log_wrapper: self.log_wrapper,
+                compression_program: self.compression_program,
+                system_program: self.system_program,
+            },
+            TransferInstructionArgs {
+                root: leaf.root,
+                data_hash: leaf.data_hash,
+                creator_hash: leaf.creator_hash,
+                nonce: leaf.nonce,
+                index: leaf.index,
+            },
+        )
+        .invoke_signed_with_remaining_accounts(signer_seeds, &proof)?;
+        Ok(())
+    }
+}
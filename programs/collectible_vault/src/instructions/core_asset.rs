@@ -0,0 +1,488 @@
+use {
+    crate::errors,
+    anchor_lang::{
+        prelude::*,
+        solana_program::{
+            instruction::{AccountMeta, Instruction},
+            program::invoke_signed,
+        },
+    },
+    std::io::Write,
+};
+
+/// Program ID of Metaplex Core
+pub const MPL_CORE_PROGRAM_ID: Pubkey = pubkey!("CoREENxT6tW1HoK8ypY1SxRMZTcVPm7R94rH4PZNhX7d");
+
+/**
+ * Metaplex Core program marker, so instructions can take it as `Program<'info, MplCore>`.
+ */
+#[derive(Clone)]
+pub struct MplCore;
+
+impl Id for MplCore {
+    fn id() -> Pubkey {
+        MPL_CORE_PROGRAM_ID
+    }
+}
+
+// Instruction discriminators of the Metaplex Core program
+const CREATE_V1: u8 = 0;
+const ADD_PLUGIN_V1: u8 = 2;
+const UPDATE_PLUGIN_V1: u8 = 6;
+const APPROVE_PLUGIN_AUTHORITY_V1: u8 = 8;
+const REVOKE_PLUGIN_AUTHORITY_V1: u8 = 10;
+const TRANSFER_V1: u8 = 14;
+
+// Account keys of the Metaplex Core program
+const ASSET_V1_KEY: u8 = 1;
+const PLUGIN_HEADER_V1_KEY: u8 = 3;
+const PLUGIN_REGISTRY_V1_KEY: u8 = 4;
+
+/**
+ * The Metaplex Core plugin types, in their on-chain order.
+ * Assets carrying a plugin type added to Core after these fail to decode and are rejected.
+ */
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CorePluginType {
+    Royalties,
+    FreezeDelegate,
+    BurnDelegate,
+    TransferDelegate,
+    UpdateDelegate,
+    PermanentFreezeDelegate,
+    Attributes,
+    PermanentTransferDelegate,
+    PermanentBurnDelegate,
+    Edition,
+    MasterEdition,
+    AddBlocker,
+    ImmutableMetadata,
+    VerifiedCreators,
+    Autograph,
+}
+
+/**
+ * The authority of a Metaplex Core plugin.
+ */
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CorePluginAuthority {
+    None,
+    Owner,
+    UpdateAuthority,
+    Address { address: Pubkey },
+}
+
+/**
+ * The Metaplex Core plugins the vault adds to assets it holds as collateral.
+ */
+enum CorePlugin {
+    FreezeDelegate { frozen: bool },
+    TransferDelegate,
+}
+
+impl AnchorSerialize for CorePlugin {
+    fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        match self {
+            Self::FreezeDelegate { frozen } => {
+                (CorePluginType::FreezeDelegate as u8, *frozen).serialize(writer)
+            }
+            Self::TransferDelegate => (CorePluginType::TransferDelegate as u8).serialize(writer),
+        }
+    }
+}
+
+#[derive(AnchorDeserialize)]
+enum CoreUpdateAuthority {
+    None,
+    // Decoded only to reach the fields after it
+    #[allow(dead_code)]
+    Address(Pubkey),
+    Collection(Pubkey),
+}
+
+#[derive(AnchorDeserialize)]
+struct CoreBaseAsset {
+    key: u8,
+    owner: Pubkey,
+    update_authority: CoreUpdateAuthority,
+    _name: String,
+    _uri: String,
+    _seq: Option<u64>,
+}
+
+#[derive(AnchorDeserialize)]
+struct CorePluginHeader {
+    key: u8,
+    plugin_registry_offset: u64,
+}
+
+#[derive(AnchorDeserialize)]
+struct CoreRegistryRecord {
+    plugin_type: CorePluginType,
+    authority: CorePluginAuthority,
+    _offset: u64,
+}
+
+/**
+ * The parts of a Metaplex Core asset the vault relies on: its owner, the collection it belongs to
+ * and the authority of each plugin it carries.
+ */
+pub struct CoreAssetState {
+    pub owner: Pubkey,
+    pub collection: Option<Pubkey>,
+    pub plugins: Vec<(CorePluginType, CorePluginAuthority)>,
+}
+
+impl CoreAssetState {
+    /// Decodes a Metaplex Core asset account, which must be owned by the Core program.
+    pub fn load(asset: &AccountInfo) -> Result<Self> {
+        require_keys_eq!(
+            *asset.owner,
+            MPL_CORE_PROGRAM_ID,
+            ErrorCode::ConstraintOwner
+        );
+        let data = asset.try_borrow_data()?;
+        let invalid = || error!(errors::ErrorCode::UnsupportedCoreAsset);
+
+        let mut cursor: &[u8] = &data;
+        let base = CoreBaseAsset::deserialize(&mut cursor).map_err(|_| invalid())?;
+        require_eq!(
+            base.key,
+            ASSET_V1_KEY,
+            errors::ErrorCode::UnsupportedCoreAsset
+        );
+
+        let mut plugins = Vec::new();
+        if !cursor.is_empty() {
+            let header = CorePluginHeader::deserialize(&mut cursor).map_err(|_| invalid())?;
+            require_eq!(
+                header.key,
+                PLUGIN_HEADER_V1_KEY,
+                errors::ErrorCode::UnsupportedCoreAsset
+            );
+            let mut registry = data
+                .get(header.plugin_registry_offset as usize..)
+                .ok_or_else(invalid)?;
+            let key = u8::deserialize(&mut registry).map_err(|_| invalid())?;
+            require_eq!(
+                key,
+                PLUGIN_REGISTRY_V1_KEY,
+                errors::ErrorCode::UnsupportedCoreAsset
+            );
+            let records =
+                Vec::<CoreRegistryRecord>::deserialize(&mut registry).map_err(|_| invalid())?;
+            plugins = records
+                .into_iter()
+                .map(|record| (record.plugin_type, record.authority))
+                .collect();
+        }
+
+        let collection = match base.update_authority {
+            CoreUpdateAuthority::Collection(collection) => Some(collection),
+            CoreUpdateAuthority::None | CoreUpdateAuthority::Address(_) => None,
+        };
+        Ok(Self {
+            owner: base.owner,
+            collection,
+            plugins,
+        })
+    }
+
+    /// Returns the authority of a plugin, if the asset carries it.
+    pub fn plugin_authority(&self, plugin_type: CorePluginType) -> Option<CorePluginAuthority> {
+        self.plugins
+            .iter()
+            .find(|(candidate, _)| *candidate == plugin_type)
+            .map(|(_, authority)| *authority)
+    }
+
+    /**
+     * Fails unless the asset is held by `owner` and carries no permanent delegate or burn
+     * delegate that would let another authority move or destroy it while it is collateral.
+     */
+    pub fn require_lockable(&self, owner: &Pubkey) -> Result<()> {
+        require_keys_eq!(self.owner, *owner, errors::ErrorCode::InvalidNFTAccount);
+        for plugin_type in [
+            CorePluginType::BurnDelegate,
+            CorePluginType::PermanentFreezeDelegate,
+            CorePluginType::PermanentTransferDelegate,
+            CorePluginType::PermanentBurnDelegate,
+        ] {
+            require!(
+                self.plugin_authority(plugin_type).is_none(),
+                errors::ErrorCode::UnsupportedCoreAsset
+            );
+        }
+        Ok(())
+    }
+}
+
+/**
+ * The Metaplex Core accounts taking part in locking or releasing an asset.
+ * `payer` funds any account growth and must sign. `collection` is required when the asset belongs
+ * to a collection, which Core loads to apply the collection's plugins.
+ */
+pub struct CoreAsset<'a, 'info> {
+    pub asset: &'a AccountInfo<'info>,
+    pub collection: Option<&'a AccountInfo<'info>>,
+    pub payer: &'a AccountInfo<'info>,
+    pub core_program: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+}
+
+impl<'info> CoreAsset<'_, 'info> {
+    /**
+     * Freezes the asset in its owner's wallet and hands its freeze and transfer delegate
+     * authorities to `delegate`. The owner must sign; plugins left behind by an earlier lock are
+     * re-approved rather than added again.
+     */
+    pub fn lock(
+        &self,
+        owner: &AccountInfo<'info>,
+        delegate: &AccountInfo<'info>,
+        delegate_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        let state = CoreAssetState::load(self.asset)?;
+        state.require_lockable(owner.key)?;
+        self.require_collection(&state)?;
+        let delegate_authority = CorePluginAuthority::Address {
+            address: delegate.key(),
+        };
+
+        if state
+            .plugin_authority(CorePluginType::TransferDelegate)
+            .is_some()
+        {
+            self.approve_plugin_authority(
+                owner,
+                CorePluginType::TransferDelegate,
+                delegate_authority,
+            )?;
+        } else {
+            self.add_plugin(owner, CorePlugin::TransferDelegate, delegate_authority)?;
+        }
+
+        if state
+            .plugin_authority(CorePluginType::FreezeDelegate)
+            .is_some()
+        {
+            self.approve_plugin_authority(
+                owner,
+                CorePluginType::FreezeDelegate,
+                delegate_authority,
+            )?;
+            self.update_plugin(
+                delegate,
+                CorePlugin::FreezeDelegate { frozen: true },
+                delegate_seeds,
+            )
+        } else {
+            self.add_plugin(
+                owner,
+                CorePlugin::FreezeDelegate { frozen: true },
+                delegate_authority,
+            )
+        }
+    }
+
+    /**
+     * Thaws an asset locked by `delegate` and returns both delegate authorities to its owner.
+     */
+    pub fn release(
+        &self,
+        delegate: &AccountInfo<'info>,
+        delegate_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        self.require_collection(&CoreAssetState::load(self.asset)?)?;
+        self.update_plugin(
+            delegate,
+            CorePlugin::FreezeDelegate { frozen: false },
+            delegate_seeds,
+        )?;
+        self.revoke_plugin_authority(delegate, CorePluginType::FreezeDelegate, delegate_seeds)?;
+        self.revoke_plugin_authority(delegate, CorePluginType::TransferDelegate, delegate_seeds)
+    }
+
+    /**
+     * Thaws an asset locked by `delegate` and moves it to `new_owner` as its transfer delegate.
+     * Core returns owner-managed plugin authorities to the owner on transfer.
+     */
+    pub fn seize(
+        &self,
+        delegate: &AccountInfo<'info>,
+        new_owner: &AccountInfo<'info>,
+        delegate_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        self.require_collection(&CoreAssetState::load(self.asset)?)?;
+        self.update_plugin(
+            delegate,
+            CorePlugin::FreezeDelegate { frozen: false },
+            delegate_seeds,
+        )?;
+        let accounts = vec![
+            AccountMeta::new(self.asset.key(), false),
+            self.collection_meta(false),
+            AccountMeta::new(self.payer.key(), true),
+            AccountMeta::new_readonly(delegate.key(), true),
+            AccountMeta::new_readonly(new_owner.key(), false),
+            AccountMeta::new_readonly(self.system_program.key(), false),
+            AccountMeta::new_readonly(MPL_CORE_PROGRAM_ID, false), // log wrapper
+        ];
+        // TransferV1 args: compression_proof: Option<CompressionProof>
+        let data = (TRANSFER_V1, None::<u8>).try_to_vec()?;
+        self.invoke(accounts, data, &[delegate, new_owner], delegate_seeds)
+    }
+
+    fn add_plugin(
+        &self,
+        owner: &AccountInfo<'info>,
+        plugin: CorePlugin,
+        init_authority: CorePluginAuthority,
+    ) -> Result<()> {
+        let data = (ADD_PLUGIN_V1, plugin, Some(init_authority)).try_to_vec()?;
+        self.invoke(self.plugin_accounts(owner), data, &[owner], &[])
+    }
+
+    fn approve_plugin_authority(
+        &self,
+        owner: &AccountInfo<'info>,
+        plugin_type: CorePluginType,
+        new_authority: CorePluginAuthority,
+    ) -> Result<()> {
+        let data = (APPROVE_PLUGIN_AUTHORITY_V1, plugin_type, new_authority).try_to_vec()?;
+        self.invoke(self.plugin_accounts(owner), data, &[owner], &[])
+    }
+
+    fn update_plugin(
+        &self,
+        authority: &AccountInfo<'info>,
+        plugin: CorePlugin,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        let data = (UPDATE_PLUGIN_V1, plugin).try_to_vec()?;
+        self.invoke(
+            self.plugin_accounts(authority),
+            data,
+            &[authority],
+            signer_seeds,
+        )
+    }
+
+    fn revoke_plugin_authority(
+        &self,
+        authority: &AccountInfo<'info>,
+        plugin_type: CorePluginType,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        let data = (REVOKE_PLUGIN_AUTHORITY_V1, plugin_type).try_to_vec()?;
+        self.invoke(
+            self.plugin_accounts(authority),
+            data,
+            &[authority],
+            signer_seeds,
+        )
+    }
+
+    /// Fails unless `collection` is the collection the asset belongs to, or absent when it has none.
+    fn require_collection(&self, state: &CoreAssetState) -> Result<()> {
+        require!(
+            self.collection.map(|collection| collection.key()) == state.collection,
+            errors::ErrorCode::InvalidCoreCollection
+        );
+        Ok(())
+    }
+
+    /**
+     * The collection account of an instruction, or the Core program ID standing in for an omitted
+     * optional account. Core writes to the collection when it manages plugins.
+     */
+    fn collection_meta(&self, is_writable: bool) -> AccountMeta {
+        match self.collection {
+            Some(collection) if is_writable => AccountMeta::new(collection.key(), false),
+            Some(collection) => AccountMeta::new_readonly(collection.key(), false),
+            None => AccountMeta::new_readonly(MPL_CORE_PROGRAM_ID, false),
+        }
+    }
+
+    /// Accounts shared by the plugin instructions; the optional log wrapper is omitted.
+    fn plugin_accounts(&self, authority: &AccountInfo<'info>) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new(self.asset.key(), false),
+            self.collection_meta(true),
+            AccountMeta::new(self.payer.key(), true),
+            AccountMeta::new_readonly(authority.key(), true),
+            AccountMeta::new_readonly(self.system_program.key(), false),
+            AccountMeta::new_readonly(MPL_CORE_PROGRAM_ID, false), // log wrapper
+        ]
+    }
+
+    fn invoke(
+        &self,
+        accounts: Vec<AccountMeta>,
+        data: Vec<u8>,
+        extra_infos: &[&AccountInfo<'info>],
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        let mut account_infos = vec![
+            self.asset.clone(),
+            self.payer.clone(),
+            self.system_program.clone(),
+            self.core_program.clone(),
+        ];
+        account_infos.extend(self.collection.cloned());
+        account_infos.extend(extra_infos.iter().map(|info| (*info).clone()));
+        invoke_signed(
+            &Instruction {
+                program_id: MPL_CORE_PROGRAM_ID,
+                accounts,
+                data,
+            },
+            &account_infos,
+            signer_seeds,
+        )?;
+        Ok(())
+    }
+}
+
+/**
+ * Creates a Metaplex Core asset owned by `owner`, with `payer` as its update authority.
+ * The new asset account must sign.
+ */
+pub fn create_core_asset<'info>(
+    asset: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    owner: &AccountInfo<'info>,
+    core_program: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    name: String,
+    uri: String,
+) -> Result<()> {
+    let accounts = vec![
+        AccountMeta::new(asset.key(), true),
+        AccountMeta::new_readonly(MPL_CORE_PROGRAM_ID, false), // collection
+        AccountMeta::new_readonly(MPL_CORE_PROGRAM_ID, false), // authority, defaults to payer
+        AccountMeta::new(payer.key(), true),
+        AccountMeta::new_readonly(owner.key(), false),
+        AccountMeta::new_readonly(payer.key(), false), // update authority
+        AccountMeta::new_readonly(system_program.key(), false),
+        AccountMeta::new_readonly(MPL_CORE_PROGRAM_ID, false), // log wrapper
+    ];
+    // CreateV1 args: data_state (account state), name, uri, plugins
+    let data = (CREATE_V1, 0u8, name, uri, None::<Vec<u8>>).try_to_vec()?;
+    invoke_signed(
+        &Instruction {
+            program_id: MPL_CORE_PROGRAM_ID,
+            accounts,
+            data,
+        },
+        &[
+            asset.clone(),
+            payer.clone(),
+            owner.clone(),
+            system_program.clone(),
+            core_program.clone(),
+        ],
+        &[],
+    )?;
+    Ok(())
+}
@@ -0,0 +1,125 @@
+use {
+    crate::{
+        constants::pda_constants::{ASSET_IMPAIRMENT_SEED, FRACTION_VAULT_SEED},
+        errors,
+        events::BuyoutNftClaimed,
+        instructions::nft_transfer::*,
+        state::{FractionVault, FractionVaultStatus},
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::{
+        associated_token::AssociatedToken,
+        token_interface::{Mint, TokenAccount, TokenInterface},
+    },
+};
+
+/**
+ * Allows the winner of a buyout auction to take the NFT out of the fraction vault.
+ * The winning bid stays in the fraction vault for the fraction holders to claim.
+ */
+#[derive(Accounts)]
+pub struct ClaimBuyoutNft<'info> {
+    #[account(mut)]
+    pub winner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [FRACTION_VAULT_SEED, nft_mint.key().as_ref()],
+        bump,
+        constraint = fraction_vault.status == FractionVaultStatus::Auction @ errors::ErrorCode::FractionVaultNotActive,
+        constraint = fraction_vault.is_bought_out(Clock::get()?.unix_timestamp) @ errors::ErrorCode::BuyoutNotComplete,
+        constraint = fraction_vault.top_bidder == Some(winner.key()) @ errors::ErrorCode::InvalidBuyoutWinner
+    )]
+    pub fraction_vault: Account<'info, FractionVault>,
+
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+
+    // Fraction vault's token account holding the NFT
+    #[account(
+        mut,
+        associated_token::mint = nft_mint,
+        associated_token::authority = fraction_vault,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_nft_account: InterfaceAccount<'info, TokenAccount>,
+
+    // Winner's token account receiving the NFT
+    #[account(
+        init_if_needed,
+        payer = winner,
+        associated_token::mint = nft_mint,
+        associated_token::authority = winner,
+        associated_token::token_program = token_program,
+    )]
+    pub winner_nft_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Curator who paid for the fraction vault's token account and receives its rent back
+    #[account(
+        mut,
+        address = fraction_vault.curator
+    )]
+    pub curator: UncheckedAccount<'info>,
+
+    /// CHECK: Impairment PDA of the NFT, which must not exist for the NFT to be claimed
+    #[account(
+        seeds = [ASSET_IMPAIRMENT_SEED, nft_mint.key().as_ref()],
+        bump,
+        constraint = asset_impairment.data_is_empty() @ errors::ErrorCode::NftImpaired
+    )]
+    pub asset_impairment: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+
+    // Token Metadata accounts, required only when the NFT is a programmable NFT
+    pub programmable: ProgrammableTransfer<'info>,
+}
+
+pub fn handle<'info>(ctx: Context<'_, '_, '_, 'info, ClaimBuyoutNft<'info>>) -> Result<()> {
+    let nft_mint_key = ctx.accounts.nft_mint.key();
+    let vault_bump = ctx.bumps.fraction_vault;
+    let seeds = &[FRACTION_VAULT_SEED, nft_mint_key.as_ref(), &[vault_bump]];
+    let signer = &[&seeds[..]];
+
+    // Transfer the NFT from the fraction vault to the winner
+    let programmable = ctx.accounts.programmable.transfer(
+        NftTransfer {
+            nft_mint: &ctx.accounts.nft_mint.to_account_info(),
+            source: &ctx.accounts.vault_nft_account.to_account_info(),
+            source_owner: &ctx.accounts.fraction_vault.to_account_info(),
+            destination: &ctx.accounts.winner_nft_account.to_account_info(),
+            destination_owner: &ctx.accounts.winner.to_account_info(),
+            payer: &ctx.accounts.winner.to_account_info(),
+            token_program: &ctx.accounts.token_program.to_account_info(),
+            associated_token_program: &ctx.accounts.associated_token_program.to_account_info(),
+            system_program: &ctx.accounts.system_program.to_account_info(),
+            additional_accounts: ctx.remaining_accounts,
+        },
+        signer,
+    )?;
+
+    // Close the fraction vault's token account, refunding the curator who paid for it
+    close_emptied_nft_account(
+        &mut ctx.accounts.vault_nft_account,
+        programmable,
+        &ctx.accounts.curator.to_account_info(),
+        &ctx.accounts.fraction_vault.to_account_info(),
+        &ctx.accounts.token_program.to_account_info(),
+        signer,
+    )?;
+
+    let fraction_vault = &mut ctx.accounts.fraction_vault;
+    fraction_vault.status = FractionVaultStatus::Released;
+
+    emit!(BuyoutNftClaimed {
+        fraction_vault: fraction_vault.key(),
+        nft_mint: nft_mint_key,
+        winner: ctx.accounts.winner.key(),
+        price: fraction_vault.top_bid,
+        redemption_requested: false,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,86 @@
+use {
+    crate::{
+        constants::pda_constants::FRACTION_VAULT_SEED, errors, events::BuyoutProceedsClaimed,
+        state::FractionVault,
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token::{Mint, Token, TokenAccount},
+};
+
+/**
+ * Allows a fraction holder to burn fractions for their share of the winning buyout bid, or of the
+ * price paid for the outstanding fractions when a supermajority redeemed the NFT.
+ * Each fraction pays an equal share of the proceeds not yet claimed, so the last fractions burned
+ * receive any rounding remainder.
+ */
+#[derive(Accounts)]
+pub struct ClaimBuyoutProceeds<'info> {
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [FRACTION_VAULT_SEED, fraction_vault.nft_mint.as_ref()],
+        bump,
+        constraint = fraction_vault.is_bought_out(Clock::get()?.unix_timestamp) @ errors::ErrorCode::BuyoutNotComplete
+    )]
+    pub fraction_vault: Account<'info, FractionVault>,
+
+    #[account(
+        mut,
+        address = fraction_vault.fraction_mint
+    )]
+    pub fraction_mint: Account<'info, Mint>,
+
+    // Holder's token account whose fractions are burned
+    #[account(
+        mut,
+        token::mint = fraction_mint,
+        token::authority = holder,
+    )]
+    pub holder_fraction_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handle(ctx: Context<ClaimBuyoutProceeds>, amount: u64) -> Result<()> {
+    require!(amount > 0, errors::ErrorCode::InvalidFractionAmount);
+
+    let outstanding = ctx.accounts.fraction_mint.supply;
+    let proceeds = ctx
+        .accounts
+        .fraction_vault
+        .proceeds_for(amount, outstanding)?;
+
+    anchor_spl::token::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            anchor_spl::token::Burn {
+                mint: ctx.accounts.fraction_mint.to_account_info(),
+                from: ctx.accounts.holder_fraction_account.to_account_info(),
+                authority: ctx.accounts.holder.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    // Pay the holder's share out of the proceeds held by the fraction vault
+    ctx.accounts.fraction_vault.sub_lamports(proceeds)?;
+    ctx.accounts.holder.add_lamports(proceeds)?;
+    let fraction_vault = &mut ctx.accounts.fraction_vault;
+    fraction_vault.proceeds_claimed = fraction_vault
+        .proceeds_claimed
+        .checked_add(proceeds)
+        .ok_or(errors::ErrorCode::CalculationError)?;
+
+    emit!(BuyoutProceedsClaimed {
+        fraction_vault: fraction_vault.key(),
+        nft_mint: fraction_vault.nft_mint,
+        holder: ctx.accounts.holder.key(),
+        fractions_burned: amount,
+        amount: proceeds,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,215 @@
+use {
+    crate::{
+        constants::pda_constants::{
+            ASSET_IMPAIRMENT_SEED, ASSET_REDEMPTION_INFO_SEED, FRACTION_MINT_SEED,
+            FRACTION_VAULT_SEED, LOAN_INFO_SEED,
+        },
+        errors,
+        events::NftFractionalized,
+        instructions::nft_transfer::*,
+        state::{AccountHeader, FractionVault, FractionVaultStatus},
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::{
+        associated_token::AssociatedToken,
+        token::{self, spl_token::instruction::AuthorityType, Token},
+        token_interface::{Mint, TokenAccount, TokenInterface},
+    },
+};
+
+/**
+ * Allows an NFT holder to fractionalize it.
+ * This instruction locks the NFT in the fraction vault, mints a fixed supply of fraction tokens to the
+ * holder, and sets the reserve price for a buyout of every fraction and the share of the supply needed
+ * to redeem the NFT. NFTs with an open loan, a redemption request or an impaired physical item cannot
+ * be fractionalized.
+ */
+#[derive(Accounts)]
+pub struct FractionalizeNft<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    // PDA holding the NFT and tracking its fractions and buyout auction
+    #[account(
+        init,
+        payer = owner,
+        space = FractionVault::INIT_SPACE,
+        seeds = [FRACTION_VAULT_SEED, nft_mint.key().as_ref()],
+        bump
+    )]
+    pub fraction_vault: Account<'info, FractionVault>,
+
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+
+    // Owner's token account holding the NFT
+    #[account(
+        mut,
+        associated_token::mint = nft_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program,
+        constraint = owner_nft_account.amount == 1 @ errors::ErrorCode::InvalidNFTAccount
+    )]
+    pub owner_nft_account: InterfaceAccount<'info, TokenAccount>,
+
+    // Fraction vault's token account that will hold the NFT
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = nft_mint,
+        associated_token::authority = fraction_vault,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_nft_account: InterfaceAccount<'info, TokenAccount>,
+
+    // Mint of the fraction tokens, with the fraction vault as its authority until the supply is minted
+    #[account(
+        init,
+        payer = owner,
+        seeds = [FRACTION_MINT_SEED, nft_mint.key().as_ref()],
+        bump,
+        mint::decimals = 0,
+        mint::authority = fraction_vault,
+        mint::token_program = fraction_token_program,
+    )]
+    pub fraction_mint: Account<'info, token::Mint>,
+
+    // Owner's token account receiving the fractions
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = fraction_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = fraction_token_program,
+    )]
+    pub owner_fraction_account: Account<'info, token::TokenAccount>,
+
+    /// CHECK: Loan PDA of the NFT, which must not exist for the NFT to be fractionalized
+    #[account(
+        seeds = [LOAN_INFO_SEED, nft_mint.key().as_ref()],
+        bump,
+        constraint = loan_info.data_is_empty() @ errors::ErrorCode::NftHasOpenLoan
+    )]
+    pub loan_info: UncheckedAccount<'info>,
+
+    /// CHECK: Redemption PDA of the NFT, which must not exist for the NFT to be fractionalized
+    #[account(
+        seeds = [ASSET_REDEMPTION_INFO_SEED, nft_mint.key().as_ref()],
+        bump,
+        constraint = asset_redemption_info.data_is_empty() @ errors::ErrorCode::NftHasRedemptionRequest
+    )]
+    pub asset_redemption_info: UncheckedAccount<'info>,
+
+    /// CHECK: Impairment PDA of the NFT, which must not exist for the NFT to be fractionalized
+    #[account(
+        seeds = [ASSET_IMPAIRMENT_SEED, nft_mint.key().as_ref()],
+        bump,
+        constraint = asset_impairment.data_is_empty() @ errors::ErrorCode::NftImpaired
+    )]
+    pub asset_impairment: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    // SPL Token program owning the fraction mint, which may differ from the NFT's token program
+    pub fraction_token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+
+    // Token Metadata accounts, required only when the NFT is a programmable NFT
+    pub programmable: ProgrammableTransfer<'info>,
+}
+
+pub fn handle<'info>(
+    ctx: Context<'_, '_, '_, 'info, FractionalizeNft<'info>>,
+    fraction_supply: u64,
+    reserve_price: u64,
+    redemption_threshold_bps: u16,
+) -> Result<()> {
+    require!(
+        fraction_supply > 0,
+        errors::ErrorCode::InvalidFractionSupply
+    );
+    require!(reserve_price > 0, errors::ErrorCode::InvalidReservePrice);
+    require!(
+        (FractionVault::MIN_REDEMPTION_THRESHOLD_BPS..=10_000).contains(&redemption_threshold_bps),
+        errors::ErrorCode::InvalidRedemptionThreshold
+    );
+
+    let created_at = Clock::get()?.unix_timestamp;
+    let fraction_vault = &mut ctx.accounts.fraction_vault;
+    fraction_vault.header = AccountHeader::new(FractionVault::VERSION);
+    fraction_vault.nft_mint = ctx.accounts.nft_mint.key();
+    fraction_vault.fraction_mint = ctx.accounts.fraction_mint.key();
+    fraction_vault.curator = ctx.accounts.owner.key();
+    fraction_vault.fraction_supply = fraction_supply;
+    fraction_vault.reserve_price = reserve_price;
+    fraction_vault.redemption_threshold_bps = redemption_threshold_bps;
+    fraction_vault.status = FractionVaultStatus::Active;
+    fraction_vault.auction_end = None;
+    fraction_vault.top_bidder = None;
+    fraction_vault.top_bid = 0;
+    fraction_vault.proceeds_claimed = 0;
+    fraction_vault.created_at = created_at;
+
+    // Lock the NFT in the fraction vault
+    ctx.accounts.programmable.transfer(
+        NftTransfer {
+            nft_mint: &ctx.accounts.nft_mint.to_account_info(),
+            source: &ctx.accounts.owner_nft_account.to_account_info(),
+            source_owner: &ctx.accounts.owner.to_account_info(),
+            destination: &ctx.accounts.vault_nft_account.to_account_info(),
+            destination_owner: &ctx.accounts.fraction_vault.to_account_info(),
+            payer: &ctx.accounts.owner.to_account_info(),
+            token_program: &ctx.accounts.token_program.to_account_info(),
+            associated_token_program: &ctx.accounts.associated_token_program.to_account_info(),
+            system_program: &ctx.accounts.system_program.to_account_info(),
+            additional_accounts: ctx.remaining_accounts,
+        },
+        &[],
+    )?;
+
+    // Mint the whole fraction supply to the owner
+    let nft_mint_key = ctx.accounts.nft_mint.key();
+    let vault_bump = ctx.bumps.fraction_vault;
+    let seeds = &[FRACTION_VAULT_SEED, nft_mint_key.as_ref(), &[vault_bump]];
+    let signer = &[&seeds[..]];
+
+    anchor_spl::token::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.fraction_token_program.to_account_info(),
+            anchor_spl::token::MintTo {
+                mint: ctx.accounts.fraction_mint.to_account_info(),
+                to: ctx.accounts.owner_fraction_account.to_account_info(),
+                authority: ctx.accounts.fraction_vault.to_account_info(),
+            },
+            signer,
+        ),
+        fraction_supply,
+    )?;
+
+    // Remove the mint authority so the fraction supply is fixed
+    anchor_spl::token::set_authority(
+        CpiContext::new_with_signer(
+            ctx.accounts.fraction_token_program.to_account_info(),
+            anchor_spl::token::SetAuthority {
+                current_authority: ctx.accounts.fraction_vault.to_account_info(),
+                account_or_mint: ctx.accounts.fraction_mint.to_account_info(),
+            },
+            signer,
+        ),
+        AuthorityType::MintTokens,
+        None,
+    )?;
+
+    emit!(NftFractionalized {
+        fraction_vault: ctx.accounts.fraction_vault.key(),
+        nft_mint: nft_mint_key,
+        fraction_mint: ctx.accounts.fraction_mint.key(),
+        curator: ctx.accounts.owner.key(),
+        fraction_supply,
+        reserve_price,
+        redemption_threshold_bps,
+        timestamp: created_at,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,6 @@
+pub mod claim_buyout_nft;
+pub mod claim_buyout_proceeds;
+pub mod fractionalize_nft;
+pub mod place_buyout_bid;
+pub mod redeem_buyout_nft;
+pub mod redeem_fractionalized_nft;
@@ -0,0 +1,104 @@
+use {
+    crate::{
+        constants::pda_constants::{ASSET_IMPAIRMENT_SEED, FRACTION_VAULT_SEED},
+        errors,
+        events::BuyoutBidPlaced,
+        state::{FractionVault, FractionVaultStatus},
+    },
+    anchor_lang::prelude::*,
+};
+
+/**
+ * Allows anyone to bid for every fraction of a fractionalized NFT.
+ * The opening bid must meet the reserve price and starts the buyout auction; later bids must raise the top
+ * bid by the minimum increment before the auction ends. The bid is held in the fraction vault and the
+ * previous top bidder is refunded.
+ */
+#[derive(Accounts)]
+pub struct PlaceBuyoutBid<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [FRACTION_VAULT_SEED, fraction_vault.nft_mint.as_ref()],
+        bump,
+        constraint = fraction_vault.status != FractionVaultStatus::Released @ errors::ErrorCode::FractionVaultNotActive
+    )]
+    pub fraction_vault: Account<'info, FractionVault>,
+
+    // Previous top bidder receiving their bid back, required once the auction has a bid
+    #[account(mut)]
+    /// CHECK: Verified against the fraction vault's top bidder in the handler
+    pub previous_bidder: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Impairment PDA of the NFT, which must not exist for the NFT to be bought out
+    #[account(
+        seeds = [ASSET_IMPAIRMENT_SEED, fraction_vault.nft_mint.as_ref()],
+        bump,
+        constraint = asset_impairment.data_is_empty() @ errors::ErrorCode::NftImpaired
+    )]
+    pub asset_impairment: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle(ctx: Context<PlaceBuyoutBid>, bid: u64) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let fraction_vault = &ctx.accounts.fraction_vault;
+    require!(
+        !matches!(fraction_vault.auction_end, Some(end) if now >= end),
+        errors::ErrorCode::BuyoutAuctionEnded
+    );
+    require!(
+        bid >= fraction_vault.minimum_bid(),
+        errors::ErrorCode::BidTooLow
+    );
+
+    // Hold the bid in the fraction vault
+    anchor_lang::system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.bidder.to_account_info(),
+                to: ctx.accounts.fraction_vault.to_account_info(),
+            },
+        ),
+        bid,
+    )?;
+
+    // Refund the bidder who was outbid
+    if let Some(top_bidder) = fraction_vault.top_bidder {
+        let previous_bidder = ctx
+            .accounts
+            .previous_bidder
+            .as_ref()
+            .filter(|previous_bidder| previous_bidder.key() == top_bidder)
+            .ok_or(errors::ErrorCode::InvalidPreviousBidder)?;
+        let refund = fraction_vault.top_bid;
+        ctx.accounts.fraction_vault.sub_lamports(refund)?;
+        previous_bidder.add_lamports(refund)?;
+    }
+
+    // The opening bid starts the auction clock
+    let fraction_vault = &mut ctx.accounts.fraction_vault;
+    let auction_end = match fraction_vault.auction_end {
+        Some(end) => end,
+        None => now + FractionVault::BUYOUT_AUCTION_DURATION,
+    };
+    fraction_vault.status = FractionVaultStatus::Auction;
+    fraction_vault.auction_end = Some(auction_end);
+    fraction_vault.top_bidder = Some(ctx.accounts.bidder.key());
+    fraction_vault.top_bid = bid;
+
+    emit!(BuyoutBidPlaced {
+        fraction_vault: fraction_vault.key(),
+        nft_mint: fraction_vault.nft_mint,
+        bidder: ctx.accounts.bidder.key(),
+        bid,
+        auction_end,
+        timestamp: now,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,161 @@
+use {
+    crate::{
+        constants::pda_constants::{
+            ASSET_IMPAIRMENT_SEED, ASSET_REDEMPTION_INFO_SEED, ASSET_REDEMPTION_VAULT_SEED,
+            FRACTION_VAULT_SEED,
+        },
+        errors,
+        events::{BuyoutNftClaimed, RedemptionRequested},
+        instructions::nft_transfer::*,
+        state::{AccountHeader, AssetRedemptionInfo, FractionVault, FractionVaultStatus},
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::{
+        associated_token::AssociatedToken,
+        token_interface::{Mint, TokenAccount, TokenInterface},
+    },
+};
+
+/**
+ * Allows the winner of a buyout auction to redeem the NFT for the physical asset.
+ * This instruction creates a redemption request in the winner's name and moves the NFT from the fraction
+ * vault into the asset redemption vault, as `create_asset_redemption_request` does for a held NFT.
+ */
+#[derive(Accounts)]
+pub struct RedeemBuyoutNft<'info> {
+    #[account(mut)]
+    pub winner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [FRACTION_VAULT_SEED, nft_mint.key().as_ref()],
+        bump,
+        constraint = fraction_vault.status == FractionVaultStatus::Auction @ errors::ErrorCode::FractionVaultNotActive,
+        constraint = fraction_vault.is_bought_out(Clock::get()?.unix_timestamp) @ errors::ErrorCode::BuyoutNotComplete,
+        constraint = fraction_vault.top_bidder == Some(winner.key()) @ errors::ErrorCode::InvalidBuyoutWinner
+    )]
+    pub fraction_vault: Account<'info, FractionVault>,
+
+    // PDA account storing the redemption request, with the winner as owner
+    #[account(
+        init,
+        payer = winner,
+        space = AssetRedemptionInfo::INIT_SPACE,
+        seeds = [ASSET_REDEMPTION_INFO_SEED, nft_mint.key().as_ref()],
+        bump
+    )]
+    pub asset_redemption_info: Account<'info, AssetRedemptionInfo>,
+
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+
+    // Fraction vault's token account holding the NFT
+    #[account(
+        mut,
+        associated_token::mint = nft_mint,
+        associated_token::authority = fraction_vault,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_nft_account: InterfaceAccount<'info, TokenAccount>,
+
+    // Program's asset redemption token account where the NFT will be held during the redemption request
+    #[account(
+        init_if_needed,
+        payer = winner,
+        associated_token::mint = nft_mint,
+        associated_token::authority = asset_redemption_vault,
+        associated_token::token_program = token_program,
+    )]
+    pub asset_redemption_nft_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: PDA for asset redemption authority
+    #[account(
+        seeds = [ASSET_REDEMPTION_VAULT_SEED],
+        bump
+    )]
+    pub asset_redemption_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Curator who paid for the fraction vault's token account and receives its rent back
+    #[account(
+        mut,
+        address = fraction_vault.curator
+    )]
+    pub curator: UncheckedAccount<'info>,
+
+    /// CHECK: Impairment PDA of the NFT, which must not exist for the NFT to be redeemed
+    #[account(
+        seeds = [ASSET_IMPAIRMENT_SEED, nft_mint.key().as_ref()],
+        bump,
+        constraint = asset_impairment.data_is_empty() @ errors::ErrorCode::NftImpaired
+    )]
+    pub asset_impairment: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+
+    // Token Metadata accounts, required only when the NFT is a programmable NFT
+    pub programmable: ProgrammableTransfer<'info>,
+}
+
+pub fn handle<'info>(ctx: Context<'_, '_, '_, 'info, RedeemBuyoutNft<'info>>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let asset_redemption_info = &mut ctx.accounts.asset_redemption_info;
+    asset_redemption_info.header = AccountHeader::new(AssetRedemptionInfo::VERSION);
+    asset_redemption_info.nft_mint = ctx.accounts.nft_mint.key();
+    asset_redemption_info.nft_owner = ctx.accounts.winner.key();
+    asset_redemption_info.request_timestamp = now;
+    asset_redemption_info.is_fulfilled = false;
+
+    let nft_mint_key = ctx.accounts.nft_mint.key();
+    let vault_bump = ctx.bumps.fraction_vault;
+    let seeds = &[FRACTION_VAULT_SEED, nft_mint_key.as_ref(), &[vault_bump]];
+    let signer = &[&seeds[..]];
+
+    // Transfer the NFT from the fraction vault to the asset redemption vault
+    let programmable = ctx.accounts.programmable.transfer(
+        NftTransfer {
+            nft_mint: &ctx.accounts.nft_mint.to_account_info(),
+            source: &ctx.accounts.vault_nft_account.to_account_info(),
+            source_owner: &ctx.accounts.fraction_vault.to_account_info(),
+            destination: &ctx.accounts.asset_redemption_nft_account.to_account_info(),
+            destination_owner: &ctx.accounts.asset_redemption_vault.to_account_info(),
+            payer: &ctx.accounts.winner.to_account_info(),
+            token_program: &ctx.accounts.token_program.to_account_info(),
+            associated_token_program: &ctx.accounts.associated_token_program.to_account_info(),
+            system_program: &ctx.accounts.system_program.to_account_info(),
+            additional_accounts: ctx.remaining_accounts,
+        },
+        signer,
+    )?;
+
+    // Close the fraction vault's token account, refunding the curator who paid for it
+    close_emptied_nft_account(
+        &mut ctx.accounts.vault_nft_account,
+        programmable,
+        &ctx.accounts.curator.to_account_info(),
+        &ctx.accounts.fraction_vault.to_account_info(),
+        &ctx.accounts.token_program.to_account_info(),
+        signer,
+    )?;
+
+    let fraction_vault = &mut ctx.accounts.fraction_vault;
+    fraction_vault.status = FractionVaultStatus::Released;
+
+    emit!(BuyoutNftClaimed {
+        fraction_vault: fraction_vault.key(),
+        nft_mint: nft_mint_key,
+        winner: ctx.accounts.winner.key(),
+        price: fraction_vault.top_bid,
+        redemption_requested: true,
+        timestamp: now,
+    });
+    emit!(RedemptionRequested {
+        asset_redemption_info: ctx.accounts.asset_redemption_info.key(),
+        nft_mint: nft_mint_key,
+        owner: ctx.accounts.winner.key(),
+        timestamp: now,
+    });
+
+    Ok(())
+}
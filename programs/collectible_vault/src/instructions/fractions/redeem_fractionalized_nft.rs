@@ -0,0 +1,239 @@
+use {
+    crate::{
+        constants::pda_constants::{
+            ASSET_IMPAIRMENT_SEED, ASSET_REDEMPTION_INFO_SEED, ASSET_REDEMPTION_VAULT_SEED,
+            FRACTION_VAULT_SEED,
+        },
+        errors,
+        events::{FractionalizedNftRedeemed, RedemptionRequested},
+        instructions::nft_transfer::*,
+        state::{AccountHeader, AssetRedemptionInfo, FractionVault, FractionVaultStatus},
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::{
+        associated_token::AssociatedToken,
+        token::{self, Token},
+        token_interface::{Mint, TokenAccount, TokenInterface},
+    },
+};
+
+/**
+ * Allows fraction holders who together hold the vault's redemption threshold to redeem a fractionalized NFT.
+ * Every co-signing holder burns all the fractions in the token account they pass, and the payer buys out
+ * the fractions left outstanding at the reserve price; their holders claim that payment with
+ * `claim_buyout_proceeds`. The NFT moves from the fraction vault into the asset redemption vault under a
+ * redemption request in the recipient's name.
+ * The holders are passed as the first remaining accounts, as `holder_count` pairs of the signing holder
+ * followed by their fraction token account, ahead of any transfer hook accounts.
+ */
+#[derive(Accounts)]
+pub struct RedeemFractionalizedNft<'info> {
+    // Pays for the redemption request and the buyout of the outstanding fractions
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [FRACTION_VAULT_SEED, nft_mint.key().as_ref()],
+        bump,
+        constraint = fraction_vault.status == FractionVaultStatus::Active @ errors::ErrorCode::FractionVaultNotActive
+    )]
+    pub fraction_vault: Account<'info, FractionVault>,
+
+    #[account(
+        mut,
+        address = fraction_vault.fraction_mint
+    )]
+    pub fraction_mint: Account<'info, token::Mint>,
+
+    // PDA account storing the redemption request, with the recipient as owner
+    #[account(
+        init,
+        payer = payer,
+        space = AssetRedemptionInfo::INIT_SPACE,
+        seeds = [ASSET_REDEMPTION_INFO_SEED, nft_mint.key().as_ref()],
+        bump
+    )]
+    pub asset_redemption_info: Account<'info, AssetRedemptionInfo>,
+
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+
+    // Fraction vault's token account holding the NFT
+    #[account(
+        mut,
+        associated_token::mint = nft_mint,
+        associated_token::authority = fraction_vault,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_nft_account: InterfaceAccount<'info, TokenAccount>,
+
+    // Program's asset redemption token account where the NFT will be held during the redemption request
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = nft_mint,
+        associated_token::authority = asset_redemption_vault,
+        associated_token::token_program = token_program,
+    )]
+    pub asset_redemption_nft_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: PDA for asset redemption authority
+    #[account(
+        seeds = [ASSET_REDEMPTION_VAULT_SEED],
+        bump
+    )]
+    pub asset_redemption_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Designated owner of the redemption request, who receives the physical asset
+    pub recipient: UncheckedAccount<'info>,
+
+    /// CHECK: Curator who paid for the fraction vault's token account and receives its rent back
+    #[account(
+        mut,
+        address = fraction_vault.curator
+    )]
+    pub curator: UncheckedAccount<'info>,
+
+    /// CHECK: Impairment PDA of the NFT, which must not exist for the NFT to be redeemed
+    #[account(
+        seeds = [ASSET_IMPAIRMENT_SEED, nft_mint.key().as_ref()],
+        bump,
+        constraint = asset_impairment.data_is_empty() @ errors::ErrorCode::NftImpaired
+    )]
+    pub asset_impairment: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    // SPL Token program owning the fraction mint, which may differ from the NFT's token program
+    pub fraction_token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+
+    // Token Metadata accounts, required only when the NFT is a programmable NFT
+    pub programmable: ProgrammableTransfer<'info>,
+}
+
+pub fn handle<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RedeemFractionalizedNft<'info>>,
+    holder_count: u8,
+) -> Result<()> {
+    let holder_accounts_len = holder_count as usize * 2;
+    require!(
+        ctx.remaining_accounts.len() >= holder_accounts_len,
+        errors::ErrorCode::InvalidFractionHolder
+    );
+    let (holder_accounts, remaining_accounts) =
+        ctx.remaining_accounts.split_at(holder_accounts_len);
+
+    // Burn every fraction held by the co-signing holders
+    let supply = ctx.accounts.fraction_mint.supply;
+    let mut fractions_burned: u64 = 0;
+    for pair in holder_accounts.chunks(2) {
+        let (holder, holder_fraction_account) = (&pair[0], &pair[1]);
+        require!(holder.is_signer, errors::ErrorCode::InvalidFractionHolder);
+        let fraction_account = Account::<token::TokenAccount>::try_from(holder_fraction_account)
+            .map_err(|_| error!(errors::ErrorCode::InvalidFractionHolder))?;
+        require!(
+            fraction_account.mint == ctx.accounts.fraction_mint.key()
+                && fraction_account.owner == holder.key(),
+            errors::ErrorCode::InvalidFractionHolder
+        );
+
+        anchor_spl::token::burn(
+            CpiContext::new(
+                ctx.accounts.fraction_token_program.to_account_info(),
+                anchor_spl::token::Burn {
+                    mint: ctx.accounts.fraction_mint.to_account_info(),
+                    from: holder_fraction_account.clone(),
+                    authority: holder.clone(),
+                },
+            ),
+            fraction_account.amount,
+        )?;
+        fractions_burned = fractions_burned
+            .checked_add(fraction_account.amount)
+            .ok_or(errors::ErrorCode::CalculationError)?;
+    }
+
+    let fraction_vault = &ctx.accounts.fraction_vault;
+    require!(
+        fractions_burned as u128 * 10_000
+            >= supply as u128 * fraction_vault.redemption_threshold_bps as u128,
+        errors::ErrorCode::RedemptionThresholdNotMet
+    );
+
+    // Buy out the fractions left outstanding; their holders claim the payment with claim_buyout_proceeds
+    let buyout_amount = fraction_vault.buyout_price(supply - fractions_burned);
+    anchor_lang::system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.payer.to_account_info(),
+                to: ctx.accounts.fraction_vault.to_account_info(),
+            },
+        ),
+        buyout_amount,
+    )?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let asset_redemption_info = &mut ctx.accounts.asset_redemption_info;
+    asset_redemption_info.header = AccountHeader::new(AssetRedemptionInfo::VERSION);
+    asset_redemption_info.nft_mint = ctx.accounts.nft_mint.key();
+    asset_redemption_info.nft_owner = ctx.accounts.recipient.key();
+    asset_redemption_info.request_timestamp = now;
+    asset_redemption_info.is_fulfilled = false;
+
+    let nft_mint_key = ctx.accounts.nft_mint.key();
+    let vault_bump = ctx.bumps.fraction_vault;
+    let seeds = &[FRACTION_VAULT_SEED, nft_mint_key.as_ref(), &[vault_bump]];
+    let signer = &[&seeds[..]];
+
+    // Transfer the NFT from the fraction vault to the asset redemption vault
+    let programmable = ctx.accounts.programmable.transfer(
+        NftTransfer {
+            nft_mint: &ctx.accounts.nft_mint.to_account_info(),
+            source: &ctx.accounts.vault_nft_account.to_account_info(),
+            source_owner: &ctx.accounts.fraction_vault.to_account_info(),
+            destination: &ctx.accounts.asset_redemption_nft_account.to_account_info(),
+            destination_owner: &ctx.accounts.asset_redemption_vault.to_account_info(),
+            payer: &ctx.accounts.payer.to_account_info(),
+            token_program: &ctx.accounts.token_program.to_account_info(),
+            associated_token_program: &ctx.accounts.associated_token_program.to_account_info(),
+            system_program: &ctx.accounts.system_program.to_account_info(),
+            additional_accounts: remaining_accounts,
+        },
+        signer,
+    )?;
+
+    // Close the fraction vault's token account, refunding the curator who paid for it
+    close_emptied_nft_account(
+        &mut ctx.accounts.vault_nft_account,
+        programmable,
+        &ctx.accounts.curator.to_account_info(),
+        &ctx.accounts.fraction_vault.to_account_info(),
+        &ctx.accounts.token_program.to_account_info(),
+        signer,
+    )?;
+
+    let fraction_vault = &mut ctx.accounts.fraction_vault;
+    fraction_vault.status = FractionVaultStatus::Released;
+    fraction_vault.top_bid = buyout_amount;
+    fraction_vault.proceeds_claimed = 0;
+
+    emit!(FractionalizedNftRedeemed {
+        fraction_vault: fraction_vault.key(),
+        nft_mint: nft_mint_key,
+        recipient: ctx.accounts.recipient.key(),
+        fractions_burned,
+        buyout_amount,
+        timestamp: now,
+    });
+    emit!(RedemptionRequested {
+        asset_redemption_info: ctx.accounts.asset_redemption_info.key(),
+        nft_mint: nft_mint_key,
+        owner: ctx.accounts.recipient.key(),
+        timestamp: now,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,169 @@
+use {
+    crate::{
+        constants::pda_constants::{
+            ASSET_IMPAIRMENT_SEED, FRACTION_VAULT_SEED, INSURANCE_RESERVE_SEED,
+        },
+        errors,
+        events::InsurancePayoutClaimed,
+        instructions::nft_burn::*,
+        state::{AssetImpairment, FractionVault, FractionVaultStatus, InsuranceReserve},
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+/**
+ * Permissionless crank that burns an impaired NFT held in its fraction vault for the insurance payout.
+ * No wallet holds such an NFT, so the payout goes to whoever owns it economically. Once a buyout
+ * auction has ended that is the winner, and the fraction holders keep the winning bid. Otherwise the
+ * fraction vault holds the payout, and the fraction holders burn their fractions for an equal share
+ * of it with `claim_buyout_proceeds` as after a buyout; a running auction's top bid is refunded.
+ * The rent of the NFT's closed token and metadata accounts goes to the curator.
+ */
+#[derive(Accounts)]
+pub struct ClaimFractionalizedInsurancePayout<'info> {
+    // Any wallet may crank the claim
+    pub cranker: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ASSET_IMPAIRMENT_SEED, nft_mint.key().as_ref()],
+        bump,
+        constraint = asset_impairment.claimed_by.is_none() @ errors::ErrorCode::InsuranceAlreadyClaimed
+    )]
+    pub asset_impairment: Account<'info, AssetImpairment>,
+
+    #[account(
+        mut,
+        seeds = [FRACTION_VAULT_SEED, nft_mint.key().as_ref()],
+        bump,
+        constraint = fraction_vault.status != FractionVaultStatus::Released @ errors::ErrorCode::FractionVaultNotActive
+    )]
+    pub fraction_vault: Account<'info, FractionVault>,
+
+    #[account(mut)]
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+
+    // Fraction vault's token account holding the NFT
+    #[account(
+        mut,
+        associated_token::mint = nft_mint,
+        associated_token::authority = fraction_vault,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_nft_account: InterfaceAccount<'info, TokenAccount>,
+
+    // Top bidder of the buyout auction, paid the payout or refunded their bid; required once the auction has a bid
+    #[account(mut)]
+    /// CHECK: Verified against the fraction vault's top bidder in the handler
+    pub top_bidder: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Curator who paid for the fraction vault's token account and receives its rent back
+    #[account(
+        mut,
+        address = fraction_vault.curator
+    )]
+    pub curator: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [INSURANCE_RESERVE_SEED],
+        bump
+    )]
+    pub insurance_reserve: Account<'info, InsuranceReserve>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+
+    // Token Metadata accounts of the NFT
+    pub metadata_burn: MetadataBurn<'info>,
+}
+
+pub fn handle(ctx: Context<ClaimFractionalizedInsurancePayout>) -> Result<()> {
+    let payout = ctx.accounts.asset_impairment.payout;
+    let reserve_info = ctx.accounts.insurance_reserve.to_account_info();
+    let available = reserve_info
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(reserve_info.data_len()));
+    require!(
+        available >= payout,
+        errors::ErrorCode::InsufficientInsuranceReserve
+    );
+
+    let fraction_vault = &ctx.accounts.fraction_vault;
+    let top_bidder = match fraction_vault.top_bidder {
+        Some(top_bidder) => Some(
+            ctx.accounts
+                .top_bidder
+                .as_ref()
+                .filter(|account| account.key() == top_bidder)
+                .ok_or(errors::ErrorCode::InvalidPreviousBidder)?
+                .to_account_info(),
+        ),
+        None => None,
+    };
+    let claimed_at = Clock::get()?.unix_timestamp;
+    let auction_won = fraction_vault.is_bought_out(claimed_at);
+
+    // Burn the NFT, passing on the rent of its closed accounts to the curator
+    let nft_mint_key = ctx.accounts.nft_mint.key();
+    let vault_bump = ctx.bumps.fraction_vault;
+    let seeds = &[FRACTION_VAULT_SEED, nft_mint_key.as_ref(), &[vault_bump]];
+    let vault_lamports = ctx.accounts.fraction_vault.get_lamports();
+    ctx.accounts.metadata_burn.burn(
+        NftBurn {
+            nft_mint: &ctx.accounts.nft_mint.to_account_info(),
+            token_account: &ctx.accounts.vault_nft_account.to_account_info(),
+            owner: &ctx.accounts.fraction_vault.to_account_info(),
+            token_program: &ctx.accounts.token_program.to_account_info(),
+            system_program: &ctx.accounts.system_program.to_account_info(),
+        },
+        &[&seeds[..]],
+    )?;
+    let reclaimed_rent = ctx
+        .accounts
+        .fraction_vault
+        .get_lamports()
+        .checked_sub(vault_lamports)
+        .ok_or(errors::ErrorCode::CalculationError)?;
+    ctx.accounts.fraction_vault.sub_lamports(reclaimed_rent)?;
+    ctx.accounts.curator.add_lamports(reclaimed_rent)?;
+
+    // Pay the winner, or refund any running auction's top bid and hold the payout in the fraction
+    // vault as proceeds for the fraction holders
+    let recipient = match top_bidder {
+        Some(winner) if auction_won => winner,
+        top_bidder => {
+            if let Some(top_bidder) = top_bidder {
+                let refund = ctx.accounts.fraction_vault.top_bid;
+                ctx.accounts.fraction_vault.sub_lamports(refund)?;
+                top_bidder.add_lamports(refund)?;
+            }
+            let fraction_vault = &mut ctx.accounts.fraction_vault;
+            fraction_vault.top_bidder = None;
+            fraction_vault.auction_end = None;
+            fraction_vault.top_bid = payout;
+            fraction_vault.to_account_info()
+        }
+    };
+    ctx.accounts.fraction_vault.status = FractionVaultStatus::Released;
+
+    // Pay the claim out of the insurance reserve
+    ctx.accounts.insurance_reserve.sub_lamports(payout)?;
+    recipient.add_lamports(payout)?;
+    ctx.accounts.insurance_reserve.record_payout(payout)?;
+
+    let asset_impairment = &mut ctx.accounts.asset_impairment;
+    asset_impairment.claimed_by = Some(recipient.key());
+    asset_impairment.claimed_at = Some(claimed_at);
+
+    emit!(InsurancePayoutClaimed {
+        asset_impairment: asset_impairment.key(),
+        nft_mint: asset_impairment.nft_mint,
+        holder: recipient.key(),
+        payout,
+        timestamp: claimed_at,
+    });
+
+    Ok(())
+}
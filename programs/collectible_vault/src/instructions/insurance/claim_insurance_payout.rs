@@ -0,0 +1,102 @@
+use {
+    crate::{
+        constants::pda_constants::{ASSET_IMPAIRMENT_SEED, INSURANCE_RESERVE_SEED},
+        errors,
+        events::InsurancePayoutClaimed,
+        instructions::nft_burn::*,
+        state::{AssetImpairment, InsuranceReserve},
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+/**
+ * Allows the holder of an impaired NFT to burn it for the insurance payout.
+ * This instruction burns the NFT through Token Metadata, closing its token account and Token Metadata
+ * accounts with their rent going to the holder, and pays the payout fixed when the item was declared
+ * impaired out of the insurance reserve. The reserve must hold the whole
+ * payout on top of its rent.
+ */
+#[derive(Accounts)]
+pub struct ClaimInsurancePayout<'info> {
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ASSET_IMPAIRMENT_SEED, nft_mint.key().as_ref()],
+        bump,
+        constraint = asset_impairment.claimed_by.is_none() @ errors::ErrorCode::InsuranceAlreadyClaimed
+    )]
+    pub asset_impairment: Account<'info, AssetImpairment>,
+
+    #[account(mut)]
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+
+    // Holder's token account holding the NFT
+    #[account(
+        mut,
+        associated_token::mint = nft_mint,
+        associated_token::authority = holder,
+        associated_token::token_program = token_program,
+        constraint = holder_nft_account.amount == 1 @ errors::ErrorCode::InvalidNFTAccount
+    )]
+    pub holder_nft_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [INSURANCE_RESERVE_SEED],
+        bump
+    )]
+    pub insurance_reserve: Account<'info, InsuranceReserve>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+
+    // Token Metadata accounts of the NFT
+    pub metadata_burn: MetadataBurn<'info>,
+}
+
+pub fn handle(ctx: Context<ClaimInsurancePayout>) -> Result<()> {
+    let payout = ctx.accounts.asset_impairment.payout;
+    let reserve_info = ctx.accounts.insurance_reserve.to_account_info();
+    let available = reserve_info
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(reserve_info.data_len()));
+    require!(
+        available >= payout,
+        errors::ErrorCode::InsufficientInsuranceReserve
+    );
+
+    // Burn the NFT, closing the holder's emptied token account and the NFT's metadata accounts
+    ctx.accounts.metadata_burn.burn(
+        NftBurn {
+            nft_mint: &ctx.accounts.nft_mint.to_account_info(),
+            token_account: &ctx.accounts.holder_nft_account.to_account_info(),
+            owner: &ctx.accounts.holder.to_account_info(),
+            token_program: &ctx.accounts.token_program.to_account_info(),
+            system_program: &ctx.accounts.system_program.to_account_info(),
+        },
+        &[],
+    )?;
+
+    // Pay the claim out of the insurance reserve
+    ctx.accounts.insurance_reserve.sub_lamports(payout)?;
+    ctx.accounts.holder.add_lamports(payout)?;
+    ctx.accounts.insurance_reserve.record_payout(payout)?;
+
+    let claimed_at = Clock::get()?.unix_timestamp;
+    let asset_impairment = &mut ctx.accounts.asset_impairment;
+    asset_impairment.claimed_by = Some(ctx.accounts.holder.key());
+    asset_impairment.claimed_at = Some(claimed_at);
+
+    emit!(InsurancePayoutClaimed {
+        asset_impairment: asset_impairment.key(),
+        nft_mint: asset_impairment.nft_mint,
+        holder: ctx.accounts.holder.key(),
+        payout,
+        timestamp: claimed_at,
+    });
+
+    Ok(())
+}
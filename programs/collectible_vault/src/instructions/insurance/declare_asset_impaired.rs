@@ -0,0 +1,68 @@
+use {
+    crate::{
+        constants::{
+            admin_constants::get_admin_account_pubkey,
+            pda_constants::{ASSET_APPRAISAL_SEED, ASSET_IMPAIRMENT_SEED},
+        },
+        errors,
+        events::AssetImpaired,
+        state::{AccountHeader, AssetAppraisal, AssetImpairment},
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token_interface::Mint,
+};
+
+/**
+ * Allows the admin to declare the physical item behind an NFT lost or damaged.
+ * This instruction blocks the NFT in the program and fixes its insurance payout at the last appraisal.
+ * The token account itself is not frozen: Token Metadata, the freeze authority of vaulted NFTs, only
+ * freezes through a delegate the holder approved, and the declaration cannot wait on the holder.
+ * The holder can still move the NFT between wallets, but no program instruction other than the
+ * insurance claim accepts it.
+ */
+#[derive(Accounts)]
+pub struct DeclareAssetImpaired<'info> {
+    #[account(mut, constraint = payer.key() == get_admin_account_pubkey() @ errors::ErrorCode::UnauthorizedTransactionSigner)]
+    pub payer: Signer<'info>,
+
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [ASSET_APPRAISAL_SEED, nft_mint.key().as_ref()],
+        bump
+    )]
+    pub asset_appraisal: Account<'info, AssetAppraisal>,
+
+    // PDA marking the NFT as impaired and tracking its insurance claim
+    #[account(
+        init,
+        payer = payer,
+        space = AssetImpairment::INIT_SPACE,
+        seeds = [ASSET_IMPAIRMENT_SEED, nft_mint.key().as_ref()],
+        bump
+    )]
+    pub asset_impairment: Account<'info, AssetImpairment>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle(ctx: Context<DeclareAssetImpaired>) -> Result<()> {
+    let declared_at = Clock::get()?.unix_timestamp;
+    let asset_impairment = &mut ctx.accounts.asset_impairment;
+    asset_impairment.header = AccountHeader::new(AssetImpairment::VERSION);
+    asset_impairment.nft_mint = ctx.accounts.nft_mint.key();
+    asset_impairment.payout = ctx.accounts.asset_appraisal.appraised_value;
+    asset_impairment.declared_at = declared_at;
+    asset_impairment.claimed_by = None;
+    asset_impairment.claimed_at = None;
+
+    emit!(AssetImpaired {
+        asset_impairment: asset_impairment.key(),
+        nft_mint: asset_impairment.nft_mint,
+        payout: asset_impairment.payout,
+        admin: ctx.accounts.payer.key(),
+        timestamp: declared_at,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use {
+    crate::{
+        constants::pda_constants::INSURANCE_RESERVE_SEED, errors, events::InsuranceReserveFunded,
+        state::InsuranceReserve,
+    },
+    anchor_lang::prelude::*,
+};
+
+/**
+ * Allows anyone to deposit lamports into the insurance reserve, on top of its share of fees.
+ */
+#[derive(Accounts)]
+pub struct FundInsuranceReserve<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [INSURANCE_RESERVE_SEED],
+        bump
+    )]
+    pub insurance_reserve: Account<'info, InsuranceReserve>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle(ctx: Context<FundInsuranceReserve>, amount: u64) -> Result<()> {
+    require!(amount > 0, errors::ErrorCode::InvalidInsuranceDeposit);
+
+    anchor_lang::system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.funder.to_account_info(),
+                to: ctx.accounts.insurance_reserve.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    emit!(InsuranceReserveFunded {
+        funder: ctx.accounts.funder.key(),
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,5 @@
+pub mod claim_fractionalized_insurance_payout;
+pub mod claim_insurance_payout;
+pub mod declare_asset_impaired;
+pub mod fund_insurance_reserve;
+pub mod record_appraisal;
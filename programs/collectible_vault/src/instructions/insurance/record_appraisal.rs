@@ -0,0 +1,57 @@
+use {
+    crate::{
+        constants::{
+            admin_constants::get_admin_account_pubkey, pda_constants::ASSET_APPRAISAL_SEED,
+        },
+        errors,
+        events::AssetAppraised,
+        state::{AccountHeader, AssetAppraisal},
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token_interface::Mint,
+};
+
+/**
+ * Allows the admin to record the appraised value of the physical item behind an NFT.
+ * This instruction creates the appraisal account the first time and overwrites it with each new appraisal.
+ */
+#[derive(Accounts)]
+pub struct RecordAppraisal<'info> {
+    #[account(mut, constraint = payer.key() == get_admin_account_pubkey() @ errors::ErrorCode::UnauthorizedTransactionSigner)]
+    pub payer: Signer<'info>,
+
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+
+    // PDA storing the last appraisal of the NFT's physical item
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = AssetAppraisal::INIT_SPACE,
+        seeds = [ASSET_APPRAISAL_SEED, nft_mint.key().as_ref()],
+        bump
+    )]
+    pub asset_appraisal: Account<'info, AssetAppraisal>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle(ctx: Context<RecordAppraisal>, appraised_value: u64) -> Result<()> {
+    require!(appraised_value > 0, errors::ErrorCode::InvalidAppraisal);
+
+    let appraised_at = Clock::get()?.unix_timestamp;
+    let asset_appraisal = &mut ctx.accounts.asset_appraisal;
+    asset_appraisal.header = AccountHeader::new(AssetAppraisal::VERSION);
+    asset_appraisal.nft_mint = ctx.accounts.nft_mint.key();
+    asset_appraisal.appraised_value = appraised_value;
+    asset_appraisal.appraised_at = appraised_at;
+
+    emit!(AssetAppraised {
+        asset_appraisal: asset_appraisal.key(),
+        nft_mint: asset_appraisal.nft_mint,
+        appraised_value,
+        admin: ctx.accounts.payer.key(),
+        timestamp: appraised_at,
+    });
+
+    Ok(())
+}
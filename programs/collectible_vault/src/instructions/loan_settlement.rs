@@ -0,0 +1,228 @@
+use {
+    crate::{
+        errors,
+        events::{DefaultRoyaltyPaid, LoanCancelled, LoanDefaulted, LoanRepaid},
+        instructions::royalty::RoyaltyPayment,
+        state::{BorrowerProfile, LoanInfo, LoanOutcome, LoanRecord, LoanStatus},
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token::{self, TokenAccount},
+};
+
+/**
+ * The program accounts updated when a loan is settled, whatever custody its collateral is held in.
+ * Settling marks the loan, archives its terms in the loan record, updates the borrower's reputation
+ * and emits the settlement events; the caller only releases or moves the collateral.
+ */
+pub struct LoanSettlement<'a, 'info> {
+    pub loan_info: &'a mut Account<'info, LoanInfo>,
+    pub loan_record: &'a mut Account<'info, LoanRecord>,
+    pub borrower_profile: &'a mut Account<'info, BorrowerProfile>,
+}
+
+impl<'info> LoanSettlement<'_, 'info> {
+    /**
+     * Pays the principal and interest from `payer` to the loan note holder `lender` and records
+     * the loan as repaid.
+     */
+    pub fn repay(
+        mut self,
+        payer: &AccountInfo<'info>,
+        lender: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+    ) -> Result<()> {
+        // Calculate total repayment amount (principal + interest)
+        let total_repayment = self
+            .loan_info
+            .loan_amount
+            .checked_add(self.loan_info.interest_amount)
+            .ok_or(errors::ErrorCode::CalculationError)?;
+
+        // Transfer repayment amount from the payer to the loan note holder
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                anchor_lang::system_program::Transfer {
+                    from: payer.clone(),
+                    to: lender.clone(),
+                },
+            ),
+            total_repayment,
+        )?;
+
+        let timestamp = self.archive(LoanStatus::Repaid, LoanOutcome::Repaid)?;
+        let loan_info = &self.loan_info;
+        self.borrower_profile
+            .record_repaid(loan_info.nft_owner, loan_info.loan_amount)?;
+
+        emit!(LoanRepaid {
+            loan_info: loan_info.key(),
+            nft_mint: loan_info.nft_mint,
+            loan_id: loan_info.loan_id,
+            borrower: loan_info.nft_owner,
+            payer: payer.key(),
+            lender: lender.key(),
+            repayment_amount: total_repayment,
+            timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Records the loan as defaulted to the loan note holder `lender`, along with any royalty it paid.
+    pub fn claim(mut self, lender: Pubkey, royalty: Option<RoyaltyPayment>) -> Result<()> {
+        let timestamp = self.archive(LoanStatus::Defaulted, LoanOutcome::Defaulted)?;
+        let loan_info = &self.loan_info;
+        self.borrower_profile
+            .record_defaulted(loan_info.nft_owner, loan_info.loan_amount)?;
+
+        if let Some(royalty) = royalty {
+            emit!(DefaultRoyaltyPaid {
+                loan_info: loan_info.key(),
+                nft_mint: loan_info.nft_mint,
+                loan_id: loan_info.loan_id,
+                lender,
+                basis_amount: loan_info.loan_amount,
+                seller_fee_basis_points: royalty.seller_fee_basis_points,
+                royalty_amount: royalty.amount,
+                timestamp,
+            });
+        }
+
+        emit!(LoanDefaulted {
+            loan_info: loan_info.key(),
+            nft_mint: loan_info.nft_mint,
+            loan_id: loan_info.loan_id,
+            borrower: loan_info.nft_owner,
+            lender,
+            loan_amount: loan_info.loan_amount,
+            interest_amount: loan_info.interest_amount,
+            timestamp,
+        });
+
+        Ok(())
+    }
+
+    /**
+     * Records an unfunded loan request as cancelled by `closed_by`; `expired` tells whether it was
+     * closed because its request expiry passed.
+     */
+    pub fn cancel(mut self, closed_by: Pubkey, expired: bool) -> Result<()> {
+        let timestamp = self.archive(LoanStatus::Cancelled, LoanOutcome::Cancelled)?;
+        let loan_info = &self.loan_info;
+        self.borrower_profile
+            .record_cancelled(loan_info.nft_owner)?;
+
+        emit!(LoanCancelled {
+            loan_info: loan_info.key(),
+            nft_mint: loan_info.nft_mint,
+            loan_id: loan_info.loan_id,
+            borrower: loan_info.nft_owner,
+            closed_by,
+            expired,
+            timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Marks the loan settled and archives its terms before the loan account is closed.
+    fn archive(&mut self, status: LoanStatus, outcome: LoanOutcome) -> Result<i64> {
+        let timestamp = Clock::get()?.unix_timestamp;
+        self.loan_info.status = status;
+        self.loan_record.archive(self.loan_info, outcome, timestamp);
+        Ok(timestamp)
+    }
+}
+
+/**
+ * The lender's position in a loan: the loan note whose holder is paid or claims the collateral, or,
+ * for a loan funded before loan notes existed, the lender recorded on the loan account.
+ * The loan note mint is empty for a loan without a loan note, in which case the note account is ignored.
+ */
+pub struct LoanNote<'a, 'info> {
+    pub loan_note_mint: &'a AccountInfo<'info>,
+    pub lender_note_account: &'a AccountInfo<'info>,
+    pub token_program: &'a AccountInfo<'info>,
+}
+
+impl<'info> LoanNote<'_, 'info> {
+    /// Returns whether the loan has a loan note.
+    pub fn exists(&self) -> bool {
+        !self.loan_note_mint.data_is_empty()
+    }
+
+    /// Checks that `lender` holds the lender's position in the loan.
+    pub fn verify_holder(&self, loan_info: &LoanInfo, lender: &Pubkey) -> Result<()> {
+        if !self.exists() {
+            require!(
+                loan_info.lender == Some(*lender),
+                errors::ErrorCode::InvalidLender
+            );
+            return Ok(());
+        }
+
+        require_keys_eq!(
+            *self.lender_note_account.owner,
+            token::ID,
+            errors::ErrorCode::InvalidLoanNoteAccount
+        );
+        let note =
+            TokenAccount::try_deserialize(&mut &self.lender_note_account.try_borrow_data()?[..])?;
+        require!(
+            note.mint == self.loan_note_mint.key() && note.amount == 1,
+            errors::ErrorCode::InvalidLoanNoteAccount
+        );
+        require_keys_eq!(note.owner, *lender, errors::ErrorCode::InvalidLender);
+        Ok(())
+    }
+
+    /**
+     * Freezes the holder's loan note once the loan is repaid without the holder's signature, so the
+     * settled note can no longer be traded as a live position.
+     */
+    pub fn freeze(
+        &self,
+        vault_authority: &AccountInfo<'info>,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        if !self.exists() {
+            return Ok(());
+        }
+        token::freeze_account(CpiContext::new_with_signer(
+            self.token_program.clone(),
+            token::FreezeAccount {
+                account: self.lender_note_account.clone(),
+                mint: self.loan_note_mint.clone(),
+                authority: vault_authority.clone(),
+            },
+            signer_seeds,
+        ))
+    }
+
+    /// Burns the loan note of `holder`, who signed the claim, and closes their emptied note account.
+    pub fn burn(&self, holder: &AccountInfo<'info>) -> Result<()> {
+        if !self.exists() {
+            return Ok(());
+        }
+        token::burn(
+            CpiContext::new(
+                self.token_program.clone(),
+                token::Burn {
+                    mint: self.loan_note_mint.clone(),
+                    from: self.lender_note_account.clone(),
+                    authority: holder.clone(),
+                },
+            ),
+            1,
+        )?;
+        token::close_account(CpiContext::new(
+            self.token_program.clone(),
+            token::CloseAccount {
+                account: self.lender_note_account.clone(),
+                destination: holder.clone(),
+                authority: holder.clone(),
+            },
+        ))
+    }
+}
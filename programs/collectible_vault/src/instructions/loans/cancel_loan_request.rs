@@ -1,13 +1,16 @@
 use {
     crate::{
-        constants::pda_constants::{LOAN_INFO_SEED, VAULT_SEED},
+        constants::pda_constants::{
+            BORROWER_PROFILE_SEED, LOAN_INFO_SEED, LOAN_RECORD_SEED, VAULT_SEED,
+        },
         errors,
-        state::LoanInfo,
+        instructions::{loan_settlement::LoanSettlement, nft_transfer::*},
+        state::{BorrowerProfile, LoanInfo, LoanRecord, LoanStatus},
     },
     anchor_lang::prelude::*,
     anchor_spl::{
         associated_token::AssociatedToken,
-        token::{Mint, Token, TokenAccount},
+        token_interface::{Mint, TokenAccount, TokenInterface},
     },
 };
 
@@ -26,26 +29,28 @@ pub struct CancelLoanRequest<'info> {
         bump,
         constraint = loan_info.nft_owner == owner.key() @ errors::ErrorCode::UnauthorizedLoanCancellation,
         constraint = loan_info.lender.is_none() @ errors::ErrorCode::LoanAlreadyFunded,
-        constraint = !loan_info.is_active @ errors::ErrorCode::LoanAlreadyActive,
+        constraint = loan_info.status == LoanStatus::Requested @ errors::ErrorCode::LoanAlreadyActive,
         close = owner
     )]
     pub loan_info: Account<'info, LoanInfo>,
 
-    pub nft_mint: Account<'info, Mint>,
+    pub nft_mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
         associated_token::mint = nft_mint,
         associated_token::authority = owner,
+        associated_token::token_program = token_program,
     )]
-    pub owner_nft_account: Account<'info, TokenAccount>,
+    pub owner_nft_account: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         mut,
         associated_token::mint = nft_mint,
         associated_token::authority = vault_authority,
+        associated_token::token_program = token_program,
     )]
-    pub vault_nft_account: Account<'info, TokenAccount>,
+    pub vault_nft_account: InterfaceAccount<'info, TokenAccount>,
 
     /// CHECK: PDA for vault authority
     #[account(
@@ -54,40 +59,73 @@ pub struct CancelLoanRequest<'info> {
     )]
     pub vault_authority: UncheckedAccount<'info>,
 
-    pub token_program: Program<'info, Token>,
+    // Archive of the loan retained after the loan account is closed
+    #[account(
+        init,
+        payer = owner,
+        space = LoanRecord::INIT_SPACE,
+        seeds = [LOAN_RECORD_SEED, nft_mint.key().as_ref(), &loan_info.loan_id.to_le_bytes()],
+        bump
+    )]
+    pub loan_record: Account<'info, LoanRecord>,
+
+    // Reputation profile of the borrower, updated with the outcome of this loan
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = BorrowerProfile::INIT_SPACE,
+        seeds = [BORROWER_PROFILE_SEED, loan_info.nft_owner.as_ref()],
+        bump
+    )]
+    pub borrower_profile: Account<'info, BorrowerProfile>,
+
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
+
+    // Token Metadata accounts, required only when the NFT is a programmable NFT
+    pub programmable: ProgrammableTransfer<'info>,
 }
 
-pub fn handle(ctx: Context<CancelLoanRequest>) -> Result<()> {
+pub fn handle<'info>(ctx: Context<'_, '_, '_, 'info, CancelLoanRequest<'info>>) -> Result<()> {
+    // Archive the cancelled request and update the borrower's reputation
+    LoanSettlement {
+        loan_info: &mut ctx.accounts.loan_info,
+        loan_record: &mut ctx.accounts.loan_record,
+        borrower_profile: &mut ctx.accounts.borrower_profile,
+    }
+    .cancel(ctx.accounts.owner.key(), false)?;
+
     let vault_bump = ctx.bumps.vault_authority;
-    let seeds = &[VAULT_SEED.as_ref(), &[vault_bump]];
+    let seeds = &[VAULT_SEED, &[vault_bump]];
     let signer = &[&seeds[..]];
 
     // Transfer NFT back to owner
-    anchor_spl::token::transfer(
-        CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            anchor_spl::token::Transfer {
-                from: ctx.accounts.vault_nft_account.to_account_info(),
-                to: ctx.accounts.owner_nft_account.to_account_info(),
-                authority: ctx.accounts.vault_authority.to_account_info(),
-            },
-            signer,
-        ),
-        1,
+    let programmable = ctx.accounts.programmable.transfer(
+        NftTransfer {
+            nft_mint: &ctx.accounts.nft_mint.to_account_info(),
+            source: &ctx.accounts.vault_nft_account.to_account_info(),
+            source_owner: &ctx.accounts.vault_authority.to_account_info(),
+            destination: &ctx.accounts.owner_nft_account.to_account_info(),
+            destination_owner: &ctx.accounts.owner.to_account_info(),
+            payer: &ctx.accounts.owner.to_account_info(),
+            token_program: &ctx.accounts.token_program.to_account_info(),
+            associated_token_program: &ctx.accounts.associated_token_program.to_account_info(),
+            system_program: &ctx.accounts.system_program.to_account_info(),
+            additional_accounts: ctx.remaining_accounts,
+        },
+        signer,
     )?;
 
     // Close the vault's token account
-    anchor_spl::token::close_account(CpiContext::new_with_signer(
-        ctx.accounts.token_program.to_account_info(),
-        anchor_spl::token::CloseAccount {
-            account: ctx.accounts.vault_nft_account.to_account_info(),
-            destination: ctx.accounts.owner.to_account_info(),
-            authority: ctx.accounts.vault_authority.to_account_info(),
-        },
+    close_emptied_nft_account(
+        &mut ctx.accounts.vault_nft_account,
+        programmable,
+        &ctx.accounts.owner.to_account_info(),
+        &ctx.accounts.vault_authority.to_account_info(),
+        &ctx.accounts.token_program.to_account_info(),
         signer,
-    ))?;
+    )?;
 
     Ok(())
 }
@@ -0,0 +1,127 @@
+use {
+    crate::{
+        constants::pda_constants::{
+            BORROWER_PROFILE_SEED, LOAN_INFO_SEED, LOAN_RECORD_SEED, VAULT_SEED,
+        },
+        errors,
+        instructions::{loan_settlement::LoanSettlement, nft_lock::DelegatedFreeze},
+        state::{BorrowerProfile, LoanInfo, LoanRecord, LoanStatus},
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token::{Mint, Token, TokenAccount},
+    mpl_token_metadata::ID as TOKEN_METADATA_PROGRAM_ID,
+};
+
+/**
+ * Allows a borrower to cancel a loan request, made without handing over their NFT, before it's funded.
+ * This instruction thaws the NFT and revokes the vault authority's delegation over it.
+ */
+#[derive(Accounts)]
+pub struct CancelLockedLoanRequest<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [LOAN_INFO_SEED, nft_mint.key().as_ref()],
+        bump,
+        constraint = loan_info.nft_owner == owner.key() @ errors::ErrorCode::UnauthorizedLoanCancellation,
+        constraint = loan_info.lender.is_none() @ errors::ErrorCode::LoanAlreadyFunded,
+        constraint = loan_info.status == LoanStatus::Requested @ errors::ErrorCode::LoanAlreadyActive,
+        close = owner
+    )]
+    pub loan_info: Account<'info, LoanInfo>,
+
+    pub nft_mint: Account<'info, Mint>,
+
+    // Owner's token account where the NFT is frozen
+    #[account(
+        mut,
+        associated_token::mint = nft_mint,
+        associated_token::authority = owner,
+        constraint = owner_nft_account.amount == 1 @ errors::ErrorCode::InvalidNFTAccount
+    )]
+    pub owner_nft_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Master edition of the NFT, which holds the mint's freeze authority
+    #[account(
+        seeds = [
+            b"metadata",
+            TOKEN_METADATA_PROGRAM_ID.as_ref(),
+            nft_mint.key().as_ref(),
+            b"edition",
+        ],
+        bump,
+        seeds::program = TOKEN_METADATA_PROGRAM_ID,
+    )]
+    pub nft_edition: UncheckedAccount<'info>,
+
+    /// CHECK: PDA for vault authority, the delegate that froze the NFT
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    // Archive of the loan retained after the loan account is closed
+    #[account(
+        init,
+        payer = owner,
+        space = LoanRecord::INIT_SPACE,
+        seeds = [LOAN_RECORD_SEED, nft_mint.key().as_ref(), &loan_info.loan_id.to_le_bytes()],
+        bump
+    )]
+    pub loan_record: Account<'info, LoanRecord>,
+
+    // Reputation profile of the borrower, updated with the outcome of this loan
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = BorrowerProfile::INIT_SPACE,
+        seeds = [BORROWER_PROFILE_SEED, loan_info.nft_owner.as_ref()],
+        bump
+    )]
+    pub borrower_profile: Account<'info, BorrowerProfile>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Metaplex program ID check
+    #[account(address = TOKEN_METADATA_PROGRAM_ID)]
+    pub token_metadata_program: UncheckedAccount<'info>,
+}
+
+pub fn handle(ctx: Context<CancelLockedLoanRequest>) -> Result<()> {
+    // Archive the cancelled request and update the borrower's reputation
+    LoanSettlement {
+        loan_info: &mut ctx.accounts.loan_info,
+        loan_record: &mut ctx.accounts.loan_record,
+        borrower_profile: &mut ctx.accounts.borrower_profile,
+    }
+    .cancel(ctx.accounts.owner.key(), false)?;
+
+    // Thaw the NFT in the owner's wallet
+    let vault_bump = ctx.bumps.vault_authority;
+    let seeds = &[VAULT_SEED, &[vault_bump]];
+    DelegatedFreeze {
+        delegate: &ctx.accounts.vault_authority.to_account_info(),
+        token_account: &ctx.accounts.owner_nft_account.to_account_info(),
+        edition: &ctx.accounts.nft_edition.to_account_info(),
+        nft_mint: &ctx.accounts.nft_mint.to_account_info(),
+        token_program: &ctx.accounts.token_program.to_account_info(),
+        token_metadata_program: &ctx.accounts.token_metadata_program.to_account_info(),
+    }
+    .thaw(&[&seeds[..]])?;
+
+    // The owner signs here, so the vault authority's delegation can be dropped as well
+    anchor_spl::token::revoke(CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        anchor_spl::token::Revoke {
+            source: ctx.accounts.owner_nft_account.to_account_info(),
+            authority: ctx.accounts.owner.to_account_info(),
+        },
+    ))?;
+
+    Ok(())
+}
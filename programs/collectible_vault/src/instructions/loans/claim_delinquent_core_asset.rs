@@ -0,0 +1,137 @@
+use {
+    crate::{
+        constants::pda_constants::{
+            BORROWER_PROFILE_SEED, LOAN_INFO_SEED, LOAN_NOTE_SEED, LOAN_RECORD_SEED, VAULT_SEED,
+        },
+        errors,
+        instructions::{
+            core_asset::{CoreAsset, MplCore, MPL_CORE_PROGRAM_ID},
+            loan_settlement::{LoanNote, LoanSettlement},
+        },
+        state::{BorrowerProfile, LoanInfo, LoanRecord, LoanStatus},
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token::Token,
+};
+
+/**
+ * Allows lenders to claim a Metaplex Core asset held as collateral when a loan is not repaid on time.
+ * This instruction closes the loan account and transfers the asset to the current loan note holder as its transfer delegate.
+ * The settled loan note is burned.
+ */
+#[derive(Accounts)]
+pub struct ClaimDelinquentCoreAsset<'info> {
+    // Loan account that will be closed after claiming the asset
+    #[account(
+        mut,
+        seeds = [LOAN_INFO_SEED, asset.key().as_ref()],
+        bump,
+        constraint = loan_info.status == LoanStatus::Active @ errors::ErrorCode::LoanNotActive,
+        constraint = loan_info.is_overdue(Clock::get()?.unix_timestamp) @ errors::ErrorCode::LoanNotExpired,
+        close = lender // Close the loan account and return rent to lender
+    )]
+    pub loan_info: Account<'info, LoanInfo>,
+
+    // The Core asset that was used as collateral
+    #[account(mut, owner = MPL_CORE_PROGRAM_ID)]
+    /// CHECK: Matched to the loan account through its seeds
+    pub asset: UncheckedAccount<'info>,
+
+    // Collection of the asset, required when the asset belongs to one
+    #[account(mut)]
+    /// CHECK: Checked against the asset's update authority before it is passed to Core
+    pub collection: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: PDA for vault authority
+    #[account(
+        seeds = [VAULT_SEED],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    // Loan note mint representing the lender's position in this loan
+    #[account(
+        mut,
+        seeds = [LOAN_NOTE_SEED, asset.key().as_ref(), &loan_info.loan_id.to_le_bytes()],
+        bump
+    )]
+    /// CHECK: PDA verified by seeds; an empty account means the loan was funded before loan notes and has none
+    pub loan_note_mint: UncheckedAccount<'info>,
+
+    // Token account of the current loan note holder, ignored when the loan has no loan note
+    #[account(mut)]
+    /// CHECK: We verify the loan note mint, balance and holder in the handler
+    pub lender_note_account: UncheckedAccount<'info>,
+
+    // Current loan note holder's account that will receive the asset
+    #[account(mut)]
+    pub lender: Signer<'info>,
+
+    // Archive of the loan retained after the loan account is closed
+    #[account(
+        init,
+        payer = lender,
+        space = LoanRecord::INIT_SPACE,
+        seeds = [LOAN_RECORD_SEED, asset.key().as_ref(), &loan_info.loan_id.to_le_bytes()],
+        bump
+    )]
+    pub loan_record: Account<'info, LoanRecord>,
+
+    // Reputation profile of the borrower, updated with the outcome of this loan
+    #[account(
+        init_if_needed,
+        payer = lender,
+        space = BorrowerProfile::INIT_SPACE,
+        seeds = [BORROWER_PROFILE_SEED, loan_info.nft_owner.as_ref()],
+        bump
+    )]
+    pub borrower_profile: Account<'info, BorrowerProfile>,
+
+    // SPL Token program owning the loan note
+    pub token_program: Program<'info, Token>,
+    pub core_program: Program<'info, MplCore>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle(ctx: Context<ClaimDelinquentCoreAsset>) -> Result<()> {
+    // Check that the lender holds the loan note, or is the recorded lender of a loan without one
+    let loan_note = LoanNote {
+        loan_note_mint: &ctx.accounts.loan_note_mint.to_account_info(),
+        lender_note_account: &ctx.accounts.lender_note_account.to_account_info(),
+        token_program: &ctx.accounts.token_program.to_account_info(),
+    };
+    loan_note.verify_holder(&ctx.accounts.loan_info, &ctx.accounts.lender.key())?;
+
+    // Archive the defaulted loan and update the borrower's reputation
+    LoanSettlement {
+        loan_info: &mut ctx.accounts.loan_info,
+        loan_record: &mut ctx.accounts.loan_record,
+        borrower_profile: &mut ctx.accounts.borrower_profile,
+    }
+    .claim(ctx.accounts.lender.key(), None)?;
+
+    // Thaw the asset and move it from the borrower's wallet to the lender
+    let vault_bump = ctx.bumps.vault_authority;
+    let seeds = &[VAULT_SEED, &[vault_bump]];
+    CoreAsset {
+        asset: &ctx.accounts.asset.to_account_info(),
+        collection: ctx
+            .accounts
+            .collection
+            .as_ref()
+            .map(|account| account.as_ref()),
+        payer: &ctx.accounts.lender.to_account_info(),
+        core_program: &ctx.accounts.core_program.to_account_info(),
+        system_program: &ctx.accounts.system_program.to_account_info(),
+    }
+    .seize(
+        &ctx.accounts.vault_authority.to_account_info(),
+        &ctx.accounts.lender.to_account_info(),
+        &[&seeds[..]],
+    )?;
+
+    // Burn the settled loan note and close the holder's note account
+    loan_note.burn(&ctx.accounts.lender.to_account_info())?;
+
+    Ok(())
+}
@@ -0,0 +1,218 @@
+use {
+    crate::{
+        constants::pda_constants::{
+            BORROWER_PROFILE_SEED, LOAN_INFO_SEED, LOAN_NOTE_SEED, LOAN_RECORD_SEED,
+            VAULT_CONFIG_SEED, VAULT_SEED,
+        },
+        errors,
+        instructions::{
+            loan_settlement::{LoanNote, LoanSettlement},
+            nft_lock::DelegatedFreeze,
+            royalty::DefaultRoyalty,
+        },
+        state::{BorrowerProfile, LoanInfo, LoanRecord, LoanStatus},
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::{
+        associated_token::AssociatedToken,
+        token::{Mint, Token, TokenAccount},
+    },
+    mpl_token_metadata::ID as TOKEN_METADATA_PROGRAM_ID,
+};
+
+/**
+ * Allows lenders to claim NFT collateral frozen in the borrower's wallet when a loan is not repaid on time.
+ * This instruction closes the loan account, thaws the NFT and moves it, as the token account's delegate,
+ * to the current loan note holder, burning the settled loan note.
+ * When the vault config enables default royalties, the lender also pays the NFT's royalty on the principal.
+ */
+#[derive(Accounts)]
+pub struct ClaimDelinquentLockedNft<'info> {
+    // Loan account that will be closed after claiming the NFT
+    #[account(
+        mut,
+        seeds = [LOAN_INFO_SEED, nft_mint.key().as_ref()],
+        bump,
+        constraint = loan_info.status == LoanStatus::Active @ errors::ErrorCode::LoanNotActive,
+        constraint = loan_info.is_overdue(Clock::get()?.unix_timestamp) @ errors::ErrorCode::LoanNotExpired,
+        close = lender // Close the loan account and return rent to lender
+    )]
+    pub loan_info: Account<'info, LoanInfo>,
+
+    // The NFT that was used as collateral
+    pub nft_mint: Account<'info, Mint>,
+
+    // Borrower who defaulted on the loan
+    #[account(
+        constraint = borrower.key() == loan_info.nft_owner @ errors::ErrorCode::InvalidBorrower
+    )]
+    /// CHECK: We verify this is the NFT owner in the constraint
+    pub borrower: UncheckedAccount<'info>,
+
+    // Borrower's token account where the NFT is frozen
+    #[account(
+        mut,
+        associated_token::mint = nft_mint,
+        associated_token::authority = borrower,
+        constraint = borrower_nft_account.amount == 1 @ errors::ErrorCode::InvalidNFTAccount
+    )]
+    pub borrower_nft_account: Account<'info, TokenAccount>,
+
+    // Lender's token account where the NFT will be transferred
+    #[account(
+        init_if_needed,
+        payer = lender,
+        associated_token::mint = nft_mint,
+        associated_token::authority = lender,
+    )]
+    pub lender_nft_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Master edition of the NFT, which holds the mint's freeze authority
+    #[account(
+        seeds = [
+            b"metadata",
+            TOKEN_METADATA_PROGRAM_ID.as_ref(),
+            nft_mint.key().as_ref(),
+            b"edition",
+        ],
+        bump,
+        seeds::program = TOKEN_METADATA_PROGRAM_ID,
+    )]
+    pub nft_edition: UncheckedAccount<'info>,
+
+    /// CHECK: PDA for vault authority, the delegate that froze the NFT
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    // Loan note mint representing the lender's position in this loan
+    #[account(
+        mut,
+        seeds = [LOAN_NOTE_SEED, nft_mint.key().as_ref(), &loan_info.loan_id.to_le_bytes()],
+        bump
+    )]
+    /// CHECK: PDA verified by seeds; an empty account means the loan was funded before loan notes and has none
+    pub loan_note_mint: UncheckedAccount<'info>,
+
+    // Token account of the current loan note holder, ignored when the loan has no loan note
+    #[account(mut)]
+    /// CHECK: We verify the loan note mint, balance and holder in the handler
+    pub lender_note_account: UncheckedAccount<'info>,
+
+    // Current loan note holder's account that will receive the NFT
+    #[account(mut)]
+    pub lender: Signer<'info>,
+
+    // Archive of the loan retained after the loan account is closed
+    #[account(
+        init,
+        payer = lender,
+        space = LoanRecord::INIT_SPACE,
+        seeds = [LOAN_RECORD_SEED, nft_mint.key().as_ref(), &loan_info.loan_id.to_le_bytes()],
+        bump
+    )]
+    pub loan_record: Account<'info, LoanRecord>,
+
+    // Reputation profile of the borrower, updated with the outcome of this loan
+    #[account(
+        init_if_needed,
+        payer = lender,
+        space = BorrowerProfile::INIT_SPACE,
+        seeds = [BORROWER_PROFILE_SEED, loan_info.nft_owner.as_ref()],
+        bump
+    )]
+    pub borrower_profile: Account<'info, BorrowerProfile>,
+
+    /// CHECK: Vault config PDA; default royalties are off until the admin creates it
+    #[account(
+        seeds = [VAULT_CONFIG_SEED],
+        bump
+    )]
+    pub vault_config: UncheckedAccount<'info>,
+
+    /// CHECK: Token Metadata account of the NFT, read for its royalty
+    #[account(
+        seeds = [
+            b"metadata",
+            TOKEN_METADATA_PROGRAM_ID.as_ref(),
+            nft_mint.key().as_ref(),
+        ],
+        bump,
+        seeds::program = TOKEN_METADATA_PROGRAM_ID,
+    )]
+    pub nft_metadata: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Metaplex program ID check
+    #[account(address = TOKEN_METADATA_PROGRAM_ID)]
+    pub token_metadata_program: UncheckedAccount<'info>,
+}
+
+pub fn handle<'info>(
+    ctx: Context<'_, '_, '_, 'info, ClaimDelinquentLockedNft<'info>>,
+) -> Result<()> {
+    // Check that the lender holds the loan note, or is the recorded lender of a loan without one
+    let loan_note = LoanNote {
+        loan_note_mint: &ctx.accounts.loan_note_mint.to_account_info(),
+        lender_note_account: &ctx.accounts.lender_note_account.to_account_info(),
+        token_program: &ctx.accounts.token_program.to_account_info(),
+    };
+    loan_note.verify_holder(&ctx.accounts.loan_info, &ctx.accounts.lender.key())?;
+
+    // Charge the lender the NFT's royalty on the principal when enabled, paying the metadata
+    // creators passed as remaining accounts
+    let (royalty, _) = DefaultRoyalty {
+        vault_config: &ctx.accounts.vault_config.to_account_info(),
+        nft_metadata: &ctx.accounts.nft_metadata.to_account_info(),
+        lender: &ctx.accounts.lender.to_account_info(),
+        system_program: &ctx.accounts.system_program.to_account_info(),
+    }
+    .pay(ctx.accounts.loan_info.loan_amount, ctx.remaining_accounts)?;
+
+    // Archive the defaulted loan and update the borrower's reputation
+    LoanSettlement {
+        loan_info: &mut ctx.accounts.loan_info,
+        loan_record: &mut ctx.accounts.loan_record,
+        borrower_profile: &mut ctx.accounts.borrower_profile,
+    }
+    .claim(ctx.accounts.lender.key(), royalty)?;
+
+    let vault_bump = ctx.bumps.vault_authority;
+    let seeds = &[VAULT_SEED, &[vault_bump]];
+    let signer = &[&seeds[..]];
+
+    // Thaw the NFT, then move it from the borrower's wallet to the lender as the account's delegate
+    DelegatedFreeze {
+        delegate: &ctx.accounts.vault_authority.to_account_info(),
+        token_account: &ctx.accounts.borrower_nft_account.to_account_info(),
+        edition: &ctx.accounts.nft_edition.to_account_info(),
+        nft_mint: &ctx.accounts.nft_mint.to_account_info(),
+        token_program: &ctx.accounts.token_program.to_account_info(),
+        token_metadata_program: &ctx.accounts.token_metadata_program.to_account_info(),
+    }
+    .thaw(signer)?;
+
+    anchor_spl::token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            anchor_spl::token::Transfer {
+                from: ctx.accounts.borrower_nft_account.to_account_info(),
+                to: ctx.accounts.lender_nft_account.to_account_info(),
+                authority: ctx.accounts.vault_authority.to_account_info(),
+            },
+            signer,
+        ),
+        1,
+    )?;
+
+    // Burn the settled loan note and close the holder's note account
+    loan_note.burn(&ctx.accounts.lender.to_account_info())?;
+
+    Ok(())
+}
@@ -1,19 +1,32 @@
 use {
     crate::{
-        constants::pda_constants::{LOAN_INFO_SEED, VAULT_SEED},
+        constants::pda_constants::{
+            BORROWER_PROFILE_SEED, LOAN_INFO_SEED, LOAN_NOTE_SEED, LOAN_RECORD_SEED,
+            VAULT_CONFIG_SEED, VAULT_SEED,
+        },
         errors,
-        state::LoanInfo,
+        instructions::{
+            loan_settlement::{LoanNote, LoanSettlement},
+            nft_transfer::*,
+            royalty::DefaultRoyalty,
+        },
+        state::{BorrowerProfile, LoanInfo, LoanRecord, LoanStatus},
     },
     anchor_lang::prelude::*,
     anchor_spl::{
         associated_token::AssociatedToken,
-        token::{Mint, Token, TokenAccount},
+        token::Token,
+        token_interface::{Mint, TokenAccount, TokenInterface},
     },
+    mpl_token_metadata::ID as TOKEN_METADATA_PROGRAM_ID,
 };
 
 /**
  * Allows lenders to claim the NFT collateral when a loan is not repaid on time.
- * This instruction closes the loan account and transfers the NFT to the lender.
+ * This instruction closes the loan account, transfers the NFT to the current loan note holder and burns the note.
+ * A loan funded before loan notes is claimed by its recorded lender.
+ * The emptied vault token account is closed as well, refunding its rent to the borrower who paid for it.
+ * When the vault config enables default royalties, the lender also pays the NFT's royalty on the principal.
  */
 #[derive(Accounts)]
 pub struct ClaimDelinquentNft<'info> {
@@ -22,32 +35,41 @@ pub struct ClaimDelinquentNft<'info> {
         mut,
         seeds = [LOAN_INFO_SEED, nft_mint.key().as_ref()],
         bump,
-        constraint = loan_info.is_active @ errors::ErrorCode::LoanNotActive,
-        constraint = lender.key() == loan_info.lender.unwrap() @ errors::ErrorCode::InvalidLender,
-        constraint = Clock::get()?.unix_timestamp > loan_info.start_time.unwrap() + loan_info.duration @ errors::ErrorCode::LoanNotExpired,
+        constraint = loan_info.status == LoanStatus::Active @ errors::ErrorCode::LoanNotActive,
+        constraint = loan_info.is_overdue(Clock::get()?.unix_timestamp) @ errors::ErrorCode::LoanNotExpired,
         close = lender // Close the loan account and return rent to lender
     )]
     pub loan_info: Account<'info, LoanInfo>,
 
     // The NFT that was used as collateral
-    pub nft_mint: Account<'info, Mint>,
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+
+    // Borrower who defaulted, refunded the rent of the vault token account they paid for when staking
+    #[account(
+        mut,
+        constraint = borrower.key() == loan_info.nft_owner @ errors::ErrorCode::InvalidBorrower
+    )]
+    /// CHECK: We verify this is the NFT owner in the constraint
+    pub borrower: UncheckedAccount<'info>,
 
     // Program's vault token account holding the NFT
     #[account(
         mut,
         associated_token::mint = nft_mint,
-        associated_token::authority = vault_authority
+        associated_token::authority = vault_authority,
+        associated_token::token_program = token_program,
     )]
-    pub vault_nft_account: Account<'info, TokenAccount>,
+    pub vault_nft_account: InterfaceAccount<'info, TokenAccount>,
 
     // Lender's token account where the NFT will be transferred
     #[account(
         init_if_needed,
         payer = lender,
         associated_token::mint = nft_mint,
-        associated_token::authority = lender
+        associated_token::authority = lender,
+        associated_token::token_program = token_program,
     )]
-    pub lender_nft_account: Account<'info, TokenAccount>,
+    pub lender_nft_account: InterfaceAccount<'info, TokenAccount>,
 
     /// CHECK: PDA for vault authority
     #[account(
@@ -56,34 +78,134 @@ pub struct ClaimDelinquentNft<'info> {
     )]
     pub vault_authority: UncheckedAccount<'info>,
 
-    // Lender's account that will receive the NFT
+    // Loan note mint representing the lender's position in this loan
+    #[account(
+        mut,
+        seeds = [LOAN_NOTE_SEED, nft_mint.key().as_ref(), &loan_info.loan_id.to_le_bytes()],
+        bump
+    )]
+    /// CHECK: PDA verified by seeds; an empty account means the loan was funded before loan notes and has none
+    pub loan_note_mint: UncheckedAccount<'info>,
+
+    // Token account of the current loan note holder, ignored when the loan has no loan note
+    #[account(mut)]
+    /// CHECK: We verify the loan note mint, balance and holder in the handler
+    pub lender_note_account: UncheckedAccount<'info>,
+
+    // Current loan note holder's account that will receive the NFT
     #[account(mut)]
     pub lender: Signer<'info>,
 
-    pub token_program: Program<'info, Token>,
+    // Archive of the loan retained after the loan account is closed
+    #[account(
+        init,
+        payer = lender,
+        space = LoanRecord::INIT_SPACE,
+        seeds = [LOAN_RECORD_SEED, nft_mint.key().as_ref(), &loan_info.loan_id.to_le_bytes()],
+        bump
+    )]
+    pub loan_record: Account<'info, LoanRecord>,
+
+    // Reputation profile of the borrower, updated with the outcome of this loan
+    #[account(
+        init_if_needed,
+        payer = lender,
+        space = BorrowerProfile::INIT_SPACE,
+        seeds = [BORROWER_PROFILE_SEED, loan_info.nft_owner.as_ref()],
+        bump
+    )]
+    pub borrower_profile: Account<'info, BorrowerProfile>,
+
+    /// CHECK: Vault config PDA; default royalties are off until the admin creates it
+    #[account(
+        seeds = [VAULT_CONFIG_SEED],
+        bump
+    )]
+    pub vault_config: UncheckedAccount<'info>,
+
+    /// CHECK: Token Metadata account of the NFT, read for its royalty; it may not exist for Token-2022 NFTs
+    #[account(
+        seeds = [
+            b"metadata",
+            TOKEN_METADATA_PROGRAM_ID.as_ref(),
+            nft_mint.key().as_ref(),
+        ],
+        bump,
+        seeds::program = TOKEN_METADATA_PROGRAM_ID,
+    )]
+    pub nft_metadata: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    // SPL Token program owning the loan note, which may differ from the NFT's token program
+    pub note_token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
+
+    // Token Metadata accounts, required only when the NFT is a programmable NFT
+    pub programmable: ProgrammableTransfer<'info>,
 }
 
-pub fn handle(ctx: Context<ClaimDelinquentNft>) -> Result<()> {
+pub fn handle<'info>(ctx: Context<'_, '_, '_, 'info, ClaimDelinquentNft<'info>>) -> Result<()> {
+    // Check that the lender holds the loan note, or is the recorded lender of a loan without one
+    let loan_note = LoanNote {
+        loan_note_mint: &ctx.accounts.loan_note_mint.to_account_info(),
+        lender_note_account: &ctx.accounts.lender_note_account.to_account_info(),
+        token_program: &ctx.accounts.note_token_program.to_account_info(),
+    };
+    loan_note.verify_holder(&ctx.accounts.loan_info, &ctx.accounts.lender.key())?;
+
+    // Charge the lender the NFT's royalty on the principal when enabled, paying the metadata
+    // creators passed ahead of any transfer hook accounts
+    let (royalty, remaining_accounts) = DefaultRoyalty {
+        vault_config: &ctx.accounts.vault_config.to_account_info(),
+        nft_metadata: &ctx.accounts.nft_metadata.to_account_info(),
+        lender: &ctx.accounts.lender.to_account_info(),
+        system_program: &ctx.accounts.system_program.to_account_info(),
+    }
+    .pay(ctx.accounts.loan_info.loan_amount, ctx.remaining_accounts)?;
+
+    // Archive the defaulted loan and update the borrower's reputation
+    LoanSettlement {
+        loan_info: &mut ctx.accounts.loan_info,
+        loan_record: &mut ctx.accounts.loan_record,
+        borrower_profile: &mut ctx.accounts.borrower_profile,
+    }
+    .claim(ctx.accounts.lender.key(), royalty)?;
+
     // Transfer NFT from vault to lender
     let vault_bump = ctx.bumps.vault_authority;
-    let nft_seeds = &[VAULT_SEED.as_ref(), &[vault_bump]];
+    let nft_seeds = &[VAULT_SEED, &[vault_bump]];
     let signer = &[&nft_seeds[..]];
 
-    anchor_spl::token::transfer(
-        CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            anchor_spl::token::Transfer {
-                from: ctx.accounts.vault_nft_account.to_account_info(),
-                to: ctx.accounts.lender_nft_account.to_account_info(),
-                authority: ctx.accounts.vault_authority.to_account_info(),
-            },
-            signer,
-        ),
-        1,
+    let programmable = ctx.accounts.programmable.transfer(
+        NftTransfer {
+            nft_mint: &ctx.accounts.nft_mint.to_account_info(),
+            source: &ctx.accounts.vault_nft_account.to_account_info(),
+            source_owner: &ctx.accounts.vault_authority.to_account_info(),
+            destination: &ctx.accounts.lender_nft_account.to_account_info(),
+            destination_owner: &ctx.accounts.lender.to_account_info(),
+            payer: &ctx.accounts.lender.to_account_info(),
+            token_program: &ctx.accounts.token_program.to_account_info(),
+            associated_token_program: &ctx.accounts.associated_token_program.to_account_info(),
+            system_program: &ctx.accounts.system_program.to_account_info(),
+            additional_accounts: remaining_accounts,
+        },
+        signer,
+    )?;
+
+    // Close the vault's token account, refunding the borrower who paid for it when staking
+    close_emptied_nft_account(
+        &mut ctx.accounts.vault_nft_account,
+        programmable,
+        &ctx.accounts.borrower.to_account_info(),
+        &ctx.accounts.vault_authority.to_account_info(),
+        &ctx.accounts.token_program.to_account_info(),
+        signer,
     )?;
 
+    // Burn the settled loan note and close the holder's note account
+    loan_note.burn(&ctx.accounts.lender.to_account_info())?;
+
     Ok(())
 }
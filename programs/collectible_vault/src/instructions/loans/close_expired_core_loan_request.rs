@@ -0,0 +1,115 @@
+use {
+    crate::{
+        constants::pda_constants::{
+            BORROWER_PROFILE_SEED, LOAN_INFO_SEED, LOAN_RECORD_SEED, VAULT_SEED,
+        },
+        errors,
+        instructions::{
+            core_asset::{CoreAsset, MplCore, MPL_CORE_PROGRAM_ID},
+            loan_settlement::LoanSettlement,
+        },
+        state::{BorrowerProfile, LoanInfo, LoanRecord, LoanStatus},
+    },
+    anchor_lang::prelude::*,
+};
+
+/**
+ * Permissionless crank that closes an unfunded loan request against a Metaplex Core asset once its request expiry has passed.
+ * This instruction thaws the asset in the borrower's wallet and refunds the loan account rent to the borrower.
+ */
+#[derive(Accounts)]
+pub struct CloseExpiredCoreLoanRequest<'info> {
+    // Any wallet may crank an expired request; it only pays for the loan record
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    // Loan request account that will be closed, returning rent to the borrower who paid it
+    #[account(
+        mut,
+        seeds = [LOAN_INFO_SEED, asset.key().as_ref()],
+        bump,
+        constraint = loan_info.lender.is_none() @ errors::ErrorCode::LoanAlreadyFunded,
+        constraint = loan_info.status == LoanStatus::Requested @ errors::ErrorCode::LoanAlreadyActive,
+        constraint = loan_info.is_request_expired(Clock::get()?.unix_timestamp) @ errors::ErrorCode::LoanRequestNotExpired,
+        close = nft_owner
+    )]
+    pub loan_info: Account<'info, LoanInfo>,
+
+    // Owner of the asset who receives the rent back
+    #[account(
+        mut,
+        constraint = nft_owner.key() == loan_info.nft_owner @ errors::ErrorCode::InvalidBorrower
+    )]
+    /// CHECK: We verify this is the asset owner in the constraint
+    pub nft_owner: UncheckedAccount<'info>,
+
+    #[account(mut, owner = MPL_CORE_PROGRAM_ID)]
+    /// CHECK: Matched to the loan account through its seeds
+    pub asset: UncheckedAccount<'info>,
+
+    // Collection of the asset, required when the asset belongs to one
+    #[account(mut)]
+    /// CHECK: Checked against the asset's update authority before it is passed to Core
+    pub collection: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: PDA for vault authority
+    #[account(
+        seeds = [VAULT_SEED],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    // Archive of the loan retained after the loan account is closed
+    #[account(
+        init,
+        payer = cranker,
+        space = LoanRecord::INIT_SPACE,
+        seeds = [LOAN_RECORD_SEED, asset.key().as_ref(), &loan_info.loan_id.to_le_bytes()],
+        bump
+    )]
+    pub loan_record: Account<'info, LoanRecord>,
+
+    // Reputation profile of the borrower, updated with the outcome of this loan
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        space = BorrowerProfile::INIT_SPACE,
+        seeds = [BORROWER_PROFILE_SEED, loan_info.nft_owner.as_ref()],
+        bump
+    )]
+    pub borrower_profile: Account<'info, BorrowerProfile>,
+
+    pub core_program: Program<'info, MplCore>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle(ctx: Context<CloseExpiredCoreLoanRequest>) -> Result<()> {
+    // Archive the cancelled request and update the borrower's reputation
+    LoanSettlement {
+        loan_info: &mut ctx.accounts.loan_info,
+        loan_record: &mut ctx.accounts.loan_record,
+        borrower_profile: &mut ctx.accounts.borrower_profile,
+    }
+    .cancel(ctx.accounts.cranker.key(), true)?;
+
+    // Thaw the asset and hand its delegate authorities back to the owner
+    let vault_bump = ctx.bumps.vault_authority;
+    let seeds = &[VAULT_SEED, &[vault_bump]];
+    CoreAsset {
+        asset: &ctx.accounts.asset.to_account_info(),
+        collection: ctx
+            .accounts
+            .collection
+            .as_ref()
+            .map(|account| account.as_ref()),
+        payer: &ctx.accounts.cranker.to_account_info(),
+        core_program: &ctx.accounts.core_program.to_account_info(),
+        system_program: &ctx.accounts.system_program.to_account_info(),
+    }
+    .release(
+        &ctx.accounts.vault_authority.to_account_info(),
+        &[&seeds[..]],
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,146 @@
+use {
+    crate::{
+        constants::pda_constants::{
+            BORROWER_PROFILE_SEED, LOAN_INFO_SEED, LOAN_RECORD_SEED, VAULT_SEED,
+        },
+        errors,
+        instructions::{loan_settlement::LoanSettlement, nft_transfer::*},
+        state::{BorrowerProfile, LoanInfo, LoanRecord, LoanStatus},
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::{
+        associated_token::AssociatedToken,
+        token_interface::{Mint, TokenAccount, TokenInterface},
+    },
+};
+
+/**
+ * Permissionless crank that closes an unfunded loan request once its request expiry has passed.
+ * This instruction returns the staked NFT to the borrower and refunds all rent to the borrower.
+ */
+#[derive(Accounts)]
+pub struct CloseExpiredLoanRequest<'info> {
+    // Any wallet may crank an expired request; it only pays for the owner's token account if missing
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    // Loan request account that will be closed, returning rent to the borrower who paid it
+    #[account(
+        mut,
+        seeds = [LOAN_INFO_SEED, nft_mint.key().as_ref()],
+        bump,
+        constraint = loan_info.lender.is_none() @ errors::ErrorCode::LoanAlreadyFunded,
+        constraint = loan_info.status == LoanStatus::Requested @ errors::ErrorCode::LoanAlreadyActive,
+        constraint = loan_info.is_request_expired(Clock::get()?.unix_timestamp) @ errors::ErrorCode::LoanRequestNotExpired,
+        close = nft_owner
+    )]
+    pub loan_info: Account<'info, LoanInfo>,
+
+    // Original owner of the NFT who receives the NFT and the rent back
+    #[account(
+        mut,
+        constraint = nft_owner.key() == loan_info.nft_owner @ errors::ErrorCode::InvalidBorrower
+    )]
+    /// CHECK: We verify this is the NFT owner in the constraint
+    pub nft_owner: UncheckedAccount<'info>,
+
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+
+    // Owner's token account where the NFT will be returned
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        associated_token::mint = nft_mint,
+        associated_token::authority = nft_owner,
+        associated_token::token_program = token_program,
+    )]
+    pub owner_nft_account: InterfaceAccount<'info, TokenAccount>,
+
+    // Program's vault token account holding the NFT
+    #[account(
+        mut,
+        associated_token::mint = nft_mint,
+        associated_token::authority = vault_authority,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_nft_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: PDA for vault authority
+    #[account(
+        seeds = [VAULT_SEED],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    // Archive of the loan retained after the loan account is closed
+    #[account(
+        init,
+        payer = cranker,
+        space = LoanRecord::INIT_SPACE,
+        seeds = [LOAN_RECORD_SEED, nft_mint.key().as_ref(), &loan_info.loan_id.to_le_bytes()],
+        bump
+    )]
+    pub loan_record: Account<'info, LoanRecord>,
+
+    // Reputation profile of the borrower, updated with the outcome of this loan
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        space = BorrowerProfile::INIT_SPACE,
+        seeds = [BORROWER_PROFILE_SEED, loan_info.nft_owner.as_ref()],
+        bump
+    )]
+    pub borrower_profile: Account<'info, BorrowerProfile>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+
+    // Token Metadata accounts, required only when the NFT is a programmable NFT
+    pub programmable: ProgrammableTransfer<'info>,
+}
+
+pub fn handle<'info>(
+    ctx: Context<'_, '_, '_, 'info, CloseExpiredLoanRequest<'info>>,
+) -> Result<()> {
+    // Archive the cancelled request and update the borrower's reputation
+    LoanSettlement {
+        loan_info: &mut ctx.accounts.loan_info,
+        loan_record: &mut ctx.accounts.loan_record,
+        borrower_profile: &mut ctx.accounts.borrower_profile,
+    }
+    .cancel(ctx.accounts.cranker.key(), true)?;
+
+    let vault_bump = ctx.bumps.vault_authority;
+    let seeds = &[VAULT_SEED, &[vault_bump]];
+    let signer = &[&seeds[..]];
+
+    // Transfer NFT back to owner
+    let programmable = ctx.accounts.programmable.transfer(
+        NftTransfer {
+            nft_mint: &ctx.accounts.nft_mint.to_account_info(),
+            source: &ctx.accounts.vault_nft_account.to_account_info(),
+            source_owner: &ctx.accounts.vault_authority.to_account_info(),
+            destination: &ctx.accounts.owner_nft_account.to_account_info(),
+            destination_owner: &ctx.accounts.nft_owner.to_account_info(),
+            payer: &ctx.accounts.cranker.to_account_info(),
+            token_program: &ctx.accounts.token_program.to_account_info(),
+            associated_token_program: &ctx.accounts.associated_token_program.to_account_info(),
+            system_program: &ctx.accounts.system_program.to_account_info(),
+            additional_accounts: ctx.remaining_accounts,
+        },
+        signer,
+    )?;
+
+    // Close the vault's token account, refunding the owner who paid for it when staking
+    close_emptied_nft_account(
+        &mut ctx.accounts.vault_nft_account,
+        programmable,
+        &ctx.accounts.nft_owner.to_account_info(),
+        &ctx.accounts.vault_authority.to_account_info(),
+        &ctx.accounts.token_program.to_account_info(),
+        signer,
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,178 @@
+use {
+    crate::{
+        constants::pda_constants::{
+            ASSET_IMPAIRMENT_SEED, LOAN_COUNTER_SEED, LOAN_INFO_SEED, VAULT_SEED,
+        },
+        errors,
+        events::LoanRequested,
+        instructions::nft_lock::DelegatedFreeze,
+        state::{AccountHeader, LoanCounter, LoanInfo, LoanStatus},
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::{
+        associated_token::AssociatedToken,
+        token::{Mint, Token, TokenAccount},
+    },
+    mpl_token_metadata::ID as TOKEN_METADATA_PROGRAM_ID,
+};
+
+/**
+ * Allows NFT owners to create a loan request without handing over custody of their NFT.
+ * This instruction initializes a loan request account, approves the vault authority as delegate of the
+ * owner's token account and freezes the NFT there for the life of the loan.
+ */
+#[derive(Accounts)]
+pub struct LockNftForLoan<'info> {
+    // PDA account to store loan information, derived from the NFT mint address
+    #[account(
+        init,
+        payer = owner,
+        space = LoanInfo::INIT_SPACE,
+        seeds = [LOAN_INFO_SEED, nft_mint.key().as_ref()],
+        bump
+    )]
+    pub loan_info: Account<'info, LoanInfo>,
+
+    // Per-mint counter that assigns each loan against this NFT a sequence number
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = LoanCounter::INIT_SPACE,
+        seeds = [LOAN_COUNTER_SEED, nft_mint.key().as_ref()],
+        bump
+    )]
+    pub loan_counter: Account<'info, LoanCounter>,
+
+    // The NFT being used as collateral
+    pub nft_mint: Account<'info, Mint>,
+
+    // Owner's token account containing the NFT, which is frozen in place
+    #[account(
+        mut,
+        associated_token::mint = nft_mint,
+        associated_token::authority = owner,
+        constraint = owner_nft_account.amount == 1 @ errors::ErrorCode::InvalidNFTAccount
+    )]
+    pub owner_nft_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Master edition of the NFT, which holds the mint's freeze authority
+    #[account(
+        seeds = [
+            b"metadata",
+            TOKEN_METADATA_PROGRAM_ID.as_ref(),
+            nft_mint.key().as_ref(),
+            b"edition",
+        ],
+        bump,
+        seeds::program = TOKEN_METADATA_PROGRAM_ID,
+    )]
+    pub nft_edition: UncheckedAccount<'info>,
+
+    /// CHECK: PDA for vault authority, which becomes the delegate of the owner's token account
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: Impairment PDA of the NFT, which must not exist for the NFT to be locked for a loan
+    #[account(
+        seeds = [ASSET_IMPAIRMENT_SEED, nft_mint.key().as_ref()],
+        bump,
+        constraint = asset_impairment.data_is_empty() @ errors::ErrorCode::NftImpaired
+    )]
+    pub asset_impairment: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// CHECK: Metaplex program ID check
+    #[account(address = TOKEN_METADATA_PROGRAM_ID)]
+    pub token_metadata_program: UncheckedAccount<'info>,
+}
+
+pub fn handle(
+    ctx: Context<LockNftForLoan>,
+    loan_amount: u64,
+    interest_amount: u64,
+    duration: i64,
+    request_expiry: Option<i64>,
+) -> Result<()> {
+    // Validate loan duration
+    require!(duration > 0, errors::ErrorCode::InvalidLoanDuration);
+
+    // Validate the optional request expiry is in the future
+    if let Some(expiry) = request_expiry {
+        require!(
+            expiry > Clock::get()?.unix_timestamp,
+            errors::ErrorCode::InvalidLoanRequestExpiry
+        );
+    }
+
+    // Assign the next sequence number for loans against this NFT
+    let loan_counter = &mut ctx.accounts.loan_counter;
+    loan_counter.header = AccountHeader::new(LoanCounter::VERSION);
+    loan_counter.nft_mint = ctx.accounts.nft_mint.key();
+    loan_counter.count = loan_counter
+        .count
+        .checked_add(1)
+        .ok_or(errors::ErrorCode::CalculationError)?;
+
+    // Initialize loan request with provided terms
+    let loan_info = &mut ctx.accounts.loan_info;
+    loan_info.header = AccountHeader::new(LoanInfo::VERSION);
+    loan_info.nft_mint = ctx.accounts.nft_mint.key();
+    loan_info.nft_owner = ctx.accounts.owner.key();
+    loan_info.loan_amount = loan_amount;
+    loan_info.interest_amount = interest_amount;
+    loan_info.duration = duration;
+    loan_info.start_time = None; // Will be set when loan is funded
+    loan_info.lender = None; // Will be set when loan is funded
+    loan_info.status = LoanStatus::Requested;
+    loan_info.request_expiry = request_expiry;
+    loan_info.loan_id = loan_counter.count;
+
+    // Make the vault authority the delegate of the NFT, then freeze it in the owner's wallet
+    anchor_spl::token::approve(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            anchor_spl::token::Approve {
+                to: ctx.accounts.owner_nft_account.to_account_info(),
+                delegate: ctx.accounts.vault_authority.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            },
+        ),
+        1,
+    )?;
+
+    let vault_bump = ctx.bumps.vault_authority;
+    let seeds = &[VAULT_SEED, &[vault_bump]];
+    DelegatedFreeze {
+        delegate: &ctx.accounts.vault_authority.to_account_info(),
+        token_account: &ctx.accounts.owner_nft_account.to_account_info(),
+        edition: &ctx.accounts.nft_edition.to_account_info(),
+        nft_mint: &ctx.accounts.nft_mint.to_account_info(),
+        token_program: &ctx.accounts.token_program.to_account_info(),
+        token_metadata_program: &ctx.accounts.token_metadata_program.to_account_info(),
+    }
+    .freeze(&[&seeds[..]])?;
+
+    emit!(LoanRequested {
+        loan_info: ctx.accounts.loan_info.key(),
+        nft_mint: ctx.accounts.nft_mint.key(),
+        loan_id: ctx.accounts.loan_info.loan_id,
+        borrower: ctx.accounts.owner.key(),
+        loan_amount,
+        interest_amount,
+        duration,
+        request_expiry,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
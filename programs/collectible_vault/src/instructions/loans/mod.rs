@@ -1,5 +1,17 @@
+pub mod cancel_core_loan_request;
 pub mod cancel_loan_request;
+pub mod cancel_locked_loan_request;
+pub mod claim_delinquent_core_asset;
+pub mod claim_delinquent_locked_nft;
 pub mod claim_delinquent_nft;
+pub mod close_expired_core_loan_request;
+pub mod close_expired_loan_request;
+pub mod close_expired_locked_loan_request;
+pub mod lock_nft_for_loan;
 pub mod provide_loan_liquidity;
+pub mod repay_core_loan;
 pub mod repay_loan;
+pub mod repay_locked_loan;
+pub mod stake_core_asset_for_loan;
 pub mod stake_nft_for_loan;
+pub mod sweep_baseline_vault_account;
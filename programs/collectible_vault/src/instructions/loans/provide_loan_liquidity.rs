@@ -1,11 +1,24 @@
 use {
-    crate::{constants::pda_constants::LOAN_INFO_SEED, errors, state::LoanInfo},
+    crate::{
+        constants::pda_constants::{
+            ASSET_IMPAIRMENT_SEED, BORROWER_PROFILE_SEED, LOAN_INFO_SEED, LOAN_NOTE_SEED,
+            VAULT_SEED,
+        },
+        errors,
+        events::LoanFunded,
+        state::{BorrowerProfile, LoanInfo, LoanStatus, ReputationGate},
+    },
     anchor_lang::prelude::*,
+    anchor_spl::{
+        associated_token::AssociatedToken,
+        token::{spl_token::instruction::AuthorityType, Mint, Token, TokenAccount},
+    },
 };
 
 /**
  * Allows lenders to fund a loan request and make it active.
  * This instruction updates the loan request account with the lender's information and marks it as active.
+ * The lender receives a loan note NFT representing their position, which can be transferred to sell the loan.
  */
 #[derive(Accounts)]
 pub struct ProvideLoanLiquidity<'info> {
@@ -14,7 +27,8 @@ pub struct ProvideLoanLiquidity<'info> {
         mut,
         seeds = [LOAN_INFO_SEED, loan_info.nft_mint.as_ref()],
         bump,
-        constraint = !loan_info.is_active @ errors::ErrorCode::LoanAlreadyActive,
+        constraint = loan_info.status == LoanStatus::Requested @ errors::ErrorCode::LoanAlreadyActive,
+        constraint = !loan_info.is_request_expired(Clock::get()?.unix_timestamp) @ errors::ErrorCode::LoanRequestExpired,
         constraint = loan_info.loan_amount <= lender.lamports() @ errors::ErrorCode::InsufficientFunds
     )]
     pub loan_info: Account<'info, LoanInfo>,
@@ -31,10 +45,78 @@ pub struct ProvideLoanLiquidity<'info> {
     /// CHECK: We verify this is the NFT owner in the constraint
     pub borrower: UncheckedAccount<'info>,
 
+    // Borrower's reputation profile, required only when the lender sets a reputation gate
+    #[account(
+        seeds = [BORROWER_PROFILE_SEED, borrower.key().as_ref()],
+        bump
+    )]
+    /// CHECK: PDA verified by seeds; an empty account means the borrower has no loan history yet
+    pub borrower_profile: Option<UncheckedAccount<'info>>,
+
+    // Loan note mint representing the lender's position in this loan
+    #[account(
+        init,
+        payer = lender,
+        seeds = [LOAN_NOTE_SEED, loan_info.nft_mint.as_ref(), &loan_info.loan_id.to_le_bytes()],
+        bump,
+        mint::decimals = 0,
+        mint::authority = vault_authority,
+        mint::freeze_authority = vault_authority,
+    )]
+    pub loan_note_mint: Account<'info, Mint>,
+
+    // Lender's token account that will receive the loan note
+    #[account(
+        init,
+        payer = lender,
+        associated_token::mint = loan_note_mint,
+        associated_token::authority = lender,
+    )]
+    pub lender_note_account: Account<'info, TokenAccount>,
+
+    /// CHECK: PDA for vault authority
+    #[account(
+        seeds = [VAULT_SEED],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Impairment PDA of the NFT, which must not exist for the NFT to be lent against
+    #[account(
+        seeds = [ASSET_IMPAIRMENT_SEED, loan_info.nft_mint.as_ref()],
+        bump,
+        constraint = asset_impairment.data_is_empty() @ errors::ErrorCode::NftImpaired
+    )]
+    pub asset_impairment: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
 }
 
-pub fn handle(ctx: Context<ProvideLoanLiquidity>) -> Result<()> {
+pub fn handle(
+    ctx: Context<ProvideLoanLiquidity>,
+    reputation_gate: Option<ReputationGate>,
+) -> Result<()> {
+    // Enforce the lender's minimum reputation requirements, if any
+    if let Some(gate) = reputation_gate {
+        let profile_account = ctx
+            .accounts
+            .borrower_profile
+            .as_ref()
+            .ok_or(errors::ErrorCode::BorrowerReputationTooLow)?;
+        let profile = if profile_account.data_is_empty() {
+            BorrowerProfile::default()
+        } else {
+            BorrowerProfile::try_deserialize(&mut &profile_account.try_borrow_data()?[..])?
+        };
+        require!(
+            profile.meets(&gate),
+            errors::ErrorCode::BorrowerReputationTooLow
+        );
+    }
+
     let loan_info = &mut ctx.accounts.loan_info;
 
     // Transfer loan amount from lender to borrower
@@ -50,9 +132,53 @@ pub fn handle(ctx: Context<ProvideLoanLiquidity>) -> Result<()> {
     )?;
 
     // Update loan status to active and record lender and start time
-    loan_info.start_time = Some(Clock::get()?.unix_timestamp);
+    let start_time = Clock::get()?.unix_timestamp;
+    loan_info.start_time = Some(start_time);
     loan_info.lender = Some(ctx.accounts.lender.key());
-    loan_info.is_active = true;
+    loan_info.status = LoanStatus::Active;
+
+    // Mint the loan note to the lender
+    let vault_bump = ctx.bumps.vault_authority;
+    let seeds = &[VAULT_SEED, &[vault_bump]];
+    let signer = &[&seeds[..]];
+
+    anchor_spl::token::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            anchor_spl::token::MintTo {
+                mint: ctx.accounts.loan_note_mint.to_account_info(),
+                to: ctx.accounts.lender_note_account.to_account_info(),
+                authority: ctx.accounts.vault_authority.to_account_info(),
+            },
+            signer,
+        ),
+        1,
+    )?;
+
+    // Remove the mint authority so the loan note can never be minted again
+    anchor_spl::token::set_authority(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            anchor_spl::token::SetAuthority {
+                current_authority: ctx.accounts.vault_authority.to_account_info(),
+                account_or_mint: ctx.accounts.loan_note_mint.to_account_info(),
+            },
+            signer,
+        ),
+        AuthorityType::MintTokens,
+        None,
+    )?;
+
+    emit!(LoanFunded {
+        loan_info: ctx.accounts.loan_info.key(),
+        nft_mint: ctx.accounts.loan_info.nft_mint,
+        loan_id: ctx.accounts.loan_info.loan_id,
+        borrower: ctx.accounts.borrower.key(),
+        lender: ctx.accounts.lender.key(),
+        loan_note_mint: ctx.accounts.loan_note_mint.key(),
+        loan_amount: ctx.accounts.loan_info.loan_amount,
+        start_time,
+    });
 
     Ok(())
 }
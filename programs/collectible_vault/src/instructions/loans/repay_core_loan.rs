@@ -0,0 +1,155 @@
+use {
+    crate::{
+        constants::pda_constants::{
+            BORROWER_PROFILE_SEED, LOAN_INFO_SEED, LOAN_NOTE_SEED, LOAN_RECORD_SEED, VAULT_SEED,
+        },
+        errors,
+        instructions::{
+            core_asset::{CoreAsset, MplCore, MPL_CORE_PROGRAM_ID},
+            loan_settlement::{LoanNote, LoanSettlement},
+        },
+        state::{BorrowerProfile, LoanInfo, LoanRecord, LoanStatus},
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token::Token,
+};
+
+/**
+ * Allows borrowers to repay a loan against a Metaplex Core asset.
+ * This instruction pays the current loan note holder, closes the loan account and thaws the asset in the borrower's wallet.
+ * The settled loan note is frozen in the holder's wallet.
+ * Any wallet may pay on the borrower's behalf; the loan account rent always returns to the borrower.
+ */
+#[derive(Accounts)]
+pub struct RepayCoreLoan<'info> {
+    // Loan account that will be closed after repayment
+    #[account(
+        mut,
+        seeds = [LOAN_INFO_SEED, asset.key().as_ref()],
+        bump,
+        constraint = loan_info.status == LoanStatus::Active @ errors::ErrorCode::LoanNotActive,
+        constraint = borrower.key() == loan_info.nft_owner @ errors::ErrorCode::InvalidBorrower,
+        constraint = loan_info.is_repayable(Clock::get()?.unix_timestamp) @ errors::ErrorCode::LoanExpired,
+        close = borrower // Close the loan account and return rent to borrower, who paid it
+    )]
+    pub loan_info: Account<'info, LoanInfo>,
+
+    // The Core asset that was used as collateral
+    #[account(mut, owner = MPL_CORE_PROGRAM_ID)]
+    /// CHECK: Matched to the loan account through its seeds
+    pub asset: UncheckedAccount<'info>,
+
+    // Collection of the asset, required when the asset belongs to one
+    #[account(mut)]
+    /// CHECK: Checked against the asset's update authority before it is passed to Core
+    pub collection: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: PDA for vault authority
+    #[account(
+        seeds = [VAULT_SEED],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    // Original owner of the asset, who still holds it
+    #[account(mut)]
+    /// CHECK: We verify this is the asset owner in the loan account constraint
+    pub borrower: UncheckedAccount<'info>,
+
+    // Account that pays the repayment; the borrower or a third party acting on their behalf
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    // Loan note mint representing the lender's position in this loan
+    #[account(
+        mut,
+        seeds = [LOAN_NOTE_SEED, asset.key().as_ref(), &loan_info.loan_id.to_le_bytes()],
+        bump
+    )]
+    /// CHECK: PDA verified by seeds; an empty account means the loan was funded before loan notes and has none
+    pub loan_note_mint: UncheckedAccount<'info>,
+
+    // Token account of the current loan note holder, ignored when the loan has no loan note
+    #[account(mut)]
+    /// CHECK: We verify the loan note mint, balance and holder in the handler
+    pub lender_note_account: UncheckedAccount<'info>,
+
+    // Current loan note holder's account that will receive the repayment
+    #[account(mut)]
+    /// CHECK: We verify this holds the lender's position in the handler
+    pub lender: UncheckedAccount<'info>,
+
+    // Archive of the loan retained after the loan account is closed
+    #[account(
+        init,
+        payer = payer,
+        space = LoanRecord::INIT_SPACE,
+        seeds = [LOAN_RECORD_SEED, asset.key().as_ref(), &loan_info.loan_id.to_le_bytes()],
+        bump
+    )]
+    pub loan_record: Account<'info, LoanRecord>,
+
+    // Reputation profile of the borrower, updated with the outcome of this loan
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = BorrowerProfile::INIT_SPACE,
+        seeds = [BORROWER_PROFILE_SEED, loan_info.nft_owner.as_ref()],
+        bump
+    )]
+    pub borrower_profile: Account<'info, BorrowerProfile>,
+
+    // SPL Token program owning the loan note
+    pub token_program: Program<'info, Token>,
+    pub core_program: Program<'info, MplCore>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle(ctx: Context<RepayCoreLoan>) -> Result<()> {
+    // Check that the lender holds the loan note, or is the recorded lender of a loan without one
+    let loan_note = LoanNote {
+        loan_note_mint: &ctx.accounts.loan_note_mint.to_account_info(),
+        lender_note_account: &ctx.accounts.lender_note_account.to_account_info(),
+        token_program: &ctx.accounts.token_program.to_account_info(),
+    };
+    loan_note.verify_holder(&ctx.accounts.loan_info, &ctx.accounts.lender.key())?;
+
+    // Pay the loan note holder, archive the loan and update the borrower's reputation
+    LoanSettlement {
+        loan_info: &mut ctx.accounts.loan_info,
+        loan_record: &mut ctx.accounts.loan_record,
+        borrower_profile: &mut ctx.accounts.borrower_profile,
+    }
+    .repay(
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.lender.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    // Thaw the asset and hand its delegate authorities back to the borrower
+    let vault_bump = ctx.bumps.vault_authority;
+    let seeds = &[VAULT_SEED, &[vault_bump]];
+    CoreAsset {
+        asset: &ctx.accounts.asset.to_account_info(),
+        collection: ctx
+            .accounts
+            .collection
+            .as_ref()
+            .map(|account| account.as_ref()),
+        payer: &ctx.accounts.payer.to_account_info(),
+        core_program: &ctx.accounts.core_program.to_account_info(),
+        system_program: &ctx.accounts.system_program.to_account_info(),
+    }
+    .release(
+        &ctx.accounts.vault_authority.to_account_info(),
+        &[&seeds[..]],
+    )?;
+
+    // Freeze the settled loan note so it can no longer be traded as a live position
+    loan_note.freeze(
+        &ctx.accounts.vault_authority.to_account_info(),
+        &[&seeds[..]],
+    )?;
+
+    Ok(())
+}
@@ -1,19 +1,29 @@
 use {
     crate::{
-        constants::pda_constants::{LOAN_INFO_SEED, VAULT_SEED},
+        constants::pda_constants::{
+            BORROWER_PROFILE_SEED, LOAN_INFO_SEED, LOAN_NOTE_SEED, LOAN_RECORD_SEED, VAULT_SEED,
+        },
         errors,
-        state::LoanInfo,
+        instructions::{
+            loan_settlement::{LoanNote, LoanSettlement},
+            nft_transfer::*,
+        },
+        state::{BorrowerProfile, LoanInfo, LoanRecord, LoanStatus},
     },
     anchor_lang::prelude::*,
     anchor_spl::{
         associated_token::AssociatedToken,
-        token::{Mint, Token, TokenAccount},
+        token::Token,
+        token_interface::{Mint, TokenAccount, TokenInterface},
     },
 };
 
 /**
  * Allows borrowers to repay their loan and retrieve their NFT.
- * This instruction closes the loan account and returns the NFT to the borrower.
+ * This instruction pays the current loan note holder, closes the loan account and returns the NFT to the borrower.
+ * The settled loan note is frozen in the holder's wallet; a loan funded before loan notes pays its recorded lender.
+ * The emptied vault token account is closed as well, refunding its rent to the borrower.
+ * Any wallet may pay on the borrower's behalf; the NFT and the loan account rent always return to the borrower.
  */
 #[derive(Accounts)]
 pub struct RepayLoan<'info> {
@@ -22,32 +32,34 @@ pub struct RepayLoan<'info> {
         mut,
         seeds = [LOAN_INFO_SEED, nft_mint.key().as_ref()],
         bump,
-        constraint = loan_info.is_active @ errors::ErrorCode::LoanNotActive,
+        constraint = loan_info.status == LoanStatus::Active @ errors::ErrorCode::LoanNotActive,
         constraint = borrower.key() == loan_info.nft_owner @ errors::ErrorCode::InvalidBorrower,
-        constraint = Clock::get()?.unix_timestamp <= loan_info.start_time.unwrap() + loan_info.duration @ errors::ErrorCode::LoanExpired,
-        close = borrower // Close the loan account and return rent to borrower
+        constraint = loan_info.is_repayable(Clock::get()?.unix_timestamp) @ errors::ErrorCode::LoanExpired,
+        close = borrower // Close the loan account and return rent to borrower, who paid it
     )]
     pub loan_info: Account<'info, LoanInfo>,
 
     // The NFT that was used as collateral
-    pub nft_mint: Account<'info, Mint>,
+    pub nft_mint: InterfaceAccount<'info, Mint>,
 
     // Program's vault token account holding the NFT
     #[account(
         mut,
         associated_token::mint = nft_mint,
-        associated_token::authority = vault_authority
+        associated_token::authority = vault_authority,
+        associated_token::token_program = token_program,
     )]
-    pub vault_nft_account: Account<'info, TokenAccount>,
+    pub vault_nft_account: InterfaceAccount<'info, TokenAccount>,
 
     // Borrower's token account where the NFT will be returned
     #[account(
         init_if_needed,
-        payer = borrower,
+        payer = payer,
         associated_token::mint = nft_mint,
-        associated_token::authority = borrower
+        associated_token::authority = borrower,
+        associated_token::token_program = token_program,
     )]
-    pub borrower_nft_account: Account<'info, TokenAccount>,
+    pub borrower_nft_account: InterfaceAccount<'info, TokenAccount>,
 
     /// CHECK: PDA for vault authority
     #[account(
@@ -56,62 +68,119 @@ pub struct RepayLoan<'info> {
     )]
     pub vault_authority: UncheckedAccount<'info>,
 
-    // Borrower's account that will repay the loan
+    // Original owner of the NFT who receives it back, along with the vault token account rent
     #[account(mut)]
-    pub borrower: Signer<'info>,
+    /// CHECK: We verify this is the NFT owner in the loan account constraint
+    pub borrower: UncheckedAccount<'info>,
 
-    // Lender's account that will receive the repayment
+    // Account that pays the repayment; the borrower or a third party acting on their behalf
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    // Loan note mint representing the lender's position in this loan
     #[account(
         mut,
-        constraint = lender.key() == loan_info.lender.unwrap() @ errors::ErrorCode::InvalidLender
+        seeds = [LOAN_NOTE_SEED, nft_mint.key().as_ref(), &loan_info.loan_id.to_le_bytes()],
+        bump
     )]
-    /// CHECK: We verify this is the lender in the constraint
+    /// CHECK: PDA verified by seeds; an empty account means the loan was funded before loan notes and has none
+    pub loan_note_mint: UncheckedAccount<'info>,
+
+    // Token account of the current loan note holder, ignored when the loan has no loan note
+    #[account(mut)]
+    /// CHECK: We verify the loan note mint, balance and holder in the handler
+    pub lender_note_account: UncheckedAccount<'info>,
+
+    // Current loan note holder's account that will receive the repayment
+    #[account(mut)]
+    /// CHECK: We verify this holds the lender's position in the handler
     pub lender: UncheckedAccount<'info>,
 
-    pub token_program: Program<'info, Token>,
+    // Archive of the loan retained after the loan account is closed
+    #[account(
+        init,
+        payer = payer,
+        space = LoanRecord::INIT_SPACE,
+        seeds = [LOAN_RECORD_SEED, nft_mint.key().as_ref(), &loan_info.loan_id.to_le_bytes()],
+        bump
+    )]
+    pub loan_record: Account<'info, LoanRecord>,
+
+    // Reputation profile of the borrower, updated with the outcome of this loan
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = BorrowerProfile::INIT_SPACE,
+        seeds = [BORROWER_PROFILE_SEED, loan_info.nft_owner.as_ref()],
+        bump
+    )]
+    pub borrower_profile: Account<'info, BorrowerProfile>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    // SPL Token program owning the loan note, which may differ from the NFT's token program
+    pub note_token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
+
+    // Token Metadata accounts, required only when the NFT is a programmable NFT
+    pub programmable: ProgrammableTransfer<'info>,
 }
 
-pub fn handle(ctx: Context<RepayLoan>) -> Result<()> {
-    let loan_info = &ctx.accounts.loan_info;
-
-    // Calculate total repayment amount (principal + interest)
-    let total_repayment = loan_info
-        .loan_amount
-        .checked_add(loan_info.interest_amount)
-        .ok_or(errors::ErrorCode::CalculationError)?;
-
-    // Transfer repayment amount from borrower to lender
-    anchor_lang::system_program::transfer(
-        CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
-            anchor_lang::system_program::Transfer {
-                from: ctx.accounts.borrower.to_account_info(),
-                to: ctx.accounts.lender.to_account_info(),
-            },
-        ),
-        total_repayment,
+pub fn handle<'info>(ctx: Context<'_, '_, '_, 'info, RepayLoan<'info>>) -> Result<()> {
+    // Check that the lender holds the loan note, or is the recorded lender of a loan without one
+    let loan_note = LoanNote {
+        loan_note_mint: &ctx.accounts.loan_note_mint.to_account_info(),
+        lender_note_account: &ctx.accounts.lender_note_account.to_account_info(),
+        token_program: &ctx.accounts.note_token_program.to_account_info(),
+    };
+    loan_note.verify_holder(&ctx.accounts.loan_info, &ctx.accounts.lender.key())?;
+
+    // Pay the loan note holder, archive the loan and update the borrower's reputation
+    LoanSettlement {
+        loan_info: &mut ctx.accounts.loan_info,
+        loan_record: &mut ctx.accounts.loan_record,
+        borrower_profile: &mut ctx.accounts.borrower_profile,
+    }
+    .repay(
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.lender.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
     )?;
 
     // Transfer NFT from vault back to borrower
     let vault_bump = ctx.bumps.vault_authority;
-    let nft_seeds = &[VAULT_SEED.as_ref(), &[vault_bump]];
+    let nft_seeds = &[VAULT_SEED, &[vault_bump]];
     let signer = &[&nft_seeds[..]];
 
-    anchor_spl::token::transfer(
-        CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            anchor_spl::token::Transfer {
-                from: ctx.accounts.vault_nft_account.to_account_info(),
-                to: ctx.accounts.borrower_nft_account.to_account_info(),
-                authority: ctx.accounts.vault_authority.to_account_info(),
-            },
-            signer,
-        ),
-        1,
+    let programmable = ctx.accounts.programmable.transfer(
+        NftTransfer {
+            nft_mint: &ctx.accounts.nft_mint.to_account_info(),
+            source: &ctx.accounts.vault_nft_account.to_account_info(),
+            source_owner: &ctx.accounts.vault_authority.to_account_info(),
+            destination: &ctx.accounts.borrower_nft_account.to_account_info(),
+            destination_owner: &ctx.accounts.borrower.to_account_info(),
+            payer: &ctx.accounts.payer.to_account_info(),
+            token_program: &ctx.accounts.token_program.to_account_info(),
+            associated_token_program: &ctx.accounts.associated_token_program.to_account_info(),
+            system_program: &ctx.accounts.system_program.to_account_info(),
+            additional_accounts: ctx.remaining_accounts,
+        },
+        signer,
     )?;
 
+    // Close the vault's token account, refunding the borrower who paid for it when staking
+    close_emptied_nft_account(
+        &mut ctx.accounts.vault_nft_account,
+        programmable,
+        &ctx.accounts.borrower.to_account_info(),
+        &ctx.accounts.vault_authority.to_account_info(),
+        &ctx.accounts.token_program.to_account_info(),
+        signer,
+    )?;
+
+    // Freeze the settled loan note so it can no longer be traded as a live position
+    loan_note.freeze(&ctx.accounts.vault_authority.to_account_info(), signer)?;
+
     Ok(())
 }
@@ -0,0 +1,169 @@
+use {
+    crate::{
+        constants::pda_constants::{
+            BORROWER_PROFILE_SEED, LOAN_INFO_SEED, LOAN_NOTE_SEED, LOAN_RECORD_SEED, VAULT_SEED,
+        },
+        errors,
+        instructions::{
+            loan_settlement::{LoanNote, LoanSettlement},
+            nft_lock::DelegatedFreeze,
+        },
+        state::{BorrowerProfile, LoanInfo, LoanRecord, LoanStatus},
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token::{Mint, Token, TokenAccount},
+    mpl_token_metadata::ID as TOKEN_METADATA_PROGRAM_ID,
+};
+
+/**
+ * Allows borrowers to repay a loan whose NFT is frozen in their own wallet.
+ * This instruction pays the current loan note holder, closes the loan account and thaws the NFT.
+ * The settled loan note is frozen in the holder's wallet.
+ * The vault authority stays the token account's delegate until the owner revokes it or the NFT moves;
+ * it can only act on the NFT through another locked loan.
+ */
+#[derive(Accounts)]
+pub struct RepayLockedLoan<'info> {
+    // Loan account that will be closed after repayment
+    #[account(
+        mut,
+        seeds = [LOAN_INFO_SEED, nft_mint.key().as_ref()],
+        bump,
+        constraint = loan_info.status == LoanStatus::Active @ errors::ErrorCode::LoanNotActive,
+        constraint = borrower.key() == loan_info.nft_owner @ errors::ErrorCode::InvalidBorrower,
+        constraint = loan_info.is_repayable(Clock::get()?.unix_timestamp) @ errors::ErrorCode::LoanExpired,
+        close = borrower // Close the loan account and return rent to borrower, who paid it
+    )]
+    pub loan_info: Account<'info, LoanInfo>,
+
+    // The NFT that was used as collateral
+    pub nft_mint: Account<'info, Mint>,
+
+    // Borrower's token account where the NFT is frozen
+    #[account(
+        mut,
+        associated_token::mint = nft_mint,
+        associated_token::authority = borrower,
+        constraint = borrower_nft_account.amount == 1 @ errors::ErrorCode::InvalidNFTAccount
+    )]
+    pub borrower_nft_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Master edition of the NFT, which holds the mint's freeze authority
+    #[account(
+        seeds = [
+            b"metadata",
+            TOKEN_METADATA_PROGRAM_ID.as_ref(),
+            nft_mint.key().as_ref(),
+            b"edition",
+        ],
+        bump,
+        seeds::program = TOKEN_METADATA_PROGRAM_ID,
+    )]
+    pub nft_edition: UncheckedAccount<'info>,
+
+    /// CHECK: PDA for vault authority, the delegate that froze the NFT
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    // Original owner of the NFT
+    #[account(mut)]
+    /// CHECK: We verify this is the NFT owner in the loan account constraint
+    pub borrower: UncheckedAccount<'info>,
+
+    // Account that pays the repayment; the borrower or a third party acting on their behalf
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    // Loan note mint representing the lender's position in this loan
+    #[account(
+        mut,
+        seeds = [LOAN_NOTE_SEED, nft_mint.key().as_ref(), &loan_info.loan_id.to_le_bytes()],
+        bump
+    )]
+    /// CHECK: PDA verified by seeds; an empty account means the loan was funded before loan notes and has none
+    pub loan_note_mint: UncheckedAccount<'info>,
+
+    // Token account of the current loan note holder, ignored when the loan has no loan note
+    #[account(mut)]
+    /// CHECK: We verify the loan note mint, balance and holder in the handler
+    pub lender_note_account: UncheckedAccount<'info>,
+
+    // Current loan note holder's account that will receive the repayment
+    #[account(mut)]
+    /// CHECK: We verify this holds the lender's position in the handler
+    pub lender: UncheckedAccount<'info>,
+
+    // Archive of the loan retained after the loan account is closed
+    #[account(
+        init,
+        payer = payer,
+        space = LoanRecord::INIT_SPACE,
+        seeds = [LOAN_RECORD_SEED, nft_mint.key().as_ref(), &loan_info.loan_id.to_le_bytes()],
+        bump
+    )]
+    pub loan_record: Account<'info, LoanRecord>,
+
+    // Reputation profile of the borrower, updated with the outcome of this loan
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = BorrowerProfile::INIT_SPACE,
+        seeds = [BORROWER_PROFILE_SEED, loan_info.nft_owner.as_ref()],
+        bump
+    )]
+    pub borrower_profile: Account<'info, BorrowerProfile>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Metaplex program ID check
+    #[account(address = TOKEN_METADATA_PROGRAM_ID)]
+    pub token_metadata_program: UncheckedAccount<'info>,
+}
+
+pub fn handle(ctx: Context<RepayLockedLoan>) -> Result<()> {
+    // Check that the lender holds the loan note, or is the recorded lender of a loan without one
+    let loan_note = LoanNote {
+        loan_note_mint: &ctx.accounts.loan_note_mint.to_account_info(),
+        lender_note_account: &ctx.accounts.lender_note_account.to_account_info(),
+        token_program: &ctx.accounts.token_program.to_account_info(),
+    };
+    loan_note.verify_holder(&ctx.accounts.loan_info, &ctx.accounts.lender.key())?;
+
+    // Pay the loan note holder, archive the loan and update the borrower's reputation
+    LoanSettlement {
+        loan_info: &mut ctx.accounts.loan_info,
+        loan_record: &mut ctx.accounts.loan_record,
+        borrower_profile: &mut ctx.accounts.borrower_profile,
+    }
+    .repay(
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.lender.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    // Thaw the NFT in the borrower's wallet
+    let vault_bump = ctx.bumps.vault_authority;
+    let seeds = &[VAULT_SEED, &[vault_bump]];
+    DelegatedFreeze {
+        delegate: &ctx.accounts.vault_authority.to_account_info(),
+        token_account: &ctx.accounts.borrower_nft_account.to_account_info(),
+        edition: &ctx.accounts.nft_edition.to_account_info(),
+        nft_mint: &ctx.accounts.nft_mint.to_account_info(),
+        token_program: &ctx.accounts.token_program.to_account_info(),
+        token_metadata_program: &ctx.accounts.token_metadata_program.to_account_info(),
+    }
+    .thaw(&[&seeds[..]])?;
+
+    // Freeze the settled loan note so it can no longer be traded as a live position
+    loan_note.freeze(
+        &ctx.accounts.vault_authority.to_account_info(),
+        &[&seeds[..]],
+    )?;
+
+    Ok(())
+}
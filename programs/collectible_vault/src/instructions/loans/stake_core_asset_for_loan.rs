@@ -0,0 +1,145 @@
+use {
+    crate::{
+        constants::pda_constants::{
+            ASSET_IMPAIRMENT_SEED, LOAN_COUNTER_SEED, LOAN_INFO_SEED, VAULT_SEED,
+        },
+        errors,
+        events::LoanRequested,
+        instructions::core_asset::{CoreAsset, MplCore, MPL_CORE_PROGRAM_ID},
+        state::{AccountHeader, LoanCounter, LoanInfo, LoanStatus},
+    },
+    anchor_lang::prelude::*,
+};
+
+/**
+ * Allows Metaplex Core asset owners to create a loan request by locking their asset as collateral.
+ * The asset stays in the owner's wallet, frozen, with the vault authority as its freeze and transfer delegate.
+ */
+#[derive(Accounts)]
+pub struct StakeCoreAssetForLoan<'info> {
+    // PDA account to store loan information, derived from the asset address
+    #[account(
+        init,
+        payer = owner,
+        space = LoanInfo::INIT_SPACE,
+        seeds = [LOAN_INFO_SEED, asset.key().as_ref()],
+        bump
+    )]
+    pub loan_info: Account<'info, LoanInfo>,
+
+    // Per-asset counter that assigns each loan against this asset a sequence number
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = LoanCounter::INIT_SPACE,
+        seeds = [LOAN_COUNTER_SEED, asset.key().as_ref()],
+        bump
+    )]
+    pub loan_counter: Account<'info, LoanCounter>,
+
+    // The Core asset being used as collateral
+    #[account(mut, owner = MPL_CORE_PROGRAM_ID)]
+    /// CHECK: Decoded and checked to be held by the owner before it is locked
+    pub asset: UncheckedAccount<'info>,
+
+    // Collection of the asset, required when the asset belongs to one
+    #[account(mut)]
+    /// CHECK: Checked against the asset's update authority before it is passed to Core
+    pub collection: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: PDA for vault authority, which becomes the asset's freeze and transfer delegate
+    #[account(
+        seeds = [VAULT_SEED],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Impairment PDA of the asset, which must not exist for the asset to be staked
+    #[account(
+        seeds = [ASSET_IMPAIRMENT_SEED, asset.key().as_ref()],
+        bump,
+        constraint = asset_impairment.data_is_empty() @ errors::ErrorCode::NftImpaired
+    )]
+    pub asset_impairment: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub core_program: Program<'info, MplCore>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle(
+    ctx: Context<StakeCoreAssetForLoan>,
+    loan_amount: u64,
+    interest_amount: u64,
+    duration: i64,
+    request_expiry: Option<i64>,
+) -> Result<()> {
+    // Validate loan duration
+    require!(duration > 0, errors::ErrorCode::InvalidLoanDuration);
+
+    // Validate the optional request expiry is in the future
+    if let Some(expiry) = request_expiry {
+        require!(
+            expiry > Clock::get()?.unix_timestamp,
+            errors::ErrorCode::InvalidLoanRequestExpiry
+        );
+    }
+
+    // Assign the next sequence number for loans against this asset
+    let loan_counter = &mut ctx.accounts.loan_counter;
+    loan_counter.header = AccountHeader::new(LoanCounter::VERSION);
+    loan_counter.nft_mint = ctx.accounts.asset.key();
+    loan_counter.count = loan_counter
+        .count
+        .checked_add(1)
+        .ok_or(errors::ErrorCode::CalculationError)?;
+
+    // Initialize loan request with provided terms; the asset address stands in for the NFT mint
+    let loan_info = &mut ctx.accounts.loan_info;
+    loan_info.header = AccountHeader::new(LoanInfo::VERSION);
+    loan_info.nft_mint = ctx.accounts.asset.key();
+    loan_info.nft_owner = ctx.accounts.owner.key();
+    loan_info.loan_amount = loan_amount;
+    loan_info.interest_amount = interest_amount;
+    loan_info.duration = duration;
+    loan_info.start_time = None; // Will be set when loan is funded
+    loan_info.lender = None; // Will be set when loan is funded
+    loan_info.status = LoanStatus::Requested;
+    loan_info.request_expiry = request_expiry;
+    loan_info.loan_id = loan_counter.count;
+
+    // Freeze the asset in the owner's wallet under the vault authority
+    let vault_bump = ctx.bumps.vault_authority;
+    let seeds = &[VAULT_SEED, &[vault_bump]];
+    CoreAsset {
+        asset: &ctx.accounts.asset.to_account_info(),
+        collection: ctx
+            .accounts
+            .collection
+            .as_ref()
+            .map(|account| account.as_ref()),
+        payer: &ctx.accounts.owner.to_account_info(),
+        core_program: &ctx.accounts.core_program.to_account_info(),
+        system_program: &ctx.accounts.system_program.to_account_info(),
+    }
+    .lock(
+        &ctx.accounts.owner.to_account_info(),
+        &ctx.accounts.vault_authority.to_account_info(),
+        &[&seeds[..]],
+    )?;
+
+    emit!(LoanRequested {
+        loan_info: ctx.accounts.loan_info.key(),
+        nft_mint: ctx.accounts.asset.key(),
+        loan_id: ctx.accounts.loan_info.loan_id,
+        borrower: ctx.accounts.owner.key(),
+        loan_amount,
+        interest_amount,
+        duration,
+        request_expiry,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -1,13 +1,17 @@
 use {
     crate::{
-        constants::pda_constants::{LOAN_INFO_SEED, VAULT_SEED},
+        constants::pda_constants::{
+            ASSET_IMPAIRMENT_SEED, LOAN_COUNTER_SEED, LOAN_INFO_SEED, VAULT_SEED,
+        },
         errors,
-        state::LoanInfo,
+        events::LoanRequested,
+        instructions::nft_transfer::*,
+        state::{AccountHeader, LoanCounter, LoanInfo, LoanStatus},
     },
     anchor_lang::prelude::*,
     anchor_spl::{
         associated_token::AssociatedToken,
-        token::{Mint, Token, TokenAccount},
+        token_interface::{Mint, TokenAccount, TokenInterface},
     },
 };
 
@@ -27,17 +31,28 @@ pub struct StakeNftForLoan<'info> {
     )]
     pub loan_info: Account<'info, LoanInfo>,
 
+    // Per-mint counter that assigns each loan against this NFT a sequence number
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = LoanCounter::INIT_SPACE,
+        seeds = [LOAN_COUNTER_SEED, nft_mint.key().as_ref()],
+        bump
+    )]
+    pub loan_counter: Account<'info, LoanCounter>,
+
     // The NFT being used as collateral
-    pub nft_mint: Account<'info, Mint>,
+    pub nft_mint: InterfaceAccount<'info, Mint>,
 
     // Owner's token account containing the NFT to be staked
     #[account(
         mut,
         associated_token::mint = nft_mint,
         associated_token::authority = owner,
+        associated_token::token_program = token_program,
         constraint = owner_nft_account.amount == 1 @ errors::ErrorCode::InvalidNFTAccount
     )]
-    pub owner_nft_account: Account<'info, TokenAccount>,
+    pub owner_nft_account: InterfaceAccount<'info, TokenAccount>,
 
     // Program's vault token account where the NFT will be held during the loan
     #[account(
@@ -45,8 +60,9 @@ pub struct StakeNftForLoan<'info> {
         payer = owner,
         associated_token::mint = nft_mint,
         associated_token::authority = vault_authority,
+        associated_token::token_program = token_program,
     )]
-    pub vault_nft_account: Account<'info, TokenAccount>,
+    pub vault_nft_account: InterfaceAccount<'info, TokenAccount>,
 
     /// CHECK: PDA for vault authority
     #[account(
@@ -57,23 +73,54 @@ pub struct StakeNftForLoan<'info> {
 
     #[account(mut)]
     pub owner: Signer<'info>,
+
+    /// CHECK: Impairment PDA of the NFT, which must not exist for the NFT to be staked
+    #[account(
+        seeds = [ASSET_IMPAIRMENT_SEED, nft_mint.key().as_ref()],
+        bump,
+        constraint = asset_impairment.data_is_empty() @ errors::ErrorCode::NftImpaired
+    )]
+    pub asset_impairment: UncheckedAccount<'info>,
+
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub rent: Sysvar<'info, Rent>,
+
+    // Token Metadata accounts, required only when the NFT is a programmable NFT
+    pub programmable: ProgrammableTransfer<'info>,
 }
 
-pub fn handle(
-    ctx: Context<StakeNftForLoan>,
+pub fn handle<'info>(
+    ctx: Context<'_, '_, '_, 'info, StakeNftForLoan<'info>>,
     loan_amount: u64,
     interest_amount: u64,
     duration: i64,
+    request_expiry: Option<i64>,
 ) -> Result<()> {
     // Validate loan duration
     require!(duration > 0, errors::ErrorCode::InvalidLoanDuration);
 
+    // Validate the optional request expiry is in the future
+    if let Some(expiry) = request_expiry {
+        require!(
+            expiry > Clock::get()?.unix_timestamp,
+            errors::ErrorCode::InvalidLoanRequestExpiry
+        );
+    }
+
+    // Assign the next sequence number for loans against this NFT
+    let loan_counter = &mut ctx.accounts.loan_counter;
+    loan_counter.header = AccountHeader::new(LoanCounter::VERSION);
+    loan_counter.nft_mint = ctx.accounts.nft_mint.key();
+    loan_counter.count = loan_counter
+        .count
+        .checked_add(1)
+        .ok_or(errors::ErrorCode::CalculationError)?;
+
     // Initialize loan request with provided terms
     let loan_info = &mut ctx.accounts.loan_info;
+    loan_info.header = AccountHeader::new(LoanInfo::VERSION);
     loan_info.nft_mint = ctx.accounts.nft_mint.key();
     loan_info.nft_owner = ctx.accounts.owner.key();
     loan_info.loan_amount = loan_amount;
@@ -81,20 +128,38 @@ pub fn handle(
     loan_info.duration = duration;
     loan_info.start_time = None; // Will be set when loan is funded
     loan_info.lender = None; // Will be set when loan is funded
-    loan_info.is_active = false;
+    loan_info.status = LoanStatus::Requested;
+    loan_info.request_expiry = request_expiry;
+    loan_info.loan_id = loan_counter.count;
 
     // Transfer NFT from owner to program vault for safekeeping
-    anchor_spl::token::transfer(
-        CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            anchor_spl::token::Transfer {
-                from: ctx.accounts.owner_nft_account.to_account_info(),
-                to: ctx.accounts.vault_nft_account.to_account_info(),
-                authority: ctx.accounts.owner.to_account_info(),
-            },
-        ),
-        1,
+    ctx.accounts.programmable.transfer(
+        NftTransfer {
+            nft_mint: &ctx.accounts.nft_mint.to_account_info(),
+            source: &ctx.accounts.owner_nft_account.to_account_info(),
+            source_owner: &ctx.accounts.owner.to_account_info(),
+            destination: &ctx.accounts.vault_nft_account.to_account_info(),
+            destination_owner: &ctx.accounts.vault_authority.to_account_info(),
+            payer: &ctx.accounts.owner.to_account_info(),
+            token_program: &ctx.accounts.token_program.to_account_info(),
+            associated_token_program: &ctx.accounts.associated_token_program.to_account_info(),
+            system_program: &ctx.accounts.system_program.to_account_info(),
+            additional_accounts: ctx.remaining_accounts,
+        },
+        &[],
     )?;
 
+    emit!(LoanRequested {
+        loan_info: ctx.accounts.loan_info.key(),
+        nft_mint: ctx.accounts.nft_mint.key(),
+        loan_id: ctx.accounts.loan_info.loan_id,
+        borrower: ctx.accounts.owner.key(),
+        loan_amount,
+        interest_amount,
+        duration,
+        request_expiry,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
     Ok(())
 }
@@ -0,0 +1,84 @@
+use {
+    crate::{
+        constants::{
+            admin_constants::get_admin_account_pubkey,
+            pda_constants::{LOAN_COUNTER_SEED, VAULT_SEED},
+        },
+        errors,
+        events::VaultAccountSwept,
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+/**
+ * Permissionless crank that closes an empty vault token account left behind by a loan taken before
+ * loans were counted and archived, which never closed the account on exit.
+ * Those loans recorded no payer for the account, so its rent goes to the admin. An NFT with a
+ * LoanCounter has been staked since, and that stake reused the account, so its loan exit closes it.
+ */
+#[derive(Accounts)]
+pub struct SweepBaselineVaultAccount<'info> {
+    // Any wallet may sweep an empty vault token account
+    pub cranker: Signer<'info>,
+
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+
+    // Empty vault token account that will be closed; a programmable NFT's account stays frozen and is reused
+    #[account(
+        mut,
+        associated_token::mint = nft_mint,
+        associated_token::authority = vault_authority,
+        associated_token::token_program = token_program,
+        constraint = vault_nft_account.amount == 0 && !vault_nft_account.is_frozen() @ errors::ErrorCode::VaultAccountNotSweepable
+    )]
+    pub vault_nft_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: PDA for vault authority
+    #[account(
+        seeds = [VAULT_SEED],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Per-mint loan counter, which must not exist for an NFT with no loan history
+    #[account(
+        seeds = [LOAN_COUNTER_SEED, nft_mint.key().as_ref()],
+        bump,
+        constraint = loan_counter.data_is_empty() @ errors::ErrorCode::InvalidVaultAccountPayer
+    )]
+    pub loan_counter: UncheckedAccount<'info>,
+
+    /// CHECK: Admin account, which receives the rent back
+    #[account(mut, address = get_admin_account_pubkey())]
+    pub admin: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handle(ctx: Context<SweepBaselineVaultAccount>) -> Result<()> {
+    // Close the vault's token account, refunding the admin in place of the unrecorded payer
+    let lamports = ctx.accounts.vault_nft_account.to_account_info().lamports();
+    let vault_bump = ctx.bumps.vault_authority;
+    let seeds = &[VAULT_SEED, &[vault_bump]];
+    anchor_spl::token_interface::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        anchor_spl::token_interface::CloseAccount {
+            account: ctx.accounts.vault_nft_account.to_account_info(),
+            destination: ctx.accounts.admin.to_account_info(),
+            authority: ctx.accounts.vault_authority.to_account_info(),
+        },
+        &[&seeds[..]],
+    ))?;
+
+    emit!(VaultAccountSwept {
+        vault_nft_account: ctx.accounts.vault_nft_account.key(),
+        nft_mint: ctx.accounts.nft_mint.key(),
+        rent_recipient: ctx.accounts.admin.key(),
+        lamports,
+        swept_by: ctx.accounts.cranker.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,160 @@
+use {
+    crate::{
+        constants::pda_constants::{
+            ASSET_IMPAIRMENT_SEED, ASSET_REDEMPTION_INFO_SEED, LOAN_INFO_SEED, OFFER_SEED,
+        },
+        errors,
+        events::OfferAccepted,
+        instructions::{nft_transfer::*, royalty::CreatorRoyalty},
+        state::Offer,
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::{
+        associated_token::AssociatedToken,
+        token_interface::{Mint, TokenAccount, TokenInterface},
+    },
+    mpl_token_metadata::ID as TOKEN_METADATA_PROGRAM_ID,
+};
+
+/**
+ * Allows an NFT holder to accept an offer on it.
+ * This instruction pays the NFT's creator royalty out of the offered lamports, pays the rest to the
+ * seller, moves the NFT from the seller's wallet to the buyer and closes the offer. A listed NFT must
+ * have its listing cancelled first, which can happen in the same transaction.
+ * The metadata creators are passed as the first remaining accounts, in metadata order, ahead of any
+ * transfer hook accounts.
+ */
+#[derive(Accounts)]
+pub struct AcceptOffer<'info> {
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [OFFER_SEED, nft_mint.key().as_ref(), buyer.key().as_ref()],
+        bump,
+        constraint = offer.is_open(Clock::get()?.unix_timestamp) @ errors::ErrorCode::OfferExpired,
+        close = buyer
+    )]
+    pub offer: Account<'info, Offer>,
+
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+
+    // Seller's token account holding the NFT
+    #[account(
+        mut,
+        associated_token::mint = nft_mint,
+        associated_token::authority = seller,
+        associated_token::token_program = token_program,
+        constraint = seller_nft_account.amount == 1 @ errors::ErrorCode::InvalidNFTAccount
+    )]
+    pub seller_nft_account: InterfaceAccount<'info, TokenAccount>,
+
+    // Buyer's token account receiving the NFT
+    #[account(
+        init_if_needed,
+        payer = seller,
+        associated_token::mint = nft_mint,
+        associated_token::authority = buyer,
+        associated_token::token_program = token_program,
+    )]
+    pub buyer_nft_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Buyer receiving the NFT and the offer rent; verified by the offer seeds
+    #[account(mut)]
+    pub buyer: UncheckedAccount<'info>,
+
+    /// CHECK: Loan PDA of the NFT, which must not exist for the NFT to be sold
+    #[account(
+        seeds = [LOAN_INFO_SEED, nft_mint.key().as_ref()],
+        bump,
+        constraint = loan_info.data_is_empty() @ errors::ErrorCode::NftHasOpenLoan
+    )]
+    pub loan_info: UncheckedAccount<'info>,
+
+    /// CHECK: Redemption PDA of the NFT, which must not exist for the NFT to be sold
+    #[account(
+        seeds = [ASSET_REDEMPTION_INFO_SEED, nft_mint.key().as_ref()],
+        bump,
+        constraint = asset_redemption_info.data_is_empty() @ errors::ErrorCode::NftHasRedemptionRequest
+    )]
+    pub asset_redemption_info: UncheckedAccount<'info>,
+
+    /// CHECK: Token Metadata account of the NFT, read for its royalty; it may not exist for Token-2022 NFTs
+    #[account(
+        seeds = [
+            b"metadata",
+            TOKEN_METADATA_PROGRAM_ID.as_ref(),
+            nft_mint.key().as_ref(),
+        ],
+        bump,
+        seeds::program = TOKEN_METADATA_PROGRAM_ID,
+    )]
+    pub nft_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Impairment PDA of the NFT, which must not exist for the NFT to be sold
+    #[account(
+        seeds = [ASSET_IMPAIRMENT_SEED, nft_mint.key().as_ref()],
+        bump,
+        constraint = asset_impairment.data_is_empty() @ errors::ErrorCode::NftImpaired
+    )]
+    pub asset_impairment: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+
+    // Token Metadata accounts, required only when the NFT is a programmable NFT
+    pub programmable: ProgrammableTransfer<'info>,
+}
+
+pub fn handle<'info>(ctx: Context<'_, '_, '_, 'info, AcceptOffer<'info>>) -> Result<()> {
+    let price = ctx.accounts.offer.price;
+    let offer = ctx.accounts.offer.to_account_info();
+
+    // Pay the creators their royalty and the seller the rest out of the escrowed lamports; the
+    // offer account's rent returns to the buyer when it closes
+    let (royalty, remaining_accounts) = CreatorRoyalty::load(
+        &ctx.accounts.nft_metadata.to_account_info(),
+        price,
+        ctx.remaining_accounts,
+    )?;
+    let royalty_amount = match royalty {
+        Some(royalty) => royalty.pay_from_program_account(&offer)?.amount,
+        None => 0,
+    };
+    let proceeds = price
+        .checked_sub(royalty_amount)
+        .ok_or(errors::ErrorCode::CalculationError)?;
+    offer.sub_lamports(proceeds)?;
+    ctx.accounts.seller.add_lamports(proceeds)?;
+
+    // Transfer the NFT from the seller to the buyer
+    ctx.accounts.programmable.transfer(
+        NftTransfer {
+            nft_mint: &ctx.accounts.nft_mint.to_account_info(),
+            source: &ctx.accounts.seller_nft_account.to_account_info(),
+            source_owner: &ctx.accounts.seller.to_account_info(),
+            destination: &ctx.accounts.buyer_nft_account.to_account_info(),
+            destination_owner: &ctx.accounts.buyer.to_account_info(),
+            payer: &ctx.accounts.seller.to_account_info(),
+            token_program: &ctx.accounts.token_program.to_account_info(),
+            associated_token_program: &ctx.accounts.associated_token_program.to_account_info(),
+            system_program: &ctx.accounts.system_program.to_account_info(),
+            additional_accounts: remaining_accounts,
+        },
+        &[],
+    )?;
+
+    emit!(OfferAccepted {
+        offer: ctx.accounts.offer.key(),
+        nft_mint: ctx.accounts.nft_mint.key(),
+        seller: ctx.accounts.seller.key(),
+        buyer: ctx.accounts.buyer.key(),
+        price,
+        royalty_amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,187 @@
+use {
+    crate::{
+        constants::pda_constants::{
+            ASSET_IMPAIRMENT_SEED, ASSET_REDEMPTION_INFO_SEED, LISTING_SEED, LOAN_INFO_SEED,
+            MARKETPLACE_ESCROW_SEED,
+        },
+        errors,
+        events::ListingSold,
+        instructions::{nft_transfer::*, royalty::CreatorRoyalty},
+        state::Listing,
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::{
+        associated_token::AssociatedToken,
+        token_interface::{Mint, TokenAccount, TokenInterface},
+    },
+    mpl_token_metadata::ID as TOKEN_METADATA_PROGRAM_ID,
+};
+
+/**
+ * Allows anyone to buy an NFT listed on the marketplace.
+ * This instruction pays the NFT's creator royalty out of the price, pays the rest to the seller and
+ * moves the NFT from the marketplace escrow to the buyer.
+ * The metadata creators are passed as the first remaining accounts, in metadata order, ahead of any
+ * transfer hook accounts.
+ */
+#[derive(Accounts)]
+pub struct BuyListing<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [LISTING_SEED, nft_mint.key().as_ref()],
+        bump,
+        constraint = listing.seller == seller.key() @ errors::ErrorCode::InvalidSeller,
+        close = seller
+    )]
+    pub listing: Account<'info, Listing>,
+
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+
+    // Marketplace escrow's token account holding the NFT
+    #[account(
+        mut,
+        associated_token::mint = nft_mint,
+        associated_token::authority = marketplace_escrow,
+        associated_token::token_program = token_program,
+    )]
+    pub escrow_nft_account: InterfaceAccount<'info, TokenAccount>,
+
+    // Buyer's token account receiving the NFT
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::mint = nft_mint,
+        associated_token::authority = buyer,
+        associated_token::token_program = token_program,
+    )]
+    pub buyer_nft_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: PDA for the marketplace escrow
+    #[account(
+        seeds = [MARKETPLACE_ESCROW_SEED],
+        bump
+    )]
+    pub marketplace_escrow: UncheckedAccount<'info>,
+
+    /// CHECK: Seller receiving the proceeds and the listing rent; verified against the listing
+    #[account(mut)]
+    pub seller: UncheckedAccount<'info>,
+
+    /// CHECK: Loan PDA of the NFT, which must not exist for the NFT to be sold
+    #[account(
+        seeds = [LOAN_INFO_SEED, nft_mint.key().as_ref()],
+        bump,
+        constraint = loan_info.data_is_empty() @ errors::ErrorCode::NftHasOpenLoan
+    )]
+    pub loan_info: UncheckedAccount<'info>,
+
+    /// CHECK: Redemption PDA of the NFT, which must not exist for the NFT to be sold
+    #[account(
+        seeds = [ASSET_REDEMPTION_INFO_SEED, nft_mint.key().as_ref()],
+        bump,
+        constraint = asset_redemption_info.data_is_empty() @ errors::ErrorCode::NftHasRedemptionRequest
+    )]
+    pub asset_redemption_info: UncheckedAccount<'info>,
+
+    /// CHECK: Token Metadata account of the NFT, read for its royalty; it may not exist for Token-2022 NFTs
+    #[account(
+        seeds = [
+            b"metadata",
+            TOKEN_METADATA_PROGRAM_ID.as_ref(),
+            nft_mint.key().as_ref(),
+        ],
+        bump,
+        seeds::program = TOKEN_METADATA_PROGRAM_ID,
+    )]
+    pub nft_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Impairment PDA of the NFT, which must not exist for the NFT to be bought
+    #[account(
+        seeds = [ASSET_IMPAIRMENT_SEED, nft_mint.key().as_ref()],
+        bump,
+        constraint = asset_impairment.data_is_empty() @ errors::ErrorCode::NftImpaired
+    )]
+    pub asset_impairment: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+
+    // Token Metadata accounts, required only when the NFT is a programmable NFT
+    pub programmable: ProgrammableTransfer<'info>,
+}
+
+pub fn handle<'info>(ctx: Context<'_, '_, '_, 'info, BuyListing<'info>>) -> Result<()> {
+    let price = ctx.accounts.listing.price;
+    let buyer = ctx.accounts.buyer.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+
+    // Pay the creators their royalty out of the price and the seller the rest
+    let (royalty, remaining_accounts) = CreatorRoyalty::load(
+        &ctx.accounts.nft_metadata.to_account_info(),
+        price,
+        ctx.remaining_accounts,
+    )?;
+    let royalty_amount = match royalty {
+        Some(royalty) => royalty.pay_from_wallet(&buyer, &system_program)?.amount,
+        None => 0,
+    };
+    anchor_lang::system_program::transfer(
+        CpiContext::new(
+            system_program.clone(),
+            anchor_lang::system_program::Transfer {
+                from: buyer.clone(),
+                to: ctx.accounts.seller.to_account_info(),
+            },
+        ),
+        price
+            .checked_sub(royalty_amount)
+            .ok_or(errors::ErrorCode::CalculationError)?,
+    )?;
+
+    let escrow_bump = ctx.bumps.marketplace_escrow;
+    let seeds = &[MARKETPLACE_ESCROW_SEED, &[escrow_bump]];
+    let signer = &[&seeds[..]];
+
+    // Transfer the NFT from the marketplace escrow to the buyer
+    let programmable = ctx.accounts.programmable.transfer(
+        NftTransfer {
+            nft_mint: &ctx.accounts.nft_mint.to_account_info(),
+            source: &ctx.accounts.escrow_nft_account.to_account_info(),
+            source_owner: &ctx.accounts.marketplace_escrow.to_account_info(),
+            destination: &ctx.accounts.buyer_nft_account.to_account_info(),
+            destination_owner: &buyer,
+            payer: &buyer,
+            token_program: &ctx.accounts.token_program.to_account_info(),
+            associated_token_program: &ctx.accounts.associated_token_program.to_account_info(),
+            system_program: &system_program,
+            additional_accounts: remaining_accounts,
+        },
+        signer,
+    )?;
+
+    // Close the escrow's token account, refunding the seller who paid for it
+    close_emptied_nft_account(
+        &mut ctx.accounts.escrow_nft_account,
+        programmable,
+        &ctx.accounts.seller.to_account_info(),
+        &ctx.accounts.marketplace_escrow.to_account_info(),
+        &ctx.accounts.token_program.to_account_info(),
+        signer,
+    )?;
+
+    emit!(ListingSold {
+        listing: ctx.accounts.listing.key(),
+        nft_mint: ctx.accounts.nft_mint.key(),
+        seller: ctx.accounts.seller.key(),
+        buyer: ctx.accounts.buyer.key(),
+        price,
+        royalty_amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
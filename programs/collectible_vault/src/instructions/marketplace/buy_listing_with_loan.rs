@@ -0,0 +1,342 @@
+use {
+    crate::{
+        constants::pda_constants::{
+            ASSET_IMPAIRMENT_SEED, ASSET_REDEMPTION_INFO_SEED, LISTING_SEED, LOAN_COUNTER_SEED,
+            LOAN_INFO_SEED, LOAN_NOTE_SEED, MARKETPLACE_ESCROW_SEED, VAULT_SEED,
+        },
+        errors,
+        events::{ListingSold, LoanFunded, LoanRequested},
+        instructions::{nft_transfer::*, royalty::CreatorRoyalty},
+        state::{AccountHeader, Listing, LoanCounter, LoanInfo, LoanStatus},
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::{
+        associated_token::AssociatedToken,
+        token::{self, spl_token::instruction::AuthorityType, Token},
+        token_interface::{Mint, TokenAccount, TokenInterface},
+    },
+    mpl_token_metadata::ID as TOKEN_METADATA_PROGRAM_ID,
+};
+
+/**
+ * Allows a buyer to finance the purchase of a listed NFT with a loan.
+ * The buyer pays a down payment, which covers the creator royalty, and the lender pays the rest of the
+ * price to the seller. The NFT moves from the marketplace escrow to the loan vault as collateral for an
+ * active loan in the buyer's name, and the lender receives the loan note.
+ * The metadata creators are passed as the first remaining accounts, in metadata order, ahead of any
+ * transfer hook accounts.
+ */
+#[derive(Accounts)]
+pub struct BuyListingWithLoan<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    // Lender funding the part of the price not covered by the down payment
+    #[account(mut)]
+    pub lender: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [LISTING_SEED, nft_mint.key().as_ref()],
+        bump,
+        constraint = listing.seller == seller.key() @ errors::ErrorCode::InvalidSeller,
+        close = seller
+    )]
+    pub listing: Account<'info, Listing>,
+
+    // PDA account storing the loan, with the buyer as borrower
+    #[account(
+        init,
+        payer = buyer,
+        space = LoanInfo::INIT_SPACE,
+        seeds = [LOAN_INFO_SEED, nft_mint.key().as_ref()],
+        bump
+    )]
+    pub loan_info: Account<'info, LoanInfo>,
+
+    // Per-mint counter that assigns each loan against this NFT a sequence number
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = LoanCounter::INIT_SPACE,
+        seeds = [LOAN_COUNTER_SEED, nft_mint.key().as_ref()],
+        bump
+    )]
+    pub loan_counter: Account<'info, LoanCounter>,
+
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+
+    // Marketplace escrow's token account holding the NFT
+    #[account(
+        mut,
+        associated_token::mint = nft_mint,
+        associated_token::authority = marketplace_escrow,
+        associated_token::token_program = token_program,
+    )]
+    pub escrow_nft_account: InterfaceAccount<'info, TokenAccount>,
+
+    // Program's vault token account where the NFT will be held during the loan
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::mint = nft_mint,
+        associated_token::authority = vault_authority,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_nft_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: PDA for the marketplace escrow
+    #[account(
+        seeds = [MARKETPLACE_ESCROW_SEED],
+        bump
+    )]
+    pub marketplace_escrow: UncheckedAccount<'info>,
+
+    /// CHECK: PDA for vault authority
+    #[account(
+        seeds = [VAULT_SEED],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Seller receiving the proceeds and the listing rent; verified against the listing
+    #[account(mut)]
+    pub seller: UncheckedAccount<'info>,
+
+    /// CHECK: Redemption PDA of the NFT, which must not exist for the NFT to be sold
+    #[account(
+        seeds = [ASSET_REDEMPTION_INFO_SEED, nft_mint.key().as_ref()],
+        bump,
+        constraint = asset_redemption_info.data_is_empty() @ errors::ErrorCode::NftHasRedemptionRequest
+    )]
+    pub asset_redemption_info: UncheckedAccount<'info>,
+
+    /// CHECK: Token Metadata account of the NFT, read for its royalty; it may not exist for Token-2022 NFTs
+    #[account(
+        seeds = [
+            b"metadata",
+            TOKEN_METADATA_PROGRAM_ID.as_ref(),
+            nft_mint.key().as_ref(),
+        ],
+        bump,
+        seeds::program = TOKEN_METADATA_PROGRAM_ID,
+    )]
+    pub nft_metadata: UncheckedAccount<'info>,
+
+    // Loan note mint representing the lender's position in this loan
+    #[account(
+        init,
+        payer = lender,
+        seeds = [
+            LOAN_NOTE_SEED,
+            nft_mint.key().as_ref(),
+            &loan_counter.count.checked_add(1).ok_or(errors::ErrorCode::CalculationError)?.to_le_bytes()
+        ],
+        bump,
+        mint::decimals = 0,
+        mint::authority = vault_authority,
+        mint::freeze_authority = vault_authority,
+        mint::token_program = note_token_program,
+    )]
+    pub loan_note_mint: Account<'info, token::Mint>,
+
+    // Lender's token account that will receive the loan note
+    #[account(
+        init,
+        payer = lender,
+        associated_token::mint = loan_note_mint,
+        associated_token::authority = lender,
+        associated_token::token_program = note_token_program,
+    )]
+    pub lender_note_account: Account<'info, token::TokenAccount>,
+
+    /// CHECK: Impairment PDA of the NFT, which must not exist for the NFT to be bought
+    #[account(
+        seeds = [ASSET_IMPAIRMENT_SEED, nft_mint.key().as_ref()],
+        bump,
+        constraint = asset_impairment.data_is_empty() @ errors::ErrorCode::NftImpaired
+    )]
+    pub asset_impairment: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    // SPL Token program owning the loan note, which may differ from the NFT's token program
+    pub note_token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+
+    // Token Metadata accounts, required only when the NFT is a programmable NFT
+    pub programmable: ProgrammableTransfer<'info>,
+}
+
+pub fn handle<'info>(
+    ctx: Context<'_, '_, '_, 'info, BuyListingWithLoan<'info>>,
+    down_payment: u64,
+    interest_amount: u64,
+    duration: i64,
+) -> Result<()> {
+    let price = ctx.accounts.listing.price;
+    require!(
+        down_payment > 0 && down_payment < price,
+        errors::ErrorCode::InvalidDownPayment
+    );
+    require!(duration > 0, errors::ErrorCode::InvalidLoanDuration);
+    let loan_amount = price - down_payment;
+
+    let buyer = ctx.accounts.buyer.to_account_info();
+    let seller = ctx.accounts.seller.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+
+    // Pay the creators their royalty out of the down payment and the seller the rest of it
+    let (royalty, remaining_accounts) = CreatorRoyalty::load(
+        &ctx.accounts.nft_metadata.to_account_info(),
+        price,
+        ctx.remaining_accounts,
+    )?;
+    let royalty_amount = match royalty {
+        Some(royalty) => royalty.pay_from_wallet(&buyer, &system_program)?.amount,
+        None => 0,
+    };
+    anchor_lang::system_program::transfer(
+        CpiContext::new(
+            system_program.clone(),
+            anchor_lang::system_program::Transfer {
+                from: buyer.clone(),
+                to: seller.clone(),
+            },
+        ),
+        down_payment
+            .checked_sub(royalty_amount)
+            .ok_or(errors::ErrorCode::InvalidDownPayment)?,
+    )?;
+
+    // The lender funds the remainder of the price directly to the seller
+    anchor_lang::system_program::transfer(
+        CpiContext::new(
+            system_program.clone(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.lender.to_account_info(),
+                to: seller,
+            },
+        ),
+        loan_amount,
+    )?;
+
+    // Assign the next sequence number for loans against this NFT
+    let loan_counter = &mut ctx.accounts.loan_counter;
+    loan_counter.header = AccountHeader::new(LoanCounter::VERSION);
+    loan_counter.nft_mint = ctx.accounts.nft_mint.key();
+    loan_counter.count = loan_counter
+        .count
+        .checked_add(1)
+        .ok_or(errors::ErrorCode::CalculationError)?;
+
+    // Open the loan in the buyer's name, already funded by the lender
+    let start_time = Clock::get()?.unix_timestamp;
+    let loan_info = &mut ctx.accounts.loan_info;
+    loan_info.header = AccountHeader::new(LoanInfo::VERSION);
+    loan_info.nft_mint = ctx.accounts.nft_mint.key();
+    loan_info.nft_owner = ctx.accounts.buyer.key();
+    loan_info.loan_amount = loan_amount;
+    loan_info.interest_amount = interest_amount;
+    loan_info.duration = duration;
+    loan_info.start_time = Some(start_time);
+    loan_info.lender = Some(ctx.accounts.lender.key());
+    loan_info.status = LoanStatus::Active;
+    loan_info.request_expiry = None;
+    loan_info.loan_id = loan_counter.count;
+
+    // Mint the loan note to the lender and remove the mint authority
+    let vault_bump = ctx.bumps.vault_authority;
+    let vault_seeds = &[VAULT_SEED, &[vault_bump]];
+    let vault_signer = &[&vault_seeds[..]];
+
+    anchor_spl::token::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.note_token_program.to_account_info(),
+            anchor_spl::token::MintTo {
+                mint: ctx.accounts.loan_note_mint.to_account_info(),
+                to: ctx.accounts.lender_note_account.to_account_info(),
+                authority: ctx.accounts.vault_authority.to_account_info(),
+            },
+            vault_signer,
+        ),
+        1,
+    )?;
+    anchor_spl::token::set_authority(
+        CpiContext::new_with_signer(
+            ctx.accounts.note_token_program.to_account_info(),
+            anchor_spl::token::SetAuthority {
+                current_authority: ctx.accounts.vault_authority.to_account_info(),
+                account_or_mint: ctx.accounts.loan_note_mint.to_account_info(),
+            },
+            vault_signer,
+        ),
+        AuthorityType::MintTokens,
+        None,
+    )?;
+
+    let escrow_bump = ctx.bumps.marketplace_escrow;
+    let escrow_seeds = &[MARKETPLACE_ESCROW_SEED, &[escrow_bump]];
+    let escrow_signer = &[&escrow_seeds[..]];
+
+    // Transfer the NFT from the marketplace escrow to the loan vault
+    let programmable = ctx.accounts.programmable.transfer(
+        NftTransfer {
+            nft_mint: &ctx.accounts.nft_mint.to_account_info(),
+            source: &ctx.accounts.escrow_nft_account.to_account_info(),
+            source_owner: &ctx.accounts.marketplace_escrow.to_account_info(),
+            destination: &ctx.accounts.vault_nft_account.to_account_info(),
+            destination_owner: &ctx.accounts.vault_authority.to_account_info(),
+            payer: &buyer,
+            token_program: &ctx.accounts.token_program.to_account_info(),
+            associated_token_program: &ctx.accounts.associated_token_program.to_account_info(),
+            system_program: &system_program,
+            additional_accounts: remaining_accounts,
+        },
+        escrow_signer,
+    )?;
+
+    // Close the escrow's token account, refunding the seller who paid for it
+    close_emptied_nft_account(
+        &mut ctx.accounts.escrow_nft_account,
+        programmable,
+        &ctx.accounts.seller.to_account_info(),
+        &ctx.accounts.marketplace_escrow.to_account_info(),
+        &ctx.accounts.token_program.to_account_info(),
+        escrow_signer,
+    )?;
+
+    emit!(ListingSold {
+        listing: ctx.accounts.listing.key(),
+        nft_mint: ctx.accounts.nft_mint.key(),
+        seller: ctx.accounts.seller.key(),
+        buyer: ctx.accounts.buyer.key(),
+        price,
+        royalty_amount,
+        timestamp: start_time,
+    });
+    emit!(LoanRequested {
+        loan_info: ctx.accounts.loan_info.key(),
+        nft_mint: ctx.accounts.nft_mint.key(),
+        loan_id: ctx.accounts.loan_info.loan_id,
+        borrower: ctx.accounts.buyer.key(),
+        loan_amount,
+        interest_amount,
+        duration,
+        request_expiry: None,
+        timestamp: start_time,
+    });
+    emit!(LoanFunded {
+        loan_info: ctx.accounts.loan_info.key(),
+        nft_mint: ctx.accounts.nft_mint.key(),
+        loan_id: ctx.accounts.loan_info.loan_id,
+        borrower: ctx.accounts.buyer.key(),
+        lender: ctx.accounts.lender.key(),
+        loan_note_mint: ctx.accounts.loan_note_mint.key(),
+        loan_amount,
+        start_time,
+    });
+
+    Ok(())
+}
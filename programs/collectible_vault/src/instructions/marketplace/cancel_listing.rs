@@ -0,0 +1,110 @@
+use {
+    crate::{
+        constants::pda_constants::{LISTING_SEED, MARKETPLACE_ESCROW_SEED},
+        errors,
+        events::ListingCancelled,
+        instructions::nft_transfer::*,
+        state::Listing,
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::{
+        associated_token::AssociatedToken,
+        token_interface::{Mint, TokenAccount, TokenInterface},
+    },
+};
+
+/**
+ * Allows a seller to withdraw their marketplace listing.
+ * This instruction returns the NFT from the marketplace escrow and closes the listing.
+ */
+#[derive(Accounts)]
+pub struct CancelListing<'info> {
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [LISTING_SEED, nft_mint.key().as_ref()],
+        bump,
+        constraint = listing.seller == seller.key() @ errors::ErrorCode::InvalidSeller,
+        close = seller
+    )]
+    pub listing: Account<'info, Listing>,
+
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+
+    // Seller's token account receiving the NFT back
+    #[account(
+        init_if_needed,
+        payer = seller,
+        associated_token::mint = nft_mint,
+        associated_token::authority = seller,
+        associated_token::token_program = token_program,
+    )]
+    pub seller_nft_account: InterfaceAccount<'info, TokenAccount>,
+
+    // Marketplace escrow's token account holding the NFT
+    #[account(
+        mut,
+        associated_token::mint = nft_mint,
+        associated_token::authority = marketplace_escrow,
+        associated_token::token_program = token_program,
+    )]
+    pub escrow_nft_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: PDA for the marketplace escrow
+    #[account(
+        seeds = [MARKETPLACE_ESCROW_SEED],
+        bump
+    )]
+    pub marketplace_escrow: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+
+    // Token Metadata accounts, required only when the NFT is a programmable NFT
+    pub programmable: ProgrammableTransfer<'info>,
+}
+
+pub fn handle<'info>(ctx: Context<'_, '_, '_, 'info, CancelListing<'info>>) -> Result<()> {
+    let escrow_bump = ctx.bumps.marketplace_escrow;
+    let seeds = &[MARKETPLACE_ESCROW_SEED, &[escrow_bump]];
+    let signer = &[&seeds[..]];
+
+    // Return the NFT to the seller
+    let programmable = ctx.accounts.programmable.transfer(
+        NftTransfer {
+            nft_mint: &ctx.accounts.nft_mint.to_account_info(),
+            source: &ctx.accounts.escrow_nft_account.to_account_info(),
+            source_owner: &ctx.accounts.marketplace_escrow.to_account_info(),
+            destination: &ctx.accounts.seller_nft_account.to_account_info(),
+            destination_owner: &ctx.accounts.seller.to_account_info(),
+            payer: &ctx.accounts.seller.to_account_info(),
+            token_program: &ctx.accounts.token_program.to_account_info(),
+            associated_token_program: &ctx.accounts.associated_token_program.to_account_info(),
+            system_program: &ctx.accounts.system_program.to_account_info(),
+            additional_accounts: ctx.remaining_accounts,
+        },
+        signer,
+    )?;
+
+    // Close the escrow's token account, refunding the seller who paid for it
+    close_emptied_nft_account(
+        &mut ctx.accounts.escrow_nft_account,
+        programmable,
+        &ctx.accounts.seller.to_account_info(),
+        &ctx.accounts.marketplace_escrow.to_account_info(),
+        &ctx.accounts.token_program.to_account_info(),
+        signer,
+    )?;
+
+    emit!(ListingCancelled {
+        listing: ctx.accounts.listing.key(),
+        nft_mint: ctx.accounts.nft_mint.key(),
+        seller: ctx.accounts.seller.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use {
+    crate::{constants::pda_constants::OFFER_SEED, events::OfferCancelled, state::Offer},
+    anchor_lang::prelude::*,
+};
+
+/**
+ * Allows a buyer to withdraw their offer, including after it has expired.
+ * This instruction closes the offer account, returning the offered lamports and the rent to the buyer.
+ */
+#[derive(Accounts)]
+pub struct CancelOffer<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [OFFER_SEED, offer.nft_mint.as_ref(), buyer.key().as_ref()],
+        bump,
+        close = buyer
+    )]
+    pub offer: Account<'info, Offer>,
+}
+
+pub fn handle(ctx: Context<CancelOffer>) -> Result<()> {
+    emit!(OfferCancelled {
+        offer: ctx.accounts.offer.key(),
+        nft_mint: ctx.accounts.offer.nft_mint,
+        buyer: ctx.accounts.buyer.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,135 @@
+use {
+    crate::{
+        constants::pda_constants::{
+            ASSET_IMPAIRMENT_SEED, ASSET_REDEMPTION_INFO_SEED, LISTING_SEED, LOAN_INFO_SEED,
+            MARKETPLACE_ESCROW_SEED,
+        },
+        errors,
+        events::NftListed,
+        instructions::nft_transfer::*,
+        state::{AccountHeader, Listing},
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::{
+        associated_token::AssociatedToken,
+        token_interface::{Mint, TokenAccount, TokenInterface},
+    },
+};
+
+/**
+ * Allows an NFT holder to list it for resale on the marketplace.
+ * This instruction moves the NFT into the marketplace escrow. NFTs with an open loan, a
+ * redemption request or an impaired physical item cannot be listed.
+ */
+#[derive(Accounts)]
+pub struct ListNft<'info> {
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    #[account(
+        init,
+        payer = seller,
+        space = Listing::INIT_SPACE,
+        seeds = [LISTING_SEED, nft_mint.key().as_ref()],
+        bump
+    )]
+    pub listing: Account<'info, Listing>,
+
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+
+    // Seller's token account holding the NFT
+    #[account(
+        mut,
+        associated_token::mint = nft_mint,
+        associated_token::authority = seller,
+        associated_token::token_program = token_program,
+    )]
+    pub seller_nft_account: InterfaceAccount<'info, TokenAccount>,
+
+    // Marketplace escrow's token account that will hold the NFT
+    #[account(
+        init_if_needed,
+        payer = seller,
+        associated_token::mint = nft_mint,
+        associated_token::authority = marketplace_escrow,
+        associated_token::token_program = token_program,
+    )]
+    pub escrow_nft_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: PDA for the marketplace escrow
+    #[account(
+        seeds = [MARKETPLACE_ESCROW_SEED],
+        bump
+    )]
+    pub marketplace_escrow: UncheckedAccount<'info>,
+
+    /// CHECK: Loan PDA of the NFT, which must not exist for the NFT to be listed
+    #[account(
+        seeds = [LOAN_INFO_SEED, nft_mint.key().as_ref()],
+        bump,
+        constraint = loan_info.data_is_empty() @ errors::ErrorCode::NftHasOpenLoan
+    )]
+    pub loan_info: UncheckedAccount<'info>,
+
+    /// CHECK: Redemption PDA of the NFT, which must not exist for the NFT to be listed
+    #[account(
+        seeds = [ASSET_REDEMPTION_INFO_SEED, nft_mint.key().as_ref()],
+        bump,
+        constraint = asset_redemption_info.data_is_empty() @ errors::ErrorCode::NftHasRedemptionRequest
+    )]
+    pub asset_redemption_info: UncheckedAccount<'info>,
+
+    /// CHECK: Impairment PDA of the NFT, which must not exist for the NFT to be listed
+    #[account(
+        seeds = [ASSET_IMPAIRMENT_SEED, nft_mint.key().as_ref()],
+        bump,
+        constraint = asset_impairment.data_is_empty() @ errors::ErrorCode::NftImpaired
+    )]
+    pub asset_impairment: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+
+    // Token Metadata accounts, required only when the NFT is a programmable NFT
+    pub programmable: ProgrammableTransfer<'info>,
+}
+
+pub fn handle<'info>(ctx: Context<'_, '_, '_, 'info, ListNft<'info>>, price: u64) -> Result<()> {
+    require!(price > 0, errors::ErrorCode::InvalidSalePrice);
+
+    let listed_at = Clock::get()?.unix_timestamp;
+    let listing = &mut ctx.accounts.listing;
+    listing.header = AccountHeader::new(Listing::VERSION);
+    listing.nft_mint = ctx.accounts.nft_mint.key();
+    listing.seller = ctx.accounts.seller.key();
+    listing.price = price;
+    listing.listed_at = listed_at;
+
+    // Move the NFT into the marketplace escrow
+    ctx.accounts.programmable.transfer(
+        NftTransfer {
+            nft_mint: &ctx.accounts.nft_mint.to_account_info(),
+            source: &ctx.accounts.seller_nft_account.to_account_info(),
+            source_owner: &ctx.accounts.seller.to_account_info(),
+            destination: &ctx.accounts.escrow_nft_account.to_account_info(),
+            destination_owner: &ctx.accounts.marketplace_escrow.to_account_info(),
+            payer: &ctx.accounts.seller.to_account_info(),
+            token_program: &ctx.accounts.token_program.to_account_info(),
+            associated_token_program: &ctx.accounts.associated_token_program.to_account_info(),
+            system_program: &ctx.accounts.system_program.to_account_info(),
+            additional_accounts: ctx.remaining_accounts,
+        },
+        &[],
+    )?;
+
+    emit!(NftListed {
+        listing: ctx.accounts.listing.key(),
+        nft_mint: ctx.accounts.nft_mint.key(),
+        seller: ctx.accounts.seller.key(),
+        price,
+        timestamp: listed_at,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use {
+    crate::{
+        constants::pda_constants::OFFER_SEED,
+        errors,
+        events::OfferMade,
+        state::{AccountHeader, Offer},
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token_interface::Mint,
+};
+
+/**
+ * Allows a buyer to make an offer on an NFT, whether or not it is listed.
+ * This instruction deposits the offered lamports into the offer account, where they stay until the
+ * offer is accepted or cancelled. A buyer can hold one offer per NFT.
+ */
+#[derive(Accounts)]
+pub struct MakeOffer<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = buyer,
+        space = Offer::INIT_SPACE,
+        seeds = [OFFER_SEED, nft_mint.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub offer: Account<'info, Offer>,
+
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle(ctx: Context<MakeOffer>, price: u64, expiry: Option<i64>) -> Result<()> {
+    require!(price > 0, errors::ErrorCode::InvalidSalePrice);
+
+    let now = Clock::get()?.unix_timestamp;
+    if let Some(expiry) = expiry {
+        require!(expiry > now, errors::ErrorCode::InvalidOfferExpiry);
+    }
+
+    let offer = &mut ctx.accounts.offer;
+    offer.header = AccountHeader::new(Offer::VERSION);
+    offer.nft_mint = ctx.accounts.nft_mint.key();
+    offer.buyer = ctx.accounts.buyer.key();
+    offer.price = price;
+    offer.expiry = expiry;
+    offer.created_at = now;
+
+    // Escrow the offered lamports in the offer account
+    anchor_lang::system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.buyer.to_account_info(),
+                to: ctx.accounts.offer.to_account_info(),
+            },
+        ),
+        price,
+    )?;
+
+    emit!(OfferMade {
+        offer: ctx.accounts.offer.key(),
+        nft_mint: ctx.accounts.nft_mint.key(),
+        buyer: ctx.accounts.buyer.key(),
+        price,
+        expiry,
+        timestamp: now,
+    });
+
+    Ok(())
+}
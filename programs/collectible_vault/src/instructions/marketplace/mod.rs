@@ -0,0 +1,7 @@
+pub mod accept_offer;
+pub mod buy_listing;
+pub mod buy_listing_with_loan;
+pub mod cancel_listing;
+pub mod cancel_offer;
+pub mod list_nft;
+pub mod make_offer;
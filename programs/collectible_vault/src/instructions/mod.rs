@@ -1,3 +1,17 @@
 pub mod admin_operations;
 pub mod asset_redemption;
+pub mod compressed_nft;
+pub mod core_asset;
+pub mod fractions;
+pub mod insurance;
+pub mod loan_settlement;
 pub mod loans;
+pub mod marketplace;
+pub mod nft_burn;
+pub mod nft_lock;
+pub mod nft_transfer;
+pub mod primary_sales;
+pub mod rental_usage;
+pub mod rentals;
+pub mod royalty;
+pub mod sale_payment;
@@ -0,0 +1,117 @@
+use {
+    crate::errors,
+    anchor_lang::{prelude::*, solana_program::sysvar},
+    mpl_token_metadata::{
+        accounts::Metadata,
+        instructions::{BurnV1Cpi, BurnV1CpiAccounts, BurnV1InstructionArgs},
+    },
+};
+
+/**
+ * Token Metadata accounts needed to burn an NFT.
+ * An NFT with a metadata account is burned through Token Metadata `BurnV1`, which also closes its
+ * metadata, master edition and token account, plus the token record of a programmable NFT. An NFT
+ * without one, such as a bare Token-2022 NFT, is burned with its token program alone.
+ */
+#[derive(Accounts)]
+pub struct MetadataBurn<'info> {
+    /// CHECK: Checked to be the NFT's metadata PDA, which only exists for Token Metadata NFTs
+    #[account(mut)]
+    pub metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Master edition of the NFT, validated by Token Metadata
+    #[account(mut)]
+    pub master_edition: UncheckedAccount<'info>,
+
+    /// CHECK: Token record of the burned token account, required for programmable NFTs; validated by Token Metadata
+    #[account(mut)]
+    pub token_record: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Metadata of the NFT's verified collection, required when it has one; validated by Token Metadata
+    #[account(mut)]
+    pub collection_metadata: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Metaplex program ID check
+    #[account(address = mpl_token_metadata::ID)]
+    pub token_metadata_program: UncheckedAccount<'info>,
+
+    /// CHECK: Instructions sysvar required by Token Metadata
+    #[account(address = sysvar::instructions::ID)]
+    pub sysvar_instructions: UncheckedAccount<'info>,
+}
+
+/**
+ * The token account holding the NFT being burned and the programs taking part.
+ * The owner of the token account is the burn authority and receives the rent of every closed account.
+ */
+pub struct NftBurn<'a, 'info> {
+    pub nft_mint: &'a AccountInfo<'info>,
+    pub token_account: &'a AccountInfo<'info>,
+    pub owner: &'a AccountInfo<'info>,
+    pub token_program: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+}
+
+impl<'info> MetadataBurn<'info> {
+    /**
+     * Burns the NFT and closes its token account, along with its Token Metadata accounts if it has
+     * any. The owner signs through `signer_seeds` when it is a PDA.
+     */
+    pub fn burn(&self, nft: NftBurn<'_, 'info>, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        require_keys_eq!(
+            self.metadata.key(),
+            Metadata::find_pda(&nft.nft_mint.key()).0,
+            errors::ErrorCode::InvalidNFTAccount
+        );
+
+        if self.metadata.data_is_empty() {
+            anchor_spl::token_interface::burn(
+                CpiContext::new_with_signer(
+                    nft.token_program.clone(),
+                    anchor_spl::token_interface::Burn {
+                        mint: nft.nft_mint.clone(),
+                        from: nft.token_account.clone(),
+                        authority: nft.owner.clone(),
+                    },
+                    signer_seeds,
+                ),
+                1,
+            )?;
+            return anchor_spl::token_interface::close_account(CpiContext::new_with_signer(
+                nft.token_program.clone(),
+                anchor_spl::token_interface::CloseAccount {
+                    account: nft.token_account.clone(),
+                    destination: nft.owner.clone(),
+                    authority: nft.owner.clone(),
+                },
+                signer_seeds,
+            ));
+        }
+
+        BurnV1Cpi::new(
+            &self.token_metadata_program,
+            BurnV1CpiAccounts {
+                authority: nft.owner,
+                collection_metadata: self
+                    .collection_metadata
+                    .as_ref()
+                    .map(|account| account.as_ref()),
+                metadata: &self.metadata,
+                edition: Some(&self.master_edition),
+                mint: nft.nft_mint,
+                token: nft.token_account,
+                master_edition: None,
+                master_edition_mint: None,
+                master_edition_token: None,
+                edition_marker: None,
+                token_record: self.token_record.as_ref().map(|account| account.as_ref()),
+                system_program: nft.system_program,
+                sysvar_instructions: &self.sysvar_instructions,
+                spl_token_program: nft.token_program,
+            },
+            BurnV1InstructionArgs { amount: 1 },
+        )
+        .invoke_signed(signer_seeds)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,57 @@
+use {
+    anchor_lang::prelude::*,
+    mpl_token_metadata::instructions::{
+        FreezeDelegatedAccountCpi, FreezeDelegatedAccountCpiAccounts, ThawDelegatedAccountCpi,
+        ThawDelegatedAccountCpiAccounts,
+    },
+};
+
+/**
+ * The accounts taking part in freezing an NFT in its owner's token account, through Token
+ * Metadata, by the token account's delegate.
+ * Token Metadata holds the mint's freeze authority through the master edition and lends it to the
+ * delegate, so this only works for classic SPL Token NFTs with a master edition. Token Metadata takes
+ * the delegate as writable.
+ */
+pub struct DelegatedFreeze<'a, 'info> {
+    pub delegate: &'a AccountInfo<'info>,
+    pub token_account: &'a AccountInfo<'info>,
+    pub edition: &'a AccountInfo<'info>,
+    pub nft_mint: &'a AccountInfo<'info>,
+    pub token_program: &'a AccountInfo<'info>,
+    pub token_metadata_program: &'a AccountInfo<'info>,
+}
+
+impl DelegatedFreeze<'_, '_> {
+    /// Freezes the token account; the delegate signs through `signer_seeds`.
+    pub fn freeze(&self, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        FreezeDelegatedAccountCpi::new(
+            self.token_metadata_program,
+            FreezeDelegatedAccountCpiAccounts {
+                delegate: self.delegate,
+                token_account: self.token_account,
+                edition: self.edition,
+                mint: self.nft_mint,
+                token_program: self.token_program,
+            },
+        )
+        .invoke_signed(signer_seeds)?;
+        Ok(())
+    }
+
+    /// Thaws the token account; the delegate signs through `signer_seeds`.
+    pub fn thaw(&self, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        ThawDelegatedAccountCpi::new(
+            self.token_metadata_program,
+            ThawDelegatedAccountCpiAccounts {
+                delegate: self.delegate,
+                token_account: self.token_account,
+                edition: self.edition,
+                mint: self.nft_mint,
+                token_program: self.token_program,
+            },
+        )
+        .invoke_signed(signer_seeds)?;
+        Ok(())
+    }
+}
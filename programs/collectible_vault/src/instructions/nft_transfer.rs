@@ -0,0 +1,244 @@
+use {
+    crate::errors,
+    anchor_lang::{prelude::*, solana_program::sysvar},
+    anchor_spl::{
+        token_2022::spl_token_2022::{
+            self,
+            extension::{
+                non_transferable::NonTransferable, BaseStateWithExtensions, StateWithExtensions,
+            },
+            onchain::invoke_transfer_checked,
+        },
+        token_interface::{self, TokenAccount},
+    },
+    mpl_token_metadata::{
+        accounts::Metadata,
+        instructions::{TransferV1Cpi, TransferV1CpiAccounts, TransferV1InstructionArgs},
+        types::TokenStandard,
+    },
+};
+
+/**
+ * The token standard of an NFT minted by the vault.
+ * Programmable NFTs enforce royalties through an optional Token Auth Rules rule set.
+ */
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum NftStandard {
+    /// A classic Metaplex NFT moved with plain SPL token transfers.
+    #[default]
+    NonFungible,
+    /// A Metaplex programmable NFT moved with Token Metadata `TransferV1`.
+    ProgrammableNonFungible { rule_set: Option<Pubkey> },
+}
+
+/**
+ * Token Metadata accounts needed to move a programmable NFT.
+ * Every account is optional; they are only required when the NFT's metadata marks it programmable.
+ */
+#[derive(Accounts)]
+pub struct ProgrammableTransfer<'info> {
+    /// CHECK: Ownership and mint are validated before use; Token Metadata validates the rest
+    #[account(mut)]
+    pub metadata: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Validated by Token Metadata
+    pub master_edition: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Token record of the source token account, validated by Token Metadata
+    #[account(mut)]
+    pub source_token_record: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Token record of the destination token account, created by Token Metadata if missing
+    #[account(mut)]
+    pub destination_token_record: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Rule set of the NFT, validated by Token Metadata
+    pub authorization_rules: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Validated by Token Metadata
+    pub authorization_rules_program: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Metaplex program ID check
+    #[account(address = mpl_token_metadata::ID)]
+    pub token_metadata_program: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Instructions sysvar required by Token Metadata
+    #[account(address = sysvar::instructions::ID)]
+    pub sysvar_instructions: Option<UncheckedAccount<'info>>,
+}
+
+/**
+ * The token accounts and programs taking part in an NFT transfer.
+ * The owner of the source token account is always the transfer authority. `additional_accounts`
+ * carries the extra accounts required by a Token-2022 transfer hook, if the mint has one.
+ */
+pub struct NftTransfer<'a, 'info> {
+    pub nft_mint: &'a AccountInfo<'info>,
+    pub source: &'a AccountInfo<'info>,
+    pub source_owner: &'a AccountInfo<'info>,
+    pub destination: &'a AccountInfo<'info>,
+    pub destination_owner: &'a AccountInfo<'info>,
+    pub payer: &'a AccountInfo<'info>,
+    pub token_program: &'a AccountInfo<'info>,
+    pub associated_token_program: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+    pub additional_accounts: &'a [AccountInfo<'info>],
+}
+
+impl<'info> ProgrammableTransfer<'info> {
+    /// Returns true if the provided metadata marks the NFT as a programmable NFT.
+    pub fn is_programmable(&self, nft_mint: &Pubkey) -> Result<bool> {
+        let Some(metadata) = &self.metadata else {
+            return Ok(false);
+        };
+        require_keys_eq!(
+            *metadata.owner,
+            mpl_token_metadata::ID,
+            ErrorCode::ConstraintOwner
+        );
+        let metadata = Metadata::from_bytes(&metadata.try_borrow_data()?)
+            .map_err(|_| error!(ErrorCode::AccountDidNotDeserialize))?;
+        require_keys_eq!(
+            metadata.mint,
+            *nft_mint,
+            errors::ErrorCode::InvalidNFTAccount
+        );
+        Ok(matches!(
+            metadata.token_standard,
+            Some(TokenStandard::ProgrammableNonFungible)
+                | Some(TokenStandard::ProgrammableNonFungibleEdition)
+        ))
+    }
+
+    /**
+     * Moves a single NFT, through Token Metadata `TransferV1` for programmable NFTs and with a
+     * checked SPL Token or Token-2022 transfer otherwise, resolving any transfer hook accounts.
+     * Returns true if the NFT was programmable, to be passed on to `close_emptied_nft_account`.
+     */
+    pub fn transfer(
+        &self,
+        accounts: NftTransfer<'_, 'info>,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<bool> {
+        if !self.is_programmable(accounts.nft_mint.key)? {
+            require!(
+                !is_non_transferable(accounts.nft_mint)?,
+                errors::ErrorCode::NonTransferableNft
+            );
+            let decimals = {
+                let mint_data = accounts.nft_mint.try_borrow_data()?;
+                StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?
+                    .base
+                    .decimals
+            };
+            invoke_transfer_checked(
+                accounts.token_program.key,
+                accounts.source.clone(),
+                accounts.nft_mint.clone(),
+                accounts.destination.clone(),
+                accounts.source_owner.clone(),
+                accounts.additional_accounts,
+                1,
+                decimals,
+                signer_seeds,
+            )?;
+            return Ok(false);
+        }
+
+        let (
+            Some(metadata),
+            Some(master_edition),
+            Some(source_token_record),
+            Some(destination_token_record),
+            Some(token_metadata_program),
+            Some(sysvar_instructions),
+        ) = (
+            &self.metadata,
+            &self.master_edition,
+            &self.source_token_record,
+            &self.destination_token_record,
+            &self.token_metadata_program,
+            &self.sysvar_instructions,
+        )
+        else {
+            return err!(errors::ErrorCode::MissingProgrammableAccounts);
+        };
+
+        TransferV1Cpi::new(
+            token_metadata_program,
+            TransferV1CpiAccounts {
+                token: accounts.source,
+                token_owner: accounts.source_owner,
+                destination_token: accounts.destination,
+                destination_owner: accounts.destination_owner,
+                mint: accounts.nft_mint,
+                metadata,
+                edition: Some(master_edition),
+                token_record: Some(source_token_record),
+                destination_token_record: Some(destination_token_record),
+                authority: accounts.source_owner,
+                payer: accounts.payer,
+                system_program: accounts.system_program,
+                sysvar_instructions,
+                spl_token_program: accounts.token_program,
+                spl_ata_program: accounts.associated_token_program,
+                authorization_rules_program: self
+                    .authorization_rules_program
+                    .as_ref()
+                    .map(|account| account.as_ref()),
+                authorization_rules: self
+                    .authorization_rules
+                    .as_ref()
+                    .map(|account| account.as_ref()),
+            },
+            TransferV1InstructionArgs {
+                amount: 1,
+                authorization_data: None,
+            },
+        )
+        .invoke_signed(signer_seeds)?;
+        Ok(true)
+    }
+}
+
+/**
+ * Returns true if the SPL Token or Token-2022 mint has the non-transferable extension. Such NFTs
+ * never leave their owner's wallet, so they are redeemed through a burn delegate instead.
+ */
+pub fn is_non_transferable(nft_mint: &AccountInfo) -> Result<bool> {
+    let mint_data = nft_mint.try_borrow_data()?;
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+    Ok(mint.get_extension::<NonTransferable>().is_ok())
+}
+
+/**
+ * Closes a program-owned token account after its NFT has left, refunding its rent to `destination`.
+ * A programmable NFT's emptied token account stays frozen by its master edition, so it is left open
+ * and reused if the same NFT comes back. Tokens sent to the account on top of the NFT also keep it
+ * open, since closing an account with a balance fails and would block the exit for good.
+ */
+pub fn close_emptied_nft_account<'info>(
+    account: &mut InterfaceAccount<'info, TokenAccount>,
+    programmable: bool,
+    destination: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    if programmable {
+        return Ok(());
+    }
+    account.reload()?;
+    if account.amount != 0 {
+        return Ok(());
+    }
+    token_interface::close_account(CpiContext::new_with_signer(
+        token_program.clone(),
+        token_interface::CloseAccount {
+            account: account.to_account_info(),
+            destination: destination.clone(),
+            authority: authority.clone(),
+        },
+        signer_seeds,
+    ))
+}
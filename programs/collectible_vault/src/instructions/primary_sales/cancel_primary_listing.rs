@@ -0,0 +1,112 @@
+use {
+    crate::{
+        constants::{
+            admin_constants::get_admin_account_pubkey,
+            pda_constants::{PRIMARY_LISTING_SEED, SALE_ESCROW_SEED},
+        },
+        errors,
+        events::PrimaryListingCancelled,
+        instructions::nft_transfer::*,
+        state::PrimaryListing,
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::{
+        associated_token::AssociatedToken,
+        token_interface::{Mint, TokenAccount, TokenInterface},
+    },
+};
+
+/**
+ * Allows the admin to withdraw an NFT from its primary sale.
+ * This instruction closes the listing and moves the NFT from the sale escrow to the admin's wallet.
+ */
+#[derive(Accounts)]
+pub struct CancelPrimaryListing<'info> {
+    #[account(
+        mut,
+        seeds = [PRIMARY_LISTING_SEED, nft_mint.key().as_ref()],
+        bump,
+        close = payer
+    )]
+    pub primary_listing: Account<'info, PrimaryListing>,
+
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+
+    // Sale escrow's token account holding the NFT
+    #[account(
+        mut,
+        associated_token::mint = nft_mint,
+        associated_token::authority = sale_escrow,
+        associated_token::token_program = token_program,
+    )]
+    pub escrow_nft_account: InterfaceAccount<'info, TokenAccount>,
+
+    // Admin's token account receiving the NFT
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = nft_mint,
+        associated_token::authority = payer,
+        associated_token::token_program = token_program,
+    )]
+    pub admin_nft_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: PDA for the sale escrow
+    #[account(
+        seeds = [SALE_ESCROW_SEED],
+        bump
+    )]
+    pub sale_escrow: UncheckedAccount<'info>,
+
+    #[account(mut, constraint = payer.key() == get_admin_account_pubkey() @ errors::ErrorCode::UnauthorizedTransactionSigner)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+
+    // Token Metadata accounts, required only when the NFT is a programmable NFT
+    pub programmable: ProgrammableTransfer<'info>,
+}
+
+pub fn handle<'info>(ctx: Context<'_, '_, '_, 'info, CancelPrimaryListing<'info>>) -> Result<()> {
+    let escrow_bump = ctx.bumps.sale_escrow;
+    let seeds = &[SALE_ESCROW_SEED, &[escrow_bump]];
+    let signer = &[&seeds[..]];
+
+    // Return the NFT to the admin
+    let programmable = ctx.accounts.programmable.transfer(
+        NftTransfer {
+            nft_mint: &ctx.accounts.nft_mint.to_account_info(),
+            source: &ctx.accounts.escrow_nft_account.to_account_info(),
+            source_owner: &ctx.accounts.sale_escrow.to_account_info(),
+            destination: &ctx.accounts.admin_nft_account.to_account_info(),
+            destination_owner: &ctx.accounts.payer.to_account_info(),
+            payer: &ctx.accounts.payer.to_account_info(),
+            token_program: &ctx.accounts.token_program.to_account_info(),
+            associated_token_program: &ctx.accounts.associated_token_program.to_account_info(),
+            system_program: &ctx.accounts.system_program.to_account_info(),
+            additional_accounts: ctx.remaining_accounts,
+        },
+        signer,
+    )?;
+
+    // Close the escrow's token account, which the admin paid for when minting
+    close_emptied_nft_account(
+        &mut ctx.accounts.escrow_nft_account,
+        programmable,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.sale_escrow.to_account_info(),
+        &ctx.accounts.token_program.to_account_info(),
+        signer,
+    )?;
+
+    emit!(PrimaryListingCancelled {
+        primary_listing: ctx.accounts.primary_listing.key(),
+        nft_mint: ctx.accounts.nft_mint.key(),
+        admin: ctx.accounts.payer.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use {
+    crate::{
+        constants::{
+            admin_constants::get_admin_account_pubkey,
+            pda_constants::{PRIMARY_LISTING_SEED, SALE_ESCROW_SEED},
+        },
+        errors,
+        events::PrimaryListingCreated,
+        state::{AccountHeader, PrimaryListing, SaleCurrency},
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+/**
+ * Allows the admin to list an NFT held by the sale escrow for its primary sale.
+ * The admin mints the NFT with `mint_nft` using the sale escrow as its owner, usually in the same
+ * transaction, and this instruction records its price.
+ */
+#[derive(Accounts)]
+pub struct CreatePrimaryListing<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = PrimaryListing::INIT_SPACE,
+        seeds = [PRIMARY_LISTING_SEED, nft_mint.key().as_ref()],
+        bump
+    )]
+    pub primary_listing: Account<'info, PrimaryListing>,
+
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+
+    // Sale escrow's token account, which must already hold the NFT
+    #[account(
+        associated_token::mint = nft_mint,
+        associated_token::authority = sale_escrow,
+        associated_token::token_program = token_program,
+        constraint = escrow_nft_account.amount == 1 @ errors::ErrorCode::InvalidNFTAccount
+    )]
+    pub escrow_nft_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: PDA for the sale escrow
+    #[account(
+        seeds = [SALE_ESCROW_SEED],
+        bump
+    )]
+    pub sale_escrow: UncheckedAccount<'info>,
+
+    #[account(mut, constraint = payer.key() == get_admin_account_pubkey() @ errors::ErrorCode::UnauthorizedTransactionSigner)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle(
+    ctx: Context<CreatePrimaryListing>,
+    price: u64,
+    currency: SaleCurrency,
+) -> Result<()> {
+    require!(price > 0, errors::ErrorCode::InvalidSalePrice);
+
+    let listed_at = Clock::get()?.unix_timestamp;
+    let primary_listing = &mut ctx.accounts.primary_listing;
+    primary_listing.header = AccountHeader::new(PrimaryListing::VERSION);
+    primary_listing.nft_mint = ctx.accounts.nft_mint.key();
+    primary_listing.currency = currency;
+    primary_listing.price = price;
+    primary_listing.listed_at = listed_at;
+
+    emit!(PrimaryListingCreated {
+        primary_listing: primary_listing.key(),
+        nft_mint: ctx.accounts.nft_mint.key(),
+        currency,
+        price,
+        admin: ctx.accounts.payer.key(),
+        timestamp: listed_at,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,3 @@
+pub mod cancel_primary_listing;
+pub mod create_primary_listing;
+pub mod purchase_listed_item;
@@ -0,0 +1,191 @@
+use {
+    crate::{
+        constants::{
+            admin_constants::get_admin_account_pubkey,
+            pda_constants::{
+                ASSET_IMPAIRMENT_SEED, INSURANCE_RESERVE_SEED, PRIMARY_LISTING_SEED,
+                SALE_ESCROW_SEED, VAULT_CONFIG_SEED,
+            },
+        },
+        errors,
+        events::ListedItemPurchased,
+        instructions::{nft_transfer::*, sale_payment::*},
+        state::{PrimaryListing, SaleCurrency, VaultConfig},
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::{
+        associated_token::AssociatedToken,
+        token_interface::{Mint, TokenAccount, TokenInterface},
+    },
+};
+
+/**
+ * Allows anyone to buy an NFT listed for its primary sale.
+ * This instruction pays the listing price in SOL or USDC to the treasury and moves the NFT from the
+ * sale escrow to the buyer in the same transaction. The insurance reserve's share of a SOL price is
+ * paid into the reserve instead of the treasury.
+ * The listing account and the emptied escrow token account are closed, refunding the admin who paid for them.
+ */
+#[derive(Accounts)]
+pub struct PurchaseListedItem<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [PRIMARY_LISTING_SEED, nft_mint.key().as_ref()],
+        bump,
+        close = admin
+    )]
+    pub primary_listing: Account<'info, PrimaryListing>,
+
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+
+    // Sale escrow's token account holding the NFT
+    #[account(
+        mut,
+        associated_token::mint = nft_mint,
+        associated_token::authority = sale_escrow,
+        associated_token::token_program = token_program,
+    )]
+    pub escrow_nft_account: InterfaceAccount<'info, TokenAccount>,
+
+    // Buyer's token account receiving the NFT
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::mint = nft_mint,
+        associated_token::authority = buyer,
+        associated_token::token_program = token_program,
+    )]
+    pub buyer_nft_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: PDA for the sale escrow
+    #[account(
+        seeds = [SALE_ESCROW_SEED],
+        bump
+    )]
+    pub sale_escrow: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [VAULT_CONFIG_SEED],
+        bump,
+        constraint = vault_config.treasury != Pubkey::default() @ errors::ErrorCode::TreasuryNotConfigured
+    )]
+    pub vault_config: Account<'info, VaultConfig>,
+
+    /// CHECK: Wallet receiving the sale proceeds, checked against the vault config
+    #[account(
+        mut,
+        constraint = treasury.key() == vault_config.treasury @ errors::ErrorCode::InvalidTreasury
+    )]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// CHECK: Insurance reserve PDA receiving its share of SOL proceeds; it exists whenever that share is set
+    #[account(
+        mut,
+        seeds = [INSURANCE_RESERVE_SEED],
+        bump
+    )]
+    pub insurance_reserve: UncheckedAccount<'info>,
+
+    /// CHECK: Admin wallet refunded the rent of the listing and escrow token accounts
+    #[account(mut, address = get_admin_account_pubkey())]
+    pub admin: UncheckedAccount<'info>,
+
+    /// CHECK: Impairment PDA of the NFT, which must not exist for the NFT to be sold
+    #[account(
+        seeds = [ASSET_IMPAIRMENT_SEED, nft_mint.key().as_ref()],
+        bump,
+        constraint = asset_impairment.data_is_empty() @ errors::ErrorCode::NftImpaired
+    )]
+    pub asset_impairment: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+
+    // Token Metadata accounts, required only when the NFT is a programmable NFT
+    pub programmable: ProgrammableTransfer<'info>,
+
+    // USDC accounts, required only when the listing is priced in USDC
+    pub usdc: UsdcPayment<'info>,
+}
+
+pub fn handle<'info>(ctx: Context<'_, '_, '_, 'info, PurchaseListedItem<'info>>) -> Result<()> {
+    let primary_listing = &ctx.accounts.primary_listing;
+
+    // The reserve holds lamports, so it only takes its share of SOL prices
+    let insurance_fee = match primary_listing.currency {
+        SaleCurrency::Sol => ctx
+            .accounts
+            .vault_config
+            .insurance_share(primary_listing.price),
+        SaleCurrency::Usdc => 0,
+    };
+
+    // Pay the listing price to the treasury, less the insurance reserve's share
+    ctx.accounts.usdc.pay(
+        primary_listing.currency,
+        primary_listing.price - insurance_fee,
+        &ctx.accounts.buyer.to_account_info(),
+        &ctx.accounts.treasury.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+    if insurance_fee > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.buyer.to_account_info(),
+                    to: ctx.accounts.insurance_reserve.to_account_info(),
+                },
+            ),
+            insurance_fee,
+        )?;
+    }
+
+    let escrow_bump = ctx.bumps.sale_escrow;
+    let seeds = &[SALE_ESCROW_SEED, &[escrow_bump]];
+    let signer = &[&seeds[..]];
+
+    // Transfer the NFT from the sale escrow to the buyer
+    let programmable = ctx.accounts.programmable.transfer(
+        NftTransfer {
+            nft_mint: &ctx.accounts.nft_mint.to_account_info(),
+            source: &ctx.accounts.escrow_nft_account.to_account_info(),
+            source_owner: &ctx.accounts.sale_escrow.to_account_info(),
+            destination: &ctx.accounts.buyer_nft_account.to_account_info(),
+            destination_owner: &ctx.accounts.buyer.to_account_info(),
+            payer: &ctx.accounts.buyer.to_account_info(),
+            token_program: &ctx.accounts.token_program.to_account_info(),
+            associated_token_program: &ctx.accounts.associated_token_program.to_account_info(),
+            system_program: &ctx.accounts.system_program.to_account_info(),
+            additional_accounts: ctx.remaining_accounts,
+        },
+        signer,
+    )?;
+
+    // Close the escrow's token account, which the admin paid for when minting
+    close_emptied_nft_account(
+        &mut ctx.accounts.escrow_nft_account,
+        programmable,
+        &ctx.accounts.admin.to_account_info(),
+        &ctx.accounts.sale_escrow.to_account_info(),
+        &ctx.accounts.token_program.to_account_info(),
+        signer,
+    )?;
+
+    emit!(ListedItemPurchased {
+        primary_listing: ctx.accounts.primary_listing.key(),
+        nft_mint: ctx.accounts.nft_mint.key(),
+        buyer: ctx.accounts.buyer.key(),
+        treasury: ctx.accounts.treasury.key(),
+        currency: ctx.accounts.primary_listing.currency,
+        price: ctx.accounts.primary_listing.price,
+        insurance_fee,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,219 @@
+use {
+    anchor_lang::{prelude::*, system_program},
+    anchor_spl::{
+        associated_token,
+        token_2022::spl_token_2022::{
+            extension::{ExtensionType, StateWithExtensions},
+            state::{Account as Token2022Account, Mint as Token2022Mint},
+        },
+        token_interface,
+    },
+};
+
+/**
+ * The token a renter holds in their wallet for the length of a rental term, so wallets and gating
+ * tools that only read token balances see who may use a rented NFT.
+ * Each rented NFT has a Token-2022 usage mint derived from its mint. The mint is non-transferable,
+ * and the rental is its mint authority, permanent delegate and close authority, so the single token
+ * cannot change hands and is burned when the rental ends even without the renter's signature.
+ */
+pub struct UsageToken<'a, 'info> {
+    pub usage_mint: &'a AccountInfo<'info>,
+    pub rental: &'a AccountInfo<'info>,
+    pub token_program: &'a AccountInfo<'info>,
+}
+
+impl<'info> UsageToken<'_, 'info> {
+    /**
+     * Creates the usage mint and mints its token into the renter's associated token account, which
+     * is created if needed. The renter pays for both accounts. The mint signs its creation through
+     * `usage_mint_seeds` and the rental mints through `rental_seeds`.
+     */
+    pub fn issue(
+        &self,
+        renter: &AccountInfo<'info>,
+        renter_usage_account: &AccountInfo<'info>,
+        associated_token_program: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+        usage_mint_seeds: &[&[u8]],
+        rental_seeds: &[&[u8]],
+    ) -> Result<()> {
+        let space = ExtensionType::try_calculate_account_len::<Token2022Mint>(&[
+            ExtensionType::NonTransferable,
+            ExtensionType::PermanentDelegate,
+            ExtensionType::MintCloseAuthority,
+        ])?;
+        self.create_mint_account(renter, system_program, space, usage_mint_seeds)?;
+
+        token_interface::non_transferable_mint_initialize(CpiContext::new(
+            self.token_program.clone(),
+            token_interface::NonTransferableMintInitialize {
+                token_program_id: self.token_program.clone(),
+                mint: self.usage_mint.clone(),
+            },
+        ))?;
+        token_interface::permanent_delegate_initialize(
+            CpiContext::new(
+                self.token_program.clone(),
+                token_interface::PermanentDelegateInitialize {
+                    token_program_id: self.token_program.clone(),
+                    mint: self.usage_mint.clone(),
+                },
+            ),
+            self.rental.key,
+        )?;
+        token_interface::mint_close_authority_initialize(
+            CpiContext::new(
+                self.token_program.clone(),
+                token_interface::MintCloseAuthorityInitialize {
+                    token_program_id: self.token_program.clone(),
+                    mint: self.usage_mint.clone(),
+                },
+            ),
+            Some(self.rental.key),
+        )?;
+        token_interface::initialize_mint2(
+            CpiContext::new(
+                self.token_program.clone(),
+                token_interface::InitializeMint2 {
+                    mint: self.usage_mint.clone(),
+                },
+            ),
+            0,
+            self.rental.key,
+            None,
+        )?;
+
+        associated_token::create_idempotent(CpiContext::new(
+            associated_token_program.clone(),
+            associated_token::Create {
+                payer: renter.clone(),
+                associated_token: renter_usage_account.clone(),
+                authority: renter.clone(),
+                mint: self.usage_mint.clone(),
+                system_program: system_program.clone(),
+                token_program: self.token_program.clone(),
+            },
+        ))?;
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                self.token_program.clone(),
+                token_interface::MintTo {
+                    mint: self.usage_mint.clone(),
+                    to: renter_usage_account.clone(),
+                    authority: self.rental.clone(),
+                },
+                &[rental_seeds],
+            ),
+            1,
+        )
+    }
+
+    /**
+     * Burns the renter's usage token as the mint's permanent delegate and closes the usage mint,
+     * refunding its rent to `rent_recipient`. The renter's emptied token account is theirs to close.
+     * A renter who already burned the token, or closed the emptied account, does not hold up the
+     * rental's end.
+     */
+    pub fn retire(
+        &self,
+        renter_usage_account: &AccountInfo<'info>,
+        rent_recipient: &AccountInfo<'info>,
+        rental_seeds: &[&[u8]],
+    ) -> Result<()> {
+        let held = !renter_usage_account.data_is_empty()
+            && StateWithExtensions::<Token2022Account>::unpack(
+                &renter_usage_account.try_borrow_data()?,
+            )?
+            .base
+            .amount
+                > 0;
+        if held {
+            token_interface::burn(
+                CpiContext::new_with_signer(
+                    self.token_program.clone(),
+                    token_interface::Burn {
+                        mint: self.usage_mint.clone(),
+                        from: renter_usage_account.clone(),
+                        authority: self.rental.clone(),
+                    },
+                    &[rental_seeds],
+                ),
+                1,
+            )?;
+        }
+
+        token_interface::close_account(CpiContext::new_with_signer(
+            self.token_program.clone(),
+            token_interface::CloseAccount {
+                account: self.usage_mint.clone(),
+                destination: rent_recipient.clone(),
+                authority: self.rental.clone(),
+            },
+            &[rental_seeds],
+        ))
+    }
+
+    /**
+     * Creates the usage mint account owned by the token program. Lamports already sent to the
+     * address are kept and topped up to the rent minimum, so pre-funding it cannot block a rental.
+     */
+    fn create_mint_account(
+        &self,
+        payer: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+        space: usize,
+        usage_mint_seeds: &[&[u8]],
+    ) -> Result<()> {
+        let rent = Rent::get()?.minimum_balance(space);
+        let current_lamports = self.usage_mint.lamports();
+        if current_lamports == 0 {
+            return system_program::create_account(
+                CpiContext::new_with_signer(
+                    system_program.clone(),
+                    system_program::CreateAccount {
+                        from: payer.clone(),
+                        to: self.usage_mint.clone(),
+                    },
+                    &[usage_mint_seeds],
+                ),
+                rent,
+                space as u64,
+                self.token_program.key,
+            );
+        }
+
+        if rent > current_lamports {
+            system_program::transfer(
+                CpiContext::new(
+                    system_program.clone(),
+                    system_program::Transfer {
+                        from: payer.clone(),
+                        to: self.usage_mint.clone(),
+                    },
+                ),
+                rent - current_lamports,
+            )?;
+        }
+        system_program::allocate(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                system_program::Allocate {
+                    account_to_allocate: self.usage_mint.clone(),
+                },
+                &[usage_mint_seeds],
+            ),
+            space as u64,
+        )?;
+        system_program::assign(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                system_program::Assign {
+                    account_to_assign: self.usage_mint.clone(),
+                },
+                &[usage_mint_seeds],
+            ),
+            self.token_program.key,
+        )
+    }
+}
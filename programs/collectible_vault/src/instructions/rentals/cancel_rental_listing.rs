@@ -0,0 +1,103 @@
+use {
+    crate::{
+        constants::pda_constants::RENTAL_SEED, errors, events::RentalEnded,
+        instructions::nft_transfer::*, state::Rental,
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::{
+        associated_token::AssociatedToken,
+        token_interface::{Mint, TokenAccount, TokenInterface},
+    },
+};
+
+/**
+ * Allows an owner to withdraw an NFT from rent before anyone has rented it.
+ * This instruction returns the NFT from rental escrow and closes the rental.
+ */
+#[derive(Accounts)]
+pub struct CancelRentalListing<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [RENTAL_SEED, nft_mint.key().as_ref()],
+        bump,
+        constraint = rental.owner == owner.key() @ errors::ErrorCode::InvalidRentalOwner,
+        constraint = rental.renter.is_none() @ errors::ErrorCode::NftAlreadyRented,
+        close = owner
+    )]
+    pub rental: Account<'info, Rental>,
+
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+
+    // Owner's token account receiving the NFT back
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = nft_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program,
+    )]
+    pub owner_nft_account: InterfaceAccount<'info, TokenAccount>,
+
+    // Rental's token account holding the NFT
+    #[account(
+        mut,
+        associated_token::mint = nft_mint,
+        associated_token::authority = rental,
+        associated_token::token_program = token_program,
+    )]
+    pub rental_nft_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+
+    // Token Metadata accounts, required only when the NFT is a programmable NFT
+    pub programmable: ProgrammableTransfer<'info>,
+}
+
+pub fn handle<'info>(ctx: Context<'_, '_, '_, 'info, CancelRentalListing<'info>>) -> Result<()> {
+    let nft_mint_key = ctx.accounts.nft_mint.key();
+    let rental_bump = ctx.bumps.rental;
+    let seeds = &[RENTAL_SEED, nft_mint_key.as_ref(), &[rental_bump]];
+    let signer = &[&seeds[..]];
+
+    // Return the NFT to the owner
+    let programmable = ctx.accounts.programmable.transfer(
+        NftTransfer {
+            nft_mint: &ctx.accounts.nft_mint.to_account_info(),
+            source: &ctx.accounts.rental_nft_account.to_account_info(),
+            source_owner: &ctx.accounts.rental.to_account_info(),
+            destination: &ctx.accounts.owner_nft_account.to_account_info(),
+            destination_owner: &ctx.accounts.owner.to_account_info(),
+            payer: &ctx.accounts.owner.to_account_info(),
+            token_program: &ctx.accounts.token_program.to_account_info(),
+            associated_token_program: &ctx.accounts.associated_token_program.to_account_info(),
+            system_program: &ctx.accounts.system_program.to_account_info(),
+            additional_accounts: ctx.remaining_accounts,
+        },
+        signer,
+    )?;
+
+    // Close the rental's token account, refunding the owner who paid for it
+    close_emptied_nft_account(
+        &mut ctx.accounts.rental_nft_account,
+        programmable,
+        &ctx.accounts.owner.to_account_info(),
+        &ctx.accounts.rental.to_account_info(),
+        &ctx.accounts.token_program.to_account_info(),
+        signer,
+    )?;
+
+    emit!(RentalEnded {
+        rental: ctx.accounts.rental.key(),
+        nft_mint: nft_mint_key,
+        owner: ctx.accounts.owner.key(),
+        renter: None,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
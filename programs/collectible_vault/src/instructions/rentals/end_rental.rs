@@ -0,0 +1,153 @@
+use {
+    crate::{
+        constants::pda_constants::{RENTAL_SEED, RENTAL_USAGE_MINT_SEED},
+        errors,
+        events::RentalEnded,
+        instructions::{nft_transfer::*, rental_usage::UsageToken},
+        state::Rental,
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::{
+        associated_token::{get_associated_token_address_with_program_id, AssociatedToken},
+        token_2022::Token2022,
+        token_interface::{Mint, TokenAccount, TokenInterface},
+    },
+};
+
+/**
+ * Allows anyone to return a rented NFT to its owner once the rental term has ended.
+ * This permissionless crank moves the NFT from rental escrow back to the owner's wallet and closes the
+ * rental, refunding its rent to the owner. It also burns the renter's usage token and closes the usage
+ * mint, refunding its rent to the renter. The caller only pays for the owner's token account if it
+ * no longer exists.
+ */
+#[derive(Accounts)]
+pub struct EndRental<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [RENTAL_SEED, nft_mint.key().as_ref()],
+        bump,
+        constraint = rental.renter.is_some() @ errors::ErrorCode::NftNotRented,
+        constraint = rental.has_ended(Clock::get()?.unix_timestamp) @ errors::ErrorCode::RentalNotEnded,
+        close = owner
+    )]
+    pub rental: Account<'info, Rental>,
+
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Owner of the NFT, who gets it back along with the rent of the rental accounts
+    #[account(
+        mut,
+        address = rental.owner
+    )]
+    pub owner: UncheckedAccount<'info>,
+
+    // Owner's token account receiving the NFT back
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = nft_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program,
+    )]
+    pub owner_nft_account: InterfaceAccount<'info, TokenAccount>,
+
+    // Rental's token account holding the NFT
+    #[account(
+        mut,
+        associated_token::mint = nft_mint,
+        associated_token::authority = rental,
+        associated_token::token_program = token_program,
+    )]
+    pub rental_nft_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Renter whose term ended, who paid for the usage mint and receives its rent back
+    #[account(
+        mut,
+        constraint = rental.renter == Some(renter.key()) @ errors::ErrorCode::InvalidRenter
+    )]
+    pub renter: UncheckedAccount<'info>,
+
+    /// CHECK: Token-2022 usage mint of the NFT, issued with the rental and closed here
+    #[account(
+        mut,
+        seeds = [RENTAL_USAGE_MINT_SEED, nft_mint.key().as_ref()],
+        bump,
+        owner = usage_token_program.key()
+    )]
+    pub usage_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Renter's associated token account for the usage token, which may already be closed
+    #[account(
+        mut,
+        address = get_associated_token_address_with_program_id(&renter.key(), &usage_mint.key(), &usage_token_program.key())
+    )]
+    pub renter_usage_account: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub usage_token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+
+    // Token Metadata accounts, required only when the NFT is a programmable NFT
+    pub programmable: ProgrammableTransfer<'info>,
+}
+
+pub fn handle<'info>(ctx: Context<'_, '_, '_, 'info, EndRental<'info>>) -> Result<()> {
+    let nft_mint_key = ctx.accounts.nft_mint.key();
+    let rental_bump = ctx.bumps.rental;
+    let seeds = &[RENTAL_SEED, nft_mint_key.as_ref(), &[rental_bump]];
+    let signer = &[&seeds[..]];
+
+    // Return the NFT to the owner
+    let programmable = ctx.accounts.programmable.transfer(
+        NftTransfer {
+            nft_mint: &ctx.accounts.nft_mint.to_account_info(),
+            source: &ctx.accounts.rental_nft_account.to_account_info(),
+            source_owner: &ctx.accounts.rental.to_account_info(),
+            destination: &ctx.accounts.owner_nft_account.to_account_info(),
+            destination_owner: &ctx.accounts.owner.to_account_info(),
+            payer: &ctx.accounts.payer.to_account_info(),
+            token_program: &ctx.accounts.token_program.to_account_info(),
+            associated_token_program: &ctx.accounts.associated_token_program.to_account_info(),
+            system_program: &ctx.accounts.system_program.to_account_info(),
+            additional_accounts: ctx.remaining_accounts,
+        },
+        signer,
+    )?;
+
+    // Close the rental's token account, refunding the owner who paid for it
+    close_emptied_nft_account(
+        &mut ctx.accounts.rental_nft_account,
+        programmable,
+        &ctx.accounts.owner.to_account_info(),
+        &ctx.accounts.rental.to_account_info(),
+        &ctx.accounts.token_program.to_account_info(),
+        signer,
+    )?;
+
+    // Take the usage token back from the renter
+    UsageToken {
+        usage_mint: &ctx.accounts.usage_mint.to_account_info(),
+        rental: &ctx.accounts.rental.to_account_info(),
+        token_program: &ctx.accounts.usage_token_program.to_account_info(),
+    }
+    .retire(
+        &ctx.accounts.renter_usage_account.to_account_info(),
+        &ctx.accounts.renter.to_account_info(),
+        seeds,
+    )?;
+
+    emit!(RentalEnded {
+        rental: ctx.accounts.rental.key(),
+        nft_mint: nft_mint_key,
+        owner: ctx.accounts.owner.key(),
+        renter: ctx.accounts.rental.renter,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,138 @@
+use {
+    crate::{
+        constants::pda_constants::{
+            ASSET_IMPAIRMENT_SEED, ASSET_REDEMPTION_INFO_SEED, LOAN_INFO_SEED, RENTAL_SEED,
+        },
+        errors,
+        events::NftListedForRent,
+        instructions::nft_transfer::*,
+        state::{AccountHeader, Rental},
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::{
+        associated_token::AssociatedToken,
+        token_interface::{Mint, TokenAccount, TokenInterface},
+    },
+};
+
+/**
+ * Allows an NFT holder to list it for rent.
+ * This instruction moves the NFT into rental escrow, where it stays the owner's while renters pay a daily
+ * fee for its usage rights. NFTs with an open loan, a redemption request or an impaired physical item
+ * cannot be listed for rent.
+ */
+#[derive(Accounts)]
+pub struct ListNftForRent<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    // PDA holding the NFT and its rental terms
+    #[account(
+        init,
+        payer = owner,
+        space = Rental::INIT_SPACE,
+        seeds = [RENTAL_SEED, nft_mint.key().as_ref()],
+        bump
+    )]
+    pub rental: Account<'info, Rental>,
+
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+
+    // Owner's token account holding the NFT
+    #[account(
+        mut,
+        associated_token::mint = nft_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program,
+    )]
+    pub owner_nft_account: InterfaceAccount<'info, TokenAccount>,
+
+    // Rental's token account that will hold the NFT
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = nft_mint,
+        associated_token::authority = rental,
+        associated_token::token_program = token_program,
+    )]
+    pub rental_nft_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Loan PDA of the NFT, which must not exist for the NFT to be listed for rent
+    #[account(
+        seeds = [LOAN_INFO_SEED, nft_mint.key().as_ref()],
+        bump,
+        constraint = loan_info.data_is_empty() @ errors::ErrorCode::NftHasOpenLoan
+    )]
+    pub loan_info: UncheckedAccount<'info>,
+
+    /// CHECK: Redemption PDA of the NFT, which must not exist for the NFT to be listed for rent
+    #[account(
+        seeds = [ASSET_REDEMPTION_INFO_SEED, nft_mint.key().as_ref()],
+        bump,
+        constraint = asset_redemption_info.data_is_empty() @ errors::ErrorCode::NftHasRedemptionRequest
+    )]
+    pub asset_redemption_info: UncheckedAccount<'info>,
+
+    /// CHECK: Impairment PDA of the NFT, which must not exist for the NFT to be listed for rent
+    #[account(
+        seeds = [ASSET_IMPAIRMENT_SEED, nft_mint.key().as_ref()],
+        bump,
+        constraint = asset_impairment.data_is_empty() @ errors::ErrorCode::NftImpaired
+    )]
+    pub asset_impairment: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+
+    // Token Metadata accounts, required only when the NFT is a programmable NFT
+    pub programmable: ProgrammableTransfer<'info>,
+}
+
+pub fn handle<'info>(
+    ctx: Context<'_, '_, '_, 'info, ListNftForRent<'info>>,
+    daily_fee: u64,
+    max_days: u16,
+) -> Result<()> {
+    require!(daily_fee > 0, errors::ErrorCode::InvalidRentalFee);
+    require!(max_days > 0, errors::ErrorCode::InvalidRentalDuration);
+
+    let listed_at = Clock::get()?.unix_timestamp;
+    let rental = &mut ctx.accounts.rental;
+    rental.header = AccountHeader::new(Rental::VERSION);
+    rental.nft_mint = ctx.accounts.nft_mint.key();
+    rental.owner = ctx.accounts.owner.key();
+    rental.daily_fee = daily_fee;
+    rental.max_days = max_days;
+    rental.renter = None;
+    rental.rental_end = None;
+    rental.listed_at = listed_at;
+
+    // Move the NFT into rental escrow
+    ctx.accounts.programmable.transfer(
+        NftTransfer {
+            nft_mint: &ctx.accounts.nft_mint.to_account_info(),
+            source: &ctx.accounts.owner_nft_account.to_account_info(),
+            source_owner: &ctx.accounts.owner.to_account_info(),
+            destination: &ctx.accounts.rental_nft_account.to_account_info(),
+            destination_owner: &ctx.accounts.rental.to_account_info(),
+            payer: &ctx.accounts.owner.to_account_info(),
+            token_program: &ctx.accounts.token_program.to_account_info(),
+            associated_token_program: &ctx.accounts.associated_token_program.to_account_info(),
+            system_program: &ctx.accounts.system_program.to_account_info(),
+            additional_accounts: ctx.remaining_accounts,
+        },
+        &[],
+    )?;
+
+    emit!(NftListedForRent {
+        rental: ctx.accounts.rental.key(),
+        nft_mint: ctx.accounts.nft_mint.key(),
+        owner: ctx.accounts.owner.key(),
+        daily_fee,
+        max_days,
+        timestamp: listed_at,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,4 @@
+pub mod cancel_rental_listing;
+pub mod end_rental;
+pub mod list_nft_for_rent;
+pub mod rent_nft;
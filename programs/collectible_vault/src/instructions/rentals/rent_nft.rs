@@ -0,0 +1,166 @@
+use {
+    crate::{
+        constants::pda_constants::{
+            ASSET_IMPAIRMENT_SEED, INSURANCE_RESERVE_SEED, RENTAL_SEED, RENTAL_USAGE_MINT_SEED,
+            VAULT_CONFIG_SEED,
+        },
+        errors,
+        events::NftRented,
+        instructions::rental_usage::UsageToken,
+        state::{Rental, VaultConfig},
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::{
+        associated_token::{get_associated_token_address_with_program_id, AssociatedToken},
+        token_2022::Token2022,
+    },
+};
+
+/**
+ * Allows anyone to rent an NFT listed for rent.
+ * The renter pays the daily fee for every day of the term straight to the owner, less the insurance
+ * reserve's share, and holds the NFT's usage rights until the term ends. The NFT itself stays in rental escrow,
+ * and the renter receives a non-transferable usage token standing for those rights in their wallet.
+ */
+#[derive(Accounts)]
+pub struct RentNft<'info> {
+    #[account(mut)]
+    pub renter: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [RENTAL_SEED, rental.nft_mint.as_ref()],
+        bump,
+        constraint = rental.renter.is_none() @ errors::ErrorCode::NftAlreadyRented
+    )]
+    pub rental: Account<'info, Rental>,
+
+    /// CHECK: Owner of the NFT, who receives the rental fee
+    #[account(
+        mut,
+        address = rental.owner
+    )]
+    pub owner: UncheckedAccount<'info>,
+
+    /// CHECK: Vault config PDA; no insurance fee is charged until the admin creates it
+    #[account(
+        seeds = [VAULT_CONFIG_SEED],
+        bump
+    )]
+    pub vault_config: UncheckedAccount<'info>,
+
+    /// CHECK: Insurance reserve PDA receiving its share of the fee; it exists whenever that share is set
+    #[account(
+        mut,
+        seeds = [INSURANCE_RESERVE_SEED],
+        bump
+    )]
+    pub insurance_reserve: UncheckedAccount<'info>,
+
+    /// CHECK: Impairment PDA of the NFT, which must not exist for the NFT to be rented
+    #[account(
+        seeds = [ASSET_IMPAIRMENT_SEED, rental.nft_mint.as_ref()],
+        bump,
+        constraint = asset_impairment.data_is_empty() @ errors::ErrorCode::NftImpaired
+    )]
+    pub asset_impairment: UncheckedAccount<'info>,
+
+    /// CHECK: Token-2022 usage mint of the NFT, created here with the rental as its authority
+    #[account(
+        mut,
+        seeds = [RENTAL_USAGE_MINT_SEED, rental.nft_mint.as_ref()],
+        bump
+    )]
+    pub usage_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Renter's associated token account for the usage token, created by the Associated Token program
+    #[account(
+        mut,
+        address = get_associated_token_address_with_program_id(&renter.key(), &usage_mint.key(), &usage_token_program.key())
+    )]
+    pub renter_usage_account: UncheckedAccount<'info>,
+
+    pub usage_token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle(ctx: Context<RentNft>, days: u16) -> Result<()> {
+    let rental = &ctx.accounts.rental;
+    require!(
+        days > 0 && days <= rental.max_days,
+        errors::ErrorCode::InvalidRentalDuration
+    );
+    let fee = rental
+        .daily_fee
+        .checked_mul(days as u64)
+        .ok_or(errors::ErrorCode::CalculationError)?;
+    let insurance_fee =
+        VaultConfig::load(&ctx.accounts.vault_config.to_account_info())?.insurance_share(fee);
+
+    anchor_lang::system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.renter.to_account_info(),
+                to: ctx.accounts.owner.to_account_info(),
+            },
+        ),
+        fee - insurance_fee,
+    )?;
+    if insurance_fee > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.renter.to_account_info(),
+                    to: ctx.accounts.insurance_reserve.to_account_info(),
+                },
+            ),
+            insurance_fee,
+        )?;
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    let rental_end = now + days as i64 * Rental::SECONDS_PER_DAY;
+    let rental = &mut ctx.accounts.rental;
+    rental.renter = Some(ctx.accounts.renter.key());
+    rental.rental_end = Some(rental_end);
+
+    // Hand the renter the usage token for the term
+    let nft_mint = rental.nft_mint;
+    let rental_seeds = &[RENTAL_SEED, nft_mint.as_ref(), &[ctx.bumps.rental]];
+    let usage_mint_seeds = &[
+        RENTAL_USAGE_MINT_SEED,
+        nft_mint.as_ref(),
+        &[ctx.bumps.usage_mint],
+    ];
+    UsageToken {
+        usage_mint: &ctx.accounts.usage_mint.to_account_info(),
+        rental: &ctx.accounts.rental.to_account_info(),
+        token_program: &ctx.accounts.usage_token_program.to_account_info(),
+    }
+    .issue(
+        &ctx.accounts.renter.to_account_info(),
+        &ctx.accounts.renter_usage_account.to_account_info(),
+        &ctx.accounts.associated_token_program.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        usage_mint_seeds,
+        rental_seeds,
+    )?;
+    let rental = &ctx.accounts.rental;
+
+    emit!(NftRented {
+        rental: rental.key(),
+        nft_mint: rental.nft_mint,
+        owner: rental.owner,
+        renter: ctx.accounts.renter.key(),
+        days,
+        fee,
+        rental_end,
+        insurance_fee,
+        timestamp: now,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,154 @@
+use {
+    crate::{errors, state::VaultConfig},
+    anchor_lang::prelude::*,
+    mpl_token_metadata::accounts::Metadata,
+};
+
+/**
+ * A royalty paid to an NFT's Metaplex creators.
+ */
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RoyaltyPayment {
+    pub seller_fee_basis_points: u16,
+    pub amount: u64,
+}
+
+/**
+ * The royalty owed to an NFT's Token Metadata creators on a given amount, split by creator share.
+ * The creators are passed as the first remaining accounts, in metadata order.
+ */
+pub struct CreatorRoyalty<'r, 'info> {
+    pub seller_fee_basis_points: u16,
+    pub shares: Vec<(&'r AccountInfo<'info>, u64)>,
+}
+
+impl<'r, 'info> CreatorRoyalty<'r, 'info> {
+    /**
+     * Reads the seller fee and creators from the NFT's Token Metadata account and splits the
+     * royalty on `basis_amount` between the creator accounts at the front of `remaining_accounts`.
+     * Returns no royalty if the metadata account does not exist, as for Token-2022 NFTs without
+     * Token Metadata, along with the remaining accounts following the creators.
+     */
+    pub fn load(
+        nft_metadata: &AccountInfo<'info>,
+        basis_amount: u64,
+        remaining_accounts: &'r [AccountInfo<'info>],
+    ) -> Result<(Option<Self>, &'r [AccountInfo<'info>])> {
+        if nft_metadata.owner != &mpl_token_metadata::ID || nft_metadata.data_is_empty() {
+            return Ok((None, remaining_accounts));
+        }
+        let metadata = Metadata::from_bytes(&nft_metadata.try_borrow_data()?)
+            .map_err(|_| error!(ErrorCode::AccountDidNotDeserialize))?;
+        let creators = metadata.creators.unwrap_or_default();
+        require!(
+            remaining_accounts.len() >= creators.len(),
+            errors::ErrorCode::InvalidRoyaltyCreator
+        );
+        let (creator_accounts, rest) = remaining_accounts.split_at(creators.len());
+
+        // Each creator's cut is rounded down, so the payer keeps any remainder
+        let amount = royalty_amount(basis_amount, metadata.seller_fee_basis_points)?;
+        let mut shares = Vec::with_capacity(creators.len());
+        for (creator, account) in creators.iter().zip(creator_accounts) {
+            require_keys_eq!(
+                creator.address,
+                account.key(),
+                errors::ErrorCode::InvalidRoyaltyCreator
+            );
+            shares.push((
+                account,
+                (amount as u128 * creator.share as u128 / 100) as u64,
+            ));
+        }
+
+        Ok((
+            Some(Self {
+                seller_fee_basis_points: metadata.seller_fee_basis_points,
+                shares,
+            }),
+            rest,
+        ))
+    }
+
+    /// Returns the total paid to the creators.
+    pub fn amount(&self) -> u64 {
+        self.shares.iter().map(|(_, share)| share).sum()
+    }
+
+    /// Pays every creator its share from a system-owned wallet with system transfers.
+    pub fn pay_from_wallet(
+        &self,
+        payer: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+    ) -> Result<RoyaltyPayment> {
+        for (creator, share) in self.shares.iter().filter(|(_, share)| *share > 0) {
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    system_program.clone(),
+                    anchor_lang::system_program::Transfer {
+                        from: payer.clone(),
+                        to: (*creator).clone(),
+                    },
+                ),
+                *share,
+            )?;
+        }
+        Ok(self.payment())
+    }
+
+    /// Pays every creator its share by debiting the lamports of an account owned by this program.
+    pub fn pay_from_program_account(&self, payer: &AccountInfo<'info>) -> Result<RoyaltyPayment> {
+        for (creator, share) in self.shares.iter().filter(|(_, share)| *share > 0) {
+            payer.sub_lamports(*share)?;
+            creator.add_lamports(*share)?;
+        }
+        Ok(self.payment())
+    }
+
+    fn payment(&self) -> RoyaltyPayment {
+        RoyaltyPayment {
+            seller_fee_basis_points: self.seller_fee_basis_points,
+            amount: self.amount(),
+        }
+    }
+}
+
+/**
+ * The accounts taking part in paying the royalty on defaulted collateral.
+ * The metadata creators are passed as the first remaining accounts, in metadata order.
+ */
+pub struct DefaultRoyalty<'a, 'info> {
+    pub vault_config: &'a AccountInfo<'info>,
+    pub nft_metadata: &'a AccountInfo<'info>,
+    pub lender: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+}
+
+impl<'info> DefaultRoyalty<'_, 'info> {
+    /**
+     * Charges the lender the NFT's seller fee on `basis_amount`, split between the metadata creators by
+     * share, when the vault config enables default royalties and the NFT has Token Metadata creators.
+     * Returns the payment, if any, and the remaining accounts following the creators.
+     */
+    pub fn pay<'r>(
+        &self,
+        basis_amount: u64,
+        remaining_accounts: &'r [AccountInfo<'info>],
+    ) -> Result<(Option<RoyaltyPayment>, &'r [AccountInfo<'info>])> {
+        if !VaultConfig::load(self.vault_config)?.default_royalties_enabled {
+            return Ok((None, remaining_accounts));
+        }
+        let (royalty, rest) =
+            CreatorRoyalty::load(self.nft_metadata, basis_amount, remaining_accounts)?;
+        let payment = royalty
+            .map(|royalty| royalty.pay_from_wallet(self.lender, self.system_program))
+            .transpose()?;
+        Ok((payment, rest))
+    }
+}
+
+/// Returns the royalty owed on `basis_amount` at `seller_fee_basis_points`, rounded down.
+pub fn royalty_amount(basis_amount: u64, seller_fee_basis_points: u16) -> Result<u64> {
+    u64::try_from(basis_amount as u128 * seller_fee_basis_points as u128 / 10_000)
+        .map_err(|_| error!(errors::ErrorCode::CalculationError))
+}
@@ -0,0 +1,89 @@
+use {
+    crate::{constants::payment_constants::get_usdc_mint_pubkey, errors, state::SaleCurrency},
+    anchor_lang::prelude::*,
+    anchor_spl::token::{Mint, Token, TokenAccount},
+};
+
+/**
+ * USDC accounts needed to pay a price denominated in USDC.
+ * Every account is optional; they are only required when the price is in USDC.
+ */
+#[derive(Accounts)]
+pub struct UsdcPayment<'info> {
+    #[account(address = get_usdc_mint_pubkey())]
+    pub usdc_mint: Option<Account<'info, Mint>>,
+
+    // USDC token account of the paying wallet
+    #[account(mut)]
+    pub payer_usdc_account: Option<Account<'info, TokenAccount>>,
+
+    // USDC token account of the receiving wallet
+    #[account(mut)]
+    pub recipient_usdc_account: Option<Account<'info, TokenAccount>>,
+
+    pub usdc_token_program: Option<Program<'info, Token>>,
+}
+
+impl<'info> UsdcPayment<'info> {
+    /**
+     * Pays `amount` from `payer` to `recipient`, in lamports with a system transfer or in USDC
+     * with a checked token transfer between the two wallets' USDC accounts.
+     */
+    pub fn pay(
+        &self,
+        currency: SaleCurrency,
+        amount: u64,
+        payer: &AccountInfo<'info>,
+        recipient: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+    ) -> Result<()> {
+        if currency == SaleCurrency::Sol {
+            return anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    system_program.clone(),
+                    anchor_lang::system_program::Transfer {
+                        from: payer.clone(),
+                        to: recipient.clone(),
+                    },
+                ),
+                amount,
+            );
+        }
+
+        let (
+            Some(usdc_mint),
+            Some(payer_usdc_account),
+            Some(recipient_usdc_account),
+            Some(usdc_token_program),
+        ) = (
+            &self.usdc_mint,
+            &self.payer_usdc_account,
+            &self.recipient_usdc_account,
+            &self.usdc_token_program,
+        )
+        else {
+            return err!(errors::ErrorCode::MissingUsdcPaymentAccounts);
+        };
+        require!(
+            payer_usdc_account.owner == payer.key()
+                && payer_usdc_account.mint == usdc_mint.key()
+                && recipient_usdc_account.owner == recipient.key()
+                && recipient_usdc_account.mint == usdc_mint.key(),
+            errors::ErrorCode::InvalidPaymentAccount
+        );
+
+        anchor_spl::token::transfer_checked(
+            CpiContext::new(
+                usdc_token_program.to_account_info(),
+                anchor_spl::token::TransferChecked {
+                    from: payer_usdc_account.to_account_info(),
+                    mint: usdc_mint.to_account_info(),
+                    to: recipient_usdc_account.to_account_info(),
+                    authority: payer.clone(),
+                },
+            ),
+            amount,
+            usdc_mint.decimals,
+        )
+    }
+}
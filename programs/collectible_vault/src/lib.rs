@@ -1,17 +1,62 @@
 pub mod constants;
 pub mod errors;
+pub mod events;
 pub mod instructions;
 pub mod state;
 
 use {
     anchor_lang::prelude::*, instructions::admin_operations::create_collection::*,
+    instructions::admin_operations::migrate_account::*,
+    instructions::admin_operations::mint_compressed_nft::*,
+    instructions::admin_operations::mint_core_asset::*,
     instructions::admin_operations::mint_nft::*,
+    instructions::admin_operations::set_default_royalties::*,
+    instructions::admin_operations::set_insurance_fee::*,
+    instructions::admin_operations::set_treasury::*,
     instructions::asset_redemption::cancel_asset_redemption_request::*,
+    instructions::asset_redemption::cancel_compressed_asset_redemption_request::*,
+    instructions::asset_redemption::cancel_core_asset_redemption_request::*,
+    instructions::asset_redemption::cancel_non_transferable_asset_redemption_request::*,
     instructions::asset_redemption::create_asset_redemption_request::*,
+    instructions::asset_redemption::create_compressed_asset_redemption_request::*,
+    instructions::asset_redemption::create_core_asset_redemption_request::*,
+    instructions::asset_redemption::create_non_transferable_asset_redemption_request::*,
     instructions::asset_redemption::fulfill_asset_redemption_request::*,
-    instructions::loans::cancel_loan_request::*, instructions::loans::claim_delinquent_nft::*,
-    instructions::loans::provide_loan_liquidity::*, instructions::loans::repay_loan::*,
+    instructions::asset_redemption::fulfill_compressed_asset_redemption_request::*,
+    instructions::asset_redemption::fulfill_core_asset_redemption_request::*,
+    instructions::asset_redemption::fulfill_non_transferable_asset_redemption_request::*,
+    instructions::compressed_nft::*, instructions::fractions::claim_buyout_nft::*,
+    instructions::fractions::claim_buyout_proceeds::*,
+    instructions::fractions::fractionalize_nft::*, instructions::fractions::place_buyout_bid::*,
+    instructions::fractions::redeem_buyout_nft::*,
+    instructions::fractions::redeem_fractionalized_nft::*,
+    instructions::insurance::claim_fractionalized_insurance_payout::*,
+    instructions::insurance::claim_insurance_payout::*,
+    instructions::insurance::declare_asset_impaired::*,
+    instructions::insurance::fund_insurance_reserve::*,
+    instructions::insurance::record_appraisal::*, instructions::loans::cancel_core_loan_request::*,
+    instructions::loans::cancel_loan_request::*,
+    instructions::loans::cancel_locked_loan_request::*,
+    instructions::loans::claim_delinquent_core_asset::*,
+    instructions::loans::claim_delinquent_locked_nft::*,
+    instructions::loans::claim_delinquent_nft::*,
+    instructions::loans::close_expired_core_loan_request::*,
+    instructions::loans::close_expired_loan_request::*,
+    instructions::loans::close_expired_locked_loan_request::*,
+    instructions::loans::lock_nft_for_loan::*, instructions::loans::provide_loan_liquidity::*,
+    instructions::loans::repay_core_loan::*, instructions::loans::repay_loan::*,
+    instructions::loans::repay_locked_loan::*, instructions::loans::stake_core_asset_for_loan::*,
     instructions::loans::stake_nft_for_loan::*,
+    instructions::loans::sweep_baseline_vault_account::*,
+    instructions::marketplace::accept_offer::*, instructions::marketplace::buy_listing::*,
+    instructions::marketplace::buy_listing_with_loan::*,
+    instructions::marketplace::cancel_listing::*, instructions::marketplace::cancel_offer::*,
+    instructions::marketplace::list_nft::*, instructions::marketplace::make_offer::*,
+    instructions::nft_transfer::*, instructions::primary_sales::cancel_primary_listing::*,
+    instructions::primary_sales::create_primary_listing::*,
+    instructions::primary_sales::purchase_listed_item::*,
+    instructions::rentals::cancel_rental_listing::*, instructions::rentals::end_rental::*,
+    instructions::rentals::list_nft_for_rent::*, instructions::rentals::rent_nft::*,
 };
 
 declare_id!("8G4KcKxZZmXikmw5i3irw9ogKWS2E61vMgwovcJ4tdbr");
@@ -23,9 +68,16 @@ pub mod collectible_vault {
     /**
      * Mints a new NFT with the provided product detail URI.
      * This instruction is used to create new NFTs in the vault.
+     * Parameters:
+     * - product_detail_uri: The URI of the NFT's product details.
+     * - standard: Whether to mint a classic NFT or a programmable NFT with an optional rule set.
      */
-    pub fn mint_nft(ctx: Context<MintNFT>, product_detail_uri: String) -> Result<()> {
-        instructions::admin_operations::mint_nft::handle(ctx, product_detail_uri)
+    pub fn mint_nft(
+        ctx: Context<MintNFT>,
+        product_detail_uri: String,
+        standard: NftStandard,
+    ) -> Result<()> {
+        instructions::admin_operations::mint_nft::handle(ctx, product_detail_uri, standard)
     }
 
     /**
@@ -36,12 +88,47 @@ pub mod collectible_vault {
         instructions::admin_operations::create_collection::handle(ctx)
     }
 
+    /**
+     * Allows the admin to upgrade any versioned state account to its current layout.
+     * The account is reallocated in place and the admin covers the additional rent.
+     */
+    pub fn migrate_account(ctx: Context<MigrateAccount>) -> Result<()> {
+        instructions::admin_operations::migrate_account::handle(ctx)
+    }
+
+    /**
+     * Allows the admin to turn royalty payments on defaulted collateral claims on or off.
+     * When enabled, lenders claiming an NFT with Token Metadata creators pay its seller fee on the loan principal.
+     */
+    pub fn set_default_royalties(ctx: Context<SetDefaultRoyalties>, enabled: bool) -> Result<()> {
+        instructions::admin_operations::set_default_royalties::handle(ctx, enabled)
+    }
+
+    /**
+     * Allows the admin to set the wallet receiving primary sale proceeds.
+     * Parameters:
+     * - treasury: The wallet receiving SOL proceeds and owning the USDC account receiving USDC proceeds.
+     */
+    pub fn set_treasury(ctx: Context<SetTreasury>, treasury: Pubkey) -> Result<()> {
+        instructions::admin_operations::set_treasury::handle(ctx, treasury)
+    }
+
+    /**
+     * Allows the admin to set the insurance reserve's share of SOL primary sale proceeds and rental fees.
+     * The first call also creates the insurance reserve.
+     * Parameters:
+     * - insurance_fee_bps: The share in basis points, at most VaultConfig::MAX_INSURANCE_FEE_BPS.
+     */
+    pub fn set_insurance_fee(ctx: Context<SetInsuranceFee>, insurance_fee_bps: u16) -> Result<()> {
+        instructions::admin_operations::set_insurance_fee::handle(ctx, insurance_fee_bps)
+    }
+
     /**
      * Creates a new asset redemption request for a digital collectible.
      * This instruction allows a user to create a redemption request for their digital collectible.
      */
-    pub fn create_asset_redemption_request(
-        ctx: Context<CreateAssetRedemptionRequest>,
+    pub fn create_asset_redemption_request<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateAssetRedemptionRequest<'info>>,
     ) -> Result<()> {
         instructions::asset_redemption::create_asset_redemption_request::handle(ctx)
     }
@@ -50,8 +137,8 @@ pub mod collectible_vault {
      * Allows an NFT owner to cancel their asset redemption request.
      * This instruction allows a user to cancel their asset redemption request.
      */
-    pub fn cancel_asset_redemption_request(
-        ctx: Context<CancelAssetRedemptionRequest>,
+    pub fn cancel_asset_redemption_request<'info>(
+        ctx: Context<'_, '_, '_, 'info, CancelAssetRedemptionRequest<'info>>,
     ) -> Result<()> {
         instructions::asset_redemption::cancel_asset_redemption_request::handle(ctx)
     }
@@ -72,29 +159,42 @@ pub mod collectible_vault {
      * - loan_amount: The amount of lamports requested for the loan.
      * - interest_rate: The interest rate for the loan.
      * - duration: The duration of the loan in seconds.
+     * - request_expiry: Optional timestamp after which the unfunded request can no longer be funded.
      */
-    pub fn stake_nft_for_loan(
-        ctx: Context<StakeNftForLoan>,
+    pub fn stake_nft_for_loan<'info>(
+        ctx: Context<'_, '_, '_, 'info, StakeNftForLoan<'info>>,
         loan_amount: u64,
         interest_rate: u64,
         duration: i64,
+        request_expiry: Option<i64>,
     ) -> Result<()> {
-        instructions::loans::stake_nft_for_loan::handle(ctx, loan_amount, interest_rate, duration)
+        instructions::loans::stake_nft_for_loan::handle(
+            ctx,
+            loan_amount,
+            interest_rate,
+            duration,
+            request_expiry,
+        )
     }
 
     /**
      * Allows a lender to provide liquidity for a loan request.
      * This instruction funds the loan and makes it active.
+     * Parameters:
+     * - reputation_gate: Optional minimum borrower reputation the lender requires.
      */
-    pub fn provide_loan_liquidity(ctx: Context<ProvideLoanLiquidity>) -> Result<()> {
-        instructions::loans::provide_loan_liquidity::handle(ctx)
+    pub fn provide_loan_liquidity(
+        ctx: Context<ProvideLoanLiquidity>,
+        reputation_gate: Option<state::ReputationGate>,
+    ) -> Result<()> {
+        instructions::loans::provide_loan_liquidity::handle(ctx, reputation_gate)
     }
 
     /**
-     * Allows a borrower to repay their loan.
-     * This instruction transfers the repayment amount to the lender.
+     * Allows a borrower, or any payer on their behalf, to repay a loan.
+     * This instruction transfers the repayment amount to the lender and returns the NFT to the borrower.
      */
-    pub fn repay_loan(ctx: Context<RepayLoan>) -> Result<()> {
+    pub fn repay_loan<'info>(ctx: Context<'_, '_, '_, 'info, RepayLoan<'info>>) -> Result<()> {
         instructions::loans::repay_loan::handle(ctx)
     }
 
@@ -102,7 +202,9 @@ pub mod collectible_vault {
      * Allows a lender to claim an NFT when a loan becomes delinquent.
      * This instruction transfers the NFT to the lender if the loan terms are not met.
      */
-    pub fn claim_delinquent_nft(ctx: Context<ClaimDelinquentNft>) -> Result<()> {
+    pub fn claim_delinquent_nft<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimDelinquentNft<'info>>,
+    ) -> Result<()> {
         instructions::loans::claim_delinquent_nft::handle(ctx)
     }
 
@@ -110,7 +212,533 @@ pub mod collectible_vault {
      * Allows a borrower to cancel their loan request before it's funded.
      * This instruction returns the staked NFT to the owner.
      */
-    pub fn cancel_loan_request(ctx: Context<CancelLoanRequest>) -> Result<()> {
+    pub fn cancel_loan_request<'info>(
+        ctx: Context<'_, '_, '_, 'info, CancelLoanRequest<'info>>,
+    ) -> Result<()> {
         instructions::loans::cancel_loan_request::handle(ctx)
     }
+
+    /**
+     * Permissionless crank that closes an unfunded loan request after its request expiry.
+     * This instruction returns the staked NFT and all rent to the borrower.
+     */
+    pub fn close_expired_loan_request<'info>(
+        ctx: Context<'_, '_, '_, 'info, CloseExpiredLoanRequest<'info>>,
+    ) -> Result<()> {
+        instructions::loans::close_expired_loan_request::handle(ctx)
+    }
+
+    /**
+     * Permissionless crank that closes an empty vault token account left behind by a loan taken
+     * before loans were counted, for an NFT with no loan history. The rent is refunded to the admin.
+     */
+    pub fn sweep_baseline_vault_account(ctx: Context<SweepBaselineVaultAccount>) -> Result<()> {
+        instructions::loans::sweep_baseline_vault_account::handle(ctx)
+    }
+
+    /**
+     * Mints a new Metaplex Core asset with the provided product detail URI.
+     * Core assets share item numbers with the NFTs in the vault collection.
+     */
+    pub fn mint_core_asset(ctx: Context<MintCoreAsset>, product_detail_uri: String) -> Result<()> {
+        instructions::admin_operations::mint_core_asset::handle(ctx, product_detail_uri)
+    }
+
+    /**
+     * Allows a Metaplex Core asset owner to lock their asset as collateral for a loan.
+     * The asset stays in the owner's wallet, frozen under the vault authority.
+     * Parameters match stake_nft_for_loan.
+     */
+    pub fn stake_core_asset_for_loan(
+        ctx: Context<StakeCoreAssetForLoan>,
+        loan_amount: u64,
+        interest_rate: u64,
+        duration: i64,
+        request_expiry: Option<i64>,
+    ) -> Result<()> {
+        instructions::loans::stake_core_asset_for_loan::handle(
+            ctx,
+            loan_amount,
+            interest_rate,
+            duration,
+            request_expiry,
+        )
+    }
+
+    /**
+     * Allows a borrower, or any payer on their behalf, to repay a loan against a Core asset.
+     * This instruction transfers the repayment amount to the lender and thaws the asset.
+     */
+    pub fn repay_core_loan(ctx: Context<RepayCoreLoan>) -> Result<()> {
+        instructions::loans::repay_core_loan::handle(ctx)
+    }
+
+    /**
+     * Allows a lender to claim a Core asset when a loan becomes delinquent.
+     * This instruction moves the asset from the borrower's wallet to the lender.
+     */
+    pub fn claim_delinquent_core_asset(ctx: Context<ClaimDelinquentCoreAsset>) -> Result<()> {
+        instructions::loans::claim_delinquent_core_asset::handle(ctx)
+    }
+
+    /**
+     * Allows a borrower to cancel their loan request against a Core asset before it's funded.
+     * This instruction thaws the asset.
+     */
+    pub fn cancel_core_loan_request(ctx: Context<CancelCoreLoanRequest>) -> Result<()> {
+        instructions::loans::cancel_core_loan_request::handle(ctx)
+    }
+
+    /**
+     * Permissionless crank that closes an unfunded loan request against a Core asset after its request expiry.
+     * This instruction thaws the asset and returns the loan account rent to the borrower.
+     */
+    pub fn close_expired_core_loan_request(
+        ctx: Context<CloseExpiredCoreLoanRequest>,
+    ) -> Result<()> {
+        instructions::loans::close_expired_core_loan_request::handle(ctx)
+    }
+
+    /**
+     * Creates a new asset redemption request for a Metaplex Core asset.
+     * The asset stays in the owner's wallet, frozen under the asset redemption vault.
+     */
+    pub fn create_core_asset_redemption_request(
+        ctx: Context<CreateCoreAssetRedemptionRequest>,
+    ) -> Result<()> {
+        instructions::asset_redemption::create_core_asset_redemption_request::handle(ctx)
+    }
+
+    /**
+     * Allows a Core asset owner to cancel their asset redemption request.
+     * This instruction thaws the asset.
+     */
+    pub fn cancel_core_asset_redemption_request(
+        ctx: Context<CancelCoreAssetRedemptionRequest>,
+    ) -> Result<()> {
+        instructions::asset_redemption::cancel_core_asset_redemption_request::handle(ctx)
+    }
+
+    /**
+     * Allows the admin to fulfill an asset redemption request for a Core asset.
+     * This instruction moves the asset into the asset redemption vault.
+     */
+    pub fn fulfill_core_asset_redemption_request(
+        ctx: Context<FulfillCoreAssetRedemptionRequest>,
+    ) -> Result<()> {
+        instructions::asset_redemption::fulfill_core_asset_redemption_request::handle(ctx)
+    }
+
+    /**
+     * Mints a new compressed NFT into a Bubblegum Merkle tree under the vault collection.
+     * The admin must be the tree's creator or delegate.
+     */
+    pub fn mint_compressed_nft(
+        ctx: Context<MintCompressedNft>,
+        product_detail_uri: String,
+    ) -> Result<()> {
+        instructions::admin_operations::mint_compressed_nft::handle(ctx, product_detail_uri)
+    }
+
+    /**
+     * Creates a new asset redemption request for a compressed NFT.
+     * The leaf's proof nodes are passed as remaining accounts.
+     */
+    pub fn create_compressed_asset_redemption_request<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateCompressedAssetRedemptionRequest<'info>>,
+        leaf: CompressedLeaf,
+    ) -> Result<()> {
+        instructions::asset_redemption::create_compressed_asset_redemption_request::handle(
+            ctx, leaf,
+        )
+    }
+
+    /**
+     * Allows a compressed NFT owner to cancel their asset redemption request.
+     * The leaf's proof nodes are passed as remaining accounts.
+     */
+    pub fn cancel_compressed_asset_redemption_request<'info>(
+        ctx: Context<'_, '_, '_, 'info, CancelCompressedAssetRedemptionRequest<'info>>,
+        leaf: CompressedLeaf,
+    ) -> Result<()> {
+        instructions::asset_redemption::cancel_compressed_asset_redemption_request::handle(
+            ctx, leaf,
+        )
+    }
+
+    /**
+     * Allows the admin to fulfill an asset redemption request for a compressed NFT.
+     * Parameters:
+     * - nonce: The leaf nonce that, with the Merkle tree, identifies the compressed NFT.
+     */
+    pub fn fulfill_compressed_asset_redemption_request(
+        ctx: Context<FulfillCompressedAssetRedemptionRequest>,
+        nonce: u64,
+    ) -> Result<()> {
+        instructions::asset_redemption::fulfill_compressed_asset_redemption_request::handle(
+            ctx, nonce,
+        )
+    }
+
+    /**
+     * Creates a new asset redemption request for a non-transferable Token-2022 NFT.
+     * The NFT stays in the owner's wallet, delegated to the asset redemption vault.
+     */
+    pub fn create_non_transferable_asset_redemption_request(
+        ctx: Context<CreateNonTransferableAssetRedemptionRequest>,
+    ) -> Result<()> {
+        instructions::asset_redemption::create_non_transferable_asset_redemption_request::handle(
+            ctx,
+        )
+    }
+
+    /**
+     * Allows a non-transferable NFT owner to cancel their asset redemption request.
+     * This instruction revokes the asset redemption vault's approval.
+     */
+    pub fn cancel_non_transferable_asset_redemption_request(
+        ctx: Context<CancelNonTransferableAssetRedemptionRequest>,
+    ) -> Result<()> {
+        instructions::asset_redemption::cancel_non_transferable_asset_redemption_request::handle(
+            ctx,
+        )
+    }
+
+    /**
+     * Allows the admin to fulfill an asset redemption request for a non-transferable NFT.
+     * This instruction burns the NFT in the owner's wallet.
+     */
+    pub fn fulfill_non_transferable_asset_redemption_request(
+        ctx: Context<FulfillNonTransferableAssetRedemptionRequest>,
+    ) -> Result<()> {
+        instructions::asset_redemption::fulfill_non_transferable_asset_redemption_request::handle(
+            ctx,
+        )
+    }
+
+    /**
+     * Allows an NFT owner to request a loan without handing over custody of their NFT.
+     * The vault authority becomes the delegate of the owner's token account and freezes the NFT there.
+     * Parameters match stake_nft_for_loan.
+     */
+    pub fn lock_nft_for_loan(
+        ctx: Context<LockNftForLoan>,
+        loan_amount: u64,
+        interest_rate: u64,
+        duration: i64,
+        request_expiry: Option<i64>,
+    ) -> Result<()> {
+        instructions::loans::lock_nft_for_loan::handle(
+            ctx,
+            loan_amount,
+            interest_rate,
+            duration,
+            request_expiry,
+        )
+    }
+
+    /**
+     * Allows a borrower, or any payer on their behalf, to repay a loan against an NFT frozen in their wallet.
+     * This instruction transfers the repayment amount to the lender and thaws the NFT.
+     */
+    pub fn repay_locked_loan(ctx: Context<RepayLockedLoan>) -> Result<()> {
+        instructions::loans::repay_locked_loan::handle(ctx)
+    }
+
+    /**
+     * Allows a lender to claim an NFT frozen in the borrower's wallet when a loan becomes delinquent.
+     * This instruction thaws the NFT and moves it from the borrower's wallet to the lender.
+     */
+    pub fn claim_delinquent_locked_nft<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimDelinquentLockedNft<'info>>,
+    ) -> Result<()> {
+        instructions::loans::claim_delinquent_locked_nft::handle(ctx)
+    }
+
+    /**
+     * Allows a borrower to cancel their loan request against an NFT frozen in their wallet before it's funded.
+     * This instruction thaws the NFT and revokes the vault authority's delegation.
+     */
+    pub fn cancel_locked_loan_request(ctx: Context<CancelLockedLoanRequest>) -> Result<()> {
+        instructions::loans::cancel_locked_loan_request::handle(ctx)
+    }
+
+    /**
+     * Permissionless crank that closes an unfunded loan request against an NFT frozen in the borrower's wallet
+     * after its request expiry.
+     * This instruction thaws the NFT and returns the loan account rent to the borrower.
+     */
+    pub fn close_expired_locked_loan_request(
+        ctx: Context<CloseExpiredLockedLoanRequest>,
+    ) -> Result<()> {
+        instructions::loans::close_expired_locked_loan_request::handle(ctx)
+    }
+
+    /**
+     * Allows the admin to list an NFT held by the sale escrow for its primary sale.
+     * Parameters:
+     * - price: The sale price in lamports or USDC base units.
+     * - currency: Whether the price is in SOL or USDC.
+     */
+    pub fn create_primary_listing(
+        ctx: Context<CreatePrimaryListing>,
+        price: u64,
+        currency: state::SaleCurrency,
+    ) -> Result<()> {
+        instructions::primary_sales::create_primary_listing::handle(ctx, price, currency)
+    }
+
+    /**
+     * Allows the admin to withdraw an NFT from its primary sale.
+     * This instruction closes the listing and returns the NFT from the sale escrow to the admin.
+     */
+    pub fn cancel_primary_listing<'info>(
+        ctx: Context<'_, '_, '_, 'info, CancelPrimaryListing<'info>>,
+    ) -> Result<()> {
+        instructions::primary_sales::cancel_primary_listing::handle(ctx)
+    }
+
+    /**
+     * Allows anyone to buy an NFT listed for its primary sale.
+     * This instruction pays the price to the treasury and moves the NFT from the sale escrow to the buyer atomically.
+     */
+    pub fn purchase_listed_item<'info>(
+        ctx: Context<'_, '_, '_, 'info, PurchaseListedItem<'info>>,
+    ) -> Result<()> {
+        instructions::primary_sales::purchase_listed_item::handle(ctx)
+    }
+
+    /**
+     * Allows an NFT holder to list it for resale on the marketplace.
+     * This instruction moves the NFT into the marketplace escrow; NFTs with an open loan or a redemption request cannot be listed.
+     * Parameters:
+     * - price: The price in lamports, including the creator royalty.
+     */
+    pub fn list_nft<'info>(
+        ctx: Context<'_, '_, '_, 'info, ListNft<'info>>,
+        price: u64,
+    ) -> Result<()> {
+        instructions::marketplace::list_nft::handle(ctx, price)
+    }
+
+    /**
+     * Allows a seller to withdraw their marketplace listing.
+     * This instruction returns the NFT from the marketplace escrow to the seller.
+     */
+    pub fn cancel_listing<'info>(
+        ctx: Context<'_, '_, '_, 'info, CancelListing<'info>>,
+    ) -> Result<()> {
+        instructions::marketplace::cancel_listing::handle(ctx)
+    }
+
+    /**
+     * Allows anyone to buy an NFT listed on the marketplace.
+     * This instruction pays the creator royalty and the seller out of the price and moves the NFT to the buyer.
+     */
+    pub fn buy_listing<'info>(ctx: Context<'_, '_, '_, 'info, BuyListing<'info>>) -> Result<()> {
+        instructions::marketplace::buy_listing::handle(ctx)
+    }
+
+    /**
+     * Allows a buyer to finance a listed NFT with a loan from a co-signing lender.
+     * This instruction takes the buyer's down payment, has the lender pay the rest of the price to the seller
+     * and moves the NFT into the loan vault as collateral for an active loan in the buyer's name.
+     */
+    pub fn buy_listing_with_loan<'info>(
+        ctx: Context<'_, '_, '_, 'info, BuyListingWithLoan<'info>>,
+        down_payment: u64,
+        interest_amount: u64,
+        duration: i64,
+    ) -> Result<()> {
+        instructions::marketplace::buy_listing_with_loan::handle(
+            ctx,
+            down_payment,
+            interest_amount,
+            duration,
+        )
+    }
+
+    /**
+     * Allows a buyer to make an offer on an NFT, escrowing the offered lamports.
+     * Parameters:
+     * - price: The offered price in lamports, including the creator royalty.
+     * - expiry: Optional timestamp after which the offer can no longer be accepted.
+     */
+    pub fn make_offer(ctx: Context<MakeOffer>, price: u64, expiry: Option<i64>) -> Result<()> {
+        instructions::marketplace::make_offer::handle(ctx, price, expiry)
+    }
+
+    /**
+     * Allows a buyer to withdraw their offer.
+     * This instruction returns the escrowed lamports to the buyer.
+     */
+    pub fn cancel_offer(ctx: Context<CancelOffer>) -> Result<()> {
+        instructions::marketplace::cancel_offer::handle(ctx)
+    }
+
+    /**
+     * Allows an NFT holder to accept an offer on it.
+     * This instruction pays the creator royalty and the seller out of the escrowed lamports and moves the NFT to the buyer.
+     */
+    pub fn accept_offer<'info>(ctx: Context<'_, '_, '_, 'info, AcceptOffer<'info>>) -> Result<()> {
+        instructions::marketplace::accept_offer::handle(ctx)
+    }
+
+    /**
+     * Allows an NFT holder to split it into fraction tokens.
+     * This instruction locks the NFT in its fraction vault and mints the whole fraction supply to the holder.
+     * Parameters:
+     * - fraction_supply: The fixed number of fraction tokens to mint.
+     * - reserve_price: The lowest opening bid, in lamports, for a buyout of every fraction.
+     * - redemption_threshold_bps: The share of the fraction supply, in basis points, that must be burned together to redeem the NFT.
+     */
+    pub fn fractionalize_nft<'info>(
+        ctx: Context<'_, '_, '_, 'info, FractionalizeNft<'info>>,
+        fraction_supply: u64,
+        reserve_price: u64,
+        redemption_threshold_bps: u16,
+    ) -> Result<()> {
+        instructions::fractions::fractionalize_nft::handle(
+            ctx,
+            fraction_supply,
+            reserve_price,
+            redemption_threshold_bps,
+        )
+    }
+
+    /**
+     * Allows anyone to bid for every fraction of a fractionalized NFT.
+     * The opening bid starts the buyout auction and the previous top bidder is refunded when outbid.
+     * Parameters:
+     * - bid: The bid in lamports, held in the fraction vault until outbid or paid out to the fraction holders.
+     */
+    pub fn place_buyout_bid(ctx: Context<PlaceBuyoutBid>, bid: u64) -> Result<()> {
+        instructions::fractions::place_buyout_bid::handle(ctx, bid)
+    }
+
+    /**
+     * Allows a fraction holder to burn fractions for their share of the winning buyout bid, or of the
+     * buyout paid when a supermajority redeemed the NFT.
+     * Parameters:
+     * - amount: The number of fractions to burn.
+     */
+    pub fn claim_buyout_proceeds(ctx: Context<ClaimBuyoutProceeds>, amount: u64) -> Result<()> {
+        instructions::fractions::claim_buyout_proceeds::handle(ctx, amount)
+    }
+
+    /**
+     * Allows the winner of a buyout auction to take the NFT out of the fraction vault.
+     */
+    pub fn claim_buyout_nft<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimBuyoutNft<'info>>,
+    ) -> Result<()> {
+        instructions::fractions::claim_buyout_nft::handle(ctx)
+    }
+
+    /**
+     * Allows the winner of a buyout auction to send the NFT from the fraction vault straight into a
+     * redemption request in their name.
+     */
+    pub fn redeem_buyout_nft<'info>(
+        ctx: Context<'_, '_, '_, 'info, RedeemBuyoutNft<'info>>,
+    ) -> Result<()> {
+        instructions::fractions::redeem_buyout_nft::handle(ctx)
+    }
+
+    /**
+     * Allows fraction holders who together hold the redemption threshold to redeem the NFT for the
+     * physical asset, burning their fractions while the payer buys out the remaining ones.
+     * Parameters:
+     * - holder_count: The number of holder and fraction token account pairs leading the remaining accounts.
+     */
+    pub fn redeem_fractionalized_nft<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RedeemFractionalizedNft<'info>>,
+        holder_count: u8,
+    ) -> Result<()> {
+        instructions::fractions::redeem_fractionalized_nft::handle(ctx, holder_count)
+    }
+
+    /**
+     * Allows an NFT holder to list it for rent, moving it into rental escrow.
+     * Parameters:
+     * - daily_fee: The rental fee per day in lamports, paid to the owner.
+     * - max_days: The longest rental term the owner accepts, in days.
+     */
+    pub fn list_nft_for_rent<'info>(
+        ctx: Context<'_, '_, '_, 'info, ListNftForRent<'info>>,
+        daily_fee: u64,
+        max_days: u16,
+    ) -> Result<()> {
+        instructions::rentals::list_nft_for_rent::handle(ctx, daily_fee, max_days)
+    }
+
+    /**
+     * Allows anyone to rent an NFT listed for rent, paying the owner for the usage rights until the term ends.
+     * Parameters:
+     * - days: The length of the rental term in days.
+     */
+    pub fn rent_nft(ctx: Context<RentNft>, days: u16) -> Result<()> {
+        instructions::rentals::rent_nft::handle(ctx, days)
+    }
+
+    /**
+     * Allows an owner to withdraw an NFT from rent before anyone has rented it.
+     */
+    pub fn cancel_rental_listing<'info>(
+        ctx: Context<'_, '_, '_, 'info, CancelRentalListing<'info>>,
+    ) -> Result<()> {
+        instructions::rentals::cancel_rental_listing::handle(ctx)
+    }
+
+    /**
+     * Allows anyone to return a rented NFT to its owner once the rental term has ended.
+     */
+    pub fn end_rental<'info>(ctx: Context<'_, '_, '_, 'info, EndRental<'info>>) -> Result<()> {
+        instructions::rentals::end_rental::handle(ctx)
+    }
+
+    /**
+     * Allows anyone to deposit lamports into the insurance reserve.
+     * Parameters:
+     * - amount: The deposit in lamports.
+     */
+    pub fn fund_insurance_reserve(ctx: Context<FundInsuranceReserve>, amount: u64) -> Result<()> {
+        instructions::insurance::fund_insurance_reserve::handle(ctx, amount)
+    }
+
+    /**
+     * Allows the admin to record the appraised value of the physical item behind an NFT.
+     * Parameters:
+     * - appraised_value: The appraised value in lamports, replacing any previous appraisal.
+     */
+    pub fn record_appraisal(ctx: Context<RecordAppraisal>, appraised_value: u64) -> Result<()> {
+        instructions::insurance::record_appraisal::handle(ctx, appraised_value)
+    }
+
+    /**
+     * Allows the admin to declare the physical item behind an NFT lost or damaged, blocking the NFT
+     * in the program and fixing its insurance payout at the last appraisal.
+     * The NFT's token account is not frozen and the NFT stays transferable outside the program.
+     */
+    pub fn declare_asset_impaired(ctx: Context<DeclareAssetImpaired>) -> Result<()> {
+        instructions::insurance::declare_asset_impaired::handle(ctx)
+    }
+
+    /**
+     * Allows the holder of an impaired NFT to burn it for the insurance payout from the reserve.
+     */
+    pub fn claim_insurance_payout(ctx: Context<ClaimInsurancePayout>) -> Result<()> {
+        instructions::insurance::claim_insurance_payout::handle(ctx)
+    }
+
+    /**
+     * Permissionless crank that burns an impaired NFT held in its fraction vault for the insurance
+     * payout. The payout goes to the winner of an ended buyout auction, or otherwise to the fraction
+     * holders, who claim it pro rata with `claim_buyout_proceeds`.
+     */
+    pub fn claim_fractionalized_insurance_payout(
+        ctx: Context<ClaimFractionalizedInsurancePayout>,
+    ) -> Result<()> {
+        instructions::insurance::claim_fractionalized_insurance_payout::handle(ctx)
+    }
 }
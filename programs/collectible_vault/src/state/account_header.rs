@@ -0,0 +1,63 @@
+use {
+    crate::errors,
+    anchor_lang::{prelude::*, Discriminator},
+};
+
+/**
+ * Header stored as the first field of every state account, after the discriminator.
+ * Accounts created before the header existed are treated as version 0.
+ */
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct AccountHeader {
+    /// The layout version the account was written with.
+    pub version: u8,
+}
+
+impl AccountHeader {
+    /// Space taken by the header
+    pub const SPACE: usize = 1; // version
+
+    pub const fn new(version: u8) -> Self {
+        Self { version }
+    }
+}
+
+/**
+ * A state account with a versioned layout that can be upgraded in place by `migrate_account`.
+ */
+pub trait VersionedAccount: AccountSerialize + AccountDeserialize + Discriminator + Sized {
+    /// The layout version written by the program today
+    const VERSION: u8;
+    /// The space allocated for accounts in the current layout
+    const SPACE: usize;
+    /// The space unversioned (version 0) accounts were allocated with
+    const LEGACY_SPACE: usize;
+
+    /// Decodes an account body (without discriminator) stored at `version` into the current layout.
+    fn upgrade_from(version: u8, data: &[u8]) -> Result<Self>;
+
+    /// Returns the layout version of raw account data, including its discriminator.
+    fn stored_version(data: &[u8]) -> Result<u8> {
+        require!(
+            data.len() >= 8 && data[..8] == Self::DISCRIMINATOR,
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        if data.len() == Self::LEGACY_SPACE {
+            return Ok(0);
+        }
+        data.get(8)
+            .copied()
+            .ok_or_else(|| error!(ErrorCode::AccountDidNotDeserialize))
+    }
+}
+
+/// Decodes a legacy account body, failing with AccountDidNotDeserialize on malformed data.
+pub fn decode_legacy<T: AnchorDeserialize>(mut data: &[u8]) -> Result<T> {
+    T::deserialize(&mut data).map_err(|_| error!(ErrorCode::AccountDidNotDeserialize))
+}
+
+/// Fails with UnsupportedAccountVersion for versions that have no upgrade path.
+pub fn unsupported_version<T>(version: u8) -> Result<T> {
+    msg!("No upgrade path from account version {}", version);
+    err!(errors::ErrorCode::UnsupportedAccountVersion)
+}
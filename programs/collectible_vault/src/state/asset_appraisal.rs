@@ -0,0 +1,30 @@
+use {super::AccountHeader, anchor_lang::prelude::*};
+
+/**
+ * Stores the last appraised value of the physical item behind an NFT.
+ * The admin records a new appraisal over the previous one; an insurance claim pays out the value
+ * appraised before the item was declared impaired.
+ */
+#[account]
+pub struct AssetAppraisal {
+    /// The versioned account header.
+    pub header: AccountHeader,
+    /// The mint address of the appraised NFT.
+    pub nft_mint: Pubkey,
+    /// The appraised value of the physical item in lamports.
+    pub appraised_value: u64,
+    /// The timestamp of the appraisal.
+    pub appraised_at: i64,
+}
+
+impl AssetAppraisal {
+    /// The current layout version
+    pub const VERSION: u8 = 1;
+
+    /// Calculates the initial space required for the AssetAppraisal account
+    pub const INIT_SPACE: usize = 8 +    // discriminator
+        AccountHeader::SPACE + // header
+        32 +    // nft_mint
+        8 +     // appraised_value
+        8; // appraised_at
+}
@@ -0,0 +1,36 @@
+use {super::AccountHeader, anchor_lang::prelude::*};
+
+/**
+ * Marks an NFT whose physical item was lost or damaged, blocking it in the program.
+ * While this account exists the NFT cannot be listed, sold, rented, staked, fractionalized or sent
+ * for redemption; its holder can only burn it against the payout from the insurance reserve.
+ */
+#[account]
+pub struct AssetImpairment {
+    /// The versioned account header.
+    pub header: AccountHeader,
+    /// The mint address of the impaired NFT.
+    pub nft_mint: Pubkey,
+    /// The payout in lamports, taken from the last appraisal when the item was declared impaired.
+    pub payout: u64,
+    /// The timestamp when the admin declared the item impaired.
+    pub declared_at: i64,
+    /// The public key of the holder who burned the NFT for the payout (None until claimed).
+    pub claimed_by: Option<Pubkey>,
+    /// The timestamp of the claim (None until claimed).
+    pub claimed_at: Option<i64>,
+}
+
+impl AssetImpairment {
+    /// The current layout version
+    pub const VERSION: u8 = 1;
+
+    /// Calculates the initial space required for the AssetImpairment account
+    pub const INIT_SPACE: usize = 8 +    // discriminator
+        AccountHeader::SPACE + // header
+        32 +    // nft_mint
+        8 +     // payout
+        8 +     // declared_at
+        33 +    // claimed_by (Option)
+        9; // claimed_at (Option)
+}
@@ -1,4 +1,7 @@
-use anchor_lang::prelude::*;
+use {
+    super::{decode_legacy, unsupported_version, AccountHeader, VersionedAccount},
+    anchor_lang::prelude::*,
+};
 
 /**
  * Stores information about an asset redemption request.
@@ -7,6 +10,8 @@ use anchor_lang::prelude::*;
 #[account]
 #[derive(Default)]
 pub struct AssetRedemptionInfo {
+    /// The versioned account header.
+    pub header: AccountHeader,
     /// The mint address of the NFT being used as collateral.
     pub nft_mint: Pubkey,
     /// The public key of the original owner of the NFT.    
@@ -18,10 +23,58 @@ pub struct AssetRedemptionInfo {
 }
 
 impl AssetRedemptionInfo {
+    /// The current layout version
+    pub const VERSION: u8 = 1;
+
     /// Calculates the initial space required for the AssetRedemptionInfo account
     pub const INIT_SPACE: usize = 8 +    // discriminator
+        AccountHeader::SPACE + // header
         32 +    // nft_mint
         32 +    // nft_owner
         8 +     // request_timestamp
         1; // is_fulfilled
 }
+
+impl VersionedAccount for AssetRedemptionInfo {
+    const VERSION: u8 = AssetRedemptionInfo::VERSION;
+    const SPACE: usize = AssetRedemptionInfo::INIT_SPACE;
+    const LEGACY_SPACE: usize = LegacyAssetRedemptionInfo::SPACE;
+
+    fn upgrade_from(version: u8, data: &[u8]) -> Result<Self> {
+        match version {
+            0 => Ok(decode_legacy::<LegacyAssetRedemptionInfo>(data)?.upgrade()),
+            _ => unsupported_version(version),
+        }
+    }
+}
+
+/**
+ * The unversioned AssetRedemptionInfo layout used before the account header was introduced.
+ */
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyAssetRedemptionInfo {
+    pub nft_mint: Pubkey,
+    pub nft_owner: Pubkey,
+    pub request_timestamp: i64,
+    pub is_fulfilled: bool,
+}
+
+impl LegacyAssetRedemptionInfo {
+    /// The space legacy AssetRedemptionInfo accounts were allocated with
+    pub const SPACE: usize = 8 +    // discriminator
+        32 +    // nft_mint
+        32 +    // nft_owner
+        8 +     // request_timestamp
+        1; // is_fulfilled
+
+    /// Converts the legacy layout to the current AssetRedemptionInfo layout.
+    pub fn upgrade(self) -> AssetRedemptionInfo {
+        AssetRedemptionInfo {
+            header: AccountHeader::new(AssetRedemptionInfo::VERSION),
+            nft_mint: self.nft_mint,
+            nft_owner: self.nft_owner,
+            request_timestamp: self.request_timestamp,
+            is_fulfilled: self.is_fulfilled,
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use {super::AccountHeader, crate::errors, anchor_lang::prelude::*};
+
+/**
+ * Minimum borrower reputation a lender can require before funding a loan.
+ */
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
+pub struct ReputationGate {
+    /// The minimum number of loans the borrower must have repaid on time.
+    pub min_loans_repaid_on_time: u64,
+    /// The maximum number of loans the borrower may have defaulted on.
+    pub max_loans_defaulted: u64,
+    /// The minimum lamports the borrower must have repaid on time across all loans.
+    pub min_repaid_volume: u64,
+}
+
+/**
+ * Tracks the loan history of a single borrower wallet.
+ * This account is updated whenever one of the borrower's loans closes, so lenders can judge their reliability.
+ * Loans below `MIN_REPUTATION_LOAN_AMOUNT` still count as taken and defaulted, but not as repaid, so
+ * a track record cannot be built from dust loans.
+ */
+#[account]
+#[derive(Default)]
+pub struct BorrowerProfile {
+    /// The versioned account header.
+    pub header: AccountHeader,
+    /// The public key of the borrower.
+    pub borrower: Pubkey,
+    /// The number of funded loans that have closed, either repaid or defaulted.
+    pub loans_taken: u64,
+    /// The number of loans repaid before expiry.
+    pub loans_repaid_on_time: u64,
+    /// The number of loans whose collateral was claimed by the lender.
+    pub loans_defaulted: u64,
+    /// The number of loan requests closed before being funded.
+    pub loans_cancelled: u64,
+    /// The total lamports borrowed across all funded loans.
+    pub total_volume: u64,
+    /// The total lamports borrowed across loans repaid on time that count towards reputation.
+    pub repaid_volume: u64,
+}
+
+impl BorrowerProfile {
+    /// The current layout version
+    pub const VERSION: u8 = 1;
+
+    /// The smallest principal, in lamports, of a repaid loan that counts towards reputation
+    pub const MIN_REPUTATION_LOAN_AMOUNT: u64 = 1_000_000_000;
+
+    /// Calculates the initial space required for the BorrowerProfile account
+    pub const INIT_SPACE: usize = 8 +    // discriminator
+        AccountHeader::SPACE + // header
+        32 +    // borrower
+        8 +     // loans_taken
+        8 +     // loans_repaid_on_time
+        8 +     // loans_defaulted
+        8 +     // loans_cancelled
+        8 +     // total_volume
+        8; // repaid_volume
+
+    /// Records a funded loan that was repaid on time, counting it towards reputation only if its
+    /// principal is at least `MIN_REPUTATION_LOAN_AMOUNT`.
+    pub fn record_repaid(&mut self, borrower: Pubkey, loan_amount: u64) -> Result<()> {
+        self.record_funded(borrower, loan_amount)?;
+        if loan_amount >= Self::MIN_REPUTATION_LOAN_AMOUNT {
+            self.loans_repaid_on_time = checked_increment(self.loans_repaid_on_time)?;
+            self.repaid_volume = self
+                .repaid_volume
+                .checked_add(loan_amount)
+                .ok_or(errors::ErrorCode::CalculationError)?;
+        }
+        Ok(())
+    }
+
+    /// Records a funded loan whose collateral was claimed by the lender.
+    pub fn record_defaulted(&mut self, borrower: Pubkey, loan_amount: u64) -> Result<()> {
+        self.record_funded(borrower, loan_amount)?;
+        self.loans_defaulted = checked_increment(self.loans_defaulted)?;
+        Ok(())
+    }
+
+    /// Records a loan request that was closed before being funded.
+    pub fn record_cancelled(&mut self, borrower: Pubkey) -> Result<()> {
+        self.header = AccountHeader::new(Self::VERSION);
+        self.borrower = borrower;
+        self.loans_cancelled = checked_increment(self.loans_cancelled)?;
+        Ok(())
+    }
+
+    /// Returns true if this borrower's history satisfies the lender's reputation gate.
+    pub fn meets(&self, gate: &ReputationGate) -> bool {
+        self.loans_repaid_on_time >= gate.min_loans_repaid_on_time
+            && self.loans_defaulted <= gate.max_loans_defaulted
+            && self.repaid_volume >= gate.min_repaid_volume
+    }
+
+    fn record_funded(&mut self, borrower: Pubkey, loan_amount: u64) -> Result<()> {
+        self.header = AccountHeader::new(Self::VERSION);
+        self.borrower = borrower;
+        self.loans_taken = checked_increment(self.loans_taken)?;
+        self.total_volume = self
+            .total_volume
+            .checked_add(loan_amount)
+            .ok_or(errors::ErrorCode::CalculationError)?;
+        Ok(())
+    }
+}
+
+fn checked_increment(value: u64) -> Result<u64> {
+    Ok(value
+        .checked_add(1)
+        .ok_or(errors::ErrorCode::CalculationError)?)
+}
@@ -1,4 +1,7 @@
-use anchor_lang::prelude::*;
+use {
+    super::{decode_legacy, unsupported_version, AccountHeader, VersionedAccount},
+    anchor_lang::prelude::*,
+};
 
 /**
  * Tracks the number of NFTs minted in a collection.
@@ -6,6 +9,8 @@ use anchor_lang::prelude::*;
  */
 #[account]
 pub struct CollectionCounter {
+    /// The versioned account header
+    pub header: AccountHeader,
     /// The mint address of the collection
     pub collection_mint: Pubkey,
     /// The current count of NFTs in the collection
@@ -13,8 +18,50 @@ pub struct CollectionCounter {
 }
 
 impl CollectionCounter {
+    /// The current layout version
+    pub const VERSION: u8 = 1;
+
     /// Calculates the initial space required for the CollectionCounter account
     pub const INIT_SPACE: usize = 8 +    // discriminator
+        AccountHeader::SPACE + // header
         32 +    // collection_mint (Pubkey)
         8; // count (u64)
 }
+
+impl VersionedAccount for CollectionCounter {
+    const VERSION: u8 = CollectionCounter::VERSION;
+    const SPACE: usize = CollectionCounter::INIT_SPACE;
+    const LEGACY_SPACE: usize = LegacyCollectionCounter::SPACE;
+
+    fn upgrade_from(version: u8, data: &[u8]) -> Result<Self> {
+        match version {
+            0 => Ok(decode_legacy::<LegacyCollectionCounter>(data)?.upgrade()),
+            _ => unsupported_version(version),
+        }
+    }
+}
+
+/**
+ * The unversioned CollectionCounter layout used before the account header was introduced.
+ */
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyCollectionCounter {
+    pub collection_mint: Pubkey,
+    pub count: u64,
+}
+
+impl LegacyCollectionCounter {
+    /// The space legacy CollectionCounter accounts were allocated with
+    pub const SPACE: usize = 8 +    // discriminator
+        32 +    // collection_mint (Pubkey)
+        8; // count (u64)
+
+    /// Converts the legacy layout to the current CollectionCounter layout.
+    pub fn upgrade(self) -> CollectionCounter {
+        CollectionCounter {
+            header: AccountHeader::new(CollectionCounter::VERSION),
+            collection_mint: self.collection_mint,
+            count: self.count,
+        }
+    }
+}
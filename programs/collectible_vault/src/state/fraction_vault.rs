@@ -0,0 +1,122 @@
+use {super::AccountHeader, crate::errors, anchor_lang::prelude::*};
+
+/**
+ * Represents the lifecycle of a fractionalized NFT.
+ */
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum FractionVaultStatus {
+    /// The NFT is locked in the fraction vault and no buyout bid has been placed.
+    #[default]
+    Active,
+    /// A buyout auction is running, or has ended and the top bidder has yet to take the NFT.
+    Auction,
+    /// The buyout winner took the NFT out of the vault or sent it for redemption, or a supermajority
+    /// of fraction holders redeemed it.
+    Released,
+}
+
+/**
+ * Stores a fractionalized NFT locked in the fraction vault, along with its buyout auction.
+ * The top bid is held in this account on top of its rent, and after the auction ends it is paid
+ * out to the fraction holders as they burn their fractions.
+ */
+#[account]
+pub struct FractionVault {
+    /// The versioned account header.
+    pub header: AccountHeader,
+    /// The mint address of the fractionalized NFT.
+    pub nft_mint: Pubkey,
+    /// The mint of the fraction tokens, whose supply is fixed when the NFT is fractionalized.
+    pub fraction_mint: Pubkey,
+    /// The public key of the owner who fractionalized the NFT.
+    pub curator: Pubkey,
+    /// The number of fraction tokens minted.
+    pub fraction_supply: u64,
+    /// The lowest opening bid, in lamports, for a buyout of every fraction. It also prices the
+    /// fractions bought out when a supermajority redeems the NFT.
+    pub reserve_price: u64,
+    /// The share of the fraction supply, in basis points, that must be burned together to redeem the NFT.
+    pub redemption_threshold_bps: u16,
+    /// The current state of the vault.
+    pub status: FractionVaultStatus,
+    /// The timestamp when the buyout auction ends (None until the first bid).
+    pub auction_end: Option<i64>,
+    /// The public key of the highest bidder (None until the first bid).
+    pub top_bidder: Option<Pubkey>,
+    /// The highest bid in lamports, or the price paid for the fractions left outstanding by a
+    /// supermajority redemption.
+    pub top_bid: u64,
+    /// The part of the winning bid or redemption buyout already paid out to fraction holders.
+    pub proceeds_claimed: u64,
+    /// The timestamp when the NFT was fractionalized.
+    pub created_at: i64,
+}
+
+impl FractionVault {
+    /// The current layout version
+    pub const VERSION: u8 = 1;
+
+    /// How long a buyout auction runs after its opening bid, in seconds
+    pub const BUYOUT_AUCTION_DURATION: i64 = 3 * 24 * 60 * 60;
+
+    /// The minimum raise over the top bid, in basis points
+    pub const MIN_BID_INCREMENT_BPS: u64 = 500;
+
+    /// The lowest redemption threshold, in basis points; redemption always needs a strict majority
+    pub const MIN_REDEMPTION_THRESHOLD_BPS: u16 = 5_001;
+
+    /// Calculates the initial space required for the FractionVault account
+    pub const INIT_SPACE: usize = 8 +    // discriminator
+        AccountHeader::SPACE + // header
+        32 +    // nft_mint
+        32 +    // fraction_mint
+        32 +    // curator
+        8 +     // fraction_supply
+        8 +     // reserve_price
+        2 +     // redemption_threshold_bps
+        1 +     // status
+        9 +     // auction_end (Option)
+        33 +    // top_bidder (Option)
+        8 +     // top_bid
+        8 +     // proceeds_claimed
+        8; // created_at
+
+    /// Returns true once the fractions can be burned for proceeds: after a buyout auction ends, including
+    /// after the winner took the NFT, or after a supermajority redemption.
+    pub fn is_bought_out(&self, now: i64) -> bool {
+        match self.status {
+            FractionVaultStatus::Active => false,
+            FractionVaultStatus::Auction => matches!(self.auction_end, Some(end) if now >= end),
+            FractionVaultStatus::Released => true,
+        }
+    }
+
+    /// Returns the lowest bid accepted next: the reserve price, or the top bid plus the minimum increment.
+    pub fn minimum_bid(&self) -> u64 {
+        if self.top_bidder.is_none() {
+            return self.reserve_price;
+        }
+        let increment =
+            (self.top_bid as u128 * Self::MIN_BID_INCREMENT_BPS as u128 / 10_000) as u64;
+        self.top_bid.saturating_add(increment.max(1))
+    }
+
+    /// Returns the price of buying out `outstanding` fractions at the reserve price, rounded up.
+    pub fn buyout_price(&self, outstanding: u64) -> u64 {
+        (self.reserve_price as u128 * outstanding as u128).div_ceil(self.fraction_supply as u128)
+            as u64
+    }
+
+    /// Returns the share of the unclaimed buyout proceeds owed for burning `amount` of the
+    /// `outstanding` fractions, so the last holder to claim receives any rounding remainder.
+    pub fn proceeds_for(&self, amount: u64, outstanding: u64) -> Result<u64> {
+        let unclaimed = self
+            .top_bid
+            .checked_sub(self.proceeds_claimed)
+            .ok_or(errors::ErrorCode::CalculationError)?;
+        let proceeds = (unclaimed as u128 * amount as u128)
+            .checked_div(outstanding as u128)
+            .ok_or(errors::ErrorCode::CalculationError)?;
+        u64::try_from(proceeds).map_err(|_| errors::ErrorCode::CalculationError.into())
+    }
+}
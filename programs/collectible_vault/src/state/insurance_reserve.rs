@@ -0,0 +1,40 @@
+use {super::AccountHeader, crate::errors, anchor_lang::prelude::*};
+
+/**
+ * Holds the lamports that pay out insurance claims on lost or damaged physical items.
+ * The reserve is funded by its share of SOL primary sale proceeds and rental fees, and by direct
+ * deposits. Every lamport above the account's rent can be paid out.
+ */
+#[account]
+pub struct InsuranceReserve {
+    /// The versioned account header.
+    pub header: AccountHeader,
+    /// The lamports paid out to claimants so far.
+    pub total_paid_out: u64,
+    /// The number of claims paid.
+    pub claims_paid: u64,
+}
+
+impl InsuranceReserve {
+    /// The current layout version
+    pub const VERSION: u8 = 1;
+
+    /// Calculates the initial space required for the InsuranceReserve account
+    pub const INIT_SPACE: usize = 8 +    // discriminator
+        AccountHeader::SPACE + // header
+        8 +     // total_paid_out
+        8; // claims_paid
+
+    /// Records a claim paid out of the reserve.
+    pub fn record_payout(&mut self, payout: u64) -> Result<()> {
+        self.total_paid_out = self
+            .total_paid_out
+            .checked_add(payout)
+            .ok_or(errors::ErrorCode::CalculationError)?;
+        self.claims_paid = self
+            .claims_paid
+            .checked_add(1)
+            .ok_or(errors::ErrorCode::CalculationError)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,32 @@
+use {super::AccountHeader, anchor_lang::prelude::*};
+
+/**
+ * Stores the terms of an NFT listed for resale on the marketplace.
+ * The NFT itself is held by the marketplace escrow until it is sold or the listing is cancelled.
+ */
+#[account]
+pub struct Listing {
+    /// The versioned account header.
+    pub header: AccountHeader,
+    /// The mint address of the listed NFT.
+    pub nft_mint: Pubkey,
+    /// The public key of the seller, who receives the proceeds.
+    pub seller: Pubkey,
+    /// The price in lamports, including the creator royalty.
+    pub price: u64,
+    /// The timestamp when the NFT was listed.
+    pub listed_at: i64,
+}
+
+impl Listing {
+    /// The current layout version
+    pub const VERSION: u8 = 1;
+
+    /// Calculates the initial space required for the Listing account
+    pub const INIT_SPACE: usize = 8 +    // discriminator
+        AccountHeader::SPACE + // header
+        32 +    // nft_mint
+        32 +    // seller
+        8 +     // price
+        8; // listed_at
+}
@@ -0,0 +1,26 @@
+use {super::AccountHeader, anchor_lang::prelude::*};
+
+/**
+ * Tracks the number of loans ever requested against a specific NFT.
+ * This account outlives individual loans so every loan can be assigned a unique sequence number.
+ */
+#[account]
+pub struct LoanCounter {
+    /// The versioned account header
+    pub header: AccountHeader,
+    /// The mint address of the NFT
+    pub nft_mint: Pubkey,
+    /// The number of loans requested against this NFT so far
+    pub count: u64,
+}
+
+impl LoanCounter {
+    /// The current layout version
+    pub const VERSION: u8 = 1;
+
+    /// Calculates the initial space required for the LoanCounter account
+    pub const INIT_SPACE: usize = 8 +    // discriminator
+        AccountHeader::SPACE + // header
+        32 +    // nft_mint (Pubkey)
+        8; // count (u64)
+}
@@ -1,15 +1,37 @@
-use anchor_lang::prelude::*;
+use {
+    super::{decode_legacy, unsupported_version, AccountHeader, VersionedAccount},
+    anchor_lang::prelude::*,
+};
+
+/**
+ * The lifecycle status of a loan.
+ */
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum LoanStatus {
+    /// The NFT is staked and the request is waiting for a lender.
+    #[default]
+    Requested,
+    /// A lender funded the loan and repayment is outstanding.
+    Active,
+    /// The borrower repaid principal and interest before the loan expired.
+    Repaid,
+    /// The lender claimed the NFT after the loan expired.
+    Defaulted,
+    /// The request was closed before it was funded, either by the borrower or after expiring.
+    Cancelled,
+}
 
 /**
  * Stores information about a loan request or active loan.
  * This account tracks all the details of a loan using an NFT as collateral.
  */
 #[account]
-#[derive(Default)]
 pub struct LoanInfo {
+    /// The versioned account header.
+    pub header: AccountHeader,
     /// The mint address of the NFT being used as collateral.
     pub nft_mint: Pubkey,
-    /// The public key of the original owner of the NFT.    
+    /// The public key of the original owner of the NFT.
     pub nft_owner: Pubkey,
     /// The amount of lamports requested for the loan.
     pub loan_amount: u64,
@@ -19,15 +41,92 @@ pub struct LoanInfo {
     pub duration: i64,
     /// The timestamp when the loan becomes active (None if not yet funded).
     pub start_time: Option<i64>,
-    /// The public key of the lender (None if not yet funded).
+    /// The public key of the lender who funded the loan (None if not yet funded).
+    /// The current holder of the loan note is entitled to repayment or the collateral.
     pub lender: Option<Pubkey>,
-    /// Indicates whether the loan is currently active.
-    pub is_active: bool,
+    /// The lifecycle status of the loan.
+    pub status: LoanStatus,
+    /// The timestamp after which an unfunded request can no longer be funded (None if it never expires).
+    pub request_expiry: Option<i64>,
+    /// The sequence number of this loan against the NFT, assigned from its LoanCounter.
+    pub loan_id: u64,
+    /// Reserved space for future fields, zeroed on creation.
+    pub reserved: [u8; 64],
 }
 
 impl LoanInfo {
+    /// The current layout version written by `stake_nft_for_loan` and the migration instructions
+    pub const VERSION: u8 = 1;
+
     /// Calculates the initial space required for the LoanInfo account
     pub const INIT_SPACE: usize = 8 +    // discriminator
+        AccountHeader::SPACE + // header
+        32 +    // nft_mint
+        32 +    // nft_owner
+        8 +     // loan_amount
+        8 +     // interest_amount
+        8 +     // duration
+        9 +     // start_time (Option)
+        33 +    // lender (Option)
+        1 +     // status (enum)
+        9 +     // request_expiry (Option)
+        8 +     // loan_id
+        64; // reserved
+
+    /// Returns true if the loan request carries an expiry that has already passed.
+    pub fn is_request_expired(&self, now: i64) -> bool {
+        matches!(self.request_expiry, Some(expiry) if now > expiry)
+    }
+
+    /// Returns the timestamp after which a funded loan is overdue (None if not yet funded).
+    pub fn due_time(&self) -> Option<i64> {
+        self.start_time
+            .and_then(|start_time| start_time.checked_add(self.duration))
+    }
+
+    /// Returns true if the loan is funded and can still be repaid.
+    pub fn is_repayable(&self, now: i64) -> bool {
+        matches!(self.due_time(), Some(due_time) if now <= due_time)
+    }
+
+    /// Returns true if the loan is funded and its repayment deadline has passed.
+    pub fn is_overdue(&self, now: i64) -> bool {
+        matches!(self.due_time(), Some(due_time) if now > due_time)
+    }
+}
+
+impl VersionedAccount for LoanInfo {
+    const VERSION: u8 = LoanInfo::VERSION;
+    const SPACE: usize = LoanInfo::INIT_SPACE;
+    const LEGACY_SPACE: usize = LegacyLoanInfo::SPACE;
+
+    fn upgrade_from(version: u8, data: &[u8]) -> Result<Self> {
+        match version {
+            0 => Ok(decode_legacy::<LegacyLoanInfo>(data)?.upgrade()),
+            _ => unsupported_version(version),
+        }
+    }
+}
+
+/**
+ * The unversioned LoanInfo layout deployed before `LoanStatus`, request expiry and loan ids existed.
+ * Accounts in this layout are upgraded in place by `migrate_account`.
+ */
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyLoanInfo {
+    pub nft_mint: Pubkey,
+    pub nft_owner: Pubkey,
+    pub loan_amount: u64,
+    pub interest_amount: u64,
+    pub duration: i64,
+    pub start_time: Option<i64>,
+    pub lender: Option<Pubkey>,
+    pub is_active: bool,
+}
+
+impl LegacyLoanInfo {
+    /// The space legacy LoanInfo accounts were allocated with
+    pub const SPACE: usize = 8 +    // discriminator
         32 +    // nft_mint
         32 +    // nft_owner
         8 +     // loan_amount
@@ -36,4 +135,27 @@ impl LoanInfo {
         9 +     // start_time (Option)
         33 +    // lender (Option)
         1; // is_active
+
+    /// Converts the legacy layout to the current LoanInfo layout. Legacy requests never expire,
+    /// and legacy loans predate loan ids, so they take id 0 and have no loan note.
+    pub fn upgrade(self) -> LoanInfo {
+        LoanInfo {
+            header: AccountHeader::new(LoanInfo::VERSION),
+            nft_mint: self.nft_mint,
+            nft_owner: self.nft_owner,
+            loan_amount: self.loan_amount,
+            interest_amount: self.interest_amount,
+            duration: self.duration,
+            start_time: self.start_time,
+            lender: self.lender,
+            status: if self.is_active {
+                LoanStatus::Active
+            } else {
+                LoanStatus::Requested
+            },
+            request_expiry: None,
+            loan_id: 0,
+            reserved: [0; 64],
+        }
+    }
 }
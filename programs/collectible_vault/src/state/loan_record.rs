@@ -0,0 +1,81 @@
+use {
+    super::{AccountHeader, LoanInfo},
+    anchor_lang::prelude::*,
+};
+
+/**
+ * The final outcome of a loan once its LoanInfo account has been closed.
+ */
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LoanOutcome {
+    /// The borrower repaid principal and interest before the loan expired.
+    Repaid,
+    /// The lender claimed the NFT after the loan expired.
+    Defaulted,
+    /// The request was closed before it was funded, either by the borrower or after expiring.
+    Cancelled,
+}
+
+/**
+ * Compact archive of a closed loan.
+ * This account is retained after the LoanInfo account closes so loan history can be audited on-chain.
+ */
+#[account]
+pub struct LoanRecord {
+    /// The versioned account header.
+    pub header: AccountHeader,
+    /// The mint address of the NFT used as collateral.
+    pub nft_mint: Pubkey,
+    /// The sequence number of this loan against the NFT.
+    pub loan_id: u64,
+    /// The public key of the borrower.
+    pub borrower: Pubkey,
+    /// The public key of the lender (None if the request was never funded).
+    pub lender: Option<Pubkey>,
+    /// The amount of lamports requested for the loan.
+    pub loan_amount: u64,
+    /// The interest amount to be paid on the loan.
+    pub interest_amount: u64,
+    /// The duration of the loan in seconds.
+    pub duration: i64,
+    /// The timestamp when the loan became active (None if the request was never funded).
+    pub start_time: Option<i64>,
+    /// The timestamp when the loan was closed.
+    pub closed_at: i64,
+    /// How the loan ended.
+    pub outcome: LoanOutcome,
+}
+
+impl LoanRecord {
+    /// The current layout version
+    pub const VERSION: u8 = 1;
+
+    /// Calculates the initial space required for the LoanRecord account
+    pub const INIT_SPACE: usize = 8 +    // discriminator
+        AccountHeader::SPACE + // header
+        32 +    // nft_mint
+        8 +     // loan_id
+        32 +    // borrower
+        33 +    // lender (Option)
+        8 +     // loan_amount
+        8 +     // interest_amount
+        8 +     // duration
+        9 +     // start_time (Option)
+        8 +     // closed_at
+        1; // outcome (enum)
+
+    /// Copies the terms of a closing loan into this record along with its outcome.
+    pub fn archive(&mut self, loan_info: &LoanInfo, outcome: LoanOutcome, closed_at: i64) {
+        self.header = AccountHeader::new(Self::VERSION);
+        self.nft_mint = loan_info.nft_mint;
+        self.loan_id = loan_info.loan_id;
+        self.borrower = loan_info.nft_owner;
+        self.lender = loan_info.lender;
+        self.loan_amount = loan_info.loan_amount;
+        self.interest_amount = loan_info.interest_amount;
+        self.duration = loan_info.duration;
+        self.start_time = loan_info.start_time;
+        self.closed_at = closed_at;
+        self.outcome = outcome;
+    }
+}
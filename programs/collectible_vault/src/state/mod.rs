@@ -1,7 +1,33 @@
+pub mod account_header;
+pub mod asset_appraisal;
+pub mod asset_impairment;
 pub mod asset_redemption_info;
+pub mod borrower_profile;
 pub mod collection_counter;
+pub mod fraction_vault;
+pub mod insurance_reserve;
+pub mod listing;
+pub mod loan_counter;
 pub mod loan_info;
+pub mod loan_record;
+pub mod offer;
+pub mod primary_listing;
+pub mod rental;
+pub mod vault_config;
 
+pub use account_header::*;
+pub use asset_appraisal::*;
+pub use asset_impairment::*;
 pub use asset_redemption_info::*;
+pub use borrower_profile::*;
 pub use collection_counter::*;
+pub use fraction_vault::*;
+pub use insurance_reserve::*;
+pub use listing::*;
+pub use loan_counter::*;
 pub use loan_info::*;
+pub use loan_record::*;
+pub use offer::*;
+pub use primary_listing::*;
+pub use rental::*;
+pub use vault_config::*;
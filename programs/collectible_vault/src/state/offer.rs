@@ -0,0 +1,40 @@
+use {super::AccountHeader, anchor_lang::prelude::*};
+
+/**
+ * Stores a buyer's offer on an NFT.
+ * The offered lamports are held in this account on top of its rent until the offer is accepted or cancelled.
+ */
+#[account]
+pub struct Offer {
+    /// The versioned account header.
+    pub header: AccountHeader,
+    /// The mint address of the NFT the offer is for.
+    pub nft_mint: Pubkey,
+    /// The public key of the buyer making the offer.
+    pub buyer: Pubkey,
+    /// The offered price in lamports, including the creator royalty.
+    pub price: u64,
+    /// The timestamp after which the offer can no longer be accepted (None if it never expires).
+    pub expiry: Option<i64>,
+    /// The timestamp when the offer was made.
+    pub created_at: i64,
+}
+
+impl Offer {
+    /// The current layout version
+    pub const VERSION: u8 = 1;
+
+    /// Calculates the initial space required for the Offer account
+    pub const INIT_SPACE: usize = 8 +    // discriminator
+        AccountHeader::SPACE + // header
+        32 +    // nft_mint
+        32 +    // buyer
+        8 +     // price
+        9 +     // expiry (Option)
+        8; // created_at
+
+    /// Returns true if the offer can still be accepted at `now`.
+    pub fn is_open(&self, now: i64) -> bool {
+        !matches!(self.expiry, Some(expiry) if now > expiry)
+    }
+}
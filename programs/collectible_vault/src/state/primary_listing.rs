@@ -0,0 +1,44 @@
+use {super::AccountHeader, anchor_lang::prelude::*};
+
+/**
+ * The currency a sale price is denominated in.
+ */
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum SaleCurrency {
+    /// The price is in lamports.
+    #[default]
+    Sol,
+    /// The price is in base units of the USDC mint.
+    Usdc,
+}
+
+/**
+ * Stores the price of an NFT listed for its primary sale.
+ * The NFT itself is held by the sale escrow until it is purchased or the listing is cancelled.
+ */
+#[account]
+pub struct PrimaryListing {
+    /// The versioned account header.
+    pub header: AccountHeader,
+    /// The mint address of the listed NFT.
+    pub nft_mint: Pubkey,
+    /// The currency the price is denominated in.
+    pub currency: SaleCurrency,
+    /// The price in lamports or USDC base units.
+    pub price: u64,
+    /// The timestamp when the NFT was listed.
+    pub listed_at: i64,
+}
+
+impl PrimaryListing {
+    /// The current layout version
+    pub const VERSION: u8 = 1;
+
+    /// Calculates the initial space required for the PrimaryListing account
+    pub const INIT_SPACE: usize = 8 +    // discriminator
+        AccountHeader::SPACE + // header
+        32 +    // nft_mint
+        1 +     // currency
+        8 +     // price
+        8; // listed_at
+}
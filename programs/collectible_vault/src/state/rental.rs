@@ -0,0 +1,59 @@
+use {super::AccountHeader, anchor_lang::prelude::*};
+
+/**
+ * Stores the rental terms of an NFT held in rental escrow, and the renter holding its usage rights.
+ * The NFT is held by this account's token account and stays the owner's throughout. The renter holds
+ * a usage token for the term instead, and integrations needing the exact term use `renter_at(now)`.
+ */
+#[account]
+pub struct Rental {
+    /// The versioned account header.
+    pub header: AccountHeader,
+    /// The mint address of the NFT listed for rent.
+    pub nft_mint: Pubkey,
+    /// The public key of the owner, who receives the rental fees and gets the NFT back.
+    pub owner: Pubkey,
+    /// The rental fee per day in lamports.
+    pub daily_fee: u64,
+    /// The longest rental term the owner accepts, in days.
+    pub max_days: u16,
+    /// The public key of the renter (None until the NFT is rented).
+    pub renter: Option<Pubkey>,
+    /// The timestamp when the rental term ends (None until the NFT is rented).
+    pub rental_end: Option<i64>,
+    /// The timestamp when the NFT was listed for rent.
+    pub listed_at: i64,
+}
+
+impl Rental {
+    /// The current layout version
+    pub const VERSION: u8 = 1;
+
+    /// The length of one rental day, in seconds
+    pub const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+    /// Calculates the initial space required for the Rental account
+    pub const INIT_SPACE: usize = 8 +    // discriminator
+        AccountHeader::SPACE + // header
+        32 +    // nft_mint
+        32 +    // owner
+        8 +     // daily_fee
+        2 +     // max_days
+        33 +    // renter (Option)
+        9 +     // rental_end (Option)
+        8; // listed_at
+
+    /// Returns the renter holding the NFT's usage rights at `now`, or None if it is not rented or
+    /// the term has ended.
+    pub fn renter_at(&self, now: i64) -> Option<Pubkey> {
+        match self.rental_end {
+            Some(end) if now < end => self.renter,
+            _ => None,
+        }
+    }
+
+    /// Returns true once a rental term has ended and the NFT can be returned to its owner.
+    pub fn has_ended(&self, now: i64) -> bool {
+        matches!(self.rental_end, Some(end) if now >= end)
+    }
+}
@@ -0,0 +1,57 @@
+use {super::AccountHeader, anchor_lang::prelude::*};
+
+/**
+ * Program-wide settings managed by the admin.
+ * Until the account is created every setting takes its default value.
+ */
+#[account]
+pub struct VaultConfig {
+    /// The versioned account header
+    pub header: AccountHeader,
+    /// Whether lenders claiming defaulted collateral pay the NFT's Metaplex royalty to its creators
+    pub default_royalties_enabled: bool,
+    /// The wallet receiving primary sale proceeds (the default pubkey until the admin sets it)
+    pub treasury: Pubkey,
+    /// The share of SOL primary sale proceeds and rental fees paid into the insurance reserve, in basis points
+    pub insurance_fee_bps: u16,
+    /// Reserved space for future settings, zeroed on creation
+    pub reserved: [u8; 30],
+}
+
+impl VaultConfig {
+    /// The current layout version
+    pub const VERSION: u8 = 1;
+
+    /// The highest insurance fee the admin can set, in basis points
+    pub const MAX_INSURANCE_FEE_BPS: u16 = 1_000;
+
+    /// Calculates the initial space required for the VaultConfig account
+    pub const INIT_SPACE: usize = 8 +    // discriminator
+        AccountHeader::SPACE + // header
+        1 +     // default_royalties_enabled (bool)
+        32 +    // treasury
+        2 +     // insurance_fee_bps
+        30; // reserved
+
+    /**
+     * Reads the config from its PDA, returning the defaults while the account has not been created.
+     * The caller must have checked the account's address.
+     */
+    pub fn load(account: &AccountInfo) -> Result<Self> {
+        if account.owner != &crate::ID || account.data_is_empty() {
+            return Ok(Self {
+                header: AccountHeader::new(Self::VERSION),
+                default_royalties_enabled: false,
+                treasury: Pubkey::default(),
+                insurance_fee_bps: 0,
+                reserved: [0; 30],
+            });
+        }
+        Self::try_deserialize(&mut &account.try_borrow_data()?[..])
+    }
+
+    /// Returns the insurance reserve's share of `amount`, rounded down.
+    pub fn insurance_share(&self, amount: u64) -> u64 {
+        (amount as u128 * self.insurance_fee_bps as u128 / 10_000) as u64
+    }
+}
@@ -0,0 +1,449 @@
+/**
+ * Admin operation tests for collection creation, NFT minting and account migration.
+ *
+ * Both instructions finish with CPIs into the Metaplex Token Metadata program, which the happy
+ * path tests run with the harness's Token Metadata stand-in. The failure path tests leave it
+ * unloaded, so a Token Metadata CPI fails and must roll the whole instruction back.
+ */
+mod common;
+
+use {
+    anchor_lang::{
+        prelude::{Pubkey, Rent},
+        solana_program::instruction::InstructionError,
+    },
+    collectible_vault::{
+        constants::admin_constants::get_admin_account_pubkey,
+        errors::ErrorCode,
+        events::{AccountMigrated, DefaultRoyaltiesConfigured},
+        instructions::nft_transfer::NftStandard,
+        state::{
+            AccountHeader, AssetRedemptionInfo, BorrowerProfile, CollectionCounter,
+            LegacyAssetRedemptionInfo, LegacyCollectionCounter, LoanCounter, LoanOutcome,
+            LoanRecord, VaultConfig,
+        },
+    },
+    collectible_vault_client::{instructions, pda},
+    common::{
+        assert_instruction_error, assert_program_error, events, Harness, StoredAccount,
+        TransactionError,
+    },
+    mpl_token_metadata::{
+        accounts::TokenRecord,
+        types::{Collection, CollectionDetails, ProgrammableConfig, TokenStandard, TokenState},
+    },
+    spl_associated_token_account::get_associated_token_address,
+};
+
+#[test]
+fn create_collection_is_admin_only() {
+    let mut harness = Harness::new();
+    let payer = harness.new_wallet();
+    let mint = Pubkey::new_unique();
+
+    let result = harness.process_transaction(
+        &[instructions::create_collection(&payer, &mint)],
+        &[payer, mint],
+    );
+    assert_program_error(result, ErrorCode::UnauthorizedTransactionSigner);
+    assert!(harness.account(&pda::collection_counter().0).is_none());
+}
+
+#[test]
+fn mint_nft_is_admin_only() {
+    let mut harness = Harness::new();
+    let payer = harness.new_wallet();
+    let owner = harness.new_wallet();
+    let collection_mint = harness.create_nft(&owner);
+    harness.set_collection_counter(&collection_mint, 0);
+    let mint = Pubkey::new_unique();
+
+    let result = harness.process_transaction(
+        &[instructions::mint_nft(
+            &payer,
+            &mint,
+            &owner,
+            &collection_mint,
+            "https://example.com/item".to_string(),
+            NftStandard::NonFungible,
+        )],
+        &[payer, mint],
+    );
+    assert_program_error(result, ErrorCode::UnauthorizedTransactionSigner);
+}
+
+#[test]
+fn mint_nft_rejects_a_foreign_collection() {
+    let mut harness = Harness::new();
+    let admin = get_admin_account_pubkey();
+    harness.airdrop(&admin, 10_000_000_000);
+    let owner = harness.new_wallet();
+    let collection_mint = harness.create_nft(&admin);
+    let foreign_collection_mint = harness.create_nft(&admin);
+    harness.set_collection_counter(&collection_mint, 0);
+    let mint = Pubkey::new_unique();
+
+    let result = harness.process_transaction(
+        &[instructions::mint_nft(
+            &admin,
+            &mint,
+            &owner,
+            &foreign_collection_mint,
+            "https://example.com/item".to_string(),
+            NftStandard::NonFungible,
+        )],
+        &[admin, mint],
+    );
+    assert_program_error(result, ErrorCode::CollectionMintDoesNotMatch);
+}
+
+#[test]
+fn mint_nft_rolls_back_when_metadata_creation_fails() {
+    let mut harness = Harness::new();
+    let admin = get_admin_account_pubkey();
+    harness.airdrop(&admin, 10_000_000_000);
+    let owner = harness.new_wallet();
+    let collection_mint = harness.create_nft(&admin);
+    harness.set_collection_counter(&collection_mint, 3);
+    let mint = Pubkey::new_unique();
+
+    // Everything up to the Metaplex CPI succeeds; its failure must undo the counter increment
+    let result = harness.process_transaction(
+        &[instructions::mint_nft(
+            &admin,
+            &mint,
+            &owner,
+            &collection_mint,
+            "https://example.com/item".to_string(),
+            NftStandard::NonFungible,
+        )],
+        &[admin, mint],
+    );
+    assert_instruction_error(result, InstructionError::IncorrectProgramId);
+
+    let counter: CollectionCounter = harness.anchor_account(&pda::collection_counter().0);
+    assert_eq!(counter.count, 3);
+    assert!(harness.account(&mint).is_none());
+}
+
+#[test]
+fn create_collection_creates_a_sized_collection() {
+    let mut harness = Harness::new();
+    harness.load_token_metadata();
+    let admin = get_admin_account_pubkey();
+
+    let collection_mint = harness.create_collection();
+
+    let counter: CollectionCounter = harness.anchor_account(&pda::collection_counter().0);
+    assert_eq!(counter.collection_mint, collection_mint);
+    assert_eq!(counter.count, 0);
+    let metadata = harness.token_metadata(&collection_mint);
+    assert_eq!(metadata.update_authority, admin);
+    assert_eq!(
+        metadata.collection_details,
+        Some(CollectionDetails::V1 { size: 0 })
+    );
+    assert_eq!(metadata.token_standard, Some(TokenStandard::NonFungible));
+    assert!(metadata.creators.unwrap()[0].verified);
+    assert!(harness
+        .account(&pda::master_edition(&collection_mint).0)
+        .is_some());
+    assert_eq!(
+        harness.token_balance(&get_associated_token_address(&admin, &collection_mint)),
+        1
+    );
+}
+
+#[test]
+fn mint_nft_mints_a_verified_member_of_the_collection() {
+    let mut harness = Harness::new();
+    harness.load_token_metadata();
+    let owner = harness.new_wallet();
+    let collection_mint = harness.create_collection();
+
+    let nft_mint = harness.mint_nft(&owner, NftStandard::NonFungible);
+
+    let metadata = harness.token_metadata(&nft_mint);
+    assert_eq!(metadata.name, "Ascendry Item #1");
+    assert_eq!(metadata.seller_fee_basis_points, 500);
+    assert_eq!(metadata.token_standard, Some(TokenStandard::NonFungible));
+    assert_eq!(
+        metadata.collection,
+        Some(Collection {
+            verified: true,
+            key: collection_mint,
+        })
+    );
+    let owner_account = get_associated_token_address(&owner, &nft_mint);
+    assert_eq!(harness.token_balance(&owner_account), 1);
+    assert!(!harness.is_frozen(&owner_account));
+    assert!(harness.account(&pda::master_edition(&nft_mint).0).is_some());
+
+    let counter: CollectionCounter = harness.anchor_account(&pda::collection_counter().0);
+    assert_eq!(counter.count, 1);
+    assert_eq!(
+        harness.token_metadata(&collection_mint).collection_details,
+        Some(CollectionDetails::V1 { size: 1 })
+    );
+}
+
+#[test]
+fn mint_nft_mints_frozen_programmable_nfts_with_a_token_record() {
+    let mut harness = Harness::new();
+    harness.load_token_metadata();
+    let owner = harness.new_wallet();
+    let collection_mint = harness.create_collection();
+    let rule_set = Pubkey::new_unique();
+
+    let nft_mint = harness.mint_nft(
+        &owner,
+        NftStandard::ProgrammableNonFungible {
+            rule_set: Some(rule_set),
+        },
+    );
+
+    let metadata = harness.token_metadata(&nft_mint);
+    assert_eq!(
+        metadata.token_standard,
+        Some(TokenStandard::ProgrammableNonFungible)
+    );
+    assert_eq!(
+        metadata.programmable_config,
+        Some(ProgrammableConfig::V1 {
+            rule_set: Some(rule_set),
+        })
+    );
+    assert_eq!(
+        metadata.collection,
+        Some(Collection {
+            verified: true,
+            key: collection_mint,
+        })
+    );
+
+    // Token Metadata keeps the NFT frozen and tracks it with a token record
+    let owner_account = get_associated_token_address(&owner, &nft_mint);
+    assert_eq!(harness.token_balance(&owner_account), 1);
+    assert!(harness.is_frozen(&owner_account));
+    let token_record = harness
+        .account(&pda::token_record(&nft_mint, &owner_account).0)
+        .unwrap();
+    assert_eq!(
+        TokenRecord::from_bytes(&token_record.data).unwrap().state,
+        TokenState::Unlocked
+    );
+}
+
+#[test]
+fn set_default_royalties_is_admin_only() {
+    let mut harness = Harness::new();
+    let payer = harness.new_wallet();
+
+    let result = harness.process_transaction(
+        &[instructions::set_default_royalties(&payer, true)],
+        &[payer],
+    );
+    assert_program_error(result, ErrorCode::UnauthorizedTransactionSigner);
+    assert!(harness.account(&pda::vault_config().0).is_none());
+}
+
+#[test]
+fn set_default_royalties_creates_and_updates_the_vault_config() {
+    let mut harness = Harness::new();
+    let admin = get_admin_account_pubkey();
+    harness.airdrop(&admin, 1_000_000_000);
+
+    let logs = harness
+        .process_transaction(
+            &[instructions::set_default_royalties(&admin, true)],
+            &[admin],
+        )
+        .unwrap();
+    let config: VaultConfig = harness.anchor_account(&pda::vault_config().0);
+    assert!(config.default_royalties_enabled);
+    assert_eq!(config.header.version, VaultConfig::VERSION);
+    let configured = &events::<DefaultRoyaltiesConfigured>(&logs)[0];
+    assert!(configured.enabled);
+    assert_eq!(configured.admin, admin);
+
+    harness
+        .process_transaction(
+            &[instructions::set_default_royalties(&admin, false)],
+            &[admin],
+        )
+        .unwrap();
+    let config: VaultConfig = harness.anchor_account(&pda::vault_config().0);
+    assert!(!config.default_royalties_enabled);
+}
+
+/// Runs `migrate_account` for a single account as the admin
+fn migrate(harness: &mut Harness, account: &Pubkey) -> Result<Vec<String>, TransactionError> {
+    let admin = get_admin_account_pubkey();
+    harness.airdrop(&admin, 1_000_000_000);
+    harness.process_transaction(&[instructions::migrate_account(&admin, account)], &[admin])
+}
+
+#[test]
+fn migrate_account_upgrades_every_legacy_state_account() {
+    let mut harness = Harness::new();
+    let nft_mint = Pubkey::new_unique();
+    let borrower = Pubkey::new_unique();
+
+    let collection_counter = Pubkey::new_unique();
+    harness.set_legacy_account::<CollectionCounter>(
+        collection_counter,
+        &LegacyCollectionCounter {
+            collection_mint: nft_mint,
+            count: 12,
+        },
+        LegacyCollectionCounter::SPACE,
+    );
+    let asset_redemption_info = Pubkey::new_unique();
+    harness.set_legacy_account::<AssetRedemptionInfo>(
+        asset_redemption_info,
+        &LegacyAssetRedemptionInfo {
+            nft_mint,
+            nft_owner: borrower,
+            request_timestamp: harness.now(),
+            is_fulfilled: true,
+        },
+        LegacyAssetRedemptionInfo::SPACE,
+    );
+
+    for account in [collection_counter, asset_redemption_info] {
+        let logs = migrate(&mut harness, &account).unwrap();
+        let migrated = events::<AccountMigrated>(&logs);
+        assert_eq!(migrated[0].account, account);
+        assert_eq!(migrated[0].from_version, 0);
+        assert_eq!(migrated[0].to_version, 1);
+
+        let stored = harness.account(&account).unwrap();
+        assert_eq!(
+            stored.lamports,
+            Rent::default().minimum_balance(stored.data.len())
+        );
+    }
+
+    let counter: CollectionCounter = harness.anchor_account(&collection_counter);
+    assert_eq!(counter.header.version, CollectionCounter::VERSION);
+    assert_eq!((counter.collection_mint, counter.count), (nft_mint, 12));
+    assert_eq!(
+        harness.account(&collection_counter).unwrap().data.len(),
+        CollectionCounter::INIT_SPACE
+    );
+
+    let info: AssetRedemptionInfo = harness.anchor_account(&asset_redemption_info);
+    assert_eq!(info.header.version, AssetRedemptionInfo::VERSION);
+    assert_eq!(info.nft_owner, borrower);
+    assert!(info.is_fulfilled);
+}
+
+#[test]
+fn migrate_account_is_admin_only() {
+    let mut harness = Harness::new();
+    let payer = harness.new_wallet();
+    let account = Pubkey::new_unique();
+    harness.set_legacy_account::<CollectionCounter>(
+        account,
+        &LegacyCollectionCounter {
+            collection_mint: Pubkey::new_unique(),
+            count: 1,
+        },
+        LegacyCollectionCounter::SPACE,
+    );
+
+    let result =
+        harness.process_transaction(&[instructions::migrate_account(&payer, &account)], &[payer]);
+    assert_program_error(result, ErrorCode::UnauthorizedTransactionSigner);
+}
+
+#[test]
+fn migrate_account_rejects_a_current_account() {
+    let mut harness = Harness::new();
+    let collection_mint = Pubkey::new_unique();
+    harness.set_collection_counter(&collection_mint, 5);
+
+    let result = migrate(&mut harness, &pda::collection_counter().0);
+    assert_program_error(result, ErrorCode::AccountAlreadyMigrated);
+}
+
+#[test]
+fn migrate_account_rejects_an_unknown_version() {
+    let mut harness = Harness::new();
+    let collection_mint = Pubkey::new_unique();
+    harness.set_collection_counter(&collection_mint, 5);
+    let key = pda::collection_counter().0;
+    let mut account = harness.account(&key).unwrap().clone();
+    account.data[8] = CollectionCounter::VERSION + 1;
+    harness.set_account(key, account);
+
+    let result = migrate(&mut harness, &key);
+    assert_program_error(result, ErrorCode::UnsupportedAccountVersion);
+}
+
+#[test]
+fn migrate_account_rejects_an_unknown_account_type() {
+    let mut harness = Harness::new();
+    let key = Pubkey::new_unique();
+    harness.set_account(
+        key,
+        StoredAccount {
+            lamports: Rent::default().minimum_balance(16),
+            data: vec![7; 16],
+            owner: collectible_vault::ID,
+            executable: false,
+        },
+    );
+
+    let result = migrate(&mut harness, &key);
+    assert_program_error(result, ErrorCode::UnsupportedAccountType);
+}
+
+#[test]
+fn migrate_account_rejects_account_types_introduced_with_the_header() {
+    let mut harness = Harness::new();
+    let nft_mint = Pubkey::new_unique();
+    let borrower = Pubkey::new_unique();
+
+    // These accounts never existed without the header, so they have no older layout to upgrade
+    let loan_counter = Pubkey::new_unique();
+    harness.set_program_account(
+        loan_counter,
+        &LoanCounter {
+            header: AccountHeader::new(LoanCounter::VERSION),
+            nft_mint,
+            count: 3,
+        },
+    );
+    let borrower_profile = Pubkey::new_unique();
+    harness.set_program_account(
+        borrower_profile,
+        &BorrowerProfile {
+            header: AccountHeader::new(BorrowerProfile::VERSION),
+            borrower,
+            ..BorrowerProfile::default()
+        },
+    );
+    let loan_record = Pubkey::new_unique();
+    harness.set_program_account(
+        loan_record,
+        &LoanRecord {
+            header: AccountHeader::new(LoanRecord::VERSION),
+            nft_mint,
+            loan_id: 1,
+            borrower,
+            lender: None,
+            loan_amount: 1_000,
+            interest_amount: 50,
+            duration: 3_600,
+            start_time: None,
+            closed_at: harness.now(),
+            outcome: LoanOutcome::Cancelled,
+        },
+    );
+
+    for account in [loan_counter, borrower_profile, loan_record] {
+        let result = migrate(&mut harness, &account);
+        assert_program_error(result, ErrorCode::UnsupportedAccountType);
+    }
+}
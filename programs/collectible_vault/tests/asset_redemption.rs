@@ -0,0 +1,210 @@
+/**
+ * Asset redemption tests: requesting, cancelling and fulfilling redemptions of an NFT for its
+ * physical asset.
+ *
+ * `RedemptionRequestNotActive` is never raised and is not covered.
+ */
+mod common;
+
+use {
+    anchor_lang::prelude::Pubkey,
+    collectible_vault::{
+        constants::admin_constants::get_admin_account_pubkey,
+        errors::ErrorCode,
+        events::{RedemptionCancelled, RedemptionFulfilled, RedemptionRequested},
+        instructions::nft_transfer::NftStandard,
+        state::AssetRedemptionInfo,
+    },
+    collectible_vault_client::{instructions, pda},
+    common::{assert_program_error, events, replace_account, Harness},
+    spl_associated_token_account::get_associated_token_address,
+};
+
+fn redemption_nft_account(nft_mint: &Pubkey) -> Pubkey {
+    get_associated_token_address(&pda::asset_redemption_vault().0, nft_mint)
+}
+
+/// Creates an NFT and files a redemption request for it, returning the owner and the mint
+fn request_redemption(harness: &mut Harness) -> (Pubkey, Pubkey) {
+    let owner = harness.new_wallet();
+    let nft_mint = harness.create_nft(&owner);
+    harness
+        .process_transaction(
+            &[instructions::create_asset_redemption_request(
+                &owner,
+                &nft_mint,
+                NftStandard::NonFungible,
+                &spl_token::ID,
+            )],
+            &[owner],
+        )
+        .unwrap();
+    (owner, nft_mint)
+}
+
+#[test]
+fn create_asset_redemption_request_escrows_the_nft() {
+    let mut harness = Harness::new();
+    let owner = harness.new_wallet();
+    let nft_mint = harness.create_nft(&owner);
+
+    let logs = harness
+        .process_transaction(
+            &[instructions::create_asset_redemption_request(
+                &owner,
+                &nft_mint,
+                NftStandard::NonFungible,
+                &spl_token::ID,
+            )],
+            &[owner],
+        )
+        .unwrap();
+
+    let info: AssetRedemptionInfo =
+        harness.anchor_account(&pda::asset_redemption_info(&nft_mint).0);
+    assert_eq!(info.nft_owner, owner);
+    assert_eq!(info.request_timestamp, harness.now());
+    assert!(!info.is_fulfilled);
+    assert_eq!(harness.token_balance(&redemption_nft_account(&nft_mint)), 1);
+
+    let requested = events::<RedemptionRequested>(&logs);
+    assert_eq!(requested.len(), 1);
+    assert_eq!(requested[0].owner, owner);
+}
+
+#[test]
+fn create_asset_redemption_request_requires_the_nft() {
+    let mut harness = Harness::new();
+    let owner = harness.new_wallet();
+    let nft_mint = harness.create_nft(&owner);
+    let other = harness.new_wallet();
+    harness.transfer_tokens(&owner, &other, &nft_mint, 1);
+
+    let result = harness.process_transaction(
+        &[instructions::create_asset_redemption_request(
+            &owner,
+            &nft_mint,
+            NftStandard::NonFungible,
+            &spl_token::ID,
+        )],
+        &[owner],
+    );
+    assert_program_error(result, ErrorCode::AssociatedTokenAccountHasNoTokenBalance);
+}
+
+#[test]
+fn cancel_asset_redemption_request_returns_the_nft() {
+    let mut harness = Harness::new();
+    let (owner, nft_mint) = request_redemption(&mut harness);
+
+    let logs = harness
+        .process_transaction(
+            &[instructions::cancel_asset_redemption_request(
+                &owner,
+                &nft_mint,
+                NftStandard::NonFungible,
+                &spl_token::ID,
+            )],
+            &[owner],
+        )
+        .unwrap();
+
+    assert!(harness
+        .account(&pda::asset_redemption_info(&nft_mint).0)
+        .is_none());
+    assert!(harness
+        .account(&redemption_nft_account(&nft_mint))
+        .is_none());
+    assert_eq!(
+        harness.token_balance(&get_associated_token_address(&owner, &nft_mint)),
+        1
+    );
+    assert_eq!(events::<RedemptionCancelled>(&logs)[0].owner, owner);
+}
+
+#[test]
+fn cancel_asset_redemption_request_rejects_anyone_but_the_owner() {
+    let mut harness = Harness::new();
+    let (owner, nft_mint) = request_redemption(&mut harness);
+    let stranger = harness.new_wallet();
+
+    let mut cancel = instructions::cancel_asset_redemption_request(
+        &stranger,
+        &nft_mint,
+        NftStandard::NonFungible,
+        &spl_token::ID,
+    );
+    // Keep the owner's token account so only the ownership check can fail
+    replace_account(
+        &mut cancel,
+        &get_associated_token_address(&stranger, &nft_mint),
+        &get_associated_token_address(&owner, &nft_mint),
+    );
+
+    let result = harness.process_transaction(&[cancel], &[stranger]);
+    assert_program_error(result, ErrorCode::UnauthorizedRedemptionRequest);
+}
+
+#[test]
+fn cancel_asset_redemption_request_rejects_a_fulfilled_request() {
+    let mut harness = Harness::new();
+    let (owner, nft_mint) = request_redemption(&mut harness);
+    let admin = get_admin_account_pubkey();
+    harness
+        .process_transaction(
+            &[instructions::fulfill_asset_redemption_request(
+                &admin, &nft_mint,
+            )],
+            &[admin],
+        )
+        .unwrap();
+
+    let result = harness.process_transaction(
+        &[instructions::cancel_asset_redemption_request(
+            &owner,
+            &nft_mint,
+            NftStandard::NonFungible,
+            &spl_token::ID,
+        )],
+        &[owner],
+    );
+    assert_program_error(result, ErrorCode::RedemptionRequestAlreadyFulfilled);
+}
+
+#[test]
+fn fulfill_asset_redemption_request_marks_the_request_fulfilled() {
+    let mut harness = Harness::new();
+    let (owner, nft_mint) = request_redemption(&mut harness);
+    let admin = get_admin_account_pubkey();
+    let fulfill = instructions::fulfill_asset_redemption_request(&admin, &nft_mint);
+
+    let logs = harness
+        .process_transaction(std::slice::from_ref(&fulfill), &[admin])
+        .unwrap();
+
+    let info: AssetRedemptionInfo =
+        harness.anchor_account(&pda::asset_redemption_info(&nft_mint).0);
+    assert!(info.is_fulfilled);
+    // The NFT stays in the vault for good
+    assert_eq!(harness.token_balance(&redemption_nft_account(&nft_mint)), 1);
+    let fulfilled = events::<RedemptionFulfilled>(&logs);
+    assert_eq!(fulfilled[0].owner, owner);
+    assert_eq!(fulfilled[0].admin, admin);
+
+    let result = harness.process_transaction(&[fulfill], &[admin]);
+    assert_program_error(result, ErrorCode::RedemptionRequestAlreadyFulfilled);
+}
+
+#[test]
+fn fulfill_asset_redemption_request_is_admin_only() {
+    let mut harness = Harness::new();
+    let (owner, nft_mint) = request_redemption(&mut harness);
+
+    let result = harness.process_transaction(
+        &[instructions::fulfill_asset_redemption_request(
+            &owner, &nft_mint,
+        )],
+        &[owner],
+    );
+    assert_program_error(result, ErrorCode::UnauthorizedRedemptionRequest);
+}
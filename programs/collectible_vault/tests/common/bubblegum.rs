@@ -0,0 +1,257 @@
+/**
+ * A native stand-in for the Metaplex Bubblegum program, enabled with `Harness::load_bubblegum`.
+ *
+ * It runs the instructions the vault invokes, encoded as in the published Bubblegum client, and
+ * hashes leaves the way Bubblegum does. Instead of account compression's concurrent Merkle tree,
+ * the tree account holds the Borsh-encoded `(index, leaf)` list of its leaves, from which the root
+ * and proofs of a fixed-depth tree are computed, so transfers still verify the caller's proof
+ * against the current root. Collections are verified against their Token Metadata accounts, but a
+ * sized collection's size is left as is.
+ */
+use {
+    anchor_lang::{
+        prelude::Pubkey,
+        solana_program::{
+            account_info::AccountInfo, entrypoint::ProgramResult, keccak,
+            program_error::ProgramError,
+        },
+        AnchorDeserialize, AnchorSerialize,
+    },
+    mpl_bubblegum::{
+        accounts::TreeConfig,
+        hash::{hash_creators, hash_metadata},
+        instructions::{MintToCollectionV1InstructionArgs, TransferInstructionArgs},
+        types::{Collection, LeafSchema},
+        utils::get_asset_id,
+    },
+    mpl_token_metadata::accounts::{MasterEdition, Metadata},
+};
+
+const MINT_TO_COLLECTION_V1: [u8; 8] = [153, 18, 178, 47, 197, 158, 86, 15];
+const TRANSFER: [u8; 8] = [163, 52, 200, 231, 140, 3, 69, 186];
+
+/// Depth of every harness tree, and so the number of proof nodes a leaf needs
+pub(super) const TREE_DEPTH: usize = 5;
+
+/// The leaves stored in a harness Merkle tree account, by index.
+pub(super) fn tree_leaves(data: &[u8]) -> Vec<(u32, LeafSchema)> {
+    Vec::<(u32, LeafSchema)>::deserialize(&mut &data[..]).expect("invalid harness Merkle tree")
+}
+
+/// Returns the hashes of every level of the tree, from the leaves up to the root.
+fn tree_levels(leaves: &[(u32, LeafSchema)]) -> Vec<Vec<[u8; 32]>> {
+    let mut level = vec![[0; 32]; 1 << TREE_DEPTH];
+    for (index, leaf) in leaves {
+        level[*index as usize] = leaf.hash();
+    }
+    let mut levels = vec![level];
+    while levels.last().unwrap().len() > 1 {
+        let parents = levels
+            .last()
+            .unwrap()
+            .chunks(2)
+            .map(|pair| keccak::hashv(&[&pair[0], &pair[1]]).to_bytes())
+            .collect();
+        levels.push(parents);
+    }
+    levels
+}
+
+/// Returns the root of the tree and the proof of the leaf at `index`, from its sibling upwards.
+pub(super) fn tree_proof(leaves: &[(u32, LeafSchema)], index: u32) -> ([u8; 32], Vec<[u8; 32]>) {
+    let levels = tree_levels(leaves);
+    let proof = levels[..TREE_DEPTH]
+        .iter()
+        .enumerate()
+        .map(|(depth, level)| level[(index as usize >> depth) ^ 1])
+        .collect();
+    (levels[TREE_DEPTH][0], proof)
+}
+
+fn account<'a, 'info>(
+    accounts: &'a [AccountInfo<'info>],
+    index: usize,
+) -> Result<&'a AccountInfo<'info>, ProgramError> {
+    accounts
+        .get(index)
+        .ok_or(ProgramError::NotEnoughAccountKeys)
+}
+
+fn require(condition: bool) -> ProgramResult {
+    if condition {
+        Ok(())
+    } else {
+        Err(ProgramError::InvalidArgument)
+    }
+}
+
+/// Reads the tree config of `merkle_tree`, which must be stored at its PDA.
+fn read_tree_config(info: &AccountInfo, merkle_tree: &Pubkey) -> Result<TreeConfig, ProgramError> {
+    require(*info.owner == mpl_bubblegum::ID && *info.key == TreeConfig::find_pda(merkle_tree).0)?;
+    TreeConfig::from_bytes(&info.try_borrow_data()?).map_err(|_| ProgramError::InvalidAccountData)
+}
+
+/// Stores the leaves of a harness Merkle tree, growing the account when needed.
+fn write_leaves(info: &AccountInfo, leaves: &[(u32, LeafSchema)]) -> ProgramResult {
+    let bytes = leaves.to_vec().try_to_vec().unwrap();
+    if bytes.len() > info.data_len() {
+        info.realloc(bytes.len(), false)?;
+    }
+    let mut data = info.try_borrow_mut_data()?;
+    data.fill(0);
+    data[..bytes.len()].copy_from_slice(&bytes);
+    Ok(())
+}
+
+fn read_token_metadata<T: AnchorDeserialize>(info: &AccountInfo) -> Result<T, ProgramError> {
+    require(*info.owner == mpl_token_metadata::ID)?;
+    T::deserialize(&mut &info.try_borrow_data()?[..]).map_err(|_| ProgramError::InvalidAccountData)
+}
+
+pub(super) fn process_bubblegum_instruction(
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    if data.len() < 8 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let (discriminator, mut args) = data.split_at(8);
+    let account = |index: usize| account(accounts, index);
+
+    match <[u8; 8]>::try_from(discriminator).unwrap() {
+        MINT_TO_COLLECTION_V1 => {
+            // Accounts: tree config, leaf owner, leaf delegate, Merkle tree, payer, tree creator
+            // or delegate, collection authority, collection authority record, collection mint,
+            // collection metadata, collection edition, Bubblegum signer, ...
+            let (tree_config_info, leaf_owner, leaf_delegate, merkle_tree, payer) = (
+                account(0)?,
+                account(1)?,
+                account(2)?,
+                account(3)?,
+                account(4)?,
+            );
+            let (tree_authority, collection_authority) = (account(5)?, account(6)?);
+            let (collection_mint, collection_metadata, collection_edition, bubblegum_signer) =
+                (account(8)?, account(9)?, account(10)?, account(11)?);
+            require(payer.is_signer && tree_authority.is_signer && collection_authority.is_signer)
+                .map_err(|_| ProgramError::MissingRequiredSignature)?;
+            let MintToCollectionV1InstructionArgs { mut metadata } =
+                MintToCollectionV1InstructionArgs::deserialize(&mut args)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+            let mut tree_config = read_tree_config(tree_config_info, merkle_tree.key)?;
+            require(
+                tree_config.is_public
+                    || *tree_authority.key == tree_config.tree_creator
+                    || *tree_authority.key == tree_config.tree_delegate,
+            )?;
+            require(tree_config.num_minted < tree_config.total_mint_capacity)?;
+            require(tree_config.num_minted < 1 << TREE_DEPTH)?;
+            require(
+                *bubblegum_signer.key
+                    == Pubkey::find_program_address(&[b"collection_cpi"], &mpl_bubblegum::ID).0,
+            )?;
+
+            // The collection authority must be the update authority of a master edition collection
+            require(*collection_metadata.key == Metadata::find_pda(collection_mint.key).0)?;
+            let collection: Metadata = read_token_metadata(collection_metadata)?;
+            require(
+                collection.mint == *collection_mint.key
+                    && collection.update_authority == *collection_authority.key,
+            )?;
+            require(*collection_edition.key == MasterEdition::find_pda(collection_mint.key).0)?;
+            read_token_metadata::<MasterEdition>(collection_edition)?;
+            require(
+                metadata.collection
+                    == Some(Collection {
+                        verified: false,
+                        key: *collection_mint.key,
+                    }),
+            )?;
+            metadata.collection = Some(Collection {
+                verified: true,
+                key: *collection_mint.key,
+            });
+            for creator in &metadata.creators {
+                require(!creator.verified || creator.address == *payer.key)?;
+            }
+
+            let nonce = tree_config.num_minted;
+            let leaf = LeafSchema::V1 {
+                id: get_asset_id(merkle_tree.key, nonce),
+                owner: *leaf_owner.key,
+                delegate: *leaf_delegate.key,
+                nonce,
+                data_hash: hash_metadata(&metadata)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?,
+                creator_hash: hash_creators(&metadata.creators),
+            };
+            let mut leaves = tree_leaves(&merkle_tree.try_borrow_data()?);
+            leaves.push((nonce as u32, leaf));
+            write_leaves(merkle_tree, &leaves)?;
+
+            tree_config.num_minted += 1;
+            let data = tree_config.try_to_vec().unwrap();
+            tree_config_info.try_borrow_mut_data()?[..data.len()].copy_from_slice(&data);
+            Ok(())
+        }
+        TRANSFER => {
+            // Accounts: tree config, leaf owner, leaf delegate, new leaf owner, Merkle tree,
+            // log wrapper, compression program, system program, proof nodes
+            let (tree_config_info, leaf_owner, leaf_delegate, new_leaf_owner, merkle_tree) = (
+                account(0)?,
+                account(1)?,
+                account(2)?,
+                account(3)?,
+                account(4)?,
+            );
+            let proof = accounts.get(8..).unwrap_or_default();
+            require(leaf_owner.is_signer || leaf_delegate.is_signer)
+                .map_err(|_| ProgramError::MissingRequiredSignature)?;
+            let TransferInstructionArgs {
+                root,
+                data_hash,
+                creator_hash,
+                nonce,
+                index,
+            } = TransferInstructionArgs::deserialize(&mut args)
+                .map_err(|_| ProgramError::InvalidInstructionData)?;
+            read_tree_config(tree_config_info, merkle_tree.key)?;
+
+            // The leaf the caller describes must be in the tree, proven against the current root
+            let mut leaves = tree_leaves(&merkle_tree.try_borrow_data()?);
+            let leaf = LeafSchema::V1 {
+                id: get_asset_id(merkle_tree.key, nonce),
+                owner: *leaf_owner.key,
+                delegate: *leaf_delegate.key,
+                nonce,
+                data_hash,
+                creator_hash,
+            };
+            let position = leaves
+                .iter()
+                .position(|(stored_index, stored)| *stored_index == index && *stored == leaf)
+                .ok_or(ProgramError::InvalidArgument)?;
+            let (current_root, expected_proof) = tree_proof(&leaves, index);
+            require(root == current_root)?;
+            require(
+                proof.len() == TREE_DEPTH
+                    && proof
+                        .iter()
+                        .zip(&expected_proof)
+                        .all(|(node, expected)| node.key.to_bytes() == *expected),
+            )?;
+
+            leaves[position].1 = LeafSchema::V1 {
+                id: leaf.id(),
+                owner: *new_leaf_owner.key,
+                delegate: *new_leaf_owner.key,
+                nonce,
+                data_hash,
+                creator_hash,
+            };
+            write_leaves(merkle_tree, &leaves)
+        }
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
@@ -0,0 +1,1811 @@
+#![allow(dead_code)]
+
+/**
+ * In-process test harness for the Collectible Vault program.
+ *
+ * Instructions are executed natively: accounts are serialized into the same input buffer layout
+ * the BPF loader uses, deserialized with `solana_program::entrypoint::deserialize` and handed to
+ * the program's `entry` function. Cross-program invocations, sysvars, logs and return data are
+ * served by a `SyscallStubs` implementation that routes CPIs to native builds of the System,
+ * SPL Token and Associated Token Account programs. Transactions are atomic: account changes are
+ * only committed when every instruction succeeds.
+ *
+ * The Metaplex Token Metadata, Bubblegum and Core programs are not available natively, so by
+ * default any CPI into them fails with `IncorrectProgramId`. A test opts into running them with
+ * `Harness::load_token_metadata`, `Harness::load_bubblegum` or `Harness::load_core`, which run
+ * the instructions the vault uses with native stand-ins.
+ */
+mod bubblegum;
+mod token_metadata;
+
+use {
+    self::{
+        bubblegum::{process_bubblegum_instruction, tree_leaves, tree_proof},
+        token_metadata::process_token_metadata_instruction,
+    },
+    anchor_lang::{
+        prelude::{borsh, Pubkey},
+        solana_program::{
+            account_info::AccountInfo,
+            clock::Clock,
+            entrypoint::{deserialize, ProgramResult, MAX_PERMITTED_DATA_INCREASE},
+            instruction::{AccountMeta, Instruction, InstructionError},
+            program_error::ProgramError,
+            program_pack::Pack,
+            program_stubs::{self, SyscallStubs},
+            rent::Rent,
+            system_instruction::{self, SystemInstruction},
+            system_program, sysvar,
+        },
+        AccountDeserialize, AccountSerialize, AnchorDeserialize, AnchorSerialize, Discriminator,
+        Event,
+    },
+    base64::{engine::general_purpose::STANDARD as BASE64, Engine},
+    collectible_vault::{
+        constants::{
+            admin_constants::get_admin_account_pubkey, payment_constants::get_usdc_mint_pubkey,
+        },
+        errors::ErrorCode,
+        instructions::{
+            compressed_nft::CompressedLeaf,
+            core_asset::{CorePluginAuthority, CorePluginType, MPL_CORE_PROGRAM_ID},
+            nft_transfer::NftStandard,
+        },
+        state::{AccountHeader, CollectionCounter},
+    },
+    mpl_bubblegum::{
+        accounts::TreeConfig,
+        programs::{SPL_ACCOUNT_COMPRESSION_ID, SPL_NOOP_ID},
+        types::DecompressibleState,
+    },
+    mpl_token_metadata::{
+        accounts::Metadata,
+        types::{Creator, Key, TokenStandard},
+    },
+    spl_associated_token_account::{
+        get_associated_token_address, get_associated_token_address_with_program_id,
+    },
+    spl_tlv_account_resolution::{account::ExtraAccountMeta, state::ExtraAccountMetaList},
+    spl_token_2022::extension::{
+        metadata_pointer, transfer_hook, ExtensionType, StateWithExtensions,
+    },
+    spl_transfer_hook_interface::{
+        get_extra_account_metas_address,
+        instruction::{ExecuteInstruction, TransferHookInstruction},
+    },
+    std::{
+        cell::RefCell,
+        collections::HashMap,
+        panic::{catch_unwind, AssertUnwindSafe},
+        sync::Once,
+    },
+};
+
+/// Unix timestamp every harness starts at
+pub const GENESIS_TIMESTAMP: i64 = 1_700_000_000;
+
+/// Lamports airdropped to wallets created through `Harness::new_wallet`
+pub const WALLET_LAMPORTS: u64 = 100 * 1_000_000_000;
+
+/// Native transfer hook program the harness runs for Token-2022 mints created with a hook
+pub const TRANSFER_HOOK_PROGRAM_ID: Pubkey = Pubkey::new_from_array([7; 32]);
+
+/// Extra account every harness transfer hook requires, resolved from its validation account
+pub const TRANSFER_HOOK_EXTRA_ACCOUNT: Pubkey = Pubkey::new_from_array([8; 32]);
+
+/// Width of the leading fields of a non-duplicate serialized account, up to the lamports
+const ACCOUNT_HEADER_LEN: usize = 1 + 1 + 1 + 1 + 4 + 32 + 32;
+
+/**
+ * An account stored by the harness between transactions.
+ */
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StoredAccount {
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub owner: Pubkey,
+    pub executable: bool,
+}
+
+/**
+ * A failed transaction: the index of the failing instruction, the runtime error, and the logs and
+ * cross-program invocations collected up to the failure, the failed invocation included.
+ */
+#[derive(Debug)]
+pub struct TransactionError {
+    pub instruction: usize,
+    pub error: InstructionError,
+    pub logs: Vec<String>,
+    pub invoked: Vec<Instruction>,
+}
+
+/**
+ * Token-2022 mint extensions `Harness::create_token_2022_nft` can initialize.
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MintExtension {
+    /// Points the mint's metadata at the mint itself
+    MetadataPointer,
+    /// Forbids transfers once the NFT is minted
+    NonTransferable,
+    /// Routes every transfer through the harness's transfer hook program
+    TransferHook,
+}
+
+impl MintExtension {
+    fn extension_type(self) -> ExtensionType {
+        match self {
+            Self::MetadataPointer => ExtensionType::MetadataPointer,
+            Self::NonTransferable => ExtensionType::NonTransferable,
+            Self::TransferHook => ExtensionType::TransferHook,
+        }
+    }
+}
+
+/**
+ * Per-thread runtime state read by the syscall stubs while an instruction executes.
+ * Tests run on separate threads, so every harness gets its own clock, call stack and logs.
+ */
+#[derive(Default)]
+struct Runtime {
+    clock: Clock,
+    invoke_stack: Vec<Pubkey>,
+    return_data: Option<(Pubkey, Vec<u8>)>,
+    logs: Vec<String>,
+    invoked: Vec<Instruction>,
+    core_loaded: bool,
+    token_metadata_loaded: bool,
+    bubblegum_loaded: bool,
+}
+
+thread_local! {
+    static RUNTIME: RefCell<Runtime> = RefCell::new(Runtime::default());
+}
+
+static INSTALL_STUBS: Once = Once::new();
+
+struct HarnessStubs;
+
+impl SyscallStubs for HarnessStubs {
+    fn sol_log(&self, message: &str) {
+        RUNTIME.with(|runtime| {
+            runtime
+                .borrow_mut()
+                .logs
+                .push(format!("Program log: {message}"))
+        });
+    }
+
+    fn sol_log_data(&self, fields: &[&[u8]]) {
+        let encoded: Vec<String> = fields.iter().map(|field| BASE64.encode(field)).collect();
+        RUNTIME.with(|runtime| {
+            runtime
+                .borrow_mut()
+                .logs
+                .push(format!("Program data: {}", encoded.join(" ")))
+        });
+    }
+
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let caller = RUNTIME.with(|runtime| {
+            let mut runtime = runtime.borrow_mut();
+            runtime.invoked.push(instruction.clone());
+            *runtime.invoke_stack.last().unwrap()
+        });
+        let signer_pdas = signers_seeds
+            .iter()
+            .map(|seeds| Pubkey::create_program_address(seeds, &caller))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| ProgramError::InvalidSeeds)?;
+
+        // Mirror the runtime's privilege checks: a callee may only sign or write where the
+        // caller could, except for PDAs of the caller signed for through `signers_seeds`
+        let mut callee_infos = Vec::with_capacity(instruction.accounts.len());
+        for meta in &instruction.accounts {
+            let info = account_infos
+                .iter()
+                .find(|info| *info.key == meta.pubkey)
+                .ok_or(ProgramError::NotEnoughAccountKeys)?;
+            if meta.is_signer && !info.is_signer && !signer_pdas.contains(&meta.pubkey) {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            if meta.is_writable && !info.is_writable {
+                return Err(ProgramError::InvalidArgument);
+            }
+            let mut callee_info = info.clone();
+            callee_info.is_signer = meta.is_signer;
+            callee_info.is_writable = meta.is_writable;
+            callee_infos.push(callee_info);
+        }
+
+        RUNTIME.with(|runtime| {
+            let mut runtime = runtime.borrow_mut();
+            runtime.invoke_stack.push(instruction.program_id);
+            runtime
+                .logs
+                .push(format!("Program {} invoke", instruction.program_id));
+        });
+        let result = process_native(&instruction.program_id, &callee_infos, &instruction.data);
+        RUNTIME.with(|runtime| runtime.borrow_mut().invoke_stack.pop());
+        result
+    }
+
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        let clock = RUNTIME.with(|runtime| runtime.borrow().clock.clone());
+        unsafe { *(var_addr as *mut Clock) = clock };
+        0
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { *(var_addr as *mut Rent) = Rent::default() };
+        0
+    }
+
+    fn sol_get_return_data(&self) -> Option<(Pubkey, Vec<u8>)> {
+        RUNTIME.with(|runtime| runtime.borrow().return_data.clone())
+    }
+
+    fn sol_set_return_data(&self, data: &[u8]) {
+        RUNTIME.with(|runtime| {
+            let mut runtime = runtime.borrow_mut();
+            let program_id = *runtime.invoke_stack.last().unwrap();
+            runtime.return_data = (!data.is_empty()).then(|| (program_id, data.to_vec()));
+        });
+    }
+
+    fn sol_get_stack_height(&self) -> u64 {
+        RUNTIME.with(|runtime| runtime.borrow().invoke_stack.len() as u64)
+    }
+}
+
+/**
+ * Executes an instruction of one of the natively available programs invoked through CPI.
+ * The Collectible Vault program never invokes itself, so it is only reachable at the top level.
+ */
+fn process_native(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    if *program_id == system_program::ID {
+        process_system_instruction(accounts, data)
+    } else if *program_id == spl_token::ID {
+        spl_token::processor::Processor::process(program_id, accounts, data)
+    } else if *program_id == spl_token_2022::ID {
+        spl_token_2022::processor::Processor::process(program_id, accounts, data)
+    } else if *program_id == spl_associated_token_account::ID {
+        spl_associated_token_account::processor::process_instruction(program_id, accounts, data)
+    } else if *program_id == TRANSFER_HOOK_PROGRAM_ID {
+        process_transfer_hook(accounts, data)
+    } else if *program_id == MPL_CORE_PROGRAM_ID
+        && RUNTIME.with(|runtime| runtime.borrow().core_loaded)
+    {
+        process_core_instruction(accounts, data)
+    } else if *program_id == mpl_token_metadata::ID
+        && RUNTIME.with(|runtime| runtime.borrow().token_metadata_loaded)
+    {
+        process_token_metadata_instruction(accounts, data)
+    } else if *program_id == mpl_bubblegum::ID
+        && RUNTIME.with(|runtime| runtime.borrow().bubblegum_loaded)
+    {
+        process_bubblegum_instruction(accounts, data)
+    } else {
+        Err(ProgramError::IncorrectProgramId)
+    }
+}
+
+/**
+ * A transfer hook that accepts every transfer whose accounts match its validation account,
+ * logging each execution.
+ */
+fn process_transfer_hook(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let TransferHookInstruction::Execute { amount } = TransferHookInstruction::unpack(data)? else {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+    let mint = accounts.get(1).ok_or(ProgramError::NotEnoughAccountKeys)?;
+    let validation = accounts.get(4).ok_or(ProgramError::NotEnoughAccountKeys)?;
+    if *validation.key != get_extra_account_metas_address(mint.key, &TRANSFER_HOOK_PROGRAM_ID) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    ExtraAccountMetaList::check_account_infos::<ExecuteInstruction>(
+        accounts,
+        data,
+        &TRANSFER_HOOK_PROGRAM_ID,
+        &validation.try_borrow_data()?,
+    )?;
+    anchor_lang::solana_program::msg!("Transfer hook executed for {} tokens", amount);
+    Ok(())
+}
+
+/**
+ * The update authority of a Metaplex Core asset.
+ */
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+enum CoreUpdateAuthority {
+    None,
+    Address(Pubkey),
+    Collection(Pubkey),
+}
+
+/**
+ * A Metaplex Core asset as Core stores it: an `AssetV1`, followed when it carries plugins by a
+ * `PluginHeaderV1`, the Borsh-encoded `Plugin` of each plugin and a `PluginRegistryV1` pointing
+ * at them.
+ */
+struct CoreAssetAccount {
+    owner: Pubkey,
+    update_authority: CoreUpdateAuthority,
+    name: String,
+    uri: String,
+    plugins: Vec<(CorePluginType, CorePluginAuthority, Vec<u8>)>,
+}
+
+impl CoreAssetAccount {
+    fn decode(data: &[u8]) -> Result<Self, ProgramError> {
+        let invalid = |_| ProgramError::InvalidAccountData;
+        let mut cursor = data;
+        let (key, owner, update_authority, name, uri, _seq) =
+            <(u8, Pubkey, CoreUpdateAuthority, String, String, Option<u64>)>::deserialize(
+                &mut cursor,
+            )
+            .map_err(invalid)?;
+        if key != 1 {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let mut plugins = Vec::new();
+        if !cursor.is_empty() {
+            let (_key, registry_offset) = <(u8, u64)>::deserialize(&mut cursor).map_err(invalid)?;
+            let mut registry = data
+                .get(registry_offset as usize..)
+                .ok_or(ProgramError::InvalidAccountData)?;
+            let (_key, records) =
+                <(u8, Vec<(CorePluginType, CorePluginAuthority, u64)>)>::deserialize(&mut registry)
+                    .map_err(invalid)?;
+            // Each plugin runs up to the next one, the last up to the registry
+            let mut offsets: Vec<u64> = records.iter().map(|(_, _, offset)| *offset).collect();
+            offsets.push(registry_offset);
+            offsets.sort_unstable();
+            for (plugin_type, authority, offset) in records {
+                let end = offsets[offsets.partition_point(|candidate| *candidate <= offset)];
+                let plugin = data
+                    .get(offset as usize..end as usize)
+                    .ok_or(ProgramError::InvalidAccountData)?;
+                plugins.push((plugin_type, authority, plugin.to_vec()));
+            }
+        }
+
+        Ok(Self {
+            owner,
+            update_authority,
+            name,
+            uri,
+            plugins,
+        })
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        let mut data = (
+            1u8,
+            self.owner,
+            self.update_authority,
+            self.name.clone(),
+            self.uri.clone(),
+            None::<u64>,
+        )
+            .try_to_vec()
+            .unwrap();
+        if self.plugins.is_empty() {
+            return data;
+        }
+
+        let plugins_start = data.len() as u64 + 9;
+        let registry_offset = plugins_start
+            + self
+                .plugins
+                .iter()
+                .map(|(_, _, plugin)| plugin.len() as u64)
+                .sum::<u64>();
+        (3u8, registry_offset).serialize(&mut data).unwrap();
+        let mut records = Vec::with_capacity(self.plugins.len());
+        for (plugin_type, authority, plugin) in &self.plugins {
+            records.push((*plugin_type, *authority, data.len() as u64));
+            data.extend_from_slice(plugin);
+        }
+        (4u8, records, Vec::<u8>::new())
+            .serialize(&mut data)
+            .unwrap();
+        data
+    }
+
+    fn plugin(
+        &mut self,
+        plugin_type: CorePluginType,
+    ) -> Option<&mut (CorePluginType, CorePluginAuthority, Vec<u8>)> {
+        self.plugins
+            .iter_mut()
+            .find(|(candidate, _, _)| *candidate == plugin_type)
+    }
+
+    /// Whether a freeze plugin holds the asset frozen.
+    fn is_frozen(&self) -> bool {
+        self.plugins.iter().any(|(plugin_type, _, plugin)| {
+            matches!(
+                plugin_type,
+                CorePluginType::FreezeDelegate | CorePluginType::PermanentFreezeDelegate
+            ) && plugin.get(1) == Some(&1)
+        })
+    }
+
+    /// Whether `signer` holds `authority` over a plugin of this asset.
+    fn is_authority(&self, authority: CorePluginAuthority, signer: &Pubkey) -> bool {
+        match authority {
+            CorePluginAuthority::None => false,
+            CorePluginAuthority::Owner => *signer == self.owner,
+            CorePluginAuthority::UpdateAuthority => {
+                matches!(self.update_authority, CoreUpdateAuthority::Address(address) if address == *signer)
+            }
+            CorePluginAuthority::Address { address } => address == *signer,
+        }
+    }
+}
+
+/**
+ * The Borsh-encoded `Plugin` Core stores for a plugin the harness adds to an asset. Only the
+ * freeze plugins' `frozen` flag is read back, so the fields of the other plugins are left out.
+ */
+fn default_core_plugin(plugin_type: CorePluginType) -> Vec<u8> {
+    match plugin_type {
+        CorePluginType::FreezeDelegate | CorePluginType::PermanentFreezeDelegate => {
+            vec![plugin_type as u8, 0]
+        }
+        _ => vec![plugin_type as u8],
+    }
+}
+
+/**
+ * A native stand-in for the Metaplex Core program, enabled with `Harness::load_core`.
+ * It runs the instructions the vault invokes, encoded as in the published Core IDL, and enforces
+ * Core's ownership, plugin authority, freeze and collection rules for them. Rent for a grown
+ * asset is taken from the payer.
+ */
+fn process_core_instruction(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    const CREATE_V1: u8 = 0;
+    const ADD_PLUGIN_V1: u8 = 2;
+    const UPDATE_PLUGIN_V1: u8 = 6;
+    const APPROVE_PLUGIN_AUTHORITY_V1: u8 = 8;
+    const REVOKE_PLUGIN_AUTHORITY_V1: u8 = 10;
+    const TRANSFER_V1: u8 = 14;
+
+    let account = |index: usize| {
+        accounts
+            .get(index)
+            .ok_or(ProgramError::NotEnoughAccountKeys)
+    };
+    // Core takes the program ID in place of an omitted optional account
+    let optional = |index: usize| {
+        account(index).map(|info| (*info.key != MPL_CORE_PROGRAM_ID).then_some(info))
+    };
+    let signer = |info: &AccountInfo| {
+        if info.is_signer {
+            Ok(*info.key)
+        } else {
+            Err(ProgramError::MissingRequiredSignature)
+        }
+    };
+    let require = |condition: bool| {
+        if condition {
+            Ok(())
+        } else {
+            Err(ProgramError::InvalidArgument)
+        }
+    };
+    let (&discriminator, mut args) = data
+        .split_first()
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    let invalid_data = |_| ProgramError::InvalidInstructionData;
+    // The plugins the vault manages: FreezeDelegate { frozen } and TransferDelegate
+    let read_plugin = |args: &mut &[u8]| {
+        let plugin_type = CorePluginType::deserialize(args).map_err(invalid_data)?;
+        let plugin = match plugin_type {
+            CorePluginType::FreezeDelegate => {
+                vec![
+                    plugin_type as u8,
+                    bool::deserialize(args).map_err(invalid_data)? as u8,
+                ]
+            }
+            CorePluginType::TransferDelegate => vec![plugin_type as u8],
+            _ => return Err(ProgramError::InvalidInstructionData),
+        };
+        Ok((plugin_type, plugin))
+    };
+
+    let asset_info = account(0)?;
+    if discriminator == CREATE_V1 {
+        // Accounts: asset, collection, authority, payer, owner, update authority, system program
+        let payer = account(3)?;
+        signer(asset_info)?;
+        signer(payer)?;
+        require(optional(1)?.is_none())?;
+        require(
+            asset_info.lamports() == 0
+                && asset_info.data_is_empty()
+                && *asset_info.owner == system_program::ID,
+        )?;
+        let (_data_state, name, uri, plugins) =
+            <(u8, String, String, Option<Vec<u8>>)>::deserialize(&mut args)
+                .map_err(invalid_data)?;
+        require(plugins.is_none())?;
+        let asset = CoreAssetAccount {
+            owner: *optional(4)?.unwrap_or(payer).key,
+            update_authority: CoreUpdateAuthority::Address(*optional(5)?.unwrap_or(payer).key),
+            name,
+            uri,
+            plugins: Vec::new(),
+        };
+        asset_info.assign(&MPL_CORE_PROGRAM_ID);
+        return write_core_asset(asset_info, payer, &asset);
+    }
+
+    if *asset_info.owner != MPL_CORE_PROGRAM_ID {
+        return Err(ProgramError::IllegalOwner);
+    }
+    let mut asset = CoreAssetAccount::decode(&asset_info.try_borrow_data()?)?;
+    // The collection must be passed exactly when the asset belongs to one
+    let collection = optional(1)?.map(|info| *info.key);
+    match asset.update_authority {
+        CoreUpdateAuthority::Collection(expected) => require(collection == Some(expected))?,
+        _ => require(collection.is_none())?,
+    }
+    let payer = account(2)?;
+    signer(payer)?;
+    let authority = signer(optional(3)?.unwrap_or(payer))?;
+
+    match discriminator {
+        ADD_PLUGIN_V1 => {
+            let (plugin_type, plugin) = read_plugin(&mut args)?;
+            let init_authority =
+                Option::<CorePluginAuthority>::deserialize(&mut args).map_err(invalid_data)?;
+            // Owner-managed plugins are added by the owner
+            require(authority == asset.owner && asset.plugin(plugin_type).is_none())?;
+            asset.plugins.push((
+                plugin_type,
+                init_authority.unwrap_or(CorePluginAuthority::Owner),
+                plugin,
+            ));
+        }
+        UPDATE_PLUGIN_V1 => {
+            let (plugin_type, plugin) = read_plugin(&mut args)?;
+            let (_, current, _) = *asset
+                .plugin(plugin_type)
+                .ok_or(ProgramError::InvalidArgument)?;
+            require(asset.is_authority(current, &authority))?;
+            asset.plugin(plugin_type).unwrap().2 = plugin;
+        }
+        APPROVE_PLUGIN_AUTHORITY_V1 => {
+            let (plugin_type, new_authority) =
+                <(CorePluginType, CorePluginAuthority)>::deserialize(&mut args)
+                    .map_err(invalid_data)?;
+            require(authority == asset.owner)?;
+            asset
+                .plugin(plugin_type)
+                .ok_or(ProgramError::InvalidArgument)?
+                .1 = new_authority;
+        }
+        REVOKE_PLUGIN_AUTHORITY_V1 => {
+            let plugin_type = CorePluginType::deserialize(&mut args).map_err(invalid_data)?;
+            let (_, current, _) = *asset
+                .plugin(plugin_type)
+                .ok_or(ProgramError::InvalidArgument)?;
+            require(authority == asset.owner || asset.is_authority(current, &authority))?;
+            asset.plugin(plugin_type).unwrap().1 = CorePluginAuthority::Owner;
+        }
+        TRANSFER_V1 => {
+            // Accounts: asset, collection, payer, authority, new owner, system program
+            let compression_proof = Option::<u8>::deserialize(&mut args).map_err(invalid_data)?;
+            require(compression_proof.is_none() && !asset.is_frozen())?;
+            let transfer_delegate = asset
+                .plugin(CorePluginType::TransferDelegate)
+                .map(|(_, delegate, _)| *delegate);
+            require(
+                authority == asset.owner
+                    || transfer_delegate
+                        .is_some_and(|delegate| asset.is_authority(delegate, &authority)),
+            )?;
+            asset.owner = *account(4)?.key;
+            // Owner-managed plugins return to the new owner
+            for (plugin_type, plugin_authority, _) in &mut asset.plugins {
+                if matches!(
+                    plugin_type,
+                    CorePluginType::FreezeDelegate
+                        | CorePluginType::TransferDelegate
+                        | CorePluginType::BurnDelegate
+                ) {
+                    *plugin_authority = CorePluginAuthority::Owner;
+                }
+            }
+        }
+        _ => return Err(ProgramError::InvalidInstructionData),
+    }
+    write_core_asset(asset_info, payer, &asset)
+}
+
+/// Stores a Core asset, topping its rent up from `payer` when it grows.
+fn write_core_asset(
+    asset_info: &AccountInfo,
+    payer: &AccountInfo,
+    asset: &CoreAssetAccount,
+) -> ProgramResult {
+    let data = asset.encode();
+    asset_info.realloc(data.len(), false)?;
+    asset_info.try_borrow_mut_data()?.copy_from_slice(&data);
+    let shortfall = Rent::default()
+        .minimum_balance(data.len())
+        .saturating_sub(asset_info.lamports());
+    let remaining = payer
+        .lamports()
+        .checked_sub(shortfall)
+        .ok_or(ProgramError::InsufficientFunds)?;
+    **payer.try_borrow_mut_lamports()? = remaining;
+    **asset_info.try_borrow_mut_lamports()? += shortfall;
+    Ok(())
+}
+
+/**
+ * The subset of the System program used by Anchor and the SPL programs.
+ */
+fn process_system_instruction(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    // SystemError::AccountAlreadyInUse and SystemError::ResultWithNegativeLamports
+    const ACCOUNT_ALREADY_IN_USE: u32 = 0;
+    const RESULT_WITH_NEGATIVE_LAMPORTS: u32 = 1;
+
+    let instruction: SystemInstruction =
+        bincode::deserialize(data).map_err(|_| ProgramError::InvalidInstructionData)?;
+    let account = |index: usize| {
+        accounts
+            .get(index)
+            .ok_or(ProgramError::NotEnoughAccountKeys)
+    };
+    let require_signer = |info: &AccountInfo| {
+        if info.is_signer {
+            Ok(())
+        } else {
+            Err(ProgramError::MissingRequiredSignature)
+        }
+    };
+    let require_unused = |info: &AccountInfo| {
+        if info.data_is_empty() && *info.owner == system_program::ID {
+            Ok(())
+        } else {
+            Err(ProgramError::Custom(ACCOUNT_ALREADY_IN_USE))
+        }
+    };
+    let move_lamports = |from: &AccountInfo, to: &AccountInfo, lamports: u64| {
+        let remaining = from
+            .lamports()
+            .checked_sub(lamports)
+            .ok_or(ProgramError::Custom(RESULT_WITH_NEGATIVE_LAMPORTS))?;
+        **from.try_borrow_mut_lamports()? = remaining;
+        **to.try_borrow_mut_lamports()? += lamports;
+        Ok::<(), ProgramError>(())
+    };
+
+    match instruction {
+        SystemInstruction::CreateAccount {
+            lamports,
+            space,
+            owner,
+        } => {
+            let (from, to) = (account(0)?, account(1)?);
+            require_signer(from)?;
+            require_signer(to)?;
+            if to.lamports() > 0 {
+                return Err(ProgramError::Custom(ACCOUNT_ALREADY_IN_USE));
+            }
+            require_unused(to)?;
+            move_lamports(from, to, lamports)?;
+            to.realloc(space as usize, true)?;
+            to.assign(&owner);
+            Ok(())
+        }
+        SystemInstruction::Transfer { lamports } => {
+            let (from, to) = (account(0)?, account(1)?);
+            require_signer(from)?;
+            if !from.data_is_empty() {
+                return Err(ProgramError::InvalidArgument);
+            }
+            move_lamports(from, to, lamports)
+        }
+        SystemInstruction::Allocate { space } => {
+            let target = account(0)?;
+            require_signer(target)?;
+            require_unused(target)?;
+            target.realloc(space as usize, true)
+        }
+        SystemInstruction::Assign { owner } => {
+            let target = account(0)?;
+            require_signer(target)?;
+            target.assign(&owner);
+            Ok(())
+        }
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+/**
+ * Executes a top-level instruction against any program the harness knows about.
+ */
+fn process_top_level<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    data: &[u8],
+) -> ProgramResult {
+    if *program_id == collectible_vault::ID {
+        collectible_vault::entry(program_id, accounts, data)
+    } else {
+        process_native(program_id, accounts, data)
+    }
+}
+
+/**
+ * A single-threaded, in-memory bank that processes transactions against the program.
+ */
+pub struct Harness {
+    accounts: HashMap<Pubkey, StoredAccount>,
+    clock: Clock,
+    core_loaded: bool,
+    token_metadata_loaded: bool,
+    bubblegum_loaded: bool,
+}
+
+impl Default for Harness {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Harness {
+    /**
+     * Creates a bank holding the program accounts and the rent sysvar, with the clock set to
+     * `GENESIS_TIMESTAMP`.
+     */
+    pub fn new() -> Self {
+        INSTALL_STUBS.call_once(|| {
+            program_stubs::set_syscall_stubs(Box::new(HarnessStubs));
+        });
+
+        let mut harness = Self {
+            accounts: HashMap::new(),
+            clock: Clock {
+                slot: 1,
+                unix_timestamp: GENESIS_TIMESTAMP,
+                ..Clock::default()
+            },
+            core_loaded: false,
+            token_metadata_loaded: false,
+            bubblegum_loaded: false,
+        };
+        for program_id in [
+            collectible_vault::ID,
+            system_program::ID,
+            spl_token::ID,
+            spl_token_2022::ID,
+            spl_associated_token_account::ID,
+            mpl_token_metadata::ID,
+            MPL_CORE_PROGRAM_ID,
+            mpl_bubblegum::ID,
+            SPL_ACCOUNT_COMPRESSION_ID,
+            SPL_NOOP_ID,
+            TRANSFER_HOOK_PROGRAM_ID,
+        ] {
+            harness.set_account(
+                program_id,
+                StoredAccount {
+                    lamports: 1,
+                    data: vec![],
+                    owner: anchor_lang::solana_program::bpf_loader_upgradeable::ID,
+                    executable: true,
+                },
+            );
+        }
+        harness.set_account(
+            sysvar::rent::ID,
+            StoredAccount {
+                lamports: 1,
+                data: bincode::serialize(&Rent::default()).unwrap(),
+                owner: sysvar::ID,
+                executable: false,
+            },
+        );
+        harness
+    }
+
+    /**
+     * Runs Metaplex Core instructions with the harness's native Core stand-in, so Core CPIs
+     * succeed where they would fail with `IncorrectProgramId`.
+     */
+    pub fn load_core(&mut self) {
+        self.core_loaded = true;
+    }
+
+    /**
+     * Runs Metaplex Token Metadata instructions with the harness's native Token Metadata
+     * stand-in, so Token Metadata CPIs succeed where they would fail with `IncorrectProgramId`.
+     */
+    pub fn load_token_metadata(&mut self) {
+        self.token_metadata_loaded = true;
+    }
+
+    /**
+     * Runs Metaplex Bubblegum instructions with the harness's native Bubblegum stand-in, so
+     * Bubblegum CPIs succeed where they would fail with `IncorrectProgramId`.
+     */
+    pub fn load_bubblegum(&mut self) {
+        self.bubblegum_loaded = true;
+    }
+
+    pub fn set_account(&mut self, key: Pubkey, account: StoredAccount) {
+        self.accounts.insert(key, account);
+    }
+
+    pub fn account(&self, key: &Pubkey) -> Option<&StoredAccount> {
+        self.accounts.get(key)
+    }
+
+    pub fn lamports(&self, key: &Pubkey) -> u64 {
+        self.account(key).map_or(0, |account| account.lamports)
+    }
+
+    pub fn airdrop(&mut self, key: &Pubkey, lamports: u64) {
+        self.accounts
+            .entry(*key)
+            .or_insert_with(|| StoredAccount {
+                owner: system_program::ID,
+                ..StoredAccount::default()
+            })
+            .lamports += lamports;
+    }
+
+    /**
+     * Creates a funded system-owned wallet. The harness does not verify signatures, so the
+     * returned key can sign any transaction it is listed as a signer of.
+     */
+    pub fn new_wallet(&mut self) -> Pubkey {
+        let wallet = Pubkey::new_unique();
+        self.airdrop(&wallet, WALLET_LAMPORTS);
+        wallet
+    }
+
+    pub fn now(&self) -> i64 {
+        self.clock.unix_timestamp
+    }
+
+    /**
+     * Moves the clock forward, as if `seconds` had elapsed since the last transaction.
+     */
+    pub fn warp_forward(&mut self, seconds: i64) {
+        self.clock.unix_timestamp += seconds;
+        self.clock.slot += 1;
+    }
+
+    /**
+     * Deserializes an Anchor account, panicking if it is missing or malformed.
+     */
+    pub fn anchor_account<T: AccountDeserialize>(&self, key: &Pubkey) -> T {
+        let account = self
+            .account(key)
+            .unwrap_or_else(|| panic!("account {key} does not exist"));
+        T::try_deserialize(&mut account.data.as_slice()).expect("invalid account data")
+    }
+
+    /**
+     * Returns the balance of an SPL Token or Token-2022 account, or 0 if it does not exist.
+     */
+    pub fn token_balance(&self, key: &Pubkey) -> u64 {
+        self.account(key).map_or(0, |account| {
+            StateWithExtensions::<spl_token_2022::state::Account>::unpack(&account.data)
+                .expect("not a token account")
+                .base
+                .amount
+        })
+    }
+
+    /**
+     * Credits one more token to an existing token account without touching the mint, as anyone
+     * holding more of a mint with a supply above one could send it.
+     */
+    pub fn send_stray_token(&mut self, key: &Pubkey) {
+        let mut account = self
+            .account(key)
+            .expect("token account does not exist")
+            .clone();
+        // The amount of a token account follows its 32-byte mint and 32-byte owner
+        let amount = u64::from_le_bytes(account.data[64..72].try_into().unwrap());
+        account.data[64..72].copy_from_slice(&(amount + 1).to_le_bytes());
+        self.set_account(*key, account);
+    }
+
+    /**
+     * Returns whether an SPL Token or Token-2022 account is frozen.
+     */
+    pub fn is_frozen(&self, key: &Pubkey) -> bool {
+        let account = self.account(key).expect("token account does not exist");
+        StateWithExtensions::<spl_token_2022::state::Account>::unpack(&account.data)
+            .expect("not a token account")
+            .base
+            .is_frozen()
+    }
+
+    /**
+     * Returns the lamports held across every account in the bank.
+     */
+    pub fn total_lamports(&self) -> u128 {
+        self.accounts
+            .values()
+            .map(|account| account.lamports as u128)
+            .sum()
+    }
+
+    /**
+     * Returns the `(token account, owner, amount)` of every non-empty token account of a mint.
+     */
+    pub fn token_holders(&self, mint: &Pubkey) -> Vec<(Pubkey, Pubkey, u64)> {
+        self.accounts
+            .iter()
+            .filter(|(_, account)| account.owner == spl_token::ID)
+            .filter_map(|(key, account)| {
+                let token_account = spl_token::state::Account::unpack(&account.data).ok()?;
+                (token_account.mint == *mint && token_account.amount > 0).then_some((
+                    *key,
+                    token_account.owner,
+                    token_account.amount,
+                ))
+            })
+            .collect()
+    }
+
+    /**
+     * Returns the supply of an SPL Token or Token-2022 mint, or 0 if it does not exist.
+     */
+    pub fn mint_supply(&self, mint: &Pubkey) -> u64 {
+        self.account(mint).map_or(0, |account| {
+            StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&account.data)
+                .expect("not a mint")
+                .base
+                .supply
+        })
+    }
+
+    /**
+     * Processes a transaction atomically. `signers` lists every key that signed it.
+     * Returns the transaction logs on success.
+     */
+    pub fn process_transaction(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[Pubkey],
+    ) -> Result<Vec<String>, TransactionError> {
+        let mut working = self.accounts.clone();
+        let mut logs = Vec::new();
+        let mut invoked = Vec::new();
+
+        for (index, instruction) in instructions.iter().enumerate() {
+            let result = self.process_instruction(&mut working, instruction, signers);
+            RUNTIME.with(|runtime| {
+                let mut runtime = runtime.borrow_mut();
+                logs.append(&mut runtime.logs);
+                invoked.append(&mut runtime.invoked);
+            });
+            if let Err(error) = result {
+                return Err(TransactionError {
+                    instruction: index,
+                    error,
+                    logs,
+                    invoked,
+                });
+            }
+        }
+
+        working.retain(|_, account| account.lamports > 0);
+        self.accounts = working;
+        Ok(logs)
+    }
+
+    fn process_instruction(
+        &self,
+        working: &mut HashMap<Pubkey, StoredAccount>,
+        instruction: &Instruction,
+        signers: &[Pubkey],
+    ) -> Result<(), InstructionError> {
+        // Merge duplicate account metas the way the runtime does
+        let mut keys: Vec<Pubkey> = Vec::new();
+        let mut writable: Vec<bool> = Vec::new();
+        let mut positions = Vec::with_capacity(instruction.accounts.len());
+        for meta in &instruction.accounts {
+            if meta.is_signer && !signers.contains(&meta.pubkey) {
+                return Err(InstructionError::MissingRequiredSignature);
+            }
+            match keys.iter().position(|key| *key == meta.pubkey) {
+                Some(position) => {
+                    writable[position] |= meta.is_writable;
+                    positions.push(position);
+                }
+                None => {
+                    keys.push(meta.pubkey);
+                    writable.push(meta.is_writable);
+                    positions.push(keys.len() - 1);
+                }
+            }
+        }
+        let before: Vec<StoredAccount> = keys
+            .iter()
+            .map(|key| {
+                working.get(key).cloned().unwrap_or_else(|| StoredAccount {
+                    owner: system_program::ID,
+                    ..StoredAccount::default()
+                })
+            })
+            .collect();
+
+        let mut input =
+            serialize_input(instruction, &keys, &writable, &positions, &before, signers);
+        RUNTIME.with(|runtime| {
+            let mut runtime = runtime.borrow_mut();
+            runtime.clock = self.clock.clone();
+            runtime.invoke_stack = vec![instruction.program_id];
+            runtime.core_loaded = self.core_loaded;
+            runtime.token_metadata_loaded = self.token_metadata_loaded;
+            runtime.bubblegum_loaded = self.bubblegum_loaded;
+            runtime.return_data = None;
+            runtime
+                .logs
+                .push(format!("Program {} invoke", instruction.program_id));
+        });
+
+        let (program_id, account_infos, data) =
+            unsafe { deserialize(input.as_mut_ptr() as *mut u8) };
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            process_top_level(program_id, &account_infos, data)
+        }));
+        let result = match result {
+            Ok(Ok(())) => Ok(()),
+            Ok(Err(error)) => Err(InstructionError::from(u64::from(error))),
+            Err(_) => Err(InstructionError::ProgramFailedToComplete),
+        };
+        RUNTIME.with(|runtime| {
+            let mut runtime = runtime.borrow_mut();
+            let message = match &result {
+                Ok(()) => format!("Program {} success", instruction.program_id),
+                Err(error) => format!("Program {} failed: {error}", instruction.program_id),
+            };
+            runtime.logs.push(message);
+            runtime.invoke_stack.clear();
+        });
+        result?;
+
+        // Read back the first occurrence of every account and enforce the runtime's invariants
+        let mut after = Vec::with_capacity(keys.len());
+        for (position, _) in keys.iter().enumerate() {
+            let meta_index = positions.iter().position(|p| *p == position).unwrap();
+            let info = &account_infos[meta_index];
+            after.push(StoredAccount {
+                lamports: info.lamports(),
+                data: info.data.borrow().to_vec(),
+                owner: *info.owner,
+                executable: info.executable,
+            });
+        }
+
+        let lamports_before: u128 = before.iter().map(|a| a.lamports as u128).sum();
+        let lamports_after: u128 = after.iter().map(|a| a.lamports as u128).sum();
+        if lamports_before != lamports_after {
+            return Err(InstructionError::UnbalancedInstruction);
+        }
+        for (position, key) in keys.iter().enumerate() {
+            if !writable[position] && before[position] != after[position] {
+                return Err(InstructionError::ReadonlyDataModified);
+            }
+            working.insert(*key, after[position].clone());
+        }
+        Ok(())
+    }
+
+    /**
+     * Creates a fresh SPL mint with 0 decimals and mints a single token into the owner's
+     * associated token account, standing in for an NFT minted by the vault.
+     */
+    pub fn create_nft(&mut self, owner: &Pubkey) -> Pubkey {
+        let payer = self.new_wallet();
+        let mint = Pubkey::new_unique();
+        let rent = Rent::default().minimum_balance(spl_token::state::Mint::LEN);
+        let owner_token_account = get_associated_token_address(owner, &mint);
+        self.process_transaction(
+            &[
+                system_instruction::create_account(
+                    &payer,
+                    &mint,
+                    rent,
+                    spl_token::state::Mint::LEN as u64,
+                    &spl_token::ID,
+                ),
+                spl_token::instruction::initialize_mint2(&spl_token::ID, &mint, &payer, None, 0)
+                    .unwrap(),
+                spl_associated_token_account::instruction::create_associated_token_account(
+                    &payer,
+                    owner,
+                    &mint,
+                    &spl_token::ID,
+                ),
+                spl_token::instruction::mint_to(
+                    &spl_token::ID,
+                    &mint,
+                    &owner_token_account,
+                    &payer,
+                    &[],
+                    1,
+                )
+                .unwrap(),
+            ],
+            &[payer, mint],
+        )
+        .expect("failed to create NFT");
+        mint
+    }
+
+    /**
+     * Creates a Token-2022 mint with 0 decimals and the given extensions, and mints a single
+     * token into the owner's associated token account. Mints with a transfer hook also get the
+     * hook's validation account, listing `TRANSFER_HOOK_EXTRA_ACCOUNT`.
+     */
+    pub fn create_token_2022_nft(
+        &mut self,
+        owner: &Pubkey,
+        extensions: &[MintExtension],
+    ) -> Pubkey {
+        let payer = self.new_wallet();
+        let mint = Pubkey::new_unique();
+        let extension_types: Vec<ExtensionType> = extensions
+            .iter()
+            .map(|extension| extension.extension_type())
+            .collect();
+        let space = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(
+            &extension_types,
+        )
+        .unwrap();
+        let owner_token_account =
+            get_associated_token_address_with_program_id(owner, &mint, &spl_token_2022::ID);
+
+        let mut instructions = vec![system_instruction::create_account(
+            &payer,
+            &mint,
+            Rent::default().minimum_balance(space),
+            space as u64,
+            &spl_token_2022::ID,
+        )];
+        for extension in extensions {
+            instructions.push(
+                match extension {
+                    MintExtension::MetadataPointer => metadata_pointer::instruction::initialize(
+                        &spl_token_2022::ID,
+                        &mint,
+                        Some(payer),
+                        Some(mint),
+                    ),
+                    MintExtension::NonTransferable => {
+                        spl_token_2022::instruction::initialize_non_transferable_mint(
+                            &spl_token_2022::ID,
+                            &mint,
+                        )
+                    }
+                    MintExtension::TransferHook => transfer_hook::instruction::initialize(
+                        &spl_token_2022::ID,
+                        &mint,
+                        Some(payer),
+                        Some(TRANSFER_HOOK_PROGRAM_ID),
+                    ),
+                }
+                .unwrap(),
+            );
+        }
+        instructions.extend([
+            spl_token_2022::instruction::initialize_mint2(
+                &spl_token_2022::ID,
+                &mint,
+                &payer,
+                None,
+                0,
+            )
+            .unwrap(),
+            spl_associated_token_account::instruction::create_associated_token_account(
+                &payer,
+                owner,
+                &mint,
+                &spl_token_2022::ID,
+            ),
+            spl_token_2022::instruction::mint_to(
+                &spl_token_2022::ID,
+                &mint,
+                &owner_token_account,
+                &payer,
+                &[],
+                1,
+            )
+            .unwrap(),
+        ]);
+        self.process_transaction(&instructions, &[payer, mint])
+            .expect("failed to create Token-2022 NFT");
+
+        if extensions.contains(&MintExtension::TransferHook) {
+            let extra_account_metas =
+                [
+                    ExtraAccountMeta::new_with_pubkey(&TRANSFER_HOOK_EXTRA_ACCOUNT, false, false)
+                        .unwrap(),
+                ];
+            let mut data =
+                vec![0; ExtraAccountMetaList::size_of(extra_account_metas.len()).unwrap()];
+            ExtraAccountMetaList::init::<ExecuteInstruction>(&mut data, &extra_account_metas)
+                .unwrap();
+            self.set_account(
+                get_extra_account_metas_address(&mint, &TRANSFER_HOOK_PROGRAM_ID),
+                StoredAccount {
+                    lamports: Rent::default().minimum_balance(data.len()),
+                    data,
+                    owner: TRANSFER_HOOK_PROGRAM_ID,
+                    executable: false,
+                },
+            );
+        }
+        mint
+    }
+
+    /**
+     * Creates the associated USDC account of `owner` holding `amount` USDC, creating the USDC
+     * mint at its mainnet address first if needed. Returns the token account.
+     */
+    pub fn create_usdc_account(&mut self, owner: &Pubkey, amount: u64) -> Pubkey {
+        let usdc_mint = get_usdc_mint_pubkey();
+        if self.account(&usdc_mint).is_none() {
+            let mut data = vec![0; spl_token::state::Mint::LEN];
+            spl_token::state::Mint {
+                mint_authority: Some(usdc_mint).into(),
+                supply: 0,
+                decimals: 6,
+                is_initialized: true,
+                freeze_authority: None.into(),
+            }
+            .pack_into_slice(&mut data);
+            self.set_account(
+                usdc_mint,
+                StoredAccount {
+                    lamports: Rent::default().minimum_balance(data.len()),
+                    data,
+                    owner: spl_token::ID,
+                    executable: false,
+                },
+            );
+        }
+        let payer = self.new_wallet();
+        let token_account = get_associated_token_address(owner, &usdc_mint);
+        self.process_transaction(
+            &[
+                spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+                    &payer,
+                    owner,
+                    &usdc_mint,
+                    &spl_token::ID,
+                ),
+                spl_token::instruction::mint_to(
+                    &spl_token::ID,
+                    &usdc_mint,
+                    &token_account,
+                    &usdc_mint,
+                    &[],
+                    amount,
+                )
+                .unwrap(),
+            ],
+            &[payer, usdc_mint],
+        )
+        .expect("failed to create USDC account");
+        token_account
+    }
+
+    /**
+     * Moves `amount` tokens of `mint` from `owner` to `recipient`, creating the recipient's
+     * associated token account if needed.
+     */
+    pub fn transfer_tokens(
+        &mut self,
+        owner: &Pubkey,
+        recipient: &Pubkey,
+        mint: &Pubkey,
+        amount: u64,
+    ) {
+        self.process_transaction(
+            &[
+                spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+                    owner,
+                    recipient,
+                    mint,
+                    &spl_token::ID,
+                ),
+                spl_token::instruction::transfer(
+                    &spl_token::ID,
+                    &get_associated_token_address(owner, mint),
+                    &get_associated_token_address(recipient, mint),
+                    owner,
+                    &[],
+                    amount,
+                )
+                .unwrap(),
+            ],
+            &[*owner],
+        )
+        .expect("failed to transfer tokens");
+    }
+
+    /**
+     * Creates the vault collection with `create_collection`, signed by the admin, and returns
+     * its mint. Requires `Harness::load_token_metadata`.
+     */
+    pub fn create_collection(&mut self) -> Pubkey {
+        let admin = get_admin_account_pubkey();
+        self.airdrop(&admin, WALLET_LAMPORTS);
+        let mint = Pubkey::new_unique();
+        self.process_transaction(
+            &[collectible_vault_client::instructions::create_collection(
+                &admin, &mint,
+            )],
+            &[admin, mint],
+        )
+        .expect("failed to create the collection");
+        mint
+    }
+
+    /**
+     * Mints an NFT of the vault collection to `owner` with `mint_nft`, creating the collection
+     * first if it does not exist, and returns the NFT's mint. Requires
+     * `Harness::load_token_metadata`.
+     */
+    pub fn mint_nft(&mut self, owner: &Pubkey, standard: NftStandard) -> Pubkey {
+        let counter = collectible_vault_client::pda::collection_counter().0;
+        let collection_mint = match self.account(&counter) {
+            Some(_) => {
+                self.anchor_account::<CollectionCounter>(&counter)
+                    .collection_mint
+            }
+            None => self.create_collection(),
+        };
+        let admin = get_admin_account_pubkey();
+        self.airdrop(&admin, WALLET_LAMPORTS);
+        let mint = Pubkey::new_unique();
+        self.process_transaction(
+            &[collectible_vault_client::instructions::mint_nft(
+                &admin,
+                &mint,
+                owner,
+                &collection_mint,
+                "https://example.com/item".to_string(),
+                standard,
+            )],
+            &[admin, mint],
+        )
+        .expect("failed to mint the NFT");
+        mint
+    }
+
+    /**
+     * Returns the Token Metadata metadata of `mint`, panicking if it is missing or malformed.
+     */
+    pub fn token_metadata(&self, mint: &Pubkey) -> Metadata {
+        let metadata = self
+            .account(&collectible_vault_client::pda::metadata(mint).0)
+            .expect("metadata account not found");
+        Metadata::from_bytes(&metadata.data).expect("invalid metadata account")
+    }
+
+    /**
+     * Writes the vault's collection counter account directly, as if `create_collection` had
+     * run, for tests that do not load the Token Metadata stand-in.
+     */
+    pub fn set_collection_counter(&mut self, collection_mint: &Pubkey, count: u64) {
+        let counter = CollectionCounter {
+            header: AccountHeader::new(CollectionCounter::VERSION),
+            collection_mint: *collection_mint,
+            count,
+        };
+        let mut data = Vec::new();
+        counter.try_serialize(&mut data).unwrap();
+        self.set_account(
+            collectible_vault_client::pda::collection_counter().0,
+            StoredAccount {
+                lamports: Rent::default().minimum_balance(data.len()),
+                data,
+                owner: collectible_vault::ID,
+                executable: false,
+            },
+        );
+    }
+
+    /**
+     * Stores a program account in its current layout, funded for its size.
+     */
+    pub fn set_program_account(&mut self, key: Pubkey, account: &impl AccountSerialize) {
+        let mut data = Vec::new();
+        account.try_serialize(&mut data).unwrap();
+        self.set_account(
+            key,
+            StoredAccount {
+                lamports: Rent::default().minimum_balance(data.len()),
+                data,
+                owner: collectible_vault::ID,
+                executable: false,
+            },
+        );
+    }
+
+    /**
+     * Stores a program account of type `T` in a legacy layout, zero-padded to `space` bytes the
+     * way accounts were allocated before they were versioned, and funded for that size.
+     */
+    pub fn set_legacy_account<T: Discriminator>(
+        &mut self,
+        key: Pubkey,
+        legacy: &impl AnchorSerialize,
+        space: usize,
+    ) {
+        let mut data = T::DISCRIMINATOR.to_vec();
+        legacy.serialize(&mut data).unwrap();
+        data.resize(space, 0);
+        self.set_account(
+            key,
+            StoredAccount {
+                lamports: Rent::default().minimum_balance(space),
+                data,
+                owner: collectible_vault::ID,
+                executable: false,
+            },
+        );
+    }
+
+    /**
+     * Stores a Token Metadata owned metadata account for `mint` with the given token standard,
+     * without a master edition. Only the program's own reads of the account can be exercised
+     * with it; NFTs that go through Token Metadata are minted with `Harness::mint_nft`.
+     */
+    pub fn set_token_metadata(&mut self, mint: &Pubkey, token_standard: TokenStandard) {
+        self.set_token_metadata_with_royalty(mint, token_standard, 0, &[]);
+    }
+
+    /**
+     * Stores a Token Metadata owned metadata account for `mint` carrying a seller fee and the
+     * given `(creator, share)` list.
+     */
+    pub fn set_token_metadata_with_royalty(
+        &mut self,
+        mint: &Pubkey,
+        token_standard: TokenStandard,
+        seller_fee_basis_points: u16,
+        creators: &[(Pubkey, u8)],
+    ) {
+        let creators = (!creators.is_empty()).then(|| {
+            creators
+                .iter()
+                .map(|(address, share)| Creator {
+                    address: *address,
+                    verified: false,
+                    share: *share,
+                })
+                .collect()
+        });
+        let metadata = Metadata {
+            key: Key::MetadataV1,
+            update_authority: Pubkey::new_unique(),
+            mint: *mint,
+            name: String::new(),
+            symbol: String::new(),
+            uri: String::new(),
+            seller_fee_basis_points,
+            creators,
+            primary_sale_happened: false,
+            is_mutable: true,
+            edition_nonce: None,
+            token_standard: Some(token_standard),
+            collection: None,
+            uses: None,
+            collection_details: None,
+            programmable_config: None,
+        };
+        let data = metadata.try_to_vec().unwrap();
+        self.set_account(
+            collectible_vault_client::pda::metadata(mint).0,
+            StoredAccount {
+                lamports: Rent::default().minimum_balance(data.len()),
+                data,
+                owner: mpl_token_metadata::ID,
+                executable: false,
+            },
+        );
+    }
+
+    /**
+     * Stores a Metaplex Core asset held by `owner` that carries the given plugins, laid out as
+     * Core stores it. Core itself cannot run in the harness, so only the program's own reads of
+     * the asset can be exercised.
+     */
+    pub fn create_core_asset(
+        &mut self,
+        owner: &Pubkey,
+        plugins: &[(CorePluginType, CorePluginAuthority)],
+    ) -> Pubkey {
+        self.store_core_asset(
+            owner,
+            CoreUpdateAuthority::Address(Pubkey::new_unique()),
+            plugins,
+        )
+    }
+
+    /// Returns the owner of a Metaplex Core asset.
+    pub fn core_asset_owner(&self, asset: &Pubkey) -> Pubkey {
+        CoreAssetAccount::decode(&self.account(asset).unwrap().data)
+            .unwrap()
+            .owner
+    }
+
+    /// Returns the plugins of a Metaplex Core asset with their authorities, and whether it is frozen.
+    pub fn core_asset_plugins(
+        &self,
+        asset: &Pubkey,
+    ) -> (Vec<(CorePluginType, CorePluginAuthority)>, bool) {
+        let asset = CoreAssetAccount::decode(&self.account(asset).unwrap().data).unwrap();
+        let plugins = asset
+            .plugins
+            .iter()
+            .map(|(plugin_type, authority, _)| (*plugin_type, *authority))
+            .collect();
+        (plugins, asset.is_frozen())
+    }
+
+    /// Stores a Metaplex Core asset held by `owner` that belongs to `collection`.
+    pub fn create_core_asset_in_collection(
+        &mut self,
+        owner: &Pubkey,
+        collection: &Pubkey,
+        plugins: &[(CorePluginType, CorePluginAuthority)],
+    ) -> Pubkey {
+        self.store_core_asset(owner, CoreUpdateAuthority::Collection(*collection), plugins)
+    }
+
+    fn store_core_asset(
+        &mut self,
+        owner: &Pubkey,
+        update_authority: CoreUpdateAuthority,
+        plugins: &[(CorePluginType, CorePluginAuthority)],
+    ) -> Pubkey {
+        let asset = Pubkey::new_unique();
+        let data = CoreAssetAccount {
+            owner: *owner,
+            update_authority,
+            name: "Ascendry Item #1".to_string(),
+            uri: "https://example.com/item".to_string(),
+            plugins: plugins
+                .iter()
+                .map(|(plugin_type, authority)| {
+                    (*plugin_type, *authority, default_core_plugin(*plugin_type))
+                })
+                .collect(),
+        }
+        .encode();
+        self.set_account(
+            asset,
+            StoredAccount {
+                lamports: Rent::default().minimum_balance(data.len()),
+                data,
+                owner: MPL_CORE_PROGRAM_ID,
+                executable: false,
+            },
+        );
+        asset
+    }
+
+    /**
+     * Stores a Bubblegum Merkle tree created by `tree_creator` that has minted `num_minted`
+     * leaves. The tree holds none of those leaves; leaves minted afterwards through the Bubblegum
+     * stand-in are stored in it and can be read back with `Harness::compressed_leaf`.
+     */
+    pub fn create_merkle_tree(&mut self, tree_creator: &Pubkey, num_minted: u64) -> Pubkey {
+        let merkle_tree = Pubkey::new_unique();
+        self.set_account(
+            merkle_tree,
+            StoredAccount {
+                lamports: 1_000_000_000,
+                data: vec![0; 1024],
+                owner: SPL_ACCOUNT_COMPRESSION_ID,
+                executable: false,
+            },
+        );
+        let mut discriminator = [0; 8];
+        discriminator.copy_from_slice(
+            &anchor_lang::solana_program::hash::hash(b"account:TreeConfig").to_bytes()[..8],
+        );
+        let tree_config = TreeConfig {
+            discriminator,
+            tree_creator: *tree_creator,
+            tree_delegate: *tree_creator,
+            total_mint_capacity: 1 << 14,
+            num_minted,
+            is_public: false,
+            is_decompressible: DecompressibleState::Disabled,
+        };
+        let data = tree_config.try_to_vec().unwrap();
+        self.set_account(
+            TreeConfig::find_pda(&merkle_tree).0,
+            StoredAccount {
+                lamports: Rent::default().minimum_balance(data.len()),
+                data,
+                owner: mpl_bubblegum::ID,
+                executable: false,
+            },
+        );
+        merkle_tree
+    }
+
+    /**
+     * Returns the leaf of a harness Merkle tree minted with `nonce` at the tree's current root,
+     * with its proof nodes, as an indexer would.
+     */
+    pub fn compressed_leaf(
+        &self,
+        merkle_tree: &Pubkey,
+        nonce: u64,
+    ) -> (CompressedLeaf, Vec<Pubkey>) {
+        let leaves = tree_leaves(&self.account(merkle_tree).unwrap().data);
+        let (index, leaf) = leaves
+            .iter()
+            .find(|(_, leaf)| leaf.nonce() == nonce)
+            .expect("no leaf with this nonce");
+        let (root, proof) = tree_proof(&leaves, *index);
+        let leaf = CompressedLeaf {
+            root,
+            data_hash: leaf.data_hash(),
+            creator_hash: leaf.creator_hash(),
+            nonce,
+            index: *index,
+        };
+        (
+            leaf,
+            proof.into_iter().map(Pubkey::new_from_array).collect(),
+        )
+    }
+
+    /// Returns the owner of the leaf of a harness Merkle tree minted with `nonce`.
+    pub fn compressed_leaf_owner(&self, merkle_tree: &Pubkey, nonce: u64) -> Pubkey {
+        tree_leaves(&self.account(merkle_tree).unwrap().data)
+            .into_iter()
+            .find(|(_, leaf)| leaf.nonce() == nonce)
+            .expect("no leaf with this nonce")
+            .1
+            .owner()
+    }
+}
+
+/**
+ * Lays out the instruction in the BPF loader's serialization format, into an 8-byte aligned
+ * buffer so the deserializer's direct u64 reads are aligned.
+ */
+fn serialize_input(
+    instruction: &Instruction,
+    keys: &[Pubkey],
+    writable: &[bool],
+    positions: &[usize],
+    accounts: &[StoredAccount],
+    signers: &[Pubkey],
+) -> Vec<u64> {
+    let mut bytes: Vec<u8> = Vec::new();
+    bytes.extend_from_slice(&(positions.len() as u64).to_le_bytes());
+    let mut serialized = vec![false; keys.len()];
+    for (meta_index, &position) in positions.iter().enumerate() {
+        if serialized[position] {
+            let first = positions.iter().position(|p| *p == position).unwrap();
+            bytes.push(first as u8);
+            bytes.extend_from_slice(&[0; 7]);
+            continue;
+        }
+        serialized[position] = true;
+        let account = &accounts[position];
+        debug_assert_eq!(bytes.len() % 8, 0, "account {meta_index} is misaligned");
+        bytes.push(u8::MAX);
+        bytes.push(signers.contains(&keys[position]) as u8);
+        bytes.push(writable[position] as u8);
+        bytes.push(account.executable as u8);
+        bytes.extend_from_slice(&[0; 4]);
+        bytes.extend_from_slice(keys[position].as_ref());
+        bytes.extend_from_slice(account.owner.as_ref());
+        debug_assert_eq!(bytes.len() % 8, ACCOUNT_HEADER_LEN % 8);
+        bytes.extend_from_slice(&account.lamports.to_le_bytes());
+        bytes.extend_from_slice(&(account.data.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(&account.data);
+        bytes.resize(bytes.len() + MAX_PERMITTED_DATA_INCREASE, 0);
+        bytes.resize(bytes.len().next_multiple_of(8), 0);
+        bytes.extend_from_slice(&u64::MAX.to_le_bytes());
+    }
+    bytes.extend_from_slice(&(instruction.data.len() as u64).to_le_bytes());
+    bytes.extend_from_slice(&instruction.data);
+    bytes.extend_from_slice(instruction.program_id.as_ref());
+
+    let mut input = vec![0u64; bytes.len().div_ceil(8)];
+    unsafe {
+        std::ptr::copy_nonoverlapping(bytes.as_ptr(), input.as_mut_ptr() as *mut u8, bytes.len())
+    };
+    input
+}
+
+/**
+ * Rewrites every occurrence of `from` in the instruction's accounts to `to`, for building
+ * instructions with deliberately mismatched accounts.
+ */
+/**
+ * The accounts a vault instruction must append for a mint created with
+ * `MintExtension::TransferHook`: the hook's validation account, program and extra account.
+ */
+pub fn transfer_hook_accounts(mint: &Pubkey) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new_readonly(
+            get_extra_account_metas_address(mint, &TRANSFER_HOOK_PROGRAM_ID),
+            false,
+        ),
+        AccountMeta::new_readonly(TRANSFER_HOOK_PROGRAM_ID, false),
+        AccountMeta::new_readonly(TRANSFER_HOOK_EXTRA_ACCOUNT, false),
+    ]
+}
+
+pub fn replace_account(instruction: &mut Instruction, from: &Pubkey, to: &Pubkey) {
+    for meta in instruction.accounts.iter_mut() {
+        if meta.pubkey == *from {
+            meta.pubkey = *to;
+        }
+    }
+}
+
+/**
+ * Decodes every event of type `T` emitted in the given transaction logs.
+ */
+pub fn events<T: Event>(logs: &[String]) -> Vec<T> {
+    logs.iter()
+        .filter_map(|log| log.strip_prefix("Program data: "))
+        .filter_map(|encoded| BASE64.decode(encoded).ok())
+        .filter(|data| data.starts_with(&T::DISCRIMINATOR))
+        .map(|data| T::try_from_slice(&data[8..]).expect("invalid event data"))
+        .collect()
+}
+
+/**
+ * Asserts that a transaction failed with the given Collectible Vault error.
+ */
+#[track_caller]
+pub fn assert_program_error<T: std::fmt::Debug>(
+    result: Result<T, TransactionError>,
+    expected: ErrorCode,
+) {
+    let error = result.expect_err("transaction should have failed").error;
+    assert_eq!(
+        collectible_vault_client::errors::decode_instruction_error(&error).map(u32::from),
+        Some(u32::from(expected)),
+        "expected {expected:?}, got {error:?}"
+    );
+}
+
+/**
+ * Asserts that a transaction failed with the given runtime error.
+ */
+#[track_caller]
+pub fn assert_instruction_error<T: std::fmt::Debug>(
+    result: Result<T, TransactionError>,
+    expected: InstructionError,
+) {
+    assert_eq!(
+        result.expect_err("transaction should have failed").error,
+        expected
+    );
+}
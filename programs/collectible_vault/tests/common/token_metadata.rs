@@ -0,0 +1,800 @@
+/**
+ * A native stand-in for the Metaplex Token Metadata program, enabled with
+ * `Harness::load_token_metadata`.
+ *
+ * It runs the instructions the vault invokes, encoded as in the published Token Metadata client,
+ * and keeps metadata, master edition and token record accounts in Token Metadata's layouts. As on
+ * chain, the master edition takes over the mint and freeze authorities of the NFT, so programmable
+ * NFTs stay frozen between transfers and delegated freezes go through it. Rule sets are not
+ * evaluated: a programmable transfer only checks that the NFT's rule set account is passed.
+ */
+use {
+    anchor_lang::{
+        prelude::Pubkey,
+        solana_program::{
+            account_info::AccountInfo, entrypoint::ProgramResult, instruction::Instruction,
+            program::invoke_signed, program_error::ProgramError, program_option::COption,
+            rent::Rent, system_instruction, system_program,
+        },
+        AnchorDeserialize, AnchorSerialize,
+    },
+    mpl_token_metadata::{
+        accounts::{MasterEdition, Metadata, TokenRecord},
+        instructions::{
+            BurnV1InstructionArgs, CreateMasterEditionV3InstructionArgs,
+            CreateMetadataAccountV3InstructionArgs, CreateV1InstructionArgs, MintV1InstructionArgs,
+            TransferV1InstructionArgs,
+        },
+        types::{
+            Collection, CollectionDetails, Key, PrintSupply, ProgrammableConfig, TokenStandard,
+            TokenState,
+        },
+    },
+    spl_token_2022::{
+        extension::StateWithExtensions,
+        state::{Account as TokenAccount, Mint},
+    },
+};
+
+const CREATE_MASTER_EDITION_V3: u8 = 17;
+const FREEZE_DELEGATED_ACCOUNT: u8 = 26;
+const THAW_DELEGATED_ACCOUNT: u8 = 27;
+const VERIFY_SIZED_COLLECTION_ITEM: u8 = 30;
+const CREATE_METADATA_ACCOUNT_V3: u8 = 33;
+const BURN: u8 = 41;
+const CREATE: u8 = 42;
+const MINT: u8 = 43;
+const TRANSFER: u8 = 49;
+const VERIFY: u8 = 52;
+
+/// Space Token Metadata allocates for every metadata and master edition account
+const MAX_METADATA_LEN: usize = 679;
+const MAX_MASTER_EDITION_LEN: usize = 282;
+
+fn account<'a, 'info>(
+    accounts: &'a [AccountInfo<'info>],
+    index: usize,
+) -> Result<&'a AccountInfo<'info>, ProgramError> {
+    accounts
+        .get(index)
+        .ok_or(ProgramError::NotEnoughAccountKeys)
+}
+
+/// Token Metadata takes its own program ID in place of an omitted optional account.
+fn optional<'a, 'info>(
+    accounts: &'a [AccountInfo<'info>],
+    index: usize,
+) -> Result<Option<&'a AccountInfo<'info>>, ProgramError> {
+    account(accounts, index).map(|info| (*info.key != mpl_token_metadata::ID).then_some(info))
+}
+
+fn signer(info: &AccountInfo) -> ProgramResult {
+    if info.is_signer {
+        Ok(())
+    } else {
+        Err(ProgramError::MissingRequiredSignature)
+    }
+}
+
+fn require(condition: bool) -> ProgramResult {
+    if condition {
+        Ok(())
+    } else {
+        Err(ProgramError::InvalidArgument)
+    }
+}
+
+fn metadata_seeds(mint: &Pubkey) -> Vec<Vec<u8>> {
+    vec![
+        b"metadata".to_vec(),
+        mpl_token_metadata::ID.to_bytes().to_vec(),
+        mint.to_bytes().to_vec(),
+    ]
+}
+
+fn edition_seeds(mint: &Pubkey) -> Vec<Vec<u8>> {
+    let mut seeds = metadata_seeds(mint);
+    seeds.push(b"edition".to_vec());
+    seeds
+}
+
+fn token_record_seeds(mint: &Pubkey, token: &Pubkey) -> Vec<Vec<u8>> {
+    let mut seeds = metadata_seeds(mint);
+    seeds.push(b"token_record".to_vec());
+    seeds.push(token.to_bytes().to_vec());
+    seeds
+}
+
+/// Appends the bump of the Token Metadata PDA derived from `seeds`, failing unless it is `expected`.
+fn signer_seeds(seeds: Vec<Vec<u8>>, expected: &Pubkey) -> Result<Vec<Vec<u8>>, ProgramError> {
+    let slices: Vec<&[u8]> = seeds.iter().map(Vec::as_slice).collect();
+    let (address, bump) = Pubkey::find_program_address(&slices, &mpl_token_metadata::ID);
+    if address != *expected {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let mut seeds = seeds;
+    seeds.push(vec![bump]);
+    Ok(seeds)
+}
+
+fn invoke_with_seeds(
+    instruction: &Instruction,
+    accounts: &[AccountInfo],
+    seeds: &[Vec<u8>],
+) -> ProgramResult {
+    let seeds: Vec<&[u8]> = seeds.iter().map(Vec::as_slice).collect();
+    invoke_signed(instruction, accounts, &[&seeds])
+}
+
+fn read<T: AnchorDeserialize>(info: &AccountInfo) -> Result<T, ProgramError> {
+    if *info.owner != mpl_token_metadata::ID {
+        return Err(ProgramError::IllegalOwner);
+    }
+    T::deserialize(&mut &info.try_borrow_data()?[..]).map_err(|_| ProgramError::InvalidAccountData)
+}
+
+/// Writes a Token Metadata account over its existing allocation, zero-filling the rest.
+fn write(info: &AccountInfo, account: &impl AnchorSerialize) -> ProgramResult {
+    let bytes = account.try_to_vec().unwrap();
+    let mut data = info.try_borrow_mut_data()?;
+    if bytes.len() > data.len() {
+        return Err(ProgramError::AccountDataTooSmall);
+    }
+    data.fill(0);
+    data[..bytes.len()].copy_from_slice(&bytes);
+    Ok(())
+}
+
+/// Creates a Token Metadata PDA holding `account`, allocated for at least `space` bytes.
+fn create<'info>(
+    payer: &AccountInfo<'info>,
+    target: &AccountInfo<'info>,
+    seeds: Vec<Vec<u8>>,
+    account: &impl AnchorSerialize,
+    space: usize,
+) -> ProgramResult {
+    let seeds = signer_seeds(seeds, target.key)?;
+    let space = space.max(account.try_to_vec().unwrap().len());
+    invoke_with_seeds(
+        &system_instruction::create_account(
+            payer.key,
+            target.key,
+            Rent::default().minimum_balance(space),
+            space as u64,
+            &mpl_token_metadata::ID,
+        ),
+        &[payer.clone(), target.clone()],
+        &seeds,
+    )?;
+    write(target, account)
+}
+
+/// Closes a Token Metadata account, refunding its rent to `destination`.
+fn close<'info>(info: &AccountInfo<'info>, destination: &AccountInfo<'info>) -> ProgramResult {
+    **destination.try_borrow_mut_lamports()? += info.lamports();
+    **info.try_borrow_mut_lamports()? = 0;
+    info.realloc(0, false)?;
+    info.assign(&system_program::ID);
+    Ok(())
+}
+
+fn read_mint(info: &AccountInfo) -> Result<Mint, ProgramError> {
+    Ok(StateWithExtensions::<Mint>::unpack(&info.try_borrow_data()?)?.base)
+}
+
+fn read_token_account(info: &AccountInfo) -> Result<TokenAccount, ProgramError> {
+    Ok(StateWithExtensions::<TokenAccount>::unpack(&info.try_borrow_data()?)?.base)
+}
+
+/// Reads the metadata of `mint`, which must be stored at its PDA.
+fn read_metadata(info: &AccountInfo, mint: &Pubkey) -> Result<Metadata, ProgramError> {
+    require(*info.key == Metadata::find_pda(mint).0)?;
+    let metadata: Metadata = read(info)?;
+    require(metadata.mint == *mint)?;
+    Ok(metadata)
+}
+
+/// Checks that the master edition of `mint` exists at its PDA.
+fn check_edition(info: &AccountInfo, mint: &Pubkey) -> ProgramResult {
+    require(*info.key == MasterEdition::find_pda(mint).0)?;
+    read::<MasterEdition>(info).map(|_| ())
+}
+
+fn is_programmable(metadata: &Metadata) -> bool {
+    matches!(
+        metadata.token_standard,
+        Some(TokenStandard::ProgrammableNonFungible)
+            | Some(TokenStandard::ProgrammableNonFungibleEdition)
+    )
+}
+
+/// Freezes or thaws a token account with the mint's master edition as freeze authority.
+fn set_frozen<'info>(
+    frozen: bool,
+    token_program: &AccountInfo<'info>,
+    token: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    edition: &AccountInfo<'info>,
+) -> ProgramResult {
+    let build = if frozen {
+        spl_token_2022::instruction::freeze_account
+    } else {
+        spl_token_2022::instruction::thaw_account
+    };
+    invoke_with_seeds(
+        &build(token_program.key, token.key, mint.key, edition.key, &[])?,
+        &[token.clone(), mint.clone(), edition.clone()],
+        &signer_seeds(edition_seeds(mint.key), edition.key)?,
+    )
+}
+
+/// Hands the mint and freeze authorities of a new NFT over to its master edition.
+fn transfer_mint_authorities<'info>(
+    token_program: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    mint_authority: &AccountInfo<'info>,
+    edition: &AccountInfo<'info>,
+) -> ProgramResult {
+    let current = read_mint(mint)?;
+    require(
+        current.mint_authority == COption::Some(*mint_authority.key)
+            && current.freeze_authority == COption::Some(*mint_authority.key),
+    )?;
+    for authority_type in [
+        spl_token_2022::instruction::AuthorityType::MintTokens,
+        spl_token_2022::instruction::AuthorityType::FreezeAccount,
+    ] {
+        invoke_signed(
+            &spl_token_2022::instruction::set_authority(
+                token_program.key,
+                mint.key,
+                Some(edition.key),
+                authority_type,
+                mint_authority.key,
+                &[],
+            )?,
+            &[mint.clone(), mint_authority.clone()],
+            &[],
+        )?;
+    }
+    Ok(())
+}
+
+/// Creates the token account of `owner` for `mint` if it does not exist yet.
+fn create_token_account_if_missing<'info>(
+    payer: &AccountInfo<'info>,
+    token: &AccountInfo<'info>,
+    owner: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+) -> ProgramResult {
+    if !token.data_is_empty() {
+        return Ok(());
+    }
+    invoke_signed(
+        &spl_associated_token_account::instruction::create_associated_token_account(
+            payer.key,
+            owner.key,
+            mint.key,
+            token_program.key,
+        ),
+        &[
+            payer.clone(),
+            token.clone(),
+            owner.clone(),
+            mint.clone(),
+            system_program.clone(),
+            token_program.clone(),
+        ],
+        &[],
+    )
+}
+
+fn new_token_record(bump_seeds: &[Vec<u8>]) -> TokenRecord {
+    TokenRecord {
+        key: Key::TokenRecord,
+        bump: bump_seeds.last().unwrap()[0],
+        state: TokenState::Unlocked,
+        rule_set_revision: None,
+        delegate: None,
+        delegate_role: None,
+        locked_transfer: None,
+    }
+}
+
+/// Creates the token record of a programmable NFT's token account.
+fn create_token_record<'info>(
+    payer: &AccountInfo<'info>,
+    token_record: &AccountInfo<'info>,
+    mint: &Pubkey,
+    token: &Pubkey,
+) -> ProgramResult {
+    let seeds = token_record_seeds(mint, token);
+    let record = new_token_record(&signer_seeds(seeds.clone(), token_record.key)?);
+    create(payer, token_record, seeds, &record, TokenRecord::LEN)
+}
+
+/// Marks `item` a verified member of the collection of `collection_mint`, growing a sized collection.
+fn verify_collection(
+    item: &AccountInfo,
+    authority: &AccountInfo,
+    collection_mint: &AccountInfo,
+    collection_metadata: &AccountInfo,
+    collection_edition: &AccountInfo,
+    sized: bool,
+) -> ProgramResult {
+    signer(authority)?;
+    let mut metadata: Metadata = read(item)?;
+    let mut collection = read_metadata(collection_metadata, collection_mint.key)?;
+    check_edition(collection_edition, collection_mint.key)?;
+    require(collection.update_authority == *authority.key)?;
+    require(
+        metadata.collection
+            == Some(Collection {
+                verified: false,
+                key: *collection_mint.key,
+            }),
+    )?;
+    metadata.collection = Some(Collection {
+        verified: true,
+        key: *collection_mint.key,
+    });
+    match &mut collection.collection_details {
+        Some(CollectionDetails::V1 { size }) => *size += 1,
+        _ => require(!sized)?,
+    }
+    write(item, &metadata)?;
+    write(collection_metadata, &collection)
+}
+
+pub(super) fn process_token_metadata_instruction(
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    let (&discriminator, mut args) = data
+        .split_first()
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    let invalid_data = |_| ProgramError::InvalidInstructionData;
+    // The V1 instructions carry a second discriminator for their variant
+    let mut variant = || {
+        let (&variant, rest) = args
+            .split_first()
+            .ok_or(ProgramError::InvalidInstructionData)?;
+        args = rest;
+        Ok::<u8, ProgramError>(variant)
+    };
+    let account = |index: usize| account(accounts, index);
+    let optional = |index: usize| optional(accounts, index);
+
+    match discriminator {
+        CREATE_METADATA_ACCOUNT_V3 => {
+            // Accounts: metadata, mint, mint authority, payer, update authority, system program
+            let (metadata_info, mint, mint_authority, payer, update_authority) = (
+                account(0)?,
+                account(1)?,
+                account(2)?,
+                account(3)?,
+                account(4)?,
+            );
+            signer(mint_authority)?;
+            signer(payer)?;
+            require(read_mint(mint)?.mint_authority == COption::Some(*mint_authority.key))?;
+            let CreateMetadataAccountV3InstructionArgs {
+                data,
+                is_mutable,
+                collection_details,
+            } = CreateMetadataAccountV3InstructionArgs::deserialize(&mut args)
+                .map_err(invalid_data)?;
+            // Only a signing update authority can mark itself a verified creator
+            for creator in data.creators.iter().flatten() {
+                require(
+                    !creator.verified
+                        || (creator.address == *update_authority.key && update_authority.is_signer),
+                )?;
+            }
+            require(data.collection.as_ref().is_none_or(|c| !c.verified))?;
+            let metadata = Metadata {
+                key: Key::MetadataV1,
+                update_authority: *update_authority.key,
+                mint: *mint.key,
+                name: data.name,
+                symbol: data.symbol,
+                uri: data.uri,
+                seller_fee_basis_points: data.seller_fee_basis_points,
+                creators: data.creators,
+                primary_sale_happened: false,
+                is_mutable,
+                edition_nonce: Some(MasterEdition::find_pda(mint.key).1),
+                token_standard: None,
+                collection: data.collection,
+                uses: data.uses,
+                collection_details,
+                programmable_config: None,
+            };
+            create(
+                payer,
+                metadata_info,
+                metadata_seeds(mint.key),
+                &metadata,
+                MAX_METADATA_LEN,
+            )
+        }
+        CREATE_MASTER_EDITION_V3 => {
+            // Accounts: edition, mint, update authority, mint authority, payer, metadata,
+            // token program, system program
+            let (edition, mint, update_authority, mint_authority, payer, metadata_info) = (
+                account(0)?,
+                account(1)?,
+                account(2)?,
+                account(3)?,
+                account(4)?,
+                account(5)?,
+            );
+            let token_program = account(6)?;
+            signer(update_authority)?;
+            signer(payer)?;
+            let CreateMasterEditionV3InstructionArgs { max_supply } =
+                CreateMasterEditionV3InstructionArgs::deserialize(&mut args)
+                    .map_err(invalid_data)?;
+            let mut metadata = read_metadata(metadata_info, mint.key)?;
+            require(metadata.update_authority == *update_authority.key)?;
+            let current = read_mint(mint)?;
+            require(current.decimals == 0 && current.supply == 1)?;
+            create(
+                payer,
+                edition,
+                edition_seeds(mint.key),
+                &MasterEdition {
+                    key: Key::MasterEditionV2,
+                    supply: 0,
+                    max_supply,
+                },
+                MAX_MASTER_EDITION_LEN,
+            )?;
+            transfer_mint_authorities(token_program, mint, mint_authority, edition)?;
+            metadata.token_standard = Some(TokenStandard::NonFungible);
+            write(metadata_info, &metadata)
+        }
+        VERIFY_SIZED_COLLECTION_ITEM => {
+            // Accounts: metadata, collection authority, payer, collection mint, collection
+            // metadata, collection master edition
+            verify_collection(
+                account(0)?,
+                account(1)?,
+                account(3)?,
+                account(4)?,
+                account(5)?,
+                true,
+            )
+        }
+        VERIFY => {
+            // Accounts: authority, delegate record, metadata, collection mint, collection
+            // metadata, collection master edition, system program, instructions sysvar
+            require(variant()? == 1)?;
+            verify_collection(
+                account(2)?,
+                account(0)?,
+                account(3)?,
+                optional(4)?.ok_or(ProgramError::NotEnoughAccountKeys)?,
+                optional(5)?.ok_or(ProgramError::NotEnoughAccountKeys)?,
+                false,
+            )
+        }
+        CREATE => {
+            // Accounts: metadata, master edition, mint, authority, payer, update authority,
+            // system program, instructions sysvar, token program
+            require(variant()? == 0)?;
+            let (metadata_info, edition, mint, authority, payer, update_authority) = (
+                account(0)?,
+                optional(1)?.ok_or(ProgramError::NotEnoughAccountKeys)?,
+                account(2)?,
+                account(3)?,
+                account(4)?,
+                account(5)?,
+            );
+            let token_program = optional(8)?.ok_or(ProgramError::NotEnoughAccountKeys)?;
+            signer(authority)?;
+            signer(payer)?;
+            let args = CreateV1InstructionArgs::deserialize(&mut args).map_err(invalid_data)?;
+            let current = read_mint(mint)?;
+            require(current.decimals == 0 && current.supply == 0)?;
+            require(current.mint_authority == COption::Some(*authority.key))?;
+            require(args.collection.as_ref().is_none_or(|c| !c.verified))?;
+            let programmable = args.token_standard == TokenStandard::ProgrammableNonFungible;
+            let metadata = Metadata {
+                key: Key::MetadataV1,
+                update_authority: *update_authority.key,
+                mint: *mint.key,
+                name: args.name,
+                symbol: args.symbol,
+                uri: args.uri,
+                seller_fee_basis_points: args.seller_fee_basis_points,
+                creators: args.creators,
+                primary_sale_happened: args.primary_sale_happened,
+                is_mutable: args.is_mutable,
+                edition_nonce: Some(MasterEdition::find_pda(mint.key).1),
+                token_standard: Some(args.token_standard),
+                collection: args.collection,
+                uses: args.uses,
+                collection_details: args.collection_details,
+                programmable_config: programmable.then_some(ProgrammableConfig::V1 {
+                    rule_set: args.rule_set,
+                }),
+            };
+            create(
+                payer,
+                metadata_info,
+                metadata_seeds(mint.key),
+                &metadata,
+                MAX_METADATA_LEN,
+            )?;
+            let max_supply = match args.print_supply {
+                Some(PrintSupply::Zero) | None => Some(0),
+                Some(PrintSupply::Limited(supply)) => Some(supply),
+                Some(PrintSupply::Unlimited) => None,
+            };
+            create(
+                payer,
+                edition,
+                edition_seeds(mint.key),
+                &MasterEdition {
+                    key: Key::MasterEditionV2,
+                    supply: 0,
+                    max_supply,
+                },
+                MAX_MASTER_EDITION_LEN,
+            )?;
+            transfer_mint_authorities(token_program, mint, authority, edition)
+        }
+        MINT => {
+            // Accounts: token, token owner, metadata, master edition, token record, mint,
+            // authority, delegate record, payer, system program, instructions sysvar,
+            // token program, associated token program
+            require(variant()? == 0)?;
+            let (token, metadata_info, mint, authority, payer) = (
+                account(0)?,
+                account(2)?,
+                account(5)?,
+                account(6)?,
+                account(8)?,
+            );
+            let edition = optional(3)?.ok_or(ProgramError::NotEnoughAccountKeys)?;
+            let (system_program, token_program) = (account(9)?, account(11)?);
+            signer(authority)?;
+            signer(payer)?;
+            let MintV1InstructionArgs { amount, .. } =
+                MintV1InstructionArgs::deserialize(&mut args).map_err(invalid_data)?;
+            let metadata = read_metadata(metadata_info, mint.key)?;
+            require(metadata.update_authority == *authority.key)?;
+            check_edition(edition, mint.key)?;
+            require(read_mint(mint)?.supply + amount <= 1)?;
+
+            let owner = optional(1)?.ok_or(ProgramError::NotEnoughAccountKeys)?;
+            create_token_account_if_missing(
+                payer,
+                token,
+                owner,
+                mint,
+                system_program,
+                token_program,
+            )?;
+            let token_account = read_token_account(token)?;
+            require(token_account.mint == *mint.key && token_account.owner == *owner.key)?;
+            invoke_with_seeds(
+                &spl_token_2022::instruction::mint_to(
+                    token_program.key,
+                    mint.key,
+                    token.key,
+                    edition.key,
+                    &[],
+                    amount,
+                )?,
+                &[mint.clone(), token.clone(), edition.clone()],
+                &signer_seeds(edition_seeds(mint.key), edition.key)?,
+            )?;
+
+            // A programmable NFT is kept frozen, its state tracked by a token record
+            if is_programmable(&metadata) {
+                let token_record = optional(4)?.ok_or(ProgramError::NotEnoughAccountKeys)?;
+                create_token_record(payer, token_record, mint.key, token.key)?;
+                set_frozen(true, token_program, token, mint, edition)?;
+            }
+            Ok(())
+        }
+        TRANSFER => {
+            // Accounts: token, token owner, destination token, destination owner, mint,
+            // metadata, edition, token record, destination token record, authority, payer,
+            // system program, instructions sysvar, token program, associated token program,
+            // authorization rules program, authorization rules
+            require(variant()? == 0)?;
+            let (token, token_owner, destination, destination_owner, mint, metadata_info) = (
+                account(0)?,
+                account(1)?,
+                account(2)?,
+                account(3)?,
+                account(4)?,
+                account(5)?,
+            );
+            let (authority, payer, system_program) = (account(9)?, account(10)?, account(11)?);
+            let token_program = optional(13)?.ok_or(ProgramError::NotEnoughAccountKeys)?;
+            signer(authority)?;
+            signer(payer)?;
+            let TransferV1InstructionArgs { amount, .. } =
+                TransferV1InstructionArgs::deserialize(&mut args).map_err(invalid_data)?;
+            let metadata = read_metadata(metadata_info, mint.key)?;
+
+            // Only the owner of the source token account transfers the NFTs the vault moves
+            let source = read_token_account(token)?;
+            require(source.mint == *mint.key && source.owner == *token_owner.key)?;
+            require(*authority.key == source.owner && source.amount >= amount)?;
+            create_token_account_if_missing(
+                payer,
+                destination,
+                destination_owner,
+                mint,
+                system_program,
+                token_program,
+            )?;
+            let target = read_token_account(destination)?;
+            require(target.mint == *mint.key && target.owner == *destination_owner.key)?;
+
+            let programmable = is_programmable(&metadata);
+            let edition = optional(6)?;
+            if programmable {
+                let edition = edition.ok_or(ProgramError::NotEnoughAccountKeys)?;
+                check_edition(edition, mint.key)?;
+                let token_record = optional(7)?.ok_or(ProgramError::NotEnoughAccountKeys)?;
+                require(*token_record.key == TokenRecord::find_pda(mint.key, token.key).0)?;
+                let record: TokenRecord = read(token_record)?;
+                require(record.state == TokenState::Unlocked)?;
+                if let Some(ProgrammableConfig::V1 {
+                    rule_set: Some(rule_set),
+                }) = metadata.programmable_config
+                {
+                    require(optional(16)?.is_some_and(|rules| *rules.key == rule_set))?;
+                }
+                let destination_record = optional(8)?.ok_or(ProgramError::NotEnoughAccountKeys)?;
+                if destination_record.data_is_empty() {
+                    create_token_record(payer, destination_record, mint.key, destination.key)?;
+                }
+                set_frozen(false, token_program, token, mint, edition)?;
+                if target.is_frozen() {
+                    set_frozen(false, token_program, destination, mint, edition)?;
+                }
+            }
+
+            let decimals = read_mint(mint)?.decimals;
+            invoke_signed(
+                &spl_token_2022::instruction::transfer_checked(
+                    token_program.key,
+                    token.key,
+                    mint.key,
+                    destination.key,
+                    authority.key,
+                    &[],
+                    amount,
+                    decimals,
+                )?,
+                &[
+                    token.clone(),
+                    mint.clone(),
+                    destination.clone(),
+                    authority.clone(),
+                ],
+                &[],
+            )?;
+
+            // Both token accounts of a programmable NFT stay frozen by its master edition
+            if programmable {
+                let edition = edition.unwrap();
+                set_frozen(true, token_program, token, mint, edition)?;
+                set_frozen(true, token_program, destination, mint, edition)?;
+            }
+            Ok(())
+        }
+        FREEZE_DELEGATED_ACCOUNT | THAW_DELEGATED_ACCOUNT => {
+            // Accounts: delegate, token account, edition, mint, token program
+            let (delegate, token, edition, mint, token_program) = (
+                account(0)?,
+                account(1)?,
+                account(2)?,
+                account(3)?,
+                account(4)?,
+            );
+            signer(delegate)?;
+            check_edition(edition, mint.key)?;
+            let token_account = read_token_account(token)?;
+            require(
+                token_account.mint == *mint.key
+                    && token_account.delegate == COption::Some(*delegate.key),
+            )?;
+            let freeze = discriminator == FREEZE_DELEGATED_ACCOUNT;
+            require(token_account.is_frozen() != freeze)?;
+            set_frozen(freeze, token_program, token, mint, edition)
+        }
+        BURN => {
+            // Accounts: authority, collection metadata, metadata, edition, mint, token,
+            // master edition, master edition mint, master edition token, edition marker,
+            // token record, system program, instructions sysvar, token program
+            require(variant()? == 0)?;
+            let (authority, metadata_info, mint, token, token_program) = (
+                account(0)?,
+                account(2)?,
+                account(4)?,
+                account(5)?,
+                account(13)?,
+            );
+            let edition = optional(3)?.ok_or(ProgramError::NotEnoughAccountKeys)?;
+            signer(authority)?;
+            let BurnV1InstructionArgs { amount } =
+                BurnV1InstructionArgs::deserialize(&mut args).map_err(invalid_data)?;
+            let metadata = read_metadata(metadata_info, mint.key)?;
+            check_edition(edition, mint.key)?;
+            let token_account = read_token_account(token)?;
+            require(
+                token_account.mint == *mint.key
+                    && token_account.owner == *authority.key
+                    && token_account.amount == amount
+                    && amount == 1,
+            )?;
+
+            // Burning a verified member shrinks its collection
+            if let Some(Collection {
+                verified: true,
+                key: collection_mint,
+            }) = metadata.collection
+            {
+                let collection_info = optional(1)?.ok_or(ProgramError::NotEnoughAccountKeys)?;
+                let mut collection = read_metadata(collection_info, &collection_mint)?;
+                if let Some(CollectionDetails::V1 { size }) = &mut collection.collection_details {
+                    *size = size.saturating_sub(1);
+                }
+                write(collection_info, &collection)?;
+            }
+
+            let token_record = if is_programmable(&metadata) {
+                let token_record = optional(10)?.ok_or(ProgramError::NotEnoughAccountKeys)?;
+                require(*token_record.key == TokenRecord::find_pda(mint.key, token.key).0)?;
+                let record: TokenRecord = read(token_record)?;
+                require(record.state == TokenState::Unlocked)?;
+                Some(token_record)
+            } else {
+                None
+            };
+            if token_account.is_frozen() {
+                set_frozen(false, token_program, token, mint, edition)?;
+            }
+            invoke_signed(
+                &spl_token_2022::instruction::burn(
+                    token_program.key,
+                    token.key,
+                    mint.key,
+                    authority.key,
+                    &[],
+                    amount,
+                )?,
+                &[token.clone(), mint.clone(), authority.clone()],
+                &[],
+            )?;
+            invoke_signed(
+                &spl_token_2022::instruction::close_account(
+                    token_program.key,
+                    token.key,
+                    authority.key,
+                    authority.key,
+                    &[],
+                )?,
+                &[token.clone(), authority.clone()],
+                &[],
+            )?;
+            close(metadata_info, authority)?;
+            close(edition, authority)?;
+            if let Some(token_record) = token_record {
+                close(token_record, authority)?;
+            }
+            Ok(())
+        }
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
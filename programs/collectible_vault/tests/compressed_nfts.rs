@@ -0,0 +1,417 @@
+/**
+ * Compressed NFT tests for minting into a Bubblegum Merkle tree and redeeming compressed NFTs.
+ *
+ * Minting and moving leaves are CPIs into Bubblegum, which the happy path tests run with the
+ * harness's Bubblegum stand-in, minting against a collection created through its Token Metadata
+ * stand-in. The other tests leave Bubblegum unloaded, so reaching its CPI fails.
+ */
+mod common;
+
+use {
+    anchor_lang::{
+        error::ErrorCode as AnchorErrorCode,
+        prelude::Pubkey,
+        solana_program::instruction::{Instruction, InstructionError},
+    },
+    collectible_vault::{
+        constants::admin_constants::get_admin_account_pubkey,
+        errors::ErrorCode,
+        events::CompressedNftMinted,
+        instructions::compressed_nft::{compressed_asset_id, CompressedLeaf},
+        state::{AccountHeader, AssetRedemptionInfo, CollectionCounter},
+    },
+    collectible_vault_client::{instructions, pda},
+    common::{assert_instruction_error, assert_program_error, events, Harness},
+};
+
+fn leaf(nonce: u64) -> CompressedLeaf {
+    CompressedLeaf {
+        root: [1; 32],
+        data_hash: [2; 32],
+        creator_hash: [3; 32],
+        nonce,
+        index: nonce as u32,
+    }
+}
+
+fn mint_instruction(payer: &Pubkey, merkle_tree: &Pubkey, collection_mint: &Pubkey) -> Instruction {
+    instructions::mint_compressed_nft(
+        payer,
+        merkle_tree,
+        &Pubkey::new_unique(),
+        collection_mint,
+        "https://example.com/item".to_string(),
+    )
+}
+
+/// Stores a redemption request for the compressed NFT with `nonce`, as if its leaf had been escrowed
+fn set_redemption_request(harness: &mut Harness, owner: &Pubkey, merkle_tree: &Pubkey, nonce: u64) {
+    let asset_id = compressed_asset_id(merkle_tree, nonce);
+    harness.set_program_account(
+        pda::asset_redemption_info(&asset_id).0,
+        &AssetRedemptionInfo {
+            header: AccountHeader::new(AssetRedemptionInfo::VERSION),
+            nft_mint: asset_id,
+            nft_owner: *owner,
+            request_timestamp: harness.now(),
+            is_fulfilled: false,
+        },
+    );
+}
+
+/// Mints a compressed NFT of a new vault collection to `owner` into a new tree, with Bubblegum loaded
+fn mint_leaf(harness: &mut Harness, owner: &Pubkey) -> Pubkey {
+    harness.load_token_metadata();
+    harness.load_bubblegum();
+    let admin = get_admin_account_pubkey();
+    let collection_mint = harness.create_collection();
+    let merkle_tree = harness.create_merkle_tree(&admin, 0);
+    harness
+        .process_transaction(
+            &[instructions::mint_compressed_nft(
+                &admin,
+                &merkle_tree,
+                owner,
+                &collection_mint,
+                "https://example.com/item".to_string(),
+            )],
+            &[admin],
+        )
+        .unwrap();
+    merkle_tree
+}
+
+fn request_redemption(harness: &mut Harness, owner: &Pubkey, merkle_tree: &Pubkey) {
+    let (leaf, proof) = harness.compressed_leaf(merkle_tree, 0);
+    harness
+        .process_transaction(
+            &[instructions::create_compressed_asset_redemption_request(
+                owner,
+                owner,
+                merkle_tree,
+                leaf,
+                &proof,
+            )],
+            &[*owner],
+        )
+        .unwrap();
+}
+
+#[test]
+fn mint_compressed_nft_mints_a_leaf_to_the_owner() {
+    let mut harness = Harness::new();
+    harness.load_token_metadata();
+    harness.load_bubblegum();
+    let admin = get_admin_account_pubkey();
+    let owner = harness.new_wallet();
+    let collection_mint = harness.create_collection();
+    let merkle_tree = harness.create_merkle_tree(&admin, 0);
+
+    let logs = harness
+        .process_transaction(
+            &[instructions::mint_compressed_nft(
+                &admin,
+                &merkle_tree,
+                &owner,
+                &collection_mint,
+                "https://example.com/item".to_string(),
+            )],
+            &[admin],
+        )
+        .unwrap();
+
+    assert_eq!(harness.compressed_leaf_owner(&merkle_tree, 0), owner);
+    let minted = events::<CompressedNftMinted>(&logs);
+    assert_eq!(minted[0].asset_id, compressed_asset_id(&merkle_tree, 0));
+    assert_eq!(minted[0].nonce, 0);
+    assert_eq!(minted[0].item_number, 1);
+    let counter: CollectionCounter = harness.anchor_account(&pda::collection_counter().0);
+    assert_eq!(counter.count, 1);
+
+    // The next leaf takes the next nonce
+    harness
+        .process_transaction(
+            &[instructions::mint_compressed_nft(
+                &admin,
+                &merkle_tree,
+                &owner,
+                &collection_mint,
+                "https://example.com/item".to_string(),
+            )],
+            &[admin],
+        )
+        .unwrap();
+    assert_eq!(harness.compressed_leaf_owner(&merkle_tree, 1), owner);
+}
+
+#[test]
+fn compressed_redemption_request_escrows_the_leaf_until_cancelled() {
+    let mut harness = Harness::new();
+    let owner = harness.new_wallet();
+    let merkle_tree = mint_leaf(&mut harness, &owner);
+    let asset_id = compressed_asset_id(&merkle_tree, 0);
+
+    request_redemption(&mut harness, &owner, &merkle_tree);
+    assert_eq!(
+        harness.compressed_leaf_owner(&merkle_tree, 0),
+        pda::asset_redemption_vault().0
+    );
+    let info: AssetRedemptionInfo =
+        harness.anchor_account(&pda::asset_redemption_info(&asset_id).0);
+    assert_eq!(info.nft_mint, asset_id);
+    assert_eq!(info.nft_owner, owner);
+
+    // The leaf moved, so cancelling needs its proof at the new root
+    let (leaf, proof) = harness.compressed_leaf(&merkle_tree, 0);
+    harness
+        .process_transaction(
+            &[instructions::cancel_compressed_asset_redemption_request(
+                &owner,
+                &merkle_tree,
+                leaf,
+                &proof,
+            )],
+            &[owner],
+        )
+        .unwrap();
+    assert_eq!(harness.compressed_leaf_owner(&merkle_tree, 0), owner);
+    assert!(harness
+        .account(&pda::asset_redemption_info(&asset_id).0)
+        .is_none());
+}
+
+#[test]
+fn compressed_redemption_request_rejects_a_stale_proof() {
+    let mut harness = Harness::new();
+    let owner = harness.new_wallet();
+    let merkle_tree = mint_leaf(&mut harness, &owner);
+    let (leaf, proof) = harness.compressed_leaf(&merkle_tree, 0);
+    let mut stale = proof.clone();
+    stale[0] = Pubkey::new_unique();
+
+    let result = harness.process_transaction(
+        &[instructions::create_compressed_asset_redemption_request(
+            &owner,
+            &owner,
+            &merkle_tree,
+            leaf,
+            &stale,
+        )],
+        &[owner],
+    );
+    assert_instruction_error(result, InstructionError::InvalidArgument);
+    assert_eq!(harness.compressed_leaf_owner(&merkle_tree, 0), owner);
+}
+
+#[test]
+fn admin_fulfills_an_escrowed_compressed_redemption_request() {
+    let mut harness = Harness::new();
+    let admin = get_admin_account_pubkey();
+    let owner = harness.new_wallet();
+    let merkle_tree = mint_leaf(&mut harness, &owner);
+    request_redemption(&mut harness, &owner, &merkle_tree);
+
+    harness
+        .process_transaction(
+            &[instructions::fulfill_compressed_asset_redemption_request(
+                &admin,
+                &merkle_tree,
+                0,
+            )],
+            &[admin],
+        )
+        .unwrap();
+    let info: AssetRedemptionInfo = harness
+        .anchor_account(&pda::asset_redemption_info(&compressed_asset_id(&merkle_tree, 0)).0);
+    assert!(info.is_fulfilled);
+    assert_eq!(
+        harness.compressed_leaf_owner(&merkle_tree, 0),
+        pda::asset_redemption_vault().0
+    );
+}
+
+#[test]
+fn mint_compressed_nft_is_admin_only() {
+    let mut harness = Harness::new();
+    let payer = harness.new_wallet();
+    let collection_mint = harness.create_nft(&payer);
+    harness.set_collection_counter(&collection_mint, 0);
+    let merkle_tree = harness.create_merkle_tree(&payer, 0);
+
+    let result = harness.process_transaction(
+        &[mint_instruction(&payer, &merkle_tree, &collection_mint)],
+        &[payer],
+    );
+    assert_program_error(result, ErrorCode::UnauthorizedTransactionSigner);
+}
+
+#[test]
+fn mint_compressed_nft_rejects_a_foreign_collection() {
+    let mut harness = Harness::new();
+    let admin = get_admin_account_pubkey();
+    harness.airdrop(&admin, 10_000_000_000);
+    let collection_mint = harness.create_nft(&admin);
+    let foreign_collection_mint = harness.create_nft(&admin);
+    harness.set_collection_counter(&collection_mint, 0);
+    let merkle_tree = harness.create_merkle_tree(&admin, 0);
+
+    let result = harness.process_transaction(
+        &[mint_instruction(
+            &admin,
+            &merkle_tree,
+            &foreign_collection_mint,
+        )],
+        &[admin],
+    );
+    assert_program_error(result, ErrorCode::CollectionMintDoesNotMatch);
+}
+
+#[test]
+fn mint_compressed_nft_requires_a_bubblegum_tree() {
+    let mut harness = Harness::new();
+    let admin = get_admin_account_pubkey();
+    harness.airdrop(&admin, 10_000_000_000);
+    let collection_mint = harness.create_nft(&admin);
+    harness.set_collection_counter(&collection_mint, 0);
+
+    // No tree config exists for a tree that was never created with Bubblegum
+    let result = harness.process_transaction(
+        &[mint_instruction(
+            &admin,
+            &Pubkey::new_unique(),
+            &collection_mint,
+        )],
+        &[admin],
+    );
+    assert_instruction_error(
+        result,
+        InstructionError::Custom(AnchorErrorCode::ConstraintOwner.into()),
+    );
+}
+
+#[test]
+fn mint_compressed_nft_rolls_back_when_the_bubblegum_mint_fails() {
+    let mut harness = Harness::new();
+    let admin = get_admin_account_pubkey();
+    harness.airdrop(&admin, 10_000_000_000);
+    let collection_mint = harness.create_nft(&admin);
+    harness.set_collection_counter(&collection_mint, 3);
+    let merkle_tree = harness.create_merkle_tree(&admin, 5);
+
+    // Everything up to the Bubblegum CPI succeeds; its failure must undo the counter increment
+    let result = harness.process_transaction(
+        &[mint_instruction(&admin, &merkle_tree, &collection_mint)],
+        &[admin],
+    );
+    assert_instruction_error(result, InstructionError::IncorrectProgramId);
+
+    let counter: CollectionCounter = harness.anchor_account(&pda::collection_counter().0);
+    assert_eq!(counter.count, 3);
+}
+
+#[test]
+fn compressed_redemption_request_reaches_the_bubblegum_transfer() {
+    let mut harness = Harness::new();
+    let owner = harness.new_wallet();
+    let merkle_tree = harness.create_merkle_tree(&get_admin_account_pubkey(), 1);
+
+    let result = harness.process_transaction(
+        &[instructions::create_compressed_asset_redemption_request(
+            &owner,
+            &owner,
+            &merkle_tree,
+            leaf(0),
+            &[Pubkey::new_unique(), Pubkey::new_unique()],
+        )],
+        &[owner],
+    );
+    assert_instruction_error(result, InstructionError::IncorrectProgramId);
+    assert!(harness
+        .account(&pda::asset_redemption_info(&compressed_asset_id(&merkle_tree, 0)).0)
+        .is_none());
+}
+
+#[test]
+fn compressed_redemption_request_can_only_be_cancelled_by_its_owner() {
+    let mut harness = Harness::new();
+    let owner = harness.new_wallet();
+    let other = harness.new_wallet();
+    let merkle_tree = harness.create_merkle_tree(&get_admin_account_pubkey(), 1);
+    set_redemption_request(&mut harness, &owner, &merkle_tree, 0);
+
+    let result = harness.process_transaction(
+        &[instructions::cancel_compressed_asset_redemption_request(
+            &other,
+            &merkle_tree,
+            leaf(0),
+            &[],
+        )],
+        &[other],
+    );
+    assert_program_error(result, ErrorCode::UnauthorizedRedemptionRequest);
+
+    // The owner gets as far as the Bubblegum transfer back to them
+    let result = harness.process_transaction(
+        &[instructions::cancel_compressed_asset_redemption_request(
+            &owner,
+            &merkle_tree,
+            leaf(0),
+            &[],
+        )],
+        &[owner],
+    );
+    assert_instruction_error(result, InstructionError::IncorrectProgramId);
+}
+
+#[test]
+fn admin_fulfills_a_compressed_redemption_request() {
+    let mut harness = Harness::new();
+    let admin = get_admin_account_pubkey();
+    harness.airdrop(&admin, 1_000_000_000);
+    let owner = harness.new_wallet();
+    let merkle_tree = harness.create_merkle_tree(&admin, 4);
+    set_redemption_request(&mut harness, &owner, &merkle_tree, 3);
+
+    harness
+        .process_transaction(
+            &[instructions::fulfill_compressed_asset_redemption_request(
+                &admin,
+                &merkle_tree,
+                3,
+            )],
+            &[admin],
+        )
+        .unwrap();
+    let info: AssetRedemptionInfo = harness
+        .anchor_account(&pda::asset_redemption_info(&compressed_asset_id(&merkle_tree, 3)).0);
+    assert!(info.is_fulfilled);
+
+    let result = harness.process_transaction(
+        &[instructions::cancel_compressed_asset_redemption_request(
+            &owner,
+            &merkle_tree,
+            leaf(3),
+            &[],
+        )],
+        &[owner],
+    );
+    assert_program_error(result, ErrorCode::RedemptionRequestAlreadyFulfilled);
+}
+
+#[test]
+fn compressed_redemption_fulfillment_is_admin_only() {
+    let mut harness = Harness::new();
+    let owner = harness.new_wallet();
+    let merkle_tree = harness.create_merkle_tree(&get_admin_account_pubkey(), 1);
+    set_redemption_request(&mut harness, &owner, &merkle_tree, 0);
+
+    let result = harness.process_transaction(
+        &[instructions::fulfill_compressed_asset_redemption_request(
+            &owner,
+            &merkle_tree,
+            0,
+        )],
+        &[owner],
+    );
+    assert_program_error(result, ErrorCode::UnauthorizedRedemptionRequest);
+}
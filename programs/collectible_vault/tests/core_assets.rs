@@ -0,0 +1,549 @@
+/**
+ * Metaplex Core tests for minting Core assets and locking them as loan or redemption collateral.
+ *
+ * Every Core instruction ends in a CPI into the Metaplex Core program. The validation tests leave
+ * Core unloaded, so those CPIs fail, and the end-to-end tests run them with the harness's Core
+ * stand-in.
+ */
+mod common;
+
+use {
+    anchor_lang::{
+        error::ErrorCode as AnchorErrorCode,
+        prelude::Pubkey,
+        solana_program::{
+            instruction::{AccountMeta, Instruction, InstructionError},
+            system_program,
+        },
+    },
+    collectible_vault::{
+        constants::admin_constants::get_admin_account_pubkey,
+        errors::ErrorCode,
+        instructions::core_asset::{CorePluginAuthority, CorePluginType, MPL_CORE_PROGRAM_ID},
+        state::{AssetRedemptionInfo, CollectionCounter, LoanOutcome, LoanRecord},
+    },
+    collectible_vault_client::{instructions, pda},
+    common::{assert_instruction_error, assert_program_error, Harness},
+};
+
+const LOAN_AMOUNT: u64 = 2_000_000_000;
+const INTEREST_AMOUNT: u64 = 100_000_000;
+const DURATION: i64 = 7 * 24 * 60 * 60;
+
+fn stake_instruction(borrower: &Pubkey, asset: &Pubkey) -> Instruction {
+    instructions::stake_core_asset_for_loan(
+        borrower,
+        asset,
+        None,
+        LOAN_AMOUNT,
+        INTEREST_AMOUNT,
+        DURATION,
+        None,
+    )
+}
+
+#[test]
+fn mint_core_asset_is_admin_only() {
+    let mut harness = Harness::new();
+    let payer = harness.new_wallet();
+    let owner = harness.new_wallet();
+    harness.set_collection_counter(&Pubkey::new_unique(), 0);
+    let asset = Pubkey::new_unique();
+
+    let result = harness.process_transaction(
+        &[instructions::mint_core_asset(
+            &payer,
+            &asset,
+            &owner,
+            "https://example.com/item".to_string(),
+        )],
+        &[payer, asset],
+    );
+    assert_program_error(result, ErrorCode::UnauthorizedTransactionSigner);
+}
+
+#[test]
+fn mint_core_asset_rolls_back_when_core_creation_fails() {
+    let mut harness = Harness::new();
+    let admin = get_admin_account_pubkey();
+    harness.airdrop(&admin, 10_000_000_000);
+    let owner = harness.new_wallet();
+    harness.set_collection_counter(&Pubkey::new_unique(), 3);
+    let asset = Pubkey::new_unique();
+
+    // Everything up to the Core CPI succeeds; its failure must undo the counter increment
+    let result = harness.process_transaction(
+        &[instructions::mint_core_asset(
+            &admin,
+            &asset,
+            &owner,
+            "https://example.com/item".to_string(),
+        )],
+        &[admin, asset],
+    );
+    assert_instruction_error(result, InstructionError::IncorrectProgramId);
+
+    let counter: CollectionCounter = harness.anchor_account(&pda::collection_counter().0);
+    assert_eq!(counter.count, 3);
+    assert!(harness.account(&asset).is_none());
+}
+
+#[test]
+fn stake_core_asset_reaches_the_core_lock() {
+    let mut harness = Harness::new();
+    let borrower = harness.new_wallet();
+    let asset = harness.create_core_asset(
+        &borrower,
+        &[(
+            CorePluginType::Royalties,
+            CorePluginAuthority::UpdateAuthority,
+        )],
+    );
+
+    // Core is not loaded in the harness, so reaching its first CPI fails here
+    let result = harness.process_transaction(&[stake_instruction(&borrower, &asset)], &[borrower]);
+    assert_instruction_error(result, InstructionError::IncorrectProgramId);
+    assert!(harness.account(&pda::loan_info(&asset).0).is_none());
+}
+
+#[test]
+fn stake_core_asset_requires_the_asset_owner() {
+    let mut harness = Harness::new();
+    let owner = harness.new_wallet();
+    let borrower = harness.new_wallet();
+    let asset = harness.create_core_asset(&owner, &[]);
+
+    let result = harness.process_transaction(&[stake_instruction(&borrower, &asset)], &[borrower]);
+    assert_program_error(result, ErrorCode::InvalidNFTAccount);
+    assert!(harness.account(&pda::loan_info(&asset).0).is_none());
+}
+
+#[test]
+fn stake_core_asset_rejects_accounts_not_owned_by_core() {
+    let mut harness = Harness::new();
+    let borrower = harness.new_wallet();
+    let nft_mint = harness.create_nft(&borrower);
+
+    let result =
+        harness.process_transaction(&[stake_instruction(&borrower, &nft_mint)], &[borrower]);
+    assert_instruction_error(
+        result,
+        InstructionError::Custom(AnchorErrorCode::ConstraintOwner.into()),
+    );
+}
+
+#[test]
+fn stake_core_asset_rejects_permanent_delegates() {
+    for plugin_type in [
+        CorePluginType::BurnDelegate,
+        CorePluginType::PermanentFreezeDelegate,
+        CorePluginType::PermanentTransferDelegate,
+        CorePluginType::PermanentBurnDelegate,
+    ] {
+        let mut harness = Harness::new();
+        let borrower = harness.new_wallet();
+        let asset = harness.create_core_asset(
+            &borrower,
+            &[(plugin_type, CorePluginAuthority::UpdateAuthority)],
+        );
+
+        let result =
+            harness.process_transaction(&[stake_instruction(&borrower, &asset)], &[borrower]);
+        assert_program_error(result, ErrorCode::UnsupportedCoreAsset);
+    }
+}
+
+#[test]
+fn stake_core_asset_rejects_undecodable_assets() {
+    let mut harness = Harness::new();
+    let borrower = harness.new_wallet();
+    let asset = harness.create_core_asset(&borrower, &[]);
+    let mut stored = harness.account(&asset).unwrap().clone();
+    stored.data[0] = 5; // Not an AssetV1
+    harness.set_account(asset, stored);
+
+    let result = harness.process_transaction(&[stake_instruction(&borrower, &asset)], &[borrower]);
+    assert_program_error(result, ErrorCode::UnsupportedCoreAsset);
+}
+
+#[test]
+fn core_asset_redemption_request_reaches_the_core_lock() {
+    let mut harness = Harness::new();
+    let owner = harness.new_wallet();
+    let asset = harness.create_core_asset(&owner, &[]);
+
+    let result = harness.process_transaction(
+        &[instructions::create_core_asset_redemption_request(
+            &owner, &asset, None,
+        )],
+        &[owner],
+    );
+    assert_instruction_error(result, InstructionError::IncorrectProgramId);
+    assert!(harness
+        .account(&pda::asset_redemption_info(&asset).0)
+        .is_none());
+}
+
+#[test]
+fn core_asset_redemption_request_requires_the_asset_owner() {
+    let mut harness = Harness::new();
+    let owner = harness.new_wallet();
+    let other = harness.new_wallet();
+    let asset = harness.create_core_asset(&owner, &[]);
+
+    let result = harness.process_transaction(
+        &[instructions::create_core_asset_redemption_request(
+            &other, &asset, None,
+        )],
+        &[other],
+    );
+    assert_program_error(result, ErrorCode::InvalidNFTAccount);
+}
+
+#[test]
+fn stake_core_asset_requires_the_asset_collection() {
+    let mut harness = Harness::new();
+    let borrower = harness.new_wallet();
+    let collection = Pubkey::new_unique();
+    let asset = harness.create_core_asset_in_collection(&borrower, &collection, &[]);
+
+    for passed in [None, Some(Pubkey::new_unique())] {
+        let result = harness.process_transaction(
+            &[instructions::stake_core_asset_for_loan(
+                &borrower,
+                &asset,
+                passed.as_ref(),
+                LOAN_AMOUNT,
+                INTEREST_AMOUNT,
+                DURATION,
+                None,
+            )],
+            &[borrower],
+        );
+        assert_program_error(result, ErrorCode::InvalidCoreCollection);
+    }
+}
+
+#[test]
+fn stake_core_asset_rejects_a_collection_for_assets_outside_one() {
+    let mut harness = Harness::new();
+    let borrower = harness.new_wallet();
+    let asset = harness.create_core_asset(&borrower, &[]);
+
+    let result = harness.process_transaction(
+        &[instructions::stake_core_asset_for_loan(
+            &borrower,
+            &asset,
+            Some(&Pubkey::new_unique()),
+            LOAN_AMOUNT,
+            INTEREST_AMOUNT,
+            DURATION,
+            None,
+        )],
+        &[borrower],
+    );
+    assert_program_error(result, ErrorCode::InvalidCoreCollection);
+}
+
+// The expected encodings below follow the published Metaplex Core IDL rather than the program's
+// own serialization, so a drift between the two shows up here even though Core cannot run.
+
+#[test]
+fn stake_core_asset_adds_the_transfer_delegate_as_core_encodes_it() {
+    let mut harness = Harness::new();
+    let borrower = harness.new_wallet();
+    let collection = Pubkey::new_unique();
+    let asset = harness.create_core_asset_in_collection(&borrower, &collection, &[]);
+
+    let error = harness
+        .process_transaction(
+            &[instructions::stake_core_asset_for_loan(
+                &borrower,
+                &asset,
+                Some(&collection),
+                LOAN_AMOUNT,
+                INTEREST_AMOUNT,
+                DURATION,
+                None,
+            )],
+            &[borrower],
+        )
+        .unwrap_err();
+    assert_eq!(error.error, InstructionError::IncorrectProgramId);
+
+    let add_plugin = error.invoked.last().unwrap();
+    assert_eq!(add_plugin.program_id, MPL_CORE_PROGRAM_ID);
+    // AddPluginV1 { plugin: Plugin::TransferDelegate, init_authority: Some(Authority::Address) }
+    let mut data = vec![2, 3, 1, 3];
+    data.extend_from_slice(pda::vault_authority().0.as_ref());
+    assert_eq!(add_plugin.data, data);
+    assert_eq!(
+        add_plugin.accounts,
+        vec![
+            AccountMeta::new(asset, false),
+            AccountMeta::new(collection, false),
+            AccountMeta::new(borrower, true),
+            AccountMeta::new_readonly(borrower, true),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(MPL_CORE_PROGRAM_ID, false),
+        ]
+    );
+}
+
+#[test]
+fn stake_core_asset_approves_an_existing_transfer_delegate_as_core_encodes_it() {
+    let mut harness = Harness::new();
+    let borrower = harness.new_wallet();
+    let asset = harness.create_core_asset(
+        &borrower,
+        &[(CorePluginType::TransferDelegate, CorePluginAuthority::Owner)],
+    );
+
+    let error = harness
+        .process_transaction(&[stake_instruction(&borrower, &asset)], &[borrower])
+        .unwrap_err();
+    assert_eq!(error.error, InstructionError::IncorrectProgramId);
+
+    let approve = error.invoked.last().unwrap();
+    assert_eq!(approve.program_id, MPL_CORE_PROGRAM_ID);
+    // ApprovePluginAuthorityV1 { plugin_type: PluginType::TransferDelegate, new_authority: Authority::Address }
+    let mut data = vec![8, 3, 3];
+    data.extend_from_slice(pda::vault_authority().0.as_ref());
+    assert_eq!(approve.data, data);
+    // The omitted collection is passed as the Core program ID
+    assert_eq!(
+        approve.accounts,
+        vec![
+            AccountMeta::new(asset, false),
+            AccountMeta::new_readonly(MPL_CORE_PROGRAM_ID, false),
+            AccountMeta::new(borrower, true),
+            AccountMeta::new_readonly(borrower, true),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(MPL_CORE_PROGRAM_ID, false),
+        ]
+    );
+}
+
+fn lock_authorities(delegate: Pubkey) -> Vec<(CorePluginType, CorePluginAuthority)> {
+    let delegate = CorePluginAuthority::Address { address: delegate };
+    vec![
+        (CorePluginType::TransferDelegate, delegate),
+        (CorePluginType::FreezeDelegate, delegate),
+    ]
+}
+
+fn owner_authorities() -> Vec<(CorePluginType, CorePluginAuthority)> {
+    vec![
+        (CorePluginType::TransferDelegate, CorePluginAuthority::Owner),
+        (CorePluginType::FreezeDelegate, CorePluginAuthority::Owner),
+    ]
+}
+
+/// Stakes a Core asset in a collection and funds the loan, returning the lender.
+fn stake_and_fund(
+    harness: &mut Harness,
+    borrower: &Pubkey,
+    asset: &Pubkey,
+    collection: &Pubkey,
+) -> Pubkey {
+    harness
+        .process_transaction(
+            &[instructions::stake_core_asset_for_loan(
+                borrower,
+                asset,
+                Some(collection),
+                LOAN_AMOUNT,
+                INTEREST_AMOUNT,
+                DURATION,
+                None,
+            )],
+            &[*borrower],
+        )
+        .unwrap();
+    assert_eq!(
+        harness.core_asset_plugins(asset),
+        (lock_authorities(pda::vault_authority().0), true)
+    );
+    assert_eq!(harness.core_asset_owner(asset), *borrower);
+
+    let lender = harness.new_wallet();
+    harness
+        .process_transaction(
+            &[instructions::provide_loan_liquidity(
+                &lender, borrower, asset, 1, None,
+            )],
+            &[lender],
+        )
+        .unwrap();
+    lender
+}
+
+#[test]
+fn mint_core_asset_creates_the_asset_for_its_owner() {
+    let mut harness = Harness::new();
+    harness.load_core();
+    let admin = get_admin_account_pubkey();
+    harness.airdrop(&admin, 10_000_000_000);
+    let owner = harness.new_wallet();
+    harness.set_collection_counter(&Pubkey::new_unique(), 3);
+    let asset = Pubkey::new_unique();
+
+    harness
+        .process_transaction(
+            &[instructions::mint_core_asset(
+                &admin,
+                &asset,
+                &owner,
+                "https://example.com/item".to_string(),
+            )],
+            &[admin, asset],
+        )
+        .unwrap();
+
+    assert_eq!(harness.core_asset_owner(&asset), owner);
+    let counter: CollectionCounter = harness.anchor_account(&pda::collection_counter().0);
+    assert_eq!(counter.count, 4);
+}
+
+#[test]
+fn repaying_a_core_loan_thaws_the_asset_and_returns_its_delegates() {
+    let mut harness = Harness::new();
+    harness.load_core();
+    let borrower = harness.new_wallet();
+    let collection = Pubkey::new_unique();
+    let asset = harness.create_core_asset_in_collection(&borrower, &collection, &[]);
+    let lender = stake_and_fund(&mut harness, &borrower, &asset, &collection);
+    let lender_balance = harness.lamports(&lender);
+
+    harness
+        .process_transaction(
+            &[instructions::repay_core_loan(
+                &borrower,
+                &borrower,
+                &lender,
+                &asset,
+                Some(&collection),
+                1,
+            )],
+            &[borrower],
+        )
+        .unwrap();
+
+    let record: LoanRecord = harness.anchor_account(&pda::loan_record(&asset, 1).0);
+    assert_eq!(record.outcome, LoanOutcome::Repaid);
+    assert!(harness.account(&pda::loan_info(&asset).0).is_none());
+    assert_eq!(
+        harness.lamports(&lender),
+        lender_balance + LOAN_AMOUNT + INTEREST_AMOUNT
+    );
+    assert_eq!(harness.core_asset_owner(&asset), borrower);
+    assert_eq!(
+        harness.core_asset_plugins(&asset),
+        (owner_authorities(), false)
+    );
+}
+
+#[test]
+fn defaulted_core_loans_move_the_asset_to_the_lender() {
+    let mut harness = Harness::new();
+    harness.load_core();
+    let borrower = harness.new_wallet();
+    let collection = Pubkey::new_unique();
+    let asset = harness.create_core_asset_in_collection(&borrower, &collection, &[]);
+    let lender = stake_and_fund(&mut harness, &borrower, &asset, &collection);
+    harness.warp_forward(DURATION + 1);
+
+    harness
+        .process_transaction(
+            &[instructions::claim_delinquent_core_asset(
+                &lender,
+                &borrower,
+                &asset,
+                Some(&collection),
+                1,
+            )],
+            &[lender],
+        )
+        .unwrap();
+
+    let record: LoanRecord = harness.anchor_account(&pda::loan_record(&asset, 1).0);
+    assert_eq!(record.outcome, LoanOutcome::Defaulted);
+    assert!(harness.account(&pda::loan_info(&asset).0).is_none());
+    assert_eq!(harness.core_asset_owner(&asset), lender);
+    assert_eq!(
+        harness.core_asset_plugins(&asset),
+        (owner_authorities(), false)
+    );
+}
+
+#[test]
+fn locked_core_assets_cannot_be_moved_by_their_owner() {
+    let mut harness = Harness::new();
+    harness.load_core();
+    let borrower = harness.new_wallet();
+    let asset = harness.create_core_asset(&borrower, &[]);
+    harness
+        .process_transaction(&[stake_instruction(&borrower, &asset)], &[borrower])
+        .unwrap();
+
+    // Thawing it directly through Core needs the vault authority as freeze delegate
+    let thaw = Instruction {
+        program_id: MPL_CORE_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(asset, false),
+            AccountMeta::new_readonly(MPL_CORE_PROGRAM_ID, false),
+            AccountMeta::new(borrower, true),
+            AccountMeta::new_readonly(borrower, true),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(MPL_CORE_PROGRAM_ID, false),
+        ],
+        // UpdatePluginV1 { plugin: FreezeDelegate { frozen: false } }
+        data: vec![6, 1, 0],
+    };
+    let result = harness.process_transaction(&[thaw], &[borrower]);
+    assert_instruction_error(result, InstructionError::InvalidArgument);
+    assert!(harness.core_asset_plugins(&asset).1);
+}
+
+#[test]
+fn fulfilled_core_redemptions_move_the_asset_to_the_redemption_vault() {
+    let mut harness = Harness::new();
+    harness.load_core();
+    let owner = harness.new_wallet();
+    let asset = harness.create_core_asset(&owner, &[]);
+    harness
+        .process_transaction(
+            &[instructions::create_core_asset_redemption_request(
+                &owner, &asset, None,
+            )],
+            &[owner],
+        )
+        .unwrap();
+    assert_eq!(
+        harness.core_asset_plugins(&asset),
+        (lock_authorities(pda::asset_redemption_vault().0), true)
+    );
+
+    let admin = get_admin_account_pubkey();
+    harness.airdrop(&admin, 10_000_000_000);
+    harness
+        .process_transaction(
+            &[instructions::fulfill_core_asset_redemption_request(
+                &admin, &asset, None,
+            )],
+            &[admin],
+        )
+        .unwrap();
+
+    let redemption: AssetRedemptionInfo =
+        harness.anchor_account(&pda::asset_redemption_info(&asset).0);
+    assert!(redemption.is_fulfilled);
+    assert_eq!(
+        harness.core_asset_owner(&asset),
+        pda::asset_redemption_vault().0
+    );
+    assert_eq!(
+        harness.core_asset_plugins(&asset),
+        (owner_authorities(), false)
+    );
+}
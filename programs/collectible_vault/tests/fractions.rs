@@ -0,0 +1,586 @@
+/**
+ * Fractionalization tests: locking an NFT in its fraction vault against a fixed supply of fraction
+ * tokens, the buyout auction, paying the winning bid out to fraction holders, the winner taking
+ * the NFT or sending it for redemption, and redemption by a supermajority of fraction holders.
+ */
+mod common;
+
+use {
+    anchor_lang::{
+        prelude::Pubkey,
+        solana_program::{instruction::Instruction, program_pack::Pack},
+    },
+    collectible_vault::{
+        errors::ErrorCode,
+        events::{
+            BuyoutBidPlaced, BuyoutNftClaimed, FractionalizedNftRedeemed, NftFractionalized,
+            RedemptionRequested,
+        },
+        instructions::nft_transfer::NftStandard,
+        state::{
+            AccountHeader, AssetRedemptionInfo, FractionVault, FractionVaultStatus, LoanInfo,
+            LoanStatus,
+        },
+    },
+    collectible_vault_client::{instructions, pda},
+    common::{assert_program_error, events, Harness},
+    spl_associated_token_account::get_associated_token_address,
+};
+
+const SUPPLY: u64 = 1_000;
+const RESERVE_PRICE: u64 = 10_000_000_000;
+const REDEMPTION_THRESHOLD_BPS: u16 = 7_500;
+
+/// Creates an NFT for a fresh curator and fractionalizes it, returning `(curator, nft_mint)`
+fn fractionalize(harness: &mut Harness) -> (Pubkey, Pubkey) {
+    let curator = harness.new_wallet();
+    let nft_mint = harness.create_nft(&curator);
+    harness
+        .process_transaction(
+            &[fractionalize_ix(&curator, &nft_mint, SUPPLY, RESERVE_PRICE)],
+            &[curator],
+        )
+        .unwrap();
+    (curator, nft_mint)
+}
+
+fn fractionalize_ix(
+    owner: &Pubkey,
+    nft_mint: &Pubkey,
+    fraction_supply: u64,
+    reserve_price: u64,
+) -> Instruction {
+    instructions::fractionalize_nft(
+        owner,
+        nft_mint,
+        fraction_supply,
+        reserve_price,
+        REDEMPTION_THRESHOLD_BPS,
+        NftStandard::NonFungible,
+        &spl_token::ID,
+    )
+}
+
+fn bid(
+    harness: &mut Harness,
+    bidder: &Pubkey,
+    previous_bidder: Option<&Pubkey>,
+    nft_mint: &Pubkey,
+    amount: u64,
+) -> Result<Vec<String>, common::TransactionError> {
+    harness.process_transaction(
+        &[instructions::place_buyout_bid(
+            bidder,
+            previous_bidder,
+            nft_mint,
+            amount,
+        )],
+        &[*bidder],
+    )
+}
+
+fn fraction_account(holder: &Pubkey, nft_mint: &Pubkey) -> Pubkey {
+    get_associated_token_address(holder, &pda::fraction_mint(nft_mint).0)
+}
+
+fn vault_nft_account(nft_mint: &Pubkey) -> Pubkey {
+    get_associated_token_address(&pda::fraction_vault(nft_mint).0, nft_mint)
+}
+
+fn redeem_ix(
+    payer: &Pubkey,
+    curator: &Pubkey,
+    nft_mint: &Pubkey,
+    holders: &[Pubkey],
+) -> Instruction {
+    instructions::redeem_fractionalized_nft(
+        payer,
+        payer,
+        curator,
+        nft_mint,
+        holders,
+        NftStandard::NonFungible,
+        &spl_token::ID,
+    )
+}
+
+fn claim_nft(winner: &Pubkey, curator: &Pubkey, nft_mint: &Pubkey) -> Instruction {
+    instructions::claim_buyout_nft(
+        winner,
+        curator,
+        nft_mint,
+        NftStandard::NonFungible,
+        &spl_token::ID,
+    )
+}
+
+#[test]
+fn fractionalize_nft_locks_the_nft_and_mints_a_fixed_supply() {
+    let mut harness = Harness::new();
+    let curator = harness.new_wallet();
+    let nft_mint = harness.create_nft(&curator);
+
+    let logs = harness
+        .process_transaction(
+            &[fractionalize_ix(&curator, &nft_mint, SUPPLY, RESERVE_PRICE)],
+            &[curator],
+        )
+        .unwrap();
+
+    assert_eq!(
+        harness.token_balance(&get_associated_token_address(&curator, &nft_mint)),
+        0
+    );
+    assert_eq!(harness.token_balance(&vault_nft_account(&nft_mint)), 1);
+    let fraction_mint = pda::fraction_mint(&nft_mint).0;
+    assert_eq!(
+        harness.token_balance(&fraction_account(&curator, &nft_mint)),
+        SUPPLY
+    );
+    let mint =
+        spl_token::state::Mint::unpack(&harness.account(&fraction_mint).unwrap().data).unwrap();
+    assert_eq!(mint.supply, SUPPLY);
+    assert!(mint.mint_authority.is_none());
+
+    let vault: FractionVault = harness.anchor_account(&pda::fraction_vault(&nft_mint).0);
+    assert_eq!(vault.curator, curator);
+    assert_eq!(vault.fraction_mint, fraction_mint);
+    assert_eq!(vault.reserve_price, RESERVE_PRICE);
+    assert_eq!(vault.status, FractionVaultStatus::Active);
+    assert_eq!(
+        events::<NftFractionalized>(&logs)[0].fraction_supply,
+        SUPPLY
+    );
+}
+
+#[test]
+fn fractionalize_nft_rejects_invalid_terms() {
+    let mut harness = Harness::new();
+    let curator = harness.new_wallet();
+    let nft_mint = harness.create_nft(&curator);
+
+    let result = harness.process_transaction(
+        &[fractionalize_ix(&curator, &nft_mint, 0, RESERVE_PRICE)],
+        &[curator],
+    );
+    assert_program_error(result, ErrorCode::InvalidFractionSupply);
+
+    let result = harness.process_transaction(
+        &[fractionalize_ix(&curator, &nft_mint, SUPPLY, 0)],
+        &[curator],
+    );
+    assert_program_error(result, ErrorCode::InvalidReservePrice);
+
+    // A simple majority of the fractions is not enough to redeem the NFT
+    let result = harness.process_transaction(
+        &[instructions::fractionalize_nft(
+            &curator,
+            &nft_mint,
+            SUPPLY,
+            RESERVE_PRICE,
+            FractionVault::MIN_REDEMPTION_THRESHOLD_BPS - 1,
+            NftStandard::NonFungible,
+            &spl_token::ID,
+        )],
+        &[curator],
+    );
+    assert_program_error(result, ErrorCode::InvalidRedemptionThreshold);
+}
+
+#[test]
+fn fractionalize_nft_rejects_an_nft_with_an_open_loan() {
+    let mut harness = Harness::new();
+    let curator = harness.new_wallet();
+    let nft_mint = harness.create_nft(&curator);
+    // A loan request against the NFT, as if it were locked for a loan in the curator's wallet
+    harness.set_program_account(
+        pda::loan_info(&nft_mint).0,
+        &LoanInfo {
+            header: AccountHeader::new(LoanInfo::VERSION),
+            nft_mint,
+            nft_owner: curator,
+            loan_amount: RESERVE_PRICE,
+            interest_amount: 0,
+            duration: 3600,
+            start_time: None,
+            lender: None,
+            status: LoanStatus::Requested,
+            request_expiry: None,
+            loan_id: 1,
+            reserved: [0; 64],
+        },
+    );
+
+    let result = harness.process_transaction(
+        &[fractionalize_ix(&curator, &nft_mint, SUPPLY, RESERVE_PRICE)],
+        &[curator],
+    );
+    assert_program_error(result, ErrorCode::NftHasOpenLoan);
+}
+
+#[test]
+fn place_buyout_bid_opens_the_auction_at_the_reserve_price() {
+    let mut harness = Harness::new();
+    let (_, nft_mint) = fractionalize(&mut harness);
+    let bidder = harness.new_wallet();
+    let vault_key = pda::fraction_vault(&nft_mint).0;
+    let vault_lamports = harness.lamports(&vault_key);
+
+    let result = bid(&mut harness, &bidder, None, &nft_mint, RESERVE_PRICE - 1);
+    assert_program_error(result, ErrorCode::BidTooLow);
+
+    let logs = bid(&mut harness, &bidder, None, &nft_mint, RESERVE_PRICE).unwrap();
+    assert_eq!(harness.lamports(&vault_key), vault_lamports + RESERVE_PRICE);
+    let vault: FractionVault = harness.anchor_account(&vault_key);
+    let auction_end = harness.now() + FractionVault::BUYOUT_AUCTION_DURATION;
+    assert_eq!(vault.status, FractionVaultStatus::Auction);
+    assert_eq!(vault.top_bidder, Some(bidder));
+    assert_eq!(vault.auction_end, Some(auction_end));
+    assert_eq!(events::<BuyoutBidPlaced>(&logs)[0].auction_end, auction_end);
+}
+
+#[test]
+fn place_buyout_bid_refunds_the_outbid_bidder() {
+    let mut harness = Harness::new();
+    let (_, nft_mint) = fractionalize(&mut harness);
+    let first = harness.new_wallet();
+    let second = harness.new_wallet();
+    bid(&mut harness, &first, None, &nft_mint, RESERVE_PRICE).unwrap();
+    let first_lamports = harness.lamports(&first);
+
+    // Each raise must be at least 5% over the top bid
+    let minimum = RESERVE_PRICE + RESERVE_PRICE / 20;
+    let result = bid(&mut harness, &second, Some(&first), &nft_mint, minimum - 1);
+    assert_program_error(result, ErrorCode::BidTooLow);
+
+    // The outbid bidder must be passed to be refunded
+    let result = bid(&mut harness, &second, None, &nft_mint, minimum);
+    assert_program_error(result, ErrorCode::InvalidPreviousBidder);
+    let result = bid(&mut harness, &second, Some(&second), &nft_mint, minimum);
+    assert_program_error(result, ErrorCode::InvalidPreviousBidder);
+
+    harness.warp_forward(3600);
+    bid(&mut harness, &second, Some(&first), &nft_mint, minimum).unwrap();
+    assert_eq!(harness.lamports(&first), first_lamports + RESERVE_PRICE);
+    let vault: FractionVault = harness.anchor_account(&pda::fraction_vault(&nft_mint).0);
+    assert_eq!(vault.top_bidder, Some(second));
+    assert_eq!(vault.top_bid, minimum);
+    // Later bids do not move the end of the auction
+    assert_eq!(
+        vault.auction_end,
+        Some(harness.now() - 3600 + FractionVault::BUYOUT_AUCTION_DURATION)
+    );
+}
+
+#[test]
+fn place_buyout_bid_rejects_bids_after_the_auction_ends() {
+    let mut harness = Harness::new();
+    let (_, nft_mint) = fractionalize(&mut harness);
+    let first = harness.new_wallet();
+    let second = harness.new_wallet();
+    bid(&mut harness, &first, None, &nft_mint, RESERVE_PRICE).unwrap();
+
+    harness.warp_forward(FractionVault::BUYOUT_AUCTION_DURATION);
+    let result = bid(
+        &mut harness,
+        &second,
+        Some(&first),
+        &nft_mint,
+        2 * RESERVE_PRICE,
+    );
+    assert_program_error(result, ErrorCode::BuyoutAuctionEnded);
+}
+
+#[test]
+fn buyout_pays_the_winning_bid_to_fraction_holders() {
+    let mut harness = Harness::new();
+    let (curator, nft_mint) = fractionalize(&mut harness);
+    let holder = harness.new_wallet();
+    let winner = harness.new_wallet();
+    let fraction_mint = pda::fraction_mint(&nft_mint).0;
+    harness.transfer_tokens(&curator, &holder, &fraction_mint, 300);
+    let vault_key = pda::fraction_vault(&nft_mint).0;
+    let vault_rent = harness.lamports(&vault_key);
+    let winning_bid = RESERVE_PRICE + 1;
+    bid(&mut harness, &winner, None, &nft_mint, winning_bid).unwrap();
+
+    let result = harness.process_transaction(
+        &[instructions::claim_buyout_proceeds(&holder, &nft_mint, 300)],
+        &[holder],
+    );
+    assert_program_error(result, ErrorCode::BuyoutNotComplete);
+
+    harness.warp_forward(FractionVault::BUYOUT_AUCTION_DURATION);
+    let holder_lamports = harness.lamports(&holder);
+    harness
+        .process_transaction(
+            &[instructions::claim_buyout_proceeds(&holder, &nft_mint, 300)],
+            &[holder],
+        )
+        .unwrap();
+    let holder_share = winning_bid * 300 / SUPPLY;
+    assert_eq!(harness.lamports(&holder), holder_lamports + holder_share);
+    assert_eq!(harness.mint_supply(&fraction_mint), SUPPLY - 300);
+
+    // The winner takes the NFT, and the remaining holder can still claim afterwards
+    harness
+        .process_transaction(&[claim_nft(&winner, &curator, &nft_mint)], &[winner])
+        .unwrap();
+    let curator_lamports = harness.lamports(&curator);
+    harness
+        .process_transaction(
+            &[instructions::claim_buyout_proceeds(
+                &curator, &nft_mint, 700,
+            )],
+            &[curator],
+        )
+        .unwrap();
+
+    // The last fractions receive the rounding remainder
+    assert_eq!(
+        harness.lamports(&curator),
+        curator_lamports + winning_bid - holder_share
+    );
+    assert_eq!(harness.mint_supply(&fraction_mint), 0);
+    let vault: FractionVault = harness.anchor_account(&vault_key);
+    assert_eq!(vault.proceeds_claimed, winning_bid);
+    assert_eq!(harness.lamports(&vault_key), vault_rent);
+}
+
+#[test]
+fn claim_buyout_proceeds_rejects_overclaimed_proceeds() {
+    let mut harness = Harness::new();
+    let (curator, nft_mint) = fractionalize(&mut harness);
+    let winner = harness.new_wallet();
+    bid(&mut harness, &winner, None, &nft_mint, RESERVE_PRICE).unwrap();
+    harness.warp_forward(FractionVault::BUYOUT_AUCTION_DURATION);
+    // More proceeds recorded as claimed than the winning bid paid in
+    let vault_key = pda::fraction_vault(&nft_mint).0;
+    let mut vault: FractionVault = harness.anchor_account(&vault_key);
+    vault.proceeds_claimed = RESERVE_PRICE + 1;
+    harness.set_program_account(vault_key, &vault);
+
+    let result = harness.process_transaction(
+        &[instructions::claim_buyout_proceeds(&curator, &nft_mint, 1)],
+        &[curator],
+    );
+    assert_program_error(result, ErrorCode::CalculationError);
+}
+
+#[test]
+fn claim_buyout_nft_releases_the_nft_to_the_winner() {
+    let mut harness = Harness::new();
+    let (curator, nft_mint) = fractionalize(&mut harness);
+    let winner = harness.new_wallet();
+    let other = harness.new_wallet();
+    bid(&mut harness, &winner, None, &nft_mint, RESERVE_PRICE).unwrap();
+
+    let result = harness.process_transaction(&[claim_nft(&winner, &curator, &nft_mint)], &[winner]);
+    assert_program_error(result, ErrorCode::BuyoutNotComplete);
+
+    harness.warp_forward(FractionVault::BUYOUT_AUCTION_DURATION);
+    let result = harness.process_transaction(&[claim_nft(&other, &curator, &nft_mint)], &[other]);
+    assert_program_error(result, ErrorCode::InvalidBuyoutWinner);
+
+    let curator_lamports = harness.lamports(&curator);
+    let vault_account_rent = harness.lamports(&vault_nft_account(&nft_mint));
+    let logs = harness
+        .process_transaction(&[claim_nft(&winner, &curator, &nft_mint)], &[winner])
+        .unwrap();
+    assert_eq!(
+        harness.token_balance(&get_associated_token_address(&winner, &nft_mint)),
+        1
+    );
+    assert!(harness.account(&vault_nft_account(&nft_mint)).is_none());
+    assert_eq!(
+        harness.lamports(&curator),
+        curator_lamports + vault_account_rent
+    );
+    let vault: FractionVault = harness.anchor_account(&pda::fraction_vault(&nft_mint).0);
+    assert_eq!(vault.status, FractionVaultStatus::Released);
+    let claimed = &events::<BuyoutNftClaimed>(&logs)[0];
+    assert_eq!(claimed.price, RESERVE_PRICE);
+    assert!(!claimed.redemption_requested);
+
+    let result = bid(
+        &mut harness,
+        &other,
+        Some(&winner),
+        &nft_mint,
+        2 * RESERVE_PRICE,
+    );
+    assert_program_error(result, ErrorCode::FractionVaultNotActive);
+}
+
+#[test]
+fn claim_buyout_nft_leaves_a_vault_account_holding_stray_tokens_open() {
+    let mut harness = Harness::new();
+    let (curator, nft_mint) = fractionalize(&mut harness);
+    let winner = harness.new_wallet();
+    bid(&mut harness, &winner, None, &nft_mint, RESERVE_PRICE).unwrap();
+    harness.send_stray_token(&vault_nft_account(&nft_mint));
+    harness.warp_forward(FractionVault::BUYOUT_AUCTION_DURATION);
+
+    harness
+        .process_transaction(&[claim_nft(&winner, &curator, &nft_mint)], &[winner])
+        .unwrap();
+
+    assert_eq!(
+        harness.token_balance(&get_associated_token_address(&winner, &nft_mint)),
+        1
+    );
+    assert_eq!(harness.token_balance(&vault_nft_account(&nft_mint)), 1);
+}
+
+#[test]
+fn redeem_buyout_nft_opens_a_redemption_request_for_the_winner() {
+    let mut harness = Harness::new();
+    let (curator, nft_mint) = fractionalize(&mut harness);
+    let winner = harness.new_wallet();
+    bid(&mut harness, &winner, None, &nft_mint, RESERVE_PRICE).unwrap();
+    harness.warp_forward(FractionVault::BUYOUT_AUCTION_DURATION);
+
+    let logs = harness
+        .process_transaction(
+            &[instructions::redeem_buyout_nft(
+                &winner,
+                &curator,
+                &nft_mint,
+                NftStandard::NonFungible,
+                &spl_token::ID,
+            )],
+            &[winner],
+        )
+        .unwrap();
+
+    let redemption_nft_account =
+        get_associated_token_address(&pda::asset_redemption_vault().0, &nft_mint);
+    assert_eq!(harness.token_balance(&redemption_nft_account), 1);
+    let redemption: AssetRedemptionInfo =
+        harness.anchor_account(&pda::asset_redemption_info(&nft_mint).0);
+    assert_eq!(redemption.nft_owner, winner);
+    assert!(!redemption.is_fulfilled);
+    assert!(events::<BuyoutNftClaimed>(&logs)[0].redemption_requested);
+    let vault: FractionVault = harness.anchor_account(&pda::fraction_vault(&nft_mint).0);
+    assert_eq!(vault.status, FractionVaultStatus::Released);
+}
+
+#[test]
+fn redeem_fractionalized_nft_buys_out_the_remaining_holders() {
+    let mut harness = Harness::new();
+    let (curator, nft_mint) = fractionalize(&mut harness);
+    let holder = harness.new_wallet();
+    let remaining_holder = harness.new_wallet();
+    let fraction_mint = pda::fraction_mint(&nft_mint).0;
+    harness.transfer_tokens(&curator, &holder, &fraction_mint, 200);
+    harness.transfer_tokens(&curator, &remaining_holder, &fraction_mint, 100);
+    let vault_key = pda::fraction_vault(&nft_mint).0;
+    let vault_rent = harness.lamports(&vault_key);
+
+    let logs = harness
+        .process_transaction(
+            &[redeem_ix(&holder, &curator, &nft_mint, &[curator, holder])],
+            &[holder, curator],
+        )
+        .unwrap();
+
+    let redemption_nft_account =
+        get_associated_token_address(&pda::asset_redemption_vault().0, &nft_mint);
+    assert_eq!(harness.token_balance(&redemption_nft_account), 1);
+    assert!(harness.account(&vault_nft_account(&nft_mint)).is_none());
+    let redemption: AssetRedemptionInfo =
+        harness.anchor_account(&pda::asset_redemption_info(&nft_mint).0);
+    assert_eq!(redemption.nft_owner, holder);
+    assert_eq!(harness.mint_supply(&fraction_mint), 100);
+
+    // The payer bought the 100 outstanding fractions at the reserve price
+    let buyout_amount = RESERVE_PRICE / 10;
+    let vault: FractionVault = harness.anchor_account(&vault_key);
+    assert_eq!(vault.status, FractionVaultStatus::Released);
+    assert_eq!(vault.top_bid, buyout_amount);
+    assert_eq!(harness.lamports(&vault_key), vault_rent + buyout_amount);
+    let redeemed = &events::<FractionalizedNftRedeemed>(&logs)[0];
+    assert_eq!(redeemed.fractions_burned, 900);
+    assert_eq!(redeemed.buyout_amount, buyout_amount);
+    assert_eq!(events::<RedemptionRequested>(&logs)[0].owner, holder);
+
+    let holder_lamports = harness.lamports(&remaining_holder);
+    harness
+        .process_transaction(
+            &[instructions::claim_buyout_proceeds(
+                &remaining_holder,
+                &nft_mint,
+                100,
+            )],
+            &[remaining_holder],
+        )
+        .unwrap();
+    assert_eq!(
+        harness.lamports(&remaining_holder),
+        holder_lamports + buyout_amount
+    );
+    assert_eq!(harness.lamports(&vault_key), vault_rent);
+}
+
+#[test]
+fn redeem_fractionalized_nft_requires_the_redemption_threshold() {
+    let mut harness = Harness::new();
+    let (curator, nft_mint) = fractionalize(&mut harness);
+    let holder = harness.new_wallet();
+    let fraction_mint = pda::fraction_mint(&nft_mint).0;
+    harness.transfer_tokens(&curator, &holder, &fraction_mint, 300);
+
+    let result = harness.process_transaction(
+        &[redeem_ix(&curator, &curator, &nft_mint, &[curator])],
+        &[curator],
+    );
+    assert_program_error(result, ErrorCode::RedemptionThresholdNotMet);
+
+    // Redeeming with the whole supply leaves nothing to buy out
+    let vault_key = pda::fraction_vault(&nft_mint).0;
+    let vault_rent = harness.lamports(&vault_key);
+    let logs = harness
+        .process_transaction(
+            &[redeem_ix(&curator, &curator, &nft_mint, &[curator, holder])],
+            &[curator, holder],
+        )
+        .unwrap();
+    assert_eq!(harness.mint_supply(&fraction_mint), 0);
+    assert_eq!(
+        events::<FractionalizedNftRedeemed>(&logs)[0].buyout_amount,
+        0
+    );
+    assert_eq!(harness.lamports(&vault_key), vault_rent);
+}
+
+#[test]
+fn redeem_fractionalized_nft_rejects_invalid_holders() {
+    let mut harness = Harness::new();
+    let (curator, nft_mint) = fractionalize(&mut harness);
+    let holder = harness.new_wallet();
+    let fraction_mint = pda::fraction_mint(&nft_mint).0;
+    harness.transfer_tokens(&curator, &holder, &fraction_mint, 300);
+
+    // The holder's fractions cannot be counted without their signature
+    let mut unsigned = redeem_ix(&curator, &curator, &nft_mint, &[curator, holder]);
+    let holder_index = unsigned.accounts.len() - 2;
+    unsigned.accounts[holder_index].is_signer = false;
+    let result = harness.process_transaction(&[unsigned], &[curator]);
+    assert_program_error(result, ErrorCode::InvalidFractionHolder);
+
+    // Nor can a signer pass fractions held by someone else
+    let mut foreign = redeem_ix(&curator, &curator, &nft_mint, &[holder]);
+    let account_index = foreign.accounts.len() - 1;
+    foreign.accounts[account_index].pubkey = fraction_account(&curator, &nft_mint);
+    let result = harness.process_transaction(&[foreign], &[curator, holder]);
+    assert_program_error(result, ErrorCode::InvalidFractionHolder);
+
+    // Once a buyout auction has started, the NFT goes to the winning bidder instead
+    let bidder = harness.new_wallet();
+    bid(&mut harness, &bidder, None, &nft_mint, RESERVE_PRICE).unwrap();
+    let result = harness.process_transaction(
+        &[redeem_ix(&curator, &curator, &nft_mint, &[curator, holder])],
+        &[curator, holder],
+    );
+    assert_program_error(result, ErrorCode::FractionVaultNotActive);
+}
@@ -0,0 +1,645 @@
+/**
+ * Insurance tests: the fee share funding the insurance reserve, appraisals, impairment declarations
+ * blocking an NFT in the program, and holders or fraction vaults burning impaired NFTs for their payout.
+ */
+mod common;
+
+use {
+    anchor_lang::{
+        prelude::Pubkey,
+        solana_program::instruction::{Instruction, InstructionError},
+    },
+    collectible_vault::{
+        constants::admin_constants::get_admin_account_pubkey,
+        errors::ErrorCode,
+        events::{AssetImpaired, InsuranceFeeConfigured, InsurancePayoutClaimed, NftRented},
+        instructions::nft_transfer::NftStandard,
+        state::{
+            AssetAppraisal, AssetImpairment, FractionVault, FractionVaultStatus, InsuranceReserve,
+            VaultConfig,
+        },
+    },
+    collectible_vault_client::{instructions, pda},
+    common::{assert_instruction_error, assert_program_error, events, replace_account, Harness},
+    mpl_token_metadata::types::{CollectionDetails, TokenStandard},
+    spl_associated_token_account::get_associated_token_address,
+};
+
+const INSURANCE_FEE_BPS: u16 = 500;
+const APPRAISED_VALUE: u64 = 2_000_000_000;
+
+/// Funds the admin and sets the insurance fee, creating the insurance reserve
+fn set_insurance_fee(harness: &mut Harness, insurance_fee_bps: u16) {
+    let admin = get_admin_account_pubkey();
+    harness.airdrop(&admin, 10_000_000_000);
+    harness
+        .process_transaction(
+            &[instructions::set_insurance_fee(&admin, insurance_fee_bps)],
+            &[admin],
+        )
+        .unwrap();
+}
+
+/// Appraises and declares impaired a fresh NFT held by a fresh wallet, returning `(holder, nft_mint)`
+fn impair(harness: &mut Harness) -> (Pubkey, Pubkey) {
+    let holder = harness.new_wallet();
+    let nft_mint = harness.create_nft(&holder);
+    impair_nft(harness, &nft_mint);
+    (holder, nft_mint)
+}
+
+/// Appraises and declares impaired `nft_mint`, wherever it is held
+fn impair_nft(harness: &mut Harness, nft_mint: &Pubkey) {
+    let admin = get_admin_account_pubkey();
+    harness.airdrop(&admin, 1_000_000_000);
+    harness
+        .process_transaction(
+            &[
+                instructions::record_appraisal(&admin, nft_mint, APPRAISED_VALUE),
+                instructions::declare_asset_impaired(&admin, nft_mint),
+            ],
+            &[admin],
+        )
+        .unwrap();
+}
+
+fn claim_ix(holder: &Pubkey, nft_mint: &Pubkey) -> Instruction {
+    instructions::claim_insurance_payout(
+        holder,
+        nft_mint,
+        NftStandard::NonFungible,
+        None,
+        &spl_token::ID,
+    )
+}
+
+#[test]
+fn set_insurance_fee_is_admin_only_and_capped() {
+    let mut harness = Harness::new();
+    let payer = harness.new_wallet();
+
+    let result = harness.process_transaction(
+        &[instructions::set_insurance_fee(&payer, INSURANCE_FEE_BPS)],
+        &[payer],
+    );
+    assert_program_error(result, ErrorCode::UnauthorizedTransactionSigner);
+
+    let admin = get_admin_account_pubkey();
+    harness.airdrop(&admin, 1_000_000_000);
+    let result = harness.process_transaction(
+        &[instructions::set_insurance_fee(
+            &admin,
+            VaultConfig::MAX_INSURANCE_FEE_BPS + 1,
+        )],
+        &[admin],
+    );
+    assert_program_error(result, ErrorCode::InvalidInsuranceFee);
+
+    let logs = harness
+        .process_transaction(
+            &[instructions::set_insurance_fee(&admin, INSURANCE_FEE_BPS)],
+            &[admin],
+        )
+        .unwrap();
+
+    let vault_config: VaultConfig = harness.anchor_account(&pda::vault_config().0);
+    assert_eq!(vault_config.insurance_fee_bps, INSURANCE_FEE_BPS);
+    let insurance_reserve: InsuranceReserve = harness.anchor_account(&pda::insurance_reserve().0);
+    assert_eq!(insurance_reserve.claims_paid, 0);
+    assert_eq!(
+        events::<InsuranceFeeConfigured>(&logs)[0].insurance_fee_bps,
+        INSURANCE_FEE_BPS
+    );
+}
+
+#[test]
+fn rent_nft_pays_the_insurance_share_into_the_reserve() {
+    let mut harness = Harness::new();
+    set_insurance_fee(&mut harness, INSURANCE_FEE_BPS);
+    let owner = harness.new_wallet();
+    let renter = harness.new_wallet();
+    let nft_mint = harness.create_nft(&owner);
+    let daily_fee = 100_000_000;
+    harness
+        .process_transaction(
+            &[instructions::list_nft_for_rent(
+                &owner,
+                &nft_mint,
+                daily_fee,
+                30,
+                NftStandard::NonFungible,
+                &spl_token::ID,
+            )],
+            &[owner],
+        )
+        .unwrap();
+
+    let owner_lamports = harness.lamports(&owner);
+    let reserve_lamports = harness.lamports(&pda::insurance_reserve().0);
+    let logs = harness
+        .process_transaction(
+            &[instructions::rent_nft(&renter, &owner, &nft_mint, 4)],
+            &[renter],
+        )
+        .unwrap();
+
+    let fee = 4 * daily_fee;
+    let insurance_fee = fee * INSURANCE_FEE_BPS as u64 / 10_000;
+    assert_eq!(
+        harness.lamports(&owner),
+        owner_lamports + fee - insurance_fee
+    );
+    assert_eq!(
+        harness.lamports(&pda::insurance_reserve().0),
+        reserve_lamports + insurance_fee
+    );
+    let rented = &events::<NftRented>(&logs)[0];
+    assert_eq!(rented.fee, fee);
+    assert_eq!(rented.insurance_fee, insurance_fee);
+}
+
+#[test]
+fn declare_asset_impaired_requires_an_appraisal_and_blocks_the_nft() {
+    let mut harness = Harness::new();
+    let admin = get_admin_account_pubkey();
+    harness.airdrop(&admin, 1_000_000_000);
+    let holder = harness.new_wallet();
+    let nft_mint = harness.create_nft(&holder);
+
+    let result = harness.process_transaction(
+        &[instructions::record_appraisal(&admin, &nft_mint, 0)],
+        &[admin],
+    );
+    assert_program_error(result, ErrorCode::InvalidAppraisal);
+    harness
+        .process_transaction(
+            &[instructions::record_appraisal(
+                &admin,
+                &nft_mint,
+                APPRAISED_VALUE,
+            )],
+            &[admin],
+        )
+        .unwrap();
+    let result = harness.process_transaction(
+        &[instructions::declare_asset_impaired(&holder, &nft_mint)],
+        &[holder],
+    );
+    assert_program_error(result, ErrorCode::UnauthorizedTransactionSigner);
+
+    let logs = harness
+        .process_transaction(
+            &[instructions::declare_asset_impaired(&admin, &nft_mint)],
+            &[admin],
+        )
+        .unwrap();
+
+    let appraisal: AssetAppraisal = harness.anchor_account(&pda::asset_appraisal(&nft_mint).0);
+    assert_eq!(appraisal.appraised_value, APPRAISED_VALUE);
+    let impairment: AssetImpairment = harness.anchor_account(&pda::asset_impairment(&nft_mint).0);
+    assert_eq!(impairment.payout, APPRAISED_VALUE);
+    assert_eq!(impairment.claimed_by, None);
+    assert_eq!(events::<AssetImpaired>(&logs)[0].payout, APPRAISED_VALUE);
+
+    // The impaired NFT can no longer be listed or rented out
+    let result = harness.process_transaction(
+        &[instructions::list_nft(
+            &holder,
+            &nft_mint,
+            1_000_000_000,
+            NftStandard::NonFungible,
+            &spl_token::ID,
+        )],
+        &[holder],
+    );
+    assert_program_error(result, ErrorCode::NftImpaired);
+    let result = harness.process_transaction(
+        &[instructions::list_nft_for_rent(
+            &holder,
+            &nft_mint,
+            100_000_000,
+            30,
+            NftStandard::NonFungible,
+            &spl_token::ID,
+        )],
+        &[holder],
+    );
+    assert_program_error(result, ErrorCode::NftImpaired);
+}
+
+#[test]
+fn impaired_nfts_in_program_custody_cannot_be_lent_against_or_redeemed() {
+    let mut harness = Harness::new();
+
+    // A loan request staked before the NFT was declared impaired can no longer be funded
+    let borrower = harness.new_wallet();
+    let staked_mint = harness.create_nft(&borrower);
+    harness
+        .process_transaction(
+            &[instructions::stake_nft_for_loan(
+                &borrower,
+                &staked_mint,
+                1_000_000_000,
+                1_000,
+                86_400,
+                None,
+                NftStandard::NonFungible,
+                &spl_token::ID,
+            )],
+            &[borrower],
+        )
+        .unwrap();
+    impair_nft(&mut harness, &staked_mint);
+    let lender = harness.new_wallet();
+    let result = harness.process_transaction(
+        &[instructions::provide_loan_liquidity(
+            &lender,
+            &borrower,
+            &staked_mint,
+            1,
+            None,
+        )],
+        &[lender],
+    );
+    assert_program_error(result, ErrorCode::NftImpaired);
+
+    // Neither can a fractionalized NFT be redeemed by its fraction holders
+    let curator = harness.new_wallet();
+    let fractionalized_mint = harness.create_nft(&curator);
+    harness
+        .process_transaction(
+            &[instructions::fractionalize_nft(
+                &curator,
+                &fractionalized_mint,
+                1_000,
+                10_000_000_000,
+                7_500,
+                NftStandard::NonFungible,
+                &spl_token::ID,
+            )],
+            &[curator],
+        )
+        .unwrap();
+    impair_nft(&mut harness, &fractionalized_mint);
+    let result = harness.process_transaction(
+        &[instructions::redeem_fractionalized_nft(
+            &curator,
+            &curator,
+            &curator,
+            &fractionalized_mint,
+            &[],
+            NftStandard::NonFungible,
+            &spl_token::ID,
+        )],
+        &[curator],
+    );
+    assert_program_error(result, ErrorCode::NftImpaired);
+}
+
+#[test]
+fn claim_insurance_payout_burns_the_nft_and_pays_the_holder() {
+    let mut harness = Harness::new();
+    set_insurance_fee(&mut harness, INSURANCE_FEE_BPS);
+    let (holder, nft_mint) = impair(&mut harness);
+    let funder = harness.new_wallet();
+
+    let result = harness.process_transaction(&[claim_ix(&holder, &nft_mint)], &[holder]);
+    assert_program_error(result, ErrorCode::InsufficientInsuranceReserve);
+    let result = harness.process_transaction(
+        &[instructions::fund_insurance_reserve(&funder, 0)],
+        &[funder],
+    );
+    assert_program_error(result, ErrorCode::InvalidInsuranceDeposit);
+
+    harness
+        .process_transaction(
+            &[instructions::fund_insurance_reserve(
+                &funder,
+                APPRAISED_VALUE,
+            )],
+            &[funder],
+        )
+        .unwrap();
+    let holder_nft_account = get_associated_token_address(&holder, &nft_mint);
+    let holder_lamports = harness.lamports(&holder) + harness.lamports(&holder_nft_account);
+    let logs = harness
+        .process_transaction(&[claim_ix(&holder, &nft_mint)], &[holder])
+        .unwrap();
+
+    assert!(harness.account(&holder_nft_account).is_none());
+    assert_eq!(harness.mint_supply(&nft_mint), 0);
+    assert_eq!(harness.lamports(&holder), holder_lamports + APPRAISED_VALUE);
+    let insurance_reserve: InsuranceReserve = harness.anchor_account(&pda::insurance_reserve().0);
+    assert_eq!(insurance_reserve.total_paid_out, APPRAISED_VALUE);
+    assert_eq!(insurance_reserve.claims_paid, 1);
+    let impairment: AssetImpairment = harness.anchor_account(&pda::asset_impairment(&nft_mint).0);
+    assert_eq!(impairment.claimed_by, Some(holder));
+    assert_eq!(
+        events::<InsurancePayoutClaimed>(&logs)[0].payout,
+        APPRAISED_VALUE
+    );
+}
+
+#[test]
+fn claim_insurance_payout_burns_token_metadata_nfts_through_token_metadata() {
+    let mut harness = Harness::new();
+    set_insurance_fee(&mut harness, INSURANCE_FEE_BPS);
+    let (holder, nft_mint) = impair(&mut harness);
+    harness.set_token_metadata(&nft_mint, TokenStandard::ProgrammableNonFungible);
+    let funder = harness.new_wallet();
+    harness
+        .process_transaction(
+            &[instructions::fund_insurance_reserve(
+                &funder,
+                APPRAISED_VALUE,
+            )],
+            &[funder],
+        )
+        .unwrap();
+    let claim = instructions::claim_insurance_payout(
+        &holder,
+        &nft_mint,
+        NftStandard::ProgrammableNonFungible { rule_set: None },
+        None,
+        &spl_token::ID,
+    );
+
+    // The metadata account must be the NFT's, so a Token Metadata NFT cannot skip `BurnV1`
+    let mut other_metadata = claim.clone();
+    replace_account(
+        &mut other_metadata,
+        &pda::metadata(&nft_mint).0,
+        &pda::metadata(&Pubkey::new_unique()).0,
+    );
+    let result = harness.process_transaction(&[other_metadata], &[holder]);
+    assert_program_error(result, ErrorCode::InvalidNFTAccount);
+
+    // Token Metadata is not loaded in the harness, so reaching its `BurnV1` CPI fails here
+    let result = harness.process_transaction(&[claim], &[holder]);
+    assert_instruction_error(result, InstructionError::IncorrectProgramId);
+    assert_eq!(
+        harness.token_balance(&get_associated_token_address(&holder, &nft_mint)),
+        1
+    );
+}
+
+#[test]
+fn claim_insurance_payout_burns_minted_nfts_and_their_token_metadata_accounts() {
+    for standard in [
+        NftStandard::NonFungible,
+        NftStandard::ProgrammableNonFungible { rule_set: None },
+    ] {
+        let mut harness = Harness::new();
+        harness.load_token_metadata();
+        set_insurance_fee(&mut harness, INSURANCE_FEE_BPS);
+        let holder = harness.new_wallet();
+        let nft_mint = harness.mint_nft(&holder, standard);
+        impair_nft(&mut harness, &nft_mint);
+        let collection_mint = harness.token_metadata(&nft_mint).collection.unwrap().key;
+        let funder = harness.new_wallet();
+        harness
+            .process_transaction(
+                &[instructions::fund_insurance_reserve(
+                    &funder,
+                    APPRAISED_VALUE,
+                )],
+                &[funder],
+            )
+            .unwrap();
+        let holder_nft_account = get_associated_token_address(&holder, &nft_mint);
+        let token_record = pda::token_record(&nft_mint, &holder_nft_account).0;
+        let closed = [
+            holder_nft_account,
+            pda::metadata(&nft_mint).0,
+            pda::master_edition(&nft_mint).0,
+            token_record,
+        ];
+        let holder_lamports = harness.lamports(&holder)
+            + closed
+                .iter()
+                .map(|account| harness.lamports(account))
+                .sum::<u64>();
+
+        harness
+            .process_transaction(
+                &[instructions::claim_insurance_payout(
+                    &holder,
+                    &nft_mint,
+                    standard,
+                    Some(&collection_mint),
+                    &spl_token::ID,
+                )],
+                &[holder],
+            )
+            .unwrap();
+
+        // `BurnV1` closes every account of the NFT, refunds their rent and shrinks the collection
+        assert_eq!(harness.mint_supply(&nft_mint), 0);
+        for account in closed {
+            assert!(harness.account(&account).is_none(), "{standard:?}");
+        }
+        assert_eq!(harness.lamports(&holder), holder_lamports + APPRAISED_VALUE);
+        assert_eq!(
+            harness.token_metadata(&collection_mint).collection_details,
+            Some(CollectionDetails::V1 { size: 0 })
+        );
+    }
+}
+
+/// Fractionalizes a fresh NFT into 1,000 fractions held by a fresh curator, returning `(curator, nft_mint)`
+fn fractionalize(harness: &mut Harness) -> (Pubkey, Pubkey) {
+    let curator = harness.new_wallet();
+    let nft_mint = harness.create_nft(&curator);
+    harness
+        .process_transaction(
+            &[instructions::fractionalize_nft(
+                &curator,
+                &nft_mint,
+                1_000,
+                APPRAISED_VALUE,
+                7_500,
+                NftStandard::NonFungible,
+                &spl_token::ID,
+            )],
+            &[curator],
+        )
+        .unwrap();
+    (curator, nft_mint)
+}
+
+/// Deposits `amount` lamports into the insurance reserve from a fresh wallet
+fn fund_reserve(harness: &mut Harness, amount: u64) {
+    let funder = harness.new_wallet();
+    harness
+        .process_transaction(
+            &[instructions::fund_insurance_reserve(&funder, amount)],
+            &[funder],
+        )
+        .unwrap();
+}
+
+fn bid(harness: &mut Harness, nft_mint: &Pubkey) -> Pubkey {
+    let bidder = harness.new_wallet();
+    harness
+        .process_transaction(
+            &[instructions::place_buyout_bid(
+                &bidder,
+                None,
+                nft_mint,
+                APPRAISED_VALUE,
+            )],
+            &[bidder],
+        )
+        .unwrap();
+    bidder
+}
+
+fn claim_fractionalized_ix(
+    cranker: &Pubkey,
+    curator: &Pubkey,
+    top_bidder: Option<&Pubkey>,
+    nft_mint: &Pubkey,
+) -> Instruction {
+    instructions::claim_fractionalized_insurance_payout(
+        cranker,
+        curator,
+        top_bidder,
+        nft_mint,
+        NftStandard::NonFungible,
+        None,
+        &spl_token::ID,
+    )
+}
+
+#[test]
+fn claim_fractionalized_insurance_payout_pays_the_fraction_holders_pro_rata() {
+    let mut harness = Harness::new();
+    set_insurance_fee(&mut harness, INSURANCE_FEE_BPS);
+    let (curator, nft_mint) = fractionalize(&mut harness);
+    let holder = harness.new_wallet();
+    harness.transfer_tokens(&curator, &holder, &pda::fraction_mint(&nft_mint).0, 250);
+    impair_nft(&mut harness, &nft_mint);
+    fund_reserve(&mut harness, APPRAISED_VALUE);
+    let vault_nft_account =
+        get_associated_token_address(&pda::fraction_vault(&nft_mint).0, &nft_mint);
+    let curator_lamports = harness.lamports(&curator) + harness.lamports(&vault_nft_account);
+
+    let cranker = harness.new_wallet();
+    let logs = harness
+        .process_transaction(
+            &[claim_fractionalized_ix(&cranker, &curator, None, &nft_mint)],
+            &[cranker],
+        )
+        .unwrap();
+
+    assert!(harness.account(&vault_nft_account).is_none());
+    assert_eq!(harness.mint_supply(&nft_mint), 0);
+    assert_eq!(harness.lamports(&curator), curator_lamports);
+    let vault: FractionVault = harness.anchor_account(&pda::fraction_vault(&nft_mint).0);
+    assert_eq!(vault.status, FractionVaultStatus::Released);
+    assert_eq!(vault.top_bid, APPRAISED_VALUE);
+    let impairment: AssetImpairment = harness.anchor_account(&pda::asset_impairment(&nft_mint).0);
+    assert_eq!(
+        impairment.claimed_by,
+        Some(pda::fraction_vault(&nft_mint).0)
+    );
+    assert_eq!(
+        events::<InsurancePayoutClaimed>(&logs)[0].holder,
+        pda::fraction_vault(&nft_mint).0
+    );
+
+    // Each fraction is worth an equal share of the payout
+    let holder_lamports = harness.lamports(&holder);
+    harness
+        .process_transaction(
+            &[instructions::claim_buyout_proceeds(&holder, &nft_mint, 250)],
+            &[holder],
+        )
+        .unwrap();
+    assert_eq!(
+        harness.lamports(&holder),
+        holder_lamports + APPRAISED_VALUE / 4
+    );
+}
+
+#[test]
+fn claim_fractionalized_insurance_payout_refunds_a_running_auction() {
+    let mut harness = Harness::new();
+    set_insurance_fee(&mut harness, INSURANCE_FEE_BPS);
+    let (curator, nft_mint) = fractionalize(&mut harness);
+    let bidder = bid(&mut harness, &nft_mint);
+    impair_nft(&mut harness, &nft_mint);
+    fund_reserve(&mut harness, APPRAISED_VALUE);
+    let bidder_lamports = harness.lamports(&bidder);
+    let cranker = harness.new_wallet();
+
+    let result = harness.process_transaction(
+        &[claim_fractionalized_ix(&cranker, &curator, None, &nft_mint)],
+        &[cranker],
+    );
+    assert_program_error(result, ErrorCode::InvalidPreviousBidder);
+
+    harness
+        .process_transaction(
+            &[claim_fractionalized_ix(
+                &cranker,
+                &curator,
+                Some(&bidder),
+                &nft_mint,
+            )],
+            &[cranker],
+        )
+        .unwrap();
+
+    assert_eq!(harness.lamports(&bidder), bidder_lamports + APPRAISED_VALUE);
+    let vault: FractionVault = harness.anchor_account(&pda::fraction_vault(&nft_mint).0);
+    assert_eq!(vault.status, FractionVaultStatus::Released);
+    assert_eq!(vault.top_bidder, None);
+    assert_eq!(vault.top_bid, APPRAISED_VALUE);
+}
+
+#[test]
+fn claim_fractionalized_insurance_payout_pays_the_winner_of_an_ended_auction() {
+    let mut harness = Harness::new();
+    set_insurance_fee(&mut harness, INSURANCE_FEE_BPS);
+    let (curator, nft_mint) = fractionalize(&mut harness);
+    let winner = bid(&mut harness, &nft_mint);
+    harness.warp_forward(FractionVault::BUYOUT_AUCTION_DURATION);
+    impair_nft(&mut harness, &nft_mint);
+    fund_reserve(&mut harness, APPRAISED_VALUE);
+    let winner_lamports = harness.lamports(&winner);
+
+    let cranker = harness.new_wallet();
+    harness
+        .process_transaction(
+            &[claim_fractionalized_ix(
+                &cranker,
+                &curator,
+                Some(&winner),
+                &nft_mint,
+            )],
+            &[cranker],
+        )
+        .unwrap();
+
+    // The winner receives the payout in place of the NFT, and the fraction holders keep the bid
+    assert_eq!(harness.lamports(&winner), winner_lamports + APPRAISED_VALUE);
+    let impairment: AssetImpairment = harness.anchor_account(&pda::asset_impairment(&nft_mint).0);
+    assert_eq!(impairment.claimed_by, Some(winner));
+    let vault: FractionVault = harness.anchor_account(&pda::fraction_vault(&nft_mint).0);
+    assert_eq!(vault.status, FractionVaultStatus::Released);
+    assert_eq!(vault.top_bid, APPRAISED_VALUE);
+    let curator_lamports = harness.lamports(&curator);
+    harness
+        .process_transaction(
+            &[instructions::claim_buyout_proceeds(
+                &curator, &nft_mint, 1_000,
+            )],
+            &[curator],
+        )
+        .unwrap();
+    assert_eq!(
+        harness.lamports(&curator),
+        curator_lamports + APPRAISED_VALUE
+    );
+}
@@ -0,0 +1,1788 @@
+/**
+ * Loan lifecycle tests: staking, funding, repayment, default claims, cancellation and the
+ * expired-request crank, including every reachable loan error.
+ *
+ * Some errors have no reachable failure path and are not covered:
+ * - `LoanAlreadyActive`: funding an active loan fails first on the loan note mint, which already
+ *   exists, and cancellation and the crank both reject funded loans with `LoanAlreadyFunded`.
+ * - `LoanNotActive`: repaying or claiming an unfunded loan fails first on the loan note mint,
+ *   which does not exist until the loan is funded.
+ * - `InvalidInterestRate`: never raised.
+ */
+mod common;
+
+use {
+    anchor_lang::{
+        error::ErrorCode as AnchorErrorCode,
+        prelude::{Pubkey, Rent},
+        solana_program::instruction::{Instruction, InstructionError},
+        Discriminator,
+    },
+    collectible_vault::{
+        constants::admin_constants::get_admin_account_pubkey,
+        errors::ErrorCode,
+        events::{
+            AccountMigrated, DefaultRoyaltyPaid, LoanCancelled, LoanDefaulted, LoanFunded,
+            LoanRepaid, LoanRequested, VaultAccountSwept,
+        },
+        instructions::nft_transfer::NftStandard,
+        state::{
+            AccountHeader, BorrowerProfile, LegacyLoanInfo, LoanCounter, LoanInfo, LoanOutcome,
+            LoanRecord, LoanStatus, ReputationGate,
+        },
+    },
+    collectible_vault_client::{instructions, pda},
+    common::{
+        assert_instruction_error, assert_program_error, events, replace_account, Harness,
+        StoredAccount, TransactionError,
+    },
+    mpl_token_metadata::types::TokenStandard,
+    spl_associated_token_account::get_associated_token_address,
+};
+
+const LOAN_AMOUNT: u64 = 2_000_000_000;
+const INTEREST_AMOUNT: u64 = 100_000_000;
+const DURATION: i64 = 7 * 24 * 60 * 60;
+/// The size of LoanInfo accounts created by the deployed baseline program
+const BASELINE_LOAN_INFO_SPACE: usize = 139;
+
+/// A borrower with an NFT staked under the default loan terms
+struct StakedLoan {
+    borrower: Pubkey,
+    nft_mint: Pubkey,
+    loan_id: u64,
+}
+
+fn stake(harness: &mut Harness, request_expiry: Option<i64>) -> StakedLoan {
+    let borrower = harness.new_wallet();
+    let nft_mint = harness.create_nft(&borrower);
+    harness
+        .process_transaction(
+            &[instructions::stake_nft_for_loan(
+                &borrower,
+                &nft_mint,
+                LOAN_AMOUNT,
+                INTEREST_AMOUNT,
+                DURATION,
+                request_expiry,
+                NftStandard::NonFungible,
+                &spl_token::ID,
+            )],
+            &[borrower],
+        )
+        .unwrap();
+    StakedLoan {
+        borrower,
+        nft_mint,
+        loan_id: 1,
+    }
+}
+
+fn fund(harness: &mut Harness, loan: &StakedLoan) -> Pubkey {
+    let lender = harness.new_wallet();
+    harness
+        .process_transaction(
+            &[instructions::provide_loan_liquidity(
+                &lender,
+                &loan.borrower,
+                &loan.nft_mint,
+                loan.loan_id,
+                None,
+            )],
+            &[lender],
+        )
+        .unwrap();
+    lender
+}
+
+fn vault_nft_account(nft_mint: &Pubkey) -> Pubkey {
+    get_associated_token_address(&pda::vault_authority().0, nft_mint)
+}
+
+fn note_account(holder: &Pubkey, loan: &StakedLoan) -> Pubkey {
+    get_associated_token_address(holder, &pda::loan_note_mint(&loan.nft_mint, loan.loan_id).0)
+}
+
+#[test]
+fn stake_nft_for_loan_escrows_the_nft() {
+    let mut harness = Harness::new();
+    let borrower = harness.new_wallet();
+    let nft_mint = harness.create_nft(&borrower);
+    let expiry = harness.now() + 3600;
+
+    let logs = harness
+        .process_transaction(
+            &[instructions::stake_nft_for_loan(
+                &borrower,
+                &nft_mint,
+                LOAN_AMOUNT,
+                INTEREST_AMOUNT,
+                DURATION,
+                Some(expiry),
+                NftStandard::NonFungible,
+                &spl_token::ID,
+            )],
+            &[borrower],
+        )
+        .unwrap();
+
+    let loan_info: LoanInfo = harness.anchor_account(&pda::loan_info(&nft_mint).0);
+    assert_eq!(loan_info.nft_owner, borrower);
+    assert_eq!(loan_info.loan_amount, LOAN_AMOUNT);
+    assert_eq!(loan_info.interest_amount, INTEREST_AMOUNT);
+    assert_eq!(loan_info.request_expiry, Some(expiry));
+    assert_eq!(loan_info.loan_id, 1);
+    assert_eq!(loan_info.status, LoanStatus::Requested);
+    assert_eq!(loan_info.header.version, LoanInfo::VERSION);
+    assert_eq!(harness.token_balance(&vault_nft_account(&nft_mint)), 1);
+    assert_eq!(
+        harness.token_balance(&get_associated_token_address(&borrower, &nft_mint)),
+        0
+    );
+
+    let requested = events::<LoanRequested>(&logs);
+    assert_eq!(requested.len(), 1);
+    assert_eq!(requested[0].borrower, borrower);
+    assert_eq!(requested[0].request_expiry, Some(expiry));
+}
+
+#[test]
+fn stake_nft_for_loan_rejects_an_empty_token_account() {
+    let mut harness = Harness::new();
+    let borrower = harness.new_wallet();
+    let nft_mint = harness.create_nft(&borrower);
+    let other = harness.new_wallet();
+    harness.transfer_tokens(&borrower, &other, &nft_mint, 1);
+
+    let result = harness.process_transaction(
+        &[instructions::stake_nft_for_loan(
+            &borrower,
+            &nft_mint,
+            LOAN_AMOUNT,
+            INTEREST_AMOUNT,
+            DURATION,
+            None,
+            NftStandard::NonFungible,
+            &spl_token::ID,
+        )],
+        &[borrower],
+    );
+    assert_program_error(result, ErrorCode::InvalidNFTAccount);
+}
+
+#[test]
+fn stake_nft_for_loan_rejects_a_non_positive_duration() {
+    let mut harness = Harness::new();
+    let borrower = harness.new_wallet();
+    let nft_mint = harness.create_nft(&borrower);
+
+    let result = harness.process_transaction(
+        &[instructions::stake_nft_for_loan(
+            &borrower,
+            &nft_mint,
+            LOAN_AMOUNT,
+            INTEREST_AMOUNT,
+            0,
+            None,
+            NftStandard::NonFungible,
+            &spl_token::ID,
+        )],
+        &[borrower],
+    );
+    assert_program_error(result, ErrorCode::InvalidLoanDuration);
+}
+
+#[test]
+fn stake_nft_for_loan_rejects_an_expiry_in_the_past() {
+    let mut harness = Harness::new();
+    let borrower = harness.new_wallet();
+    let nft_mint = harness.create_nft(&borrower);
+
+    let result = harness.process_transaction(
+        &[instructions::stake_nft_for_loan(
+            &borrower,
+            &nft_mint,
+            LOAN_AMOUNT,
+            INTEREST_AMOUNT,
+            DURATION,
+            Some(harness.now()),
+            NftStandard::NonFungible,
+            &spl_token::ID,
+        )],
+        &[borrower],
+    );
+    assert_program_error(result, ErrorCode::InvalidLoanRequestExpiry);
+}
+
+/// Builds a `stake_nft_for_loan` instruction that passes the programmable NFT accounts
+fn stake_programmable(borrower: &Pubkey, nft_mint: &Pubkey) -> Instruction {
+    instructions::stake_nft_for_loan(
+        borrower,
+        nft_mint,
+        LOAN_AMOUNT,
+        INTEREST_AMOUNT,
+        DURATION,
+        None,
+        NftStandard::ProgrammableNonFungible { rule_set: None },
+        &spl_token::ID,
+    )
+}
+
+#[test]
+fn stake_nft_for_loan_moves_a_classic_nft_with_a_plain_transfer() {
+    let mut harness = Harness::new();
+    let borrower = harness.new_wallet();
+    let nft_mint = harness.create_nft(&borrower);
+    harness.set_token_metadata(&nft_mint, TokenStandard::NonFungible);
+
+    // The metadata says the NFT is not programmable, so the extra accounts are ignored
+    harness
+        .process_transaction(&[stake_programmable(&borrower, &nft_mint)], &[borrower])
+        .unwrap();
+    assert_eq!(harness.token_balance(&vault_nft_account(&nft_mint)), 1);
+}
+
+#[test]
+fn stake_nft_for_loan_requires_the_programmable_accounts() {
+    let mut harness = Harness::new();
+    let borrower = harness.new_wallet();
+    let nft_mint = harness.create_nft(&borrower);
+    harness.set_token_metadata(&nft_mint, TokenStandard::ProgrammableNonFungible);
+
+    let mut instruction = stake_programmable(&borrower, &nft_mint);
+    let source = get_associated_token_address(&borrower, &nft_mint);
+    replace_account(
+        &mut instruction,
+        &pda::token_record(&nft_mint, &source).0,
+        &collectible_vault::ID,
+    );
+    let result = harness.process_transaction(&[instruction], &[borrower]);
+    assert_program_error(result, ErrorCode::MissingProgrammableAccounts);
+}
+
+#[test]
+fn stake_nft_for_loan_routes_programmable_nfts_through_token_metadata() {
+    let mut harness = Harness::new();
+    let borrower = harness.new_wallet();
+    let nft_mint = harness.create_nft(&borrower);
+    harness.set_token_metadata(&nft_mint, TokenStandard::ProgrammableNonFungible);
+
+    // Token Metadata is not loaded in the harness, so reaching its CPI fails here
+    let result =
+        harness.process_transaction(&[stake_programmable(&borrower, &nft_mint)], &[borrower]);
+    assert_instruction_error(result, InstructionError::IncorrectProgramId);
+    assert!(harness.account(&pda::loan_info(&nft_mint).0).is_none());
+}
+
+#[test]
+fn programmable_nfts_enter_and_leave_a_loan_through_token_metadata() {
+    let mut harness = Harness::new();
+    harness.load_token_metadata();
+    let borrower = harness.new_wallet();
+    let nft_mint = harness.mint_nft(
+        &borrower,
+        NftStandard::ProgrammableNonFungible { rule_set: None },
+    );
+    let borrower_account = get_associated_token_address(&borrower, &nft_mint);
+    let vault_account = vault_nft_account(&nft_mint);
+
+    harness
+        .process_transaction(&[stake_programmable(&borrower, &nft_mint)], &[borrower])
+        .unwrap();
+    // TransferV1 gives the vault's token account a token record and keeps both accounts frozen
+    assert_eq!(harness.token_balance(&vault_account), 1);
+    assert!(harness.is_frozen(&vault_account));
+    assert!(harness.is_frozen(&borrower_account));
+    assert!(harness
+        .account(&pda::token_record(&nft_mint, &vault_account).0)
+        .is_some());
+
+    harness
+        .process_transaction(
+            &[instructions::cancel_loan_request(
+                &borrower,
+                &nft_mint,
+                1,
+                NftStandard::ProgrammableNonFungible { rule_set: None },
+                &spl_token::ID,
+            )],
+            &[borrower],
+        )
+        .unwrap();
+    assert_eq!(harness.token_balance(&borrower_account), 1);
+    assert!(harness.is_frozen(&borrower_account));
+    // The emptied vault account stays open, frozen by the master edition
+    assert_eq!(harness.token_balance(&vault_account), 0);
+    assert!(harness.is_frozen(&vault_account));
+}
+
+#[test]
+fn stake_nft_for_loan_rejects_metadata_of_another_mint() {
+    let mut harness = Harness::new();
+    let borrower = harness.new_wallet();
+    let nft_mint = harness.create_nft(&borrower);
+    let other_mint = Pubkey::new_unique();
+    harness.set_token_metadata(&other_mint, TokenStandard::ProgrammableNonFungible);
+
+    let mut instruction = stake_programmable(&borrower, &nft_mint);
+    replace_account(
+        &mut instruction,
+        &pda::metadata(&nft_mint).0,
+        &pda::metadata(&other_mint).0,
+    );
+    let result = harness.process_transaction(&[instruction], &[borrower]);
+    assert_program_error(result, ErrorCode::InvalidNFTAccount);
+}
+
+#[test]
+fn provide_loan_liquidity_funds_the_borrower_and_mints_a_note() {
+    let mut harness = Harness::new();
+    let loan = stake(&mut harness, None);
+    let lender = harness.new_wallet();
+    let borrower_lamports = harness.lamports(&loan.borrower);
+
+    let logs = harness
+        .process_transaction(
+            &[instructions::provide_loan_liquidity(
+                &lender,
+                &loan.borrower,
+                &loan.nft_mint,
+                loan.loan_id,
+                None,
+            )],
+            &[lender],
+        )
+        .unwrap();
+
+    let loan_info: LoanInfo = harness.anchor_account(&pda::loan_info(&loan.nft_mint).0);
+    assert_eq!(loan_info.status, LoanStatus::Active);
+    assert_eq!(loan_info.lender, Some(lender));
+    assert_eq!(loan_info.start_time, Some(harness.now()));
+    assert_eq!(
+        harness.lamports(&loan.borrower),
+        borrower_lamports + LOAN_AMOUNT
+    );
+    assert_eq!(harness.token_balance(&note_account(&lender, &loan)), 1);
+
+    let funded = events::<LoanFunded>(&logs);
+    assert_eq!(funded.len(), 1);
+    assert_eq!(funded[0].lender, lender);
+    assert_eq!(
+        funded[0].loan_note_mint,
+        pda::loan_note_mint(&loan.nft_mint, loan.loan_id).0
+    );
+}
+
+#[test]
+fn provide_loan_liquidity_rejects_a_lender_without_enough_lamports() {
+    let mut harness = Harness::new();
+    let loan = stake(&mut harness, None);
+    let lender = Pubkey::new_unique();
+    harness.airdrop(&lender, LOAN_AMOUNT - 1);
+
+    let result = harness.process_transaction(
+        &[instructions::provide_loan_liquidity(
+            &lender,
+            &loan.borrower,
+            &loan.nft_mint,
+            loan.loan_id,
+            None,
+        )],
+        &[lender],
+    );
+    assert_program_error(result, ErrorCode::InsufficientFunds);
+}
+
+#[test]
+fn provide_loan_liquidity_rejects_the_wrong_borrower() {
+    let mut harness = Harness::new();
+    let loan = stake(&mut harness, None);
+    let lender = harness.new_wallet();
+    let impostor = harness.new_wallet();
+
+    let result = harness.process_transaction(
+        &[instructions::provide_loan_liquidity(
+            &lender,
+            &impostor,
+            &loan.nft_mint,
+            loan.loan_id,
+            None,
+        )],
+        &[lender],
+    );
+    assert_program_error(result, ErrorCode::InvalidBorrower);
+}
+
+#[test]
+fn provide_loan_liquidity_rejects_an_expired_request() {
+    let mut harness = Harness::new();
+    let expiry = harness.now() + 3600;
+    let loan = stake(&mut harness, Some(expiry));
+    let lender = harness.new_wallet();
+    let fund_ix = instructions::provide_loan_liquidity(
+        &lender,
+        &loan.borrower,
+        &loan.nft_mint,
+        loan.loan_id,
+        None,
+    );
+
+    // A request can still be funded at its expiry, but not after it
+    harness.warp_forward(3601);
+    let result = harness.process_transaction(&[fund_ix], &[lender]);
+    assert_program_error(result, ErrorCode::LoanRequestExpired);
+}
+
+#[test]
+fn provide_loan_liquidity_enforces_the_reputation_gate() {
+    let mut harness = Harness::new();
+    let loan = stake(&mut harness, None);
+    let lender = harness.new_wallet();
+    let gate = ReputationGate {
+        min_loans_repaid_on_time: 1,
+        max_loans_defaulted: 0,
+        min_repaid_volume: 0,
+    };
+
+    let result = harness.process_transaction(
+        &[instructions::provide_loan_liquidity(
+            &lender,
+            &loan.borrower,
+            &loan.nft_mint,
+            loan.loan_id,
+            Some(gate),
+        )],
+        &[lender],
+    );
+    assert_program_error(result, ErrorCode::BorrowerReputationTooLow);
+
+    // A gate the empty profile satisfies lets the loan through
+    let lenient = ReputationGate {
+        min_loans_repaid_on_time: 0,
+        max_loans_defaulted: 0,
+        min_repaid_volume: 0,
+    };
+    harness
+        .process_transaction(
+            &[instructions::provide_loan_liquidity(
+                &lender,
+                &loan.borrower,
+                &loan.nft_mint,
+                loan.loan_id,
+                Some(lenient),
+            )],
+            &[lender],
+        )
+        .unwrap();
+}
+
+#[test]
+fn repay_loan_returns_the_nft_and_archives_the_loan() {
+    let mut harness = Harness::new();
+    let loan = stake(&mut harness, None);
+    let lender = fund(&mut harness, &loan);
+    let lender_lamports = harness.lamports(&lender);
+    harness.warp_forward(DURATION);
+
+    let logs = harness
+        .process_transaction(
+            &[instructions::repay_loan(
+                &loan.borrower,
+                &loan.borrower,
+                &lender,
+                &loan.nft_mint,
+                loan.loan_id,
+                NftStandard::NonFungible,
+                &spl_token::ID,
+            )],
+            &[loan.borrower],
+        )
+        .unwrap();
+
+    assert!(harness.account(&pda::loan_info(&loan.nft_mint).0).is_none());
+    assert_eq!(
+        harness.lamports(&lender),
+        lender_lamports + LOAN_AMOUNT + INTEREST_AMOUNT
+    );
+    assert!(harness
+        .account(&vault_nft_account(&loan.nft_mint))
+        .is_none());
+    assert_eq!(
+        harness.token_balance(&get_associated_token_address(
+            &loan.borrower,
+            &loan.nft_mint
+        )),
+        1
+    );
+
+    let record: LoanRecord =
+        harness.anchor_account(&pda::loan_record(&loan.nft_mint, loan.loan_id).0);
+    assert_eq!(record.outcome, LoanOutcome::Repaid);
+    assert_eq!(record.lender, Some(lender));
+    assert_eq!(record.closed_at, harness.now());
+
+    let profile: BorrowerProfile = harness.anchor_account(&pda::borrower_profile(&loan.borrower).0);
+    assert_eq!(profile.loans_taken, 1);
+    assert_eq!(profile.loans_repaid_on_time, 1);
+    assert_eq!(profile.total_volume, LOAN_AMOUNT);
+    assert_eq!(profile.repaid_volume, LOAN_AMOUNT);
+
+    let repaid = events::<LoanRepaid>(&logs);
+    assert_eq!(repaid.len(), 1);
+    assert_eq!(repaid[0].repayment_amount, LOAN_AMOUNT + INTEREST_AMOUNT);
+
+    // The settled loan note stays with the lender but can no longer be traded
+    assert!(harness.is_frozen(&note_account(&lender, &loan)));
+}
+
+#[test]
+fn repay_loan_below_the_reputation_minimum_does_not_build_reputation() {
+    let mut harness = Harness::new();
+    let borrower = harness.new_wallet();
+    let nft_mint = harness.create_nft(&borrower);
+    let dust_amount = BorrowerProfile::MIN_REPUTATION_LOAN_AMOUNT - 1;
+    harness
+        .process_transaction(
+            &[instructions::stake_nft_for_loan(
+                &borrower,
+                &nft_mint,
+                dust_amount,
+                0,
+                DURATION,
+                None,
+                NftStandard::NonFungible,
+                &spl_token::ID,
+            )],
+            &[borrower],
+        )
+        .unwrap();
+    let loan = StakedLoan {
+        borrower,
+        nft_mint,
+        loan_id: 1,
+    };
+    // The borrower funds their own loan, as someone farming reputation would
+    harness
+        .process_transaction(
+            &[instructions::provide_loan_liquidity(
+                &borrower, &borrower, &nft_mint, 1, None,
+            )],
+            &[borrower],
+        )
+        .unwrap();
+    harness
+        .process_transaction(
+            &[instructions::repay_loan(
+                &borrower,
+                &borrower,
+                &borrower,
+                &nft_mint,
+                loan.loan_id,
+                NftStandard::NonFungible,
+                &spl_token::ID,
+            )],
+            &[borrower],
+        )
+        .unwrap();
+
+    let profile: BorrowerProfile = harness.anchor_account(&pda::borrower_profile(&borrower).0);
+    assert_eq!(profile.loans_taken, 1);
+    assert_eq!(profile.total_volume, dust_amount);
+    assert_eq!(profile.loans_repaid_on_time, 0);
+    assert_eq!(profile.repaid_volume, 0);
+    assert!(!profile.meets(&ReputationGate {
+        min_loans_repaid_on_time: 1,
+        max_loans_defaulted: 0,
+        min_repaid_volume: 0,
+    }));
+}
+
+#[test]
+fn provide_loan_liquidity_enforces_the_repaid_volume_gate() {
+    let mut harness = Harness::new();
+    let loan = stake(&mut harness, None);
+    let lender = harness.new_wallet();
+    let mut profile = BorrowerProfile {
+        header: AccountHeader::new(BorrowerProfile::VERSION),
+        borrower: loan.borrower,
+        loans_taken: 3,
+        loans_repaid_on_time: 3,
+        total_volume: 3 * LOAN_AMOUNT,
+        repaid_volume: 3 * LOAN_AMOUNT,
+        ..BorrowerProfile::default()
+    };
+    harness.set_program_account(pda::borrower_profile(&loan.borrower).0, &profile);
+    let gate = ReputationGate {
+        min_loans_repaid_on_time: 3,
+        max_loans_defaulted: 0,
+        min_repaid_volume: 4 * LOAN_AMOUNT,
+    };
+    let fund_ix = instructions::provide_loan_liquidity(
+        &lender,
+        &loan.borrower,
+        &loan.nft_mint,
+        loan.loan_id,
+        Some(gate),
+    );
+
+    let result = harness.process_transaction(std::slice::from_ref(&fund_ix), &[lender]);
+    assert_program_error(result, ErrorCode::BorrowerReputationTooLow);
+
+    profile.repaid_volume = 4 * LOAN_AMOUNT;
+    harness.set_program_account(pda::borrower_profile(&loan.borrower).0, &profile);
+    harness.process_transaction(&[fund_ix], &[lender]).unwrap();
+}
+
+#[test]
+fn repay_loan_accepts_a_third_party_payer() {
+    let mut harness = Harness::new();
+    let loan = stake(&mut harness, None);
+    let lender = fund(&mut harness, &loan);
+    let payer = harness.new_wallet();
+    let borrower_lamports = harness.lamports(&loan.borrower);
+
+    let logs = harness
+        .process_transaction(
+            &[instructions::repay_loan(
+                &payer,
+                &loan.borrower,
+                &lender,
+                &loan.nft_mint,
+                loan.loan_id,
+                NftStandard::NonFungible,
+                &spl_token::ID,
+            )],
+            &[payer],
+        )
+        .unwrap();
+
+    // The borrower only gains the rent of the closed loan account
+    assert!(harness.lamports(&loan.borrower) > borrower_lamports);
+    assert_eq!(
+        harness.token_balance(&get_associated_token_address(
+            &loan.borrower,
+            &loan.nft_mint
+        )),
+        1
+    );
+    assert_eq!(events::<LoanRepaid>(&logs)[0].payer, payer);
+}
+
+#[test]
+fn repay_loan_leaves_a_vault_account_holding_stray_tokens_open() {
+    let mut harness = Harness::new();
+    let loan = stake(&mut harness, None);
+    let lender = fund(&mut harness, &loan);
+    harness.send_stray_token(&vault_nft_account(&loan.nft_mint));
+
+    harness
+        .process_transaction(
+            &[instructions::repay_loan(
+                &loan.borrower,
+                &loan.borrower,
+                &lender,
+                &loan.nft_mint,
+                loan.loan_id,
+                NftStandard::NonFungible,
+                &spl_token::ID,
+            )],
+            &[loan.borrower],
+        )
+        .unwrap();
+
+    assert_eq!(
+        harness.token_balance(&get_associated_token_address(
+            &loan.borrower,
+            &loan.nft_mint
+        )),
+        1
+    );
+    assert_eq!(harness.token_balance(&vault_nft_account(&loan.nft_mint)), 1);
+}
+
+#[test]
+fn claim_delinquent_nft_leaves_a_vault_account_holding_stray_tokens_open() {
+    let mut harness = Harness::new();
+    let loan = stake(&mut harness, None);
+    let lender = fund(&mut harness, &loan);
+    harness.send_stray_token(&vault_nft_account(&loan.nft_mint));
+    harness.warp_forward(DURATION + 1);
+
+    harness
+        .process_transaction(&[claim(&lender, &loan)], &[lender])
+        .unwrap();
+
+    assert_eq!(
+        harness.token_balance(&get_associated_token_address(&lender, &loan.nft_mint)),
+        1
+    );
+    assert_eq!(harness.token_balance(&vault_nft_account(&loan.nft_mint)), 1);
+}
+
+#[test]
+fn repay_loan_pays_the_current_note_holder() {
+    let mut harness = Harness::new();
+    let loan = stake(&mut harness, None);
+    let lender = fund(&mut harness, &loan);
+    let buyer = harness.new_wallet();
+    let note_mint = pda::loan_note_mint(&loan.nft_mint, loan.loan_id).0;
+    harness.transfer_tokens(&lender, &buyer, &note_mint, 1);
+    let buyer_lamports = harness.lamports(&buyer);
+
+    // The original lender no longer holds the note
+    let result = harness.process_transaction(
+        &[instructions::repay_loan(
+            &loan.borrower,
+            &loan.borrower,
+            &lender,
+            &loan.nft_mint,
+            loan.loan_id,
+            NftStandard::NonFungible,
+            &spl_token::ID,
+        )],
+        &[loan.borrower],
+    );
+    assert_program_error(result, ErrorCode::InvalidLoanNoteAccount);
+
+    harness
+        .process_transaction(
+            &[instructions::repay_loan(
+                &loan.borrower,
+                &loan.borrower,
+                &buyer,
+                &loan.nft_mint,
+                loan.loan_id,
+                NftStandard::NonFungible,
+                &spl_token::ID,
+            )],
+            &[loan.borrower],
+        )
+        .unwrap();
+    assert_eq!(
+        harness.lamports(&buyer),
+        buyer_lamports + LOAN_AMOUNT + INTEREST_AMOUNT
+    );
+}
+
+#[test]
+fn repay_loan_rejects_a_lender_that_does_not_own_the_note_account() {
+    let mut harness = Harness::new();
+    let loan = stake(&mut harness, None);
+    let lender = fund(&mut harness, &loan);
+    let impostor = harness.new_wallet();
+
+    let mut repay = instructions::repay_loan(
+        &loan.borrower,
+        &loan.borrower,
+        &lender,
+        &loan.nft_mint,
+        loan.loan_id,
+        NftStandard::NonFungible,
+        &spl_token::ID,
+    );
+    // Swap in a different lender wallet while keeping the genuine note account
+    replace_account(&mut repay, &lender, &impostor);
+
+    let result = harness.process_transaction(&[repay], &[loan.borrower]);
+    assert_program_error(result, ErrorCode::InvalidLender);
+}
+
+#[test]
+fn repay_loan_rejects_an_unfunded_loan() {
+    let mut harness = Harness::new();
+    let loan = stake(&mut harness, None);
+
+    let result = harness.process_transaction(
+        &[instructions::repay_loan(
+            &loan.borrower,
+            &loan.borrower,
+            &loan.borrower,
+            &loan.nft_mint,
+            loan.loan_id,
+            NftStandard::NonFungible,
+            &spl_token::ID,
+        )],
+        &[loan.borrower],
+    );
+    // An unfunded request is not an active loan, and has no loan note yet
+    assert_program_error(result, ErrorCode::LoanNotActive);
+}
+
+#[test]
+fn repay_loan_rejects_the_wrong_borrower() {
+    let mut harness = Harness::new();
+    let loan = stake(&mut harness, None);
+    let lender = fund(&mut harness, &loan);
+    let impostor = harness.new_wallet();
+
+    let mut repay = instructions::repay_loan(
+        &impostor,
+        &impostor,
+        &lender,
+        &loan.nft_mint,
+        loan.loan_id,
+        NftStandard::NonFungible,
+        &spl_token::ID,
+    );
+    // The profile is derived from the loan's borrower, so keep the genuine one
+    replace_account(
+        &mut repay,
+        &pda::borrower_profile(&impostor).0,
+        &pda::borrower_profile(&loan.borrower).0,
+    );
+
+    let result = harness.process_transaction(&[repay], &[impostor]);
+    assert_program_error(result, ErrorCode::InvalidBorrower);
+}
+
+#[test]
+fn repay_loan_rejects_an_overdue_loan() {
+    let mut harness = Harness::new();
+    let loan = stake(&mut harness, None);
+    let lender = fund(&mut harness, &loan);
+    harness.warp_forward(DURATION + 1);
+
+    let result = harness.process_transaction(
+        &[instructions::repay_loan(
+            &loan.borrower,
+            &loan.borrower,
+            &lender,
+            &loan.nft_mint,
+            loan.loan_id,
+            NftStandard::NonFungible,
+            &spl_token::ID,
+        )],
+        &[loan.borrower],
+    );
+    assert_program_error(result, ErrorCode::LoanExpired);
+}
+
+#[test]
+fn repay_loan_rejects_an_overflowing_repayment() {
+    let mut harness = Harness::new();
+    let borrower = harness.new_wallet();
+    let nft_mint = harness.create_nft(&borrower);
+    harness
+        .process_transaction(
+            &[instructions::stake_nft_for_loan(
+                &borrower,
+                &nft_mint,
+                1,
+                u64::MAX,
+                DURATION,
+                None,
+                NftStandard::NonFungible,
+                &spl_token::ID,
+            )],
+            &[borrower],
+        )
+        .unwrap();
+    let loan = StakedLoan {
+        borrower,
+        nft_mint,
+        loan_id: 1,
+    };
+    let lender = fund(&mut harness, &loan);
+
+    let result = harness.process_transaction(
+        &[instructions::repay_loan(
+            &borrower,
+            &borrower,
+            &lender,
+            &nft_mint,
+            1,
+            NftStandard::NonFungible,
+            &spl_token::ID,
+        )],
+        &[borrower],
+    );
+    assert_program_error(result, ErrorCode::CalculationError);
+}
+
+#[test]
+fn claim_delinquent_nft_transfers_the_collateral_after_expiry() {
+    let mut harness = Harness::new();
+    let loan = stake(&mut harness, None);
+    let lender = fund(&mut harness, &loan);
+    let claim = instructions::claim_delinquent_nft(
+        &lender,
+        &loan.borrower,
+        &loan.nft_mint,
+        loan.loan_id,
+        NftStandard::NonFungible,
+        &spl_token::ID,
+    );
+
+    let result = harness.process_transaction(std::slice::from_ref(&claim), &[lender]);
+    assert_program_error(result, ErrorCode::LoanNotExpired);
+
+    harness.warp_forward(DURATION + 1);
+    let borrower_lamports = harness.lamports(&loan.borrower);
+    let vault_account_rent = harness.lamports(&vault_nft_account(&loan.nft_mint));
+    let logs = harness.process_transaction(&[claim], &[lender]).unwrap();
+
+    assert!(harness.account(&pda::loan_info(&loan.nft_mint).0).is_none());
+    assert!(harness
+        .account(&vault_nft_account(&loan.nft_mint))
+        .is_none());
+    assert_eq!(
+        harness.lamports(&loan.borrower),
+        borrower_lamports + vault_account_rent
+    );
+    assert_eq!(
+        harness.token_balance(&get_associated_token_address(&lender, &loan.nft_mint)),
+        1
+    );
+    let record: LoanRecord =
+        harness.anchor_account(&pda::loan_record(&loan.nft_mint, loan.loan_id).0);
+    assert_eq!(record.outcome, LoanOutcome::Defaulted);
+    let profile: BorrowerProfile = harness.anchor_account(&pda::borrower_profile(&loan.borrower).0);
+    assert_eq!(profile.loans_defaulted, 1);
+    assert_eq!(events::<LoanDefaulted>(&logs)[0].lender, lender);
+
+    // The settled loan note is burned and its token account closed
+    assert!(harness.account(&note_account(&lender, &loan)).is_none());
+    assert_eq!(
+        harness.mint_supply(&pda::loan_note_mint(&loan.nft_mint, loan.loan_id).0),
+        0
+    );
+}
+
+#[test]
+fn claim_delinquent_nft_rejects_an_unfunded_loan() {
+    let mut harness = Harness::new();
+    let loan = stake(&mut harness, None);
+    let lender = harness.new_wallet();
+
+    let result = harness.process_transaction(
+        &[instructions::claim_delinquent_nft(
+            &lender,
+            &loan.borrower,
+            &loan.nft_mint,
+            loan.loan_id,
+            NftStandard::NonFungible,
+            &spl_token::ID,
+        )],
+        &[lender],
+    );
+    // An unfunded request is not an active loan, and has no loan note yet
+    assert_program_error(result, ErrorCode::LoanNotActive);
+}
+
+#[test]
+fn claim_delinquent_nft_requires_the_loan_note() {
+    let mut harness = Harness::new();
+    let loan = stake(&mut harness, None);
+    let lender = fund(&mut harness, &loan);
+    let buyer = harness.new_wallet();
+    let note_mint = pda::loan_note_mint(&loan.nft_mint, loan.loan_id).0;
+    harness.transfer_tokens(&lender, &buyer, &note_mint, 1);
+    harness.warp_forward(DURATION + 1);
+
+    let result = harness.process_transaction(
+        &[instructions::claim_delinquent_nft(
+            &lender,
+            &loan.borrower,
+            &loan.nft_mint,
+            loan.loan_id,
+            NftStandard::NonFungible,
+            &spl_token::ID,
+        )],
+        &[lender],
+    );
+    assert_program_error(result, ErrorCode::InvalidLoanNoteAccount);
+
+    let mut claim = instructions::claim_delinquent_nft(
+        &lender,
+        &loan.borrower,
+        &loan.nft_mint,
+        loan.loan_id,
+        NftStandard::NonFungible,
+        &spl_token::ID,
+    );
+    // Present the buyer's note account while the original lender signs
+    let lender_note_account = note_account(&lender, &loan);
+    replace_account(
+        &mut claim,
+        &lender_note_account,
+        &note_account(&buyer, &loan),
+    );
+    let result = harness.process_transaction(&[claim], &[lender]);
+    assert_program_error(result, ErrorCode::InvalidLender);
+}
+
+/// Enables default royalties and gives the loan's NFT a 5% seller fee split 70/30 between two creators
+fn enable_default_royalties(harness: &mut Harness, loan: &StakedLoan) -> [Pubkey; 2] {
+    let admin = get_admin_account_pubkey();
+    harness.airdrop(&admin, 1_000_000_000);
+    harness
+        .process_transaction(
+            &[instructions::set_default_royalties(&admin, true)],
+            &[admin],
+        )
+        .unwrap();
+    let creators = [harness.new_wallet(), harness.new_wallet()];
+    harness.set_token_metadata_with_royalty(
+        &loan.nft_mint,
+        TokenStandard::NonFungible,
+        500,
+        &[(creators[0], 70), (creators[1], 30)],
+    );
+    creators
+}
+
+fn claim(lender: &Pubkey, loan: &StakedLoan) -> Instruction {
+    instructions::claim_delinquent_nft(
+        lender,
+        &loan.borrower,
+        &loan.nft_mint,
+        loan.loan_id,
+        NftStandard::NonFungible,
+        &spl_token::ID,
+    )
+}
+
+#[test]
+fn claim_delinquent_nft_pays_creator_royalties_when_enabled() {
+    let mut harness = Harness::new();
+    let loan = stake(&mut harness, None);
+    let lender = fund(&mut harness, &loan);
+    let creators = enable_default_royalties(&mut harness, &loan);
+    harness.warp_forward(DURATION + 1);
+    let creator_lamports = creators.map(|creator| harness.lamports(&creator));
+
+    let logs = harness
+        .process_transaction(
+            &[instructions::with_royalty_creators(
+                claim(&lender, &loan),
+                &creators,
+            )],
+            &[lender],
+        )
+        .unwrap();
+
+    // 5% of the principal, split 70/30
+    let royalty = LOAN_AMOUNT / 20;
+    assert_eq!(
+        harness.lamports(&creators[0]),
+        creator_lamports[0] + royalty * 70 / 100
+    );
+    assert_eq!(
+        harness.lamports(&creators[1]),
+        creator_lamports[1] + royalty * 30 / 100
+    );
+    let paid = &events::<DefaultRoyaltyPaid>(&logs)[0];
+    assert_eq!(paid.lender, lender);
+    assert_eq!(paid.basis_amount, LOAN_AMOUNT);
+    assert_eq!(paid.seller_fee_basis_points, 500);
+    assert_eq!(paid.royalty_amount, royalty);
+}
+
+#[test]
+fn claim_delinquent_nft_pays_royalties_from_metadata_minted_by_the_vault() {
+    let mut harness = Harness::new();
+    harness.load_token_metadata();
+    let admin = get_admin_account_pubkey();
+    let borrower = harness.new_wallet();
+    let nft_mint = harness.mint_nft(&borrower, NftStandard::NonFungible);
+    harness
+        .process_transaction(
+            &[instructions::stake_nft_for_loan(
+                &borrower,
+                &nft_mint,
+                LOAN_AMOUNT,
+                INTEREST_AMOUNT,
+                DURATION,
+                None,
+                NftStandard::NonFungible,
+                &spl_token::ID,
+            )],
+            &[borrower],
+        )
+        .unwrap();
+    let loan = StakedLoan {
+        borrower,
+        nft_mint,
+        loan_id: 1,
+    };
+    let lender = fund(&mut harness, &loan);
+    harness
+        .process_transaction(
+            &[instructions::set_default_royalties(&admin, true)],
+            &[admin],
+        )
+        .unwrap();
+    harness.warp_forward(DURATION + 1);
+    let admin_lamports = harness.lamports(&admin);
+
+    // `mint_nft` sets a 5% seller fee paid in full to the admin as sole creator
+    harness
+        .process_transaction(
+            &[instructions::with_royalty_creators(
+                claim(&lender, &loan),
+                &[admin],
+            )],
+            &[lender],
+        )
+        .unwrap();
+    assert_eq!(harness.lamports(&admin), admin_lamports + LOAN_AMOUNT / 20);
+    assert_eq!(
+        harness.token_balance(&get_associated_token_address(&lender, &nft_mint)),
+        1
+    );
+}
+
+#[test]
+fn claim_delinquent_nft_skips_royalties_when_disabled() {
+    let mut harness = Harness::new();
+    let loan = stake(&mut harness, None);
+    let lender = fund(&mut harness, &loan);
+    let creators = enable_default_royalties(&mut harness, &loan);
+    let admin = get_admin_account_pubkey();
+    harness
+        .process_transaction(
+            &[instructions::set_default_royalties(&admin, false)],
+            &[admin],
+        )
+        .unwrap();
+    harness.warp_forward(DURATION + 1);
+    let creator_lamports = harness.lamports(&creators[0]);
+
+    let logs = harness
+        .process_transaction(
+            &[instructions::with_royalty_creators(
+                claim(&lender, &loan),
+                &creators,
+            )],
+            &[lender],
+        )
+        .unwrap();
+
+    assert_eq!(harness.lamports(&creators[0]), creator_lamports);
+    assert!(events::<DefaultRoyaltyPaid>(&logs).is_empty());
+    assert_eq!(events::<LoanDefaulted>(&logs).len(), 1);
+}
+
+#[test]
+fn claim_delinquent_nft_rejects_creators_not_in_the_metadata() {
+    let mut harness = Harness::new();
+    let loan = stake(&mut harness, None);
+    let lender = fund(&mut harness, &loan);
+    let creators = enable_default_royalties(&mut harness, &loan);
+    harness.warp_forward(DURATION + 1);
+
+    // Swapped order
+    let result = harness.process_transaction(
+        &[instructions::with_royalty_creators(
+            claim(&lender, &loan),
+            &[creators[1], creators[0]],
+        )],
+        &[lender],
+    );
+    assert_program_error(result, ErrorCode::InvalidRoyaltyCreator);
+
+    // Missing creators
+    let result = harness.process_transaction(&[claim(&lender, &loan)], &[lender]);
+    assert_program_error(result, ErrorCode::InvalidRoyaltyCreator);
+}
+
+#[test]
+fn cancel_loan_request_returns_the_nft() {
+    let mut harness = Harness::new();
+    let loan = stake(&mut harness, None);
+
+    let logs = harness
+        .process_transaction(
+            &[instructions::cancel_loan_request(
+                &loan.borrower,
+                &loan.nft_mint,
+                loan.loan_id,
+                NftStandard::NonFungible,
+                &spl_token::ID,
+            )],
+            &[loan.borrower],
+        )
+        .unwrap();
+
+    assert!(harness.account(&pda::loan_info(&loan.nft_mint).0).is_none());
+    assert!(harness
+        .account(&vault_nft_account(&loan.nft_mint))
+        .is_none());
+    assert_eq!(
+        harness.token_balance(&get_associated_token_address(
+            &loan.borrower,
+            &loan.nft_mint
+        )),
+        1
+    );
+    let record: LoanRecord =
+        harness.anchor_account(&pda::loan_record(&loan.nft_mint, loan.loan_id).0);
+    assert_eq!(record.outcome, LoanOutcome::Cancelled);
+    let cancelled = events::<LoanCancelled>(&logs);
+    assert_eq!(cancelled[0].closed_by, loan.borrower);
+    assert!(!cancelled[0].expired);
+}
+
+#[test]
+fn cancel_loan_request_rejects_anyone_but_the_borrower() {
+    let mut harness = Harness::new();
+    let loan = stake(&mut harness, None);
+    let stranger = harness.new_wallet();
+
+    let mut cancel = instructions::cancel_loan_request(
+        &stranger,
+        &loan.nft_mint,
+        loan.loan_id,
+        NftStandard::NonFungible,
+        &spl_token::ID,
+    );
+    // Keep the borrower's token account and profile so only the ownership check can fail
+    replace_account(
+        &mut cancel,
+        &get_associated_token_address(&stranger, &loan.nft_mint),
+        &get_associated_token_address(&loan.borrower, &loan.nft_mint),
+    );
+    replace_account(
+        &mut cancel,
+        &pda::borrower_profile(&stranger).0,
+        &pda::borrower_profile(&loan.borrower).0,
+    );
+
+    let result = harness.process_transaction(&[cancel], &[stranger]);
+    assert_program_error(result, ErrorCode::UnauthorizedLoanCancellation);
+}
+
+#[test]
+fn cancel_loan_request_rejects_a_funded_loan() {
+    let mut harness = Harness::new();
+    let loan = stake(&mut harness, None);
+    fund(&mut harness, &loan);
+
+    let result = harness.process_transaction(
+        &[instructions::cancel_loan_request(
+            &loan.borrower,
+            &loan.nft_mint,
+            loan.loan_id,
+            NftStandard::NonFungible,
+            &spl_token::ID,
+        )],
+        &[loan.borrower],
+    );
+    assert_program_error(result, ErrorCode::LoanAlreadyFunded);
+}
+
+#[test]
+fn close_expired_loan_request_is_permissionless_after_expiry() {
+    let mut harness = Harness::new();
+    let expiry = harness.now() + 3600;
+    let loan = stake(&mut harness, Some(expiry));
+    let cranker = harness.new_wallet();
+    let crank = instructions::close_expired_loan_request(
+        &cranker,
+        &loan.borrower,
+        &loan.nft_mint,
+        loan.loan_id,
+        NftStandard::NonFungible,
+        &spl_token::ID,
+    );
+
+    let result = harness.process_transaction(std::slice::from_ref(&crank), &[cranker]);
+    assert_program_error(result, ErrorCode::LoanRequestNotExpired);
+
+    harness.warp_forward(3601);
+    let logs = harness.process_transaction(&[crank], &[cranker]).unwrap();
+
+    assert!(harness.account(&pda::loan_info(&loan.nft_mint).0).is_none());
+    assert_eq!(
+        harness.token_balance(&get_associated_token_address(
+            &loan.borrower,
+            &loan.nft_mint
+        )),
+        1
+    );
+    let profile: BorrowerProfile = harness.anchor_account(&pda::borrower_profile(&loan.borrower).0);
+    assert_eq!(profile.loans_cancelled, 1);
+    let cancelled = events::<LoanCancelled>(&logs);
+    assert_eq!(cancelled[0].closed_by, cranker);
+    assert!(cancelled[0].expired);
+}
+
+#[test]
+fn close_expired_loan_request_rejects_requests_without_expiry() {
+    let mut harness = Harness::new();
+    let loan = stake(&mut harness, None);
+    let cranker = harness.new_wallet();
+    harness.warp_forward(365 * 24 * 60 * 60);
+
+    let result = harness.process_transaction(
+        &[instructions::close_expired_loan_request(
+            &cranker,
+            &loan.borrower,
+            &loan.nft_mint,
+            loan.loan_id,
+            NftStandard::NonFungible,
+            &spl_token::ID,
+        )],
+        &[cranker],
+    );
+    assert_program_error(result, ErrorCode::LoanRequestNotExpired);
+}
+
+#[test]
+fn close_expired_loan_request_rejects_a_funded_loan() {
+    let mut harness = Harness::new();
+    let expiry = harness.now() + 3600;
+    let loan = stake(&mut harness, Some(expiry));
+    fund(&mut harness, &loan);
+    let cranker = harness.new_wallet();
+    harness.warp_forward(3601);
+
+    let result = harness.process_transaction(
+        &[instructions::close_expired_loan_request(
+            &cranker,
+            &loan.borrower,
+            &loan.nft_mint,
+            loan.loan_id,
+            NftStandard::NonFungible,
+            &spl_token::ID,
+        )],
+        &[cranker],
+    );
+    assert_program_error(result, ErrorCode::LoanAlreadyFunded);
+}
+
+#[test]
+fn close_expired_loan_request_rejects_the_wrong_owner() {
+    let mut harness = Harness::new();
+    let expiry = harness.now() + 3600;
+    let loan = stake(&mut harness, Some(expiry));
+    let cranker = harness.new_wallet();
+    harness.warp_forward(3601);
+
+    let mut crank = instructions::close_expired_loan_request(
+        &cranker,
+        &cranker,
+        &loan.nft_mint,
+        loan.loan_id,
+        NftStandard::NonFungible,
+        &spl_token::ID,
+    );
+    // The profile is derived from the loan's borrower, so keep the genuine one
+    replace_account(
+        &mut crank,
+        &pda::borrower_profile(&cranker).0,
+        &pda::borrower_profile(&loan.borrower).0,
+    );
+
+    let result = harness.process_transaction(&[crank], &[cranker]);
+    assert_program_error(result, ErrorCode::InvalidBorrower);
+}
+
+#[test]
+fn loans_against_the_same_nft_get_sequential_ids() {
+    let mut harness = Harness::new();
+    let loan = stake(&mut harness, None);
+    harness
+        .process_transaction(
+            &[instructions::cancel_loan_request(
+                &loan.borrower,
+                &loan.nft_mint,
+                loan.loan_id,
+                NftStandard::NonFungible,
+                &spl_token::ID,
+            )],
+            &[loan.borrower],
+        )
+        .unwrap();
+
+    harness
+        .process_transaction(
+            &[instructions::stake_nft_for_loan(
+                &loan.borrower,
+                &loan.nft_mint,
+                LOAN_AMOUNT,
+                INTEREST_AMOUNT,
+                DURATION,
+                None,
+                NftStandard::NonFungible,
+                &spl_token::ID,
+            )],
+            &[loan.borrower],
+        )
+        .unwrap();
+
+    let loan_info: LoanInfo = harness.anchor_account(&pda::loan_info(&loan.nft_mint).0);
+    assert_eq!(loan_info.loan_id, 2);
+}
+
+#[test]
+fn failed_transactions_leave_no_trace() {
+    let mut harness = Harness::new();
+    let borrower = harness.new_wallet();
+    let nft_mint = harness.create_nft(&borrower);
+    let lamports = harness.lamports(&borrower);
+
+    // The stake succeeds but the transaction's second instruction does not
+    let result = harness.process_transaction(
+        &[
+            instructions::stake_nft_for_loan(
+                &borrower,
+                &nft_mint,
+                LOAN_AMOUNT,
+                INTEREST_AMOUNT,
+                DURATION,
+                None,
+                NftStandard::NonFungible,
+                &spl_token::ID,
+            ),
+            instructions::cancel_loan_request(
+                &borrower,
+                &nft_mint,
+                2,
+                NftStandard::NonFungible,
+                &spl_token::ID,
+            ),
+        ],
+        &[borrower],
+    );
+    assert_instruction_error(
+        result,
+        InstructionError::Custom(AnchorErrorCode::ConstraintSeeds.into()),
+    );
+
+    assert!(harness.account(&pda::loan_info(&nft_mint).0).is_none());
+    assert_eq!(harness.lamports(&borrower), lamports);
+    assert_eq!(
+        harness.token_balance(&get_associated_token_address(&borrower, &nft_mint)),
+        1
+    );
+}
+
+/// A loan as stored by the deployed program before LoanInfo was versioned
+struct BaselineLoan {
+    borrower: Pubkey,
+    nft_mint: Pubkey,
+    lender: Option<Pubkey>,
+}
+
+/// Stores a baseline loan against a fresh NFT held by the vault: the NFT sits in the vault's token
+/// account and the LoanInfo account holds the 139 bytes written by the baseline program
+fn set_baseline_loan(harness: &mut Harness, funded: bool) -> BaselineLoan {
+    let borrower = harness.new_wallet();
+    let lender = funded.then(|| harness.new_wallet());
+    let nft_mint = harness.create_nft(&pda::vault_authority().0);
+    let start_time = funded.then(|| harness.now());
+
+    // Borsh encoding of the baseline fields, written out by hand so the test does not depend on
+    // the program's own legacy struct
+    let mut data = LoanInfo::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(nft_mint.as_ref());
+    data.extend_from_slice(borrower.as_ref());
+    data.extend_from_slice(&LOAN_AMOUNT.to_le_bytes());
+    data.extend_from_slice(&INTEREST_AMOUNT.to_le_bytes());
+    data.extend_from_slice(&DURATION.to_le_bytes());
+    match start_time {
+        Some(start_time) => {
+            data.push(1);
+            data.extend_from_slice(&start_time.to_le_bytes());
+        }
+        None => data.push(0),
+    }
+    match lender {
+        Some(lender) => {
+            data.push(1);
+            data.extend_from_slice(lender.as_ref());
+        }
+        None => data.push(0),
+    }
+    data.push(funded as u8);
+    data.resize(BASELINE_LOAN_INFO_SPACE, 0);
+    harness.set_account(
+        pda::loan_info(&nft_mint).0,
+        StoredAccount {
+            lamports: Rent::default().minimum_balance(BASELINE_LOAN_INFO_SPACE),
+            data,
+            owner: collectible_vault::ID,
+            executable: false,
+        },
+    );
+
+    BaselineLoan {
+        borrower,
+        nft_mint,
+        lender,
+    }
+}
+
+/// Runs `migrate_account` on the LoanInfo account of `nft_mint` as the admin
+fn migrate_loan_info(
+    harness: &mut Harness,
+    nft_mint: &Pubkey,
+) -> Result<Vec<String>, TransactionError> {
+    let admin = get_admin_account_pubkey();
+    harness.airdrop(&admin, 1_000_000_000);
+    harness.process_transaction(
+        &[instructions::migrate_account(
+            &admin,
+            &pda::loan_info(nft_mint).0,
+        )],
+        &[admin],
+    )
+}
+
+#[test]
+fn migrate_account_upgrades_a_baseline_funded_loan() {
+    let mut harness = Harness::new();
+    let loan = set_baseline_loan(&mut harness, true);
+    let key = pda::loan_info(&loan.nft_mint).0;
+    assert_eq!(LegacyLoanInfo::SPACE, BASELINE_LOAN_INFO_SPACE);
+
+    // Only the admin can migrate accounts
+    let result = harness.process_transaction(
+        &[instructions::migrate_account(&loan.borrower, &key)],
+        &[loan.borrower],
+    );
+    assert_program_error(result, ErrorCode::UnauthorizedTransactionSigner);
+
+    let logs = migrate_loan_info(&mut harness, &loan.nft_mint).unwrap();
+    assert_eq!(events::<AccountMigrated>(&logs)[0].from_version, 0);
+
+    let account = harness.account(&key).unwrap();
+    assert_eq!(account.data.len(), LoanInfo::INIT_SPACE);
+    assert_eq!(
+        account.lamports,
+        Rent::default().minimum_balance(LoanInfo::INIT_SPACE)
+    );
+    let loan_info: LoanInfo = harness.anchor_account(&key);
+    assert_eq!(loan_info.header.version, LoanInfo::VERSION);
+    assert_eq!(loan_info.nft_mint, loan.nft_mint);
+    assert_eq!(loan_info.nft_owner, loan.borrower);
+    assert_eq!(loan_info.loan_amount, LOAN_AMOUNT);
+    assert_eq!(loan_info.interest_amount, INTEREST_AMOUNT);
+    assert_eq!(loan_info.duration, DURATION);
+    assert_eq!(loan_info.start_time, Some(harness.now()));
+    assert_eq!(loan_info.lender, loan.lender);
+    assert_eq!(loan_info.status, LoanStatus::Active);
+    assert_eq!(loan_info.request_expiry, None);
+    assert_eq!(loan_info.loan_id, 0);
+    assert_eq!(loan_info.reserved, [0; 64]);
+
+    let result = migrate_loan_info(&mut harness, &loan.nft_mint);
+    assert_program_error(result, ErrorCode::AccountAlreadyMigrated);
+}
+
+#[test]
+fn migrate_account_upgrades_a_baseline_unfunded_request() {
+    let mut harness = Harness::new();
+    let loan = set_baseline_loan(&mut harness, false);
+
+    migrate_loan_info(&mut harness, &loan.nft_mint).unwrap();
+
+    let loan_info: LoanInfo = harness.anchor_account(&pda::loan_info(&loan.nft_mint).0);
+    assert_eq!(loan_info.status, LoanStatus::Requested);
+    assert_eq!(loan_info.start_time, None);
+    assert_eq!(loan_info.lender, None);
+    assert_eq!(loan_info.request_expiry, None);
+    assert_eq!(loan_info.loan_id, 0);
+}
+
+#[test]
+fn repay_loan_pays_the_recorded_lender_of_a_migrated_loan() {
+    let mut harness = Harness::new();
+    let loan = set_baseline_loan(&mut harness, true);
+    let lender = loan.lender.unwrap();
+    migrate_loan_info(&mut harness, &loan.nft_mint).unwrap();
+    let impostor = harness.new_wallet();
+    let repay = |note_holder: &Pubkey| {
+        instructions::repay_loan(
+            &loan.borrower,
+            &loan.borrower,
+            note_holder,
+            &loan.nft_mint,
+            0,
+            NftStandard::NonFungible,
+            &spl_token::ID,
+        )
+    };
+
+    // A migrated loan has no loan note, so only the lender recorded on the loan is paid
+    let result = harness.process_transaction(&[repay(&impostor)], &[loan.borrower]);
+    assert_program_error(result, ErrorCode::InvalidLender);
+
+    let lender_lamports = harness.lamports(&lender);
+    harness
+        .process_transaction(&[repay(&lender)], &[loan.borrower])
+        .unwrap();
+
+    assert_eq!(
+        harness.lamports(&lender),
+        lender_lamports + LOAN_AMOUNT + INTEREST_AMOUNT
+    );
+    assert_eq!(
+        harness.token_balance(&get_associated_token_address(
+            &loan.borrower,
+            &loan.nft_mint
+        )),
+        1
+    );
+    let record: LoanRecord = harness.anchor_account(&pda::loan_record(&loan.nft_mint, 0).0);
+    assert_eq!(record.outcome, LoanOutcome::Repaid);
+    assert_eq!(record.lender, Some(lender));
+}
+
+#[test]
+fn claim_delinquent_nft_lets_the_recorded_lender_claim_a_migrated_loan() {
+    let mut harness = Harness::new();
+    let loan = set_baseline_loan(&mut harness, true);
+    let lender = loan.lender.unwrap();
+    migrate_loan_info(&mut harness, &loan.nft_mint).unwrap();
+    let impostor = harness.new_wallet();
+    harness.warp_forward(DURATION + 1);
+    let claim = |note_holder: &Pubkey| {
+        instructions::claim_delinquent_nft(
+            note_holder,
+            &loan.borrower,
+            &loan.nft_mint,
+            0,
+            NftStandard::NonFungible,
+            &spl_token::ID,
+        )
+    };
+
+    let result = harness.process_transaction(&[claim(&impostor)], &[impostor]);
+    assert_program_error(result, ErrorCode::InvalidLender);
+
+    harness
+        .process_transaction(&[claim(&lender)], &[lender])
+        .unwrap();
+
+    assert_eq!(
+        harness.token_balance(&get_associated_token_address(&lender, &loan.nft_mint)),
+        1
+    );
+    let record: LoanRecord = harness.anchor_account(&pda::loan_record(&loan.nft_mint, 0).0);
+    assert_eq!(record.outcome, LoanOutcome::Defaulted);
+}
+
+/// Creates an empty vault token account for `nft_mint`, as a loan repaid or defaulted before loan
+/// exits closed it would have left behind
+fn leave_vault_account_behind(harness: &mut Harness, nft_mint: &Pubkey) {
+    let payer = harness.new_wallet();
+    harness
+        .process_transaction(
+            &[
+                spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+                    &payer,
+                    &pda::vault_authority().0,
+                    nft_mint,
+                    &spl_token::ID,
+                ),
+            ],
+            &[payer],
+        )
+        .unwrap();
+}
+
+/// Stores the loan history of `nft_mint`: one closed loan per borrower and outcome, in order
+fn set_loan_history(harness: &mut Harness, nft_mint: &Pubkey, loans: &[(Pubkey, LoanOutcome)]) {
+    harness.set_program_account(
+        pda::loan_counter(nft_mint).0,
+        &LoanCounter {
+            header: AccountHeader::new(LoanCounter::VERSION),
+            nft_mint: *nft_mint,
+            count: loans.len() as u64,
+        },
+    );
+    for (index, (borrower, outcome)) in loans.iter().enumerate() {
+        let loan_id = index as u64 + 1;
+        harness.set_program_account(
+            pda::loan_record(nft_mint, loan_id).0,
+            &LoanRecord {
+                header: AccountHeader::new(LoanRecord::VERSION),
+                nft_mint: *nft_mint,
+                loan_id,
+                borrower: *borrower,
+                lender: None,
+                loan_amount: LOAN_AMOUNT,
+                interest_amount: INTEREST_AMOUNT,
+                duration: DURATION,
+                start_time: None,
+                closed_at: harness.now(),
+                outcome: *outcome,
+            },
+        );
+    }
+}
+
+#[test]
+fn sweep_baseline_vault_account_refunds_the_admin_for_an_nft_without_loan_history() {
+    let mut harness = Harness::new();
+    // A baseline loan that has since been repaid: its empty vault token account remains, but the
+    // NFT has neither a LoanCounter nor LoanRecords
+    let owner = harness.new_wallet();
+    let nft_mint = harness.create_nft(&owner);
+    leave_vault_account_behind(&mut harness, &nft_mint);
+    let vault_account_rent = harness.lamports(&vault_nft_account(&nft_mint));
+    let admin = get_admin_account_pubkey();
+    let admin_lamports = harness.lamports(&admin);
+
+    let cranker = harness.new_wallet();
+    let logs = harness
+        .process_transaction(
+            &[instructions::sweep_baseline_vault_account(
+                &cranker,
+                &nft_mint,
+                &spl_token::ID,
+            )],
+            &[cranker],
+        )
+        .unwrap();
+
+    assert!(harness.account(&vault_nft_account(&nft_mint)).is_none());
+    assert_eq!(
+        harness.lamports(&admin),
+        admin_lamports + vault_account_rent
+    );
+    let swept = events::<VaultAccountSwept>(&logs);
+    assert_eq!(swept[0].rent_recipient, admin);
+    assert_eq!(swept[0].lamports, vault_account_rent);
+}
+
+#[test]
+fn sweep_baseline_vault_account_rejects_an_nft_with_loan_history() {
+    let mut harness = Harness::new();
+    let owner = harness.new_wallet();
+    let nft_mint = harness.create_nft(&owner);
+    set_loan_history(&mut harness, &nft_mint, &[(owner, LoanOutcome::Repaid)]);
+    leave_vault_account_behind(&mut harness, &nft_mint);
+    let cranker = harness.new_wallet();
+
+    let result = harness.process_transaction(
+        &[instructions::sweep_baseline_vault_account(
+            &cranker,
+            &nft_mint,
+            &spl_token::ID,
+        )],
+        &[cranker],
+    );
+    assert_program_error(result, ErrorCode::InvalidVaultAccountPayer);
+    assert!(harness.account(&vault_nft_account(&nft_mint)).is_some());
+}
+
+#[test]
+fn sweep_baseline_vault_account_rejects_an_account_holding_a_baseline_loans_nft() {
+    let mut harness = Harness::new();
+    let loan = set_baseline_loan(&mut harness, true);
+    let cranker = harness.new_wallet();
+
+    let result = harness.process_transaction(
+        &[instructions::sweep_baseline_vault_account(
+            &cranker,
+            &loan.nft_mint,
+            &spl_token::ID,
+        )],
+        &[cranker],
+    );
+    assert_program_error(result, ErrorCode::VaultAccountNotSweepable);
+    assert_eq!(harness.token_balance(&vault_nft_account(&loan.nft_mint)), 1);
+}
@@ -0,0 +1,544 @@
+/**
+ * Property-based tests of the loan state machine.
+ *
+ * Random sequences of loan instructions, loan note transfers and clock advances are run against
+ * a single NFT shared by a handful of wallets. After every step the test checks that the step
+ * was a legal transition from the loan's previous phase, and that the bank-wide invariants hold:
+ * lamports are conserved, the NFT sits in exactly one place, and the loan's accounts, notes,
+ * records and borrower profiles agree with each other.
+ */
+mod common;
+
+use {
+    anchor_lang::{prelude::Pubkey, solana_program::native_token::LAMPORTS_PER_SOL},
+    collectible_vault::{
+        instructions::nft_transfer::NftStandard,
+        state::{
+            BorrowerProfile, LoanCounter, LoanInfo, LoanOutcome, LoanRecord, LoanStatus,
+            ReputationGate,
+        },
+    },
+    collectible_vault_client::{instructions, pda},
+    common::{Harness, TransactionError},
+    proptest::prelude::*,
+    spl_associated_token_account::get_associated_token_address,
+};
+
+/// Number of wallets taking part; wallet 0 owns the NFT at the start
+const ACTORS: usize = 4;
+
+const DAY: i64 = 24 * 60 * 60;
+
+/// The gate used by gated funding attempts
+const REPUTATION_GATE: ReputationGate = ReputationGate {
+    min_loans_repaid_on_time: 1,
+    max_loans_defaulted: 0,
+    min_repaid_volume: LAMPORTS_PER_SOL,
+};
+
+/**
+ * One step of a generated scenario. `None` for an optional actor means the wallet entitled to
+ * act is used, so that generated scenarios make progress instead of mostly failing.
+ */
+#[derive(Clone, Debug)]
+enum Operation {
+    Stake {
+        actor: Option<usize>,
+        loan_amount: u64,
+        interest_amount: u64,
+        duration: i64,
+        request_expiry_offset: Option<i64>,
+    },
+    Fund {
+        actor: usize,
+        gated: bool,
+    },
+    Repay {
+        payer: usize,
+        note_holder: Option<usize>,
+    },
+    Claim {
+        actor: Option<usize>,
+    },
+    Cancel {
+        actor: Option<usize>,
+    },
+    CloseExpired {
+        cranker: usize,
+    },
+    TransferNote {
+        to: usize,
+    },
+    Warp {
+        seconds: i64,
+    },
+}
+
+fn actor() -> impl Strategy<Value = usize> {
+    0..ACTORS
+}
+
+fn maybe_actor() -> impl Strategy<Value = Option<usize>> {
+    prop::option::weighted(0.25, actor())
+}
+
+fn operation() -> impl Strategy<Value = Operation> {
+    prop_oneof![
+        3 => (
+            maybe_actor(),
+            1..=5u64,
+            0..=1_000u64,
+            1..=10 * DAY,
+            prop::option::of(0..=5 * DAY),
+        )
+            .prop_map(|(actor, sol, milli_sol, duration, request_expiry_offset)| {
+                Operation::Stake {
+                    actor,
+                    loan_amount: sol * LAMPORTS_PER_SOL,
+                    interest_amount: milli_sol * (LAMPORTS_PER_SOL / 1_000),
+                    duration,
+                    request_expiry_offset,
+                }
+            }),
+        3 => (actor(), prop::bool::weighted(0.2))
+            .prop_map(|(actor, gated)| Operation::Fund { actor, gated }),
+        2 => (actor(), maybe_actor())
+            .prop_map(|(payer, note_holder)| Operation::Repay { payer, note_holder }),
+        2 => maybe_actor().prop_map(|actor| Operation::Claim { actor }),
+        1 => maybe_actor().prop_map(|actor| Operation::Cancel { actor }),
+        1 => actor().prop_map(|cranker| Operation::CloseExpired { cranker }),
+        1 => actor().prop_map(|to| Operation::TransferNote { to }),
+        3 => (0..=6 * DAY).prop_map(|seconds| Operation::Warp { seconds }),
+    ]
+}
+
+/// The loan's phase as read from the bank before a step
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Phase {
+    Idle,
+    Requested,
+    Active,
+}
+
+struct LoanFuzz {
+    harness: Harness,
+    actors: Vec<Pubkey>,
+    nft_mint: Pubkey,
+    lamports: u128,
+}
+
+impl LoanFuzz {
+    fn new() -> Self {
+        let mut harness = Harness::new();
+        let actors: Vec<Pubkey> = (0..ACTORS).map(|_| harness.new_wallet()).collect();
+        let nft_mint = harness.create_nft(&actors[0]);
+        let lamports = harness.total_lamports();
+        Self {
+            harness,
+            actors,
+            nft_mint,
+            lamports,
+        }
+    }
+
+    fn loan_info(&self) -> Option<LoanInfo> {
+        let key = pda::loan_info(&self.nft_mint).0;
+        self.harness
+            .account(&key)
+            .map(|_| self.harness.anchor_account(&key))
+    }
+
+    fn loan_count(&self) -> u64 {
+        let key = pda::loan_counter(&self.nft_mint).0;
+        self.harness.account(&key).map_or(0, |_| {
+            self.harness.anchor_account::<LoanCounter>(&key).count
+        })
+    }
+
+    fn phase(&self) -> Phase {
+        match self.loan_info() {
+            None => Phase::Idle,
+            Some(loan_info) if loan_info.status == LoanStatus::Active => Phase::Active,
+            Some(_) => Phase::Requested,
+        }
+    }
+
+    /// The loan the next step refers to: the open loan, or the most recent one
+    fn loan_id(&self) -> u64 {
+        self.loan_info()
+            .map_or(self.loan_count(), |loan_info| loan_info.loan_id)
+    }
+
+    fn note_mint(&self, loan_id: u64) -> Pubkey {
+        pda::loan_note_mint(&self.nft_mint, loan_id).0
+    }
+
+    /// The wallet currently holding the NFT in its own token account, if any
+    fn nft_holder(&self) -> Option<Pubkey> {
+        self.actors.iter().copied().find(|actor| {
+            self.harness
+                .token_balance(&get_associated_token_address(actor, &self.nft_mint))
+                == 1
+        })
+    }
+
+    /// The wallet currently holding the note of a loan, if any
+    fn note_holder(&self, loan_id: u64) -> Option<Pubkey> {
+        let note_mint = self.note_mint(loan_id);
+        self.actors.iter().copied().find(|actor| {
+            self.harness
+                .token_balance(&get_associated_token_address(actor, &note_mint))
+                == 1
+        })
+    }
+
+    fn profile(&self, borrower: &Pubkey) -> BorrowerProfile {
+        let key = pda::borrower_profile(borrower).0;
+        self.harness
+            .account(&key)
+            .map_or_else(BorrowerProfile::default, |_| {
+                self.harness.anchor_account(&key)
+            })
+    }
+
+    fn pick(&self, actor: Option<usize>, entitled: Option<Pubkey>) -> Pubkey {
+        actor
+            .map(|index| self.actors[index])
+            .or(entitled)
+            .unwrap_or(self.actors[0])
+    }
+
+    fn run(
+        &mut self,
+        instruction: anchor_lang::solana_program::instruction::Instruction,
+        signer: Pubkey,
+    ) -> Result<Vec<String>, TransactionError> {
+        self.harness.process_transaction(&[instruction], &[signer])
+    }
+
+    /**
+     * Applies one operation and checks that its outcome is a legal transition.
+     */
+    fn step(&mut self, operation: &Operation) -> Result<(), TestCaseError> {
+        let phase = self.phase();
+        let before = self.loan_info();
+        let loan_id = self.loan_id();
+        let now = self.harness.now();
+        let due = before
+            .as_ref()
+            .and_then(|loan_info| loan_info.start_time)
+            .map(|start_time| start_time + before.as_ref().unwrap().duration);
+        let request_expired = before
+            .as_ref()
+            .is_some_and(|loan_info| loan_info.is_request_expired(now));
+
+        match *operation {
+            Operation::Stake {
+                actor,
+                loan_amount,
+                interest_amount,
+                duration,
+                request_expiry_offset,
+            } => {
+                let holder = self.nft_holder();
+                let owner = self.pick(actor, holder);
+                let request_expiry = request_expiry_offset.map(|offset| now + offset);
+                let result = self.run(
+                    instructions::stake_nft_for_loan(
+                        &owner,
+                        &self.nft_mint,
+                        loan_amount,
+                        interest_amount,
+                        duration,
+                        request_expiry,
+                        NftStandard::NonFungible,
+                        &spl_token::ID,
+                    ),
+                    owner,
+                );
+                let valid = phase == Phase::Idle
+                    && holder == Some(owner)
+                    && request_expiry.is_none_or(|expiry| expiry > now);
+                prop_assert_eq!(result.is_ok(), valid, "stake by {}: {:?}", owner, result);
+                if valid {
+                    let loan_info = self.loan_info().unwrap();
+                    prop_assert_eq!(loan_info.nft_owner, owner);
+                    prop_assert_eq!(loan_info.loan_id, loan_id + 1);
+                    prop_assert_eq!(loan_info.request_expiry, request_expiry);
+                }
+            }
+            Operation::Fund { actor, gated } => {
+                let lender = self.actors[actor];
+                let borrower = before
+                    .as_ref()
+                    .map_or(self.actors[0], |loan_info| loan_info.nft_owner);
+                let gate = gated.then_some(REPUTATION_GATE);
+                let result = self.run(
+                    instructions::provide_loan_liquidity(
+                        &lender,
+                        &borrower,
+                        &self.nft_mint,
+                        loan_id,
+                        gate,
+                    ),
+                    lender,
+                );
+                let affordable = before.as_ref().is_some_and(|loan_info| {
+                    self.harness.lamports(&lender) >= loan_info.loan_amount + LAMPORTS_PER_SOL
+                });
+                let reputable = !gated || self.profile(&borrower).meets(&REPUTATION_GATE);
+                if result.is_ok() {
+                    prop_assert_eq!(phase, Phase::Requested);
+                    prop_assert!(!request_expired);
+                    prop_assert!(reputable);
+                    let loan_info = self.loan_info().unwrap();
+                    prop_assert_eq!(loan_info.lender, Some(lender));
+                    prop_assert_eq!(loan_info.start_time, Some(now));
+                    prop_assert_eq!(self.note_holder(loan_id), Some(lender));
+                } else {
+                    prop_assert!(
+                        phase != Phase::Requested || request_expired || !reputable || !affordable,
+                        "funding failed: {:?}",
+                        result
+                    );
+                }
+            }
+            Operation::Repay { payer, note_holder } => {
+                let payer = self.actors[payer];
+                let current_holder = self.note_holder(loan_id);
+                let note_holder = self.pick(note_holder, current_holder);
+                let borrower = before
+                    .as_ref()
+                    .map_or(self.actors[0], |loan_info| loan_info.nft_owner);
+                let holder_lamports = self.harness.lamports(&note_holder);
+                let result = self.run(
+                    instructions::repay_loan(
+                        &payer,
+                        &borrower,
+                        &note_holder,
+                        &self.nft_mint,
+                        loan_id,
+                        NftStandard::NonFungible,
+                        &spl_token::ID,
+                    ),
+                    payer,
+                );
+                if result.is_ok() {
+                    let loan_info = before.unwrap();
+                    prop_assert_eq!(phase, Phase::Active);
+                    prop_assert!(now <= due.unwrap());
+                    prop_assert_eq!(current_holder, Some(note_holder));
+                    prop_assert_eq!(self.nft_holder(), Some(borrower));
+                    self.assert_record(loan_id, LoanOutcome::Repaid)?;
+                    if note_holder != payer && note_holder != borrower {
+                        prop_assert_eq!(
+                            self.harness.lamports(&note_holder),
+                            holder_lamports + loan_info.loan_amount + loan_info.interest_amount
+                        );
+                    }
+                } else if phase == Phase::Active
+                    && now <= due.unwrap()
+                    && current_holder == Some(note_holder)
+                {
+                    let loan_info = before.unwrap();
+                    let owed = loan_info.loan_amount as u128 + loan_info.interest_amount as u128;
+                    prop_assert!(
+                        (self.harness.lamports(&payer) as u128) < owed + LAMPORTS_PER_SOL as u128,
+                        "repayment failed: {:?}",
+                        result
+                    );
+                }
+            }
+            Operation::Claim { actor } => {
+                let current_holder = self.note_holder(loan_id);
+                let claimant = self.pick(actor, current_holder);
+                let borrower = before
+                    .as_ref()
+                    .map_or(self.actors[0], |loan_info| loan_info.nft_owner);
+                let result = self.run(
+                    instructions::claim_delinquent_nft(
+                        &claimant,
+                        &borrower,
+                        &self.nft_mint,
+                        loan_id,
+                        NftStandard::NonFungible,
+                        &spl_token::ID,
+                    ),
+                    claimant,
+                );
+                let valid = phase == Phase::Active
+                    && now > due.unwrap()
+                    && current_holder == Some(claimant);
+                prop_assert_eq!(result.is_ok(), valid, "claim by {}: {:?}", claimant, result);
+                if valid {
+                    prop_assert_eq!(self.nft_holder(), Some(claimant));
+                    self.assert_record(loan_id, LoanOutcome::Defaulted)?;
+                }
+            }
+            Operation::Cancel { actor } => {
+                let owner = self.pick(actor, before.as_ref().map(|loan_info| loan_info.nft_owner));
+                let result = self.run(
+                    instructions::cancel_loan_request(
+                        &owner,
+                        &self.nft_mint,
+                        loan_id,
+                        NftStandard::NonFungible,
+                        &spl_token::ID,
+                    ),
+                    owner,
+                );
+                let valid = phase == Phase::Requested && before.unwrap().nft_owner == owner;
+                prop_assert_eq!(result.is_ok(), valid, "cancel by {}: {:?}", owner, result);
+                if valid {
+                    prop_assert_eq!(self.nft_holder(), Some(owner));
+                    self.assert_record(loan_id, LoanOutcome::Cancelled)?;
+                }
+            }
+            Operation::CloseExpired { cranker } => {
+                let cranker = self.actors[cranker];
+                let owner = before
+                    .as_ref()
+                    .map_or(self.actors[0], |loan_info| loan_info.nft_owner);
+                let result = self.run(
+                    instructions::close_expired_loan_request(
+                        &cranker,
+                        &owner,
+                        &self.nft_mint,
+                        loan_id,
+                        NftStandard::NonFungible,
+                        &spl_token::ID,
+                    ),
+                    cranker,
+                );
+                let valid = phase == Phase::Requested && request_expired;
+                prop_assert_eq!(result.is_ok(), valid, "crank: {:?}", result);
+                if valid {
+                    prop_assert_eq!(self.nft_holder(), Some(owner));
+                    self.assert_record(loan_id, LoanOutcome::Cancelled)?;
+                }
+            }
+            Operation::TransferNote { to } => {
+                // Only a live note can be traded; settled notes are frozen or burned
+                if let (Phase::Active, Some(holder)) = (phase, self.note_holder(loan_id)) {
+                    let note_mint = self.note_mint(loan_id);
+                    self.harness
+                        .transfer_tokens(&holder, &self.actors[to], &note_mint, 1);
+                }
+            }
+            Operation::Warp { seconds } => self.harness.warp_forward(seconds),
+        }
+
+        self.check_invariants()
+    }
+
+    fn assert_record(&self, loan_id: u64, outcome: LoanOutcome) -> Result<(), TestCaseError> {
+        let record: LoanRecord = self
+            .harness
+            .anchor_account(&pda::loan_record(&self.nft_mint, loan_id).0);
+        prop_assert_eq!(record.loan_id, loan_id);
+        prop_assert_eq!(record.outcome, outcome);
+        prop_assert_eq!(record.closed_at, self.harness.now());
+        Ok(())
+    }
+
+    /**
+     * Checks the invariants that must hold between any two transactions.
+     */
+    fn check_invariants(&self) -> Result<(), TestCaseError> {
+        // Lamports are only ever moved, never created or destroyed
+        prop_assert_eq!(self.harness.total_lamports(), self.lamports);
+
+        // The NFT is in exactly one token account: the vault's while a loan is open, a wallet's
+        // otherwise
+        let holders = self.harness.token_holders(&self.nft_mint);
+        prop_assert_eq!(holders.len(), 1, "NFT holders: {:?}", holders);
+        prop_assert_eq!(self.harness.mint_supply(&self.nft_mint), 1);
+        let (token_account, owner, _) = holders[0];
+        prop_assert_eq!(
+            token_account,
+            get_associated_token_address(&owner, &self.nft_mint)
+        );
+        let loan_info = self.loan_info();
+        match &loan_info {
+            Some(_) => prop_assert_eq!(owner, pda::vault_authority().0),
+            None => prop_assert!(self.actors.contains(&owner)),
+        }
+
+        // An open loan's funding fields and note agree with its phase
+        let count = self.loan_count();
+        if let Some(loan_info) = &loan_info {
+            prop_assert_eq!(loan_info.loan_id, count);
+            let note_mint = self.note_mint(loan_info.loan_id);
+            if loan_info.status == LoanStatus::Active {
+                prop_assert!(loan_info.lender.is_some() && loan_info.start_time.is_some());
+                prop_assert_eq!(self.harness.mint_supply(&note_mint), 1);
+                prop_assert_eq!(self.harness.token_holders(&note_mint).len(), 1);
+            } else {
+                prop_assert!(loan_info.lender.is_none() && loan_info.start_time.is_none());
+                prop_assert!(self.harness.account(&note_mint).is_none());
+            }
+        }
+
+        // Every closed loan has been archived exactly once and counted in its borrower's profile
+        let mut records = 0;
+        for loan_id in 1..=count {
+            let open = loan_info
+                .as_ref()
+                .is_some_and(|loan_info| loan_info.loan_id == loan_id);
+            let archived = self
+                .harness
+                .account(&pda::loan_record(&self.nft_mint, loan_id).0)
+                .is_some();
+            prop_assert_eq!(archived, !open, "loan {} archived: {}", loan_id, archived);
+            records += archived as u64;
+
+            // A settled loan's note is frozen once repaid and burned once claimed
+            if archived {
+                let record: LoanRecord = self
+                    .harness
+                    .anchor_account(&pda::loan_record(&self.nft_mint, loan_id).0);
+                let note_mint = self.note_mint(loan_id);
+                match record.outcome {
+                    LoanOutcome::Repaid => {
+                        let holder = self.note_holder(loan_id).unwrap();
+                        prop_assert!(self
+                            .harness
+                            .is_frozen(&get_associated_token_address(&holder, &note_mint)));
+                    }
+                    LoanOutcome::Defaulted => {
+                        prop_assert_eq!(self.harness.mint_supply(&note_mint), 0);
+                        prop_assert!(self.harness.token_holders(&note_mint).is_empty());
+                    }
+                    LoanOutcome::Cancelled => {
+                        prop_assert!(self.harness.account(&note_mint).is_none());
+                    }
+                }
+            }
+        }
+        let mut profiled = 0;
+        for actor in &self.actors {
+            let profile = self.profile(actor);
+            prop_assert_eq!(
+                profile.loans_taken,
+                profile.loans_repaid_on_time + profile.loans_defaulted
+            );
+            profiled += profile.loans_taken + profile.loans_cancelled;
+        }
+        prop_assert_eq!(profiled, records);
+        Ok(())
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(128))]
+
+    #[test]
+    fn loan_state_machine_preserves_invariants(
+        operations in prop::collection::vec(operation(), 1..48)
+    ) {
+        let mut fuzz = LoanFuzz::new();
+        for operation in &operations {
+            fuzz.step(operation)?;
+        }
+    }
+}
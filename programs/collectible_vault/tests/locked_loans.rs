@@ -0,0 +1,332 @@
+/**
+ * Escrow-free loan tests: loans whose NFT stays frozen in the borrower's wallet under the vault
+ * authority's delegation instead of moving into the vault.
+ *
+ * Freezing and thawing go through Token Metadata, which the full lifecycle tests run with the
+ * harness's Token Metadata stand-in on NFTs minted by `mint_nft`. The validation tests leave it
+ * unloaded, so reaching the freeze or thaw fails and must roll the instruction back.
+ */
+mod common;
+
+use {
+    anchor_lang::{
+        prelude::Pubkey,
+        solana_program::{instruction::InstructionError, program_pack::Pack},
+    },
+    collectible_vault::{
+        errors::ErrorCode,
+        instructions::nft_transfer::NftStandard,
+        state::{AccountHeader, LoanInfo, LoanOutcome, LoanRecord, LoanStatus},
+    },
+    collectible_vault_client::{instructions, pda},
+    common::{assert_instruction_error, assert_program_error, Harness},
+    spl_associated_token_account::get_associated_token_address,
+};
+
+const LOAN_AMOUNT: u64 = 2_000_000_000;
+const INTEREST_AMOUNT: u64 = 100_000_000;
+const DURATION: i64 = 7 * 24 * 60 * 60;
+
+/// Stores an unfunded loan request for `nft_mint`, as if `lock_nft_for_loan` had frozen the NFT
+fn set_locked_request(
+    harness: &mut Harness,
+    borrower: &Pubkey,
+    nft_mint: &Pubkey,
+    request_expiry: Option<i64>,
+) {
+    harness.set_program_account(
+        pda::loan_info(nft_mint).0,
+        &LoanInfo {
+            header: AccountHeader::new(LoanInfo::VERSION),
+            nft_mint: *nft_mint,
+            nft_owner: *borrower,
+            loan_amount: LOAN_AMOUNT,
+            interest_amount: INTEREST_AMOUNT,
+            duration: DURATION,
+            start_time: None,
+            lender: None,
+            status: LoanStatus::Requested,
+            request_expiry,
+            loan_id: 1,
+            reserved: [0; 64],
+        },
+    );
+}
+
+/// Mints an NFT to a new borrower and locks it for a loan request, with Token Metadata loaded
+fn lock(harness: &mut Harness, request_expiry: Option<i64>) -> (Pubkey, Pubkey) {
+    harness.load_token_metadata();
+    let borrower = harness.new_wallet();
+    let nft_mint = harness.mint_nft(&borrower, NftStandard::NonFungible);
+    harness
+        .process_transaction(
+            &[instructions::lock_nft_for_loan(
+                &borrower,
+                &nft_mint,
+                LOAN_AMOUNT,
+                INTEREST_AMOUNT,
+                DURATION,
+                request_expiry,
+            )],
+            &[borrower],
+        )
+        .unwrap();
+    (borrower, nft_mint)
+}
+
+fn fund(harness: &mut Harness, borrower: &Pubkey, nft_mint: &Pubkey) -> Pubkey {
+    let lender = harness.new_wallet();
+    harness
+        .process_transaction(
+            &[instructions::provide_loan_liquidity(
+                &lender, borrower, nft_mint, 1, None,
+            )],
+            &[lender],
+        )
+        .unwrap();
+    lender
+}
+
+fn delegate(harness: &Harness, token_account: &Pubkey) -> Option<Pubkey> {
+    spl_token::state::Account::unpack(&harness.account(token_account).unwrap().data)
+        .unwrap()
+        .delegate
+        .into()
+}
+
+#[test]
+fn locked_nft_stays_frozen_in_the_wallet_until_the_loan_is_repaid() {
+    let mut harness = Harness::new();
+    let (borrower, nft_mint) = lock(&mut harness, None);
+    let borrower_nft_account = get_associated_token_address(&borrower, &nft_mint);
+
+    // The vault authority is the delegate of the NFT, which Token Metadata froze in place
+    assert_eq!(harness.token_balance(&borrower_nft_account), 1);
+    assert!(harness.is_frozen(&borrower_nft_account));
+    assert_eq!(
+        delegate(&harness, &borrower_nft_account),
+        Some(pda::vault_authority().0)
+    );
+    let loan: LoanInfo = harness.anchor_account(&pda::loan_info(&nft_mint).0);
+    assert_eq!(loan.status, LoanStatus::Requested);
+
+    let lender = fund(&mut harness, &borrower, &nft_mint);
+    assert!(harness.is_frozen(&borrower_nft_account));
+
+    harness
+        .process_transaction(
+            &[instructions::repay_locked_loan(
+                &borrower, &borrower, &lender, &nft_mint, 1,
+            )],
+            &[borrower],
+        )
+        .unwrap();
+    assert_eq!(harness.token_balance(&borrower_nft_account), 1);
+    assert!(!harness.is_frozen(&borrower_nft_account));
+    let record: LoanRecord = harness.anchor_account(&pda::loan_record(&nft_mint, 1).0);
+    assert_eq!(record.outcome, LoanOutcome::Repaid);
+}
+
+#[test]
+fn cancel_locked_loan_request_thaws_the_nft_and_revokes_the_vault() {
+    let mut harness = Harness::new();
+    let (borrower, nft_mint) = lock(&mut harness, None);
+    let borrower_nft_account = get_associated_token_address(&borrower, &nft_mint);
+
+    harness
+        .process_transaction(
+            &[instructions::cancel_locked_loan_request(
+                &borrower, &nft_mint, 1,
+            )],
+            &[borrower],
+        )
+        .unwrap();
+    assert!(!harness.is_frozen(&borrower_nft_account));
+    assert_eq!(delegate(&harness, &borrower_nft_account), None);
+    assert!(harness.account(&pda::loan_info(&nft_mint).0).is_none());
+}
+
+#[test]
+fn close_expired_locked_loan_request_thaws_the_nft_after_the_expiry() {
+    let mut harness = Harness::new();
+    let expiry = harness.now() + 3600;
+    let (borrower, nft_mint) = lock(&mut harness, Some(expiry));
+    let cranker = harness.new_wallet();
+    harness.warp_forward(3601);
+
+    harness
+        .process_transaction(
+            &[instructions::close_expired_locked_loan_request(
+                &cranker, &borrower, &nft_mint, 1,
+            )],
+            &[cranker],
+        )
+        .unwrap();
+    let borrower_nft_account = get_associated_token_address(&borrower, &nft_mint);
+    assert!(!harness.is_frozen(&borrower_nft_account));
+    assert!(harness.account(&pda::loan_info(&nft_mint).0).is_none());
+}
+
+#[test]
+fn claim_delinquent_locked_nft_moves_the_nft_to_the_note_holder() {
+    let mut harness = Harness::new();
+    let (borrower, nft_mint) = lock(&mut harness, None);
+    let lender = fund(&mut harness, &borrower, &nft_mint);
+    harness.warp_forward(DURATION + 1);
+
+    harness
+        .process_transaction(
+            &[instructions::claim_delinquent_locked_nft(
+                &lender, &borrower, &nft_mint, 1,
+            )],
+            &[lender],
+        )
+        .unwrap();
+    assert_eq!(
+        harness.token_balance(&get_associated_token_address(&borrower, &nft_mint)),
+        0
+    );
+    let lender_nft_account = get_associated_token_address(&lender, &nft_mint);
+    assert_eq!(harness.token_balance(&lender_nft_account), 1);
+    assert!(!harness.is_frozen(&lender_nft_account));
+    let record: LoanRecord = harness.anchor_account(&pda::loan_record(&nft_mint, 1).0);
+    assert_eq!(record.outcome, LoanOutcome::Defaulted);
+}
+
+#[test]
+fn lock_nft_for_loan_reaches_the_token_metadata_freeze() {
+    let mut harness = Harness::new();
+    let borrower = harness.new_wallet();
+    let nft_mint = harness.create_nft(&borrower);
+    let borrower_nft_account = get_associated_token_address(&borrower, &nft_mint);
+
+    // Token Metadata is not loaded in the harness, so the freeze after the approval fails here
+    let result = harness.process_transaction(
+        &[instructions::lock_nft_for_loan(
+            &borrower,
+            &nft_mint,
+            LOAN_AMOUNT,
+            INTEREST_AMOUNT,
+            DURATION,
+            None,
+        )],
+        &[borrower],
+    );
+    assert_instruction_error(result, InstructionError::IncorrectProgramId);
+    assert!(harness.account(&pda::loan_info(&nft_mint).0).is_none());
+
+    // The NFT never left the borrower's wallet and the approval was rolled back
+    assert_eq!(harness.token_balance(&borrower_nft_account), 1);
+    let token_account =
+        spl_token::state::Account::unpack(&harness.account(&borrower_nft_account).unwrap().data)
+            .unwrap();
+    assert!(token_account.delegate.is_none());
+}
+
+#[test]
+fn lock_nft_for_loan_requires_the_nft_in_the_owners_wallet() {
+    let mut harness = Harness::new();
+    let borrower = harness.new_wallet();
+    let buyer = harness.new_wallet();
+    let nft_mint = harness.create_nft(&borrower);
+    harness.transfer_tokens(&borrower, &buyer, &nft_mint, 1);
+
+    let result = harness.process_transaction(
+        &[instructions::lock_nft_for_loan(
+            &borrower,
+            &nft_mint,
+            LOAN_AMOUNT,
+            INTEREST_AMOUNT,
+            DURATION,
+            None,
+        )],
+        &[borrower],
+    );
+    assert_program_error(result, ErrorCode::InvalidNFTAccount);
+}
+
+#[test]
+fn lock_nft_for_loan_validates_the_loan_terms() {
+    let mut harness = Harness::new();
+    let borrower = harness.new_wallet();
+    let nft_mint = harness.create_nft(&borrower);
+
+    let result = harness.process_transaction(
+        &[instructions::lock_nft_for_loan(
+            &borrower,
+            &nft_mint,
+            LOAN_AMOUNT,
+            INTEREST_AMOUNT,
+            0,
+            None,
+        )],
+        &[borrower],
+    );
+    assert_program_error(result, ErrorCode::InvalidLoanDuration);
+}
+
+#[test]
+fn cancel_locked_loan_request_reaches_the_token_metadata_thaw() {
+    let mut harness = Harness::new();
+    let borrower = harness.new_wallet();
+    let nft_mint = harness.create_nft(&borrower);
+    set_locked_request(&mut harness, &borrower, &nft_mint, None);
+
+    let result = harness.process_transaction(
+        &[instructions::cancel_locked_loan_request(
+            &borrower, &nft_mint, 1,
+        )],
+        &[borrower],
+    );
+    assert_instruction_error(result, InstructionError::IncorrectProgramId);
+    assert!(harness.account(&pda::loan_info(&nft_mint).0).is_some());
+}
+
+#[test]
+fn locked_instructions_reject_vault_custodied_loans() {
+    let mut harness = Harness::new();
+    let borrower = harness.new_wallet();
+    let nft_mint = harness.create_nft(&borrower);
+    harness
+        .process_transaction(
+            &[instructions::stake_nft_for_loan(
+                &borrower,
+                &nft_mint,
+                LOAN_AMOUNT,
+                INTEREST_AMOUNT,
+                DURATION,
+                None,
+                NftStandard::NonFungible,
+                &spl_token::ID,
+            )],
+            &[borrower],
+        )
+        .unwrap();
+
+    // The NFT is in the vault, so there is nothing frozen in the borrower's wallet to thaw
+    let result = harness.process_transaction(
+        &[instructions::cancel_locked_loan_request(
+            &borrower, &nft_mint, 1,
+        )],
+        &[borrower],
+    );
+    assert_program_error(result, ErrorCode::InvalidNFTAccount);
+}
+
+#[test]
+fn close_expired_locked_loan_request_waits_for_the_expiry() {
+    let mut harness = Harness::new();
+    let borrower = harness.new_wallet();
+    let cranker = harness.new_wallet();
+    let nft_mint = harness.create_nft(&borrower);
+    let expiry = harness.now() + 3600;
+    set_locked_request(&mut harness, &borrower, &nft_mint, Some(expiry));
+
+    let close = instructions::close_expired_locked_loan_request(&cranker, &borrower, &nft_mint, 1);
+    let result = harness.process_transaction(std::slice::from_ref(&close), &[cranker]);
+    assert_program_error(result, ErrorCode::LoanRequestNotExpired);
+
+    harness.warp_forward(3601);
+    let result = harness.process_transaction(&[close], &[cranker]);
+    assert_instruction_error(result, InstructionError::IncorrectProgramId);
+}
@@ -0,0 +1,634 @@
+/**
+ * Secondary marketplace tests: listings held in the marketplace escrow, offers holding the
+ * offered lamports, creator royalties on sales, and the rule that NFTs with an open loan or a
+ * redemption request cannot be traded.
+ */
+mod common;
+
+use {
+    anchor_lang::{prelude::Pubkey, solana_program::instruction::Instruction},
+    collectible_vault::{
+        errors::ErrorCode,
+        events::{ListingSold, LoanFunded, NftListed, OfferAccepted},
+        instructions::nft_transfer::NftStandard,
+        state::{AccountHeader, AssetRedemptionInfo, Listing, LoanInfo, LoanStatus, Offer},
+    },
+    collectible_vault_client::{instructions, pda},
+    common::{assert_program_error, events, Harness},
+    mpl_token_metadata::types::TokenStandard,
+    spl_associated_token_account::get_associated_token_address,
+};
+
+const PRICE: u64 = 5_000_000_000;
+const INTEREST: u64 = 250_000_000;
+const DURATION: i64 = 30 * 24 * 3600;
+
+fn list(seller: &Pubkey, nft_mint: &Pubkey, price: u64) -> Instruction {
+    instructions::list_nft(
+        seller,
+        nft_mint,
+        price,
+        NftStandard::NonFungible,
+        &spl_token::ID,
+    )
+}
+
+fn buy(buyer: &Pubkey, seller: &Pubkey, nft_mint: &Pubkey) -> Instruction {
+    instructions::buy_listing(
+        buyer,
+        seller,
+        nft_mint,
+        NftStandard::NonFungible,
+        &spl_token::ID,
+    )
+}
+
+fn accept(seller: &Pubkey, buyer: &Pubkey, nft_mint: &Pubkey) -> Instruction {
+    instructions::accept_offer(
+        seller,
+        buyer,
+        nft_mint,
+        NftStandard::NonFungible,
+        &spl_token::ID,
+    )
+}
+
+fn buy_with_loan(
+    buyer: &Pubkey,
+    lender: &Pubkey,
+    seller: &Pubkey,
+    nft_mint: &Pubkey,
+    down_payment: u64,
+) -> Instruction {
+    instructions::buy_listing_with_loan(
+        buyer,
+        lender,
+        seller,
+        nft_mint,
+        1,
+        down_payment,
+        INTEREST,
+        DURATION,
+        NftStandard::NonFungible,
+        &spl_token::ID,
+    )
+}
+
+fn escrow_nft_account(nft_mint: &Pubkey) -> Pubkey {
+    get_associated_token_address(&pda::marketplace_escrow().0, nft_mint)
+}
+
+/// Gives the NFT a 5% seller fee split 60/40 between two creators
+fn set_royalty(harness: &mut Harness, nft_mint: &Pubkey) -> [Pubkey; 2] {
+    let creators = [harness.new_wallet(), harness.new_wallet()];
+    harness.set_token_metadata_with_royalty(
+        nft_mint,
+        TokenStandard::NonFungible,
+        500,
+        &[(creators[0], 60), (creators[1], 40)],
+    );
+    creators
+}
+
+/// Stores a loan request against the NFT, as if it had been locked for a loan in the owner's wallet
+fn set_loan(harness: &mut Harness, owner: &Pubkey, nft_mint: &Pubkey) {
+    harness.set_program_account(
+        pda::loan_info(nft_mint).0,
+        &LoanInfo {
+            header: AccountHeader::new(LoanInfo::VERSION),
+            nft_mint: *nft_mint,
+            nft_owner: *owner,
+            loan_amount: PRICE,
+            interest_amount: 0,
+            duration: 3600,
+            start_time: None,
+            lender: None,
+            status: LoanStatus::Requested,
+            request_expiry: None,
+            loan_id: 1,
+            reserved: [0; 64],
+        },
+    );
+}
+
+fn set_redemption_request(harness: &mut Harness, owner: &Pubkey, nft_mint: &Pubkey) {
+    harness.set_program_account(
+        pda::asset_redemption_info(nft_mint).0,
+        &AssetRedemptionInfo {
+            header: AccountHeader::new(AssetRedemptionInfo::VERSION),
+            nft_mint: *nft_mint,
+            nft_owner: *owner,
+            request_timestamp: harness.now(),
+            is_fulfilled: false,
+        },
+    );
+}
+
+#[test]
+fn list_nft_moves_the_nft_into_the_marketplace_escrow() {
+    let mut harness = Harness::new();
+    let seller = harness.new_wallet();
+    let nft_mint = harness.create_nft(&seller);
+
+    let logs = harness
+        .process_transaction(&[list(&seller, &nft_mint, PRICE)], &[seller])
+        .unwrap();
+
+    assert_eq!(
+        harness.token_balance(&get_associated_token_address(&seller, &nft_mint)),
+        0
+    );
+    assert_eq!(harness.token_balance(&escrow_nft_account(&nft_mint)), 1);
+    let listing: Listing = harness.anchor_account(&pda::listing(&nft_mint).0);
+    assert_eq!(listing.seller, seller);
+    assert_eq!(listing.price, PRICE);
+    assert_eq!(events::<NftListed>(&logs)[0].price, PRICE);
+}
+
+#[test]
+fn list_nft_rejects_a_zero_price() {
+    let mut harness = Harness::new();
+    let seller = harness.new_wallet();
+    let nft_mint = harness.create_nft(&seller);
+
+    let result = harness.process_transaction(&[list(&seller, &nft_mint, 0)], &[seller]);
+    assert_program_error(result, ErrorCode::InvalidSalePrice);
+}
+
+#[test]
+fn list_nft_rejects_an_nft_with_an_open_loan() {
+    let mut harness = Harness::new();
+    let seller = harness.new_wallet();
+    let nft_mint = harness.create_nft(&seller);
+    set_loan(&mut harness, &seller, &nft_mint);
+
+    let result = harness.process_transaction(&[list(&seller, &nft_mint, PRICE)], &[seller]);
+    assert_program_error(result, ErrorCode::NftHasOpenLoan);
+}
+
+#[test]
+fn list_nft_rejects_an_nft_with_a_redemption_request() {
+    let mut harness = Harness::new();
+    let seller = harness.new_wallet();
+    let nft_mint = harness.create_nft(&seller);
+    set_redemption_request(&mut harness, &seller, &nft_mint);
+
+    let result = harness.process_transaction(&[list(&seller, &nft_mint, PRICE)], &[seller]);
+    assert_program_error(result, ErrorCode::NftHasRedemptionRequest);
+}
+
+#[test]
+fn buy_listing_pays_the_seller_and_the_creator_royalty() {
+    let mut harness = Harness::new();
+    let seller = harness.new_wallet();
+    let buyer = harness.new_wallet();
+    let nft_mint = harness.create_nft(&seller);
+    harness
+        .process_transaction(&[list(&seller, &nft_mint, PRICE)], &[seller])
+        .unwrap();
+    let creators = set_royalty(&mut harness, &nft_mint);
+    let creator_lamports = creators.map(|creator| harness.lamports(&creator));
+    let seller_lamports = harness.lamports(&seller);
+    let refunded_rent = harness.lamports(&pda::listing(&nft_mint).0)
+        + harness.lamports(&escrow_nft_account(&nft_mint));
+
+    let logs = harness
+        .process_transaction(
+            &[instructions::with_royalty_creators(
+                buy(&buyer, &seller, &nft_mint),
+                &creators,
+            )],
+            &[buyer],
+        )
+        .unwrap();
+
+    // 5% of the price, split 60/40, comes out of the seller's proceeds
+    let royalty = PRICE / 20;
+    assert_eq!(
+        harness.lamports(&creators[0]),
+        creator_lamports[0] + royalty * 60 / 100
+    );
+    assert_eq!(
+        harness.lamports(&creators[1]),
+        creator_lamports[1] + royalty * 40 / 100
+    );
+    assert_eq!(
+        harness.lamports(&seller),
+        seller_lamports + PRICE - royalty + refunded_rent
+    );
+    assert_eq!(
+        harness.token_balance(&get_associated_token_address(&buyer, &nft_mint)),
+        1
+    );
+    assert!(harness.account(&pda::listing(&nft_mint).0).is_none());
+    assert!(harness.account(&escrow_nft_account(&nft_mint)).is_none());
+    let sold = &events::<ListingSold>(&logs)[0];
+    assert_eq!(sold.buyer, buyer);
+    assert_eq!(sold.royalty_amount, royalty);
+}
+
+#[test]
+fn buy_listing_leaves_an_escrow_account_holding_stray_tokens_open() {
+    let mut harness = Harness::new();
+    let seller = harness.new_wallet();
+    let buyer = harness.new_wallet();
+    let nft_mint = harness.create_nft(&seller);
+    harness
+        .process_transaction(&[list(&seller, &nft_mint, PRICE)], &[seller])
+        .unwrap();
+    harness.send_stray_token(&escrow_nft_account(&nft_mint));
+
+    harness
+        .process_transaction(&[buy(&buyer, &seller, &nft_mint)], &[buyer])
+        .unwrap();
+
+    assert_eq!(
+        harness.token_balance(&get_associated_token_address(&buyer, &nft_mint)),
+        1
+    );
+    assert_eq!(harness.token_balance(&escrow_nft_account(&nft_mint)), 1);
+}
+
+#[test]
+fn buy_listing_requires_the_metadata_creators() {
+    let mut harness = Harness::new();
+    let seller = harness.new_wallet();
+    let buyer = harness.new_wallet();
+    let nft_mint = harness.create_nft(&seller);
+    harness
+        .process_transaction(&[list(&seller, &nft_mint, PRICE)], &[seller])
+        .unwrap();
+    set_royalty(&mut harness, &nft_mint);
+
+    let result = harness.process_transaction(&[buy(&buyer, &seller, &nft_mint)], &[buyer]);
+    assert_program_error(result, ErrorCode::InvalidRoyaltyCreator);
+}
+
+#[test]
+fn buy_listing_rejects_the_wrong_seller() {
+    let mut harness = Harness::new();
+    let seller = harness.new_wallet();
+    let buyer = harness.new_wallet();
+    let nft_mint = harness.create_nft(&seller);
+    harness
+        .process_transaction(&[list(&seller, &nft_mint, PRICE)], &[seller])
+        .unwrap();
+
+    let result = harness.process_transaction(&[buy(&buyer, &buyer, &nft_mint)], &[buyer]);
+    assert_program_error(result, ErrorCode::InvalidSeller);
+}
+
+#[test]
+fn buy_listing_rejects_an_nft_with_a_redemption_request() {
+    let mut harness = Harness::new();
+    let seller = harness.new_wallet();
+    let buyer = harness.new_wallet();
+    let nft_mint = harness.create_nft(&seller);
+    harness
+        .process_transaction(&[list(&seller, &nft_mint, PRICE)], &[seller])
+        .unwrap();
+    set_redemption_request(&mut harness, &seller, &nft_mint);
+
+    let result = harness.process_transaction(&[buy(&buyer, &seller, &nft_mint)], &[buyer]);
+    assert_program_error(result, ErrorCode::NftHasRedemptionRequest);
+}
+
+#[test]
+fn cancel_listing_returns_the_nft_to_the_seller() {
+    let mut harness = Harness::new();
+    let seller = harness.new_wallet();
+    let other = harness.new_wallet();
+    let nft_mint = harness.create_nft(&seller);
+    harness
+        .process_transaction(&[list(&seller, &nft_mint, PRICE)], &[seller])
+        .unwrap();
+
+    let cancel = |signer: &Pubkey| {
+        instructions::cancel_listing(signer, &nft_mint, NftStandard::NonFungible, &spl_token::ID)
+    };
+    let result = harness.process_transaction(&[cancel(&other)], &[other]);
+    assert_program_error(result, ErrorCode::InvalidSeller);
+
+    harness
+        .process_transaction(&[cancel(&seller)], &[seller])
+        .unwrap();
+    assert_eq!(
+        harness.token_balance(&get_associated_token_address(&seller, &nft_mint)),
+        1
+    );
+    assert!(harness.account(&pda::listing(&nft_mint).0).is_none());
+    assert!(harness.account(&escrow_nft_account(&nft_mint)).is_none());
+}
+
+#[test]
+fn make_offer_escrows_the_lamports_until_cancelled() {
+    let mut harness = Harness::new();
+    let owner = harness.new_wallet();
+    let buyer = harness.new_wallet();
+    let nft_mint = harness.create_nft(&owner);
+    let buyer_lamports = harness.lamports(&buyer);
+
+    harness
+        .process_transaction(
+            &[instructions::make_offer(&buyer, &nft_mint, PRICE, None)],
+            &[buyer],
+        )
+        .unwrap();
+    let offer_key = pda::offer(&nft_mint, &buyer).0;
+    let offer: Offer = harness.anchor_account(&offer_key);
+    assert_eq!(offer.price, PRICE);
+    assert_eq!(offer.buyer, buyer);
+    // The offer account holds its rent plus the offered price, all paid by the buyer
+    assert!(harness.lamports(&offer_key) > PRICE);
+    assert_eq!(
+        harness.lamports(&buyer) + harness.lamports(&offer_key),
+        buyer_lamports
+    );
+
+    harness
+        .process_transaction(&[instructions::cancel_offer(&buyer, &nft_mint)], &[buyer])
+        .unwrap();
+    assert!(harness.account(&offer_key).is_none());
+    assert_eq!(harness.lamports(&buyer), buyer_lamports);
+}
+
+#[test]
+fn make_offer_rejects_an_expiry_in_the_past() {
+    let mut harness = Harness::new();
+    let owner = harness.new_wallet();
+    let buyer = harness.new_wallet();
+    let nft_mint = harness.create_nft(&owner);
+
+    let result = harness.process_transaction(
+        &[instructions::make_offer(
+            &buyer,
+            &nft_mint,
+            PRICE,
+            Some(harness.now()),
+        )],
+        &[buyer],
+    );
+    assert_program_error(result, ErrorCode::InvalidOfferExpiry);
+}
+
+#[test]
+fn accept_offer_pays_the_seller_and_the_creator_royalty() {
+    let mut harness = Harness::new();
+    let seller = harness.new_wallet();
+    let buyer = harness.new_wallet();
+    let nft_mint = harness.create_nft(&seller);
+    let creators = set_royalty(&mut harness, &nft_mint);
+    harness
+        .process_transaction(
+            &[instructions::make_offer(&buyer, &nft_mint, PRICE, None)],
+            &[buyer],
+        )
+        .unwrap();
+    let offer_key = pda::offer(&nft_mint, &buyer).0;
+    let offer_rent = harness.lamports(&offer_key) - PRICE;
+    let seller_lamports = harness.lamports(&seller);
+    let buyer_lamports = harness.lamports(&buyer);
+    let creator_lamports = harness.lamports(&creators[0]);
+
+    let logs = harness
+        .process_transaction(
+            &[instructions::with_royalty_creators(
+                accept(&seller, &buyer, &nft_mint),
+                &creators,
+            )],
+            &[seller],
+        )
+        .unwrap();
+
+    let royalty = PRICE / 20;
+    let buyer_nft_account = get_associated_token_address(&buyer, &nft_mint);
+    assert_eq!(harness.token_balance(&buyer_nft_account), 1);
+    assert_eq!(
+        harness.lamports(&creators[0]),
+        creator_lamports + royalty * 60 / 100
+    );
+    // The seller pays for the buyer's token account out of the proceeds
+    assert_eq!(
+        harness.lamports(&seller),
+        seller_lamports + PRICE - royalty - harness.lamports(&buyer_nft_account)
+    );
+    assert_eq!(harness.lamports(&buyer), buyer_lamports + offer_rent);
+    assert!(harness.account(&offer_key).is_none());
+    let accepted = &events::<OfferAccepted>(&logs)[0];
+    assert_eq!(accepted.seller, seller);
+    assert_eq!(accepted.royalty_amount, royalty);
+}
+
+#[test]
+fn accept_offer_rejects_an_expired_offer() {
+    let mut harness = Harness::new();
+    let seller = harness.new_wallet();
+    let buyer = harness.new_wallet();
+    let nft_mint = harness.create_nft(&seller);
+    let expiry = harness.now() + 60;
+    harness
+        .process_transaction(
+            &[instructions::make_offer(
+                &buyer,
+                &nft_mint,
+                PRICE,
+                Some(expiry),
+            )],
+            &[buyer],
+        )
+        .unwrap();
+
+    harness.warp_forward(61);
+    let result = harness.process_transaction(&[accept(&seller, &buyer, &nft_mint)], &[seller]);
+    assert_program_error(result, ErrorCode::OfferExpired);
+
+    // The buyer can still withdraw the lamports
+    harness
+        .process_transaction(&[instructions::cancel_offer(&buyer, &nft_mint)], &[buyer])
+        .unwrap();
+}
+
+#[test]
+fn accept_offer_rejects_an_nft_with_an_open_loan() {
+    let mut harness = Harness::new();
+    let seller = harness.new_wallet();
+    let buyer = harness.new_wallet();
+    let nft_mint = harness.create_nft(&seller);
+    harness
+        .process_transaction(
+            &[instructions::make_offer(&buyer, &nft_mint, PRICE, None)],
+            &[buyer],
+        )
+        .unwrap();
+    set_loan(&mut harness, &seller, &nft_mint);
+
+    let result = harness.process_transaction(&[accept(&seller, &buyer, &nft_mint)], &[seller]);
+    assert_program_error(result, ErrorCode::NftHasOpenLoan);
+}
+
+#[test]
+fn accept_offer_requires_the_nft_in_the_sellers_wallet() {
+    let mut harness = Harness::new();
+    let seller = harness.new_wallet();
+    let buyer = harness.new_wallet();
+    let nft_mint = harness.create_nft(&seller);
+    harness
+        .process_transaction(
+            &[
+                list(&seller, &nft_mint, PRICE),
+                instructions::make_offer(&buyer, &nft_mint, PRICE, None),
+            ],
+            &[seller, buyer],
+        )
+        .unwrap();
+
+    // A listed NFT sits in the marketplace escrow, so its listing must be cancelled first
+    let result = harness.process_transaction(&[accept(&seller, &buyer, &nft_mint)], &[seller]);
+    assert_program_error(result, ErrorCode::InvalidNFTAccount);
+}
+
+#[test]
+fn buy_listing_with_loan_opens_a_funded_loan_in_the_buyers_name() {
+    let mut harness = Harness::new();
+    let seller = harness.new_wallet();
+    let buyer = harness.new_wallet();
+    let lender = harness.new_wallet();
+    let nft_mint = harness.create_nft(&seller);
+    harness
+        .process_transaction(&[list(&seller, &nft_mint, PRICE)], &[seller])
+        .unwrap();
+    let creators = set_royalty(&mut harness, &nft_mint);
+    let creator_lamports = harness.lamports(&creators[0]);
+    let seller_lamports = harness.lamports(&seller);
+    let lender_lamports = harness.lamports(&lender);
+    let refunded_rent = harness.lamports(&pda::listing(&nft_mint).0)
+        + harness.lamports(&escrow_nft_account(&nft_mint));
+    let down_payment = PRICE / 5;
+
+    let logs = harness
+        .process_transaction(
+            &[instructions::with_royalty_creators(
+                buy_with_loan(&buyer, &lender, &seller, &nft_mint, down_payment),
+                &creators,
+            )],
+            &[buyer, lender],
+        )
+        .unwrap();
+
+    // The down payment covers the royalty and the lender pays the rest of the price to the seller
+    let royalty = PRICE / 20;
+    let loan_amount = PRICE - down_payment;
+    assert_eq!(
+        harness.lamports(&creators[0]),
+        creator_lamports + royalty * 60 / 100
+    );
+    assert_eq!(
+        harness.lamports(&seller),
+        seller_lamports + PRICE - royalty + refunded_rent
+    );
+    let note_account = get_associated_token_address(&lender, &pda::loan_note_mint(&nft_mint, 1).0);
+    assert_eq!(harness.token_balance(&note_account), 1);
+    assert_eq!(
+        harness.lamports(&lender),
+        lender_lamports
+            - loan_amount
+            - harness.lamports(&note_account)
+            - harness.lamports(&pda::loan_note_mint(&nft_mint, 1).0)
+    );
+
+    let vault_nft_account = get_associated_token_address(&pda::vault_authority().0, &nft_mint);
+    assert_eq!(harness.token_balance(&vault_nft_account), 1);
+    assert!(harness.account(&pda::listing(&nft_mint).0).is_none());
+    let loan: LoanInfo = harness.anchor_account(&pda::loan_info(&nft_mint).0);
+    assert_eq!(loan.nft_owner, buyer);
+    assert_eq!(loan.lender, Some(lender));
+    assert_eq!(loan.status, LoanStatus::Active);
+    assert_eq!(loan.loan_amount, loan_amount);
+    assert_eq!(loan.interest_amount, INTEREST);
+    assert_eq!(loan.start_time, Some(harness.now()));
+    assert_eq!(loan.loan_id, 1);
+    assert_eq!(events::<ListingSold>(&logs)[0].royalty_amount, royalty);
+    assert_eq!(events::<LoanFunded>(&logs)[0].borrower, buyer);
+
+    // Repaying the loan releases the NFT to the buyer
+    harness
+        .process_transaction(
+            &[instructions::repay_loan(
+                &buyer,
+                &buyer,
+                &lender,
+                &nft_mint,
+                1,
+                NftStandard::NonFungible,
+                &spl_token::ID,
+            )],
+            &[buyer],
+        )
+        .unwrap();
+    assert_eq!(
+        harness.token_balance(&get_associated_token_address(&buyer, &nft_mint)),
+        1
+    );
+}
+
+#[test]
+fn buy_listing_with_loan_rejects_an_invalid_down_payment() {
+    let mut harness = Harness::new();
+    let seller = harness.new_wallet();
+    let buyer = harness.new_wallet();
+    let lender = harness.new_wallet();
+    let nft_mint = harness.create_nft(&seller);
+    harness
+        .process_transaction(&[list(&seller, &nft_mint, PRICE)], &[seller])
+        .unwrap();
+
+    for down_payment in [0, PRICE] {
+        let result = harness.process_transaction(
+            &[buy_with_loan(
+                &buyer,
+                &lender,
+                &seller,
+                &nft_mint,
+                down_payment,
+            )],
+            &[buyer, lender],
+        );
+        assert_program_error(result, ErrorCode::InvalidDownPayment);
+    }
+
+    // The down payment must also cover the 5% creator royalty
+    let creators = set_royalty(&mut harness, &nft_mint);
+    let result = harness.process_transaction(
+        &[instructions::with_royalty_creators(
+            buy_with_loan(&buyer, &lender, &seller, &nft_mint, PRICE / 40),
+            &creators,
+        )],
+        &[buyer, lender],
+    );
+    assert_program_error(result, ErrorCode::InvalidDownPayment);
+}
+
+#[test]
+fn buy_listing_with_loan_rejects_an_nft_with_a_redemption_request() {
+    let mut harness = Harness::new();
+    let seller = harness.new_wallet();
+    let buyer = harness.new_wallet();
+    let lender = harness.new_wallet();
+    let nft_mint = harness.create_nft(&seller);
+    harness
+        .process_transaction(&[list(&seller, &nft_mint, PRICE)], &[seller])
+        .unwrap();
+    set_redemption_request(&mut harness, &seller, &nft_mint);
+
+    let result = harness.process_transaction(
+        &[buy_with_loan(
+            &buyer,
+            &lender,
+            &seller,
+            &nft_mint,
+            PRICE / 5,
+        )],
+        &[buyer, lender],
+    );
+    assert_program_error(result, ErrorCode::NftHasRedemptionRequest);
+}
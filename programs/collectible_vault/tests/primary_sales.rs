@@ -0,0 +1,400 @@
+/**
+ * Primary sale tests: listing NFTs held by the sale escrow, buying them in SOL or USDC with the
+ * proceeds going to the treasury, and withdrawing listings.
+ *
+ * `mint_nft` needs Token Metadata, which the harness cannot execute, so listed NFTs are created
+ * directly in the sale escrow's token account.
+ */
+mod common;
+
+use {
+    anchor_lang::{
+        error::ErrorCode as AnchorErrorCode,
+        prelude::Pubkey,
+        solana_program::instruction::{Instruction, InstructionError},
+    },
+    collectible_vault::{
+        constants::{
+            admin_constants::get_admin_account_pubkey, payment_constants::get_usdc_mint_pubkey,
+        },
+        errors::ErrorCode,
+        events::{ListedItemPurchased, PrimaryListingCreated, TreasuryConfigured},
+        instructions::nft_transfer::NftStandard,
+        state::{PrimaryListing, SaleCurrency, VaultConfig},
+    },
+    collectible_vault_client::{instructions, pda},
+    common::{assert_instruction_error, assert_program_error, events, replace_account, Harness},
+    spl_associated_token_account::get_associated_token_address,
+};
+
+const SOL_PRICE: u64 = 3_000_000_000;
+const USDC_PRICE: u64 = 250_000_000;
+
+/// Funds the admin and points sale proceeds at a fresh treasury wallet
+fn set_treasury(harness: &mut Harness) -> Pubkey {
+    let admin = get_admin_account_pubkey();
+    harness.airdrop(&admin, 10_000_000_000);
+    let treasury = harness.new_wallet();
+    harness
+        .process_transaction(&[instructions::set_treasury(&admin, &treasury)], &[admin])
+        .unwrap();
+    treasury
+}
+
+/// Creates an NFT in the sale escrow and lists it at `price`
+fn list(harness: &mut Harness, price: u64, currency: SaleCurrency) -> Pubkey {
+    let admin = get_admin_account_pubkey();
+    let nft_mint = harness.create_nft(&pda::sale_escrow().0);
+    harness
+        .process_transaction(
+            &[instructions::create_primary_listing(
+                &admin,
+                &nft_mint,
+                price,
+                currency,
+                &spl_token::ID,
+            )],
+            &[admin],
+        )
+        .unwrap();
+    nft_mint
+}
+
+fn escrow_nft_account(nft_mint: &Pubkey) -> Pubkey {
+    get_associated_token_address(&pda::sale_escrow().0, nft_mint)
+}
+
+fn purchase(
+    buyer: &Pubkey,
+    treasury: &Pubkey,
+    nft_mint: &Pubkey,
+    currency: SaleCurrency,
+) -> Vec<Instruction> {
+    vec![instructions::purchase_listed_item(
+        buyer,
+        treasury,
+        nft_mint,
+        currency,
+        NftStandard::NonFungible,
+        &spl_token::ID,
+    )]
+}
+
+#[test]
+fn set_treasury_is_admin_only() {
+    let mut harness = Harness::new();
+    let payer = harness.new_wallet();
+    let treasury = harness.new_wallet();
+
+    let result =
+        harness.process_transaction(&[instructions::set_treasury(&payer, &treasury)], &[payer]);
+    assert_program_error(result, ErrorCode::UnauthorizedTransactionSigner);
+    assert!(harness.account(&pda::vault_config().0).is_none());
+}
+
+#[test]
+fn set_treasury_stores_the_treasury_in_the_vault_config() {
+    let mut harness = Harness::new();
+    let admin = get_admin_account_pubkey();
+    harness.airdrop(&admin, 1_000_000_000);
+    let treasury = harness.new_wallet();
+
+    let logs = harness
+        .process_transaction(&[instructions::set_treasury(&admin, &treasury)], &[admin])
+        .unwrap();
+    let config: VaultConfig = harness.anchor_account(&pda::vault_config().0);
+    assert_eq!(config.treasury, treasury);
+    assert!(!config.default_royalties_enabled);
+    assert_eq!(events::<TreasuryConfigured>(&logs)[0].treasury, treasury);
+
+    let result = harness.process_transaction(
+        &[instructions::set_treasury(&admin, &Pubkey::default())],
+        &[admin],
+    );
+    assert_program_error(result, ErrorCode::InvalidTreasury);
+}
+
+#[test]
+fn create_primary_listing_records_the_price() {
+    let mut harness = Harness::new();
+    let admin = get_admin_account_pubkey();
+    harness.airdrop(&admin, 1_000_000_000);
+    let nft_mint = harness.create_nft(&pda::sale_escrow().0);
+
+    let logs = harness
+        .process_transaction(
+            &[instructions::create_primary_listing(
+                &admin,
+                &nft_mint,
+                USDC_PRICE,
+                SaleCurrency::Usdc,
+                &spl_token::ID,
+            )],
+            &[admin],
+        )
+        .unwrap();
+
+    let listing: PrimaryListing = harness.anchor_account(&pda::primary_listing(&nft_mint).0);
+    assert_eq!(listing.nft_mint, nft_mint);
+    assert_eq!(listing.currency, SaleCurrency::Usdc);
+    assert_eq!(listing.price, USDC_PRICE);
+    assert_eq!(listing.listed_at, harness.now());
+    let created = &events::<PrimaryListingCreated>(&logs)[0];
+    assert_eq!(created.nft_mint, nft_mint);
+    assert_eq!(created.price, USDC_PRICE);
+}
+
+#[test]
+fn create_primary_listing_is_admin_only() {
+    let mut harness = Harness::new();
+    let payer = harness.new_wallet();
+    let nft_mint = harness.create_nft(&pda::sale_escrow().0);
+
+    let result = harness.process_transaction(
+        &[instructions::create_primary_listing(
+            &payer,
+            &nft_mint,
+            SOL_PRICE,
+            SaleCurrency::Sol,
+            &spl_token::ID,
+        )],
+        &[payer],
+    );
+    assert_program_error(result, ErrorCode::UnauthorizedTransactionSigner);
+}
+
+#[test]
+fn create_primary_listing_rejects_a_zero_price() {
+    let mut harness = Harness::new();
+    let admin = get_admin_account_pubkey();
+    harness.airdrop(&admin, 1_000_000_000);
+    let nft_mint = harness.create_nft(&pda::sale_escrow().0);
+
+    let result = harness.process_transaction(
+        &[instructions::create_primary_listing(
+            &admin,
+            &nft_mint,
+            0,
+            SaleCurrency::Sol,
+            &spl_token::ID,
+        )],
+        &[admin],
+    );
+    assert_program_error(result, ErrorCode::InvalidSalePrice);
+}
+
+#[test]
+fn create_primary_listing_requires_the_nft_in_the_sale_escrow() {
+    let mut harness = Harness::new();
+    let admin = get_admin_account_pubkey();
+    harness.airdrop(&admin, 1_000_000_000);
+    let nft_mint = harness.create_nft(&admin);
+    harness
+        .process_transaction(
+            &[
+                spl_associated_token_account::instruction::create_associated_token_account(
+                    &admin,
+                    &pda::sale_escrow().0,
+                    &nft_mint,
+                    &spl_token::ID,
+                ),
+            ],
+            &[admin],
+        )
+        .unwrap();
+
+    let result = harness.process_transaction(
+        &[instructions::create_primary_listing(
+            &admin,
+            &nft_mint,
+            SOL_PRICE,
+            SaleCurrency::Sol,
+            &spl_token::ID,
+        )],
+        &[admin],
+    );
+    assert_program_error(result, ErrorCode::InvalidNFTAccount);
+}
+
+#[test]
+fn purchase_listed_item_pays_the_treasury_in_sol() {
+    let mut harness = Harness::new();
+    let treasury = set_treasury(&mut harness);
+    let nft_mint = list(&mut harness, SOL_PRICE, SaleCurrency::Sol);
+    let buyer = harness.new_wallet();
+    let admin = get_admin_account_pubkey();
+    let treasury_lamports = harness.lamports(&treasury);
+    let admin_lamports = harness.lamports(&admin);
+    let refunded_rent = harness.lamports(&pda::primary_listing(&nft_mint).0)
+        + harness.lamports(&escrow_nft_account(&nft_mint));
+
+    let logs = harness
+        .process_transaction(
+            &purchase(&buyer, &treasury, &nft_mint, SaleCurrency::Sol),
+            &[buyer],
+        )
+        .unwrap();
+
+    assert_eq!(harness.lamports(&treasury), treasury_lamports + SOL_PRICE);
+    assert_eq!(
+        harness.token_balance(&get_associated_token_address(&buyer, &nft_mint)),
+        1
+    );
+    // The listing and the escrow token account are closed, refunding the admin
+    assert!(harness
+        .account(&pda::primary_listing(&nft_mint).0)
+        .is_none());
+    assert!(harness.account(&escrow_nft_account(&nft_mint)).is_none());
+    assert_eq!(harness.lamports(&admin), admin_lamports + refunded_rent);
+    let purchased = &events::<ListedItemPurchased>(&logs)[0];
+    assert_eq!(purchased.buyer, buyer);
+    assert_eq!(purchased.treasury, treasury);
+    assert_eq!(purchased.currency, SaleCurrency::Sol);
+    assert_eq!(purchased.price, SOL_PRICE);
+}
+
+#[test]
+fn purchase_listed_item_pays_the_treasury_in_usdc() {
+    let mut harness = Harness::new();
+    let treasury = set_treasury(&mut harness);
+    let nft_mint = list(&mut harness, USDC_PRICE, SaleCurrency::Usdc);
+    let buyer = harness.new_wallet();
+    let buyer_usdc = harness.create_usdc_account(&buyer, USDC_PRICE + 1);
+    let treasury_usdc = harness.create_usdc_account(&treasury, 0);
+    let treasury_lamports = harness.lamports(&treasury);
+
+    harness
+        .process_transaction(
+            &purchase(&buyer, &treasury, &nft_mint, SaleCurrency::Usdc),
+            &[buyer],
+        )
+        .unwrap();
+
+    assert_eq!(harness.token_balance(&buyer_usdc), 1);
+    assert_eq!(harness.token_balance(&treasury_usdc), USDC_PRICE);
+    assert_eq!(harness.lamports(&treasury), treasury_lamports);
+    assert_eq!(
+        harness.token_balance(&get_associated_token_address(&buyer, &nft_mint)),
+        1
+    );
+}
+
+#[test]
+fn purchase_listed_item_requires_the_usdc_accounts() {
+    let mut harness = Harness::new();
+    let treasury = set_treasury(&mut harness);
+    let nft_mint = list(&mut harness, USDC_PRICE, SaleCurrency::Usdc);
+    let buyer = harness.new_wallet();
+    let buyer_usdc = harness.create_usdc_account(&buyer, USDC_PRICE);
+    harness.create_usdc_account(&treasury, 0);
+
+    // A USDC listing cannot be paid in SOL
+    let result = harness.process_transaction(
+        &purchase(&buyer, &treasury, &nft_mint, SaleCurrency::Sol),
+        &[buyer],
+    );
+    assert_program_error(result, ErrorCode::MissingUsdcPaymentAccounts);
+
+    // Proceeds cannot be redirected to a USDC account the treasury does not own
+    let other = harness.new_wallet();
+    let other_usdc = harness.create_usdc_account(&other, 0);
+    let mut purchase_ix = purchase(&buyer, &treasury, &nft_mint, SaleCurrency::Usdc);
+    replace_account(
+        &mut purchase_ix[0],
+        &get_associated_token_address(&treasury, &get_usdc_mint_pubkey()),
+        &other_usdc,
+    );
+    let result = harness.process_transaction(&purchase_ix, &[buyer]);
+    assert_program_error(result, ErrorCode::InvalidPaymentAccount);
+    assert_eq!(harness.token_balance(&buyer_usdc), USDC_PRICE);
+}
+
+#[test]
+fn purchase_listed_item_requires_the_configured_treasury() {
+    let mut harness = Harness::new();
+    let admin = get_admin_account_pubkey();
+    harness.airdrop(&admin, 10_000_000_000);
+    let nft_mint = list(&mut harness, SOL_PRICE, SaleCurrency::Sol);
+    let buyer = harness.new_wallet();
+    let impostor = harness.new_wallet();
+
+    // No treasury has been set, so there is no vault config yet
+    let result = harness.process_transaction(
+        &purchase(&buyer, &impostor, &nft_mint, SaleCurrency::Sol),
+        &[buyer],
+    );
+    assert_instruction_error(
+        result,
+        InstructionError::Custom(AnchorErrorCode::AccountNotInitialized.into()),
+    );
+
+    // A vault config created for royalties alone still has no treasury
+    harness
+        .process_transaction(
+            &[instructions::set_default_royalties(&admin, false)],
+            &[admin],
+        )
+        .unwrap();
+    let result = harness.process_transaction(
+        &purchase(&buyer, &impostor, &nft_mint, SaleCurrency::Sol),
+        &[buyer],
+    );
+    assert_program_error(result, ErrorCode::TreasuryNotConfigured);
+
+    set_treasury(&mut harness);
+    let result = harness.process_transaction(
+        &purchase(&buyer, &impostor, &nft_mint, SaleCurrency::Sol),
+        &[buyer],
+    );
+    assert_program_error(result, ErrorCode::InvalidTreasury);
+}
+
+#[test]
+fn cancel_primary_listing_returns_the_nft_to_the_admin() {
+    let mut harness = Harness::new();
+    let treasury = set_treasury(&mut harness);
+    let nft_mint = list(&mut harness, SOL_PRICE, SaleCurrency::Sol);
+    let admin = get_admin_account_pubkey();
+
+    let result = harness.process_transaction(
+        &[instructions::cancel_primary_listing(
+            &treasury,
+            &nft_mint,
+            NftStandard::NonFungible,
+            &spl_token::ID,
+        )],
+        &[treasury],
+    );
+    assert_program_error(result, ErrorCode::UnauthorizedTransactionSigner);
+
+    harness
+        .process_transaction(
+            &[instructions::cancel_primary_listing(
+                &admin,
+                &nft_mint,
+                NftStandard::NonFungible,
+                &spl_token::ID,
+            )],
+            &[admin],
+        )
+        .unwrap();
+    assert_eq!(
+        harness.token_balance(&get_associated_token_address(&admin, &nft_mint)),
+        1
+    );
+    assert!(harness
+        .account(&pda::primary_listing(&nft_mint).0)
+        .is_none());
+    assert!(harness.account(&escrow_nft_account(&nft_mint)).is_none());
+
+    // The NFT is no longer for sale
+    let buyer = harness.new_wallet();
+    let result = harness.process_transaction(
+        &purchase(&buyer, &treasury, &nft_mint, SaleCurrency::Sol),
+        &[buyer],
+    );
+    assert_instruction_error(
+        result,
+        InstructionError::Custom(AnchorErrorCode::AccountNotInitialized.into()),
+    );
+}
@@ -0,0 +1,457 @@
+/**
+ * Rental tests: NFTs listed for rent in rental escrow, renters paying the owner per day for the
+ * usage rights and holding a usage token for the term, and the permissionless crank returning the
+ * NFT to its owner and burning the usage token when the term ends.
+ */
+mod common;
+
+use {
+    anchor_lang::{
+        error::ErrorCode as AnchorErrorCode,
+        prelude::Pubkey,
+        solana_program::{
+            instruction::{Instruction, InstructionError},
+            system_program,
+        },
+    },
+    collectible_vault::{
+        errors::ErrorCode,
+        events::{NftListedForRent, NftRented, RentalEnded},
+        instructions::nft_transfer::NftStandard,
+        state::Rental,
+    },
+    collectible_vault_client::{instructions, pda},
+    common::{assert_instruction_error, assert_program_error, events, Harness, StoredAccount},
+    spl_associated_token_account::get_associated_token_address,
+    spl_token_2022::{
+        error::TokenError,
+        extension::{
+            mint_close_authority::MintCloseAuthority, permanent_delegate::PermanentDelegate,
+            BaseStateWithExtensions, StateWithExtensions,
+        },
+        state::Mint,
+    },
+};
+
+const DAILY_FEE: u64 = 100_000_000;
+const MAX_DAYS: u16 = 30;
+
+/// Creates an NFT for a fresh owner and lists it for rent, returning `(owner, nft_mint)`
+fn list_for_rent(harness: &mut Harness) -> (Pubkey, Pubkey) {
+    let owner = harness.new_wallet();
+    let nft_mint = harness.create_nft(&owner);
+    harness
+        .process_transaction(&[list_ix(&owner, &nft_mint, DAILY_FEE, MAX_DAYS)], &[owner])
+        .unwrap();
+    (owner, nft_mint)
+}
+
+fn list_ix(owner: &Pubkey, nft_mint: &Pubkey, daily_fee: u64, max_days: u16) -> Instruction {
+    instructions::list_nft_for_rent(
+        owner,
+        nft_mint,
+        daily_fee,
+        max_days,
+        NftStandard::NonFungible,
+        &spl_token::ID,
+    )
+}
+
+fn end_rental_ix(
+    payer: &Pubkey,
+    owner: &Pubkey,
+    renter: &Pubkey,
+    nft_mint: &Pubkey,
+) -> Instruction {
+    instructions::end_rental(
+        payer,
+        owner,
+        renter,
+        nft_mint,
+        NftStandard::NonFungible,
+        &spl_token::ID,
+    )
+}
+
+fn cancel_ix(owner: &Pubkey, nft_mint: &Pubkey) -> Instruction {
+    instructions::cancel_rental_listing(owner, nft_mint, NftStandard::NonFungible, &spl_token::ID)
+}
+
+fn rental_nft_account(nft_mint: &Pubkey) -> Pubkey {
+    get_associated_token_address(&pda::rental(nft_mint).0, nft_mint)
+}
+
+#[test]
+fn list_nft_for_rent_moves_the_nft_into_rental_escrow() {
+    let mut harness = Harness::new();
+    let owner = harness.new_wallet();
+    let nft_mint = harness.create_nft(&owner);
+
+    let result = harness.process_transaction(&[list_ix(&owner, &nft_mint, 0, MAX_DAYS)], &[owner]);
+    assert_program_error(result, ErrorCode::InvalidRentalFee);
+    let result = harness.process_transaction(&[list_ix(&owner, &nft_mint, DAILY_FEE, 0)], &[owner]);
+    assert_program_error(result, ErrorCode::InvalidRentalDuration);
+
+    let logs = harness
+        .process_transaction(&[list_ix(&owner, &nft_mint, DAILY_FEE, MAX_DAYS)], &[owner])
+        .unwrap();
+
+    assert_eq!(
+        harness.token_balance(&get_associated_token_address(&owner, &nft_mint)),
+        0
+    );
+    assert_eq!(harness.token_balance(&rental_nft_account(&nft_mint)), 1);
+    let rental: Rental = harness.anchor_account(&pda::rental(&nft_mint).0);
+    assert_eq!(rental.owner, owner);
+    assert_eq!(rental.daily_fee, DAILY_FEE);
+    assert_eq!(rental.max_days, MAX_DAYS);
+    assert_eq!(rental.renter_at(harness.now()), None);
+    assert_eq!(events::<NftListedForRent>(&logs)[0].owner, owner);
+}
+
+#[test]
+fn rent_nft_pays_the_owner_and_grants_usage_rights() {
+    let mut harness = Harness::new();
+    let (owner, nft_mint) = list_for_rent(&mut harness);
+    let renter = harness.new_wallet();
+    let other = harness.new_wallet();
+
+    for days in [0, MAX_DAYS + 1] {
+        let result = harness.process_transaction(
+            &[instructions::rent_nft(&renter, &owner, &nft_mint, days)],
+            &[renter],
+        );
+        assert_program_error(result, ErrorCode::InvalidRentalDuration);
+    }
+
+    let owner_lamports = harness.lamports(&owner);
+    let logs = harness
+        .process_transaction(
+            &[instructions::rent_nft(&renter, &owner, &nft_mint, 7)],
+            &[renter],
+        )
+        .unwrap();
+
+    assert_eq!(harness.lamports(&owner), owner_lamports + 7 * DAILY_FEE);
+    let rented = &events::<NftRented>(&logs)[0];
+    assert_eq!(rented.fee, 7 * DAILY_FEE);
+    assert_eq!(
+        rented.rental_end,
+        harness.now() + 7 * Rental::SECONDS_PER_DAY
+    );
+    let rental: Rental = harness.anchor_account(&pda::rental(&nft_mint).0);
+    assert_eq!(rental.renter_at(harness.now()), Some(renter));
+    assert_eq!(rental.renter_at(rented.rental_end), None);
+    // The NFT stays in escrow, owned by the lister, for the whole term
+    assert_eq!(harness.token_balance(&rental_nft_account(&nft_mint)), 1);
+
+    // The renter holds the usage token, which the rental can take back
+    let usage_account = instructions::rental_usage_account(&renter, &nft_mint);
+    assert_eq!(harness.token_balance(&usage_account), 1);
+    let usage_mint = harness
+        .account(&pda::rental_usage_mint(&nft_mint).0)
+        .unwrap();
+    assert_eq!(usage_mint.owner, spl_token_2022::ID);
+    let usage_mint = StateWithExtensions::<Mint>::unpack(&usage_mint.data).unwrap();
+    let rental_key = Some(pda::rental(&nft_mint).0).try_into().unwrap();
+    assert_eq!(usage_mint.base.supply, 1);
+    assert_eq!(
+        usage_mint
+            .get_extension::<PermanentDelegate>()
+            .unwrap()
+            .delegate,
+        rental_key
+    );
+    assert_eq!(
+        usage_mint
+            .get_extension::<MintCloseAuthority>()
+            .unwrap()
+            .close_authority,
+        rental_key
+    );
+
+    let result = harness.process_transaction(
+        &[instructions::rent_nft(&other, &owner, &nft_mint, 1)],
+        &[other],
+    );
+    assert_program_error(result, ErrorCode::NftAlreadyRented);
+    let result = harness.process_transaction(&[cancel_ix(&owner, &nft_mint)], &[owner]);
+    assert_program_error(result, ErrorCode::NftAlreadyRented);
+}
+
+#[test]
+fn end_rental_returns_the_nft_to_the_owner_once_the_term_ends() {
+    let mut harness = Harness::new();
+    let (owner, nft_mint) = list_for_rent(&mut harness);
+    let renter = harness.new_wallet();
+    let cranker = harness.new_wallet();
+
+    let result = harness.process_transaction(
+        &[end_rental_ix(&cranker, &owner, &renter, &nft_mint)],
+        &[cranker],
+    );
+    assert_program_error(result, ErrorCode::NftNotRented);
+
+    harness
+        .process_transaction(
+            &[instructions::rent_nft(&renter, &owner, &nft_mint, 2)],
+            &[renter],
+        )
+        .unwrap();
+    harness.warp_forward(Rental::SECONDS_PER_DAY);
+    let result = harness.process_transaction(
+        &[end_rental_ix(&cranker, &owner, &renter, &nft_mint)],
+        &[cranker],
+    );
+    assert_program_error(result, ErrorCode::RentalNotEnded);
+
+    harness.warp_forward(Rental::SECONDS_PER_DAY);
+    let rental_key = pda::rental(&nft_mint).0;
+    let escrow_rent =
+        harness.lamports(&rental_key) + harness.lamports(&rental_nft_account(&nft_mint));
+    let owner_lamports = harness.lamports(&owner);
+    let renter_lamports = harness.lamports(&renter);
+    let usage_mint_rent = harness.lamports(&pda::rental_usage_mint(&nft_mint).0);
+    let logs = harness
+        .process_transaction(
+            &[end_rental_ix(&cranker, &owner, &renter, &nft_mint)],
+            &[cranker],
+        )
+        .unwrap();
+
+    assert_eq!(
+        harness.token_balance(&get_associated_token_address(&owner, &nft_mint)),
+        1
+    );
+    assert!(harness.account(&rental_key).is_none());
+    assert!(harness.account(&rental_nft_account(&nft_mint)).is_none());
+    assert_eq!(harness.lamports(&owner), owner_lamports + escrow_rent);
+    assert_eq!(events::<RentalEnded>(&logs)[0].renter, Some(renter));
+
+    // The usage token is burned and its mint's rent returned to the renter
+    assert_eq!(
+        harness.token_balance(&instructions::rental_usage_account(&renter, &nft_mint)),
+        0
+    );
+    assert!(harness
+        .account(&pda::rental_usage_mint(&nft_mint).0)
+        .is_none());
+    assert_eq!(harness.lamports(&renter), renter_lamports + usage_mint_rent);
+}
+
+#[test]
+fn end_rental_leaves_a_rental_account_holding_stray_tokens_open() {
+    let mut harness = Harness::new();
+    let (owner, nft_mint) = list_for_rent(&mut harness);
+    let renter = harness.new_wallet();
+    harness
+        .process_transaction(
+            &[instructions::rent_nft(&renter, &owner, &nft_mint, 1)],
+            &[renter],
+        )
+        .unwrap();
+    harness.send_stray_token(&rental_nft_account(&nft_mint));
+    harness.warp_forward(Rental::SECONDS_PER_DAY);
+
+    harness
+        .process_transaction(
+            &[end_rental_ix(&owner, &owner, &renter, &nft_mint)],
+            &[owner],
+        )
+        .unwrap();
+
+    assert_eq!(
+        harness.token_balance(&get_associated_token_address(&owner, &nft_mint)),
+        1
+    );
+    assert_eq!(harness.token_balance(&rental_nft_account(&nft_mint)), 1);
+}
+
+#[test]
+fn usage_tokens_cannot_be_transferred() {
+    let mut harness = Harness::new();
+    let (owner, nft_mint) = list_for_rent(&mut harness);
+    let renter = harness.new_wallet();
+    let other = harness.new_wallet();
+    harness
+        .process_transaction(
+            &[instructions::rent_nft(&renter, &owner, &nft_mint, 1)],
+            &[renter],
+        )
+        .unwrap();
+
+    let usage_mint = pda::rental_usage_mint(&nft_mint).0;
+    let other_account = instructions::rental_usage_account(&other, &nft_mint);
+    let result = harness.process_transaction(
+        &[
+            spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+                &renter,
+                &other,
+                &usage_mint,
+                &spl_token_2022::ID,
+            ),
+            spl_token_2022::instruction::transfer_checked(
+                &spl_token_2022::ID,
+                &instructions::rental_usage_account(&renter, &nft_mint),
+                &usage_mint,
+                &other_account,
+                &renter,
+                &[],
+                1,
+                0,
+            )
+            .unwrap(),
+        ],
+        &[renter],
+    );
+    assert_instruction_error(
+        result,
+        InstructionError::Custom(TokenError::NonTransferable as u32),
+    );
+}
+
+#[test]
+fn end_rental_takes_back_usage_tokens_the_renter_already_gave_up() {
+    let mut harness = Harness::new();
+    let (owner, nft_mint) = list_for_rent(&mut harness);
+    let renter = harness.new_wallet();
+    let cranker = harness.new_wallet();
+    harness
+        .process_transaction(
+            &[instructions::rent_nft(&renter, &owner, &nft_mint, 1)],
+            &[renter],
+        )
+        .unwrap();
+
+    // The renter burns the usage token and closes its account
+    let usage_mint = pda::rental_usage_mint(&nft_mint).0;
+    let usage_account = instructions::rental_usage_account(&renter, &nft_mint);
+    harness
+        .process_transaction(
+            &[
+                spl_token_2022::instruction::burn(
+                    &spl_token_2022::ID,
+                    &usage_account,
+                    &usage_mint,
+                    &renter,
+                    &[],
+                    1,
+                )
+                .unwrap(),
+                spl_token_2022::instruction::close_account(
+                    &spl_token_2022::ID,
+                    &usage_account,
+                    &renter,
+                    &renter,
+                    &[],
+                )
+                .unwrap(),
+            ],
+            &[renter],
+        )
+        .unwrap();
+    harness.warp_forward(Rental::SECONDS_PER_DAY);
+
+    let result = harness.process_transaction(
+        &[end_rental_ix(&cranker, &owner, &cranker, &nft_mint)],
+        &[cranker],
+    );
+    assert_program_error(result, ErrorCode::InvalidRenter);
+
+    harness
+        .process_transaction(
+            &[end_rental_ix(&cranker, &owner, &renter, &nft_mint)],
+            &[cranker],
+        )
+        .unwrap();
+    assert!(harness.account(&usage_mint).is_none());
+
+    // The NFT can be rented again, reissuing the usage token from a fresh mint
+    harness
+        .process_transaction(&[list_ix(&owner, &nft_mint, DAILY_FEE, MAX_DAYS)], &[owner])
+        .unwrap();
+    harness
+        .process_transaction(
+            &[instructions::rent_nft(&renter, &owner, &nft_mint, 1)],
+            &[renter],
+        )
+        .unwrap();
+    assert_eq!(harness.token_balance(&usage_account), 1);
+}
+
+#[test]
+fn end_rental_requires_the_usage_mint() {
+    let mut harness = Harness::new();
+    let (owner, nft_mint) = list_for_rent(&mut harness);
+    let renter = harness.new_wallet();
+    harness
+        .process_transaction(
+            &[instructions::rent_nft(&renter, &owner, &nft_mint, 1)],
+            &[renter],
+        )
+        .unwrap();
+    harness.warp_forward(Rental::SECONDS_PER_DAY);
+    harness.set_account(
+        pda::rental_usage_mint(&nft_mint).0,
+        StoredAccount {
+            lamports: 0,
+            data: vec![],
+            owner: system_program::ID,
+            executable: false,
+        },
+    );
+
+    let result = harness.process_transaction(
+        &[end_rental_ix(&owner, &owner, &renter, &nft_mint)],
+        &[owner],
+    );
+    assert_instruction_error(
+        result,
+        InstructionError::Custom(AnchorErrorCode::ConstraintOwner.into()),
+    );
+}
+
+#[test]
+fn cancel_rental_listing_returns_an_unrented_nft() {
+    let mut harness = Harness::new();
+    let (owner, nft_mint) = list_for_rent(&mut harness);
+    let other = harness.new_wallet();
+
+    let result = harness.process_transaction(&[cancel_ix(&other, &nft_mint)], &[other]);
+    assert_program_error(result, ErrorCode::InvalidRentalOwner);
+
+    let logs = harness
+        .process_transaction(&[cancel_ix(&owner, &nft_mint)], &[owner])
+        .unwrap();
+
+    assert_eq!(
+        harness.token_balance(&get_associated_token_address(&owner, &nft_mint)),
+        1
+    );
+    assert!(harness.account(&pda::rental(&nft_mint).0).is_none());
+    assert_eq!(events::<RentalEnded>(&logs)[0].renter, None);
+
+    // The NFT can be listed again once it is back in the owner's wallet
+    harness
+        .process_transaction(&[list_ix(&owner, &nft_mint, DAILY_FEE, MAX_DAYS)], &[owner])
+        .unwrap();
+}
+
+#[test]
+fn rent_nft_is_not_blocked_by_a_prefunded_usage_mint() {
+    let mut harness = Harness::new();
+    let (owner, nft_mint) = list_for_rent(&mut harness);
+    let renter = harness.new_wallet();
+    harness.airdrop(&pda::rental_usage_mint(&nft_mint).0, 1);
+
+    harness
+        .process_transaction(
+            &[instructions::rent_nft(&renter, &owner, &nft_mint, 1)],
+            &[renter],
+        )
+        .unwrap();
+    assert_eq!(
+        harness.token_balance(&instructions::rental_usage_account(&renter, &nft_mint)),
+        1
+    );
+}
@@ -0,0 +1,409 @@
+/**
+ * Token-2022 tests: loans and redemptions of NFTs minted under Token-2022, including mints with a
+ * metadata pointer, a transfer hook, or the non-transferable extension. Non-transferable NFTs are
+ * redeemed by approving the asset redemption vault and burning the NFT on fulfillment.
+ */
+mod common;
+
+use {
+    anchor_lang::{
+        error::ErrorCode as AnchorErrorCode,
+        prelude::Pubkey,
+        solana_program::instruction::{Instruction, InstructionError},
+    },
+    collectible_vault::{
+        constants::admin_constants::get_admin_account_pubkey, errors::ErrorCode,
+        instructions::nft_transfer::NftStandard, state::AssetRedemptionInfo,
+    },
+    collectible_vault_client::{instructions, pda},
+    common::{
+        assert_instruction_error, assert_program_error, transfer_hook_accounts, Harness,
+        MintExtension,
+    },
+    spl_associated_token_account::get_associated_token_address_with_program_id,
+    spl_token_2022::{
+        extension::StateWithExtensions, solana_program::program_option::COption, state::Account,
+    },
+    spl_transfer_hook_interface::error::TransferHookError,
+};
+
+const LOAN_AMOUNT: u64 = 2_000_000_000;
+const INTEREST_AMOUNT: u64 = 100_000_000;
+const DURATION: i64 = 7 * 24 * 60 * 60;
+
+fn token_account(owner: &Pubkey, nft_mint: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program_id(owner, nft_mint, &spl_token_2022::ID)
+}
+
+fn delegate(harness: &Harness, token_account: &Pubkey) -> COption<Pubkey> {
+    let account = harness.account(token_account).unwrap();
+    StateWithExtensions::<Account>::unpack(&account.data)
+        .unwrap()
+        .base
+        .delegate
+}
+
+/// Requests the redemption of a new non-transferable NFT, returning its owner and mint.
+fn request_non_transferable_redemption(harness: &mut Harness) -> (Pubkey, Pubkey) {
+    let owner = harness.new_wallet();
+    let nft_mint = harness.create_token_2022_nft(&owner, &[MintExtension::NonTransferable]);
+    harness
+        .process_transaction(
+            &[instructions::create_non_transferable_asset_redemption_request(&owner, &nft_mint)],
+            &[owner],
+        )
+        .unwrap();
+    (owner, nft_mint)
+}
+
+fn stake_instruction(borrower: &Pubkey, nft_mint: &Pubkey) -> Instruction {
+    instructions::stake_nft_for_loan(
+        borrower,
+        nft_mint,
+        LOAN_AMOUNT,
+        INTEREST_AMOUNT,
+        DURATION,
+        None,
+        NftStandard::NonFungible,
+        &spl_token_2022::ID,
+    )
+}
+
+#[test]
+fn token_2022_nft_goes_through_a_full_loan() {
+    let mut harness = Harness::new();
+    let borrower = harness.new_wallet();
+    let nft_mint = harness.create_token_2022_nft(&borrower, &[MintExtension::MetadataPointer]);
+    let vault_nft_account = token_account(&pda::vault_authority().0, &nft_mint);
+
+    harness
+        .process_transaction(&[stake_instruction(&borrower, &nft_mint)], &[borrower])
+        .unwrap();
+    assert_eq!(harness.token_balance(&vault_nft_account), 1);
+    assert_eq!(
+        harness.account(&vault_nft_account).unwrap().owner,
+        spl_token_2022::ID
+    );
+
+    let lender = harness.new_wallet();
+    harness
+        .process_transaction(
+            &[instructions::provide_loan_liquidity(
+                &lender, &borrower, &nft_mint, 1, None,
+            )],
+            &[lender],
+        )
+        .unwrap();
+    harness
+        .process_transaction(
+            &[instructions::repay_loan(
+                &borrower,
+                &borrower,
+                &lender,
+                &nft_mint,
+                1,
+                NftStandard::NonFungible,
+                &spl_token_2022::ID,
+            )],
+            &[borrower],
+        )
+        .unwrap();
+    assert_eq!(
+        harness.token_balance(&token_account(&borrower, &nft_mint)),
+        1
+    );
+    assert_eq!(harness.token_balance(&vault_nft_account), 0);
+}
+
+#[test]
+fn token_2022_nft_is_claimed_by_the_lender_on_default() {
+    let mut harness = Harness::new();
+    let borrower = harness.new_wallet();
+    let nft_mint = harness.create_token_2022_nft(&borrower, &[]);
+    harness
+        .process_transaction(&[stake_instruction(&borrower, &nft_mint)], &[borrower])
+        .unwrap();
+    let lender = harness.new_wallet();
+    harness
+        .process_transaction(
+            &[instructions::provide_loan_liquidity(
+                &lender, &borrower, &nft_mint, 1, None,
+            )],
+            &[lender],
+        )
+        .unwrap();
+    harness.warp_forward(DURATION + 1);
+
+    harness
+        .process_transaction(
+            &[instructions::claim_delinquent_nft(
+                &lender,
+                &borrower,
+                &nft_mint,
+                1,
+                NftStandard::NonFungible,
+                &spl_token_2022::ID,
+            )],
+            &[lender],
+        )
+        .unwrap();
+    assert_eq!(harness.token_balance(&token_account(&lender, &nft_mint)), 1);
+}
+
+#[test]
+fn cancel_loan_request_closes_the_token_2022_vault_account() {
+    let mut harness = Harness::new();
+    let borrower = harness.new_wallet();
+    let nft_mint = harness.create_token_2022_nft(&borrower, &[MintExtension::MetadataPointer]);
+    harness
+        .process_transaction(&[stake_instruction(&borrower, &nft_mint)], &[borrower])
+        .unwrap();
+
+    harness
+        .process_transaction(
+            &[instructions::cancel_loan_request(
+                &borrower,
+                &nft_mint,
+                1,
+                NftStandard::NonFungible,
+                &spl_token_2022::ID,
+            )],
+            &[borrower],
+        )
+        .unwrap();
+    assert_eq!(
+        harness.token_balance(&token_account(&borrower, &nft_mint)),
+        1
+    );
+    assert!(harness
+        .account(&token_account(&pda::vault_authority().0, &nft_mint))
+        .is_none());
+}
+
+#[test]
+fn token_2022_nft_can_be_redeemed_and_the_request_cancelled() {
+    let mut harness = Harness::new();
+    let owner = harness.new_wallet();
+    let nft_mint = harness.create_token_2022_nft(&owner, &[MintExtension::MetadataPointer]);
+    let redemption_nft_account = token_account(&pda::asset_redemption_vault().0, &nft_mint);
+
+    harness
+        .process_transaction(
+            &[instructions::create_asset_redemption_request(
+                &owner,
+                &nft_mint,
+                NftStandard::NonFungible,
+                &spl_token_2022::ID,
+            )],
+            &[owner],
+        )
+        .unwrap();
+    assert_eq!(harness.token_balance(&redemption_nft_account), 1);
+    let info: AssetRedemptionInfo =
+        harness.anchor_account(&pda::asset_redemption_info(&nft_mint).0);
+    assert_eq!(info.nft_owner, owner);
+
+    harness
+        .process_transaction(
+            &[instructions::cancel_asset_redemption_request(
+                &owner,
+                &nft_mint,
+                NftStandard::NonFungible,
+                &spl_token_2022::ID,
+            )],
+            &[owner],
+        )
+        .unwrap();
+    assert_eq!(harness.token_balance(&token_account(&owner, &nft_mint)), 1);
+    assert!(harness.account(&redemption_nft_account).is_none());
+}
+
+#[test]
+fn transfer_hook_runs_when_the_nft_enters_and_leaves_the_vault() {
+    let mut harness = Harness::new();
+    let borrower = harness.new_wallet();
+    let nft_mint = harness.create_token_2022_nft(&borrower, &[MintExtension::TransferHook]);
+
+    let mut stake = stake_instruction(&borrower, &nft_mint);
+    stake.accounts.extend(transfer_hook_accounts(&nft_mint));
+    let logs = harness.process_transaction(&[stake], &[borrower]).unwrap();
+    assert!(logs
+        .iter()
+        .any(|log| log == "Program log: Transfer hook executed for 1 tokens"));
+    assert_eq!(
+        harness.token_balance(&token_account(&pda::vault_authority().0, &nft_mint)),
+        1
+    );
+
+    let mut cancel = instructions::cancel_loan_request(
+        &borrower,
+        &nft_mint,
+        1,
+        NftStandard::NonFungible,
+        &spl_token_2022::ID,
+    );
+    cancel.accounts.extend(transfer_hook_accounts(&nft_mint));
+    let logs = harness.process_transaction(&[cancel], &[borrower]).unwrap();
+    assert!(logs
+        .iter()
+        .any(|log| log == "Program log: Transfer hook executed for 1 tokens"));
+    assert_eq!(
+        harness.token_balance(&token_account(&borrower, &nft_mint)),
+        1
+    );
+}
+
+#[test]
+fn transfer_hook_accounts_are_required() {
+    let mut harness = Harness::new();
+    let borrower = harness.new_wallet();
+    let nft_mint = harness.create_token_2022_nft(&borrower, &[MintExtension::TransferHook]);
+
+    let result =
+        harness.process_transaction(&[stake_instruction(&borrower, &nft_mint)], &[borrower]);
+    assert_instruction_error(
+        result,
+        InstructionError::Custom(TransferHookError::IncorrectAccount as u32),
+    );
+    assert_eq!(
+        harness.token_balance(&token_account(&borrower, &nft_mint)),
+        1
+    );
+}
+
+#[test]
+fn non_transferable_nfts_cannot_be_staked() {
+    let mut harness = Harness::new();
+    let borrower = harness.new_wallet();
+    let nft_mint = harness.create_token_2022_nft(&borrower, &[MintExtension::NonTransferable]);
+
+    let result =
+        harness.process_transaction(&[stake_instruction(&borrower, &nft_mint)], &[borrower]);
+    assert_program_error(result, ErrorCode::NonTransferableNft);
+    assert!(harness.account(&pda::loan_info(&nft_mint).0).is_none());
+}
+
+#[test]
+fn token_2022_nfts_require_the_matching_token_program() {
+    let mut harness = Harness::new();
+    let borrower = harness.new_wallet();
+    let nft_mint = harness.create_token_2022_nft(&borrower, &[]);
+
+    // Deriving the accounts under SPL Token points at token accounts that do not exist
+    let result = harness.process_transaction(
+        &[instructions::stake_nft_for_loan(
+            &borrower,
+            &nft_mint,
+            LOAN_AMOUNT,
+            INTEREST_AMOUNT,
+            DURATION,
+            None,
+            NftStandard::NonFungible,
+            &spl_token::ID,
+        )],
+        &[borrower],
+    );
+    assert_instruction_error(
+        result,
+        InstructionError::Custom(AnchorErrorCode::AccountNotInitialized.into()),
+    );
+    assert!(harness.account(&pda::loan_info(&nft_mint).0).is_none());
+}
+
+#[test]
+fn non_transferable_nft_is_burned_when_its_redemption_is_fulfilled() {
+    let mut harness = Harness::new();
+    let (owner, nft_mint) = request_non_transferable_redemption(&mut harness);
+    let owner_nft_account = token_account(&owner, &nft_mint);
+
+    // The NFT stays with the owner, delegated to the asset redemption vault
+    assert_eq!(harness.token_balance(&owner_nft_account), 1);
+    assert_eq!(
+        delegate(&harness, &owner_nft_account),
+        COption::Some(pda::asset_redemption_vault().0)
+    );
+
+    let admin = get_admin_account_pubkey();
+    harness
+        .process_transaction(
+            &[
+                instructions::fulfill_non_transferable_asset_redemption_request(
+                    &admin, &owner, &nft_mint,
+                ),
+            ],
+            &[admin],
+        )
+        .unwrap();
+    assert_eq!(harness.token_balance(&owner_nft_account), 0);
+    assert_eq!(harness.mint_supply(&nft_mint), 0);
+    let info: AssetRedemptionInfo =
+        harness.anchor_account(&pda::asset_redemption_info(&nft_mint).0);
+    assert!(info.is_fulfilled);
+}
+
+#[test]
+fn non_transferable_redemption_request_can_be_cancelled() {
+    let mut harness = Harness::new();
+    let (owner, nft_mint) = request_non_transferable_redemption(&mut harness);
+    let owner_nft_account = token_account(&owner, &nft_mint);
+
+    harness
+        .process_transaction(
+            &[instructions::cancel_non_transferable_asset_redemption_request(&owner, &nft_mint)],
+            &[owner],
+        )
+        .unwrap();
+    assert_eq!(delegate(&harness, &owner_nft_account), COption::None);
+    assert_eq!(harness.token_balance(&owner_nft_account), 1);
+    assert!(harness
+        .account(&pda::asset_redemption_info(&nft_mint).0)
+        .is_none());
+}
+
+#[test]
+fn non_transferable_redemption_fails_once_the_approval_is_revoked() {
+    let mut harness = Harness::new();
+    let (owner, nft_mint) = request_non_transferable_redemption(&mut harness);
+    let owner_nft_account = token_account(&owner, &nft_mint);
+
+    harness
+        .process_transaction(
+            &[spl_token_2022::instruction::revoke(
+                &spl_token_2022::ID,
+                &owner_nft_account,
+                &owner,
+                &[],
+            )
+            .unwrap()],
+            &[owner],
+        )
+        .unwrap();
+
+    let admin = get_admin_account_pubkey();
+    let result = harness.process_transaction(
+        &[
+            instructions::fulfill_non_transferable_asset_redemption_request(
+                &admin, &owner, &nft_mint,
+            ),
+        ],
+        &[admin],
+    );
+    assert_program_error(result, ErrorCode::RedemptionApprovalRevoked);
+    assert_eq!(harness.token_balance(&owner_nft_account), 1);
+}
+
+#[test]
+fn transferable_nfts_cannot_use_the_non_transferable_redemption() {
+    let mut harness = Harness::new();
+    let owner = harness.new_wallet();
+    let nft_mint = harness.create_token_2022_nft(&owner, &[MintExtension::MetadataPointer]);
+
+    let result = harness.process_transaction(
+        &[instructions::create_non_transferable_asset_redemption_request(&owner, &nft_mint)],
+        &[owner],
+    );
+    assert_program_error(result, ErrorCode::TransferableNft);
+    assert!(harness
+        .account(&pda::asset_redemption_info(&nft_mint).0)
+        .is_none());
+}
@@ -203,6 +203,8 @@ describe('NFT Minting Integration Tests', () => {
 			collectionMetadata: collectionMetadataPDA,
 			collectionMasterEdition: collectionMasterEditionPDA,
 			collectionCounter: collectionCounterPDA,
+			tokenRecord: null,
+			sysvarInstructions: null,
 		};
 
 		console.log('Executing mintNft transaction...');
@@ -211,7 +213,7 @@ describe('NFT Minting Integration Tests', () => {
 		// Verify the owner received the NFT
 		try {
 			const tx = await program.methods
-				.mintNft(TEST_NFT_INFO.productDetailUri)
+				.mintNft(TEST_NFT_INFO.productDetailUri, { nonFungible: {} })
 				.accounts(accounts)
 				.preInstructions([modifyComputeUnits])
 				.signers([ADMIN_KEYPAIR, mint])
@@ -331,13 +333,15 @@ describe('NFT Minting Integration Tests', () => {
 			collectionMetadata: collectionMetadataPDA,
 			collectionMasterEdition: collectionMasterEditionPDA,
 			collectionCounter: collectionCounterPDA,
+			tokenRecord: null,
+			sysvarInstructions: null,
 		};
 
 		console.log('Executing mintNft transaction with unauthorized wallet (expected to fail)...');
 		try {
 			console.time('Unauthorized Attempt Time');
 			const tx = await program.methods
-				.mintNft(TEST_NFT_INFO.productDetailUri)
+				.mintNft(TEST_NFT_INFO.productDetailUri, { nonFungible: {} })
 				.accounts(accounts)
 				.preInstructions([modifyComputeUnits])
 				.signers([OTHER_KEYPAIR, mint])
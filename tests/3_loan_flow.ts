@@ -10,7 +10,57 @@ import { CollectibleVault } from '../target/types/collectible_vault';
 import { assert } from 'chai';
 import { getCollectionAddress, getNftAddress } from '../utils/collection_store';
 import { formatSOL, logBalances, getBalances, logBalanceChanges } from './test-utils';
-import { SEED_PDA_CONSTANTS, TEST_RPC_CONNECTION } from './constants';
+import {
+	METADATA_PROGRAM_ID,
+	NO_PROGRAMMABLE_ACCOUNTS,
+	SEED_PDA_CONSTANTS,
+	TEST_RPC_CONNECTION,
+} from './constants';
+
+// Derive a PDA scoped to the loan currently open against the NFT (e.g. its record or note mint)
+async function getLoanScopedPDA(
+	program: anchor.Program<CollectibleVault>,
+	seed: string,
+	nftMint: PublicKey,
+	loanInfoPDA: PublicKey
+): Promise<PublicKey> {
+	const loanInfo = await program.account.loanInfo.fetch(loanInfoPDA);
+	const [pda] = PublicKey.findProgramAddressSync(
+		[Buffer.from(seed), nftMint.toBuffer(), loanInfo.loanId.toArrayLike(Buffer, 'le', 8)],
+		program.programId
+	);
+	return pda;
+}
+
+const getLoanRecordPDA = (
+	program: anchor.Program<CollectibleVault>,
+	nftMint: PublicKey,
+	loanInfoPDA: PublicKey
+) => getLoanScopedPDA(program, SEED_PDA_CONSTANTS.LOAN_RECORD, nftMint, loanInfoPDA);
+
+const getLoanNoteMintPDA = (
+	program: anchor.Program<CollectibleVault>,
+	nftMint: PublicKey,
+	loanInfoPDA: PublicKey
+) => getLoanScopedPDA(program, SEED_PDA_CONSTANTS.LOAN_NOTE, nftMint, loanInfoPDA);
+
+const getAssetImpairmentPDA = (program: anchor.Program<CollectibleVault>, nftMint: PublicKey) =>
+	PublicKey.findProgramAddressSync(
+		[Buffer.from(SEED_PDA_CONSTANTS.ASSET_IMPAIRMENT), nftMint.toBuffer()],
+		program.programId
+	)[0];
+
+const getVaultConfigPDA = (program: anchor.Program<CollectibleVault>) =>
+	PublicKey.findProgramAddressSync(
+		[Buffer.from(SEED_PDA_CONSTANTS.VAULT_CONFIG)],
+		program.programId
+	)[0];
+
+const getMetadataPDA = (nftMint: PublicKey) =>
+	PublicKey.findProgramAddressSync(
+		[Buffer.from('metadata'), METADATA_PROGRAM_ID.toBuffer(), nftMint.toBuffer()],
+		METADATA_PROGRAM_ID
+	)[0];
 
 async function stakeNftForLoan(
 	program: anchor.Program<CollectibleVault>,
@@ -24,6 +74,7 @@ async function stakeNftForLoan(
 		loanAmount = new anchor.BN(1 * anchor.web3.LAMPORTS_PER_SOL),
 		interestAmount = new anchor.BN(0.25 * anchor.web3.LAMPORTS_PER_SOL),
 		duration = new anchor.BN(24 * 60 * 60), // 1 day default
+		requestExpiry = null,
 	}: {
 		nftOwner: Keypair;
 		nftMint: PublicKey;
@@ -34,6 +85,7 @@ async function stakeNftForLoan(
 		loanAmount?: anchor.BN;
 		interestAmount?: anchor.BN;
 		duration?: anchor.BN;
+		requestExpiry?: anchor.BN | null;
 	}
 ) {
 	console.log('\nStaking NFT for loan...');
@@ -41,10 +93,16 @@ async function stakeNftForLoan(
 	console.log(`Interest Amount: ${formatSOL(interestAmount)}`);
 	console.log(`Duration: ${duration.toString()} seconds`);
 
+	const [loanCounterPDA] = PublicKey.findProgramAddressSync(
+		[Buffer.from(SEED_PDA_CONSTANTS.LOAN_COUNTER), nftMint.toBuffer()],
+		program.programId
+	);
+
 	await program.methods
-		.stakeNftForLoan(loanAmount, interestAmount, duration)
+		.stakeNftForLoan(loanAmount, interestAmount, duration, requestExpiry)
 		.accounts({
 			loanInfo: loanInfoPDA,
+			loanCounter: loanCounterPDA,
 			nftMint: nftMint,
 			ownerNftAccount: ownerNftAccount,
 			vaultNftAccount: vaultNftAccount,
@@ -54,6 +112,8 @@ async function stakeNftForLoan(
 			tokenProgram: TOKEN_PROGRAM_ID,
 			associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
 			rent: SYSVAR_RENT_PUBKEY,
+			assetImpairment: getAssetImpairmentPDA(program, nftMint),
+			programmable: NO_PROGRAMMABLE_ACCOUNTS,
 		})
 		.signers([nftOwner])
 		.rpc();
@@ -62,6 +122,9 @@ async function stakeNftForLoan(
 	const vaultAccount = await program.provider.connection.getTokenAccountBalance(vaultNftAccount);
 	console.log(`NFT tokens in vault: ${vaultAccount.value.uiAmount}`);
 	assert.equal(vaultAccount.value.uiAmount, 1, 'NFT should be in the vault');
+
+	const loanInfo = await program.account.loanInfo.fetch(loanInfoPDA);
+	assert.deepEqual(loanInfo.status, { requested: {} }, 'Loan should be requested');
 }
 
 async function provideLoanLiquidity(
@@ -70,14 +133,28 @@ async function provideLoanLiquidity(
 		lender,
 		borrower,
 		loanInfoPDA,
+		reputationGate = null,
 	}: {
 		lender: Keypair;
 		borrower: PublicKey;
 		loanInfoPDA: PublicKey;
+		reputationGate?: {
+			minLoansRepaidOnTime: anchor.BN;
+			maxLoansDefaulted: anchor.BN;
+			minRepaidVolume: anchor.BN;
+		} | null;
 	}
 ) {
 	console.log('\nProviding loan liquidity...');
 
+	const loanInfo = await program.account.loanInfo.fetch(loanInfoPDA);
+	const loanNoteMint = await getLoanNoteMintPDA(program, loanInfo.nftMint, loanInfoPDA);
+	const lenderNoteAccount = await getAssociatedTokenAddress(loanNoteMint, lender.publicKey);
+	const [vaultAuthority] = PublicKey.findProgramAddressSync(
+		[Buffer.from(SEED_PDA_CONSTANTS.VAULT)],
+		program.programId
+	);
+
 	// Log balances before
 	const balancesBefore = await getBalances(program, [
 		{ key: borrower, label: 'Borrower' },
@@ -85,12 +162,23 @@ async function provideLoanLiquidity(
 	]);
 
 	await program.methods
-		.provideLoanLiquidity()
+		.provideLoanLiquidity(reputationGate)
 		.accounts({
 			loanInfo: loanInfoPDA,
 			lender: lender.publicKey,
 			borrower: borrower,
+			borrowerProfile: PublicKey.findProgramAddressSync(
+				[Buffer.from(SEED_PDA_CONSTANTS.BORROWER_PROFILE), borrower.toBuffer()],
+				program.programId
+			)[0],
+			loanNoteMint,
+			lenderNoteAccount,
+			vaultAuthority,
+			tokenProgram: TOKEN_PROGRAM_ID,
+			associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
 			systemProgram: SystemProgram.programId,
+			rent: SYSVAR_RENT_PUBKEY,
+			assetImpairment: getAssetImpairmentPDA(program, loanInfo.nftMint),
 		})
 		.signers([lender])
 		.rpc();
@@ -103,6 +191,13 @@ async function provideLoanLiquidity(
 
 	// Log changes
 	logBalanceChanges(balancesBefore, balancesAfter);
+
+	// Verify the lender received the loan note
+	const noteAccount = await program.provider.connection.getTokenAccountBalance(lenderNoteAccount);
+	assert.equal(noteAccount.value.uiAmount, 1, 'Lender should hold the loan note');
+
+	const fundedLoanInfo = await program.account.loanInfo.fetch(loanInfoPDA);
+	assert.deepEqual(fundedLoanInfo.status, { active: {} }, 'Loan should be active');
 }
 
 describe('NFT Loan Flow Tests', () => {
@@ -139,6 +234,8 @@ describe('NFT Loan Flow Tests', () => {
 	let lenderNftAccount: PublicKey;
 	let vaultAuthority: PublicKey;
 	let loanInfoPDA: PublicKey;
+	let loanCounterPDA: PublicKey;
+	let borrowerProfilePDA: PublicKey;
 	let collectionMint: PublicKey;
 
 	before(async () => {
@@ -177,6 +274,20 @@ describe('NFT Loan Flow Tests', () => {
 		);
 		console.log(`Loan Info PDA: ${loanInfoPDA.toString()}`);
 
+		// Derive per-mint loan counter PDA
+		[loanCounterPDA] = PublicKey.findProgramAddressSync(
+			[Buffer.from(SEED_PDA_CONSTANTS.LOAN_COUNTER), nftMint.toBuffer()],
+			program.programId
+		);
+		console.log(`Loan Counter PDA: ${loanCounterPDA.toString()}`);
+
+		// Derive borrower reputation profile PDA
+		[borrowerProfilePDA] = PublicKey.findProgramAddressSync(
+			[Buffer.from(SEED_PDA_CONSTANTS.BORROWER_PROFILE), nftOwner.publicKey.toBuffer()],
+			program.programId
+		);
+		console.log(`Borrower Profile PDA: ${borrowerProfilePDA.toString()}`);
+
 		// Log initial balances
 		await logBalances(
 			TEST_RPC_CONNECTION,
@@ -210,6 +321,8 @@ describe('NFT Loan Flow Tests', () => {
 			.accounts({
 				owner: nftOwner.publicKey,
 				loanInfo: loanInfoPDA,
+				loanRecord: await getLoanRecordPDA(program, nftMint, loanInfoPDA),
+				borrowerProfile: borrowerProfilePDA,
 				nftMint: nftMint,
 				ownerNftAccount: ownerNftAccount,
 				vaultNftAccount: vaultNftAccount,
@@ -217,6 +330,7 @@ describe('NFT Loan Flow Tests', () => {
 				tokenProgram: TOKEN_PROGRAM_ID,
 				associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
 				systemProgram: SystemProgram.programId,
+				programmable: NO_PROGRAMMABLE_ACCOUNTS,
 			})
 			.signers([nftOwner])
 			.rpc();
@@ -249,6 +363,79 @@ describe('NFT Loan Flow Tests', () => {
 		console.log('✅ Loan request successfully canceled');
 	});
 
+	/**
+	 * Test: Expired loan request cannot be funded and can be closed by anyone
+	 * Flow:
+	 * 1. NFT owner stakes their NFT with a request expiry a few seconds away
+	 * 2. Wait for the request to expire
+	 * 3. Lender attempts to fund the request (should fail)
+	 * 4. Lender cranks the expired request closed
+	 * 5. Verify NFT is returned to owner and loan account is closed
+	 */
+	it('should reject funding and close an expired loan request', async () => {
+		const requestExpiry = new anchor.BN(Math.floor(Date.now() / 1000) + 5);
+
+		await stakeNftForLoan(program, {
+			nftOwner,
+			nftMint,
+			ownerNftAccount,
+			vaultNftAccount,
+			vaultAuthority,
+			loanInfoPDA,
+			requestExpiry,
+		});
+
+		const waitTimeMs = 8000;
+		console.log(`Waiting ${waitTimeMs / 1000} seconds for loan request to expire...`);
+		await new Promise((resolve) => setTimeout(resolve, waitTimeMs));
+
+		console.log('\nAttempting to fund expired loan request (should fail)...');
+		try {
+			await provideLoanLiquidity(program, {
+				lender,
+				borrower: nftOwner.publicKey,
+				loanInfoPDA,
+			});
+			assert.fail('Should have thrown an error when funding an expired loan request');
+		} catch (error) {
+			assert.include(error.message, 'LoanRequestExpired', 'Should throw LoanRequestExpired error');
+			console.log('✅ Successfully prevented funding of expired loan request');
+		}
+
+		console.log('\n🧹 TEST: Closing expired loan request...');
+		await program.methods
+			.closeExpiredLoanRequest()
+			.accounts({
+				cranker: lender.publicKey,
+				loanInfo: loanInfoPDA,
+				loanRecord: await getLoanRecordPDA(program, nftMint, loanInfoPDA),
+				borrowerProfile: borrowerProfilePDA,
+				nftOwner: nftOwner.publicKey,
+				nftMint: nftMint,
+				ownerNftAccount: ownerNftAccount,
+				vaultNftAccount: vaultNftAccount,
+				vaultAuthority,
+				tokenProgram: TOKEN_PROGRAM_ID,
+				associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+				systemProgram: SystemProgram.programId,
+				programmable: NO_PROGRAMMABLE_ACCOUNTS,
+			})
+			.signers([lender])
+			.rpc();
+
+		const ownerAccount = await provider.connection.getTokenAccountBalance(ownerNftAccount);
+		assert.equal(ownerAccount.value.uiAmount, 1, 'NFT should be returned to owner');
+
+		try {
+			await program.account.loanInfo.fetch(loanInfoPDA);
+			assert.fail('Loan account should be closed');
+		} catch (error) {
+			assert.include(error.message, 'Account does not exist', 'Expected loan account to be closed');
+		}
+
+		console.log('✅ Expired loan request successfully closed');
+	});
+
 	/**
 	 * Test: Provide loan liquidity
 	 * Flow:
@@ -266,6 +453,28 @@ describe('NFT Loan Flow Tests', () => {
 			loanInfoPDA,
 		});
 
+		console.log('\nAttempting to fund with an unmet reputation gate (should fail)...');
+		try {
+			await provideLoanLiquidity(program, {
+				lender,
+				borrower: nftOwner.publicKey,
+				loanInfoPDA,
+				reputationGate: {
+					minLoansRepaidOnTime: new anchor.BN(1_000),
+					maxLoansDefaulted: new anchor.BN(0),
+					minRepaidVolume: new anchor.BN(0),
+				},
+			});
+			assert.fail('Should have thrown an error when the reputation gate is not met');
+		} catch (error) {
+			assert.include(
+				error.message,
+				'BorrowerReputationTooLow',
+				'Should throw BorrowerReputationTooLow error'
+			);
+			console.log('✅ Successfully enforced lender reputation gate');
+		}
+
 		await provideLoanLiquidity(program, {
 			lender,
 			borrower: nftOwner.publicKey,
@@ -299,15 +508,27 @@ describe('NFT Loan Flow Tests', () => {
 				.claimDelinquentNft()
 				.accounts({
 					loanInfo: loanInfoPDA,
+					loanRecord: await getLoanRecordPDA(program, nftMint, loanInfoPDA),
+					borrowerProfile: borrowerProfilePDA,
+					loanNoteMint: await getLoanNoteMintPDA(program, nftMint, loanInfoPDA),
+					lenderNoteAccount: await getAssociatedTokenAddress(
+						await getLoanNoteMintPDA(program, nftMint, loanInfoPDA),
+						lender.publicKey
+					),
 					nftMint: nftMint,
 					vaultNftAccount,
 					lenderNftAccount,
 					vaultAuthority,
 					lender: lender.publicKey,
 					tokenProgram: TOKEN_PROGRAM_ID,
+					noteTokenProgram: TOKEN_PROGRAM_ID,
 					associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
 					systemProgram: SystemProgram.programId,
 					rent: SYSVAR_RENT_PUBKEY,
+					borrower: nftOwner.publicKey,
+					vaultConfig: getVaultConfigPDA(program),
+					nftMetadata: getMetadataPDA(nftMint),
+					programmable: NO_PROGRAMMABLE_ACCOUNTS,
 				})
 				.signers([lender])
 				.rpc();
@@ -319,20 +540,31 @@ describe('NFT Loan Flow Tests', () => {
 		}
 
 		console.log('Executing repayLoan transaction...');
+		const loanRecordPDA = await getLoanRecordPDA(program, nftMint, loanInfoPDA);
 		await program.methods
 			.repayLoan()
 			.accounts({
 				loanInfo: loanInfoPDA,
+				loanRecord: loanRecordPDA,
+				borrowerProfile: borrowerProfilePDA,
+				loanNoteMint: await getLoanNoteMintPDA(program, nftMint, loanInfoPDA),
+				lenderNoteAccount: await getAssociatedTokenAddress(
+					await getLoanNoteMintPDA(program, nftMint, loanInfoPDA),
+					lender.publicKey
+				),
 				nftMint: nftMint,
 				vaultNftAccount,
 				borrowerNftAccount: ownerNftAccount,
 				vaultAuthority,
 				borrower: nftOwner.publicKey,
+				payer: nftOwner.publicKey,
 				lender: lender.publicKey,
 				tokenProgram: TOKEN_PROGRAM_ID,
+				noteTokenProgram: TOKEN_PROGRAM_ID,
 				associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
 				systemProgram: SystemProgram.programId,
 				rent: SYSVAR_RENT_PUBKEY,
+				programmable: NO_PROGRAMMABLE_ACCOUNTS,
 			})
 			.signers([nftOwner])
 			.rpc();
@@ -367,6 +599,18 @@ describe('NFT Loan Flow Tests', () => {
 		console.log(`NFT tokens in owner's account: ${ownerAccount.value.uiAmount}`);
 		assert.equal(ownerAccount.value.uiAmount, 1, 'NFT should be returned to owner');
 
+		// Verify the loan was archived with a repaid outcome
+		const loanRecord = await program.account.loanRecord.fetch(loanRecordPDA);
+		assert.deepEqual(loanRecord.outcome, { repaid: {} }, 'Loan record should be marked repaid');
+		assert.isTrue(loanRecord.lender.equals(lender.publicKey), 'Loan record should keep the lender');
+
+		// Verify the borrower's reputation reflects the on-time repayment
+		const borrowerProfile = await program.account.borrowerProfile.fetch(borrowerProfilePDA);
+		assert.isTrue(
+			borrowerProfile.loansRepaidOnTime.gtn(0),
+			'Borrower profile should count the repayment'
+		);
+
 		console.log('✅ Loan successfully repaid and NFT returned');
 	});
 
@@ -393,9 +637,10 @@ describe('NFT Loan Flow Tests', () => {
 
 		console.log('Executing stakeNftForLoan transaction...');
 		await program.methods
-			.stakeNftForLoan(loanAmount, interestAmount, duration)
+			.stakeNftForLoan(loanAmount, interestAmount, duration, null)
 			.accounts({
 				loanInfo: loanInfoPDA,
+				loanCounter: loanCounterPDA,
 				nftMint: nftMint,
 				ownerNftAccount: ownerNftAccount,
 				vaultNftAccount: vaultNftAccount,
@@ -405,22 +650,19 @@ describe('NFT Loan Flow Tests', () => {
 				tokenProgram: TOKEN_PROGRAM_ID,
 				associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
 				rent: SYSVAR_RENT_PUBKEY,
+				assetImpairment: getAssetImpairmentPDA(program, nftMint),
+				programmable: NO_PROGRAMMABLE_ACCOUNTS,
 			})
 			.signers([nftOwner])
 			.rpc();
 
 		// Provide liquidity
 		console.log('Executing provideLoanLiquidity transaction...');
-		await program.methods
-			.provideLoanLiquidity()
-			.accounts({
-				loanInfo: loanInfoPDA,
-				lender: lender.publicKey,
-				borrower: nftOwner.publicKey,
-				systemProgram: SystemProgram.programId,
-			})
-			.signers([lender])
-			.rpc();
+		await provideLoanLiquidity(program, {
+			lender,
+			borrower: nftOwner.publicKey,
+			loanInfoPDA,
+		});
 
 		// Wait for loan to expire
 		const waitTimeMs = 2000; // 2 seconds
@@ -442,15 +684,27 @@ describe('NFT Loan Flow Tests', () => {
 			.claimDelinquentNft()
 			.accounts({
 				loanInfo: loanInfoPDA,
+				loanRecord: await getLoanRecordPDA(program, nftMint, loanInfoPDA),
+				borrowerProfile: borrowerProfilePDA,
+				loanNoteMint: await getLoanNoteMintPDA(program, nftMint, loanInfoPDA),
+				lenderNoteAccount: await getAssociatedTokenAddress(
+					await getLoanNoteMintPDA(program, nftMint, loanInfoPDA),
+					lender.publicKey
+				),
 				nftMint: nftMint,
 				vaultNftAccount,
 				lenderNftAccount,
 				vaultAuthority,
 				lender: lender.publicKey,
 				tokenProgram: TOKEN_PROGRAM_ID,
+				noteTokenProgram: TOKEN_PROGRAM_ID,
 				associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
 				systemProgram: SystemProgram.programId,
 				rent: SYSVAR_RENT_PUBKEY,
+				borrower: nftOwner.publicKey,
+				vaultConfig: getVaultConfigPDA(program),
+				nftMetadata: getMetadataPDA(nftMint),
+				programmable: NO_PROGRAMMABLE_ACCOUNTS,
 			})
 			.signers([lender])
 			.rpc();
@@ -10,7 +10,7 @@ import {
 import { PublicKey, SystemProgram, SYSVAR_RENT_PUBKEY } from '@solana/web3.js';
 import { expect } from 'chai';
 import { PAYER_KEYPAIR, ALTERNATIVE_PAYER_KEYPAIR } from '../utils/testing-keypairs';
-import { SEED_PDA_CONSTANTS, TEST_RPC_CONNECTION } from './constants';
+import { NO_PROGRAMMABLE_ACCOUNTS, SEED_PDA_CONSTANTS, TEST_RPC_CONNECTION } from './constants';
 import { mintNft } from './mint_nft_util';
 
 describe('testing asset redemption', () => {
@@ -71,8 +71,19 @@ describe('testing asset redemption', () => {
 				tokenProgram: TOKEN_PROGRAM_ID,
 				associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
 				rent: SYSVAR_RENT_PUBKEY,
+				assetImpairment: PublicKey.findProgramAddressSync(
+					[Buffer.from(SEED_PDA_CONSTANTS.ASSET_IMPAIRMENT), nftMint.toBuffer()],
+					program.programId
+				)[0],
+				programmable: NO_PROGRAMMABLE_ACCOUNTS,
 			};
 
+			// Capture the typed event emitted for indexers
+			let requestedEvent: any = null;
+			const listener = program.addEventListener('redemptionRequested', (event) => {
+				requestedEvent = event;
+			});
+
 			console.log('Creating asset redemption request...');
 			const createAssetRedemptionRequestTx = await program.methods
 				.createAssetRedemptionRequest()
@@ -96,6 +107,11 @@ describe('testing asset redemption', () => {
 			expect(redemptionInfo_state1.requestTimestamp.toNumber()).to.be.greaterThan(0);
 			expect(redemptionInfo_state1.isFulfilled).to.be.false;
 
+			await program.removeEventListener(listener);
+			expect(requestedEvent).to.not.be.null;
+			expect(requestedEvent.nftMint.toString()).to.equal(nftMint.toString());
+			expect(requestedEvent.owner.toString()).to.equal(OTHER_KEYPAIR.publicKey.toString());
+
 			// STATE 2: Fulfill Asset Redemption Request - Fails because not admin.
 			console.log('Fulfilling asset redemption request (should fail)...');
 
@@ -158,6 +174,11 @@ describe('testing asset redemption', () => {
 				tokenProgram: TOKEN_PROGRAM_ID,
 				associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
 				rent: SYSVAR_RENT_PUBKEY,
+				assetImpairment: PublicKey.findProgramAddressSync(
+					[Buffer.from(SEED_PDA_CONSTANTS.ASSET_IMPAIRMENT), nftMint.toBuffer()],
+					program.programId
+				)[0],
+				programmable: NO_PROGRAMMABLE_ACCOUNTS,
 			};
 
 			try {
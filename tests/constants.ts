@@ -21,4 +21,22 @@ export const SEED_PDA_CONSTANTS = {
 	VAULT: 'vault',
 	ASSET_REDEMPTION_INFO: 'asset_redemption_info',
 	ASSET_REDEMPTION_VAULT: 'asset_redemption_vault',
-}
\ No newline at end of file
+	LOAN_COUNTER: 'loan_counter',
+	LOAN_RECORD: 'loan_record',
+	BORROWER_PROFILE: 'borrower_profile',
+	LOAN_NOTE: 'loan_note',
+	ASSET_IMPAIRMENT: 'asset_impairment',
+	VAULT_CONFIG: 'vault_config',
+};
+
+// Token Metadata accounts of a programmable NFT transfer, left out when moving a classic NFT
+export const NO_PROGRAMMABLE_ACCOUNTS = {
+	metadata: null,
+	masterEdition: null,
+	sourceTokenRecord: null,
+	destinationTokenRecord: null,
+	authorizationRules: null,
+	authorizationRulesProgram: null,
+	tokenMetadataProgram: null,
+	sysvarInstructions: null,
+};
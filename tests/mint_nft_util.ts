@@ -134,12 +134,14 @@ export async function mintNft(owner: PublicKey): Promise<PublicKey> {
 		collectionMetadata: collectionMetadataPDA,
 		collectionMasterEdition: collectionMasterEditionPDA,
 		collectionCounter: collectionCounterPDA,
+		tokenRecord: null,
+		sysvarInstructions: null,
 	};
 
 	// Verify the owner received the NFT
 	try {
 		const tx = await program.methods
-			.mintNft(TEST_NFT_INFO.productDetailUri)
+			.mintNft(TEST_NFT_INFO.productDetailUri, { nonFungible: {} })
 			.accounts(accounts)
 			.preInstructions([modifyComputeUnits])
 			.signers([ADMIN_KEYPAIR, mint])